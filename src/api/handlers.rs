@@ -2,23 +2,64 @@
 
 use alloy_primitives::{Address, U256};
 use axum::{
-    extract::{Json, State},
-    http::StatusCode,
+    extract::{Json, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
 };
-use std::sync::Arc;
+use dashmap::DashMap;
+use futures_util::Stream;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
 use tokio::sync::Semaphore;
 use tracing::{info, error, warn};
 
 use super::types::*;
-use crate::utils::cache::HoneypotCache;
-use crate::utils::constants::{is_solana_address, CHAIN_ID_SOLANA};
-use crate::providers::dexscreener::DexScreenerClient;
+use crate::utils::cache::{amount_bucket, HoneypotCache, JobCache, JobState};
+use crate::utils::chains::parse_chain;
+use crate::utils::alerts::{Alert, AlertDispatcher};
+use crate::utils::audit::AuditLog;
+use crate::utils::failure_log::{FailureLog, FailureRecord};
+use crate::utils::latency::UPSTREAM_LATENCY;
+use crate::utils::constants::{
+    chain_id_to_dexscreener_name, dexscreener_name_to_chain_id, eth_to_wei, get_chain_name, infer_chain_kind,
+    parse_native_amount, unix_timestamp, wei_to_eth, whole_eth_to_wei, ChainKind, CHAIN_ID_ETHEREUM,
+    CHAIN_ID_SOLANA, SUPPORTED_CHAIN_IDS,
+};
+use crate::providers::alchemy::AlchemyPricesClient;
+use crate::providers::dexscreener::{AutoDetectedToken, ChainCandidate, DexScreenerClient};
+use crate::providers::holders::{HolderCount, HolderCountCache, HolderCounter};
+use crate::providers::reserves::{is_reserve_price_mismatch, ReserveReader};
+use crate::providers::rpc::{
+    age_hours_since, ProxyUpgrade, RpcHealthStatus, RpcManager, RpcProvider, PROXY_UPGRADE_WINDOW_SECS,
+};
+use crate::providers::rpc_db::RpcDb;
 use crate::providers::solana::SolanaClient;
-use crate::core::honeypot::HoneypotDetector;
-use crate::core::risk_score::RiskScoreBuilder;
-use crate::core::ml_risk::{MLRiskScorer, MLFeatureSet, LiquidityFeatures, TradingFeatures, SocialFeatures};
-use crate::utils::telemetry::TelemetryCollector;
+use crate::providers::trace::TraceClient;
+use crate::providers::websocket::{DetectedPair, NewPairFeed, NewTokenDetector};
+use crate::core::honeypot::{
+    cap_risk_factors, select_pair_target, ExitPoint, HoneypotDetector, HoneypotResult, TokenInfo,
+    TransferProbe, MAX_TOKEN_DECIMALS,
+};
+use crate::core::pipeline::{AnalysisPipeline, Stage};
+use crate::core::sell_trace::SellTrace;
+use crate::models::config::{chain_test_amount, ChainConfig, SentryConfig};
+use crate::models::types::RiskLevel;
+use crate::core::risk_score::{RiskScoreBuilder, ScoreBlend};
+use crate::core::ml_risk::{
+    ContractFeatures, FeedbackLearner, LiquidityFeatures, MLFeatureSet, MLRiskScore, MLRiskScorer, RecordedPrediction,
+    Recommendation, RiskCategory, SocialFeatures, TradingFeatures,
+};
+use crate::utils::telemetry::{ReportData, TelemetryCollector};
+
+/// Permits on `AppState::batch_semaphore`: every running analysis (single checks, batch items,
+/// async jobs, all-chains fan-out, new-pair quick checks) holds one
+pub const MAX_CONCURRENT_ANALYSES: usize = 100;
 
 /// Shared application state
 pub struct AppState {
@@ -26,49 +67,193 @@ pub struct AppState {
     pub cache: Arc<HoneypotCache>,
     pub start_time: Instant,
     pub batch_semaphore: Arc<Semaphore>,
+    /// Analyses currently running (single checks, batch items), reported by `/health`
+    pub in_flight: Arc<AtomicUsize>,
+    /// Bounds DexScreener / reserve lookups so overlapped fetches don't flood DexScreener
+    pub market_data_semaphore: Arc<Semaphore>,
+    /// Results of `/analyze/async` jobs, keyed by job id
+    pub jobs: Arc<JobCache<HoneypotCheckData>>,
+    /// Recent failed analyses (served at `/debug/failures`)
+    pub failures: Arc<FailureLog>,
+    /// Per-analysis decision records (audit mode, off unless AUDIT_LOG_DIR is set)
+    pub audit: Arc<AuditLog>,
+    /// Honeypot / high-risk notifications (off unless an ALERT_* sink is configured)
+    pub alerts: Arc<AlertDispatcher>,
+    /// Contract creation (block, timestamp) keyed by (chain_id, token) - immutable, never expire
+    pub creation_times: Arc<DashMap<(u64, Address), (u64, u64)>>,
+    /// Holder counts keyed by (chain_id, token), short TTL
+    pub holder_counts: Arc<HolderCountCache>,
+    /// Shared ML scorer; weights move with `/feedback`
+    pub ml_scorer: Arc<RwLock<MLRiskScorer>>,
+    /// Accumulates feedback until enough reports agree to move a weight
+    pub feedback: Arc<Mutex<FeedbackLearner>>,
+    /// ML score + features per analyzed (chain_id, token), consumed by `/feedback`
+    pub ml_predictions: Arc<DashMap<(u64, String), RecordedPrediction>>,
+    pub config: SentryConfig,
+    /// Multi-chain RPC manager (warmed up at startup, optional)
+    pub rpc_manager: Option<Arc<RpcManager>>,
+    /// Recent PairCreated events per scanned chain (`NEW_PAIRS_CHAINS`)
+    pub new_pairs: HashMap<u64, Arc<NewPairFeed>>,
 }
 
 impl AppState {
     pub fn new(telemetry: Arc<TelemetryCollector>) -> Self {
-        let cache = Arc::new(HoneypotCache::new());
+        let config = SentryConfig::default();
+        // Honeypots cached long, safe verdicts revalidated sooner
+        let cache = Arc::new(HoneypotCache::with_ttls(
+            config.cache_safe_ttl_secs,
+            config.cache_honeypot_ttl_secs,
+        ));
         
-        // Background task: cleanup expired cache entries every 60 seconds
+        let mut ml_scorer = MLRiskScorer::new();
+        if let Some(path) = config.ml_weights_path.as_deref() {
+            let path = std::path::Path::new(path);
+            if path.exists() {
+                if let Err(e) = ml_scorer.load_weights(path) {
+                    warn!("⚠️ Could not load ML weights from {}: {}", path.display(), e);
+                }
+            }
+        }
+        let ml_predictions: Arc<DashMap<(u64, String), RecordedPrediction>> = Arc::new(DashMap::new());
+
+        let jobs = Arc::new(JobCache::default());
+        let failures = Arc::new(FailureLog::new(
+            config.failure_log_size,
+            config.failure_log_path.as_ref().map(std::path::PathBuf::from),
+        ));
+
+        // Background task: cleanup expired cache entries (and sample the hit rate) every 60 seconds
         let cache_clone = cache.clone();
+        let jobs_clone = jobs.clone();
+        let predictions_clone = ml_predictions.clone();
+        let holder_counts = Arc::new(HolderCountCache::default());
+        let holder_counts_clone = holder_counts.clone();
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(CACHE_MAINTENANCE_INTERVAL_SECS));
             loop {
                 interval.tick().await;
                 let removed = cache_clone.cleanup_expired();
                 if removed > 0 {
                     tracing::info!("🧹 Cache cleanup: {} expired entries removed", removed);
                 }
+                cache_clone.record_sample();
+                let removed_jobs = jobs_clone.cleanup_expired();
+                if removed_jobs > 0 {
+                    tracing::info!("🧹 Job cleanup: {} expired job results removed", removed_jobs);
+                }
+                let cutoff = unix_timestamp().saturating_sub(FEEDBACK_WINDOW_SECS);
+                predictions_clone.retain(|_, p| p.recorded_at >= cutoff);
+                holder_counts_clone.cleanup_expired();
             }
         });
 
+        // Live PairCreated scanners: one WebSocket subscription per configured chain
+        let new_pairs: HashMap<u64, Arc<NewPairFeed>> = config
+            .new_pairs_chains
+            .iter()
+            .map(|&chain_id| (chain_id, Arc::new(NewPairFeed::new(config.new_pairs_buffer))))
+            .collect();
+        for (&chain_id, feed) in &new_pairs {
+            let feed = feed.clone();
+            tokio::spawn(async move {
+                let started = match NewTokenDetector::new(chain_id) {
+                    Ok(detector) => detector.start_feed(feed).await,
+                    Err(e) => Err(e),
+                };
+                match started {
+                    Ok(()) => info!("🆕 New-pair scanner running on chain {}", chain_id),
+                    Err(e) => warn!("⚠️ New-pair scanner for chain {} not started: {}", chain_id, e),
+                }
+            });
+        }
+
         Self {
             telemetry,
             cache,
             start_time: Instant::now(),
-            batch_semaphore: Arc::new(Semaphore::new(100)),
+            batch_semaphore: Arc::new(Semaphore::new(MAX_CONCURRENT_ANALYSES)),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            market_data_semaphore: Arc::new(Semaphore::new(config.market_data_concurrency)),
+            jobs,
+            failures,
+            audit: Arc::new(AuditLog::new(config.audit_log_dir.as_ref().map(std::path::PathBuf::from))),
+            alerts: Arc::new(AlertDispatcher::from_sentry_config(&config)),
+            creation_times: Arc::new(DashMap::new()),
+            holder_counts,
+            ml_scorer: Arc::new(RwLock::new(ml_scorer)),
+            feedback: Arc::new(Mutex::new(FeedbackLearner::new())),
+            ml_predictions,
+            config,
+            rpc_manager: None,
+            new_pairs,
         }
     }
 
+    /// Attach a warmed-up RPC manager (enables per-chain health reporting)
+    pub fn with_rpc_manager(mut self, rpc_manager: Arc<RpcManager>) -> Self {
+        self.rpc_manager = Some(rpc_manager);
+        self
+    }
+
     pub fn uptime_seconds(&self) -> u64 {
         self.start_time.elapsed().as_secs()
     }
+
+    /// Running analyses; permits held on the shared semaphore count even before their analysis starts
+    pub fn in_flight(&self) -> usize {
+        let permits_held = MAX_CONCURRENT_ANALYSES.saturating_sub(self.batch_semaphore.available_permits());
+        self.in_flight.load(Ordering::Relaxed).max(permits_held)
+    }
+}
+
+/// Counts one running analysis in `AppState::in_flight` until dropped (also on early return / panic)
+struct InFlightGuard(Arc<AtomicUsize>);
+
+impl InFlightGuard {
+    fn enter(counter: &Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter.clone())
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 // ============================================
 // Health Check
 // ============================================
 
+#[utoipa::path(
+    get,
+    path = "/v1/health",
+    tag = "status",
+    responses((status = 200, description = "Service and per-chain RPC health", body = HealthResponse))
+)]
 pub async fn health_check(State(state): State<Arc<AppState>>) -> Json<ApiResponse<HealthData>> {
     let start = Instant::now();
 
+    // Per-chain RPC health from startup probes (if a manager is attached)
+    let chains = state.rpc_manager.as_ref().map(|manager| {
+        let mut chains: Vec<_> = manager.health().values().cloned().collect();
+        chains.sort_by_key(|c| c.chain_id);
+        chains
+    });
+    let any_down = chains
+        .as_ref()
+        .map(|c| c.iter().any(|h| h.status == RpcHealthStatus::Down))
+        .unwrap_or(false);
+
     let data = HealthData {
-        status: "healthy".to_string(),
+        status: if any_down { "degraded" } else { "healthy" }.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
         uptime_seconds: state.uptime_seconds(),
+        in_flight: state.in_flight(),
+        max_concurrency: MAX_CONCURRENT_ANALYSES,
+        chains,
     };
 
     Json(ApiResponse::success(
@@ -81,11 +266,32 @@ pub async fn health_check(State(state): State<Arc<AppState>>) -> Json<ApiRespons
 // Token Analysis
 // ============================================
 
+#[utoipa::path(
+    post,
+    path = "/v1/analyze/token",
+    tag = "analysis",
+    request_body = TokenAnalysisRequest,
+    responses(
+        (status = 200, description = "PERS risk score", body = TokenAnalysisResponse),
+        (status = 400, description = "Invalid input (ApiResponse with `error`)"),
+        (status = 500, description = "Analysis failed (ApiResponse with `error`)")
+    )
+)]
 pub async fn analyze_token(
     State(state): State<Arc<AppState>>,
     Json(req): Json<TokenAnalysisRequest>,
 ) -> Result<Json<ApiResponse<TokenAnalysisData>>, (StatusCode, Json<ApiResponse<()>>)> {
     let start = Instant::now();
+    let _permit = state.batch_semaphore.acquire().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ApiError::internal(format!("Worker pool closed: {}", e)),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        )
+    })?;
+    let _in_flight = InFlightGuard::enter(&state.in_flight);
 
     // Validate address
     let token: Address = req.token_address.parse().map_err(|_| {
@@ -98,12 +304,20 @@ pub async fn analyze_token(
         )
     })?;
 
-    // Parse test amount
-    let test_amount: f64 = req.test_amount_eth.parse().unwrap_or(0.1);
-    let test_wei = U256::from((test_amount * 1e18) as u128);
+    // Parse test amount (chain default when omitted)
+    let requested_amount = parse_test_amount(req.test_amount_eth.as_deref()).map_err(|message| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(message),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        )
+    })?;
+    let test_wei = test_amount_wei(requested_amount, req.chain_id);
 
-    // Run honeypot detection (async with RPC)
-    let detector = HoneypotDetector::mainnet();
+    // Run honeypot detection (async with RPC) on the requested chain
+    let detector = HoneypotDetector::for_chain(req.chain_id).unwrap_or_else(HoneypotDetector::mainnet);
     let hp_result = detector.detect_async(token, test_wei).await;
 
     // Build risk score
@@ -137,14 +351,16 @@ pub async fn analyze_token(
         use crate::utils::telemetry::{TelemetryEvent, ThreatType};
         let event = TelemetryEvent::new(
             ThreatType::Honeypot,
-            U256::from((test_amount * 1e18) as u128),
+            test_wei,
             latency,
             risk_score.total,
             format!("Token analysis: score {}", risk_score.total),
-        );
+        )
+        .on_chain(req.chain_id)
+        .for_token(token);
         state.telemetry.record_threat(event);
     } else {
-        state.telemetry.record_analysis(latency);
+        state.telemetry.record_analysis(req.chain_id, latency);
     }
 
     let data = TokenAnalysisData {
@@ -163,18 +379,171 @@ pub async fn analyze_token(
 // Honeypot Check
 // ============================================
 
+#[utoipa::path(
+    post,
+    path = "/v1/honeypot/check",
+    tag = "analysis",
+    params(HoneypotTraceQuery),
+    request_body = HoneypotCheckRequest,
+    responses(
+        (status = 200, description = "Honeypot verdict (`MinimalCheckResponse` with `?fields=minimal`)", body = HoneypotCheckResponse),
+        (status = 400, description = "Invalid input (ApiResponse with `error`)"),
+        (status = 500, description = "Analysis failed (ApiResponse with `error`)")
+    )
+)]
 pub async fn check_honeypot(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<HoneypotTraceQuery>,
+    Json(req): Json<HoneypotCheckRequest>,
+) -> Result<Json<ApiResponse<HoneypotCheckData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    run_honeypot_check(state, query, req, None).await
+}
+
+/// Sink of `/honeypot/check/stream`: each finished phase becomes an SSE `progress` event
+#[derive(Clone)]
+struct CheckProgress {
+    events: tokio::sync::mpsc::UnboundedSender<Event>,
+    start: Instant,
+}
+
+impl CheckProgress {
+    /// Dropped silently once the client has disconnected
+    fn emit(&self, phase: &str, message: &str) {
+        let event = ProgressEvent {
+            phase: phase.to_string(),
+            message: message.to_string(),
+            elapsed_ms: self.start.elapsed().as_secs_f64() * 1000.0,
+        };
+        if let Ok(event) = Event::default().event("progress").json_data(&event) {
+            let _ = self.events.send(event);
+        }
+    }
+}
+
+fn report_progress(progress: Option<&CheckProgress>, phase: &str, message: &str) {
+    if let Some(progress) = progress {
+        progress.emit(phase, message);
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/honeypot/check/stream",
+    tag = "analysis",
+    params(HoneypotTraceQuery),
+    request_body = HoneypotCheckRequest,
+    responses(
+        (status = 200, description = "Server-sent events: `progress` (ProgressEvent) per finished phase, then one `result` (HoneypotCheckResponse) or `error` (ApiResponse with `error`)", content_type = "text/event-stream", body = ProgressEvent)
+    )
+)]
+pub async fn check_honeypot_stream(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<HoneypotTraceQuery>,
     Json(req): Json<HoneypotCheckRequest>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let (events, rx) = tokio::sync::mpsc::unbounded_channel();
+    let progress = CheckProgress {
+        events: events.clone(),
+        start: Instant::now(),
+    };
+
+    // Same deadline as the non-streaming check: the TimeoutLayer only covers the headers here
+    let request_timeout = state.config.request_timeout;
+    tokio::spawn(async move {
+        let check = tokio::time::timeout(request_timeout, run_honeypot_check(state, query, req, Some(progress)));
+        let last = tokio::select! {
+            // Client disconnected: drop the analysis instead of finishing it for nobody
+            _ = events.closed() => return,
+            outcome = check => match outcome {
+                Ok(Ok(Json(response))) => Event::default().event("result").json_data(&response),
+                Ok(Err((_, Json(response)))) => Event::default().event("error").json_data(&response),
+                Err(_) => {
+                    warn!("⏱️ Streamed check exceeded {}s deadline", request_timeout.as_secs());
+                    let response = ApiResponse::<()>::error(
+                        ApiError::gateway_timeout(request_timeout.as_secs()),
+                        request_timeout.as_secs_f64() * 1000.0,
+                    );
+                    Event::default().event("error").json_data(&response)
+                }
+            },
+        };
+        if let Ok(last) = last {
+            let _ = events.send(last);
+        }
+    });
+
+    let stream = futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|event| (Ok(event), rx))
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Body of `/honeypot/check`, reporting each finished phase to `progress` when streaming
+async fn run_honeypot_check(
+    state: Arc<AppState>,
+    mut query: HoneypotTraceQuery,
+    mut req: HoneypotCheckRequest,
+    progress: Option<CheckProgress>,
 ) -> Result<Json<ApiResponse<HoneypotCheckData>>, (StatusCode, Json<ApiResponse<()>>)> {
     let start = Instant::now();
+    let _permit = state.batch_semaphore.acquire().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ApiError::internal(format!("Worker pool closed: {}", e)),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        )
+    })?;
+    let _in_flight = InFlightGuard::enter(&state.in_flight);
+    info!("⚙️ Analysis profile: {:?} (target < {}ms)", req.profile, req.profile.latency_target_ms());
+
+    // `?fields=minimal` only returns the verdict - skip the I/O behind every other field
+    let minimal = query.fields == ResponseFields::Minimal;
+    if minimal {
+        req.include_market_data = false;
+        query.trace = false;
+    }
+
+    // `"chain": "base"` resolves to its chain id once, up front
+    req.chain_id = req.resolved_chain_id().map_err(|message| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(message),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        )
+    })?;
 
     // ============================================
-    // SOLANA DETECTION - Check if address is Solana format
+    // SOLANA DETECTION - Address format must agree with chain_id
     // ============================================
-    if is_solana_address(&req.token_address) || req.chain_id == CHAIN_ID_SOLANA {
-        info!("🌐 Detected Solana token: {}", req.token_address);
-        return handle_solana_token(&state, &req, start).await;
+    let wants_solana = req.chain_id == CHAIN_ID_SOLANA;
+    let mismatch = match infer_chain_kind(&req.token_address) {
+        ChainKind::Solana if wants_solana || req.chain_id == 0 => {
+            info!("🌐 Detected Solana token: {}", req.token_address);
+            return handle_solana_token(&state, &req, start).await;
+        }
+        ChainKind::Solana => Some(format!(
+            "Solana address given with EVM chain_id {}", req.chain_id
+        )),
+        ChainKind::Evm if wants_solana => Some(format!(
+            "EVM address given with Solana chain_id {}", CHAIN_ID_SOLANA
+        )),
+        ChainKind::Evm => None,
+        ChainKind::Unknown => Some(
+            "Invalid token address format (expected 0x EVM address or base58 Solana pubkey)".to_string(),
+        ),
+    };
+    if let Some(message) = mismatch {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(message),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
     }
 
     // Validate EVM address
@@ -188,6 +557,51 @@ pub async fn check_honeypot(
         )
     })?;
 
+    let requested_amount = parse_test_amount(req.test_amount_eth.as_deref()).map_err(|message| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(message),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        )
+    })?;
+
+    // ============================================
+    // CANONICAL BYPASS: WETH/stablecoins never need a simulation
+    // (and WETH has no WETH/WETH pair to simulate against)
+    // ============================================
+    if let Some(canonical_chain) = ChainConfig::canonical_chain(&token, req.chain_id) {
+        info!("🛡️ Canonical token {} on chain {} - skipping analysis", req.token_address, canonical_chain);
+        state.telemetry.record_analysis(canonical_chain, 0);
+        let data = HoneypotCheckData {
+            is_honeypot: Some(false),
+            verdict: Verdict::Safe,
+            confidence: 100,
+            buy_success: true,
+            sell_success: true,
+            reason: "✅ Canonical token (wrapped native / major stablecoin) - no simulation needed".to_string(),
+            source: Some("canonical".to_string()),
+            ..HoneypotCheckData::new(req.token_address, canonical_chain, req.profile)
+        };
+
+        return Ok(Json(ApiResponse::success(
+            data,
+            start.elapsed().as_secs_f64() * 1000.0,
+        )));
+    }
+
+    // Routers / factories submitted as the token: the sim would only produce a confusing verdict
+    if let Some((chain_id, label)) = ChainConfig::infrastructure_chain(&token, req.chain_id) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(infrastructure_message(&req.token_address, &label, chain_id)),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    }
+
     // ============================================
     // AUTO-DETECT CHAIN & DEX via DexScreener
     // This finds the actual DEX with liquidity
@@ -213,9 +627,14 @@ pub async fn check_honeypot(
             }
             Err(e) => {
                 warn!("⚠️ DexScreener failed: {}. Token may not be listed.", e);
-                (1, None, false) // Default to Ethereum, no DEX info
+                // Not indexed yet: first chain whose factories know a pair, else Ethereum
+                let chain_id = discover_pair_chain(token).await.unwrap_or(CHAIN_ID_ETHEREUM);
+                (chain_id, None, false)
             }
         }
+    } else if !req.include_market_data {
+        // Pure sim verdict: no DexScreener round trip, chain's default routers
+        (req.chain_id, None, false)
     } else {
         // Chain specified, but still try to get DEX info from DexScreener
         info!("🔍 Looking up DEX info for {} on chain {}...", req.token_address, req.chain_id);
@@ -244,8 +663,11 @@ pub async fn check_honeypot(
                     total_pairs: pairs.len(),
                     // Market data
                     price_usd: best.price_usd.clone(),
+                    price_native: best.price_native.clone(),
+                    base_token_address: Some(best.base_token.address.clone()),
                     volume_24h_usd: best.volume.as_ref().and_then(|v| v.h24),
                     pair_address: Some(best.pair_address.clone()),
+                    chain_candidates: vec![],
                 }), v3_only)
             }
             _ => {
@@ -288,20 +710,10 @@ pub async fn check_honeypot(
         };
         
         let data = HoneypotCheckData {
-            token_address: req.token_address,
             token_name: auto_detected_name,
             token_symbol: auto_detected_symbol,
-            token_decimals: None,
-            chain_id: effective_chain_id,
-            chain_name: chain_name.clone(),
-            native_symbol: "ETH".to_string(),
-            is_honeypot: false,
+            chain_name,
             risk_score: 70, // HIGH risk - cannot verify
-            buy_success: false,
-            sell_success: false,
-            buy_tax_percent: 0.0,
-            sell_tax_percent: 0.0,
-            total_loss_percent: 0.0,
             reason: format!("Token only available on {} (V3/Velodrome-style) - not supported yet. Use DEX directly.", dex_name),
             simulation_latency_ms: start.elapsed().as_millis() as u64,
             // DexScreener market data
@@ -310,6 +722,7 @@ pub async fn check_honeypot(
             volume_24h_usd,
             dex_name: Some(dex_name),
             pair_address,
+            ..HoneypotCheckData::new(req.token_address, effective_chain_id, req.profile)
         };
 
         return Ok(Json(ApiResponse::success(
@@ -318,6 +731,10 @@ pub async fn check_honeypot(
         )));
     }
 
+    if detected_info.is_some() {
+        report_progress(progress.as_ref(), "market_data", "fetched market data");
+    }
+
     // Get detector for detected/specified chain
     let detector = HoneypotDetector::for_chain(effective_chain_id).ok_or_else(|| {
         (
@@ -330,7 +747,43 @@ pub async fn check_honeypot(
                 start.elapsed().as_secs_f64() * 1000.0,
             )),
         )
-    })?;
+    })?
+    .with_max_bytecode_size(state.config.max_bytecode_size)
+    .with_test_balance(whole_eth_to_wei(state.config.test_account_balance_eth))
+    .with_sell_lock_max_blocks(state.config.sell_lock_max_blocks)
+    .with_verdict_confirmation(query.confirm)
+    .with_pipeline(req.profile.pipeline());
+
+    // Caller-supplied decimals for non-standard ERC20s
+    let detector = match req.decimals {
+        Some(decimals) if decimals > MAX_TOKEN_DECIMALS => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ApiError::bad_request(format!("decimals must be 0-{}", MAX_TOKEN_DECIMALS)),
+                    start.elapsed().as_secs_f64() * 1000.0,
+                )),
+            ));
+        }
+        Some(decimals) => detector.with_decimals_override(decimals),
+        None => detector,
+    };
+
+    // Historical analysis (`?block=`): every RPC read and the sim run at that block
+    let detector = match query.block {
+        Some(block) => {
+            info!("🕰️ Analyzing historical state at block {}", block);
+            detector.at_historical_block(block)
+        }
+        None => detector,
+    };
+    let detector = match &progress {
+        Some(progress) => {
+            let progress = progress.clone();
+            detector.with_progress(Arc::new(move |stage: Stage| progress.emit(stage.as_str(), stage.done_message())))
+        }
+        None => detector,
+    };
 
     let chain_id = detector.chain_id;
     let chain_name = detector.chain_name.clone();
@@ -338,41 +791,133 @@ pub async fn check_honeypot(
 
     info!("🔗 Chain: {} ({}) - {}", chain_name, chain_id, native_symbol);
 
-    // Cache key includes chain_id for multi-chain support
-    let cache_key = format!("{}:{}", chain_id, req.token_address.to_lowercase());
+    // ============================================
+    // MIN-LIQUIDITY GATE (opt-in, per-chain)
+    // Pools too thin to trade are not worth an EVM sim
+    // ============================================
+    let min_liquidity = ChainConfig::get(chain_id).and_then(|c| c.min_liquidity_usd);
+    // DexScreener liquidity is current-only, so the gate is skipped for historical blocks
+    let detected_liquidity = detected_info.as_ref()
+        .filter(|_| query.block.is_none())
+        .map(|i| i.best_dex.liquidity_usd);
+    if let (Some(min_usd), Some(liquidity_usd)) = (min_liquidity, detected_liquidity) {
+        if liquidity_usd < min_usd {
+            info!("💧 Liquidity ${:.2} below ${:.2} minimum - skipping simulation", liquidity_usd, min_usd);
+            let hp_result = HoneypotResult::too_illiquid(
+                liquidity_usd,
+                min_usd,
+                start.elapsed().as_millis() as u64,
+            );
+            state.telemetry.record_analysis(chain_id, hp_result.latency_ms);
+
+            let info = detected_info.as_ref();
+            let data = HoneypotCheckData {
+                token_name: auto_detected_name,
+                token_symbol: auto_detected_symbol,
+                chain_name,
+                native_symbol,
+                risk_score: calculate_risk_score(&hp_result),
+                simulation_latency_ms: 0, // No simulation run
+                // DexScreener market data
+                price_usd: info.and_then(|i| i.price_usd.clone()),
+                liquidity_usd: Some(liquidity_usd),
+                volume_24h_usd: info.and_then(|i| i.volume_24h_usd),
+                dex_name: info.map(|i| i.best_dex.dex_name.clone()),
+                pair_address: info.and_then(|i| i.pair_address.clone()),
+                ..HoneypotCheckData::from_result(
+                    &hp_result,
+                    req.token_address,
+                    chain_id,
+                    req.profile,
+                    state.config.min_confidence,
+                    state.config.max_risk_factors,
+                )
+            };
+
+            return Ok(Json(ApiResponse::success(
+                data,
+                start.elapsed().as_secs_f64() * 1000.0,
+            )));
+        }
+    }
+
+    let test_wei = test_amount_wei(requested_amount, chain_id);
+    let test_amount = wei_to_eth(test_wei);
+
+    let cache_key = honeypot_cache_key(chain_id, &req.token_address, test_amount, query.block, req.decimals);
 
     // ============================================
     // CACHE-FIRST: Check cache before RPC call
+    // `?confirm=true` re-runs the sell, so it always simulates - a cached verdict was never confirmed
     // ============================================
-    if let Some(cached_result) = state.cache.get(&cache_key) {
+    let cached = if query.confirm { None } else { state.cache.get(&cache_key) };
+    if let Some(cached_result) = cached {
         info!("⚡ Returning cached result for {} on {}", req.token_address, chain_name);
+        report_progress(progress.as_ref(), "cache", "cached simulation found");
         
-        // ALWAYS fetch token name/symbol from RPC (instant, no DexScreener delay)
-        let token_info = detector.fetch_token_info(token).await;
-        let (token_name, token_symbol, token_decimals) = (token_info.name, token_info.symbol, token_info.decimals);
+        let runs_ml = req.profile.runs_ml() || query.recommendation;
+
+        // Token name/symbol from RPC, market data from DexScreener, token age and proxy history, concurrently
+        let ((token_name, token_symbol, token_decimals), (price_usd, liquidity_usd, volume_24h_usd, dex_name, pair_address), (age_hours, holders), proxy) = tokio::join!(
+            fetch_token_info_for_profile(&detector, token, req.profile, !minimal),
+            fetch_market_data_bounded(&state, &req.token_address, chain_id, req.fetches_market_data()),
+            fetch_age_and_holders(&state, chain_id, token, runs_ml),
+            fetch_proxy_upgrade(&state, chain_id, token, query.block, req.profile.fetches_token_info() && !minimal),
+        );
 
-        // Market data from DexScreener (optional, with timeout)
-        let (price_usd, liquidity_usd, volume_24h_usd, dex_name, pair_address) = 
-            fetch_market_data_optional(&req.token_address, chain_id).await;
-        
-        // Calculate risk score from cached result
-        let risk_score = calculate_risk_score(&cached_result);
+        // Calculate risk score from cached result, reconciled with ML (thorough profile)
+        let ml_scores = runs_ml.then(|| evm_ml_scores(&state, chain_id, &req.token_address, &cached_result, liquidity_usd, volume_24h_usd, age_hours, holders, proxy.as_ref()));
+        let rug_risk = ml_scores.map(|(_, rug_risk)| rug_risk);
+        let score_blend = blend_scores(
+            calculate_risk_score(&cached_result),
+            &cached_result,
+            ml_scores.map(|(ml_score, _)| ml_score),
+        );
+        let risk_score = score_blend.score;
+        let ensemble = score_blend.ensemble();
+        let (recommendation, category) = ml_recommendation(runs_ml, risk_score);
+        report_progress(progress.as_ref(), "scoring", "scoring complete");
+
+        // Trace is never cached - run it fresh if requested
+        let sell_trace = if query.trace || (req.profile.runs_sell_trace() && !minimal) {
+            let sell_trace = trace_sell_optional(&detector, token, test_wei, pair_address.as_deref()).await;
+            report_progress(progress.as_ref(), "trace", "trace complete");
+            sell_trace
+        } else {
+            None
+        };
+        let exit_curve = if query.exit_curve && !minimal {
+            let deadline = quick_check_deadline(&state, start);
+            let exit_curve = exit_curve_optional(&state, &detector, token, pair_address.as_deref(), query.block, deadline).await;
+            report_progress(progress.as_ref(), "exit curve", "exit curve simulated");
+            exit_curve
+        } else {
+            None
+        };
+        // No pair was simulated: can the token at least move between wallets?
+        let transfer_probe = if cached_result.no_pair && !minimal {
+            let deadline = quick_check_deadline(&state, start);
+            transfer_probe_optional(&state, chain_id, token, token_decimals, query.block, deadline).await
+        } else {
+            None
+        };
         
-        let data = HoneypotCheckData {
-            token_address: req.token_address,
+        let audit = state.audit.is_enabled().then(|| {
+            let inputs = check_inputs(&req, &query, chain_id, test_amount, discovered_router.clone());
+            (inputs, SimulationRecord::from(&cached_result))
+        });
+
+        let mut data = HoneypotCheckData {
             token_name,
             token_symbol,
             token_decimals,
-            chain_id,
             chain_name,
             native_symbol,
-            is_honeypot: cached_result.is_honeypot || cached_result.sell_reverted,
             risk_score,
-            buy_success: cached_result.buy_success,
-            sell_success: cached_result.sell_success,
-            buy_tax_percent: cached_result.buy_tax_percent,
-            sell_tax_percent: cached_result.sell_tax_percent,
-            total_loss_percent: cached_result.total_loss_percent,
+            risk_factors: with_proxy_risk(
+                cached_result.capped_risk_factors(state.config.max_risk_factors),
+                proxy.as_ref(),
+            ),
             reason: format!("{} (cached)", cached_result.reason),
             simulation_latency_ms: 0, // Instant from cache
             // DexScreener market data
@@ -381,7 +926,38 @@ pub async fn check_honeypot(
             volume_24h_usd,
             dex_name,
             pair_address,
+            holder_count: holders.map(|h| h.holders),
+            holder_count_truncated: holders.map(|h| h.truncated),
+            proxy_implementation: proxy.as_ref().map(|p| p.implementation.clone()),
+            recently_upgraded: proxy.as_ref().is_some_and(|p| p.recently_upgraded),
+            rug_risk,
+            ensemble,
+            score_blend: Some(score_blend),
+            recommendation,
+            category,
+            sell_trace,
+            exit_curve,
+            transferable: transfer_probe.as_ref().map(|p| p.transferable),
+            transfer_revert_reason: transfer_probe.and_then(|p| p.revert_reason),
+            sim_detail: cached_result.sim_detail.as_ref().filter(|_| query.detail).map(SimDetailResponse::from),
+            source: Some("cache".to_string()),
+            ..HoneypotCheckData::from_result(
+                &cached_result,
+                req.token_address,
+                chain_id,
+                req.profile,
+                state.config.min_confidence,
+                state.config.max_risk_factors,
+            )
         };
+        // Ambiguous auto-detect: the other chains' verdicts, so the caller can pick
+        let detected = detected_info.as_ref().filter(|_| !minimal && query.block.is_none());
+        data.candidates = candidate_verdicts(&state, detected, token, &data, requested_amount, start).await;
+
+        if let Some((inputs, simulation)) = audit {
+            let ml = ml_record(&state, &data, ml_scores, age_hours, holders);
+            audit_check(&state, inputs, simulation, ml, &data, true);
+        }
 
         return Ok(Json(ApiResponse::success(
             data,
@@ -392,14 +968,18 @@ pub async fn check_honeypot(
     // ============================================
     // CACHE MISS: Perform RPC simulation
     // ============================================
-    let test_amount: f64 = req.test_amount_eth.parse().unwrap_or(0.1);
-    let test_wei = U256::from((test_amount * 1e18) as u128);
-
     info!("🔍 CACHE MISS - Starting RPC simulation for: {} on {}", req.token_address, chain_name);
     info!("   Test amount: {} {}", test_amount, native_symbol);
     
-    // If DexScreener found a router, add it as priority
-    let detector = if let Some(router_addr) = discovered_router {
+    // DexScreener was asked and knows no pair (fresh launch): ask the factories directly
+    let onchain_pair = if detected_info.is_none() && (req.chain_id == 0 || req.include_market_data) {
+        detector.discover_pair(token).await
+    } else {
+        None
+    };
+
+    // If DexScreener (or the on-chain lookup) found a router, add it as priority
+    let detector = if let Some(router_addr) = &discovered_router {
         if let Ok(router) = router_addr.parse::<alloy_primitives::Address>() {
             let dex_name = detected_info.as_ref()
                 .map(|i| i.best_dex.dex_name.clone())
@@ -409,16 +989,47 @@ pub async fn check_honeypot(
         } else {
             detector
         }
+    } else if let Some((dex, pair)) = &onchain_pair {
+        info!("⛓️ On-chain pair {:?} via {} factory", pair, dex.name);
+        detector.with_priority_router(dex.name.clone(), dex.address)
     } else {
         detector
     };
     
-    let result = detector.detect_async(token, test_wei).await;
+    let runs_ml = req.profile.runs_ml() || query.recommendation;
+
+    // Simulation, token info, market data, token age, holders and proxy history are independent I/O -
+    // overlap them (DexScreener + reserve lookups bounded by the market data sub-semaphore)
+    let (
+        result,
+        (token_name, token_symbol, token_decimals),
+        ((price_usd, liquidity_usd, volume_24h_usd, dex_name, pair_address), reserve_price_mismatch),
+        (age_hours, holders),
+        proxy,
+    ) = tokio::join!(
+        detector.detect_async(token, test_wei),
+        fetch_token_info_for_profile(&detector, token, req.profile, !minimal),
+        fetch_market_enrichment(
+            &state,
+            &req.token_address,
+            chain_id,
+            token,
+            req.fetches_market_data(),
+            query.block,
+            detected_info.as_ref(),
+        ),
+        fetch_age_and_holders(&state, chain_id, token, runs_ml),
+        fetch_proxy_upgrade(&state, chain_id, token, query.block, req.profile.fetches_token_info() && !minimal),
+    );
+    let (dex_name, pair_address) = match (&onchain_pair, pair_address) {
+        (Some((dex, pair)), None) => (dex_name.or_else(|| Some(dex.name.clone())), Some(format!("{:?}", pair))),
+        (_, pair_address) => (dex_name, pair_address),
+    };
 
     match &result {
         Ok(data) => {
             info!("✅ Simulation successful for {} on {}", req.token_address, chain_name);
-            info!("   is_honeypot: {}, buy_success: {}, sell_success: {}", 
+            info!("   is_honeypot: {:?}, buy_success: {}, sell_success: {}", 
                   data.is_honeypot, data.buy_success, data.sell_success);
             info!("   buy_tax: {:.2}%, sell_tax: {:.2}%, total_loss: {:.2}%",
                   data.buy_tax_percent, data.sell_tax_percent, data.total_loss_percent);
@@ -426,6 +1037,21 @@ pub async fn check_honeypot(
         Err(e) => {
             error!("❌ SIMULATION FAILED for {}: {:?}", req.token_address, e);
             // DO NOT cache failed results per CEO directive
+            // ...but keep a record so the operator can replay it
+            state.failures.record(FailureRecord::new(
+                req.token_address.clone(),
+                chain_id,
+                format!("{:?}", e),
+                serde_json::json!({
+                    "test_amount_eth": test_amount.to_string(),
+                    "test_amount_wei": test_wei.to_string(),
+                    "requested_chain_id": req.chain_id,
+                    "profile": req.profile,
+                    "decimals": req.decimals,
+                    "block": query.block,
+                    "dexscreener_router": discovered_router,
+                }),
+            ));
         }
     }
 
@@ -433,20 +1059,53 @@ pub async fn check_honeypot(
         Ok(hp_result) => {
             // ============================================
             // CACHE SET: Store valid result (with chain_id in key)
+            // Fast runs skip the bytecode scan - never serve them to fuller profiles;
+            // confirmed runs may be downgraded by the rerun - never serve them to plain requests
             // ============================================
-            state.cache.set(&cache_key, hp_result.clone());
+            if req.profile.pipeline() == AnalysisPipeline::full() && !query.confirm {
+                state.cache.set(&cache_key, hp_result.clone());
+            }
 
-            // ALWAYS fetch token name/symbol from RPC (instant, no DexScreener delay)
-            let token_info = detector.fetch_token_info(token).await;
-            let (token_name, token_symbol, token_decimals) = (token_info.name, token_info.symbol, token_info.decimals);
             info!("📛 Token info from RPC: {:?} ({:?})", token_name, token_symbol);
 
-            // Market data from DexScreener (optional, with timeout)
-            let (price_usd, liquidity_usd, volume_24h_usd, dex_name, pair_address) = 
-                fetch_market_data_optional(&req.token_address, chain_id).await;
-
-            // Calculate risk score based on actual simulation results
-            let risk_score = calculate_risk_score(&hp_result);
+            // Calculate risk score based on actual simulation results, reconciled with ML (thorough profile)
+            let ml_scores = runs_ml.then(|| evm_ml_scores(&state, chain_id, &req.token_address, &hp_result, liquidity_usd, volume_24h_usd, age_hours, holders, proxy.as_ref()));
+            let rug_risk = ml_scores.map(|(_, rug_risk)| rug_risk);
+            let score_blend = blend_scores(
+                calculate_risk_score(&hp_result),
+                &hp_result,
+                ml_scores.map(|(ml_score, _)| ml_score),
+            );
+            let risk_score = score_blend.score;
+            let ensemble = score_blend.ensemble();
+            let (recommendation, category) = ml_recommendation(runs_ml, risk_score);
+            report_progress(progress.as_ref(), "scoring", "scoring complete");
+
+            // Optional REVM opcode trace of the sell (expensive - ?trace=true or thorough profile)
+            let sell_trace = if query.trace || (req.profile.runs_sell_trace() && !minimal) {
+                let sell_trace = trace_sell_optional(&detector, token, test_wei, pair_address.as_deref()).await;
+                report_progress(progress.as_ref(), "trace", "trace complete");
+                sell_trace
+            } else {
+                None
+            };
+            // Forked sells of growing size (?exit_curve=true) - never cached either
+            let exit_curve = if query.exit_curve && !minimal {
+                let deadline = quick_check_deadline(&state, start);
+                let exit_curve =
+                    exit_curve_optional(&state, &detector, token, pair_address.as_deref(), query.block, deadline).await;
+                report_progress(progress.as_ref(), "exit curve", "exit curve simulated");
+                exit_curve
+            } else {
+                None
+            };
+            // No pair was simulated: can the token at least move between wallets?
+            let transfer_probe = if hp_result.no_pair && !minimal {
+                let deadline = quick_check_deadline(&state, start);
+                transfer_probe_optional(&state, chain_id, token, token_decimals, query.block, deadline).await
+            } else {
+                None
+            };
 
             // Record telemetry for honeypot checks
             let latency = start.elapsed().as_millis() as u64;
@@ -454,40 +1113,73 @@ pub async fn check_honeypot(
                 use crate::utils::telemetry::{TelemetryEvent, ThreatType};
                 let event = TelemetryEvent::new(
                     ThreatType::Honeypot,
-                    U256::from((test_amount * 1e18) as u128),
+                    test_wei,
                     latency,
                     risk_score,
                     hp_result.reason.clone(),
-                );
+                )
+                .on_chain(chain_id)
+                .for_token(token);
                 state.telemetry.record_threat(event);
             } else {
-                state.telemetry.record_analysis(latency);
+                state.telemetry.record_analysis(chain_id, latency);
             }
 
-            let data = HoneypotCheckData {
-                token_address: req.token_address,
+            // Audit mode: inputs and raw sim outputs, captured before the response takes ownership
+            let audit = state.audit.is_enabled().then(|| {
+                let inputs = check_inputs(&req, &query, chain_id, test_amount, discovered_router.clone());
+                (inputs, SimulationRecord::from(&hp_result))
+            });
+
+            let mut data = HoneypotCheckData {
                 token_name,
                 token_symbol,
                 token_decimals,
-                chain_id,
                 chain_name,
                 native_symbol,
-                is_honeypot: hp_result.is_honeypot || hp_result.sell_reverted,
                 risk_score,
-                buy_success: hp_result.buy_success,
-                sell_success: hp_result.sell_success,
-                buy_tax_percent: hp_result.buy_tax_percent,
-                sell_tax_percent: hp_result.sell_tax_percent,
-                total_loss_percent: hp_result.total_loss_percent,
-                reason: hp_result.reason,
-                simulation_latency_ms: hp_result.latency_ms,
+                risk_factors: with_proxy_risk(
+                    hp_result.capped_risk_factors(state.config.max_risk_factors),
+                    proxy.as_ref(),
+                ),
+                reserve_price_mismatch,
                 // DexScreener market data
                 price_usd,
                 liquidity_usd,
                 volume_24h_usd,
                 dex_name,
                 pair_address,
+                holder_count: holders.map(|h| h.holders),
+                holder_count_truncated: holders.map(|h| h.truncated),
+                proxy_implementation: proxy.as_ref().map(|p| p.implementation.clone()),
+                recently_upgraded: proxy.as_ref().is_some_and(|p| p.recently_upgraded),
+                rug_risk,
+                ensemble,
+                score_blend: Some(score_blend),
+                recommendation,
+                category,
+                sell_trace,
+                exit_curve,
+                transferable: transfer_probe.as_ref().map(|p| p.transferable),
+                transfer_revert_reason: transfer_probe.and_then(|p| p.revert_reason),
+                sim_detail: hp_result.sim_detail.as_ref().filter(|_| query.detail).map(SimDetailResponse::from),
+                ..HoneypotCheckData::from_result(
+                    &hp_result,
+                    req.token_address,
+                    chain_id,
+                    req.profile,
+                    state.config.min_confidence,
+                    state.config.max_risk_factors,
+                )
             };
+            let detected = detected_info.as_ref().filter(|_| !minimal && query.block.is_none());
+            data.candidates = candidate_verdicts(&state, detected, token, &data, requested_amount, start).await;
+
+            if let Some((inputs, simulation)) = audit {
+                let ml = ml_record(&state, &data, ml_scores, age_hours, holders);
+                audit_check(&state, inputs, simulation, ml, &data, false);
+            }
+            alert_on_verdict(&state.alerts, chain_id, &data);
 
             Ok(Json(ApiResponse::success(
                 data,
@@ -504,63 +1196,1384 @@ pub async fn check_honeypot(
     }
 }
 
-// ============================================
-// Batch Analysis (NEW!)
-// ============================================
+/// Request inputs of a `/honeypot/check` for its audit record
+fn check_inputs(
+    req: &HoneypotCheckRequest,
+    query: &HoneypotTraceQuery,
+    chain_id: u64,
+    test_amount: f64,
+    dexscreener_router: Option<String>,
+) -> AnalysisInputs {
+    AnalysisInputs {
+        token_address: req.token_address.clone(),
+        requested_chain_id: req.chain_id,
+        requested_chain: req.chain.clone(),
+        chain_id,
+        test_amount_eth: test_amount,
+        profile: req.profile,
+        decimals: req.decimals,
+        block: query.block,
+        include_market_data: req.include_market_data,
+        trace: query.trace,
+        recommendation: query.recommendation,
+        dexscreener_router,
+    }
+}
 
-pub async fn batch_analyze(
-    State(state): State<Arc<AppState>>,
-    Json(req): Json<BatchAnalysisRequest>,
-) -> Result<Json<ApiResponse<BatchAnalysisData>>, (StatusCode, Json<ApiResponse<()>>)> {
-    let start = Instant::now();
+/// ML scores of `data` with the features that fired, for its audit record
+fn ml_record(
+    state: &AppState,
+    data: &HoneypotCheckData,
+    ml_scores: Option<(u8, u8)>,
+    age_hours: Option<u32>,
+    holders: Option<HolderCount>,
+) -> Option<MlRecord> {
+    ml_scores.map(|(score, rug_risk)| MlRecord {
+        score,
+        rug_risk,
+        features: state
+            .ml_predictions
+            .get(&prediction_key(data.chain_id, &data.token_address))
+            .map(|p| p.features.clone())
+            .unwrap_or_default(),
+        age_hours,
+        holder_count: holders.map(|h| h.holders),
+    })
+}
 
-    // Validate request
-    if req.tokens.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error(
-                ApiError::bad_request("tokens array cannot be empty"),
-                start.elapsed().as_secs_f64() * 1000.0,
-            )),
-        ));
-    }
+/// Audit mode: write the decision record of a `/honeypot/check` answer in the background
+fn audit_check(
+    state: &AppState,
+    inputs: AnalysisInputs,
+    simulation: SimulationRecord,
+    ml: Option<MlRecord>,
+    data: &HoneypotCheckData,
+    cached: bool,
+) {
+    let report = AnalysisReport {
+        inputs,
+        block_number: data.block_number,
+        cached,
+        simulation,
+        ml,
+        verdict: data.clone(),
+        recorded_at: unix_timestamp(),
+    };
+    state.audit.record(data.chain_id, data.token_address.clone(), report);
+}
 
-    if req.tokens.len() > 100 {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error(
-                ApiError::bad_request("Maximum 100 tokens per batch request"),
-                start.elapsed().as_secs_f64() * 1000.0,
-            )),
-        ));
-    }
+/// Budget for probing every chain's factories when DexScreener has no pair
+const ONCHAIN_PAIR_DISCOVERY_TIMEOUT_SECS: u64 = 5;
 
-    let concurrency = req.concurrency.clamp(1, 50);
-    let test_amount: f64 = req.test_amount_eth.parse().unwrap_or(0.1);
-    let test_wei = U256::from((test_amount * 1e18) as u128);
+/// First supported EVM chain to answer with a token/WETH pair on its known factories,
+/// probed concurrently (on-chain fallback when DexScreener has not indexed the token).
+/// Returns as soon as one probe hits, so a slow RPC on another chain doesn't hold it up.
+async fn discover_pair_chain(token: Address) -> Option<u64> {
+    use futures_util::StreamExt;
 
-    // Process tokens concurrently
-    let semaphore = Arc::new(Semaphore::new(concurrency));
-    let mut handles = Vec::new();
+    let mut probes: futures_util::stream::FuturesUnordered<_> = SUPPORTED_CHAIN_IDS
+        .iter()
+        .filter_map(|&chain_id| HoneypotDetector::for_chain(chain_id))
+        .map(|detector| async move { detector.discover_pair(token).await.map(|_| detector.chain_id) })
+        .collect();
+    let first_hit = async {
+        while let Some(found) = probes.next().await {
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    };
+    let chain_id = tokio::time::timeout(
+        std::time::Duration::from_secs(ONCHAIN_PAIR_DISCOVERY_TIMEOUT_SECS),
+        first_hit,
+    )
+    .await
+    .ok()
+    .flatten()?;
+    info!("⛓️ On-chain pair found on {} (not on DexScreener)", get_chain_name(chain_id));
+    Some(chain_id)
+}
 
-    for token_addr in req.tokens.iter() {
-        let sem = semaphore.clone();
-        let addr = token_addr.clone();
-        let wei = test_wei;
+/// Notify alert sinks of a fresh honeypot / high-risk verdict (honeypots count as Critical)
+fn alert_on_verdict(alerts: &AlertDispatcher, chain_id: u64, data: &HoneypotCheckData) {
+    let severity = if data.verdict == Verdict::Honeypot { RiskLevel::Critical } else { RiskLevel::from_score(data.risk_score) };
+    if !alerts.should_alert(severity) {
+        return;
+    }
+    let mut alert = Alert::new(severity, chain_id, data.reason.clone(), "api")
+        .for_token(data.token_address.clone())
+        .with_risk_score(data.risk_score)
+        .with_risk_factors(data.risk_factors.clone());
+    if data.verdict == Verdict::Honeypot {
+        alert = alert.honeypot();
+    }
+    alerts.notify(&alert);
+}
 
-        let handle = tokio::spawn(async move {
-            let _permit = sem.acquire().await.unwrap();
-            let item_start = Instant::now();
+/// Error for a router / factory submitted as the token (`label` from `ChainConfig::infrastructure`)
+fn infrastructure_message(address: &str, label: &str, chain_id: u64) -> String {
+    format!(
+        "{} is the {} on {} - DEX infrastructure, not a token. Submit the token contract address instead",
+        address,
+        label,
+        crate::utils::constants::get_chain_name(chain_id)
+    )
+}
 
-            // Parse address
-            let token: Result<Address, _> = addr.parse();
+/// Parse `test_amount_eth` into wei (None when omitted).
+/// Malformed or zero amounts are a 400, not a silent fallback to the default
+fn parse_test_amount(requested: Option<&str>) -> Result<Option<U256>, String> {
+    requested
+        .map(|amount| parse_native_amount(amount).map_err(|e| format!("Invalid test_amount_eth: {}", e)))
+        .transpose()
+}
 
-            match token {
-                Ok(token) => {
-                    let detector = HoneypotDetector::mainnet();
-                    match detector.detect_async(token, wei).await {
-                        Ok(result) => {
-                            // PERS v2: sell_reverted = 100, + access_control_penalty
+/// Requested test amount in wei, else the chain's `default_test_amount`
+fn test_amount_wei(requested: Option<U256>, chain_id: u64) -> U256 {
+    requested.unwrap_or_else(|| eth_to_wei(chain_test_amount(chain_id)))
+}
+
+/// Cache key: chain, token and the bucketed test amount (limits and slippage depend on
+/// probe size). Historical verdicts are immutable, keyed separately by block; a caller's
+/// `decimals` override changes the simulation, so it gets its own key too
+fn honeypot_cache_key(
+    chain_id: u64,
+    token_address: &str,
+    test_amount: f64,
+    block: Option<u64>,
+    decimals: Option<u8>,
+) -> String {
+    let mut key = format!("{}:{}:{}", chain_id, token_address.to_lowercase(), amount_bucket(test_amount));
+    if let Some(block) = block {
+        key.push_str(&format!("@{}", block));
+    }
+    if let Some(decimals) = decimals {
+        key.push_str(&format!("#d{}", decimals));
+    }
+    key
+}
+
+/// Verdicts for every chain an ambiguous auto-detect found (more than one chain with
+/// meaningful liquidity), most liquid first. The analyzed chain reuses `analyzed`;
+/// the others are served from the cache or simulated concurrently
+async fn candidate_verdicts(
+    state: &AppState,
+    detected: Option<&AutoDetectedToken>,
+    token: Address,
+    analyzed: &HoneypotCheckData,
+    requested_amount: Option<U256>,
+    start: Instant,
+) -> Vec<ChainCandidateVerdict> {
+    let Some(detected) = detected.filter(|d| d.chain_candidates.len() > 1) else {
+        return Vec::new();
+    };
+    let deadline = quick_check_deadline(state, start);
+    let checks = detected.chain_candidates.iter().take(MAX_CHAIN_CANDIDATES).map(|candidate| async move {
+        let (verdict, risk_score) = if candidate.chain_id == analyzed.chain_id {
+            (Some(analyzed.verdict), Some(analyzed.risk_score))
+        } else {
+            match candidate_result(state, candidate, token, requested_amount, deadline).await {
+                Some(result) => (
+                    Some(Verdict::gate(
+                        result.is_honeypot || result.sell_reverted,
+                        result.confidence,
+                        state.config.min_confidence,
+                    )),
+                    Some(calculate_risk_score(&result)),
+                ),
+                None => (None, None),
+            }
+        };
+        ChainCandidateVerdict {
+            chain_id: candidate.chain_id,
+            chain_name: candidate.chain_name.clone(),
+            liquidity_usd: candidate.liquidity_usd,
+            verdict,
+            risk_score,
+        }
+    });
+    futures_util::future::join_all(checks).await
+}
+
+/// One candidate chain's result, simulated against the chain's most liquid V2 router
+async fn candidate_result(
+    state: &AppState,
+    candidate: &ChainCandidate,
+    token: Address,
+    requested_amount: Option<U256>,
+    deadline: Instant,
+) -> Option<HoneypotResult> {
+    let router = candidate.best_v2_dex.as_ref().and_then(|dex| {
+        let router = dex.router_address.as_deref()?.parse::<Address>().ok()?;
+        Some((dex.dex_name.clone(), router))
+    });
+    let test_wei = test_amount_wei(requested_amount, candidate.chain_id);
+    quick_result(state, candidate.chain_id, token, test_wei, router, deadline).await
+}
+
+/// Cached result, else a plain simulation (through `router` first, when given) that
+/// is cached too. None on failure or after `CANDIDATE_TIMEOUT_SECS` / `deadline`,
+/// whichever comes first
+async fn quick_result(
+    state: &AppState,
+    chain_id: u64,
+    token: Address,
+    test_wei: U256,
+    router: Option<(String, Address)>,
+    deadline: Instant,
+) -> Option<HoneypotResult> {
+    let token_address = format!("{:?}", token);
+    let cache_key = honeypot_cache_key(chain_id, &token_address, wei_to_eth(test_wei), None, None);
+    if let Some(cached) = state.cache.get(&cache_key) {
+        return Some(cached);
+    }
+    let budget = deadline
+        .saturating_duration_since(Instant::now())
+        .min(std::time::Duration::from_secs(CANDIDATE_TIMEOUT_SECS));
+    if budget.is_zero() {
+        warn!("⏱️ No time left to check {} on {}", token_address, get_chain_name(chain_id));
+        return None;
+    }
+
+    let mut detector = HoneypotDetector::for_chain(chain_id)?.with_max_bytecode_size(state.config.max_bytecode_size);
+    if let Some((dex_name, router)) = router {
+        detector = detector.with_priority_router(dex_name, router);
+    }
+    match tokio::time::timeout(budget, detector.detect_async(token, test_wei))
+    .await
+    {
+        Ok(Ok(result)) => {
+            state.cache.set(&cache_key, result.clone());
+            Some(result)
+        }
+        Ok(Err(e)) => {
+            warn!("⚠️ Quick check of {} on {} failed: {}", token_address, get_chain_name(chain_id), e);
+            None
+        }
+        Err(_) => {
+            warn!("⏱️ Quick check of {} on {} timed out", token_address, get_chain_name(chain_id));
+            None
+        }
+    }
+}
+
+/// Most chains simulated for an ambiguous auto-detect (analyzed chain included)
+const MAX_CHAIN_CANDIDATES: usize = 4;
+
+/// Part of `REQUEST_TIMEOUT_SECS` kept back for building and sending the response
+const RESPONSE_MARGIN_MS: u64 = 500;
+
+/// When extra quick checks for a request that started at `start` must give up, so
+/// they never push the response past the global request timeout (a 504 would lose it)
+fn quick_check_deadline(state: &AppState, start: Instant) -> Instant {
+    (start + state.config.request_timeout)
+        .checked_sub(std::time::Duration::from_millis(RESPONSE_MARGIN_MS))
+        .unwrap_or(start)
+}
+
+/// Deadline for each quick simulation (candidate chains, new pairs)
+const CANDIDATE_TIMEOUT_SECS: u64 = 10;
+
+/// Run the REVM sell trace, logging (not failing) on error
+/// The mock WETH holds `pair`'s real WETH reserve when the pair is known
+async fn trace_sell_optional(
+    detector: &HoneypotDetector,
+    token: Address,
+    test_wei: U256,
+    pair: Option<&str>,
+) -> Option<SellTrace> {
+    let pair = pair.and_then(|pair| pair.parse::<Address>().ok());
+    match detector.trace_sell_async(token, test_wei, pair).await {
+        Ok(trace) => Some(trace),
+        Err(e) => {
+            warn!("⚠️ Sell trace failed for {:?}: {}", token, e);
+            None
+        }
+    }
+}
+
+/// Exit curve of `token` sold into `pair` on a fork of the detector's chain (at `block`, or the
+/// head), through the router the simulation used. None when the pair is unknown, the fork can't be
+/// pinned or the sells are inconclusive by `deadline`
+async fn exit_curve_optional(
+    state: &AppState,
+    detector: &HoneypotDetector,
+    token: Address,
+    pair: Option<&str>,
+    block: Option<u64>,
+    deadline: Instant,
+) -> Option<Vec<ExitPoint>> {
+    let pair: Address = pair?.parse().ok()?;
+    let provider = chain_provider(state, detector.chain_id)?;
+    let fork = match tokio::time::timeout(
+        deadline.saturating_duration_since(Instant::now()),
+        RpcDb::pinned(provider, block),
+    )
+    .await
+    {
+        Ok(Ok(fork)) => fork,
+        Ok(Err(e)) => {
+            warn!("⚠️ Exit curve fork for {:?} unavailable: {}", token, e);
+            return None;
+        }
+        Err(_) => {
+            warn!("⏱️ Exit curve fork for {:?} timed out", token);
+            return None;
+        }
+    };
+
+    let exit_detector = HoneypotDetector::for_chain(detector.chain_id)?;
+    let exit_detector = match detector.routers().first() {
+        Some(dex) => exit_detector.with_priority_router(dex.name.clone(), dex.address),
+        None => exit_detector,
+    };
+    match tokio::task::spawn_blocking(move || exit_detector.estimate_exit_liquidity(&fork, token, pair, Some(deadline)))
+        .await
+    {
+        Ok(Ok(curve)) => Some(curve),
+        Ok(Err(e)) => {
+            warn!("⚠️ Exit curve failed for {:?}: {}", token, e);
+            None
+        }
+        Err(e) => {
+            warn!("⚠️ Exit curve task for {:?} died: {}", token, e);
+            None
+        }
+    }
+}
+
+/// Wallet-to-wallet transfer of one whole token on a fork of `chain_id` (at `block`, or the
+/// head), for tokens without a pair to simulate against. None when the fork can't be pinned
+/// or the probe is inconclusive by `deadline`
+async fn transfer_probe_optional(
+    state: &AppState,
+    chain_id: u64,
+    token: Address,
+    decimals: Option<u8>,
+    block: Option<u64>,
+    deadline: Instant,
+) -> Option<TransferProbe> {
+    let provider = chain_provider(state, chain_id)?;
+    let fork = match tokio::time::timeout(
+        deadline.saturating_duration_since(Instant::now()),
+        RpcDb::pinned(provider, block),
+    )
+    .await
+    {
+        Ok(Ok(fork)) => fork,
+        Ok(Err(e)) => {
+            warn!("⚠️ Transfer probe fork for {:?} unavailable: {}", token, e);
+            return None;
+        }
+        Err(_) => {
+            warn!("⏱️ Transfer probe fork for {:?} timed out", token);
+            return None;
+        }
+    };
+
+    let detector = HoneypotDetector::for_chain(chain_id)?;
+    let amount = U256::from(10u64).pow(U256::from(decimals.unwrap_or(18)));
+    match tokio::task::spawn_blocking(move || detector.test_transferability(&fork, token, amount, Some(deadline)))
+        .await
+    {
+        Ok(Ok(probe)) => Some(probe),
+        Ok(Err(e)) => {
+            warn!("⚠️ Transfer probe failed for {:?}: {}", token, e);
+            None
+        }
+        Err(e) => {
+            warn!("⚠️ Transfer probe task for {:?} died: {}", token, e);
+            None
+        }
+    }
+}
+
+// ============================================
+// Pair Analysis
+// ============================================
+
+/// Analyze a pair by address: read token0/token1, pick the non-base side and
+/// run the standard honeypot check on it. `chain` is a chain id or a
+/// DexScreener chain name ("ethereum", "bsc", ...).
+/// Entry point for scanners that only know the pair (e.g. `PairCreated` events).
+#[utoipa::path(
+    get,
+    path = "/v1/pair/{chain}/{pair_address}",
+    tag = "analysis",
+    params(
+        ("chain" = String, Path, description = "Chain id, name or alias (`bsc`, `binance`, `mainnet`, ...)"),
+        ("pair_address" = String, Path, description = "V2 pair address"),
+        HoneypotTraceQuery
+    ),
+    responses(
+        (status = 200, description = "Verdict for the non-base token of the pair", body = PairAnalysisResponse),
+        (status = 400, description = "Invalid input (ApiResponse with `error`)"),
+        (status = 500, description = "Analysis failed (ApiResponse with `error`)")
+    )
+)]
+pub async fn analyze_pair(
+    State(state): State<Arc<AppState>>,
+    Path((chain, pair_address)): Path<(String, String)>,
+    Query(query): Query<HoneypotTraceQuery>,
+) -> Result<Json<ApiResponse<PairAnalysisData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+    let bad_request = |message: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(message),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        )
+    };
+
+    let chain_id = parse_chain(&chain).ok_or_else(|| bad_request(format!("Unknown chain: {}", chain)))?;
+    let pair: Address = pair_address
+        .parse()
+        .map_err(|_| bad_request("Invalid pair address format".to_string()))?;
+    let (Some(detector), Some(chain_config)) = (HoneypotDetector::for_chain(chain_id), ChainConfig::get(chain_id)) else {
+        return Err(bad_request(format!("Unsupported chain for pair analysis: {}", chain_id)));
+    };
+
+    let (token0, token1) = detector.fetch_pair_tokens(pair).await.map_err(|e| {
+        warn!("⚠️ Pair {:?} lookup failed: {}", pair, e);
+        bad_request(format!("Could not read token0/token1 from {:?}: {}", pair, e))
+    })?;
+    let Some((target, _)) = select_pair_target(token0, token1, |t| chain_config.is_canonical(t)) else {
+        return Err(bad_request(format!(
+            "Pair {:?} has no base token (wrapped native / stablecoin) on chain {}",
+            pair, chain_id
+        )));
+    };
+    info!("🔗 Pair {:?}: analyzing {:?}", pair, target);
+
+    let (info0, info1) = tokio::join!(detector.fetch_token_info(token0), detector.fetch_token_info(token1));
+    let pair_token = |address: Address, info: TokenInfo| PairTokenInfo {
+        address: format!("{:?}", address),
+        name: info.name,
+        symbol: info.symbol,
+        decimals: info.decimals,
+        is_base: chain_config.is_canonical(&address),
+    };
+
+    let request = HoneypotCheckRequest {
+        token_address: format!("{:?}", target),
+        test_amount_eth: None,
+        chain_id,
+        chain: None,
+        profile: Default::default(),
+        decimals: None,
+        include_market_data: true,
+    };
+    let Json(response) = check_honeypot(State(state), Query(query), Json(request)).await?;
+    let Some(verdict) = response.data else {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ApiError::internal("Analysis returned no data"),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    };
+
+    let data = PairAnalysisData {
+        pair_address: format!("{:?}", pair),
+        chain_id,
+        token0: pair_token(token0, info0),
+        token1: pair_token(token1, info1),
+        analyzed_token: format!("{:?}", target),
+        verdict,
+    };
+
+    Ok(Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
+// ============================================
+// New Pairs Feed (live PairCreated scanner)
+// ============================================
+
+/// Most pairs returned by `GET /new-pairs/:chain_id`
+const MAX_NEW_PAIRS_LIMIT: usize = 50;
+
+/// Most recent pairs the chain's PairCreated scanner saw, newest first, each with a
+/// quick verdict for its non-base token (cached, else simulated with
+/// `CANDIDATE_TIMEOUT_SECS`, within the request timeout). Each check holds a permit of
+/// the shared batch semaphore.
+#[utoipa::path(
+    get,
+    path = "/v1/new-pairs/{chain_id}",
+    tag = "analysis",
+    params(
+        ("chain_id" = String, Path, description = "Chain id, name or alias (`bsc`, `base`, `1`, ...)"),
+        NewPairsQuery
+    ),
+    responses(
+        (status = 200, description = "Recently created pairs with quick verdicts", body = NewPairsResponse),
+        (status = 400, description = "Unknown chain (ApiResponse with `error`)"),
+        (status = 404, description = "No scanner on this chain (ApiResponse with `error`)")
+    )
+)]
+pub async fn get_new_pairs(
+    State(state): State<Arc<AppState>>,
+    Path(chain): Path<String>,
+    Query(query): Query<NewPairsQuery>,
+) -> Result<Json<ApiResponse<NewPairsData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+    let error = |status: StatusCode, error: ApiError| {
+        (status, Json(ApiResponse::error(error, start.elapsed().as_secs_f64() * 1000.0)))
+    };
+
+    let chain_id = parse_chain(&chain)
+        .ok_or_else(|| error(StatusCode::BAD_REQUEST, ApiError::bad_request(format!("Unknown chain: {}", chain))))?;
+    let Some(feed) = state.new_pairs.get(&chain_id).cloned() else {
+        return Err(error(
+            StatusCode::NOT_FOUND,
+            ApiError::not_found(format!("No new-pair scanner on chain {} (see NEW_PAIRS_CHAINS)", chain_id)),
+        ));
+    };
+    let chain_config = ChainConfig::get(chain_id);
+    let test_wei = test_amount_wei(None, chain_id);
+    let deadline = quick_check_deadline(&state, start);
+
+    let checks = feed.recent(query.limit.min(MAX_NEW_PAIRS_LIMIT)).into_iter().map(|detected| {
+        let state = state.clone();
+        let target = new_pair_target(&detected, chain_config.as_ref());
+        async move {
+            let result = match target {
+                Some(token) => {
+                    let wait = deadline.saturating_duration_since(Instant::now());
+                    match tokio::time::timeout(wait, state.batch_semaphore.acquire()).await {
+                        Ok(Ok(_permit)) => quick_result(&state, chain_id, token, test_wei, None, deadline).await,
+                        _ => None,
+                    }
+                }
+                None => None,
+            };
+            NewPairData {
+                pair_address: detected.event.pair.clone(),
+                factory: detected.event.factory.clone(),
+                token0: detected.event.token0.clone(),
+                token1: detected.event.token1.clone(),
+                analyzed_token: target.map(|t| format!("{:?}", t)),
+                block_number: detected.block_number(),
+                tx_hash: detected.event.tx_hash.clone(),
+                detected_at: detected.detected_at,
+                verdict: result.as_ref().map(|r| {
+                    Verdict::gate(r.is_honeypot || r.sell_reverted, r.confidence, state.config.min_confidence)
+                }),
+                risk_score: result.as_ref().map(calculate_risk_score),
+            }
+        }
+    });
+    let pairs = futures_util::future::join_all(checks).await;
+
+    let data = NewPairsData {
+        chain_id,
+        chain_name: get_chain_name(chain_id).to_string(),
+        total_detected: feed.total_detected(),
+        pairs,
+    };
+
+    Ok(Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
+/// Non-base side of a detected pair (None = unparseable, or no base token on either side)
+fn new_pair_target(detected: &DetectedPair, chain_config: Option<&ChainConfig>) -> Option<Address> {
+    let token0 = detected.event.token0.parse::<Address>().ok()?;
+    let token1 = detected.event.token1.parse::<Address>().ok()?;
+    let chain_config = chain_config?;
+    select_pair_target(token0, token1, |t| chain_config.is_canonical(t)).map(|(target, _)| target)
+}
+
+// ============================================
+// All-chains Analysis (same address everywhere)
+// ============================================
+
+/// Check one address on every supported chain concurrently (bridged tokens often
+/// exist on several) and rank the chains by liquidity and risk. Each chain holds a
+/// permit of the shared batch semaphore while its check runs.
+#[utoipa::path(
+    get,
+    path = "/v1/analyze/all-chains/{address}",
+    tag = "analysis",
+    params(
+        ("address" = String, Path, description = "EVM token address")
+    ),
+    responses(
+        (status = 200, description = "Per-chain verdicts, safest first", body = AllChainsAnalysisResponse),
+        (status = 400, description = "Invalid input (ApiResponse with `error`)")
+    )
+)]
+pub async fn analyze_all_chains(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Result<Json<ApiResponse<AllChainsAnalysisData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+    if address.parse::<Address>().is_err() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request("Invalid token address format"),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    }
+
+    // Chains the RpcManager has a provider for (all EVM chains when it was never warmed up)
+    let chain_ids: Vec<u64> = SUPPORTED_CHAIN_IDS
+        .into_iter()
+        .filter(|&id| state.rpc_manager.as_ref().is_none_or(|m| m.is_supported(id)))
+        .collect();
+    info!("🌐 All-chains analysis of {} on {} chains", address, chain_ids.len());
+
+    let checks = chain_ids.into_iter().map(|chain_id| {
+        let state = state.clone();
+        let token_address = address.clone();
+        async move {
+            let chain_name = get_chain_name(chain_id).to_string();
+            let request = HoneypotCheckRequest {
+                token_address,
+                test_amount_eth: None,
+                chain_id,
+                chain: None,
+                profile: Default::default(),
+                decimals: None,
+                include_market_data: true,
+            };
+            match check_honeypot(State(state.clone()), Query(HoneypotTraceQuery::default()), Json(request)).await {
+                Ok(Json(response)) => {
+                    let verdict = response.data;
+                    let liquidity_usd = verdict.as_ref().and_then(|v| v.liquidity_usd);
+                    ChainVerdict {
+                        chain_id,
+                        chain_name,
+                        has_liquidity: verdict.as_ref().is_some_and(|v| v.buy_success)
+                            || liquidity_usd.is_some_and(|l| l > 0.0),
+                        liquidity_usd,
+                        verdict,
+                        error: None,
+                    }
+                }
+                Err((_, Json(response))) => ChainVerdict {
+                    chain_id,
+                    chain_name,
+                    has_liquidity: false,
+                    liquidity_usd: None,
+                    verdict: None,
+                    error: Some(
+                        response
+                            .error
+                            .map(|e| e.message)
+                            .unwrap_or_else(|| "Analysis failed".to_string()),
+                    ),
+                },
+            }
+        }
+    });
+    let chains = futures_util::future::join_all(checks).await;
+
+    let data = AllChainsAnalysisData::new(address, chains);
+    if let Some(name) = &data.safest_chain_name {
+        info!("🏆 {} is safest on {}", data.token_address, name);
+    }
+
+    Ok(Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
+/// Chain id from a numeric id or a DexScreener chain name
+// ============================================
+// Token Search (symbol / name → address)
+// ============================================
+
+/// Resolve a symbol or name to token addresses via DexScreener search.
+/// Ambiguous tickers return every match, ranked (exact symbol first, then
+/// liquidity) - the caller picks by address. `analyze=true` also runs the
+/// honeypot check on the top candidate.
+#[utoipa::path(
+    get,
+    path = "/v1/search",
+    tag = "analysis",
+    params(TokenSearchQuery),
+    responses(
+        (status = 200, description = "Ranked candidates (+ verdict of the best one)", body = TokenSearchResponse),
+        (status = 400, description = "Invalid input (ApiResponse with `error`)"),
+        (status = 502, description = "DexScreener search failed (ApiResponse with `error`)")
+    )
+)]
+pub async fn search_tokens(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TokenSearchQuery>,
+) -> Result<Json<ApiResponse<TokenSearchData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+    let error = |status: StatusCode, error: ApiError| {
+        (status, Json(ApiResponse::error(error, start.elapsed().as_secs_f64() * 1000.0)))
+    };
+
+    let q = query.q.trim();
+    if q.is_empty() {
+        return Err(error(StatusCode::BAD_REQUEST, ApiError::bad_request("Query `q` is empty")));
+    }
+    let chain = match query.chain_id {
+        0 => None,
+        id => match chain_id_to_dexscreener_name(id) {
+            name if dexscreener_name_to_chain_id(name) == id => Some(name),
+            _ => {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    ApiError::bad_request(format!("Unsupported chain for search: {}", id)),
+                ))
+            }
+        },
+    };
+
+    let candidates = DexScreenerClient::new()
+        .search_tokens(q, chain, query.limit)
+        .await
+        .map_err(|e| {
+            warn!("⚠️ Token search for \"{}\" failed: {}", q, e);
+            error(StatusCode::BAD_GATEWAY, ApiError::internal(format!("Token search failed: {}", e)))
+        })?;
+    let candidates: Vec<TokenSearchCandidate> = candidates
+        .into_iter()
+        .map(|c| TokenSearchCandidate {
+            chain_id: parse_chain(&c.chain),
+            token_address: c.address,
+            chain_name: c.chain,
+            token_name: c.name,
+            token_symbol: c.symbol,
+            liquidity_usd: c.liquidity_usd,
+            pair_count: c.pair_count,
+            best_pair_address: c.best_pair_address,
+            best_dex: c.best_dex,
+            price_usd: c.price_usd,
+            exact_symbol_match: c.exact_symbol_match,
+        })
+        .collect();
+
+    // Verdict of the best match only - the rest stay candidates
+    let best = candidates.first().and_then(|c| c.chain_id.map(|id| (c.token_address.clone(), id)));
+    let analysis = match best {
+        Some((token_address, chain_id)) if query.analyze => {
+            let request = HoneypotCheckRequest {
+                token_address,
+                test_amount_eth: None,
+                chain_id,
+                chain: None,
+                profile: Default::default(),
+                decimals: None,
+                include_market_data: true,
+            };
+            match check_honeypot(State(state), Query(HoneypotTraceQuery::default()), Json(request)).await {
+                Ok(Json(response)) => response.data,
+                Err((status, _)) => {
+                    warn!("⚠️ Auto-analysis of top search match failed ({})", status);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
+    Ok(Json(ApiResponse::success(
+        TokenSearchData {
+            query: q.to_string(),
+            candidates,
+            analysis,
+        },
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
+// ============================================
+// Async Analysis (job + polling)
+// ============================================
+
+/// Enqueue a honeypot check and return a job id immediately
+/// Long (e.g. `thorough`) analyses run outside the request timeout, on the shared semaphore
+#[utoipa::path(
+    post,
+    path = "/v1/analyze/async",
+    tag = "analysis",
+    params(HoneypotTraceQuery),
+    request_body = HoneypotCheckRequest,
+    responses(
+        (status = 200, description = "Job accepted", body = AsyncJobAcceptedResponse),
+        (status = 400, description = "Invalid input (ApiResponse with `error`)"),
+        (status = 500, description = "Analysis failed (ApiResponse with `error`)")
+    )
+)]
+pub async fn analyze_async(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<HoneypotTraceQuery>,
+    Json(req): Json<HoneypotCheckRequest>,
+) -> Result<Json<ApiResponse<AsyncJobAccepted>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+    let job_id = state.jobs.create();
+    info!("📥 Async job {} queued for {} ({:?})", job_id, req.token_address, req.profile);
+
+    let task_state = state.clone();
+    let task_job_id = job_id.clone();
+    tokio::spawn(async move {
+        // `run_honeypot_check` waits for a permit on the shared worker pool
+        match check_honeypot(State(task_state.clone()), Query(query), Json(req)).await {
+            Ok(Json(response)) => match response.data {
+                Some(data) => task_state.jobs.complete(&task_job_id, data),
+                None => task_state.jobs.fail(&task_job_id, "Analysis returned no data".to_string()),
+            },
+            Err((_, Json(response))) => {
+                let message = response
+                    .error
+                    .map(|e| e.message)
+                    .unwrap_or_else(|| "Analysis failed".to_string());
+                task_state.jobs.fail(&task_job_id, message);
+            }
+        }
+        info!("✅ Async job {} finished", task_job_id);
+    });
+
+    let data = AsyncJobAccepted {
+        poll_url: format!("/v1/analyze/result/{}", job_id),
+        job_id,
+        status: "pending".to_string(),
+    };
+
+    Ok(Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
+/// Poll the result of an `/analyze/async` job
+#[utoipa::path(
+    get,
+    path = "/v1/analyze/result/{job_id}",
+    tag = "analysis",
+    params(("job_id" = String, Path, description = "Id returned by /v1/analyze/async")),
+    responses(
+        (status = 200, description = "Job status and result", body = AsyncJobResultResponse),
+        (status = 404, description = "Unknown or expired job (ApiResponse with `error`)")
+    )
+)]
+pub async fn get_analysis_result(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<String>,
+) -> Result<Json<ApiResponse<AsyncJobResult>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+
+    let Some(job) = state.jobs.get(&job_id) else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ApiError::not_found(format!("Job {} not found or expired", job_id)),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    };
+
+    let data = match job {
+        JobState::Pending => AsyncJobResult {
+            job_id,
+            status: "pending".to_string(),
+            result: None,
+            error: None,
+        },
+        JobState::Done(result) => AsyncJobResult {
+            job_id,
+            status: "done".to_string(),
+            result: Some(result),
+            error: None,
+        },
+        JobState::Failed(error) => AsyncJobResult {
+            job_id,
+            status: "failed".to_string(),
+            result: None,
+            error: Some(error),
+        },
+    };
+
+    Ok(Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
+// ============================================
+// Debug: Failure Log
+// ============================================
+
+/// `X-Admin-Key` must match ADMIN_API_KEY; 404 when no key is configured
+fn require_admin(
+    state: &AppState,
+    headers: &HeaderMap,
+    endpoint: &str,
+    start: Instant,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    let Some(admin_key) = state.config.admin_api_key.as_deref() else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ApiError::not_found("Admin endpoints are disabled (set ADMIN_API_KEY)"),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    };
+
+    let provided = headers.get("X-Admin-Key").and_then(|v| v.to_str().ok());
+    if provided != Some(admin_key) {
+        warn!("Invalid admin key attempted on {}", endpoint);
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::error(
+                ApiError::unauthorized(),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    }
+    Ok(())
+}
+
+/// Recent failed analyses with their inputs, for replaying user reports
+/// Requires `X-Admin-Key` matching ADMIN_API_KEY (endpoint disabled when unset)
+#[utoipa::path(
+    get,
+    path = "/v1/debug/failures",
+    tag = "admin",
+    params(FailureLogQuery, ("X-Admin-Key" = String, Header, description = "ADMIN_API_KEY")),
+    responses(
+        (status = 200, description = "Recent failed analyses", body = FailureLogResponse),
+        (status = 401, description = "Missing or wrong X-Admin-Key (ApiResponse with `error`)"),
+        (status = 404, description = "ADMIN_API_KEY not configured (ApiResponse with `error`)")
+    )
+)]
+pub async fn get_failures(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<FailureLogQuery>,
+) -> Result<Json<ApiResponse<FailureLogData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+    require_admin(&state, &headers, "/debug/failures", start)?;
+
+    let data = FailureLogData {
+        capacity: state.failures.capacity(),
+        total_recorded: state.failures.total_recorded(),
+        failures: state.failures.recent(query.limit),
+    };
+
+    Ok(Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
+// ============================================
+// ML Feedback
+// ============================================
+
+/// Predictions older than this can no longer receive feedback
+const FEEDBACK_WINDOW_SECS: u64 = 7 * 24 * 3600;
+
+/// Recorded predictions kept at most (new analyses are not recorded beyond this)
+const MAX_RECORDED_PREDICTIONS: usize = 50_000;
+
+/// Key for `ml_predictions` (EVM addresses are case-insensitive, Solana mints are not)
+fn prediction_key(chain_id: u64, token: &str) -> (u64, String) {
+    if chain_id == CHAIN_ID_SOLANA {
+        (chain_id, token.to_string())
+    } else {
+        (chain_id, token.to_lowercase())
+    }
+}
+
+/// Score features with the shared (feedback-tuned) scorer and remember the
+/// prediction so `/feedback` can later grade it
+fn score_and_record(state: &AppState, chain_id: u64, token: &str, features: &MLFeatureSet) -> MLRiskScore {
+    let mut ml_result = state
+        .ml_scorer
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .calculate_score(features);
+
+    let key = prediction_key(chain_id, token);
+    if state.ml_predictions.len() < MAX_RECORDED_PREDICTIONS || state.ml_predictions.contains_key(&key) {
+        state
+            .ml_predictions
+            .insert(key, RecordedPrediction::from_score(&ml_result, unix_timestamp()));
+    }
+    ml_result.cap_risk_factors(state.config.max_risk_factors);
+    ml_result
+}
+
+/// Report whether an analyzed token really was a honeypot
+/// The ML features recorded at analysis time nudge the scorer's weights
+/// (bounded, after a minimum number of agreeing reports) and are saved to
+/// ML_WEIGHTS_PATH. Each analysis can be graded once. Requires `X-Admin-Key`
+#[utoipa::path(
+    post,
+    path = "/v1/feedback",
+    tag = "admin",
+    params(("X-Admin-Key" = String, Header, description = "ADMIN_API_KEY")),
+    request_body = FeedbackRequest,
+    responses(
+        (status = 200, description = "Feedback recorded", body = FeedbackResponse),
+        (status = 400, description = "Invalid input (ApiResponse with `error`)"),
+        (status = 401, description = "Missing or wrong X-Admin-Key (ApiResponse with `error`)"),
+        (status = 404, description = "No recorded prediction for the token (ApiResponse with `error`)")
+    )
+)]
+pub async fn submit_feedback(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(req): Json<FeedbackRequest>,
+) -> Result<Json<ApiResponse<FeedbackData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+    require_admin(&state, &headers, "/feedback", start)?;
+
+    let Some(chain_id) = parse_chain(&req.chain) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(format!("Unknown chain: {}", req.chain)),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    };
+
+    let Some((_, prediction)) = state.ml_predictions.remove(&prediction_key(chain_id, &req.token)) else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ApiError::not_found(format!(
+                    "No recent ML analysis of {} on chain {} (analyze it with ML enabled first)",
+                    req.token, chain_id
+                )),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    };
+
+    let outcome = {
+        let mut scorer = state.ml_scorer.write().unwrap_or_else(|e| e.into_inner());
+        let mut learner = state.feedback.lock().unwrap_or_else(|e| e.into_inner());
+        learner.record(&mut scorer, &prediction, req.was_actually_honeypot)
+    };
+
+    let mut persisted = false;
+    if !outcome.adjusted.is_empty() {
+        info!("🧠 Feedback on {} moved {} ML weight(s)", req.token, outcome.adjusted.len());
+        if let Some(path) = state.config.ml_weights_path.as_deref() {
+            let saved = state
+                .ml_scorer
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .save_weights(std::path::Path::new(path));
+            match saved {
+                Ok(()) => persisted = true,
+                Err(e) => warn!("⚠️ Could not save ML weights to {}: {}", path, e),
+            }
+        }
+    }
+
+    let data = FeedbackData {
+        token: req.token,
+        chain_id,
+        predicted_score: prediction.score,
+        was_actually_honeypot: req.was_actually_honeypot,
+        counted_features: outcome.counted,
+        adjusted_weights: outcome.adjusted.into_iter().collect(),
+        persisted,
+    };
+
+    Ok(Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
+// ============================================
+// Output Formats (?format=json|pretty|text)
+// ============================================
+
+/// `POST /honeypot/check` honoring `?format=`
+pub async fn check_honeypot_formatted(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<HoneypotTraceQuery>,
+    Json(req): Json<HoneypotCheckRequest>,
+) -> Response {
+    let format = query.format;
+    let fields = query.fields;
+    match check_honeypot(State(state), Query(query), Json(req)).await {
+        Ok(Json(response)) if fields == ResponseFields::Minimal => {
+            let response = ApiResponse {
+                success: response.success,
+                data: response.data.as_ref().map(MinimalCheckData::from),
+                error: response.error,
+                latency_ms: response.latency_ms,
+                timestamp: response.timestamp,
+            };
+            render(format, StatusCode::OK, &response, MinimalCheckData::summary)
+        }
+        Ok(Json(response)) => render(format, StatusCode::OK, &response, HoneypotCheckData::summary),
+        Err((status, Json(response))) => render(format, status, &response, |_| String::new()),
+    }
+}
+
+/// `POST /analyze/batch` honoring `?format=`
+pub async fn batch_analyze_formatted(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<FormatQuery>,
+    Json(req): Json<BatchAnalysisRequest>,
+) -> Response {
+    match batch_analyze(State(state), Json(req)).await {
+        Ok(Json(response)) => render(query.format, StatusCode::OK, &response, BatchAnalysisData::summary),
+        Err((status, Json(response))) => render(query.format, status, &response, |_| String::new()),
+    }
+}
+
+/// Encode an envelope as compact JSON, indented JSON, or `text(data)` / "❌ message" as text/plain
+fn render<T: serde::Serialize>(
+    format: OutputFormat,
+    status: StatusCode,
+    response: &ApiResponse<T>,
+    text: impl Fn(&T) -> String,
+) -> Response {
+    match format {
+        OutputFormat::Json => (status, Json(response)).into_response(),
+        OutputFormat::Pretty => match serde_json::to_string_pretty(response) {
+            Ok(body) => (status, [(header::CONTENT_TYPE, "application/json")], body).into_response(),
+            Err(_) => (status, Json(response)).into_response(),
+        },
+        OutputFormat::Text => {
+            let body = match (&response.data, &response.error) {
+                (Some(data), _) => text(data),
+                (None, Some(error)) => format!("❌ {}: {}", error.code, error.message),
+                (None, None) => String::new(),
+            };
+            (status, [(header::CONTENT_TYPE, "text/plain; charset=utf-8")], body + "\n").into_response()
+        }
+    }
+}
+
+// ============================================
+// Batch Analysis (NEW!)
+// ============================================
+
+#[utoipa::path(
+    post,
+    path = "/v1/analyze/batch",
+    tag = "analysis",
+    params(FormatQuery),
+    request_body = BatchAnalysisRequest,
+    responses(
+        (status = 200, description = "Per-token verdicts and summary", body = BatchAnalysisResponse),
+        (status = 400, description = "Empty or oversized batch (ApiResponse with `error`)")
+    )
+)]
+pub async fn batch_analyze(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<BatchAnalysisRequest>,
+) -> Result<Json<ApiResponse<BatchAnalysisData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+
+    // Validate request
+    if req.tokens.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request("tokens array cannot be empty"),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    }
+
+    let max_tokens = state.config.batch_max_tokens;
+    if req.tokens.len() > max_tokens {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(format!("Maximum {} tokens per batch request", max_tokens)),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    }
+
+    // Per-entry chain, falling back to the request's (`chain_id` 0 = auto-detect)
+    let requested_chain_ids = req
+        .resolved_chain_id()
+        .and_then(|chain_id| {
+            req.tokens
+                .iter()
+                .map(|token| token.resolved_chain_id(chain_id))
+                .collect::<Result<Vec<u64>, String>>()
+        })
+        .map_err(|message| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ApiError::bad_request(message),
+                    start.elapsed().as_secs_f64() * 1000.0,
+                )),
+            )
+        })?;
+
+    let concurrency = req.concurrency.clamp(1, 50);
+    let max_token_timeout = state.config.batch_token_timeout;
+    let token_timeout = req
+        .token_timeout_secs
+        .map(|secs| std::time::Duration::from_secs(secs.max(1)).min(max_token_timeout))
+        .unwrap_or(max_token_timeout);
+    let max_bytecode_size = state.config.max_bytecode_size;
+    let sell_lock_max_blocks = state.config.sell_lock_max_blocks;
+    let test_balance = whole_eth_to_wei(state.config.test_account_balance_eth);
+    let min_confidence = state.config.min_confidence;
+    let permit_check = req.permit_check;
+    let gas_price_check = req.gas_price_check;
+    let direct_holder_check = req.direct_holder_check;
+    let contract_recipient_check = req.contract_recipient_check;
+    let detail = req.detail;
+    let test_amount = parse_test_amount(req.test_amount_eth.as_deref()).map_err(|message| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(message),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        )
+    })?;
+
+    // ============================================
+    // BULK MARKET DATA: 1 DexScreener request per 30 tokens
+    // (instead of one auto-detect per token)
+    // ============================================
+    let addresses: Vec<&str> = req.tokens.iter().map(|t| t.address()).collect();
+    let market = match DexScreenerClient::new().get_tokens_batch(&addresses).await {
+        Ok(market) => market,
+        Err(e) => {
+            warn!("⚠️ DexScreener batch lookup failed: {} - using requested chain", e);
+            std::collections::HashMap::new()
+        }
+    };
+
+    // chain_id 0 = auto-detect from DexScreener data
+    let detected: Vec<Option<AutoDetectedToken>> = addresses
+        .iter()
+        .zip(&requested_chain_ids)
+        .map(|(addr, &chain_id)| {
+            let pairs = market.get(&addr.to_lowercase())?;
+            if chain_id == 0 {
+                DexScreenerClient::auto_detect_from_pairs(pairs).ok()
+            } else {
+                // Market data of the entry's own chain, not the most liquid one
+                DexScreenerClient::auto_detect_on_chain(pairs, chain_id).ok()
+            }
+        })
+        .collect();
+    let chain_ids: Vec<u64> = detected
+        .iter()
+        .zip(&requested_chain_ids)
+        .map(|(detected, &requested)| match (detected, requested) {
+            (Some(info), 0) => info.chain_id,
+            (None, 0) => CHAIN_ID_ETHEREUM,
+            (_, chain_id) => chain_id,
+        })
+        .collect();
+
+    // ============================================
+    // SHARED FORKS: one RpcDb per chain, pinned to one block
+    // (router / WETH / factory state is read once per chain, not once per token)
+    // ============================================
+    let forks = batch_forks(&state, &chain_ids).await;
+    let chain_configs = ChainConfig::all_chains();
+    // Tokens still running at the global request deadline are reported as timed out
+    let request_deadline = quick_check_deadline(&state, start);
+
+    // Process tokens concurrently
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut handles = Vec::new();
+
+    for (((token_addr, detected), chain_id), &requested_chain_id) in
+        addresses.iter().copied().zip(detected).zip(chain_ids).zip(&requested_chain_ids)
+    {
+        let sem = semaphore.clone();
+        let pool = state.batch_semaphore.clone();
+        let in_flight = state.in_flight.clone();
+        let audit = state.audit.clone();
+        let addr = token_addr.to_string();
+        let wei = test_amount_wei(test_amount, chain_id);
+        let fork = forks.get(&chain_id).cloned();
+        let infrastructure = token_addr.parse::<Address>().ok().and_then(|token| {
+            let label = chain_configs.get(&chain_id)?.infrastructure_label(&token)?;
+            Some(infrastructure_message(token_addr, label, chain_id))
+        });
+
+        let (liquidity_usd, price_usd, router) = match detected {
+            Some(info) if info.chain_id == chain_id => (
+                Some(info.best_dex.liquidity_usd),
+                info.price_usd,
+                info.best_dex
+                    .router_address
+                    .and_then(|r| r.parse::<Address>().ok())
+                    .map(|r| (info.best_dex.dex_name, r)),
+            ),
+            _ => (None, None, None),
+        };
+        let dexscreener_router = router.as_ref().map(|(_, router)| format!("{:?}", router));
+
+        let handle = tokio::spawn(async move {
+            let _permit = sem.acquire().await.unwrap();
+            // The per-request limit caps this batch; the shared pool caps the whole server
+            let _slot = pool.acquire().await.unwrap();
+            let _in_flight = InFlightGuard::enter(&in_flight);
+            let item_start = Instant::now();
+
+            // Parse address
+            let token: Result<Address, _> = addr.parse();
+
+            match token {
+                Ok(token) => {
+                    let detector = HoneypotDetector::for_chain(chain_id)
+                        .unwrap_or_else(HoneypotDetector::mainnet)
+                        .with_max_bytecode_size(max_bytecode_size)
+                        .with_sell_lock_max_blocks(sell_lock_max_blocks)
+                        .with_test_balance(test_balance)
+                        .with_permit_check(permit_check)
+                        .with_gas_price_check(gas_price_check)
+                        .with_direct_holder_check(direct_holder_check)
+                        .with_contract_recipient_check(contract_recipient_check);
+                    let detector = match router {
+                        Some((dex_name, router)) => detector.with_priority_router(dex_name, router),
+                        None => detector,
+                    };
+                    let detector = match &fork {
+                        Some(fork) => detector.at_historical_block(fork.block()),
+                        None => detector,
+                    };
+                    // Bounded per-token wait: one slow token must not stall the whole batch
+                    let deadline = (item_start + token_timeout).min(request_deadline);
+                    let detection = match infrastructure {
+                        Some(message) => Err(eyre::eyre!(message)),
+                        None => tokio::time::timeout(
+                            deadline.saturating_duration_since(Instant::now()),
+                            detect_batch_token(detector, fork, token, wei, deadline),
+                        )
+                        .await
+                        .unwrap_or_else(|_| {
+                            warn!("⏱️ Batch token {} timed out after {:.1}s", addr, item_start.elapsed().as_secs_f64());
+                            Err(eyre::eyre!("timeout"))
+                        }),
+                    };
+                    match detection {
+                        Ok(result) => {
+                            // PERS v2: sell_reverted = 100, + access_control_penalty
                             let base_score = if result.sell_reverted {
                                 100
                             } else if result.is_honeypot {
@@ -584,24 +2597,83 @@ pub async fn batch_analyze(
                             }
                             .to_string();
 
-                            BatchTokenResult {
+                            let batch_result = BatchTokenResult {
                                 token_address: addr,
                                 status: "success".to_string(),
                                 risk_score: Some(risk_score),
-                                is_honeypot: Some(result.is_honeypot),
+                                is_honeypot: Verdict::gate(
+                                    result.is_honeypot || result.sell_reverted,
+                                    result.confidence,
+                                    min_confidence,
+                                )
+                                .is_honeypot(),
+                                confidence: Some(result.confidence),
+                                permit_supported: permit_check.then_some(result.permit_supported),
+                                gas_price_gated: gas_price_check.then_some(result.gas_price_gated),
+                                direct_holder_sell_blocked: direct_holder_check
+                                    .then_some(result.direct_holder_sell_blocked),
+                                contract_recipient_blocked: contract_recipient_check
+                                    .then_some(result.contract_recipient_blocked),
+                                sell_lock_blocks: result.sell_lock_blocks,
+                                asymmetric_gas: result.sell_buy_gas_ratio.map(|_| result.asymmetric_gas),
+                                sim_detail: result.sim_detail.as_ref().filter(|_| detail).map(SimDetailResponse::from),
                                 level: Some(level),
                                 error: None,
                                 latency_ms: item_start.elapsed().as_secs_f64() * 1000.0,
+                                chain_id: Some(chain_id),
+                                liquidity_usd,
+                                price_usd,
+                                block_number: result.block_number,
+                                analyzed_at: result.analyzed_at,
+                            };
+                            if audit.is_enabled() {
+                                let report = AnalysisReport {
+                                    inputs: AnalysisInputs {
+                                        token_address: batch_result.token_address.clone(),
+                                        requested_chain_id,
+                                        requested_chain: None,
+                                        chain_id,
+                                        test_amount_eth: wei_to_eth(wei),
+                                        profile: AnalysisProfile::default(),
+                                        decimals: None,
+                                        block: None,
+                                        include_market_data: true,
+                                        trace: false,
+                                        recommendation: false,
+                                        dexscreener_router,
+                                    },
+                                    block_number: result.block_number,
+                                    cached: false,
+                                    simulation: SimulationRecord::from(&result),
+                                    ml: None,
+                                    verdict: batch_result.clone(),
+                                    recorded_at: unix_timestamp(),
+                                };
+                                audit.record(chain_id, batch_result.token_address.clone(), report);
                             }
+                            batch_result
                         }
                         Err(e) => BatchTokenResult {
                             token_address: addr,
                             status: "error".to_string(),
                             risk_score: None,
                             is_honeypot: None,
+                            confidence: None,
+                            permit_supported: None,
+                            gas_price_gated: None,
+                            direct_holder_sell_blocked: None,
+                            contract_recipient_blocked: None,
+                            sell_lock_blocks: None,
+                            asymmetric_gas: None,
+                            sim_detail: None,
                             level: None,
                             error: Some(e.to_string()),
                             latency_ms: item_start.elapsed().as_secs_f64() * 1000.0,
+                            chain_id: Some(chain_id),
+                            liquidity_usd,
+                            price_usd,
+                            block_number: None,
+                            analyzed_at: unix_timestamp(),
                         },
                     }
                 }
@@ -610,9 +2682,22 @@ pub async fn batch_analyze(
                     status: "error".to_string(),
                     risk_score: None,
                     is_honeypot: None,
+                    confidence: None,
+                    permit_supported: None,
+                    gas_price_gated: None,
+                    direct_holder_sell_blocked: None,
+                    contract_recipient_blocked: None,
+                    sell_lock_blocks: None,
+                    asymmetric_gas: None,
+                    sim_detail: None,
                     level: None,
                     error: Some("Invalid address format".to_string()),
                     latency_ms: item_start.elapsed().as_secs_f64() * 1000.0,
+                    chain_id: Some(chain_id),
+                    liquidity_usd,
+                    price_usd,
+                    block_number: None,
+                    analyzed_at: unix_timestamp(),
                 },
             }
         });
@@ -620,14 +2705,51 @@ pub async fn batch_analyze(
         handles.push(handle);
     }
 
-    // Collect results
-    let mut results = Vec::new();
-    for handle in handles {
-        if let Ok(result) = handle.await {
-            results.push(result);
+    // Collect results - every requested token gets an entry, even if its task died
+    let mut results = Vec::with_capacity(handles.len());
+    for (handle, token_addr) in handles.into_iter().zip(&addresses) {
+        match handle.await {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                let reason = if e.is_panic() { "panicked" } else { "was cancelled" };
+                error!("❌ Batch task for {} {}: {}", token_addr, reason, e);
+                results.push(BatchTokenResult {
+                    token_address: token_addr.to_string(),
+                    status: "error".to_string(),
+                    risk_score: None,
+                    is_honeypot: None,
+                    confidence: None,
+                    permit_supported: None,
+                    gas_price_gated: None,
+                    direct_holder_sell_blocked: None,
+                    contract_recipient_blocked: None,
+                    sell_lock_blocks: None,
+                    asymmetric_gas: None,
+                    sim_detail: None,
+                    level: None,
+                    error: Some(format!("Analysis task {} - result unavailable", reason)),
+                    latency_ms: 0.0,
+                    chain_id: None,
+                    liquidity_usd: None,
+                    price_usd: None,
+                    block_number: None,
+                    analyzed_at: unix_timestamp(),
+                });
+            }
         }
     }
 
+    for fork in forks.values() {
+        let stats = fork.stats();
+        info!(
+            "🍴 Batch fork chain {} @ block {}: {} RPC reads, {} cache hits",
+            fork.chain_id(),
+            fork.block(),
+            stats.rpc_reads,
+            stats.cache_hits
+        );
+    }
+
     // Calculate summary
     let total_safe = results
         .iter()
@@ -648,14 +2770,20 @@ pub async fn batch_analyze(
         if result.is_honeypot.unwrap_or(false) {
             let event = TelemetryEvent::new(
                 ThreatType::Honeypot,
-                U256::from((test_amount * 1e18) as u128),
+                test_amount_wei(test_amount, result.chain_id.unwrap_or(CHAIN_ID_ETHEREUM)),
                 result.latency_ms as u64,
                 result.risk_score.unwrap_or(95),
                 format!("Batch: {}", result.token_address),
-            );
+            )
+            .on_chain(result.chain_id.unwrap_or(CHAIN_ID_ETHEREUM));
+            let event = match result.token_address.parse::<Address>() {
+                Ok(token) => event.for_token(token),
+                Err(_) => event,
+            };
             state.telemetry.record_threat(event);
         } else if result.status == "success" {
-            state.telemetry.record_analysis(result.latency_ms as u64);
+            state.telemetry
+                .record_analysis(result.chain_id.unwrap_or(CHAIN_ID_ETHEREUM), result.latency_ms as u64);
         }
     }
 
@@ -675,10 +2803,78 @@ pub async fn batch_analyze(
     )))
 }
 
+// ============================================
+// Loss Explanation (post-mortem of a victim tx)
+// ============================================
+
+#[utoipa::path(
+    get,
+    path = "/v1/explain/{tx_hash}",
+    tag = "analysis",
+    params(("tx_hash" = String, Path, description = "Victim transaction hash"), ExplainLossQuery),
+    responses(
+        (status = 200, description = "Post-mortem of the transaction", body = ExplainLossResponse),
+        (status = 400, description = "Invalid input (ApiResponse with `error`)"),
+        (status = 500, description = "Analysis failed (ApiResponse with `error`)")
+    )
+)]
+pub async fn explain_loss(
+    Path(tx_hash): Path<String>,
+    Query(query): Query<ExplainLossQuery>,
+) -> Result<Json<ApiResponse<ExplainLossData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+
+    let is_valid_hash = tx_hash.len() == 66
+        && tx_hash.starts_with("0x")
+        && tx_hash[2..].chars().all(|c| c.is_ascii_hexdigit());
+    if !is_valid_hash {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request("Invalid transaction hash format"),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    }
+
+    let provider = RpcProvider::new(query.chain_id).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request(format!("Trace API unavailable: {}", e)),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        )
+    })?;
+
+    info!("🔬 Explaining loss for {} on chain {}", tx_hash, query.chain_id);
+    let explanation = TraceClient::new(provider).explain_loss(&tx_hash).await.map_err(|e| {
+        error!("❌ Loss explanation failed for {}: {}", tx_hash, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ApiError::internal(format!("Loss explanation failed: {}", e)),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        )
+    })?;
+
+    Ok(Json(ApiResponse::success(
+        ExplainLossData::from_explanation(explanation, query.chain_id),
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
 // ============================================
 // Stats
 // ============================================
 
+#[utoipa::path(
+    get,
+    path = "/v1/stats",
+    tag = "status",
+    responses((status = 200, description = "Telemetry counters", body = StatsResponse))
+)]
 pub async fn get_stats(State(state): State<Arc<AppState>>) -> Json<ApiResponse<StatsData>> {
     let start = Instant::now();
     let stats = state.telemetry.get_stats();
@@ -691,6 +2887,8 @@ pub async fn get_stats(State(state): State<Arc<AppState>>) -> Json<ApiResponse<S
         total_value_protected_eth: stats.total_value_protected_eth,
         avg_latency_ms: stats.avg_latency_ms,
         uptime_seconds: state.uptime_seconds(),
+        in_flight: state.in_flight(),
+        max_concurrency: MAX_CONCURRENT_ANALYSES,
         api_version: env!("CARGO_PKG_VERSION").to_string(),
     };
 
@@ -704,13 +2902,157 @@ pub async fn get_stats(State(state): State<Arc<AppState>>) -> Json<ApiResponse<S
     ))
 }
 
+#[utoipa::path(
+    get,
+    path = "/v1/stats/by-chain",
+    tag = "status",
+    responses((status = 200, description = "Telemetry counters per chain id", body = ChainStatsResponse))
+)]
+pub async fn get_stats_by_chain(State(state): State<Arc<AppState>>) -> Json<ApiResponse<ChainStatsData>> {
+    let start = Instant::now();
+    let data = ChainStatsData {
+        chains: state.telemetry.get_stats_by_chain(),
+        uptime_seconds: state.uptime_seconds(),
+    };
+
+    Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    ))
+}
+
+/// Upstream call latency per RPC method / DexScreener endpoint (histogram + percentiles),
+/// to see which call dominates analysis time
+#[utoipa::path(
+    get,
+    path = "/v1/stats/latency",
+    tag = "status",
+    responses((status = 200, description = "Upstream latency histograms per method", body = LatencyStatsResponse))
+)]
+pub async fn get_latency_stats(State(state): State<Arc<AppState>>) -> Json<ApiResponse<LatencyStatsData>> {
+    let start = Instant::now();
+    let data = LatencyStatsData {
+        methods: UPSTREAM_LATENCY.snapshot(),
+        uptime_seconds: state.uptime_seconds(),
+    };
+
+    Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    ))
+}
+
+// ============================================
+// Cache
+// ============================================
+
+/// Period of the background cache/job cleanup, which also takes a hit-rate sample
+const CACHE_MAINTENANCE_INTERVAL_SECS: u64 = 60;
+
+/// Verdict cache counters and the rolling hit-rate history (one sample per
+/// maintenance tick), for tuning TTLs and capacity
+#[utoipa::path(
+    get,
+    path = "/v1/cache/stats",
+    tag = "status",
+    responses((status = 200, description = "Cache counters and hit-rate history", body = CacheStatsResponse))
+)]
+pub async fn get_cache_stats(State(state): State<Arc<AppState>>) -> Json<ApiResponse<CacheStatsData>> {
+    let start = Instant::now();
+    let data = CacheStatsData {
+        current: state.cache.stats(),
+        history: state.cache.history(),
+        sample_interval_secs: CACHE_MAINTENANCE_INTERVAL_SECS,
+    };
+
+    Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    ))
+}
+
+/// Drop every cached verdict (counters and history are kept)
+/// Requires `X-Admin-Key` matching ADMIN_API_KEY (endpoint disabled when unset)
+#[utoipa::path(
+    post,
+    path = "/v1/cache/purge",
+    tag = "admin",
+    params(("X-Admin-Key" = String, Header, description = "ADMIN_API_KEY")),
+    responses(
+        (status = 200, description = "Cache purged", body = CachePurgeResponse),
+        (status = 401, description = "Missing or wrong X-Admin-Key (ApiResponse with `error`)"),
+        (status = 404, description = "ADMIN_API_KEY not configured (ApiResponse with `error`)")
+    )
+)]
+pub async fn purge_cache(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<CachePurgeData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+    require_admin(&state, &headers, "/cache/purge", start)?;
+
+    let removed = state.cache.purge();
+    info!("🗑️ Cache purged via admin endpoint ({} entries)", removed);
+
+    Ok(Json(ApiResponse::success(
+        CachePurgeData { removed },
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
+// ============================================
+// Protection Report (JSON variant of the marketing report)
+// ============================================
+
+#[utoipa::path(
+    get,
+    path = "/v1/report",
+    tag = "status",
+    params(ReportQuery),
+    responses(
+        (status = 200, description = "Protection report", body = ReportResponse),
+        (status = 400, description = "Invalid eth_price (ApiResponse with `error`)")
+    )
+)]
+pub async fn get_report(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Json<ApiResponse<ReportData>>, (StatusCode, Json<ApiResponse<()>>)> {
+    let start = Instant::now();
+
+    if !query.eth_price.is_finite() || query.eth_price < 0.0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ApiError::bad_request("eth_price must be a non-negative number"),
+                start.elapsed().as_secs_f64() * 1000.0,
+            )),
+        ));
+    }
+
+    // Price each chain's native token (BNB, MATIC, AVAX...) instead of assuming ETH
+    let native_prices = match AlchemyPricesClient::new() {
+        Ok(client) => client.get_native_prices(&state.telemetry.chains_with_value()).await,
+        Err(e) => {
+            warn!("⚠️ Native prices unavailable ({}), using eth_price for ETH chains only", e);
+            std::collections::HashMap::new()
+        }
+    };
+    let data = state.telemetry.generate_report_data_priced(query.eth_price, native_prices);
+
+    Ok(Json(ApiResponse::success(
+        data,
+        start.elapsed().as_secs_f64() * 1000.0,
+    )))
+}
+
 // ============================================
 // Helper Functions
 // ============================================
 
 /// Calculate risk score from HoneypotResult
 /// PERS v2 algorithm implementation
-fn calculate_risk_score(result: &crate::core::honeypot::HoneypotResult) -> u8 {
+fn calculate_risk_score(result: &HoneypotResult) -> u8 {
     // Special case: No liquidity found - THIS IS SUSPICIOUS!
     // If we can't simulate buy/sell, we can't verify safety
     // Treat as HIGH RISK (not safe to trade)
@@ -737,21 +3079,352 @@ fn calculate_risk_score(result: &crate::core::honeypot::HoneypotResult) -> u8 {
         10 // Safe - minimal loss
     };
 
-    // Only add access control penalty if there's suspicious loss
-    let penalty = if result.total_loss_percent > 5.0 {
-        result.access_control_penalty as u32
+    // Cap at 100
+    (base_score + counted_access_penalty(result) as u32).min(100) as u8
+}
+
+/// Access control penalty `calculate_risk_score` adds: only with suspicious loss
+/// (SELFDESTRUCT is a rug vector regardless of tax, and a likely honeypot carries the
+/// composite rule's penalty, so both always count)
+fn counted_access_penalty(result: &HoneypotResult) -> u8 {
+    let has_selfdestruct = result.risk_factors.iter().any(|f| f.contains("SELFDESTRUCT"));
+    if result.total_loss_percent > 5.0 || has_selfdestruct || result.likely_honeypot {
+        result.access_control_penalty
     } else {
         0 // Ignore for low-loss tokens (likely legit)
-    };
-
-    // Cap at 100
-    (base_score + penalty).min(100) as u8
+    }
 }
 
 // ============================================
 // MARKET DATA HELPER (DexScreener with timeout)
 // ============================================
 
+/// Token metadata from RPC, skipped for the fast profile
+async fn fetch_token_info_for_profile(
+    detector: &HoneypotDetector,
+    token: Address,
+    profile: AnalysisProfile,
+    fetch: bool,
+) -> (Option<String>, Option<String>, Option<u8>) {
+    if !fetch || !profile.fetches_token_info() {
+        return (None, None, None);
+    }
+    let token_info = detector.fetch_token_info(token).await;
+    (token_info.name, token_info.symbol, detector.decimals_override().or(token_info.decimals))
+}
+
+/// DexScreener market data, holding a market data permit
+async fn fetch_market_data_bounded(
+    state: &AppState,
+    token_address: &str,
+    chain_id: u64,
+    fetch: bool,
+) -> (Option<String>, Option<f64>, Option<f64>, Option<String>, Option<String>) {
+    if !fetch {
+        return (None, None, None, None, None);
+    }
+    let Ok(_permit) = state.market_data_semaphore.acquire().await else {
+        return (None, None, None, None, None);
+    };
+    fetch_market_data_optional(token_address, chain_id).await
+}
+
+/// Market data + reserve price cross-check, run alongside the simulation on cache miss
+/// Reserve check uses latest state only - skipped for historical blocks
+async fn fetch_market_enrichment(
+    state: &AppState,
+    token_address: &str,
+    chain_id: u64,
+    token: Address,
+    fetch: bool,
+    block: Option<u64>,
+    info: Option<&AutoDetectedToken>,
+) -> ((Option<String>, Option<f64>, Option<f64>, Option<String>, Option<String>), bool) {
+    let reserve_check = async {
+        if !fetch || block.is_some() {
+            return false;
+        }
+        let Ok(_permit) = state.market_data_semaphore.acquire().await else {
+            return false;
+        };
+        check_reserve_price(state, chain_id, token, info).await
+    };
+    tokio::join!(fetch_market_data_bounded(state, token_address, chain_id, fetch), reserve_check)
+}
+
+/// Final `risk_score`: the simulation weighted by its confidence, blended with the ML score
+/// (when it ran) and static bytecode findings (when the scan found any - a clean scan is
+/// absence of evidence, not a safe verdict). A confirmed honeypot keeps the simulation score
+/// as its floor; the access control penalty counts as static only when `sim_score` left it out
+fn blend_scores(sim_score: u8, result: &HoneypotResult, ml_score: Option<u8>) -> ScoreBlend {
+    let mut builder = RiskScoreBuilder::new()
+        .with_sim_verdict(sim_score, result.is_honeypot || result.sell_reverted)
+        .with_sim_confidence(result.confidence);
+    if let Some(ml_score) = ml_score {
+        builder = builder.with_ml_score(ml_score);
+    }
+    if result.access_control_penalty > 0 && counted_access_penalty(result) == 0 {
+        builder = builder.with_static_score(result.access_control_penalty);
+    }
+    let blend = builder.build_blend();
+    if let Some(ensemble) = blend.ensemble().filter(|e| e.disagreement) {
+        warn!(
+            "⚖️ Sim/ML disagreement: sim {} vs ML {:?} -> {} ({:?})",
+            ensemble.sim_score, ensemble.ml_score, ensemble.score, ensemble.dominant
+        );
+    }
+    blend
+}
+
+/// ML liquidity scoring for EVM tokens from DexScreener data (thorough profile)
+/// Returns (ml_score, rug_risk)
+#[allow(clippy::too_many_arguments)]
+fn evm_ml_scores(
+    state: &AppState,
+    chain_id: u64,
+    token: &str,
+    result: &HoneypotResult,
+    liquidity_usd: Option<f64>,
+    volume_24h_usd: Option<f64>,
+    age_hours: Option<u32>,
+    holders: Option<HolderCount>,
+    proxy: Option<&ProxyUpgrade>,
+) -> (u8, u8) {
+    let features = MLFeatureSet {
+        // Contract signals the simulation (and the proxy slot read) already produced
+        contract: ContractFeatures {
+            is_proxy: proxy.is_some(),
+            recently_upgraded: proxy.is_some_and(|p| p.recently_upgraded),
+            has_blacklist: result.dynamic_blacklist_detected || result.access_control_penalty > 0,
+            has_anti_bot: result.same_block_sell_blocked,
+            ..Default::default()
+        },
+        liquidity: LiquidityFeatures {
+            total_liquidity_usd: liquidity_usd.unwrap_or(0.0),
+            pool_count: 1,
+            ..Default::default()
+        },
+        trading: TradingFeatures {
+            volume_24h_usd: volume_24h_usd.unwrap_or(0.0),
+            holder_count: holders.map_or(0, |h| h.holders),
+            holder_count_truncated: holders.is_some_and(|h| h.truncated),
+            ..Default::default()
+        },
+        // Unknown age (lookup failed / timed out) scores as a new token, as before
+        social: SocialFeatures {
+            age_hours: age_hours.unwrap_or(0),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let ml_result = score_and_record(state, chain_id, token, &features);
+    info!("🧠 ML score: {}, rug risk: {}", ml_result.score, ml_result.rug_risk);
+    ((ml_result.score as u32).min(100) as u8, ml_result.rug_risk.min(100) as u8)
+}
+
+/// Token age in hours (ML `age_hours` feature) and holder count (ML `holder_count` feature),
+/// both skipped unless ML runs. The holder scan starts at the creation block the age lookup found
+async fn fetch_age_and_holders(
+    state: &AppState,
+    chain_id: u64,
+    token: Address,
+    runs_ml: bool,
+) -> (Option<u32>, Option<HolderCount>) {
+    if !runs_ml {
+        return (None, None);
+    }
+    let creation = fetch_contract_creation(state, chain_id, token).await;
+    let holders = fetch_holder_count(state, chain_id, token, creation.map_or(0, |(block, _)| block)).await;
+    (creation.map(|(_, created_at)| age_hours_since(created_at)), holders)
+}
+
+/// (block, timestamp) the token contract was deployed in
+/// Cached per (chain, token); the first lookup is a ~25-call eth_getCode binary search
+async fn fetch_contract_creation(state: &AppState, chain_id: u64, token: Address) -> Option<(u64, u64)> {
+    if let Some(creation) = state.creation_times.get(&(chain_id, token)) {
+        return Some(*creation);
+    }
+
+    let provider = chain_provider(state, chain_id)?;
+    let address = format!("{:?}", token);
+    match tokio::time::timeout(
+        std::time::Duration::from_secs(TOKEN_AGE_TIMEOUT_SECS),
+        provider.contract_creation(&address),
+    )
+    .await
+    {
+        Ok(Ok(Some(creation))) => {
+            state.creation_times.insert((chain_id, token), creation);
+            Some(creation)
+        }
+        Ok(Ok(None)) => None,
+        Ok(Err(e)) => {
+            warn!("⚠️ Token age lookup failed for {}: {}", address, e);
+            None
+        }
+        Err(_) => {
+            warn!("⏱️ Token age lookup timed out for {}", address);
+            None
+        }
+    }
+}
+
+/// Deadline for the creation-block search (a cold lookup is ~25 sequential calls)
+const TOKEN_AGE_TIMEOUT_SECS: u64 = 5;
+
+/// EIP-1967 implementation now vs `PROXY_UPGRADE_WINDOW_SECS` ago (at `?block=` when set)
+/// 2-3 RPC reads, the historical one needs an archive node. None when skipped,
+/// not a proxy, or the lookup failed
+async fn fetch_proxy_upgrade(
+    state: &AppState,
+    chain_id: u64,
+    token: Address,
+    block: Option<u64>,
+    enabled: bool,
+) -> Option<ProxyUpgrade> {
+    if !enabled {
+        return None;
+    }
+    let provider = chain_provider(state, chain_id)?;
+    let address = format!("{:?}", token);
+    match tokio::time::timeout(
+        std::time::Duration::from_secs(PROXY_UPGRADE_TIMEOUT_SECS),
+        provider.proxy_upgrade(&address, block, PROXY_UPGRADE_WINDOW_SECS),
+    )
+    .await
+    {
+        Ok(Ok(proxy)) => proxy,
+        Ok(Err(e)) => {
+            warn!("⚠️ Proxy implementation lookup failed for {}: {}", address, e);
+            None
+        }
+        Err(_) => {
+            warn!("⏱️ Proxy implementation lookup timed out for {}", address);
+            None
+        }
+    }
+}
+
+/// Deadline for the proxy implementation reads
+const PROXY_UPGRADE_TIMEOUT_SECS: u64 = 3;
+
+/// Lead with a recent proxy upgrade: the code just simulated may be days old
+fn with_proxy_risk(mut risk_factors: Vec<String>, proxy: Option<&ProxyUpgrade>) -> Vec<String> {
+    if let Some(proxy) = proxy.filter(|p| p.recently_upgraded) {
+        risk_factors.insert(
+            0,
+            format!(
+                "PROXY UPGRADED: implementation changed to {} in the last {} days",
+                proxy.implementation,
+                PROXY_UPGRADE_WINDOW_SECS / 86_400
+            ),
+        );
+    }
+    risk_factors
+}
+
+/// Holder count from Transfer logs since `from_block` (0 if unknown), capped at HOLDER_SCAN_MAX_BLOCKS
+/// Cached per (chain, token) with a short TTL
+async fn fetch_holder_count(state: &AppState, chain_id: u64, token: Address, from_block: u64) -> Option<HolderCount> {
+    let provider = chain_provider(state, chain_id)?;
+    let counter = HolderCounter::new(provider)
+        .with_max_blocks(state.config.holder_scan_max_blocks)
+        .with_cache(state.holder_counts.clone());
+    match tokio::time::timeout(
+        std::time::Duration::from_secs(HOLDER_COUNT_TIMEOUT_SECS),
+        counter.estimate_holder_count(token, from_block),
+    )
+    .await
+    {
+        Ok(Ok(count)) => Some(count),
+        Ok(Err(e)) => {
+            warn!("⚠️ Holder count failed for {:?}: {}", token, e);
+            None
+        }
+        Err(_) => {
+            warn!("⏱️ Holder count timed out for {:?}", token);
+            None
+        }
+    }
+}
+
+/// Deadline for the Transfer log scan (HOLDER_SCAN_MAX_BLOCKS / 2000-block chunks)
+const HOLDER_COUNT_TIMEOUT_SECS: u64 = 5;
+
+/// Max wait for a chain's head block when forking state for a batch
+const BATCH_FORK_TIMEOUT_SECS: u64 = 5;
+
+/// One forked state per distinct chain of a batch, pinned to that chain's current block hash
+/// (all chains at once). Chains whose block can't be fetched get no fork (their tokens use eth_call)
+async fn batch_forks(state: &AppState, chain_ids: &[u64]) -> std::collections::HashMap<u64, Arc<RpcDb>> {
+    let mut distinct = chain_ids.to_vec();
+    distinct.sort_unstable();
+    distinct.dedup();
+
+    let pins = distinct.into_iter().map(|chain_id| async move {
+        let provider = chain_provider(state, chain_id)?;
+        match tokio::time::timeout(
+            std::time::Duration::from_secs(BATCH_FORK_TIMEOUT_SECS),
+            RpcDb::pinned(provider, None),
+        )
+        .await
+        {
+            Ok(Ok(fork)) => Some((chain_id, Arc::new(fork))),
+            Ok(Err(e)) => {
+                warn!("⚠️ Batch fork for chain {} unavailable: {}", chain_id, e);
+                None
+            }
+            Err(_) => {
+                warn!("⏱️ Batch fork for chain {} timed out fetching the head block", chain_id);
+                None
+            }
+        }
+    });
+    futures_util::future::join_all(pins).await.into_iter().flatten().collect()
+}
+
+/// The managed provider for `chain_id`, or a fresh one from the environment
+fn chain_provider(state: &AppState, chain_id: u64) -> Option<RpcProvider> {
+    match state.rpc_manager.as_ref().and_then(|m| m.get(chain_id)).cloned() {
+        Some(provider) => Some(provider),
+        None => RpcProvider::new(chain_id).ok(),
+    }
+}
+
+/// Forked REVM round trip on the batch's shared chain state; falls back to the
+/// eth_call path (pinned to the same block) when the fork can't serve the token.
+/// The forked sim stops at `deadline` even if nobody awaits it anymore
+async fn detect_batch_token(
+    detector: HoneypotDetector,
+    fork: Option<Arc<RpcDb>>,
+    token: Address,
+    wei: U256,
+    deadline: Instant,
+) -> eyre::Result<HoneypotResult> {
+    let detector = Arc::new(detector);
+    if let Some(fork) = fork {
+        let forked = detector.clone();
+        match tokio::task::spawn_blocking(move || forked.detect_forked_until(&fork, token, wei, Some(deadline))).await {
+            Ok(Ok(result)) => return Ok(result),
+            Ok(Err(e)) => warn!("⚠️ Forked sim failed for {:?}: {} - falling back to eth_call", token, e),
+            Err(e) => warn!("⚠️ Forked sim task for {:?} died: {} - falling back to eth_call", token, e),
+        }
+    }
+    detector.detect_async(token, wei).await
+}
+
+/// ML recommendation + category for the final (reconciled) risk score
+/// Derived from the reconciled score so a confirmed honeypot is never "Buy"
+fn ml_recommendation(runs_ml: bool, risk_score: u8) -> (Option<Recommendation>, Option<RiskCategory>) {
+    if !runs_ml {
+        return (None, None);
+    }
+    (
+        Some(Recommendation::from_score(risk_score as u32)),
+        Some(RiskCategory::from_score(risk_score as u32)),
+    )
+}
+
 /// Fetch market data from DexScreener with 3 second timeout
 /// Returns (price_usd, liquidity_usd, volume_24h_usd, dex_name, pair_address)
 async fn fetch_market_data_optional(
@@ -778,10 +3451,62 @@ async fn fetch_market_data_optional(
     }
 }
 
+/// Cross-check the DexScreener pair price against its on-chain reserves
+/// Any missing data (no pair, non-V2 pair, RPC error) = no flag
+async fn check_reserve_price(
+    state: &AppState,
+    chain_id: u64,
+    token: Address,
+    info: Option<&AutoDetectedToken>,
+) -> bool {
+    let Some((pair, reported)) = info.and_then(|i| {
+        let price = i.price_native.as_deref()?.parse::<f64>().ok()?;
+        let base: Address = i.base_token_address.as_deref()?.parse().ok()?;
+        // priceNative is quoted for the base side - invert when the token is the quote
+        let reported = if base == token { price } else { 1.0 / price };
+        Some((i.pair_address.clone()?, reported))
+    }) else {
+        return false;
+    };
+
+    let provider = match state.rpc_manager.as_ref().and_then(|m| m.get(chain_id)).cloned() {
+        Some(provider) => provider,
+        None => match RpcProvider::new(chain_id) {
+            Ok(provider) => provider,
+            Err(_) => return false,
+        },
+    };
+
+    let reserves = match tokio::time::timeout(
+        std::time::Duration::from_secs(3),
+        ReserveReader::new(provider).read_pair(&pair),
+    )
+    .await
+    {
+        Ok(Ok(reserves)) => reserves,
+        _ => return false,
+    };
+
+    let Some(reserve_price) = reserves.price_of(token) else {
+        return false;
+    };
+    let mismatch = is_reserve_price_mismatch(reserve_price, reported);
+    if mismatch {
+        warn!(
+            "⚠️ Reserve price mismatch on {}: reserves imply {:.6e}, DexScreener reports {:.6e}",
+            pair, reserve_price, reported
+        );
+    }
+    mismatch
+}
+
 // ============================================
 // SOLANA TOKEN HANDLER
 // ============================================
 
+/// Confidence of a Solana verdict backed by on-chain mint/DAS flags (no swap simulation)
+const SOLANA_DAS_CONFIDENCE: u8 = 60;
+
 /// Handle Solana token analysis using DexScreener + Solana RPC
 async fn handle_solana_token(
     state: &Arc<AppState>,
@@ -850,6 +3575,7 @@ async fn handle_solana_token(
         trading: TradingFeatures {
             volume_24h_usd: volume_24h_usd.unwrap_or(0.0),
             holder_count: 0,
+            holder_count_truncated: false,
             top_10_holder_percent: 0.0,
             buy_count_24h: 0,
             sell_count_24h: 0,
@@ -872,8 +3598,7 @@ async fn handle_solana_token(
     let is_pump_fun = best_pair.dex_id.to_lowercase().contains("pump");
     
     // Calculate ML risk score
-    let ml_scorer = MLRiskScorer::new();
-    let ml_result = ml_scorer.calculate_score(&features);
+    let ml_result = score_and_record(state, CHAIN_ID_SOLANA, &req.token_address, &features);
     
     // Adjust risk for pump.fun tokens (inherently risky)
     let mut risk_score = ml_result.score as u8;
@@ -888,6 +3613,7 @@ async fn handle_solana_token(
     
     // Determine if honeypot based on Solana analysis
     let is_honeypot = solana_analysis.as_ref().map(|a| a.is_honeypot).unwrap_or(false);
+    let confidence = solana_analysis.as_ref().map_or(0, |_| SOLANA_DAS_CONFIDENCE);
     if is_honeypot {
         risk_score = risk_score.max(80);
     }
@@ -913,26 +3639,26 @@ async fn handle_solana_token(
     };
     
     // Record telemetry
-    state.telemetry.record_analysis(start.elapsed().as_millis() as u64);
+    state.telemetry.record_analysis(CHAIN_ID_SOLANA, start.elapsed().as_millis() as u64);
     
     // Clone for logging before moving into struct
     let symbol_for_log = token_symbol.clone();
     
+    let verdict = Verdict::gate(is_honeypot, confidence, state.config.min_confidence);
     let data = HoneypotCheckData {
-        token_address: req.token_address.clone(),
         token_name,
         token_symbol,
         token_decimals: solana_analysis.as_ref().map(|_| 6), // Most Solana tokens use 6 decimals
-        chain_id: CHAIN_ID_SOLANA,
-        chain_name: "Solana".to_string(),
-        native_symbol: "SOL".to_string(),
-        is_honeypot,
+        is_honeypot: verdict.is_honeypot(),
+        verdict,
+        confidence,
         risk_score,
         buy_success: true, // Can't simulate on Solana
         sell_success: !is_honeypot,
-        buy_tax_percent: 0.0, // Solana doesn't have built-in tax
-        sell_tax_percent: 0.0,
-        total_loss_percent: 0.0,
+        risk_factors: cap_risk_factors(
+            &reasons.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+            state.config.max_risk_factors,
+        ),
         reason,
         simulation_latency_ms: start.elapsed().as_millis() as u64,
         price_usd,
@@ -940,6 +3666,10 @@ async fn handle_solana_token(
         volume_24h_usd,
         dex_name,
         pair_address,
+        rug_risk: Some(ml_result.rug_risk.min(100) as u8),
+        recommendation: Some(Recommendation::from_score(risk_score as u32)),
+        category: Some(RiskCategory::from_score(risk_score as u32)),
+        ..HoneypotCheckData::new(req.token_address.clone(), CHAIN_ID_SOLANA, req.profile)
     };
     
     info!("✅ Solana analysis complete: {} - Risk: {}/100", 
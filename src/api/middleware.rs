@@ -158,14 +158,19 @@ pub fn start_cleanup_task() {
     });
 }
 
+/// Paths served without API key or rate limit (health probes, OpenAPI document, Swagger UI)
+fn is_public_path(path: &str) -> bool {
+    matches!(path, "/health" | "/v1/health" | "/openapi.json" | "/docs")
+}
+
 /// API Key authentication middleware
 pub async fn auth_middleware(
     headers: HeaderMap,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Skip auth for health check
-    if request.uri().path() == "/health" || request.uri().path() == "/v1/health" {
+    // Skip auth for health check and API docs
+    if is_public_path(request.uri().path()) {
         return Ok(next.run(request).await);
     }
 
@@ -201,8 +206,8 @@ pub async fn rate_limit_middleware(
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Skip rate limiting for health check
-    if request.uri().path() == "/health" || request.uri().path() == "/v1/health" {
+    // Skip rate limiting for health check and API docs
+    if is_public_path(request.uri().path()) {
         return Ok(next.run(request).await);
     }
 
@@ -3,9 +3,11 @@
 
 pub mod handlers;
 pub mod middleware;
+pub mod openapi;
 pub mod routes;
 pub mod types;
 
 pub use middleware::start_cleanup_task;
+pub use openapi::ApiDoc;
 pub use routes::create_router;
 pub use types::*;
@@ -0,0 +1,183 @@
+//! OpenAPI document for the REST API
+//!
+//! Schemas are derived from `api/types.rs` (utoipa `ToSchema`), paths from the
+//! `#[utoipa::path]` annotations on the handlers. Served at `/openapi.json`,
+//! with Swagger UI at `/docs`.
+
+use axum::{response::Html, Json};
+use utoipa::OpenApi;
+
+use super::handlers;
+use super::types::*;
+use crate::core::ml_risk::{Recommendation, RiskCategory};
+use crate::core::risk_score::{EnsembleVerdict, ScoreBlend, ScoreContribution, ScoreSource};
+use crate::core::sell_trace::{FailedCall, SellTrace, TraceStep};
+use crate::providers::rpc::{ChainHealth, RpcHealthStatus};
+use crate::utils::cache::{CacheStats, HitRateSample};
+use crate::utils::failure_log::FailureRecord;
+use crate::utils::latency::{LatencyBucket, MethodLatency};
+use crate::utils::telemetry::{ReportData, TelemetryStats};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Ruster REVM API", description = "Honeypot detection and token risk scoring"),
+    paths(
+        handlers::health_check,
+        handlers::get_stats,
+        handlers::get_stats_by_chain,
+        handlers::get_report,
+        handlers::analyze_token,
+        handlers::check_honeypot,
+        handlers::check_honeypot_stream,
+        handlers::batch_analyze,
+        handlers::analyze_async,
+        handlers::get_analysis_result,
+        handlers::analyze_pair,
+        handlers::search_tokens,
+        handlers::analyze_all_chains,
+        handlers::get_new_pairs,
+        handlers::explain_loss,
+        handlers::get_latency_stats,
+        handlers::get_cache_stats,
+        handlers::purge_cache,
+        handlers::get_failures,
+        handlers::submit_feedback,
+    ),
+    components(schemas(
+        // Envelopes
+        ApiError,
+        HealthResponse,
+        StatsResponse,
+        ChainStatsResponse,
+        CacheStatsResponse,
+        LatencyStatsResponse,
+        CachePurgeResponse,
+        ReportResponse,
+        TokenAnalysisResponse,
+        HoneypotCheckResponse,
+        MinimalCheckResponse,
+        ProgressEvent,
+        BatchAnalysisResponse,
+        AsyncJobAcceptedResponse,
+        AsyncJobResultResponse,
+        PairAnalysisResponse,
+        TokenSearchResponse,
+        AllChainsAnalysisResponse,
+        NewPairsResponse,
+        ExplainLossResponse,
+        FailureLogResponse,
+        FeedbackResponse,
+        // Requests
+        TokenAnalysisRequest,
+        HoneypotCheckRequest,
+        BatchAnalysisRequest,
+        BatchToken,
+        SimDetailResponse,
+        FeedbackRequest,
+        AnalysisProfile,
+        OutputFormat,
+        ResponseFields,
+        // Payloads
+        HealthData,
+        ChainHealth,
+        RpcHealthStatus,
+        StatsData,
+        ChainStatsData,
+        TelemetryStats,
+        LatencyStatsData,
+        MethodLatency,
+        LatencyBucket,
+        CacheStatsData,
+        CacheStats,
+        HitRateSample,
+        CachePurgeData,
+        ReportData,
+        TokenAnalysisData,
+        RiskScoreResponse,
+        RiskComponentsResponse,
+        ScoreFactorResponse,
+        HoneypotCheckData,
+        MinimalCheckData,
+        Verdict,
+        EnsembleVerdict,
+        ScoreBlend,
+        ScoreContribution,
+        ScoreSource,
+        Recommendation,
+        RiskCategory,
+        SellTrace,
+        TraceStep,
+        FailedCall,
+        BatchAnalysisData,
+        BatchTokenResult,
+        AsyncJobAccepted,
+        AsyncJobResult,
+        PairAnalysisData,
+        PairTokenInfo,
+        TokenSearchData,
+        TokenSearchCandidate,
+        AllChainsAnalysisData,
+        ChainVerdict,
+        ChainCandidateVerdict,
+        NewPairData,
+        NewPairsData,
+        ExplainLossData,
+        TokenFlowResponse,
+        ValueLeakResponse,
+        FailureLogData,
+        FailureRecord,
+        FeedbackData,
+    )),
+    modifiers(&ApiKeyAuth),
+    security(("api_key" = [])),
+    tags(
+        (name = "analysis", description = "Honeypot and risk analysis"),
+        (name = "status", description = "Health, telemetry and reports"),
+        (name = "admin", description = "Operator endpoints (X-Admin-Key)"),
+    )
+)]
+pub struct ApiDoc;
+
+/// `X-API-Key` header scheme enforced by `auth_middleware`
+struct ApiKeyAuth;
+
+impl utoipa::Modify for ApiKeyAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+    }
+}
+
+/// GET /openapi.json
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// GET /docs - Swagger UI (assets from the swagger-ui CDN) over `/openapi.json`
+pub async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI_HTML)
+}
+
+const SWAGGER_UI_HTML: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>Ruster REVM API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+    };
+  </script>
+</body>
+</html>
+"#;
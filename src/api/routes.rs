@@ -1,46 +1,147 @@
 //! API Route Configuration
 
 use axum::{
+    error_handling::HandleErrorLayer,
+    http::{HeaderName, HeaderValue, Method, StatusCode},
     middleware,
     routing::{get, post},
-    Router,
+    BoxError, Json, Router,
 };
 use std::sync::Arc;
+use std::time::Duration;
+use tower::{timeout::TimeoutLayer, ServiceBuilder};
 use tower_http::{
     compression::CompressionLayer,
-    cors::{Any, CorsLayer},
+    cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer},
     trace::TraceLayer,
 };
+use tracing::warn;
 
 use super::handlers::{self, AppState};
 use super::middleware::{auth_middleware, logging_middleware, rate_limit_middleware};
+use super::openapi;
+use super::types::{ApiError, ApiResponse};
+use crate::models::config::SentryConfig;
+
+/// Build the CORS layer from `SentryConfig` ("*" in any list = allow any)
+pub fn build_cors_layer(config: &SentryConfig) -> CorsLayer {
+    let is_any = |list: &[String]| list.iter().any(|v| v == "*");
+
+    let origins: AllowOrigin = if is_any(&config.cors_allowed_origins) {
+        Any.into()
+    } else {
+        AllowOrigin::list(config.cors_allowed_origins.iter().filter_map(|o| {
+            HeaderValue::from_str(o)
+                .map_err(|_| warn!("⚠️ Ignoring invalid CORS origin: {}", o))
+                .ok()
+        }))
+    };
+
+    let methods: AllowMethods = if is_any(&config.cors_allowed_methods) {
+        Any.into()
+    } else {
+        AllowMethods::list(config.cors_allowed_methods.iter().filter_map(|m| {
+            Method::from_bytes(m.to_uppercase().as_bytes())
+                .map_err(|_| warn!("⚠️ Ignoring invalid CORS method: {}", m))
+                .ok()
+        }))
+    };
+
+    let headers: AllowHeaders = if is_any(&config.cors_allowed_headers) {
+        Any.into()
+    } else {
+        AllowHeaders::list(config.cors_allowed_headers.iter().filter_map(|h| {
+            HeaderName::from_bytes(h.to_lowercase().as_bytes())
+                .map_err(|_| warn!("⚠️ Ignoring invalid CORS header: {}", h))
+                .ok()
+        }))
+    };
+
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(methods)
+        .allow_headers(headers)
+}
+
+/// Map a timed-out (or otherwise failed) handler into the standard error envelope
+async fn handle_timeout_error(
+    timeout: Duration,
+    err: BoxError,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let latency_ms = timeout.as_secs_f64() * 1000.0;
+    if err.is::<tower::timeout::error::Elapsed>() {
+        warn!("⏱️ Request exceeded {}s deadline", timeout.as_secs());
+        (
+            StatusCode::GATEWAY_TIMEOUT,
+            Json(ApiResponse::error(ApiError::gateway_timeout(timeout.as_secs()), latency_ms)),
+        )
+    } else {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(ApiError::internal(err.to_string()), latency_ms)),
+        )
+    }
+}
 
 /// Create the API router with all routes and middleware
 pub fn create_router(state: Arc<AppState>) -> Router {
-    // CORS configuration
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
+    // CORS configuration (driven by SentryConfig / CORS_* env vars)
+    let cors = build_cors_layer(&state.config);
+
+    // Hard upper bound on response time (504 in ApiResponse envelope)
+    let request_timeout = state.config.request_timeout;
+    let timeout = ServiceBuilder::new()
+        .layer(HandleErrorLayer::new(move |err: BoxError| {
+            handle_timeout_error(request_timeout, err)
+        }))
+        .layer(TimeoutLayer::new(request_timeout));
 
     // API v1 routes
     let api_v1 = Router::new()
         // Health & Status
         .route("/health", get(handlers::health_check))
         .route("/stats", get(handlers::get_stats))
+        .route("/stats/by-chain", get(handlers::get_stats_by_chain))
+        .route("/stats/latency", get(handlers::get_latency_stats))
+        .route("/cache/stats", get(handlers::get_cache_stats))
+        .route("/report", get(handlers::get_report))
         // Token Analysis
         .route("/analyze/token", post(handlers::analyze_token))
-        .route("/honeypot/check", post(handlers::check_honeypot))
+        .route("/honeypot/check", post(handlers::check_honeypot_formatted))
+        // Same check as server-sent events: per-phase progress, then the result
+        .route("/honeypot/check/stream", post(handlers::check_honeypot_stream))
         // Batch Analysis (NEW!)
-        .route("/analyze/batch", post(handlers::batch_analyze));
+        .route("/analyze/batch", post(handlers::batch_analyze_formatted))
+        // Async Analysis (job id + polling, for analyses longer than the request timeout)
+        .route("/analyze/async", post(handlers::analyze_async))
+        .route("/analyze/result/:job_id", get(handlers::get_analysis_result))
+        // Pair Analysis (non-base token of a V2 pair)
+        .route("/pair/:chain/:pair_address", get(handlers::analyze_pair))
+        // Same address on every supported chain, safest first
+        .route("/analyze/all-chains/:address", get(handlers::analyze_all_chains))
+        // Live feed of freshly created pairs (PairCreated scanner, NEW_PAIRS_CHAINS)
+        .route("/new-pairs/:chain_id", get(handlers::get_new_pairs))
+        // Symbol / name search (ranked candidates, optional verdict of the best)
+        .route("/search", get(handlers::search_tokens))
+        // Post-mortem of a historical victim transaction
+        .route("/explain/:tx_hash", get(handlers::explain_loss))
+        // Operator debugging (X-Admin-Key)
+        .route("/debug/failures", get(handlers::get_failures))
+        .route("/cache/purge", post(handlers::purge_cache))
+        // ML feedback loop (X-Admin-Key)
+        .route("/feedback", post(handlers::submit_feedback));
 
     // Build full router
     Router::new()
         .nest("/v1", api_v1)
         // Also expose at root for convenience
         .route("/health", get(handlers::health_check))
+        // API contract (OpenAPI 3) + Swagger UI
+        .route("/openapi.json", get(openapi::openapi_json))
+        .route("/docs", get(openapi::swagger_ui))
         .with_state(state)
         // Middleware (order matters - bottom runs first)
+        .layer(timeout)
         .layer(CompressionLayer::new())
         .layer(TraceLayer::new_for_http())
         .layer(cors)
@@ -1,10 +1,48 @@
 //! API Request/Response Types
 
-use crate::core::risk_score::RiskScore;
+use crate::core::honeypot::{ExitPoint, HoneypotResult, SimDetail, SummaryFacts};
+use crate::core::ml_risk::{Recommendation, RiskCategory};
+use crate::core::pipeline::{AnalysisPipeline, Stage};
+use crate::core::risk_score::{EnsembleVerdict, RiskScore, ScoreBlend};
+use crate::core::sell_trace::SellTrace;
+use crate::models::types::RiskLevel;
+use crate::providers::rpc::ChainHealth;
+use crate::providers::trace::LossExplanation;
+use crate::utils::cache::{CacheStats, HitRateSample};
+use crate::utils::chains::parse_chain;
+use crate::utils::constants::{get_chain_name, get_native_symbol, unix_timestamp};
+use crate::utils::failure_log::FailureRecord;
+use crate::utils::latency::MethodLatency;
+use crate::utils::telemetry::{ReportData, TelemetryStats};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
 
 /// API Response wrapper
-#[derive(Debug, Serialize)]
+/// Error responses carry `error` and no `data`
+#[derive(Debug, Serialize, ToSchema)]
+#[aliases(
+    HealthResponse = ApiResponse<HealthData>,
+    StatsResponse = ApiResponse<StatsData>,
+    ChainStatsResponse = ApiResponse<ChainStatsData>,
+    CacheStatsResponse = ApiResponse<CacheStatsData>,
+    LatencyStatsResponse = ApiResponse<LatencyStatsData>,
+    CachePurgeResponse = ApiResponse<CachePurgeData>,
+    ReportResponse = ApiResponse<ReportData>,
+    TokenAnalysisResponse = ApiResponse<TokenAnalysisData>,
+    HoneypotCheckResponse = ApiResponse<HoneypotCheckData>,
+    MinimalCheckResponse = ApiResponse<MinimalCheckData>,
+    BatchAnalysisResponse = ApiResponse<BatchAnalysisData>,
+    AsyncJobAcceptedResponse = ApiResponse<AsyncJobAccepted>,
+    AsyncJobResultResponse = ApiResponse<AsyncJobResult>,
+    PairAnalysisResponse = ApiResponse<PairAnalysisData>,
+    TokenSearchResponse = ApiResponse<TokenSearchData>,
+    AllChainsAnalysisResponse = ApiResponse<AllChainsAnalysisData>,
+    NewPairsResponse = ApiResponse<NewPairsData>,
+    ExplainLossResponse = ApiResponse<ExplainLossData>,
+    FailureLogResponse = ApiResponse<FailureLogData>,
+    FeedbackResponse = ApiResponse<FeedbackData>
+)]
 pub struct ApiResponse<T: Serialize> {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -40,7 +78,7 @@ impl ApiResponse<()> {
 }
 
 /// API Error
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiError {
     pub code: String,
     pub message: String,
@@ -81,6 +119,14 @@ impl ApiError {
         }
     }
 
+    pub fn gateway_timeout(timeout_secs: u64) -> Self {
+        Self {
+            code: "GATEWAY_TIMEOUT".to_string(),
+            message: format!("Request exceeded the {}s deadline", timeout_secs),
+            details: None,
+        }
+    }
+
     pub fn not_found(message: impl Into<String>) -> Self {
         Self {
             code: "NOT_FOUND".to_string(),
@@ -94,30 +140,29 @@ impl ApiError {
 // Token Analysis
 // ============================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct TokenAnalysisRequest {
     pub token_address: String,
-    #[serde(default = "default_test_amount")]
-    pub test_amount_eth: String,
+    /// Native amount to simulate with (default: the chain's `default_test_amount`)
+    /// Decimal ("0.1") or raw wei ("100000000000000000wei"); malformed amounts are rejected
+    #[serde(default)]
+    pub test_amount_eth: Option<String>,
     #[serde(default = "default_chain_id")]
     pub chain_id: u64,
 }
 
-fn default_test_amount() -> String {
-    "0.1".to_string()
-}
 fn default_chain_id() -> u64 {
     1
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TokenAnalysisData {
     pub token_address: String,
     pub risk_score: RiskScoreResponse,
     pub chain_id: u64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RiskScoreResponse {
     pub total: u8,
     pub confidence: u8,
@@ -129,7 +174,7 @@ pub struct RiskScoreResponse {
     pub breakdown: Vec<ScoreFactorResponse>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RiskComponentsResponse {
     pub honeypot: u8,
     pub tax: u8,
@@ -138,7 +183,7 @@ pub struct RiskComponentsResponse {
     pub mev_exposure: u8,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ScoreFactorResponse {
     pub name: String,
     pub score: u8,
@@ -183,22 +228,258 @@ impl From<RiskScore> for RiskScoreResponse {
     }
 }
 
+// ============================================
+// Analysis Profile
+// ============================================
+
+/// Latency/accuracy tradeoff, selected per request (`"profile": "fast"`)
+///
+/// | Profile    | Subsystems                                         | Target  |
+/// |------------|----------------------------------------------------|---------|
+/// | `fast`     | cache + RPC quote round-trip only (no bytecode)    | < 1s    |
+/// | `standard` | + bytecode scan + token metadata + market data     | < 3s    |
+/// | `thorough` | + REVM sell trace + ML liquidity (rug) scoring     | < 10s   |
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalysisProfile {
+    /// Wallet pre-flight: sub-second answer
+    Fast,
+    /// Default pipeline
+    #[default]
+    Standard,
+    /// Due diligence: everything we have
+    Thorough,
+}
+
+impl AnalysisProfile {
+    /// Fetch name/symbol/decimals via RPC
+    pub fn fetches_token_info(&self) -> bool {
+        *self != AnalysisProfile::Fast
+    }
+
+    /// Fetch DexScreener price/liquidity/volume
+    pub fn fetches_market_data(&self) -> bool {
+        *self != AnalysisProfile::Fast
+    }
+
+    /// Attach the REVM opcode trace of the sell
+    pub fn runs_sell_trace(&self) -> bool {
+        *self == AnalysisProfile::Thorough
+    }
+
+    /// Run ML liquidity scoring (rug_risk)
+    pub fn runs_ml(&self) -> bool {
+        *self == AnalysisProfile::Thorough
+    }
+
+    /// Detector stages: fast skips the bytecode fetch (and the scan that needs it)
+    pub fn pipeline(&self) -> AnalysisPipeline {
+        match self {
+            AnalysisProfile::Fast => AnalysisPipeline::full()
+                .without(Stage::FetchBytecode)
+                .without(Stage::ScanStatic),
+            AnalysisProfile::Standard | AnalysisProfile::Thorough => AnalysisPipeline::full(),
+        }
+    }
+
+    /// Documented latency target in milliseconds
+    pub fn latency_target_ms(&self) -> u64 {
+        match self {
+            AnalysisProfile::Fast => 1_000,
+            AnalysisProfile::Standard => 3_000,
+            AnalysisProfile::Thorough => 10_000,
+        }
+    }
+}
+
 // ============================================
 // Honeypot Check
 // ============================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct HoneypotCheckRequest {
     pub token_address: String,
-    #[serde(default = "default_test_amount")]
-    pub test_amount_eth: String,
+    /// Native amount to simulate with (default: the chain's `default_test_amount`)
+    /// Decimal ("0.1") or raw wei ("100000000000000000wei"); malformed amounts are rejected
+    #[serde(default)]
+    pub test_amount_eth: Option<String>,
     /// Chain ID (1 = Ethereum, 56 = BSC, 137 = Polygon, etc.)
     /// Default: 0 (auto-detect via DexScreener)
     #[serde(default)]
     pub chain_id: u64,
+    /// Chain by name or alias ("base", "bsc", "mainnet", ...), takes precedence over `chain_id`
+    #[serde(default)]
+    pub chain: Option<String>,
+    /// Analysis profile: "fast" | "standard" (default) | "thorough"
+    #[serde(default)]
+    pub profile: AnalysisProfile,
+    /// Decimals override for tokens without a usable decimals() (0-36)
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Fetch DexScreener price/liquidity/volume and DEX discovery (default: true)
+    /// `false` returns the pure simulation verdict without aggregator round trips
+    /// (chain auto-detect with `chain_id` 0 still queries DexScreener)
+    #[serde(default = "default_include_market_data")]
+    pub include_market_data: bool,
+}
+
+fn default_include_market_data() -> bool {
+    true
 }
 
-#[derive(Debug, Serialize)]
+impl HoneypotCheckRequest {
+    /// Market data is fetched only if requested and the profile allows it
+    pub fn fetches_market_data(&self) -> bool {
+        self.include_market_data && self.profile.fetches_market_data()
+    }
+
+    /// `chain` parsed to an id if given, else `chain_id`
+    pub fn resolved_chain_id(&self) -> Result<u64, String> {
+        resolve_chain(self.chain.as_deref(), self.chain_id)
+    }
+}
+
+/// Chain id from an optional `"chain"` name / alias, falling back to the numeric id
+fn resolve_chain(chain: Option<&str>, chain_id: u64) -> Result<u64, String> {
+    match chain {
+        Some(name) => parse_chain(name).ok_or_else(|| format!("Unknown chain: {}", name)),
+        None => Ok(chain_id),
+    }
+}
+
+/// Query flags for `/honeypot/check`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HoneypotTraceQuery {
+    /// Attach the REVM opcode trace of the sell (`?trace=true`, expensive)
+    #[serde(default)]
+    pub trace: bool,
+    /// Analyze state at a historical block (`?block=18000000`) instead of latest
+    #[serde(default)]
+    pub block: Option<u64>,
+    /// Run the ML scorer and return `recommendation` / `category` (`?recommendation=true`)
+    /// Always on for the thorough profile
+    #[serde(default)]
+    pub recommendation: bool,
+    /// Response format (`?format=text` for a one-line verdict)
+    #[serde(default)]
+    pub format: OutputFormat,
+    /// Response fields (`?fields=minimal` for verdict, score and reason code only)
+    #[serde(default)]
+    pub fields: ResponseFields,
+    /// Re-run the sell on a honeypot verdict and return Unverified unless both runs
+    /// agree (`?confirm=true`); cached verdicts are returned as is
+    #[serde(default)]
+    pub confirm: bool,
+    /// Attach the raw round-trip amounts and final balances (`?detail=true`)
+    #[serde(default)]
+    pub detail: bool,
+    /// Attach `exit_curve`: forked sells of 1-25% of a holding (`?exit_curve=true`)
+    #[serde(default)]
+    pub exit_curve: bool,
+}
+
+/// Which fields `/honeypot/check` returns, selected per request (`?fields=`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseFields {
+    /// Full `HoneypotCheckData`
+    #[default]
+    Full,
+    /// `MinimalCheckData` only - market data, token info and traces are never fetched
+    Minimal,
+}
+
+/// Response encoding, selected per request (`?format=`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Compact JSON envelope
+    #[default]
+    Json,
+    /// Indented JSON envelope
+    Pretty,
+    /// Plain-text verdict line(s) for CLI / chat bots
+    Text,
+}
+
+/// `?format=` for endpoints without other query flags
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FormatQuery {
+    #[serde(default)]
+    pub format: OutputFormat,
+}
+
+/// Raw amounts of the simulated round trip (decimal strings: tokens in raw units, native in wei)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SimDetailResponse {
+    /// Tokens credited by the buy
+    pub tokens_bought: String,
+    /// Native wei the sell returned ("0" when the sell did not go through)
+    pub eth_returned: String,
+    /// Test account's token balance after the sell
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_token_balance: Option<String>,
+    /// Test account's native balance (wei) after the sell
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_eth_balance: Option<String>,
+}
+
+impl From<&SimDetail> for SimDetailResponse {
+    fn from(detail: &SimDetail) -> Self {
+        Self {
+            tokens_bought: detail.tokens_bought.to_string(),
+            eth_returned: detail.eth_returned.to_string(),
+            final_token_balance: detail.final_token_balance.map(|balance| balance.to_string()),
+            final_eth_balance: detail.final_eth_balance.map(|balance| balance.to_string()),
+        }
+    }
+}
+
+/// Client-facing verdict: definitive only when confidence clears `MIN_CONFIDENCE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum Verdict {
+    Honeypot,
+    Safe,
+    /// Evidence too weak (mocked sim, failed reverse quote, no simulation) to call either way
+    Unverified,
+}
+
+impl Verdict {
+    /// Gate a raw `is_honeypot` on the confidence behind it
+    pub fn gate(is_honeypot: bool, confidence: u8, min_confidence: u8) -> Self {
+        match (confidence >= min_confidence, is_honeypot) {
+            (false, _) => Verdict::Unverified,
+            (true, true) => Verdict::Honeypot,
+            (true, false) => Verdict::Safe,
+        }
+    }
+
+    /// Client-facing `is_honeypot`: None when Unverified (single check and batch alike)
+    pub fn is_honeypot(&self) -> Option<bool> {
+        match self {
+            Verdict::Honeypot => Some(true),
+            Verdict::Safe => Some(false),
+            Verdict::Unverified => None,
+        }
+    }
+
+    /// Display label: the verdict, or the risk level of a sellable token so a
+    /// high score never reads as safe
+    pub fn summary_label(&self, risk_score: u8) -> String {
+        match self {
+            Verdict::Honeypot => "🚨 HONEYPOT DETECTED".to_string(),
+            Verdict::Unverified => "⚠️ UNVERIFIED".to_string(),
+            Verdict::Safe => {
+                let level = RiskLevel::from_score(risk_score);
+                format!("{} {}", level.emoji(), level.as_str())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct HoneypotCheckData {
     pub token_address: String,
     /// Token name (e.g., "Tether USD")
@@ -216,13 +497,91 @@ pub struct HoneypotCheckData {
     pub chain_name: String,
     /// Native token symbol (e.g., "ETH", "BNB")
     pub native_symbol: String,
-    pub is_honeypot: bool,
+    /// Omitted when the verdict is `Unverified` (see `confidence`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_honeypot: Option<bool>,
+    /// Honeypot / Safe, or Unverified when `confidence` is below `MIN_CONFIDENCE`
+    pub verdict: Verdict,
+    /// How much real execution backs the verdict (0-100)
+    pub confidence: u8,
     pub risk_score: u8,
     pub buy_success: bool,
     pub sell_success: bool,
     pub buy_tax_percent: f64,
     pub sell_tax_percent: f64,
     pub total_loss_percent: f64,
+    /// Round-trip loss to token taxes alone, gas excluded (same as `total_loss_percent`)
+    pub tax_loss_pct: f64,
+    /// Round-trip loss including the simulated gas cost (buy + approve + sell). Only the forked
+    /// simulation measures gas - omitted on eth_call quotes rather than repeating `tax_loss_pct`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_loss_pct: Option<f64>,
+    /// transferFrom beyond the approved allowance reverts (false = approvals can be bypassed)
+    pub allowance_enforced: bool,
+    /// Sell only reverts in the buy block (anti-bot delay, not a honeypot)
+    pub same_block_sell_blocked: bool,
+    /// Blocks after the buy before the sell first went through (timed sell lock). Only the
+    /// forked simulation (`/analyze/batch`) executes delayed sells - always omitted on eth_call quotes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sell_lock_blocks: Option<u64>,
+    /// Sell reverted right after reading a seller-keyed storage flag (runtime blacklist)
+    pub dynamic_blacklist_detected: bool,
+    /// Transfers into the pair revert while wallet-to-wallet transfers succeed (sell block)
+    pub transfer_to_pair_blocked: bool,
+    /// Token accepted an EIP-2612 permit (REVM permit check only)
+    pub permit_supported: bool,
+    /// approve() and permit() approvals disagree on whether the sell goes through
+    pub permit_mismatch: bool,
+    /// Sell succeeds at one gas price / basefee and reverts at the other (REVM gas price check only)
+    pub gas_price_gated: bool,
+    /// Holder credited outside the router (airdrop / transfer) cannot sell (REVM direct holder check only)
+    pub direct_holder_sell_blocked: bool,
+    /// Transfer to an EOA goes through, transfer to a contract reverts (REVM contract recipient check only)
+    pub contract_recipient_blocked: bool,
+    /// Seller's balance grew by the router-reported sell proceeds (false = output diverted elsewhere)
+    pub proceeds_received: bool,
+    /// Sell used 5x the buy's gas or more (restriction logic on the sell path only).
+    /// Omitted when the gas of both legs wasn't measured (eth_call quotes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asymmetric_gas: Option<bool>,
+    /// Sell passed, but enough risk dimensions co-occur (access control, sell tax,
+    /// restricted sell path, deceptive transfer) to raise the risk score; not a honeypot verdict
+    pub likely_honeypot: bool,
+    /// Measured buy/sell tax exceeds what the token's public tax getters advertise
+    pub declared_vs_measured_tax_mismatch: bool,
+    /// Most severe risk factors, capped at `MAX_RISK_FACTORS` (+ an "and X more" line)
+    pub risk_factors: Vec<String>,
+    /// Provenance caveats, e.g. "simulation used mock router bytecode" or "reverse quote unavailable"
+    pub warnings: Vec<String>,
+    /// Transfer fee class when the token is sellable: "reflection" | "high_tax"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_class: Option<String>,
+    /// Measured fee per transfer (%) behind `fee_class`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_percent: Option<f64>,
+    /// "erc20" | "erc777" | "erc1363" | "rebasing" (rebasing = loss percentages unreliable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_standard: Option<String>,
+    /// 4-byte selector of the revert that failed the buy / approve (`0x08c379a0` = Error(string))
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_selector: Option<String>,
+    /// Decoded revert: require message, panic, or custom error signature (`TradingNotOpen()`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// Buy tax (%) advertised by a public getter (`buyTax()`, `_taxFee()`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declared_buy_tax_percent: Option<f64>,
+    /// Sell tax (%) advertised by a public getter (`sellTax()`, `_taxFee()`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declared_sell_tax_percent: Option<f64>,
+    /// On-chain reserves imply a price far from DexScreener's (possibly manipulated pair)
+    pub reserve_price_mismatch: bool,
+    /// Router the buy/sell simulation actually executed through
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simulated_router: Option<String>,
+    /// DEX name behind `simulated_router`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simulated_dex: Option<String>,
     pub reason: String,
     pub simulation_latency_ms: u64,
     
@@ -244,29 +603,771 @@ pub struct HoneypotCheckData {
     /// Pair address on DEX
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pair_address: Option<String>,
+    /// Every chain the address trades on with meaningful liquidity, when auto-detect (`chain_id` 0)
+    /// found several; this result is for the most liquid, pass another `chain_id` to analyze it
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<ChainCandidateVerdict>,
+    /// Estimated holders from Transfer logs since deployment (thorough profile / ML runs only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder_count: Option<u32>,
+    /// `holder_count` only covers the last `HOLDER_SCAN_MAX_BLOCKS` blocks - a lower bound,
+    /// not scored as low holders by the ML model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder_count_truncated: Option<bool>,
+    /// EIP-1967 implementation behind the token, when it is an upgradeable proxy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_implementation: Option<String>,
+    /// Proxy implementation changed within the last 7 days
+    pub recently_upgraded: bool,
+    /// Rug-pull readiness of liquidity (0-100), separate from the honeypot verdict
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rug_risk: Option<u8>,
+    /// Simulation vs ML view of `score_blend` (thorough profile; `score` equals `risk_score`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ensemble: Option<EnsembleVerdict>,
+    /// `risk_score` as a confidence-weighted blend, with each subsystem's contribution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_blend: Option<ScoreBlend>,
+    /// Actionable advice for `risk_score`: "Buy" | "Caution" | "Avoid" | "DoNotTrade" (ML runs only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommendation: Option<Recommendation>,
+    /// Risk category for `risk_score`: "Safe" | "Low" | "Medium" | "High" | "Critical" (ML runs only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<RiskCategory>,
+    /// REVM opcode trace of the sell (only with `?trace=true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sell_trace: Option<SellTrace>,
+    /// Loss vs spot when selling 1% / 5% / 10% / 25% of a holding of 1% of supply,
+    /// simulated on a fork (only with `?exit_curve=true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_curve: Option<Vec<ExitPoint>>,
+    /// No pair to simulate against: a wallet-to-wallet transfer on a fork went through
+    /// ("not listed yet") or not ("transfers are blocked"). Omitted when a pair was simulated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transferable: Option<bool>,
+    /// Revert reason of the failed wallet-to-wallet transfer (only with `transferable: false`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_revert_reason: Option<String>,
+    /// Raw round-trip amounts to check the tax / loss math against (only with `?detail=true`).
+    /// Quoted amounts without final balances on the eth_call path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sim_detail: Option<SimDetailResponse>,
+    /// Analysis profile that produced this result
+    pub profile: AnalysisProfile,
+    /// Verdict source when no simulation ran ("canonical" = known-safe token, "cache")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Block the verdict was computed at (absent when no simulation ran)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Unix timestamp (seconds) when the verdict was computed (original time for cached results)
+    pub analyzed_at: u64,
+}
+
+impl HoneypotCheckData {
+    /// Unverified response with nothing simulated or fetched yet; sites fill in what they
+    /// know with struct update syntax (`HoneypotCheckData { reason, ..Self::new(..) }`)
+    pub fn new(token_address: String, chain_id: u64, profile: AnalysisProfile) -> Self {
+        Self {
+            token_address,
+            token_name: None,
+            token_symbol: None,
+            token_decimals: None,
+            chain_id,
+            chain_name: get_chain_name(chain_id).to_string(),
+            native_symbol: get_native_symbol(chain_id).to_string(),
+            is_honeypot: None,
+            verdict: Verdict::Unverified,
+            confidence: 0,
+            risk_score: 0,
+            buy_success: false,
+            sell_success: false,
+            buy_tax_percent: 0.0,
+            sell_tax_percent: 0.0,
+            total_loss_percent: 0.0,
+            tax_loss_pct: 0.0,
+            net_loss_pct: None,
+            allowance_enforced: true,
+            same_block_sell_blocked: false,
+            sell_lock_blocks: None,
+            dynamic_blacklist_detected: false,
+            transfer_to_pair_blocked: false,
+            permit_supported: false,
+            permit_mismatch: false,
+            gas_price_gated: false,
+            direct_holder_sell_blocked: false,
+            contract_recipient_blocked: false,
+            proceeds_received: true,
+            asymmetric_gas: None,
+            likely_honeypot: false,
+            declared_vs_measured_tax_mismatch: false,
+            risk_factors: Vec::new(),
+            warnings: Vec::new(),
+            fee_class: None,
+            fee_percent: None,
+            token_standard: None,
+            revert_selector: None,
+            revert_reason: None,
+            declared_buy_tax_percent: None,
+            declared_sell_tax_percent: None,
+            reserve_price_mismatch: false,
+            simulated_router: None,
+            simulated_dex: None,
+            reason: String::new(),
+            simulation_latency_ms: 0,
+            price_usd: None,
+            liquidity_usd: None,
+            volume_24h_usd: None,
+            dex_name: None,
+            pair_address: None,
+            candidates: Vec::new(),
+            holder_count: None,
+            holder_count_truncated: None,
+            proxy_implementation: None,
+            recently_upgraded: false,
+            rug_risk: None,
+            ensemble: None,
+            score_blend: None,
+            recommendation: None,
+            category: None,
+            sell_trace: None,
+            exit_curve: None,
+            transferable: None,
+            transfer_revert_reason: None,
+            sim_detail: None,
+            profile,
+            source: None,
+            block_number: None,
+            analyzed_at: unix_timestamp(),
+        }
+    }
+
+    /// Response for a simulation result: verdict gated on `min_confidence`, sim fields copied
+    /// over, risk score / market data / enrichment left to the caller
+    pub fn from_result(
+        result: &HoneypotResult,
+        token_address: String,
+        chain_id: u64,
+        profile: AnalysisProfile,
+        min_confidence: u8,
+        max_risk_factors: usize,
+    ) -> Self {
+        // An unconfirmed or low-confidence honeypot is Unverified, not a honeypot
+        let verdict = Verdict::gate(
+            result.is_honeypot || result.sell_reverted,
+            result.confidence,
+            min_confidence,
+        );
+        Self {
+            is_honeypot: verdict.is_honeypot(),
+            verdict,
+            confidence: result.confidence,
+            buy_success: result.buy_success,
+            sell_success: result.sell_success,
+            buy_tax_percent: result.buy_tax_percent,
+            sell_tax_percent: result.sell_tax_percent,
+            total_loss_percent: result.total_loss_percent,
+            tax_loss_pct: result.total_loss_percent,
+            net_loss_pct: result.net_loss_percent,
+            allowance_enforced: result.allowance_enforced,
+            same_block_sell_blocked: result.same_block_sell_blocked,
+            sell_lock_blocks: result.sell_lock_blocks,
+            dynamic_blacklist_detected: result.dynamic_blacklist_detected,
+            transfer_to_pair_blocked: result.transfer_to_pair_blocked,
+            permit_supported: result.permit_supported,
+            permit_mismatch: result.permit_mismatch,
+            gas_price_gated: result.gas_price_gated,
+            direct_holder_sell_blocked: result.direct_holder_sell_blocked,
+            contract_recipient_blocked: result.contract_recipient_blocked,
+            proceeds_received: result.proceeds_received,
+            asymmetric_gas: result.sell_buy_gas_ratio.map(|_| result.asymmetric_gas),
+            likely_honeypot: result.likely_honeypot,
+            declared_vs_measured_tax_mismatch: result.declared_vs_measured_tax_mismatch,
+            risk_factors: result.capped_risk_factors(max_risk_factors),
+            warnings: result.warnings.clone(),
+            fee_class: result.fee_class.map(|f| f.label().to_string()),
+            fee_percent: result.fee_class.map(|f| f.fee_percent()),
+            token_standard: result.token_standard.map(|t| t.label().to_string()),
+            revert_selector: result.revert.as_ref().and_then(|r| r.selector.clone()),
+            revert_reason: result.revert.as_ref().map(|r| r.message.clone()),
+            declared_buy_tax_percent: result.declared_tax.buy_percent,
+            declared_sell_tax_percent: result.declared_tax.sell_percent,
+            simulated_router: result.simulated_router.map(|r| format!("{:?}", r)),
+            simulated_dex: result.simulated_dex.clone(),
+            reason: result.reason.clone(),
+            simulation_latency_ms: result.latency_ms,
+            block_number: result.block_number,
+            analyzed_at: result.analyzed_at,
+            ..Self::new(token_address, chain_id, profile)
+        }
+    }
+
+    /// One-line human verdict: `HoneypotResult::summary` details behind a label keyed off
+    /// the verdict and risk score, plus token and score
+    pub fn summary(&self) -> String {
+        let token = self.token_symbol.as_deref().unwrap_or(&self.token_address);
+        let facts = SummaryFacts {
+            reason: &self.reason,
+            buy_success: self.buy_success,
+            sell_success: self.sell_success,
+            buy_tax_percent: self.buy_tax_percent,
+            sell_tax_percent: self.sell_tax_percent,
+            total_loss_percent: self.total_loss_percent,
+            latency_ms: self.simulation_latency_ms,
+        };
+        format!(
+            "{} | {} on {} | Risk: {}/100 | Confidence: {}% | {}",
+            self.verdict.summary_label(self.risk_score),
+            token,
+            self.chain_name,
+            self.risk_score,
+            self.confidence,
+            facts.details()
+        )
+    }
+
+    /// Stable machine-readable code for the verdict, most specific cause first
+    pub fn reason_code(&self) -> &'static str {
+        if self.source.as_deref() == Some("canonical") {
+            "CANONICAL"
+        } else if self.verdict == Verdict::Unverified {
+            "UNVERIFIED"
+        } else if self.transfer_to_pair_blocked {
+            "TRANSFER_TO_PAIR_BLOCKED"
+        } else if self.dynamic_blacklist_detected {
+            "BLACKLISTED"
+        } else if self.gas_price_gated {
+            "GAS_PRICE_GATED"
+        } else if self.direct_holder_sell_blocked {
+            "DIRECT_HOLDER_BLOCKED"
+        } else if self.contract_recipient_blocked {
+            "CONTRACT_RECIPIENT_BLOCKED"
+        } else if !self.proceeds_received {
+            "PROCEEDS_DIVERTED"
+        } else if self.likely_honeypot {
+            "LIKELY_HONEYPOT"
+        } else if !self.buy_success {
+            "BUY_FAILED"
+        } else if self.verdict == Verdict::Honeypot && !self.sell_success {
+            "SELL_BLOCKED"
+        } else if self.verdict == Verdict::Honeypot {
+            "HIGH_TAX"
+        } else if self.declared_vs_measured_tax_mismatch {
+            "TAX_UNDERSTATED"
+        } else if self.same_block_sell_blocked {
+            "SAME_BLOCK_SELL_BLOCKED"
+        } else {
+            "OK"
+        }
+    }
+}
+
+/// One finished phase of a `/honeypot/check/stream` analysis (SSE `progress` event)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProgressEvent {
+    /// Pipeline stage ("fetch_bytecode", "sim_buy", ...) or handler phase ("market_data", "scoring", "trace")
+    pub phase: String,
+    /// Human-readable ("fetched bytecode", "buy simulated", "trace complete")
+    pub message: String,
+    /// Since the request arrived
+    pub elapsed_ms: f64,
+}
+
+/// Lean `/honeypot/check` payload for `?fields=minimal`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MinimalCheckData {
+    /// Omitted when the verdict is `Unverified`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_honeypot: Option<bool>,
+    pub verdict: Verdict,
+    pub risk_score: u8,
+    /// See `HoneypotCheckData::reason_code`: "OK" | "SELL_BLOCKED" | "HIGH_TAX" | "UNVERIFIED" | ...
+    pub reason_code: String,
+}
+
+impl MinimalCheckData {
+    /// One-line verdict for `?format=text`
+    pub fn summary(&self) -> String {
+        format!("{:?} | Risk: {}/100 | {}", self.verdict, self.risk_score, self.reason_code)
+    }
+}
+
+impl From<&HoneypotCheckData> for MinimalCheckData {
+    fn from(data: &HoneypotCheckData) -> Self {
+        Self {
+            is_honeypot: data.is_honeypot,
+            verdict: data.verdict,
+            risk_score: data.risk_score,
+            reason_code: data.reason_code().to_string(),
+        }
+    }
+}
+
+// ============================================
+// Audit Report (AUDIT_LOG_DIR)
+// ============================================
+
+/// Complete decision record of one simulated analysis, written per analysis in audit mode
+/// (`/honeypot/check` fresh or cached, and every batch token)
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisReport<V = HoneypotCheckData> {
+    pub inputs: AnalysisInputs,
+    /// Block the fork / quotes ran against (None for local REVM-only sims)
+    pub block_number: Option<u64>,
+    /// Answered from the result cache - `simulation` is the earlier run's
+    pub cached: bool,
+    pub simulation: SimulationRecord,
+    /// ML scoring (None unless the thorough profile or `?recommendation=true` ran it)
+    pub ml: Option<MlRecord>,
+    /// Final response (`HoneypotCheckData` / `BatchTokenResult`)
+    pub verdict: V,
+    /// Unix timestamp (seconds) the record was written
+    pub recorded_at: u64,
+}
+
+/// Request inputs after defaults and chain resolution - enough to replay the analysis
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisInputs {
+    pub token_address: String,
+    /// `chain_id` as requested (0 = auto-detect)
+    pub requested_chain_id: u64,
+    /// `chain` name as requested, if any
+    pub requested_chain: Option<String>,
+    /// Chain the analysis ran on
+    pub chain_id: u64,
+    pub test_amount_eth: f64,
+    pub profile: AnalysisProfile,
+    pub decimals: Option<u8>,
+    /// Historical block (`?block=`)
+    pub block: Option<u64>,
+    pub include_market_data: bool,
+    pub trace: bool,
+    pub recommendation: bool,
+    /// Router DexScreener pointed the simulation at
+    pub dexscreener_router: Option<String>,
+}
+
+/// Raw simulation outputs before response shaping
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationRecord {
+    pub buy_success: bool,
+    pub sell_success: bool,
+    pub sell_reverted: bool,
+    pub gas_used: u64,
+    /// Sell gas / buy gas (None unless both legs executed)
+    pub sell_buy_gas_ratio: Option<f64>,
+    /// Tax getters' values (%), compared against the measured buy/sell tax
+    pub declared_buy_tax_percent: Option<f64>,
+    pub declared_sell_tax_percent: Option<f64>,
+    pub confidence: u8,
+    /// Static scan / access control penalty
+    pub access_control_penalty: u8,
+    /// Every risk factor (static scan hits, sim checks, heuristics), uncapped
+    pub risk_factors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub latency_ms: u64,
+    /// Per-step amounts: tokens bought, native returned, final balances (None when mocked)
+    pub amounts: Option<SimDetailResponse>,
+}
+
+impl From<&HoneypotResult> for SimulationRecord {
+    fn from(result: &HoneypotResult) -> Self {
+        Self {
+            buy_success: result.buy_success,
+            sell_success: result.sell_success,
+            sell_reverted: result.sell_reverted,
+            gas_used: result.gas_used,
+            sell_buy_gas_ratio: result.sell_buy_gas_ratio,
+            declared_buy_tax_percent: result.declared_tax.buy_percent,
+            declared_sell_tax_percent: result.declared_tax.sell_percent,
+            confidence: result.confidence,
+            access_control_penalty: result.access_control_penalty,
+            risk_factors: result.risk_factors.clone(),
+            warnings: result.warnings.clone(),
+            latency_ms: result.latency_ms,
+            amounts: result.sim_detail.as_ref().map(SimDetailResponse::from),
+        }
+    }
+}
+
+/// ML inputs and outputs behind `ensemble` / `rug_risk`
+#[derive(Debug, Clone, Serialize)]
+pub struct MlRecord {
+    pub score: u8,
+    pub rug_risk: u8,
+    /// Weighted features that fired
+    pub features: Vec<String>,
+    pub age_hours: Option<u32>,
+    pub holder_count: Option<u32>,
+}
+
+// ============================================
+// Async Analysis (job + polling)
+// ============================================
+
+/// One side of a pair analyzed by `GET /pair/:chain/:pair_address`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PairTokenInfo {
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+    /// Wrapped native / stablecoin side of the pair
+    pub is_base: bool,
+}
+
+/// Response of `GET /pair/:chain/:pair_address`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PairAnalysisData {
+    pub pair_address: String,
+    pub chain_id: u64,
+    pub token0: PairTokenInfo,
+    pub token1: PairTokenInfo,
+    /// The non-base token the honeypot verdict applies to
+    pub analyzed_token: String,
+    pub verdict: HoneypotCheckData,
+}
+
+/// Query of `GET /search`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TokenSearchQuery {
+    /// Symbol or name to look up (e.g. `PEPE`)
+    pub q: String,
+    /// Only return tokens on this chain (0 = all chains)
+    #[serde(default)]
+    pub chain_id: u64,
+    /// Candidates returned (default 10, max 25)
+    #[serde(default = "default_search_limit")]
+    pub limit: usize,
+    /// Also run the honeypot check on the top-ranked candidate
+    #[serde(default)]
+    pub analyze: bool,
+}
+
+fn default_search_limit() -> usize {
+    10
+}
+
+/// A token matching a search, ranked by exact-symbol match then liquidity
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TokenSearchCandidate {
+    pub token_address: String,
+    /// None for chains the API cannot analyze
+    pub chain_id: Option<u64>,
+    pub chain_name: String,
+    pub token_name: Option<String>,
+    pub token_symbol: Option<String>,
+    /// Summed USD liquidity across the token's pairs
+    pub liquidity_usd: f64,
+    pub pair_count: usize,
+    pub best_pair_address: String,
+    pub best_dex: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_usd: Option<String>,
+    /// Symbol equals the query (case-insensitive)
+    pub exact_symbol_match: bool,
+}
+
+/// Response of `GET /search`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TokenSearchData {
+    pub query: String,
+    /// Best match first; several entries = ambiguous symbol, pick by address
+    pub candidates: Vec<TokenSearchCandidate>,
+    /// Honeypot verdict of `candidates[0]` (only with `analyze=true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analysis: Option<HoneypotCheckData>,
+}
+
+/// Query of `GET /new-pairs/:chain_id`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NewPairsQuery {
+    /// Pairs returned, most recent first (default 20, max 50)
+    #[serde(default = "default_new_pairs_limit")]
+    pub limit: usize,
+}
+
+fn default_new_pairs_limit() -> usize {
+    20
+}
+
+/// A pair the live PairCreated scanner detected, with a quick verdict
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NewPairData {
+    pub pair_address: String,
+    pub factory: String,
+    pub token0: String,
+    pub token1: String,
+    /// Non-base token the verdict applies to (None = neither side is a base token)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analyzed_token: Option<String>,
+    pub block_number: Option<u64>,
+    pub tx_hash: String,
+    /// Unix seconds the scanner received the event
+    pub detected_at: u64,
+    /// None = no base token, or the simulation failed or timed out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<Verdict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_score: Option<u8>,
+}
+
+/// Response of `GET /new-pairs/:chain_id`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NewPairsData {
+    pub chain_id: u64,
+    pub chain_name: String,
+    /// Pairs detected since startup, including ones evicted from the buffer
+    pub total_detected: u64,
+    /// Newest first
+    pub pairs: Vec<NewPairData>,
+}
+
+/// One chain's entry in `GET /analyze/all-chains/:address`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChainVerdict {
+    pub chain_id: u64,
+    pub chain_name: String,
+    /// A pool was found on this chain (quote succeeded or DexScreener reports liquidity)
+    pub has_liquidity: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liquidity_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<HoneypotCheckData>,
+    /// Why the check failed on this chain (no `verdict` then)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One chain an ambiguous auto-detect found, with its own verdict
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChainCandidateVerdict {
+    pub chain_id: u64,
+    pub chain_name: String,
+    /// Total DexScreener liquidity on the chain
+    pub liquidity_usd: f64,
+    /// None = the simulation on this chain failed or timed out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<Verdict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_score: Option<u8>,
+}
+
+/// Response of `GET /analyze/all-chains/:address`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AllChainsAnalysisData {
+    pub token_address: String,
+    /// Chain with liquidity, a `Safe` verdict and the lowest risk score (None = no such chain)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safest_chain_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safest_chain_name: Option<String>,
+    /// Every checked chain, safest first
+    pub chains: Vec<ChainVerdict>,
+}
+
+impl ChainVerdict {
+    /// Tradable here: liquidity and a definitive `Safe` verdict
+    pub fn is_tradable(&self) -> bool {
+        self.has_liquidity && self.verdict.as_ref().is_some_and(|v| v.verdict == Verdict::Safe)
+    }
+
+    /// Sort key, lower = safer: tradable first, then risk score, then deeper liquidity
+    fn rank(&self) -> (bool, u8, std::cmp::Reverse<u64>) {
+        (
+            !self.is_tradable(),
+            self.verdict.as_ref().map_or(u8::MAX, |v| v.risk_score),
+            std::cmp::Reverse(self.liquidity_usd.unwrap_or(0.0) as u64),
+        )
+    }
+}
+
+impl AllChainsAnalysisData {
+    /// Rank `chains` safest first and pick the safest tradable one
+    pub fn new(token_address: String, mut chains: Vec<ChainVerdict>) -> Self {
+        chains.sort_by_key(ChainVerdict::rank);
+        let safest = chains.first().filter(|c| c.is_tradable());
+        Self {
+            token_address,
+            safest_chain_id: safest.map(|c| c.chain_id),
+            safest_chain_name: safest.map(|c| c.chain_name.clone()),
+            chains,
+        }
+    }
+}
+
+/// Returned immediately by `POST /analyze/async`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AsyncJobAccepted {
+    pub job_id: String,
+    /// Always "pending" on submit
+    pub status: String,
+    /// Relative URL to poll for the result
+    pub poll_url: String,
+}
+
+/// Returned by `GET /analyze/result/:job_id`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AsyncJobResult {
+    pub job_id: String,
+    pub status: String, // "pending" | "done" | "failed"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<HoneypotCheckData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================
+// Debug: Failure Log
+// ============================================
+
+/// Query for `GET /debug/failures`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FailureLogQuery {
+    /// Maximum records returned, most recent first
+    #[serde(default = "default_failure_limit")]
+    pub limit: usize,
+}
+
+fn default_failure_limit() -> usize {
+    50
+}
+
+/// Returned by `GET /debug/failures`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FailureLogData {
+    /// Ring buffer size (FAILURE_LOG_SIZE)
+    pub capacity: usize,
+    /// Failures recorded since startup, including evicted ones
+    pub total_recorded: u64,
+    pub failures: Vec<FailureRecord>,
+}
+
+// ============================================
+// ML Feedback
+// ============================================
+
+/// Body of `POST /feedback`: ground truth for a previously analyzed token
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FeedbackRequest {
+    /// Token address as submitted to the analysis
+    pub token: String,
+    /// Chain id, name or alias ("1", "bsc", "binance", "solana", ...)
+    pub chain: String,
+    pub was_actually_honeypot: bool,
+}
+
+/// Returned by `POST /feedback`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeedbackData {
+    pub token: String,
+    pub chain_id: u64,
+    /// ML score recorded when the token was analyzed
+    pub predicted_score: u8,
+    pub was_actually_honeypot: bool,
+    /// Weighted features this report counted towards
+    pub counted_features: Vec<String>,
+    /// Weights that moved as a result (feature → new weight)
+    pub adjusted_weights: HashMap<String, f64>,
+    /// Adjusted weights were written to ML_WEIGHTS_PATH
+    pub persisted: bool,
 }
 
 // ============================================
 // Batch Analysis (NEW!)
 // ============================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BatchAnalysisRequest {
-    pub tokens: Vec<String>,
-    #[serde(default = "default_test_amount")]
-    pub test_amount_eth: String,
+    /// Addresses (`"0x..."`, on the request's chain) and / or `{"address", "chain_id" | "chain"}`
+    /// entries, so one batch can mix chains
+    pub tokens: Vec<BatchToken>,
+    /// Native amount to simulate with (default: the chain's `default_test_amount`)
+    /// Decimal ("0.1") or raw wei ("100000000000000000wei"); malformed amounts are rejected
+    #[serde(default)]
+    pub test_amount_eth: Option<String>,
+    /// Chain ID for all tokens (default: 1, 0 = auto-detect per token via DexScreener)
     #[serde(default = "default_chain_id")]
     pub chain_id: u64,
+    /// Chain by name or alias for all tokens ("base", "bsc", ...), takes precedence over `chain_id`
+    #[serde(default)]
+    pub chain: Option<String>,
     /// Max concurrent checks (default: 10, max: 50)
     #[serde(default = "default_concurrency")]
     pub concurrency: usize,
+    /// Per-token analysis timeout in seconds (default/max: BATCH_TOKEN_TIMEOUT_SECS)
+    /// Stragglers are reported as `error: "timeout"` instead of holding up the batch
+    #[serde(default)]
+    pub token_timeout_secs: Option<u64>,
+    /// Also sell through an EIP-2612 permit approval (forked simulation only)
+    #[serde(default)]
+    pub permit_check: bool,
+    /// Also sell at a low and a high gas price / basefee (forked simulation only)
+    #[serde(default)]
+    pub gas_price_check: bool,
+    /// Also sell from a holder credited by a balanceOf storage write, not a buy (forked simulation only)
+    #[serde(default)]
+    pub direct_holder_check: bool,
+    /// Also transfer the bought tokens to a contract and to an EOA (forked simulation only)
+    #[serde(default)]
+    pub contract_recipient_check: bool,
+    /// Add each token's raw round-trip amounts (`sim_detail`)
+    #[serde(default)]
+    pub detail: bool,
+}
+
+/// One batch entry: a bare address, or an address with its own chain
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum BatchToken {
+    Address(String),
+    OnChain {
+        address: String,
+        /// Chain ID of this token (0 = auto-detect), overrides the request's
+        #[serde(default)]
+        chain_id: Option<u64>,
+        /// Chain by name or alias ("base", "bsc", ...), takes precedence over `chain_id`
+        #[serde(default)]
+        chain: Option<String>,
+    },
+}
+
+impl BatchToken {
+    pub fn address(&self) -> &str {
+        match self {
+            BatchToken::Address(address) | BatchToken::OnChain { address, .. } => address,
+        }
+    }
+
+    /// The entry's own `chain` / `chain_id`, else the request's `default_chain_id`
+    pub fn resolved_chain_id(&self, default_chain_id: u64) -> Result<u64, String> {
+        match self {
+            BatchToken::Address(_) => Ok(default_chain_id),
+            BatchToken::OnChain { chain_id, chain, .. } => {
+                resolve_chain(chain.as_deref(), chain_id.unwrap_or(default_chain_id))
+            }
+        }
+    }
 }
 
 fn default_concurrency() -> usize {
     10
 }
 
-#[derive(Debug, Serialize)]
+impl BatchAnalysisRequest {
+    /// `chain` parsed to an id if given, else `chain_id`
+    pub fn resolved_chain_id(&self) -> Result<u64, String> {
+        resolve_chain(self.chain.as_deref(), self.chain_id)
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BatchAnalysisData {
     pub total_requested: usize,
     pub total_processed: usize,
@@ -277,26 +1378,196 @@ pub struct BatchAnalysisData {
     pub processing_time_ms: f64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct BatchTokenResult {
     pub token_address: String,
     pub status: String, // "success" | "error"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub risk_score: Option<u8>,
+    /// Omitted when the verdict is `Unverified` (see `confidence`)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_honeypot: Option<bool>,
+    /// How much real execution backs the verdict (0-100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+    /// Token accepted an EIP-2612 permit (only with `permit_check`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permit_supported: Option<bool>,
+    /// Sell outcome depends on the gas price (only with `gas_price_check`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price_gated: Option<bool>,
+    /// Holder credited outside the router cannot sell (only with `direct_holder_check`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direct_holder_sell_blocked: Option<bool>,
+    /// Transfer to an EOA goes through, transfer to a contract reverts (only with `contract_recipient_check`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_recipient_blocked: Option<bool>,
+    /// Blocks after the buy before the sell first went through (timed sell lock, forked simulation)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sell_lock_blocks: Option<u64>,
+    /// Sell used 5x the buy's gas or more (omitted when gas wasn't measured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asymmetric_gas: Option<bool>,
+    /// Raw round-trip amounts (only with `detail`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sim_detail: Option<SimDetailResponse>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub level: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
     pub latency_ms: f64,
+    /// Chain the token was simulated on (auto-detected when chain_id = 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    /// Liquidity in USD (DexScreener batch lookup)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liquidity_usd: Option<f64>,
+    /// Price in USD (DexScreener batch lookup)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_usd: Option<String>,
+    /// Block the verdict was computed at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Unix timestamp (seconds) when the verdict was computed
+    pub analyzed_at: u64,
+}
+
+impl BatchTokenResult {
+    /// One-line human verdict for `?format=text`
+    pub fn summary(&self) -> String {
+        match (self.risk_score, &self.error) {
+            (Some(score), _) => format!(
+                "{} {} | {} | Risk: {}/100 | Chain: {}",
+                match self.is_honeypot {
+                    Some(true) => "🚨",
+                    Some(false) => "✅",
+                    None => "⚠️",
+                },
+                self.token_address,
+                self.level.as_deref().unwrap_or("UNKNOWN"),
+                score,
+                self.chain_id.map_or_else(|| "?".to_string(), |id| id.to_string())
+            ),
+            (None, error) => format!(
+                "❌ {} | Error: {}",
+                self.token_address,
+                error.as_deref().unwrap_or("unknown")
+            ),
+        }
+    }
+}
+
+impl BatchAnalysisData {
+    /// Totals line followed by one line per token, for `?format=text`
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "Batch: {}/{} analyzed | Safe: {} | Risky: {} | Honeypots: {} | {:.0}ms",
+            self.total_processed,
+            self.total_requested,
+            self.total_safe,
+            self.total_risky,
+            self.total_honeypots,
+            self.processing_time_ms
+        )];
+        lines.extend(self.results.iter().map(BatchTokenResult::summary));
+        lines.join("\n")
+    }
+}
+
+// ============================================
+// Loss Explanation (post-mortem)
+// ============================================
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExplainLossQuery {
+    #[serde(default = "default_chain_id")]
+    pub chain_id: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExplainLossData {
+    pub tx_hash: String,
+    pub chain_id: u64,
+    pub victim: String,
+    pub tx_success: bool,
+    /// Raw wei amounts (decimal strings)
+    pub native_sent_wei: String,
+    pub native_received_wei: String,
+    pub effective_tax_percent: f64,
+    pub is_honeypot: bool,
+    pub confidence: f64,
+    pub flows: Vec<TokenFlowResponse>,
+    pub leaks: Vec<ValueLeakResponse>,
+    pub red_flags: Vec<String>,
+    /// Score comparable to `/analyze/token` (trace red flags + observed taxes)
+    pub risk_score: RiskScoreResponse,
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenFlowResponse {
+    pub token: String,
+    /// Raw token amounts (decimal strings, not decimals-adjusted)
+    pub expected_in: String,
+    pub received: String,
+    pub sent: String,
+    pub buy_tax_percent: f64,
+    pub sell_tax_percent: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ValueLeakResponse {
+    pub token: String,
+    pub to: String,
+    pub amount: String,
+}
+
+impl ExplainLossData {
+    pub fn from_explanation(explanation: LossExplanation, chain_id: u64) -> Self {
+        Self {
+            tx_hash: explanation.tx_hash,
+            chain_id,
+            victim: explanation.victim,
+            tx_success: explanation.tx_success,
+            native_sent_wei: explanation.native_sent.to_string(),
+            native_received_wei: explanation.native_received.to_string(),
+            effective_tax_percent: explanation.effective_tax_percent,
+            is_honeypot: explanation.is_honeypot,
+            confidence: explanation.confidence,
+            flows: explanation
+                .flows
+                .into_iter()
+                .map(|f| TokenFlowResponse {
+                    token: f.token,
+                    expected_in: f.expected_in.to_string(),
+                    received: f.received.to_string(),
+                    sent: f.sent.to_string(),
+                    buy_tax_percent: f.buy_tax_percent,
+                    sell_tax_percent: f.sell_tax_percent,
+                })
+                .collect(),
+            leaks: explanation
+                .leaks
+                .into_iter()
+                .map(|l| ValueLeakResponse {
+                    token: l.token,
+                    to: l.to,
+                    amount: l.amount.to_string(),
+                })
+                .collect(),
+            red_flags: explanation.red_flags,
+            risk_score: explanation.risk_score.into(),
+            summary: explanation.summary,
+        }
+    }
 }
 
 // ============================================
 // Stats / Telemetry
 // ============================================
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct StatsData {
     pub total_analyzed: u64,
     pub total_threats: u64,
@@ -304,16 +1575,148 @@ pub struct StatsData {
     pub total_value_protected_eth: f64,
     pub avg_latency_ms: f64,
     pub uptime_seconds: u64,
+    /// Analyses running right now (saturation = `in_flight` / `max_concurrency`)
+    pub in_flight: usize,
+    /// Permits on the shared analysis semaphore
+    pub max_concurrency: usize,
     pub api_version: String,
 }
 
+/// `/stats/by-chain` payload: telemetry counters keyed by chain id
+/// Value figures are in each chain's native token
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChainStatsData {
+    pub chains: HashMap<u64, TelemetryStats>,
+    pub uptime_seconds: u64,
+}
+
+/// `/stats/latency` payload: per-method upstream call latency since startup
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LatencyStatsData {
+    /// RPC methods and DexScreener endpoints, largest total time first
+    pub methods: Vec<MethodLatency>,
+    pub uptime_seconds: u64,
+}
+
+/// `/cache/stats` payload: lifetime counters plus recent per-interval hit rates
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CacheStatsData {
+    pub current: CacheStats,
+    /// Oldest first, one sample per `sample_interval_secs`
+    pub history: Vec<HitRateSample>,
+    pub sample_interval_secs: u64,
+}
+
+/// `/cache/purge` payload
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CachePurgeData {
+    /// Entries removed from the verdict cache
+    pub removed: usize,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReportQuery {
+    /// ETH price in USD used for the "USD saved" figure
+    #[serde(default = "default_eth_price")]
+    pub eth_price: f64,
+}
+
+fn default_eth_price() -> f64 {
+    2500.0
+}
+
 // ============================================
 // Health Check
 // ============================================
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct HealthData {
     pub status: String,
     pub version: String,
     pub uptime_seconds: u64,
+    /// Analyses running right now (saturation = `in_flight` / `max_concurrency`)
+    pub in_flight: usize,
+    /// Permits on the shared analysis semaphore
+    pub max_concurrency: usize,
+    /// Per-chain RPC health (present when the server warmed up an RpcManager)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chains: Option<Vec<ChainHealth>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_token_deserialization() {
+        let tokens: Vec<BatchToken> = serde_json::from_value(serde_json::json!([
+            "0xaaa",
+            { "address": "0xbbb", "chain_id": 56 },
+            { "address": "0xccc", "chain": "base" },
+            { "address": "0xddd" },
+        ]))
+        .unwrap();
+
+        assert!(matches!(&tokens[0], BatchToken::Address(address) if address == "0xaaa"));
+        assert!(matches!(
+            &tokens[1],
+            BatchToken::OnChain { address, chain_id: Some(56), chain: None } if address == "0xbbb"
+        ));
+        assert!(matches!(
+            &tokens[2],
+            BatchToken::OnChain { chain_id: None, chain: Some(chain), .. } if chain == "base"
+        ));
+        assert_eq!(tokens.iter().map(BatchToken::address).collect::<Vec<_>>(), ["0xaaa", "0xbbb", "0xccc", "0xddd"]);
+
+        // Neither a string nor an object with an address
+        assert!(serde_json::from_value::<BatchToken>(serde_json::json!({ "chain_id": 1 })).is_err());
+    }
+
+    #[test]
+    fn test_batch_token_resolved_chain_id() {
+        let on_chain = |chain_id: Option<u64>, chain: Option<&str>| BatchToken::OnChain {
+            address: "0xaaa".to_string(),
+            chain_id,
+            chain: chain.map(str::to_string),
+        };
+
+        // Bare addresses and entries without a chain use the request's
+        assert_eq!(BatchToken::Address("0xaaa".to_string()).resolved_chain_id(137), Ok(137));
+        assert_eq!(on_chain(None, None).resolved_chain_id(137), Ok(137));
+        // The entry's own chain_id, then its chain name, win over the request's
+        assert_eq!(on_chain(Some(56), None).resolved_chain_id(137), Ok(56));
+        assert_eq!(on_chain(Some(56), Some("base")).resolved_chain_id(137), Ok(8453));
+        assert_eq!(on_chain(Some(0), None).resolved_chain_id(137), Ok(0));
+        assert!(on_chain(None, Some("dogechain")).resolved_chain_id(1).is_err());
+    }
+
+    #[test]
+    fn test_profile_pipeline() {
+        let fast = AnalysisProfile::Fast.pipeline();
+        assert!(!fast.runs(Stage::FetchBytecode));
+        assert!(!fast.runs(Stage::ScanStatic));
+        assert!(fast.simulates());
+        assert!(fast.runs(Stage::ComputeTax));
+        assert_eq!(AnalysisProfile::Standard.pipeline(), AnalysisPipeline::full());
+        assert_eq!(AnalysisProfile::Thorough.pipeline(), AnalysisPipeline::full());
+    }
+
+    #[test]
+    fn test_verdict_is_honeypot() {
+        assert_eq!(Verdict::gate(true, 90, 50).is_honeypot(), Some(true));
+        assert_eq!(Verdict::gate(false, 90, 50).is_honeypot(), Some(false));
+        // Too little confidence either way: no boolean at all
+        assert_eq!(Verdict::gate(true, 10, 50), Verdict::Unverified);
+        assert_eq!(Verdict::Unverified.is_honeypot(), None);
+    }
+
+    #[test]
+    fn test_verdict_summary_label() {
+        assert_eq!(Verdict::Safe.summary_label(10), "✅ SAFE");
+        // A sellable token with a high score is labelled by its risk, not as safe
+        assert_eq!(Verdict::Safe.summary_label(75), "🔴 HIGH");
+        assert_eq!(Verdict::Honeypot.summary_label(100), "🚨 HONEYPOT DETECTED");
+        assert_eq!(Verdict::Unverified.summary_label(10), "⚠️ UNVERIFIED");
+    }
 }
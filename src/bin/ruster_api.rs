@@ -11,7 +11,8 @@
 //!   RUST_LOG    - Log level (default: info)
 
 use ruster_revm::api::{create_router, handlers::AppState, start_cleanup_task};
-use ruster_revm::TelemetryCollector;
+use ruster_revm::providers::rpc::set_insecure_tls_hosts;
+use ruster_revm::{RpcManager, SentryConfig, TelemetryCollector};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::TcpListener;
@@ -29,12 +30,27 @@ async fn main() -> eyre::Result<()> {
 
     print_banner();
 
-    // Initialize telemetry
-    let telemetry = Arc::new(TelemetryCollector::new());
+    // Initialize telemetry (periodic export so a crash doesn't lose everything)
+    let config = SentryConfig::default();
+    let telemetry = Arc::new(TelemetryCollector::from_sentry_config(&config));
     let telemetry_for_shutdown = telemetry.clone();
+    if config.telemetry_export_interval_secs > 0 {
+        telemetry.start_periodic_export(std::time::Duration::from_secs(config.telemetry_export_interval_secs));
+        info!("📊 Telemetry export every {}s to {}", config.telemetry_export_interval_secs, config.telemetry_export_dir);
+    }
+    if !config.insecure_tls_hosts.is_empty() {
+        warn!(
+            "⚠️ RPC_ALLOW_INSECURE_TLS is set: TLS certificates are NOT validated for {}",
+            config.insecure_tls_hosts.join(", ")
+        );
+    }
+    set_insecure_tls_hosts(config.insecure_tls_hosts.clone());
+
+    // Warm up per-chain RPC providers (concurrent eth_blockNumber probes)
+    let rpc_manager = Arc::new(RpcManager::warmup().await);
 
     // Create app state
-    let state = Arc::new(AppState::new(telemetry));
+    let state = Arc::new(AppState::new(telemetry).with_rpc_manager(rpc_manager));
 
     // Start background cleanup task for rate limiter
     start_cleanup_task();
@@ -59,9 +75,21 @@ async fn main() -> eyre::Result<()> {
     info!("");
     info!("Endpoints:");
     info!("  POST /v1/analyze/token    - Full token risk analysis (PERS)");
-    info!("  POST /v1/honeypot/check   - Quick honeypot detection");
-    info!("  POST /v1/analyze/batch    - Batch analysis (up to 100 tokens)");
+    info!("  POST /v1/honeypot/check   - Quick honeypot detection (?trace=true for REVM sell trace, ?block=N for historical state, ?recommendation=true for ML advice)");
+    info!("  POST /v1/analyze/batch    - Batch analysis (up to BATCH_MAX_TOKENS, default 100)");
+    info!("  POST /v1/analyze/async    - Queue a honeypot check, returns job_id");
+    info!("  GET  /v1/analyze/result/:job_id - Poll an async job");
+    info!("  GET  /v1/pair/:chain/:pair - Honeypot check of a pair's non-base token");
+    info!("  GET  /v1/analyze/all-chains/:address - Verdict on every supported chain, safest first");
+    info!("  GET  /v1/search?q=SYMBOL  - Token candidates by symbol / name (&analyze=true)");
+    info!("  GET  /v1/explain/:tx_hash - Post-mortem of a victim transaction");
+    info!("  GET  /v1/debug/failures - Recent failed analyses (admin)");
+    info!("  POST /v1/feedback - Grade an ML verdict, tunes weights (admin)");
+    info!("  POST /v1/cache/purge - Drop all cached verdicts (admin)");
     info!("  GET  /v1/stats            - Protection statistics");
+    info!("  GET  /v1/stats/by-chain   - Protection statistics per chain id");
+    info!("  GET  /v1/cache/stats      - Cache counters + hit-rate history");
+    info!("  GET  /v1/report           - Protection report JSON (?eth_price=)");
     info!("  GET  /v1/health           - Health check");
     info!("");
     info!("Press Ctrl+C for graceful shutdown");
@@ -94,6 +122,9 @@ async fn main() -> eyre::Result<()> {
     info!("   Honeypots detected: {}", stats.honeypots_detected);
 
     // Try to export stats to file
+    if let Err(e) = telemetry_for_shutdown.flush().await {
+        warn!("   ⚠️ Failed to flush telemetry events: {}", e);
+    }
     match telemetry_for_shutdown.export_stats_json() {
         Ok(path) => info!("   ✅ Stats exported to: {}", path.display()),
         Err(e) => warn!("   ⚠️ Failed to export stats: {}", e),
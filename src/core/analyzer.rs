@@ -14,11 +14,13 @@ use tokio::sync::Semaphore;
 use tracing::{debug, info};
 
 use crate::models::config::{DexRouters, SentryConfig};
-use crate::models::types::{AnalysisResult, RiskFactor, RiskLevel, SentryStats};
-use crate::utils::constants::wei_to_eth;
+use crate::models::types::{AnalysisResult, RiskFactor, RiskLevel, SentryStats, TxFees};
+use crate::utils::constants::{wei_to_eth, CHAIN_ID_ETHEREUM};
+use crate::utils::alerts::{Alert, AlertDispatcher};
 use crate::utils::decoder::SwapDecoder;
 use crate::utils::telemetry::{TelemetryCollector, TelemetryEvent, ThreatType};
 use crate::core::honeypot::HoneypotDetector;
+use crate::core::simulator::{sandwich_exposure, SIM_BASE_FEE_WEI};
 
 /// Main analyzer struct - the heart of Mempool Sentry
 pub struct MempoolAnalyzer {
@@ -34,6 +36,10 @@ pub struct MempoolAnalyzer {
     stats: Arc<AnalyzerStats>,
     /// Telemetry collector
     telemetry: Arc<TelemetryCollector>,
+    /// Operator alerts for verdicts at or above the configured severity
+    alerts: Arc<AlertDispatcher>,
+    /// Base fee of the latest block (wei) - EIP-1559 fees are priced against it
+    base_fee: Arc<AtomicU64>,
 }
 
 /// Thread-safe statistics
@@ -61,6 +67,7 @@ impl MempoolAnalyzer {
     /// Create a new analyzer instance
     pub fn new(config: SentryConfig, telemetry: Arc<TelemetryCollector>) -> Self {
         let max_concurrent = config.max_concurrent_tasks;
+        let alerts = Arc::new(AlertDispatcher::from_sentry_config(&config));
         Self {
             config,
             dex_routers: DexRouters::default(),
@@ -68,6 +75,8 @@ impl MempoolAnalyzer {
             seen_txs: Arc::new(DashMap::new()),
             stats: Arc::new(AnalyzerStats::default()),
             telemetry,
+            alerts,
+            base_fee: Arc::new(AtomicU64::new(SIM_BASE_FEE_WEI as u64)),
         }
     }
 
@@ -95,6 +104,25 @@ impl MempoolAnalyzer {
 
         let mut stream = sub.into_stream();
 
+        // Follow the base fee block by block (the sim default until the first header)
+        let base_fee = self.base_fee.clone();
+        let blocks_provider = provider.clone();
+        tokio::spawn(async move {
+            let blocks = match blocks_provider.subscribe_blocks().await {
+                Ok(sub) => sub,
+                Err(e) => {
+                    debug!("Base fee tracking unavailable: {}", e);
+                    return;
+                }
+            };
+            let mut blocks = blocks.into_stream();
+            while let Some(header) = blocks.next().await {
+                if let Some(fee) = header.base_fee_per_gas {
+                    base_fee.store(fee, Ordering::Relaxed);
+                }
+            }
+        });
+
         info!("🔍 Listening for mempool transactions...");
         self.print_stats_header();
 
@@ -144,6 +172,8 @@ impl MempoolAnalyzer {
             let stats = self.stats.clone();
             let config = self.config.clone();
             let telemetry = self.telemetry.clone();
+            let alerts = self.alerts.clone();
+            let base_fee = self.base_fee.load(Ordering::Relaxed) as u128;
 
             tokio::spawn(async move {
                 let _permit = match semaphore.acquire().await {
@@ -157,10 +187,12 @@ impl MempoolAnalyzer {
                         if let Err(e) = process_transaction(
                             tx,
                             tx_hash,
+                            base_fee,
                             &dex_routers,
                             stats,
                             &config,
                             telemetry,
+                            &alerts,
                         ) {
                             debug!("Error processing tx {}: {}", tx_hash, e);
                         }
@@ -202,22 +234,40 @@ impl MempoolAnalyzer {
     }
 }
 
+/// Legacy gas price, or the EIP-1559 caps for transactions without one
+fn tx_fees(tx: &Transaction) -> TxFees {
+    match TransactionTrait::gas_price(tx) {
+        Some(gas_price) => TxFees::legacy(gas_price),
+        None => TxFees::eip1559(
+            TransactionTrait::max_fee_per_gas(tx),
+            TransactionTrait::max_priority_fee_per_gas(tx).unwrap_or(0),
+        ),
+    }
+}
+
 /// Process a single transaction (synchronous analysis)
+/// `base_fee` prices EIP-1559 fees (latest block)
+#[allow(clippy::too_many_arguments)]
 fn process_transaction(
     tx: Transaction,
     tx_hash: B256,
+    base_fee: u128,
     dex_routers: &std::collections::HashSet<Address>,
     stats: Arc<AnalyzerStats>,
     config: &SentryConfig,
     telemetry: Arc<TelemetryCollector>,
+    alerts: &AlertDispatcher,
 ) -> Result<()> {
     let start = Instant::now();
 
     // Extract fields
     let to_addr = TransactionTrait::to(&tx);
     let value = TransactionTrait::value(&tx);
-    let gas_price = TransactionTrait::gas_price(&tx).unwrap_or(0);
+    // What the tx actually pays per gas (1559: min(fee cap, base fee + tip))
+    let fees = tx_fees(&tx);
+    let gas_price = fees.effective_gas_price(base_fee);
     let input = TransactionTrait::input(&tx).clone();
+    let chain_id = TransactionTrait::chain_id(&tx).unwrap_or(CHAIN_ID_ETHEREUM);
 
     // CRITICAL FILTER: Only process DEX router transactions
     let target = match to_addr {
@@ -242,6 +292,8 @@ fn process_transaction(
 
     // Create analysis result
     let mut result = AnalysisResult::new(tx_hash, tx.from, target, value, U256::from(gas_price));
+    // Token + reason of a simulated honeypot, for the alert
+    let mut honeypot: Option<(Address, String)> = None;
 
     // Analyze swap parameters for risks
     if let Some(ref params) = swap_params {
@@ -277,11 +329,16 @@ fn process_transaction(
                 0
             };
 
-            if slippage_pct > 3 {
+            // Only worth flagging when the slippage pays for outbidding the victim's tip
+            let exposure = sandwich_exposure(params.amount_in, slippage_pct * 100, &fees, base_fee);
+            if slippage_pct > 3 && exposure.is_profitable() {
                 result.add_risk(RiskFactor::SandwichTarget {
                     reason: format!(
-                        "Swap of {:.4} ETH with {}% slippage - prime MEV target",
-                        value_eth, slippage_pct
+                        "Swap of {:.4} ETH with {}% slippage - prime MEV target ({} urgency, ~{:.4} ETH profit window)",
+                        value_eth,
+                        slippage_pct,
+                        exposure.urgency.as_str(),
+                        wei_to_eth(exposure.profit_window)
                     ),
                 });
             }
@@ -318,6 +375,7 @@ fn process_transaction(
                     match detector.detect(token, test_amount, None, None, None, None) {
                         Ok(hp_result) => {
                             if hp_result.is_honeypot {
+                                honeypot = Some((token, hp_result.reason.clone()));
                                 result.add_risk(RiskFactor::Honeypot {
                                     reason: hp_result.reason,
                                     buy_success: hp_result.buy_success,
@@ -393,13 +451,33 @@ fn process_transaction(
                 result.latency_ms,
                 result.risk_level as u8,
                 factor.description(),
-            );
+            )
+            .on_chain(chain_id);
 
             telemetry.record_threat(event);
         }
     } else {
         // Record clean analysis
-        telemetry.record_analysis(result.latency_ms);
+        telemetry.record_analysis(chain_id, result.latency_ms);
+    }
+
+    // ============================================
+    // ALERTING
+    // ============================================
+    if alerts.should_alert(result.risk_level) {
+        let factors = result.risk_factors.iter().map(|f| f.description()).collect();
+        let alert = match honeypot {
+            Some((token, reason)) => Alert::new(result.risk_level, chain_id, reason, "mempool")
+                .for_token(format!("{:?}", token))
+                .honeypot(),
+            None => Alert::new(
+                result.risk_level,
+                chain_id,
+                format!("{} risk swap via router {:?}", result.risk_level.as_str(), target),
+                "mempool",
+            ),
+        };
+        alerts.notify(&alert.for_tx(format!("{:?}", tx_hash)).with_risk_factors(factors));
     }
 
     // Output result for risky transactions
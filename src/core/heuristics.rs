@@ -0,0 +1,717 @@
+//! Pluggable Honeypot Heuristics
+//!
+//! Checks that run after the core buy/sell simulation and contribute extra
+//! risk factors (e.g. a proprietary bytecode signature) without forking the
+//! detector. Register custom heuristics with `HoneypotDetector::with_heuristic`.
+//!
+//! The built-in access control scan reads its selectors from the embedded
+//! `access_control_signatures.json`, extended by `ACCESS_CONTROL_SIGNATURES_PATH`.
+//! The built-in drain scan flags owner functions that empty the contract's own
+//! balance (`withdrawStuckETH`, `manualswap`, ...) as a separate risk factor.
+//!
+//! Merging: each heuristic contributes its `penalty` (by default the weight of
+//! its strongest factor) to `access_control_penalty` (capped at 100); factor
+//! descriptions are appended to `risk_factors`. The access control scan sums
+//! its matches instead, see `AccessControlWeighting`.
+//!
+//! After merging, `CompositeRiskRule` (opt-in) flags a token that passed the sell
+//! test as a likely honeypot when enough independent risk dimensions co-occur.
+
+use alloy_primitives::{Address, Bytes};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::honeypot::{HoneypotResult, LossCutoffs};
+use super::ml_risk::{RiskFactor, Severity};
+
+/// Everything a heuristic may inspect about the analyzed token
+pub struct AnalysisContext<'a> {
+    pub token: Address,
+    pub chain_id: u64,
+    /// Deployed token bytecode (None if it could not be fetched)
+    pub bytecode: Option<&'a Bytes>,
+    /// Verdict of the core simulation
+    pub result: &'a HoneypotResult,
+}
+
+/// A detector check run after the core simulation
+pub trait HoneypotHeuristic: Send + Sync {
+    /// Short identifier used in logs
+    fn name(&self) -> &str;
+
+    /// Risk factors found for this token (empty = nothing suspicious)
+    /// `weight` is the penalty (0-100) the factor adds to the verdict
+    fn evaluate(&self, ctx: &AnalysisContext) -> Vec<RiskFactor>;
+
+    /// Penalty for this heuristic's factors; default = the strongest one
+    fn penalty(&self, factors: &[RiskFactor]) -> u8 {
+        factors
+            .iter()
+            .map(|f| f.weight.clamp(0.0, 100.0) as u8)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Built-in heuristics every detector starts with
+pub fn default_heuristics() -> Vec<Box<dyn HoneypotHeuristic>> {
+    vec![
+        Box::new(AccessControlHeuristic::default()),
+        Box::new(DrainFunctionHeuristic),
+    ]
+}
+
+/// Merge heuristic output into a simulation result
+/// Penalty per heuristic = `HoneypotHeuristic::penalty`; total capped at 100
+pub fn apply_heuristics(
+    heuristics: &[Box<dyn HoneypotHeuristic>],
+    ctx: &AnalysisContext,
+) -> (u8, Vec<String>) {
+    let mut penalty: u8 = 0;
+    let mut descriptions = Vec::new();
+
+    for heuristic in heuristics {
+        let factors = heuristic.evaluate(ctx);
+        if factors.is_empty() {
+            continue;
+        }
+        tracing::debug!("🧩 Heuristic {} flagged {} factor(s)", heuristic.name(), factors.len());
+
+        penalty = penalty.saturating_add(heuristic.penalty(&factors)).min(100);
+        descriptions.extend(factors.into_iter().map(|f| f.description));
+    }
+
+    (penalty, descriptions)
+}
+
+// ============================================
+// Built-in: Access Control Scan
+// ============================================
+
+/// Default signature weight; `AccessControlWeighting::per_selector` is charged at this weight
+pub const ACCESS_CONTROL_PENALTY: f64 = 50.0;
+
+/// Env vars overriding `AccessControlWeighting::default()`
+pub const ACCESS_CONTROL_PER_SELECTOR_ENV: &str = "ACCESS_CONTROL_PER_SELECTOR_PENALTY";
+pub const ACCESS_CONTROL_STORAGE_PATTERN_ENV: &str = "ACCESS_CONTROL_STORAGE_PATTERN_PENALTY";
+pub const ACCESS_CONTROL_CAP_ENV: &str = "ACCESS_CONTROL_PENALTY_CAP";
+
+/// How access control matches add up: one `setBots` is suspicious, `setBots` +
+/// `blacklist` + `setMaxTx` + `enableTrading` is a full rug kit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AccessControlWeighting {
+    /// Penalty per distinct matched selector, scaled by the signature's
+    /// `weight / ACCESS_CONTROL_PENALTY` (embedded signatures: exactly this)
+    pub per_selector: f64,
+    /// Penalty of a "bots" / "blacklist" storage-name hit without any selector
+    /// (a string in the bytecode is weaker evidence than a callable function)
+    pub storage_pattern: f64,
+    /// Ceiling of the summed penalty
+    pub cap: f64,
+}
+
+impl Default for AccessControlWeighting {
+    fn default() -> Self {
+        Self {
+            per_selector: 20.0,
+            storage_pattern: 10.0,
+            cap: 60.0,
+        }
+    }
+}
+
+impl AccessControlWeighting {
+    /// Defaults, each overridable by its env var (values outside 0-100 are ignored)
+    pub fn from_env() -> Self {
+        let env = |key: &str, default: f64| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| (0.0..=100.0).contains(v))
+                .unwrap_or(default)
+        };
+        let defaults = Self::default();
+        Self {
+            per_selector: env(ACCESS_CONTROL_PER_SELECTOR_ENV, defaults.per_selector),
+            storage_pattern: env(ACCESS_CONTROL_STORAGE_PATTERN_ENV, defaults.storage_pattern),
+            cap: env(ACCESS_CONTROL_CAP_ENV, defaults.cap),
+        }
+    }
+
+    /// Penalty charged for one matched signature
+    fn selector_penalty(&self, signature: &SelectorSignature) -> f64 {
+        (self.per_selector * signature.weight / ACCESS_CONTROL_PENALTY).clamp(0.0, 100.0)
+    }
+}
+
+/// Description prefix of access control selector matches
+const ACCESS_CONTROL_FACTOR: &str = "⚠️ Access Control:";
+
+/// Description of a blacklist storage-name hit
+const BLACKLIST_STORAGE_FACTOR: &str = "⚠️ Blacklist storage pattern detected";
+
+/// Env var naming a JSON file of extra / overriding access-control signatures
+pub const ACCESS_CONTROL_SIGNATURES_ENV: &str = "ACCESS_CONTROL_SIGNATURES_PATH";
+
+/// Built-in signature set, compiled into the binary
+const EMBEDDED_SIGNATURES: &str = include_str!("access_control_signatures.json");
+
+/// A dangerous access-control function, matched by its 4-byte selector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectorSignature {
+    /// keccak256(signature)[..4] as lowercase hex, no 0x
+    pub selector: String,
+    /// Function name shown in risk factors
+    pub name: String,
+    /// Full signature, documentation only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Penalty (0-100); 0 disables an embedded entry from an override file
+    #[serde(default = "default_signature_weight")]
+    pub weight: f64,
+}
+
+fn default_signature_weight() -> f64 {
+    ACCESS_CONTROL_PENALTY
+}
+
+/// Selector → name/weight map for the access control scan
+#[derive(Debug, Clone, Default)]
+pub struct SignatureDb {
+    signatures: Vec<SelectorSignature>,
+}
+
+impl SignatureDb {
+    /// The embedded default set
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED_SIGNATURES).expect("embedded access control signatures are valid JSON")
+    }
+
+    /// Embedded set plus the file named by `ACCESS_CONTROL_SIGNATURES_PATH` (if set)
+    /// A bad override file is logged and ignored - the embedded set still applies
+    pub fn from_env() -> Self {
+        let mut db = Self::embedded();
+        if let Some(path) = std::env::var(ACCESS_CONTROL_SIGNATURES_ENV).ok().filter(|p| !p.is_empty()) {
+            match std::fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|json| Self::parse(&json)) {
+                Ok(overrides) => {
+                    tracing::info!("🧬 Loaded {} access control signature(s) from {}", overrides.len(), path);
+                    db.merge(overrides);
+                }
+                Err(e) => tracing::warn!("⚠️ Ignoring access control signatures from {}: {}", path, e),
+            }
+        }
+        db
+    }
+
+    /// Parse a JSON array of `SelectorSignature` (selectors normalized to bare lowercase hex)
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let mut signatures: Vec<SelectorSignature> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        for sig in &mut signatures {
+            sig.selector = sig.selector.trim().trim_start_matches("0x").to_lowercase();
+            if sig.selector.len() != 8 || !sig.selector.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("invalid selector for {}: {:?}", sig.name, sig.selector));
+            }
+        }
+        Ok(Self { signatures })
+    }
+
+    /// Add `other`'s entries; an entry with an existing selector replaces it
+    pub fn merge(&mut self, other: SignatureDb) {
+        for sig in other.signatures {
+            match self.signatures.iter_mut().find(|s| s.selector == sig.selector) {
+                Some(existing) => *existing = sig,
+                None => self.signatures.push(sig),
+            }
+        }
+    }
+
+    /// Number of signatures (including disabled ones)
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    /// No signatures at all
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    /// Enabled signatures whose selector appears in the hex-encoded bytecode
+    fn matches<'a>(&'a self, code_hex: &'a str) -> impl Iterator<Item = &'a SelectorSignature> {
+        self.signatures
+            .iter()
+            .filter(move |s| s.weight > 0.0 && code_hex.contains(&s.selector))
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Loaded once per process: restart to pick up an edited override file
+    static ref SIGNATURES: Arc<SignatureDb> = Arc::new(SignatureDb::from_env());
+}
+
+/// Scan bytecode for access control functions that could be used for blacklisting
+pub struct AccessControlHeuristic {
+    signatures: Arc<SignatureDb>,
+    weighting: AccessControlWeighting,
+}
+
+impl AccessControlHeuristic {
+    /// Scan with a custom signature set
+    pub fn with_signatures(signatures: SignatureDb) -> Self {
+        Self {
+            signatures: Arc::new(signatures),
+            weighting: AccessControlWeighting::from_env(),
+        }
+    }
+
+    /// Override how matches add up to the penalty
+    pub fn with_weighting(mut self, weighting: AccessControlWeighting) -> Self {
+        self.weighting = weighting;
+        self
+    }
+}
+
+impl Default for AccessControlHeuristic {
+    /// Embedded signatures + `ACCESS_CONTROL_SIGNATURES_PATH` overrides, weighting from env
+    fn default() -> Self {
+        Self {
+            signatures: SIGNATURES.clone(),
+            weighting: AccessControlWeighting::from_env(),
+        }
+    }
+}
+
+impl HoneypotHeuristic for AccessControlHeuristic {
+    fn name(&self) -> &str {
+        "access_control"
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> Vec<RiskFactor> {
+        let Some(bytecode) = ctx.bytecode else {
+            return Vec::new();
+        };
+        let code_hex = hex::encode(bytecode.as_ref());
+
+        let mut factors: Vec<RiskFactor> = self
+            .signatures
+            .matches(&code_hex)
+            .map(|sig| RiskFactor {
+                name: "Access Control".to_string(),
+                description: format!("{} {} detected", ACCESS_CONTROL_FACTOR, sig.name),
+                weight: self.weighting.selector_penalty(sig),
+                severity: Severity::High,
+            })
+            .collect();
+
+        // Also check for common blacklist storage patterns
+        // mapping(address => bool) bots/blacklist
+        if factors.is_empty()
+            && (code_hex.contains("626f7473") || // "bots" in hex
+                code_hex.contains("626c61636b6c697374")) // "blacklist" in hex
+        {
+            factors.push(RiskFactor {
+                name: "Blacklist Storage".to_string(),
+                description: BLACKLIST_STORAGE_FACTOR.to_string(),
+                weight: self.weighting.storage_pattern,
+                severity: Severity::Medium,
+            });
+        }
+
+        factors
+    }
+
+    /// Sum of the matches (cumulative), capped at `AccessControlWeighting::cap`
+    fn penalty(&self, factors: &[RiskFactor]) -> u8 {
+        let total: f64 = factors.iter().map(|f| f.weight).sum();
+        total.min(self.weighting.cap).clamp(0.0, 100.0) as u8
+    }
+}
+
+// ============================================
+// Built-in: Drain Function Scan
+// ============================================
+
+/// Penalty of a token exposing drain functions (strongest match, not summed)
+pub const DRAIN_FUNCTION_PENALTY: f64 = 15.0;
+
+/// Description prefix of drain function matches
+const DRAIN_FUNCTION_FACTOR: &str = "⚠️ Drain Function:";
+
+/// Functions that move the contract's own ETH / token balance out - the
+/// accumulated tax or a stuck LP share - as (selector, signature)
+pub const DRAIN_SELECTORS: [(&str, &str); 12] = [
+    ("3ccfd60b", "withdraw()"),
+    ("00ae3bf8", "rescueTokens(address)"),
+    ("57376198", "rescueTokens(address,uint256)"),
+    ("20800a00", "rescueETH()"),
+    ("364333f4", "clearStuckBalance()"),
+    ("1da1db5e", "clearStuckBalance(uint256)"),
+    ("77b54bad", "clearStuckToken(address,uint256)"),
+    ("51bc3c85", "manualSwap()"),
+    ("c3c8cd80", "manualswap()"),
+    ("6fc3eaec", "manualsend()"),
+    ("f5648a4f", "withdrawStuckETH()"),
+    ("cb963728", "withdrawStuckTokens(address)"),
+];
+
+/// Owner-callable withdraw / drain functions: unlike a blacklist they do not block
+/// sells, they let the owner empty the contract (rug via contract drain)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DrainFunctionHeuristic;
+
+impl HoneypotHeuristic for DrainFunctionHeuristic {
+    fn name(&self) -> &str {
+        "drain_function"
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> Vec<RiskFactor> {
+        let Some(bytecode) = ctx.bytecode else {
+            return Vec::new();
+        };
+        let code_hex = hex::encode(bytecode.as_ref());
+
+        DRAIN_SELECTORS
+            .iter()
+            .filter(|(selector, _)| code_hex.contains(selector))
+            .map(|(_, signature)| RiskFactor {
+                name: "Drain Function".to_string(),
+                description: format!("{} {} can withdraw the contract's balance", DRAIN_FUNCTION_FACTOR, signature),
+                weight: DRAIN_FUNCTION_PENALTY,
+                severity: Severity::Medium,
+            })
+            .collect()
+    }
+}
+
+// ============================================
+// Composite Rule: co-occurring risk dimensions
+// ============================================
+
+/// Env vars overriding `CompositeRiskRule::default()`
+pub const COMPOSITE_RISK_MIN_DIMENSIONS_ENV: &str = "COMPOSITE_RISK_MIN_DIMENSIONS";
+pub const COMPOSITE_RISK_SELL_TAX_ENV: &str = "COMPOSITE_RISK_SELL_TAX_PERCENT";
+
+/// Risk added to `access_control_penalty` when the composite rule escalates a token
+pub const COMPOSITE_RISK_PENALTY: u8 = 50;
+
+/// One successful sell proves little when the owner can still block the next one:
+/// a blacklist function plus a high sell tax is a near-certain rug even though the
+/// simulated sell went through. When `min_dimensions` of these co-occur on a sellable
+/// token, the result is flagged `likely_honeypot` and its risk raised (the verdict
+/// itself stays sellable):
+/// - access control: blacklist / setBots functions or storage, or a runtime blacklist read
+///   (trading switches and tx / wallet limits don't count)
+/// - sell tax at or above `min_sell_tax_percent` (the chain's high-tax cutoff by default)
+/// - restricted sell path: the sell only passes for some callers, recipients, gas prices
+///   or blocks, or costs far more gas than the buy
+/// - deceptive transfer: understated tax, diverted proceeds or an unenforced allowance
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CompositeRiskRule {
+    /// Dimensions that must co-occur to escalate (0 = rule disabled, the default)
+    pub min_dimensions: usize,
+    /// Sell tax (%) counted as the tax dimension (None = `LossCutoffs::high_tax_percent`)
+    pub min_sell_tax_percent: Option<f64>,
+}
+
+impl CompositeRiskRule {
+    /// Defaults, each overridable by its env var (a tax outside 0-100 is ignored)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            min_dimensions: std::env::var(COMPOSITE_RISK_MIN_DIMENSIONS_ENV)
+                .ok()
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(defaults.min_dimensions),
+            min_sell_tax_percent: std::env::var(COMPOSITE_RISK_SELL_TAX_ENV)
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| (0.0..=100.0).contains(v))
+                .or(defaults.min_sell_tax_percent),
+        }
+    }
+
+    /// Risk dimensions present in `result` (labels, in the order documented above)
+    pub fn dimensions(&self, result: &HoneypotResult, cutoffs: &LossCutoffs) -> Vec<String> {
+        let mut dimensions = Vec::new();
+        let access_control = result.dynamic_blacklist_detected
+            || result
+                .risk_factors
+                .iter()
+                .any(|f| is_blacklist_factor(f) || f == BLACKLIST_STORAGE_FACTOR);
+        if access_control {
+            dimensions.push("access control".to_string());
+        }
+        let min_sell_tax_percent = self.min_sell_tax_percent.unwrap_or(cutoffs.high_tax_percent);
+        if result.sell_tax_percent >= min_sell_tax_percent {
+            dimensions.push(format!("sell tax {:.1}%", result.sell_tax_percent));
+        }
+        if result.same_block_sell_blocked
+            || result.caller_type_gated
+            || result.transfer_to_pair_blocked
+            || result.gas_price_gated
+            || result.direct_holder_sell_blocked
+            || result.contract_recipient_blocked
+            || result.asymmetric_gas
+        {
+            dimensions.push("restricted sell path".to_string());
+        }
+        if result.declared_vs_measured_tax_mismatch || !result.proceeds_received || !result.allowance_enforced {
+            dimensions.push("deceptive transfer".to_string());
+        }
+        dimensions
+    }
+
+    /// Flag a sellable result whose dimensions reach `min_dimensions` as a likely honeypot
+    /// and raise its risk by `COMPOSITE_RISK_PENALTY`; `is_honeypot` is never touched.
+    /// Returns true when the result was escalated
+    pub fn apply(&self, result: &mut HoneypotResult, cutoffs: &LossCutoffs) -> bool {
+        if self.min_dimensions == 0
+            || result.is_honeypot
+            || result.likely_honeypot
+            || !result.sell_success
+        {
+            return false;
+        }
+        let dimensions = self.dimensions(result, cutoffs);
+        if dimensions.len() < self.min_dimensions {
+            return false;
+        }
+
+        let combined = dimensions.join(" + ");
+        result.likely_honeypot = true;
+        result.access_control_penalty =
+            result.access_control_penalty.saturating_add(COMPOSITE_RISK_PENALTY).min(100);
+        result.reason = format!("Likely honeypot: {} (the simulated sell passed)", combined);
+        result.risk_factors.push(format!("⛔ COMPOSITE RISK: {}", combined));
+        true
+    }
+}
+
+/// Access control match of a function that can block chosen holders (blacklist / bot
+/// setters), not a trading switch or tx / wallet limit every launch token ships with
+fn is_blacklist_factor(factor: &str) -> bool {
+    factor.strip_prefix(ACCESS_CONTROL_FACTOR).is_some_and(|name| {
+        let name = name.to_ascii_lowercase();
+        name.contains("bot") || name.contains("blacklist")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AlwaysFlag(f64);
+
+    impl HoneypotHeuristic for AlwaysFlag {
+        fn name(&self) -> &str {
+            "always_flag"
+        }
+
+        fn evaluate(&self, _ctx: &AnalysisContext) -> Vec<RiskFactor> {
+            vec![RiskFactor {
+                name: "Custom".to_string(),
+                description: "custom signature".to_string(),
+                weight: self.0,
+                severity: Severity::Medium,
+            }]
+        }
+    }
+
+    #[test]
+    fn test_apply_heuristics_merges_factors() {
+        let result = HoneypotResult::safe(0.0, 0.0, 0, vec![], 10);
+        let bytecode = Bytes::from(hex::decode("608060405234801561001057600080fd5b509c0db5f3").unwrap());
+        let ctx = AnalysisContext {
+            token: Address::ZERO,
+            chain_id: 1,
+            bytecode: Some(&bytecode),
+            result: &result,
+        };
+
+        let mut heuristics = default_heuristics();
+        let (penalty, factors) = apply_heuristics(&heuristics, &ctx);
+        assert_eq!(penalty, 20);
+        assert_eq!(factors, vec!["⚠️ Access Control: setBots detected".to_string()]);
+
+        // Custom heuristics stack, capped at 100
+        heuristics.push(Box::new(AlwaysFlag(30.0)));
+        heuristics.push(Box::new(AlwaysFlag(40.0)));
+        let (penalty, factors) = apply_heuristics(&heuristics, &ctx);
+        assert_eq!(penalty, 90);
+        assert_eq!(factors.len(), 3);
+        heuristics.push(Box::new(AlwaysFlag(40.0)));
+        let (penalty, _) = apply_heuristics(&heuristics, &ctx);
+        assert_eq!(penalty, 100);
+
+        // No bytecode = access control scan is silent
+        let ctx = AnalysisContext { bytecode: None, ..ctx };
+        let (penalty, factors) = apply_heuristics(&default_heuristics(), &ctx);
+        assert_eq!(penalty, 0);
+        assert!(factors.is_empty());
+    }
+
+    #[test]
+    fn test_access_control_penalty_is_cumulative_and_capped() {
+        let result = HoneypotResult::safe(0.0, 0.0, 0, vec![], 10);
+        let scan = |code_hex: &str, heuristic: &AccessControlHeuristic| {
+            let bytecode = Bytes::from(hex::decode(code_hex).unwrap());
+            let ctx = AnalysisContext {
+                token: Address::ZERO,
+                chain_id: 1,
+                bytecode: Some(&bytecode),
+                result: &result,
+            };
+            let factors = heuristic.evaluate(&ctx);
+            (heuristic.penalty(&factors), factors.len())
+        };
+        let heuristic = AccessControlHeuristic::with_signatures(SignatureDb::embedded())
+            .with_weighting(AccessControlWeighting::default());
+
+        // setBots + setBlacklist: 20 each
+        assert_eq!(scan("9c0db5f3153b0d1e", &heuristic), (40, 2));
+        // setBots + setBlacklist + setMaxTxAmount + enableTrading: capped at 60
+        assert_eq!(scan("9c0db5f3153b0d1eec28438a8a8c523c", &heuristic), (60, 4));
+        // "blacklist" string only: weaker than any selector
+        assert_eq!(scan("626c61636b6c697374", &heuristic), (10, 1));
+        assert_eq!(scan("6080604052", &heuristic), (0, 0));
+
+        // Heavier override weight counts more per selector
+        let mut signatures = SignatureDb::embedded();
+        let heavier = r#"[{ "selector": "9c0db5f3", "name": "setBots", "weight": 100 }]"#;
+        signatures.merge(SignatureDb::parse(heavier).unwrap());
+        let weighting = AccessControlWeighting { cap: 100.0, ..AccessControlWeighting::default() };
+        let heuristic =
+            AccessControlHeuristic::with_signatures(signatures).with_weighting(weighting);
+        assert_eq!(scan("9c0db5f3153b0d1e", &heuristic), (60, 2));
+    }
+
+    #[test]
+    fn test_drain_functions_reported_apart_from_access_control() {
+        let result = HoneypotResult::safe(0.0, 0.0, 0, vec![], 10);
+        // withdrawStuckETH + manualswap + setBots
+        let bytecode = Bytes::from(hex::decode("6080604052f5648a4f00c3c8cd80009c0db5f3").unwrap());
+        let ctx = AnalysisContext {
+            token: Address::ZERO,
+            chain_id: 1,
+            bytecode: Some(&bytecode),
+            result: &result,
+        };
+
+        let factors = DrainFunctionHeuristic.evaluate(&ctx);
+        let names: Vec<_> = factors.iter().map(|f| f.description.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "⚠️ Drain Function: manualswap() can withdraw the contract's balance",
+                "⚠️ Drain Function: withdrawStuckETH() can withdraw the contract's balance",
+            ]
+        );
+        assert_eq!(DrainFunctionHeuristic.penalty(&factors), DRAIN_FUNCTION_PENALTY as u8);
+
+        // Default set: access control (20) + drain (15), each with its own factor
+        let (penalty, factors) = apply_heuristics(&default_heuristics(), &ctx);
+        assert_eq!(penalty, 35);
+        assert_eq!(factors[0], "⚠️ Access Control: setBots detected");
+        assert_eq!(factors.len(), 3);
+
+        // Drain functions alone are not an access control dimension
+        let mut drained = HoneypotResult::safe(0.0, 15.0, 15, factors[1..].to_vec(), 10);
+        let rule = CompositeRiskRule { min_dimensions: 2, ..CompositeRiskRule::default() };
+        assert!(!rule.apply(&mut drained, &LossCutoffs::default()));
+    }
+
+    #[test]
+    fn test_composite_rule_escalates_co_occurring_dimensions() {
+        let rule = CompositeRiskRule { min_dimensions: 2, ..CompositeRiskRule::default() };
+        let cutoffs = LossCutoffs::default();
+        let factors = vec!["⚠️ Access Control: setBots detected".to_string()];
+
+        // Access control alone: penalized, still sellable
+        let mut result = HoneypotResult::safe(2.0, 3.0, 20, factors.clone(), 10);
+        assert!(!rule.apply(&mut result, &cutoffs));
+        assert!(!result.likely_honeypot);
+
+        // Access control + high sell tax: likely honeypot, riskier, but the sell passed
+        let mut result = HoneypotResult::safe(2.0, 15.0, 20, factors.clone(), 10);
+        assert_eq!(rule.dimensions(&result, &cutoffs), vec!["access control", "sell tax 15.0%"]);
+        assert!(rule.apply(&mut result, &cutoffs));
+        assert!(result.likely_honeypot);
+        assert!(!result.is_honeypot);
+        assert_eq!(result.access_control_penalty, 20 + COMPOSITE_RISK_PENALTY);
+        assert_eq!(
+            result.risk_factors.last().unwrap(),
+            "⛔ COMPOSITE RISK: access control + sell tax 15.0%"
+        );
+        // Already escalated: applied once only
+        assert!(!rule.apply(&mut result, &cutoffs));
+
+        // The tax dimension follows the chain's high-tax cutoff unless overridden
+        let lenient = LossCutoffs { high_tax_percent: 20.0, ..cutoffs };
+        let result = HoneypotResult::safe(2.0, 15.0, 20, factors.clone(), 10);
+        assert_eq!(rule.dimensions(&result, &lenient), vec!["access control"]);
+        let fixed = CompositeRiskRule { min_sell_tax_percent: Some(12.0), ..rule };
+        assert_eq!(fixed.dimensions(&result, &lenient).len(), 2);
+
+        // Trading switches and tx limits are not a blacklist
+        let launch = vec![
+            "⚠️ Access Control: enableTrading detected".to_string(),
+            "⚠️ Access Control: setMaxTxAmount detected".to_string(),
+        ];
+        let mut result = HoneypotResult::safe(2.0, 15.0, 40, launch, 10);
+        assert!(!rule.apply(&mut result, &cutoffs));
+
+        // Blacklist storage + restricted sell path, no tax
+        let mut result =
+            HoneypotResult::safe(0.0, 0.0, 10, vec![BLACKLIST_STORAGE_FACTOR.to_string()], 10);
+        result.same_block_sell_blocked = true;
+        assert!(rule.apply(&mut result, &cutoffs));
+        assert!(!result.is_honeypot);
+
+        // Stricter rule needs a third dimension
+        let strict = CompositeRiskRule { min_dimensions: 3, ..rule };
+        let mut result = HoneypotResult::safe(2.0, 15.0, 20, factors.clone(), 10);
+        assert!(!strict.apply(&mut result, &cutoffs));
+        result.declared_vs_measured_tax_mismatch = true;
+        assert!(strict.apply(&mut result, &cutoffs));
+
+        // Off by default
+        let mut result = HoneypotResult::safe(2.0, 15.0, 20, factors, 10);
+        assert!(!CompositeRiskRule::default().apply(&mut result, &cutoffs));
+    }
+
+    #[test]
+    fn test_signature_db_embedded_and_override() {
+        let mut db = SignatureDb::embedded();
+        assert_eq!(db.len(), 12);
+
+        let overrides = SignatureDb::parse(
+            r#"[
+                { "selector": "0x9C0DB5F3", "name": "setBots", "weight": 0 },
+                { "selector": "deadbeef", "name": "setHoneypotMode" }
+            ]"#,
+        )
+        .unwrap();
+        db.merge(overrides);
+        assert_eq!(db.len(), 13);
+
+        // Disabled embedded entry is skipped, new one uses the default weight
+        let code_hex = "9c0db5f300deadbeef";
+        let matched: Vec<_> = db.matches(code_hex).map(|s| (s.name.as_str(), s.weight)).collect();
+        assert_eq!(matched, vec![("setHoneypotMode", ACCESS_CONTROL_PENALTY)]);
+
+        assert!(SignatureDb::parse(r#"[{ "selector": "xyz", "name": "bad" }]"#).is_err());
+        assert!(SignatureDb::parse("not json").is_err());
+    }
+
+    #[test]
+    fn test_selectors_match_their_signatures() {
+        let selector = |signature: &str| hex::encode(&alloy_primitives::keccak256(signature.as_bytes())[..4]);
+
+        for sig in &SignatureDb::embedded().signatures {
+            let signature = sig.signature.as_deref().expect("embedded signatures document their signature");
+            assert_eq!(sig.selector, selector(signature), "{}", signature);
+        }
+        for (drain_selector, signature) in DRAIN_SELECTORS {
+            assert_eq!(drain_selector, selector(signature), "{}", signature);
+        }
+    }
+}
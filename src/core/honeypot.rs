@@ -12,20 +12,42 @@
 //! If sell reverts → is_honeypot = true, risk_score = 100
 //! If blacklist functions detected → risk_score += 50
 
-use alloy_primitives::{Address, Bytes, FixedBytes, U256};
-use alloy_sol_types::{sol, SolCall};
+use alloy::signers::{local::PrivateKeySigner, SignerSync};
+use alloy_primitives::{keccak256, Address, Bytes, FixedBytes, B256, U256};
+use alloy_sol_types::{sol, SolCall, SolStruct};
 use eyre::{eyre, Result};
 use rand::Rng;
 use revm::{
     db::{CacheDB, EmptyDB},
     primitives::{
-        AccountInfo, BlockEnv, Bytecode, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult,
-        Output, SpecId, TxEnv, TxKind, KECCAK_EMPTY,
+        AccountInfo, BlockEnv, Bytecode, CfgEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg,
+        ExecutionResult, Output, SpecId, TxEnv, TxKind, KECCAK_EMPTY,
     },
-    Evm,
+    inspector_handle_register, DatabaseRef, Evm,
 };
+use serde::Serialize;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::core::heuristics::{
+    apply_heuristics, default_heuristics, AnalysisContext, CompositeRiskRule, HoneypotHeuristic,
+};
+use crate::core::pipeline::{
+    compute_tax, scan_static, AnalysisPipeline, BuyOutcome, DeclaredTax, FetchedCode, SellOutcome, SellStatus, Stage,
+    StaticScan,
+};
+use crate::core::sell_trace::{SellTrace, SellTraceInspector};
+use crate::core::storage_probe::StorageProbeInspector;
+use crate::utils::constants::{
+    get_native_symbol, loss_percent, to_decimal_units, unix_timestamp, wei_to_eth,
+    whole_eth_to_wei,
+};
+use crate::models::types::{NativeSwapAbi, SwapParams};
+use crate::providers::rpc::{check_chain_id, rpc_client_builder, rpc_http_client};
+use crate::providers::rpc_db::RpcDb;
+use crate::utils::latency::UPSTREAM_LATENCY;
 
 // ERC20 and Router interfaces
 sol! {
@@ -33,32 +55,42 @@ sol! {
     function balanceOf(address account) external view returns (uint256);
     function approve(address spender, uint256 amount) external returns (bool);
     function transfer(address to, uint256 amount) external returns (bool);
+    function transferFrom(address from, address to, uint256 amount) external returns (bool);
     
     // ERC20 metadata
     function name() external view returns (string);
     function symbol() external view returns (string);
     function decimals() external view returns (uint8);
 
-    // Uniswap V2 Router
-    function swapExactETHForTokens(
-        uint256 amountOutMin,
-        address[] calldata path,
-        address to,
-        uint256 deadline
-    ) external payable returns (uint256[] memory amounts);
-
-    function swapExactTokensForETH(
-        uint256 amountIn,
-        uint256 amountOutMin,
-        address[] calldata path,
-        address to,
-        uint256 deadline
-    ) external returns (uint256[] memory amounts);
-
+    // Uniswap V2 Router (swap calldata lives in `SwapParams::encode_*`)
     function getAmountsOut(
         uint256 amountIn,
         address[] calldata path
     ) external view returns (uint256[] memory amounts);
+
+    // Uniswap V2 Router/Factory (pair lookup)
+    function factory() external view returns (address);
+    function getPair(address tokenA, address tokenB) external view returns (address pair);
+
+    // Uniswap V2 Pair
+    function token0() external view returns (address);
+    function token1() external view returns (address);
+    function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast);
+
+    // ERC20 supply (exit curve sizing)
+    function totalSupply() external view returns (uint256);
+
+    // EIP-2612 permit (signature-based approval)
+    function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+    function nonces(address owner) external view returns (uint256);
+    function DOMAIN_SEPARATOR() external view returns (bytes32);
+    struct Permit {
+        address owner;
+        address spender;
+        uint256 value;
+        uint256 nonce;
+        uint256 deadline;
+    }
 }
 
 /// Token metadata (name, symbol, decimals)
@@ -70,6 +102,638 @@ pub struct TokenInfo {
     pub decimals: Option<u8>,
 }
 
+/// Shown for a name/symbol that returned data but nothing printable
+pub const UNREADABLE_STRING: &str = "???";
+
+/// eth_call returned bare `0x` without a JSON-RPC error object
+/// Some routers revert this way (e.g. getAmountsOut with no pair) - classified
+/// as a clean revert (no liquidity), never as a honeypot signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyCallResult;
+
+impl std::fmt::Display for EmptyCallResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "eth_call returned empty data (0x) - call reverted")
+    }
+}
+
+impl std::error::Error for EmptyCallResult {}
+
+/// eth_call reverted with a JSON-RPC error object (code 3 / "execution reverted")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallReverted {
+    pub message: String,
+}
+
+impl CallReverted {
+    /// Classify a JSON-RPC error object; None if it is not an execution revert
+    fn from_rpc_error(error: &serde_json::Value) -> Option<Self> {
+        let code = error.get("code").and_then(|c| c.as_i64());
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or_default();
+
+        if code == Some(3) || message.to_lowercase().contains("revert") {
+            Some(Self {
+                message: message.to_string(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for CallReverted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "eth_call reverted: {}", self.message)
+    }
+}
+
+impl std::error::Error for CallReverted {}
+
+/// Minimal forwarding contract used as a contract-account caller.
+/// Calldata = target (20 bytes) ++ payload: forwards CALLVALUE and bubbles up
+/// return/revert data. Shorter calldata (plain ETH transfers) just STOPs.
+const FORWARDER_RUNTIME: [u8; 53] = [
+    0x60, 0x14, 0x36, 0x10, 0x15, 0x60, 0x09, 0x57, 0x00, // if calldatasize < 20: stop
+    0x5b, 0x36, 0x60, 0x14, 0x90, 0x03, // size = calldatasize - 20
+    0x80, 0x60, 0x14, 0x60, 0x00, 0x37, // calldatacopy(0, 20, size)
+    0x60, 0x00, 0x60, 0x00, 0x91, 0x60, 0x00, 0x34, // retSize, retOffset, size, argsOffset, callvalue
+    0x60, 0x00, 0x35, 0x60, 0x60, 0x1c, 0x5a, 0xf1, // target = calldataload(0) >> 96; call(gas, ...)
+    0x3d, 0x60, 0x00, 0x80, 0x3e, // returndatacopy(0, 0, returndatasize)
+    0x3d, 0x60, 0x00, 0x90, 0x91, 0x60, 0x33, 0x57, 0xfd, // success ? jump : revert(0, rds)
+    0x5b, 0xf3, // return(0, rds)
+];
+
+/// Contract recipient for the recipient-type check: STOPs on any call,
+/// so it accepts every token hook without reverting - only its code size differs from an EOA
+const CONTRACT_RECIPIENT_RUNTIME: [u8; 1] = [0x00];
+
+/// Public tax getters read for the declared-vs-measured comparison, first readable wins
+const BUY_TAX_GETTERS: [&str; 5] = ["buyTax()", "_buyTax()", "buyFee()", "buyTotalFees()", "totalBuyFee()"];
+const SELL_TAX_GETTERS: [&str; 5] = ["sellTax()", "_sellTax()", "sellFee()", "sellTotalFees()", "totalSellFee()"];
+/// Single-fee getters, applied to whichever side has no dedicated getter
+const FLAT_TAX_GETTERS: [&str; 2] = ["_taxFee()", "taxFee()"];
+
+/// Below this per-transfer fee (%) a token is treated as fee-free
+pub const MIN_TRANSFER_FEE_PERCENT: f64 = 0.5;
+
+/// Largest per-transfer fee (%) still considered reflection (redistribution)
+pub const REFLECTION_MAX_FEE_PERCENT: f64 = 15.0;
+
+/// Buy and sell fees within this many percentage points count as symmetric
+pub const REFLECTION_SYMMETRY_TOLERANCE: f64 = 2.0;
+
+/// Asymmetric fee (%) on either side labelled as high tax
+pub const HIGH_TAX_MIN_PERCENT: f64 = 10.0;
+
+/// Round-trip loss (%) above which a sellable token is still reported as a honeypot
+pub const HONEYPOT_LOSS_PERCENT: f64 = 50.0;
+
+/// Confidence of a verdict backed by real router + token execution
+pub const CONFIDENCE_VERIFIED: u8 = 90;
+
+/// Confidence when the REVM round trip fell back to mock token / router results
+pub const CONFIDENCE_MOCKED: u8 = 30;
+
+/// Confidence of "cannot sell" when only the reverse quote failed (no executed revert)
+pub const CONFIDENCE_QUOTE_FAILED: u8 = 45;
+
+/// Warning: the local REVM run had no real router code and executed the built-in mock
+pub const WARNING_MOCK_ROUTER: &str = "simulation used mock router bytecode";
+
+/// Warning: the local REVM run had no real token code and executed the built-in mock ERC20
+pub const WARNING_MOCK_TOKEN: &str = "simulation used mock token bytecode";
+
+/// Warning: buy or sell output was too small to be real and was replaced by a fabricated amount
+pub const WARNING_MOCKED_AMOUNTS: &str = "simulated buy/sell amounts were mocked";
+
+/// Warning: the sell-side quote failed, so "cannot sell" rests on a quote rather than an executed revert
+pub const WARNING_REVERSE_QUOTE_UNAVAILABLE: &str = "reverse quote unavailable";
+
+/// Confidence cap of a honeypot verdict that a second sell run did not reproduce
+pub const CONFIDENCE_UNCONFIRMED: u8 = 20;
+
+/// Warning: the honeypot verdict flipped on the confirmation sell run (transient RPC state)
+pub const WARNING_VERDICT_UNCONFIRMED: &str = "honeypot verdict not reproduced by a second sell run";
+
+/// Confidence cap of a round trip on a token without a valid decimals()
+pub const CONFIDENCE_UNKNOWN_DECIMALS: u8 = 20;
+
+/// Warning: decimals() is missing or out of range, so token amounts cannot be labelled
+pub const WARNING_UNKNOWN_DECIMALS: &str = "token decimals unknown";
+
+/// Confidence lost per inconclusive side check (approve / allowance)
+pub const CONFIDENCE_INCONCLUSIVE_PENALTY: u8 = 15;
+
+/// Penalty when approve() and permit() approvals disagree on whether the sell goes through
+pub const PERMIT_MISMATCH_PENALTY: u8 = 30;
+
+/// Default minimum confidence for a definitive honeypot / safe verdict (`MIN_CONFIDENCE`)
+pub const DEFAULT_MIN_CONFIDENCE: u8 = 60;
+
+/// Default cap on risk factors in responses (`MAX_RISK_FACTORS`, 0 = unlimited)
+pub const DEFAULT_MAX_RISK_FACTORS: usize = 10;
+
+/// Per-chain loss cutoffs for honeypot / high-tax verdicts (see `chain_loss_cutoffs`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LossCutoffs {
+    /// Round-trip loss (%) above which the token is reported as a honeypot
+    pub honeypot_loss_percent: f64,
+    /// Fee (%) on either side labelled as high tax
+    pub high_tax_percent: f64,
+}
+
+impl Default for LossCutoffs {
+    fn default() -> Self {
+        Self {
+            honeypot_loss_percent: HONEYPOT_LOSS_PERCENT,
+            high_tax_percent: HIGH_TAX_MIN_PERCENT,
+        }
+    }
+}
+
+/// Sell sizes for the exit curve, as fractions of the hypothetical holding (basis points)
+pub const EXIT_CURVE_FRACTIONS_BPS: [u64; 4] = [100, 500, 1_000, 2_500];
+
+/// Hypothetical holding the exit curve sells from: 1% of total supply (basis points)
+pub const EXIT_HOLDING_SUPPLY_BPS: u64 = 100;
+
+/// One point of the exit curve: selling `fraction` of the holding loses `impact_pct` vs spot
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ExitPoint {
+    /// Share of the hypothetical holding sold (0.01 = 1%)
+    pub fraction: f64,
+    /// Native received vs the spot value of the tokens sold, in percent - LP fee, price
+    /// impact and sell tax together. 100 when the sell reverts
+    pub impact_pct: f64,
+    /// Why the sell reverted (max sell / max tx limits show up here)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+}
+
+/// Outcome of selling an exact token amount (`HoneypotDetector::probe_sell_forked`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SellProbe {
+    /// Token base units sold
+    pub amount_tokens: U256,
+    /// approve + sell went through
+    pub sellable: bool,
+    /// Native the router paid out (wei), when sold
+    pub native_received: Option<U256>,
+    /// approve / sell revert reason, when not sellable
+    pub revert_reason: Option<String>,
+}
+
+/// Outcome of a plain wallet-to-wallet transfer (`HoneypotDetector::test_transferability`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferProbe {
+    /// Token base units sent
+    pub amount_tokens: U256,
+    /// transfer() went through and did not return false
+    pub transferable: bool,
+    /// Growth of the recipient's balanceOf (below `amount_tokens` = fee on transfer)
+    pub received_tokens: Option<U256>,
+    /// Revert reason (or "transfer returned false"), when not transferable
+    pub revert_reason: Option<String>,
+}
+
+/// Complete local state for `HoneypotDetector::detect_offline` - nothing is fetched or mocked
+#[derive(Debug, Clone, PartialEq)]
+pub struct OfflineAnalysisInput {
+    pub token: Address,
+    /// Deployed (runtime) token bytecode
+    pub token_bytecode: Bytes,
+    /// Runtime bytecode run at the detector's primary router address
+    pub router_bytecode: Bytes,
+    /// Runtime bytecode run at the detector's WETH address
+    pub weth_bytecode: Bytes,
+    /// Uniswap V2 layout pair - the address the router derives for token / WETH
+    pub pair: Address,
+    pub pair_bytecode: Bytes,
+    /// Pair reserves in base units (1..2^112 each)
+    pub token_reserve: U256,
+    pub weth_reserve: U256,
+    /// Native (wei) spent on the simulated buy
+    pub test_amount_eth: U256,
+}
+
+/// Price impact (%) of selling `amount_in` into a V2 pool (0.3% fee) vs the spot price
+/// None for an empty pool or zero amount
+pub fn v2_sell_impact_percent(amount_in: U256, reserve_in: U256, reserve_out: U256) -> Option<f64> {
+    if amount_in.is_zero() || reserve_in.is_zero() || reserve_out.is_zero() {
+        return None;
+    }
+    let amount_with_fee = amount_in.checked_mul(U256::from(997u64))?;
+    let amount_out = amount_with_fee.checked_mul(reserve_out)?
+        / reserve_in.checked_mul(U256::from(1_000u64))?.checked_add(amount_with_fee)?;
+    // Execution price / spot price, in parts per million
+    let ratio_ppm = amount_out.checked_mul(reserve_in)?.checked_mul(U256::from(1_000_000u64))?
+        / amount_in.checked_mul(reserve_out)?;
+    let ratio_ppm: u64 = ratio_ppm.try_into().ok()?;
+    Some(100.0 - ratio_ppm as f64 / 10_000.0)
+}
+
+/// Loss (%) of selling `amount_in` for `received` vs the spot value at `reserve_in` / `reserve_out`
+/// None for an empty pool or zero amount
+fn exit_loss_percent(amount_in: U256, received: U256, reserve_in: U256, reserve_out: U256) -> Option<f64> {
+    if amount_in.is_zero() || reserve_in.is_zero() || reserve_out.is_zero() {
+        return None;
+    }
+    // Received / spot value, in parts per million
+    let ratio_ppm = received.checked_mul(reserve_in)?.checked_mul(U256::from(1_000_000u64))?
+        / amount_in.checked_mul(reserve_out)?;
+    let ratio_ppm: u64 = ratio_ppm.try_into().ok()?;
+    Some(100.0 - ratio_ppm as f64 / 10_000.0)
+}
+
+/// ERC-165 interface id of ERC1363 (transferAndCall / transferFromAndCall / approveAndCall)
+const ERC1363_INTERFACE_ID: [u8; 4] = [0xb0, 0x20, 0x2a, 0x11];
+
+/// ERC777 getter: every ERC777 token answers with a granularity >= 1
+const ERC777_GRANULARITY_GETTER: &str = "granularity()";
+
+/// Getters only rebasing / share-based tokens expose (Ampleforth, OUSD, stETH, Aave aTokens)
+/// `(address)` getters are probed with the zero address
+const REBASING_GETTERS: [&str; 5] = [
+    "gonsPerFragment()",
+    "rebasingCreditsPerToken()",
+    "getTotalShares()",
+    "sharesOf(address)",
+    "scaledBalanceOf(address)",
+];
+
+/// Warning: balances of a rebasing token move without transfers, so measured taxes are not fees
+pub const WARNING_REBASING_TOKEN: &str = "rebasing token - loss percentages are unreliable";
+
+/// Token standard beyond plain ERC20, classified before simulation (`classify_token_standard`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenStandard {
+    Erc20,
+    /// tokensToSend / tokensReceived hooks run on every transfer (reentrancy surface)
+    Erc777,
+    /// transferAndCall & co. call the recipient, plain transfers do not
+    Erc1363,
+    /// Balances change without Transfer events - buy/sell tax math does not hold
+    Rebasing,
+}
+
+impl TokenStandard {
+    /// Combine probe results; rebasing wins (it breaks the loss math), then ERC777, then ERC1363
+    pub fn classify(granularity: Option<U256>, supports_erc1363: bool, has_rebasing_getter: bool) -> Self {
+        let erc777 = granularity
+            .is_some_and(|g| g >= U256::from(1u64) && g <= U256::from(10u64).pow(U256::from(18u64)));
+        if has_rebasing_getter {
+            Self::Rebasing
+        } else if erc777 {
+            Self::Erc777
+        } else if supports_erc1363 {
+            Self::Erc1363
+        } else {
+            Self::Erc20
+        }
+    }
+
+    /// API label ("erc20" / "erc777" / "erc1363" / "rebasing")
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Erc20 => "erc20",
+            Self::Erc777 => "erc777",
+            Self::Erc1363 => "erc1363",
+            Self::Rebasing => "rebasing",
+        }
+    }
+}
+
+/// Calldata of the token standard probes: granularity(), supportsInterface(ERC1363), rebasing getters
+fn token_standard_probes() -> (Vec<u8>, Vec<u8>, Vec<Vec<u8>>) {
+    let selector = |signature: &str| keccak256(signature.as_bytes())[..4].to_vec();
+    let mut supports_erc1363 = selector("supportsInterface(bytes4)");
+    supports_erc1363.extend_from_slice(&ERC1363_INTERFACE_ID);
+    supports_erc1363.resize(4 + 32, 0);
+    let rebasing = REBASING_GETTERS
+        .iter()
+        .map(|signature| {
+            let mut calldata = selector(signature);
+            if signature.ends_with("(address)") {
+                calldata.resize(4 + 32, 0);
+            }
+            calldata
+        })
+        .collect();
+    (selector(ERC777_GRANULARITY_GETTER), supports_erc1363, rebasing)
+}
+
+/// First word of a call result (None for short / failed calls)
+fn first_word(bytes: Option<&[u8]>) -> Option<U256> {
+    bytes.filter(|b| b.len() >= 32).map(|b| U256::from_be_slice(&b[..32]))
+}
+
+/// `Error(string)` selector (require / revert with a message)
+const REVERT_ERROR_STRING: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// `Panic(uint256)` selector (assert, overflow, division by zero, ...)
+const REVERT_PANIC: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Custom errors tokens and routers commonly revert a buy / approve with
+const KNOWN_CUSTOM_ERRORS: [&str; 22] = [
+    "TradingNotOpen()",
+    "TradingNotEnabled()",
+    "TradingNotActive()",
+    "TradingClosed()",
+    "MaxWalletExceeded()",
+    "ExceedsMaxWallet()",
+    "MaxTxExceeded()",
+    "ExceedsMaxTx()",
+    "MaxTransactionExceeded()",
+    "CooldownActive()",
+    "Blacklisted()",
+    "Blacklisted(address)",
+    "BotDetected()",
+    "TransferFailed()",
+    "TransferNotAllowed()",
+    "EnforcedPause()",
+    "ERC20InsufficientBalance(address,uint256,uint256)",
+    "ERC20InsufficientAllowance(address,uint256,uint256)",
+    "ERC20InvalidReceiver(address)",
+    "ERC20InvalidSpender(address)",
+    "OwnableUnauthorizedAccount(address)",
+    "V2TooLittleReceived()",
+];
+
+/// Decoded revert data of a simulated call; carried as the error of a reverted tx
+/// (`downcast_ref::<RevertData>()` on the eyre report)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevertData {
+    /// First 4 bytes as 0x-hex (None = reverted without data)
+    pub selector: Option<String>,
+    /// Error(string) message, panic description or custom error signature
+    pub message: String,
+    /// Neither Error(string) nor Panic(uint256)
+    pub custom_error: bool,
+}
+
+impl std::fmt::Display for RevertData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.selector {
+            Some(selector) => write!(f, "Reverted [{}]: {}", selector, self.message),
+            None => write!(f, "Reverted: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for RevertData {}
+
+/// Description of a `Panic(uint256)` code
+fn panic_description(code: u64) -> &'static str {
+    match code {
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow/underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "corrupt storage byte array",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to zero-initialized function",
+        _ => "unknown panic code",
+    }
+}
+
+/// Decode revert output: Error(string), Panic(uint256), a known custom error, honeypot
+/// keywords in the raw bytes, else the bare custom error selector
+pub fn decode_revert(output: &[u8]) -> RevertData {
+    let Some(selector) = output.get(..4) else {
+        return RevertData {
+            selector: None,
+            message: if output.is_empty() {
+                "no revert data".to_string()
+            } else {
+                format!("0x{}", hex::encode(output))
+            },
+            custom_error: false,
+        };
+    };
+    let data = |message: String, custom_error: bool| RevertData {
+        selector: Some(format!("0x{}", hex::encode(selector))),
+        message,
+        custom_error,
+    };
+
+    if selector == REVERT_ERROR_STRING && output.len() >= 68 {
+        // Skip selector (4) + offset (32) + length position
+        let len_start = 36;
+        let len: usize = U256::from_be_slice(&output[len_start..len_start + 32])
+            .try_into()
+            .unwrap_or(0);
+        let str_start = len_start + 32;
+        let message = output
+            .get(str_start..str_start.saturating_add(len))
+            .and_then(|s| String::from_utf8(s.to_vec()).ok());
+        if let Some(message) = message {
+            return data(message, false);
+        }
+    }
+    if selector == REVERT_PANIC && output.len() >= 36 {
+        let code: u64 = U256::from_be_slice(&output[4..36]).try_into().unwrap_or(u64::MAX);
+        return data(format!("Panic 0x{:02x}: {}", code, panic_description(code)), false);
+    }
+    if let Some(signature) = KNOWN_CUSTOM_ERRORS
+        .iter()
+        .find(|signature| keccak256(signature.as_bytes())[..4] == *selector)
+    {
+        return data(signature.to_string(), true);
+    }
+
+    // Common revert reasons in honeypots
+    let hex_output = hex::encode(output);
+    let keyword = if hex_output.contains("626f74") {
+        // "bot"
+        Some("Bot detected / Blacklisted")
+    } else if hex_output.contains("74726164696e67") {
+        // "trading"
+        Some("Trading not enabled")
+    } else if hex_output.contains("7472616e73666572") {
+        // "transfer"
+        Some("Transfer blocked")
+    } else {
+        None
+    };
+    if let Some(keyword) = keyword {
+        return data(keyword.to_string(), selector != REVERT_ERROR_STRING);
+    }
+
+    let args = &output[4..output.len().min(68)];
+    let message = if args.is_empty() {
+        "custom error".to_string()
+    } else {
+        format!("custom error (0x{})", hex::encode(args))
+    };
+    data(message, selector != REVERT_ERROR_STRING)
+}
+
+/// Fee charged on transfers that do not revert (safe but expensive, not a honeypot)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FeeClass {
+    /// Same bounded fee on buy and sell (reflection / redistribution token)
+    Reflection { fee_percent: f64 },
+    /// Large or one-sided fee, highest side reported
+    HighTax { fee_percent: f64 },
+}
+
+impl FeeClass {
+    /// Classify measured buy/sell fees; None when the token is effectively fee-free
+    pub fn classify(buy_fee_percent: f64, sell_fee_percent: f64) -> Option<Self> {
+        Self::classify_with(buy_fee_percent, sell_fee_percent, HIGH_TAX_MIN_PERCENT)
+    }
+
+    /// `classify` with a chain-specific high-tax cutoff
+    pub fn classify_with(buy_fee_percent: f64, sell_fee_percent: f64, high_tax_percent: f64) -> Option<Self> {
+        let high = buy_fee_percent.max(sell_fee_percent);
+        if high < MIN_TRANSFER_FEE_PERCENT {
+            return None;
+        }
+        let symmetric = (buy_fee_percent - sell_fee_percent).abs() <= REFLECTION_SYMMETRY_TOLERANCE;
+        if symmetric && high <= REFLECTION_MAX_FEE_PERCENT {
+            Some(Self::Reflection { fee_percent: (buy_fee_percent + sell_fee_percent) / 2.0 })
+        } else if high >= high_tax_percent {
+            Some(Self::HighTax { fee_percent: high })
+        } else {
+            None
+        }
+    }
+
+    /// API label ("reflection" / "high_tax")
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Reflection { .. } => "reflection",
+            Self::HighTax { .. } => "high_tax",
+        }
+    }
+
+    /// Measured fee per transfer (%)
+    pub fn fee_percent(&self) -> f64 {
+        match self {
+            Self::Reflection { fee_percent } | Self::HighTax { fee_percent } => *fee_percent,
+        }
+    }
+}
+
+/// Sell gas / buy gas at or above which the sell path is flagged as asymmetric
+/// (restriction logic hidden on the sell side only)
+pub const ASYMMETRIC_GAS_RATIO: f64 = 5.0;
+
+/// Shortest block offset after the buy at which `sell_at` succeeds, with its output.
+/// Tries `first_retry` then `max_blocks` for an upper bound, then binary-searches down
+/// to the buy block (offset 0, known to revert). Assumes a lock stays open once elapsed
+pub fn search_sell_lock<T>(
+    first_retry: u64,
+    max_blocks: u64,
+    mut sell_at: impl FnMut(u64) -> Option<T>,
+) -> Option<(u64, T)> {
+    let mut bounds = vec![first_retry, max_blocks];
+    bounds.retain(|&blocks| blocks > 0);
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    // Longest offset known to revert
+    let mut reverted = 0;
+    let mut found = None;
+    for blocks in bounds {
+        match sell_at(blocks) {
+            Some(output) => {
+                found = Some((blocks, output));
+                break;
+            }
+            None => reverted = blocks,
+        }
+    }
+
+    let mut found = found?;
+    while found.0 - reverted > 1 {
+        let mid = reverted + (found.0 - reverted) / 2;
+        match sell_at(mid) {
+            Some(output) => found = (mid, output),
+            None => reverted = mid,
+        }
+    }
+    Some(found)
+}
+
+/// Gas of the sell relative to the buy (None when either side used no gas)
+pub fn sell_buy_gas_ratio(buy_gas: u64, sell_gas: u64) -> Option<f64> {
+    (buy_gas > 0 && sell_gas > 0).then(|| sell_gas as f64 / buy_gas as f64)
+}
+
+/// Sell-side fee implied by the round-trip loss once the measured buy fee is removed
+/// (retained = (1 - buy) * (1 - sell))
+pub fn implied_sell_fee(total_loss_percent: f64, buy_fee_percent: f64) -> f64 {
+    let kept_after_buy = 1.0 - buy_fee_percent / 100.0;
+    if kept_after_buy <= 0.0 {
+        return 0.0;
+    }
+    let kept = 1.0 - total_loss_percent / 100.0;
+    ((1.0 - kept / kept_after_buy) * 100.0).clamp(0.0, 100.0)
+}
+
+/// Rank of a risk factor string: 2 = ⛔ critical, 1 = "TAG: ..." finding, 0 = note
+fn risk_factor_severity(factor: &str) -> u8 {
+    if factor.starts_with('⛔') {
+        return 2;
+    }
+    let tagged = factor
+        .split_once(':')
+        .is_some_and(|(tag, _)| tag.chars().any(char::is_uppercase) && !tag.chars().any(char::is_lowercase));
+    u8::from(tagged)
+}
+
+/// The `max` most severe of `factors` plus an "and X more" line (0 = all)
+pub fn cap_risk_factors(factors: &[String], max: usize) -> Vec<String> {
+    if max == 0 || factors.len() <= max {
+        return factors.to_vec();
+    }
+    let mut ranked: Vec<&String> = factors.iter().collect();
+    ranked.sort_by_key(|f| std::cmp::Reverse(risk_factor_severity(f)));
+
+    let mut capped: Vec<String> = ranked.into_iter().take(max).cloned().collect();
+    capped.push(format!("and {} more", factors.len() - max));
+    capped
+}
+
+/// Simulation outcome a one-line verdict is built from
+/// (`HoneypotResult::summary`, the API's `?format=text`)
+pub struct SummaryFacts<'a> {
+    pub reason: &'a str,
+    pub buy_success: bool,
+    pub sell_success: bool,
+    pub buy_tax_percent: f64,
+    pub sell_tax_percent: f64,
+    pub total_loss_percent: f64,
+    pub latency_ms: u64,
+}
+
+impl SummaryFacts<'_> {
+    /// Everything after the verdict label: what failed when the round trip did not
+    /// complete, the taxes otherwise
+    pub fn details(&self) -> String {
+        if self.buy_success && self.sell_success {
+            format!(
+                "Buy Tax: {:.2}% | Sell Tax: {:.2}% | Total Loss: {:.2}% | Latency: {}ms",
+                self.buy_tax_percent, self.sell_tax_percent, self.total_loss_percent, self.latency_ms
+            )
+        } else {
+            format!(
+                "Reason: {} | Buy: {} | Sell: {} | Latency: {}ms",
+                self.reason,
+                if self.buy_success { "✅" } else { "❌" },
+                if self.sell_success { "✅" } else { "❌" },
+                self.latency_ms
+            )
+        }
+    }
+}
+
 /// Result of honeypot detection
 #[derive(Debug, Clone)]
 pub struct HoneypotResult {
@@ -87,8 +751,19 @@ pub struct HoneypotResult {
     pub buy_tax_percent: f64,
     /// Sell tax percentage (if detectable)
     pub sell_tax_percent: f64,
-    /// Total round-trip loss percentage
+    /// Total round-trip loss percentage (token taxes only - gas excluded)
     pub total_loss_percent: f64,
+    /// Round-trip loss including the gas of buy + approve + sell at the sim gas price.
+    /// None when the gas wasn't measured (eth_call quotes, reverted or skipped simulations)
+    pub net_loss_percent: Option<f64>,
+    /// Gas used by the simulated buy + approve + sell
+    pub gas_used: u64,
+    /// Sell gas / buy gas (None unless both legs executed)
+    pub sell_buy_gas_ratio: Option<f64>,
+    /// Sell costs `ASYMMETRIC_GAS_RATIO`x the buy's gas or more (hidden sell-side checks)
+    pub asymmetric_gas: bool,
+    /// Escalated by `CompositeRiskRule`: the sell passed, but enough risk dimensions co-occur
+    pub likely_honeypot: bool,
     /// Access control penalty (blacklist/setBots detected)
     pub access_control_penalty: u8,
     /// Risk factors detected
@@ -97,6 +772,101 @@ pub struct HoneypotResult {
     /// Detection latency in milliseconds
     #[allow(dead_code)]
     pub latency_ms: u64,
+    /// transferFrom beyond the approved allowance reverts (false = spender can drain holders)
+    pub allowance_enforced: bool,
+    /// Sell reverts in the buy block but succeeds a few blocks later (anti-bot delay, not a honeypot)
+    pub same_block_sell_blocked: bool,
+    /// Shortest block offset after the buy at which the sell went through (lock-on-buy),
+    /// None when it went through in the buy block or never
+    pub sell_lock_blocks: Option<u64>,
+    /// Sell reverted right after reading a seller-keyed storage flag (runtime blacklist evidence)
+    pub dynamic_blacklist_detected: bool,
+    /// Transfer fee class when buy and sell both succeed (reflection vs high tax)
+    pub fee_class: Option<FeeClass>,
+    /// Round trip from a contract account behaves differently than from an EOA
+    pub caller_type_gated: bool,
+    /// Direct transfer to the pair reverts while a transfer to a fresh EOA succeeds
+    pub transfer_to_pair_blocked: bool,
+    /// Token accepted an EIP-2612 permit (only tested with `with_permit_check`)
+    pub permit_supported: bool,
+    /// approve() and permit() approvals disagree on whether the sell goes through
+    pub permit_mismatch: bool,
+    /// Sell succeeds at one gas price / basefee and reverts at the other
+    /// (only tested with `with_gas_price_check`)
+    pub gas_price_gated: bool,
+    /// A holder credited by a direct balanceOf storage write (airdrop / transfer, no router buy)
+    /// cannot sell (only tested with `with_direct_holder_check`)
+    pub direct_holder_sell_blocked: bool,
+    /// Token transfer to an EOA succeeds, the same transfer to a contract reverts
+    /// (only tested with `with_contract_recipient_check`)
+    pub contract_recipient_blocked: bool,
+    /// Seller's native balance grew by the router-reported sell proceeds
+    /// (false = the sell "succeeded" but the output went to another address)
+    pub proceeds_received: bool,
+    /// No router had a pair with liquidity, so nothing was simulated (see `test_transferability`)
+    pub no_pair: bool,
+    /// Taxes the token advertises through public getters (only read on verified round trips)
+    pub declared_tax: DeclaredTax,
+    /// Measured buy/sell tax exceeds the advertised one (sellable, but deceptive)
+    pub declared_vs_measured_tax_mismatch: bool,
+    /// Router the buy/sell actually executed through (None if no venue was tested)
+    pub simulated_router: Option<Address>,
+    /// Name of the DEX behind `simulated_router`
+    pub simulated_dex: Option<String>,
+    /// Chain block the RPC quotes ran against (None for local REVM-only sims)
+    pub block_number: Option<u64>,
+    /// Unix timestamp (seconds) when the verdict was computed
+    pub analyzed_at: u64,
+    /// How much real execution backs the verdict (0-100, 0 = nothing simulated)
+    pub confidence: u8,
+    /// Provenance caveats (mock bytecode, missing reverse quote) for downweighting the verdict
+    pub warnings: Vec<String>,
+    /// ERC20 / ERC777 / ERC1363 / rebasing (None = not classified)
+    pub token_standard: Option<TokenStandard>,
+    /// Decoded revert of a failed buy / approve (selector + reason)
+    pub revert: Option<RevertData>,
+    /// Raw amounts of the round trip (None when no real buy executed or amounts were mocked)
+    pub sim_detail: Option<SimDetail>,
+}
+
+/// Raw outcome of the simulated round trip, so the tax / loss math can be checked by hand
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimDetail {
+    /// Tokens credited to the test account by the buy (quoted on the eth_call path)
+    pub tokens_bought: U256,
+    /// Native the router paid out for the sell (zero when the sell did not go through)
+    pub eth_returned: U256,
+    /// Test account's token balance after the sell (None if unreadable or only quoted)
+    pub final_token_balance: Option<U256>,
+    /// Test account's native balance after the sell (None if unreadable or only quoted)
+    pub final_eth_balance: Option<U256>,
+}
+
+impl SimDetail {
+    /// None when the buy or the sell stood in mocked amounts
+    pub fn from_outcomes(buy: &BuyOutcome, sell: &SellOutcome) -> Option<Self> {
+        let eth_returned = match sell.status {
+            SellStatus::Sold { mocked: true, .. } => return None,
+            SellStatus::Sold { native_received, mocked: false } => native_received,
+            SellStatus::Reverted(_) | SellStatus::Failed(_) => U256::ZERO,
+        };
+        (!buy.mocked).then_some(Self {
+            tokens_bought: buy.tokens_received,
+            eth_returned,
+            final_token_balance: sell.final_token_balance,
+            final_eth_balance: sell.final_native_balance,
+        })
+    }
+
+    /// Buy and sell amounts of the eth_call quotes (no committed state, so no final balances)
+    pub fn from_quotes(tokens_quoted: U256, native_quoted: U256) -> Self {
+        Self {
+            tokens_bought: tokens_quoted,
+            eth_returned: native_quoted,
+            final_token_balance: None,
+            final_eth_balance: None,
+        }
+    }
 }
 
 impl HoneypotResult {
@@ -117,9 +887,39 @@ impl HoneypotResult {
             buy_tax_percent: buy_tax,
             sell_tax_percent: sell_tax,
             total_loss_percent: buy_tax + sell_tax,
+            net_loss_percent: None,
+            gas_used: 0,
+            sell_buy_gas_ratio: None,
+            asymmetric_gas: false,
+            likely_honeypot: false,
             access_control_penalty: access_penalty,
             risk_factors,
             latency_ms,
+            allowance_enforced: true,
+            same_block_sell_blocked: false,
+            sell_lock_blocks: None,
+            sim_detail: None,
+            dynamic_blacklist_detected: false,
+            fee_class: None,
+            caller_type_gated: false,
+            transfer_to_pair_blocked: false,
+            permit_supported: false,
+            permit_mismatch: false,
+            gas_price_gated: false,
+            direct_holder_sell_blocked: false,
+            contract_recipient_blocked: false,
+            proceeds_received: true,
+            no_pair: false,
+            declared_tax: DeclaredTax::default(),
+            declared_vs_measured_tax_mismatch: false,
+            simulated_router: None,
+            simulated_dex: None,
+            block_number: None,
+            analyzed_at: unix_timestamp(),
+            confidence: CONFIDENCE_VERIFIED,
+            warnings: Vec::new(),
+            token_standard: None,
+            revert: None,
         }
     }
 
@@ -142,35 +942,192 @@ impl HoneypotResult {
             buy_tax_percent: if buy_success { 0.0 } else { 100.0 },
             sell_tax_percent: 100.0,
             total_loss_percent: 100.0,
+            net_loss_percent: None,
+            gas_used: 0,
+            sell_buy_gas_ratio: None,
+            asymmetric_gas: false,
+            likely_honeypot: false,
             access_control_penalty: access_penalty,
             risk_factors,
             latency_ms,
+            allowance_enforced: true,
+            same_block_sell_blocked: false,
+            sell_lock_blocks: None,
+            sim_detail: None,
+            dynamic_blacklist_detected: false,
+            fee_class: None,
+            caller_type_gated: false,
+            transfer_to_pair_blocked: false,
+            permit_supported: false,
+            permit_mismatch: false,
+            gas_price_gated: false,
+            direct_holder_sell_blocked: false,
+            contract_recipient_blocked: false,
+            proceeds_received: true,
+            no_pair: false,
+            declared_tax: DeclaredTax::default(),
+            declared_vs_measured_tax_mismatch: false,
+            simulated_router: None,
+            simulated_dex: None,
+            block_number: None,
+            analyzed_at: unix_timestamp(),
+            confidence: CONFIDENCE_VERIFIED,
+            warnings: Vec::new(),
+            token_standard: None,
+            revert: None,
+        }
+    }
+
+    /// Create an UNVERIFIED result for pools below the min-liquidity gate.
+    /// No simulation is run, so buy/sell are both false (scored as unverified)
+    pub fn too_illiquid(liquidity_usd: f64, min_liquidity_usd: f64, latency_ms: u64) -> Self {
+        Self {
+            is_honeypot: false,
+            reason: format!(
+                "⚠️ UNVERIFIED - TOO ILLIQUID: ${:.2} liquidity is below the ${:.2} minimum",
+                liquidity_usd, min_liquidity_usd
+            ),
+            buy_success: false,
+            sell_success: false,
+            sell_reverted: false,
+            buy_tax_percent: 0.0,
+            sell_tax_percent: 0.0,
+            total_loss_percent: 0.0,
+            net_loss_percent: None,
+            gas_used: 0,
+            sell_buy_gas_ratio: None,
+            asymmetric_gas: false,
+            likely_honeypot: false,
+            access_control_penalty: 0,
+            risk_factors: vec![format!("Liquidity ${:.2} < ${:.2} minimum", liquidity_usd, min_liquidity_usd)],
+            latency_ms,
+            allowance_enforced: true,
+            same_block_sell_blocked: false,
+            sell_lock_blocks: None,
+            sim_detail: None,
+            dynamic_blacklist_detected: false,
+            fee_class: None,
+            caller_type_gated: false,
+            transfer_to_pair_blocked: false,
+            permit_supported: false,
+            permit_mismatch: false,
+            gas_price_gated: false,
+            direct_holder_sell_blocked: false,
+            contract_recipient_blocked: false,
+            proceeds_received: true,
+            no_pair: false,
+            declared_tax: DeclaredTax::default(),
+            declared_vs_measured_tax_mismatch: false,
+            simulated_router: None,
+            simulated_dex: None,
+            block_number: None,
+            analyzed_at: unix_timestamp(),
+            confidence: 0,
+            warnings: Vec::new(),
+            token_standard: None,
+            revert: None,
+        }
+    }
+
+    /// Token has no valid decimals(): the round trip still ran, but the verdict is capped
+    /// to Unverified (evidence such as a reverted sell is kept)
+    pub fn with_unknown_decimals(mut self) -> Self {
+        self.risk_factors.push("decimals() missing or out of range".to_string());
+        self.confidence = self.confidence.min(CONFIDENCE_UNKNOWN_DECIMALS);
+        self.with_warning(WARNING_UNKNOWN_DECIMALS)
+    }
+
+    /// Simulation stages disabled by the analysis pipeline - bytecode checks only
+    pub fn not_simulated(access_penalty: u8, risk_factors: Vec<String>, latency_ms: u64) -> Self {
+        Self {
+            reason: "⚠️ UNVERIFIED - simulation disabled by the analysis pipeline".to_string(),
+            buy_success: false,
+            sell_success: false,
+            confidence: 0,
+            ..Self::safe(0.0, 0.0, access_penalty, risk_factors, latency_ms)
         }
     }
 
     /// Summary for display
-    #[allow(dead_code)]
     pub fn summary(&self) -> String {
-        if self.is_honeypot {
-            format!(
-                "🚨 HONEYPOT DETECTED | Reason: {} | Buy: {} | Sell: {} | Reverted: {} | Latency: {}ms",
-                self.reason,
-                if self.buy_success { "✅" } else { "❌" },
-                if self.sell_success { "✅" } else { "❌" },
-                if self.sell_reverted { "⛔" } else { "✅" },
-                self.latency_ms
-            )
-        } else {
-            format!(
-                "✅ SAFE | Buy Tax: {:.2}% | Sell Tax: {:.2}% | Total Loss: {:.2}% | AC Penalty: {} | Latency: {}ms",
-                self.buy_tax_percent,
-                self.sell_tax_percent,
-                self.total_loss_percent,
-                self.access_control_penalty,
-                self.latency_ms
-            )
+        let facts = SummaryFacts {
+            reason: &self.reason,
+            buy_success: self.buy_success,
+            sell_success: self.sell_success,
+            buy_tax_percent: self.buy_tax_percent,
+            sell_tax_percent: self.sell_tax_percent,
+            total_loss_percent: self.total_loss_percent,
+            latency_ms: self.latency_ms,
+        };
+        let label = if self.is_honeypot { "🚨 HONEYPOT DETECTED" } else { "✅ SAFE" };
+        format!("{} | {} | AC Penalty: {}", label, facts.details(), self.access_control_penalty)
+    }
+
+    /// Record which block the verdict was computed at
+    pub fn at_block(mut self, block_number: Option<u64>) -> Self {
+        self.block_number = block_number;
+        self
+    }
+
+    /// Override how much evidence backs the verdict
+    pub fn with_confidence(mut self, confidence: u8) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    /// Record a provenance caveat (deduplicated)
+    pub fn with_warning(mut self, warning: &str) -> Self {
+        if !self.warnings.iter().any(|w| w == warning) {
+            self.warnings.push(warning.to_string());
+        }
+        self
+    }
+
+    /// Honeypot verdict the confirmation run disagreed with: capped below any
+    /// definitive confidence so it surfaces as Unverified instead
+    pub fn unconfirmed(mut self) -> Self {
+        self.confidence = self.confidence.min(CONFIDENCE_UNCONFIRMED);
+        self.risk_factors
+            .push("UNCONFIRMED: second sell run did not reproduce the honeypot verdict".to_string());
+        self.with_warning(WARNING_VERDICT_UNCONFIRMED)
+    }
+
+    /// Record the classified token standard: rebasing tokens get a warning (loss math
+    /// unreliable), ERC777 a reentrancy risk factor
+    pub fn with_token_standard(mut self, standard: TokenStandard) -> Self {
+        self.token_standard = Some(standard);
+        match standard {
+            TokenStandard::Rebasing => {
+                self.risk_factors
+                    .push("REBASING: balances change without transfers - buy/sell tax math is unreliable".to_string());
+                self.with_warning(WARNING_REBASING_TOKEN)
+            }
+            TokenStandard::Erc777 => {
+                self.risk_factors
+                    .push("ERC777 HOOKS: tokensToSend/tokensReceived hooks can reenter on every transfer".to_string());
+                self
+            }
+            TokenStandard::Erc20 | TokenStandard::Erc1363 => self,
         }
     }
+
+    /// Confident enough to report `is_honeypot` as a definitive verdict
+    pub fn is_verified(&self, min_confidence: u8) -> bool {
+        self.confidence >= min_confidence
+    }
+
+    /// The `max` most severe risk factors plus an "and X more" line (0 = all)
+    /// Severity: ⛔ factors, then TAGGED: findings, then notes; ties keep detection order
+    pub fn capped_risk_factors(&self, max: usize) -> Vec<String> {
+        cap_risk_factors(&self.risk_factors, max)
+    }
+
+    /// Record the DEX/router the verdict was simulated against
+    pub fn via_dex(mut self, dex: &DexInfo) -> Self {
+        self.simulated_router = Some(dex.address);
+        self.simulated_dex = Some(dex.name.clone());
+        self
+    }
 }
 
 /// DEX Router info for multi-DEX support
@@ -195,18 +1152,195 @@ pub struct HoneypotDetector {
     routers: Vec<DexInfo>,
     /// HTTP RPC URL for fetching bytecode
     rpc_url: String,
+    /// Native balance funded to the random test account
+    test_account_balance: U256,
+    /// WETH contract native balance in the mocked REVM state (`detect`, `trace_sell`).
+    /// Verdicts from `detect_async` / `detect_forked` read the chain's real WETH, so only
+    /// the mocked runs need funding; `trace_sell_async` swaps in the pair's forked reserve
+    weth_liquidity: U256,
+    /// Caller-supplied decimals for tokens without a usable decimals()
+    decimals_override: Option<u8>,
+    /// Historical block to analyze (None = latest)
+    target_block: Option<u64>,
+    /// Blocks to advance before retrying a sell that reverted in the buy block
+    sell_delay_blocks: u64,
+    /// Longest post-buy sell lock searched for when the delayed retry also reverts
+    sell_lock_max_blocks: u64,
+    /// Repeat the REVM round trip from a contract account and compare with the EOA run
+    caller_type_check: bool,
+    /// Also sell through an EIP-2612 permit approval and compare with the approve() run
+    permit_check: bool,
+    /// Derive simulated callers from this seed instead of at random (reproducible runs)
+    caller_seed: Option<u64>,
+    /// Repeat the sell at a low and a high gas price and compare
+    gas_price_check: bool,
+    /// Also sell from a holder credited by a balanceOf storage write instead of a buy
+    direct_holder_check: bool,
+    /// Repeat the sell paying out to a contract recipient and compare with the EOA recipient
+    contract_recipient_check: bool,
+    /// Re-run the buy → sell simulation once before reporting a honeypot
+    confirm_verdict: bool,
+    /// EVM hardfork the REVM simulation runs with (per chain)
+    spec_id: SpecId,
+    /// Honeypot / high-tax loss cutoffs (per chain)
+    loss_cutoffs: LossCutoffs,
+    /// Largest token bytecode accepted from RPC (bytes); larger code is rejected
+    max_bytecode_size: usize,
+    /// Checks run after the core simulation (access control scan built in)
+    heuristics: Vec<Box<dyn HoneypotHeuristic>>,
+    /// Escalates sellable tokens with co-occurring risk dimensions (after the heuristics)
+    composite_rule: CompositeRiskRule,
+    /// Stages of the REVM check that run (all by default)
+    pipeline: AnalysisPipeline,
+    /// Called as each stage of `detect_async` finishes (streamed progress)
+    progress: Option<StageProgress>,
+}
+
+/// Stage-finished callback (`HoneypotDetector::with_progress`)
+pub type StageProgress = Arc<dyn Fn(Stage) + Send + Sync>;
+
+/// Penalty for tokens whose bytecode contains SELFDESTRUCT
+pub const SELFDESTRUCT_PENALTY: u8 = 60;
+
+/// Block number used by local REVM sims when no target block is set
+const DEFAULT_SIM_BLOCK: u64 = 19_000_000;
+
+/// EIP-170 contract size limit (bytes)
+pub const EIP170_MAX_CODE_SIZE: usize = 24_576;
+
+/// Default cap on fetched bytecode: EIP-170 plus margin for chains with larger limits
+pub const DEFAULT_MAX_BYTECODE_SIZE: usize = EIP170_MAX_CODE_SIZE + 8_192;
+
+/// Blocks the sell is pushed forward when it reverts in the buy block (0 = disabled)
+pub const DEFAULT_SELL_DELAY_BLOCKS: u64 = 2;
+
+/// Longest sell lock searched for (0 = only retry after `DEFAULT_SELL_DELAY_BLOCKS`):
+/// one day of 12s blocks
+pub const DEFAULT_SELL_LOCK_MAX_BLOCKS: u64 = 7_200;
+
+/// Simulated block time used to advance the timestamp with the block number
+const SIM_BLOCK_TIME_SECS: u64 = 12;
+
+/// Penalty for tokens that block sells in the buy block (anti-bot)
+pub const SAME_BLOCK_SELL_PENALTY: u8 = 10;
+
+/// Penalty for tokens that lock sells longer than the anti-bot delay after a buy
+pub const SELL_LOCK_PENALTY: u8 = 25;
+
+/// Gas price (and basefee) of every simulated transaction: 20 gwei
+pub const SIM_GAS_PRICE_WEI: u64 = 20_000_000_000;
+
+/// Gas-price gate probe: the sell is repeated at a quiet-network price...
+pub const GAS_GATE_LOW_PRICE_WEI: u64 = 1_000_000_000;
+
+/// ...and at a congested-network / sniper price (basefee pinned to the gas price)
+pub const GAS_GATE_HIGH_PRICE_WEI: u64 = 300_000_000_000;
+
+/// Storage slots probed for the balanceOf mapping (Solidity and Vyper layouts)
+const MAX_BALANCE_SLOT_PROBE: u64 = 32;
+
+/// UniswapV2Pair storage slots seeded by `detect_offline`
+const V2_PAIR_TOKEN0_SLOT: u64 = 6;
+const V2_PAIR_TOKEN1_SLOT: u64 = 7;
+/// reserve0 (uint112) | reserve1 (uint112) << 112 | blockTimestampLast (uint32) << 224
+const V2_PAIR_RESERVES_SLOT: u64 = 8;
+/// Reentrancy lock: swap() reverts "LOCKED" unless it holds 1
+const V2_PAIR_UNLOCKED_SLOT: u64 = 12;
+
+/// Largest decimals() value accepted as sane (anything above is garbage/malicious)
+pub const MAX_TOKEN_DECIMALS: u8 = 36;
+
+/// Penalty for tokens whose transferFrom ignores allowance
+pub const ALLOWANCE_BYPASS_PENALTY: u8 = 50;
+
+/// Default funding for the simulated test account (whole ETH)
+pub const DEFAULT_TEST_ACCOUNT_BALANCE_ETH: u64 = 100;
+/// Default WETH liquidity in the mocked simulation state (whole ETH)
+pub const DEFAULT_WETH_LIQUIDITY_ETH: u64 = 1_000;
+
+lazy_static::lazy_static! {
+    /// `eth_chainId` answered by each detector RPC URL - queried once per process
+    static ref RPC_CHAIN_IDS: dashmap::DashMap<String, u64> = dashmap::DashMap::new();
 }
 
+/// Router / token / pair bytecode for the contract-caller rerun
+type CallerRunInputs = (Option<Bytes>, Option<Bytes>, Option<Bytes>);
+
 /// Result of sell simulation with revert detection
 enum SimSellResult {
     Success(U256),
     Reverted(String),
 }
 
-impl HoneypotDetector {
-    /// Create detector for Ethereum mainnet
-    pub fn mainnet() -> Self {
-        Self::for_chain(1).unwrap_or_else(|| Self {
+/// Result of approve simulation against live state
+enum ApproveCheck {
+    Allowed,
+    /// approve reverted or returned false - holder can never grant allowance
+    Blocked(String),
+    /// RPC/transport problem - no verdict
+    Unknown(String),
+}
+
+/// Result of selling through an EIP-2612 `permit` approval instead of `approve`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PermitCheck {
+    /// No DOMAIN_SEPARATOR() / nonces() - not an EIP-2612 token
+    Unsupported,
+    /// permit accepted and the router sold the holder's tokens
+    Sellable,
+    /// permit accepted, but the sell relying on it reverted
+    SellBlocked(String),
+    /// EIP-2612 views exist but permit() reverted (non-standard permit, e.g. DAI-style)
+    Rejected(String),
+    /// Permit holder could not be set up (funding transfer failed) - no verdict
+    Unknown(String),
+}
+
+impl PermitCheck {
+    /// Risk factor when the permit sell (buy block only) and the approve() sell disagree
+    fn mismatch_factor(&self, sell: &SellOutcome) -> Option<String> {
+        let permit_sold = match self {
+            PermitCheck::Sellable => true,
+            PermitCheck::SellBlocked(_) => false,
+            _ => return None,
+        };
+        if !sell.differs_from_buy_block_sell(permit_sold) {
+            return None;
+        }
+        Some(match self {
+            PermitCheck::SellBlocked(reason) => {
+                format!("APPROVAL PATH GATED: approve() sells, sell after permit() reverts ({})", reason)
+            }
+            _ => "APPROVAL PATH GATED: sell after approve() reverts, permit() sells".to_string(),
+        })
+    }
+}
+
+/// Result of selling from a holder credited by a storage write (no router buy)
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DirectHolderCheck {
+    /// Directly credited holder approved the router and sold (native received)
+    Sellable(U256),
+    /// Approve or sell reverted for the directly credited holder
+    SellBlocked(String),
+    /// balanceOf slot not found or holder could not be credited - no verdict
+    Unknown(String),
+}
+
+/// Result of the transferFrom-without-allowance check against live state
+enum AllowanceCheck {
+    /// transferFrom reverted or returned false
+    Enforced,
+    /// transferFrom moved tokens the spender was never approved for
+    Bypassed,
+    /// No pair/balance to test with, or RPC problem - no verdict
+    Unknown(String),
+}
+
+impl HoneypotDetector {
+    /// Create detector for Ethereum mainnet
+    pub fn mainnet() -> Self {
+        Self::for_chain(1).unwrap_or_else(|| Self {
             chain_id: 1,
             chain_name: "Ethereum".to_string(),
             native_symbol: "ETH".to_string(),
@@ -216,6 +1350,26 @@ impl HoneypotDetector {
             ],
             rpc_url: std::env::var("ETH_HTTP_URL")
                 .unwrap_or_else(|_| "https://eth.llamarpc.com".to_string()),
+            test_account_balance: whole_eth_to_wei(DEFAULT_TEST_ACCOUNT_BALANCE_ETH),
+            weth_liquidity: whole_eth_to_wei(DEFAULT_WETH_LIQUIDITY_ETH),
+            decimals_override: None,
+            target_block: None,
+            sell_delay_blocks: DEFAULT_SELL_DELAY_BLOCKS,
+            sell_lock_max_blocks: DEFAULT_SELL_LOCK_MAX_BLOCKS,
+            caller_type_check: false,
+            permit_check: false,
+            caller_seed: None,
+            gas_price_check: false,
+            direct_holder_check: false,
+            contract_recipient_check: false,
+            confirm_verdict: false,
+            spec_id: crate::models::config::chain_spec_id(1),
+            loss_cutoffs: LossCutoffs::default(),
+            max_bytecode_size: DEFAULT_MAX_BYTECODE_SIZE,
+            heuristics: default_heuristics(),
+            composite_rule: CompositeRiskRule::from_env(),
+            pipeline: AnalysisPipeline::default(),
+            progress: None,
         })
     }
 
@@ -230,6 +1384,26 @@ impl HoneypotDetector {
             weth: config.weth,
             routers: config.routers.into_iter().map(|r| DexInfo { name: r.name, address: r.address }).collect(),
             rpc_url: config.rpc_url,
+            test_account_balance: whole_eth_to_wei(DEFAULT_TEST_ACCOUNT_BALANCE_ETH),
+            weth_liquidity: whole_eth_to_wei(DEFAULT_WETH_LIQUIDITY_ETH),
+            decimals_override: None,
+            target_block: None,
+            sell_delay_blocks: DEFAULT_SELL_DELAY_BLOCKS,
+            sell_lock_max_blocks: DEFAULT_SELL_LOCK_MAX_BLOCKS,
+            caller_type_check: false,
+            permit_check: false,
+            caller_seed: None,
+            gas_price_check: false,
+            direct_holder_check: false,
+            contract_recipient_check: false,
+            confirm_verdict: false,
+            spec_id: config.spec_id,
+            loss_cutoffs: config.loss_cutoffs,
+            max_bytecode_size: DEFAULT_MAX_BYTECODE_SIZE,
+            heuristics: default_heuristics(),
+            composite_rule: CompositeRiskRule::from_env(),
+            pipeline: AnalysisPipeline::default(),
+            progress: None,
         })
     }
 
@@ -239,11 +1413,31 @@ impl HoneypotDetector {
         Self {
             chain_id,
             chain_name: "Custom".to_string(),
-            native_symbol: "ETH".to_string(),
+            native_symbol: get_native_symbol(chain_id).to_string(),
             weth,
             routers: vec![DexInfo { name: "Custom".to_string(), address: router }],
             rpc_url: std::env::var("ETH_HTTP_URL")
                 .unwrap_or_else(|_| "https://eth.llamarpc.com".to_string()),
+            test_account_balance: whole_eth_to_wei(DEFAULT_TEST_ACCOUNT_BALANCE_ETH),
+            weth_liquidity: whole_eth_to_wei(DEFAULT_WETH_LIQUIDITY_ETH),
+            decimals_override: None,
+            target_block: None,
+            sell_delay_blocks: DEFAULT_SELL_DELAY_BLOCKS,
+            sell_lock_max_blocks: DEFAULT_SELL_LOCK_MAX_BLOCKS,
+            caller_type_check: false,
+            permit_check: false,
+            caller_seed: None,
+            gas_price_check: false,
+            direct_holder_check: false,
+            contract_recipient_check: false,
+            confirm_verdict: false,
+            spec_id: crate::models::config::chain_spec_id(chain_id),
+            loss_cutoffs: crate::models::config::chain_loss_cutoffs(chain_id),
+            max_bytecode_size: DEFAULT_MAX_BYTECODE_SIZE,
+            heuristics: default_heuristics(),
+            composite_rule: CompositeRiskRule::from_env(),
+            pipeline: AnalysisPipeline::default(),
+            progress: None,
         }
     }
 
@@ -256,6 +1450,237 @@ impl HoneypotDetector {
         self
     }
 
+    /// Override the native balance funded to the simulated test account
+    /// (never below twice the test amount, see `build_simulation_db`)
+    pub fn with_test_balance(mut self, balance: U256) -> Self {
+        self.test_account_balance = balance;
+        self
+    }
+
+    /// Use caller-supplied decimals instead of the token's decimals()
+    /// For non-standard ERC20s without (or with a broken) decimals()
+    pub fn with_decimals_override(mut self, decimals: u8) -> Self {
+        self.decimals_override = Some(decimals);
+        self
+    }
+
+    /// Override the honeypot / high-tax loss cutoffs
+    #[allow(dead_code)]
+    pub fn with_loss_cutoffs(mut self, cutoffs: LossCutoffs) -> Self {
+        self.loss_cutoffs = cutoffs;
+        self
+    }
+
+    /// Blocks to advance before retrying a sell that reverted in the buy block
+    /// (tokens gating sells by blocks since acquisition). 0 = sell only in the buy block
+    #[allow(dead_code)]
+    pub fn with_sell_delay_blocks(mut self, blocks: u64) -> Self {
+        self.sell_delay_blocks = blocks;
+        self
+    }
+
+    /// Longest lock-on-buy searched for when the sell still reverts `sell_delay_blocks`
+    /// later: the shortest working offset up to `blocks` is binary-searched (~log2 extra sells).
+    /// 0 = no search past the anti-bot retry
+    pub fn with_sell_lock_max_blocks(mut self, blocks: u64) -> Self {
+        self.sell_lock_max_blocks = blocks;
+        self
+    }
+
+    /// Also buy/sell from a contract account (forwarder) and flag tokens that
+    /// treat contract and EOA callers differently. Costs a second REVM round trip
+    #[allow(dead_code)]
+    pub fn with_caller_type_check(mut self, enabled: bool) -> Self {
+        self.caller_type_check = enabled;
+        self
+    }
+
+    /// Also approve the router via a signed EIP-2612 `permit` and sell, flagging tokens
+    /// that block one approval mechanism but not the other. Costs a second sell
+    #[allow(dead_code)]
+    pub fn with_permit_check(mut self, enabled: bool) -> Self {
+        self.permit_check = enabled;
+        self
+    }
+
+    /// Run only the enabled stages of the REVM check (e.g. `AnalysisPipeline::static_only()`)
+    pub fn with_pipeline(mut self, pipeline: AnalysisPipeline) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    /// Call `progress` as each stage of `detect_async` finishes (fetch, scan, buy, sell,
+    /// approve, score); a verdict confirmation re-run is not reported
+    pub fn with_progress(mut self, progress: StageProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    fn report(&self, stage: Stage) {
+        if let Some(progress) = &self.progress {
+            progress(stage);
+        }
+    }
+
+    /// Also sell at GAS_GATE_LOW_PRICE_WEI and GAS_GATE_HIGH_PRICE_WEI, flagging tokens
+    /// whose sell only goes through at one of them. Costs two extra sells
+    #[allow(dead_code)]
+    pub fn with_gas_price_check(mut self, enabled: bool) -> Self {
+        self.gas_price_check = enabled;
+        self
+    }
+
+    /// Also credit a fresh account with the bought amount by writing the token's balanceOf
+    /// storage slot and sell from it, flagging tokens that only let router buyers sell.
+    /// Costs an approve and a sell plus the slot probe
+    #[allow(dead_code)]
+    pub fn with_direct_holder_check(mut self, enabled: bool) -> Self {
+        self.direct_holder_check = enabled;
+        self
+    }
+
+    /// Also transfer the bought tokens to a contract account and to an EOA, flagging tokens
+    /// that refuse contract recipients (code-size gates against aggregators and MEV bots).
+    /// Costs two extra transfers
+    pub fn with_contract_recipient_check(mut self, enabled: bool) -> Self {
+        self.contract_recipient_check = enabled;
+        self
+    }
+
+    /// Re-run the buy → sell simulation when it reports a honeypot and keep the verdict
+    /// only if both runs agree; otherwise it is downgraded (see `HoneypotResult::unconfirmed`).
+    /// Guards against transient RPC failures read as reverts. Costs a second run on honeypots
+    pub fn with_verdict_confirmation(mut self, enabled: bool) -> Self {
+        self.confirm_verdict = enabled;
+        self
+    }
+
+    /// Derive the test caller (and other simulated accounts) from `seed` instead of
+    /// `thread_rng`, so caller-dependent behavior reproduces across runs
+    #[allow(dead_code)]
+    pub fn with_caller_seed(mut self, seed: u64) -> Self {
+        self.caller_seed = Some(seed);
+        self
+    }
+
+    /// Cap on bytecode accepted from eth_getCode (bytes)
+    #[allow(dead_code)]
+    pub fn with_max_bytecode_size(mut self, bytes: usize) -> Self {
+        self.max_bytecode_size = bytes;
+        self
+    }
+
+    /// Register a custom heuristic, run after the built-in ones
+    #[allow(dead_code)]
+    pub fn with_heuristic<H: HoneypotHeuristic + 'static>(mut self, heuristic: H) -> Self {
+        self.heuristics.push(Box::new(heuristic));
+        self
+    }
+
+    /// Rule flagging a passed sell as a likely honeypot (off by default: `min_dimensions: 0`)
+    pub fn with_composite_rule(mut self, rule: CompositeRiskRule) -> Self {
+        self.composite_rule = rule;
+        self
+    }
+
+    /// Run registered heuristics against a simulation result, merging their factors,
+    /// then the composite rule
+    fn run_heuristics(
+        &self,
+        token: Address,
+        bytecode: Option<&Bytes>,
+        mut result: HoneypotResult,
+    ) -> HoneypotResult {
+        if !self.pipeline.runs(Stage::Score) {
+            return result;
+        }
+        let ctx = AnalysisContext {
+            token,
+            chain_id: self.chain_id,
+            bytecode,
+            result: &result,
+        };
+        let (penalty, factors) = apply_heuristics(&self.heuristics, &ctx);
+        result.access_control_penalty = result.access_control_penalty.saturating_add(penalty).min(100);
+        result.risk_factors.extend(factors);
+        if self.composite_rule.apply(&mut result, &self.loss_cutoffs) {
+            warn!("⛔ {:?}: {}", token, result.reason);
+        }
+        result
+    }
+
+    /// Analyze state at a historical block instead of latest
+    /// Applies to eth_call, eth_getCode and the local REVM sim block
+    #[allow(dead_code)]
+    pub fn at_historical_block(mut self, block: u64) -> Self {
+        self.target_block = Some(block);
+        self
+    }
+
+    /// JSON-RPC block tag for the target block ("latest" or hex number)
+    fn block_tag(&self) -> String {
+        match self.target_block {
+            Some(block) => format!("0x{:x}", block),
+            None => "latest".to_string(),
+        }
+    }
+
+    /// Caller-supplied decimals (if any)
+    pub fn decimals_override(&self) -> Option<u8> {
+        self.decimals_override
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Native token symbol (e.g., "ETH", "AVAX")
+    pub fn native_symbol(&self) -> &str {
+        &self.native_symbol
+    }
+
+    /// Wrapped native token the pair is quoted against (WETH / WAVAX / WBNB)
+    pub fn weth(&self) -> Address {
+        self.weth
+    }
+
+    /// Routers tried in order (priority router first)
+    pub fn routers(&self) -> &[DexInfo] {
+        &self.routers
+    }
+
+    /// Read the pair's real WETH reserve (WETH.balanceOf(pair)) from RPC
+    /// Replaces the default mock liquidity in `trace_sell_async` so the trace is funded like
+    /// the verdict it explains (eth_call / forked runs see the same reserve on chain)
+    async fn forked_weth_liquidity(&self, pair: Address) -> Option<U256> {
+        let calldata = balanceOfCall { account: pair }.abi_encode();
+        let bytes = self.eth_call_raw(self.weth, Bytes::from(calldata)).await.ok()?;
+        if bytes.len() < 32 {
+            return None;
+        }
+        Some(U256::from_be_slice(&bytes[..32])).filter(|r| !r.is_zero())
+    }
+
+    /// Price impact (%) of selling exactly `amount_tokens` (base units, see
+    /// `from_decimal_units`) into `pair` - for exits sized in tokens rather than supply
+    pub async fn estimate_exit_impact(&self, token: Address, pair: Address, amount_tokens: U256) -> Result<f64> {
+        let (reserve_token, reserve_quote) = self.pair_reserves(token, pair).await?;
+        v2_sell_impact_percent(amount_tokens, reserve_token, reserve_quote)
+            .ok_or_else(|| eyre!("Pair {:?} has no usable reserves for a {} token exit", pair, amount_tokens))
+    }
+
+    /// (token reserve, quote reserve) of a V2 pair
+    async fn pair_reserves(&self, token: Address, pair: Address) -> Result<(U256, U256)> {
+        let token0 = self.eth_call_address(pair, token0Call {}.abi_encode()).await?;
+        let reserves = self.eth_call_raw(pair, Bytes::from(getReservesCall {}.abi_encode())).await?;
+        if reserves.len() < 64 {
+            return Err(eyre!("getReserves on {:?} returned {} bytes", pair, reserves.len()));
+        }
+        let reserve0 = U256::from_be_slice(&reserves[..32]);
+        let reserve1 = U256::from_be_slice(&reserves[32..64]);
+        Ok(if token == token0 { (reserve0, reserve1) } else { (reserve1, reserve0) })
+    }
+
     /// Get primary router
     fn primary_router(&self) -> Address {
         self.routers.first().map(|r| r.address).unwrap_or_default()
@@ -264,68 +1689,340 @@ impl HoneypotDetector {
     /// Fetch bytecode from RPC
     #[allow(dead_code)]
     async fn fetch_bytecode(&self, address: Address) -> Option<Bytes> {
-        let client = reqwest::Client::new();
-        let payload = serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": "eth_getCode",
-            "params": [format!("{:?}", address), "latest"],
-            "id": 1
-        });
-
-        match client.post(&self.rpc_url)
-            .json(&payload)
-            .timeout(std::time::Duration::from_secs(5))
-            .send()
-            .await
-        {
-            Ok(response) => {
-                if let Ok(json) = response.json::<serde_json::Value>().await {
-                    if let Some(result) = json.get("result").and_then(|r| r.as_str()) {
-                        if result != "0x" && result.len() > 2 {
-                            if let Ok(bytes) = hex::decode(&result[2..]) {
-                                info!("📦 Fetched bytecode for {:?}: {} bytes", address, bytes.len());
-                                return Some(Bytes::from(bytes));
-                            }
+        // Timed into `UPSTREAM_LATENCY` like the RpcProvider calls (request + capped body read)
+        let started = Instant::now();
+        let body: Option<Vec<u8>> = async {
+            let client = rpc_http_client(&self.rpc_url);
+            let payload = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "eth_getCode",
+                "params": [format!("{:?}", address), self.block_tag()],
+                "id": 1
+            });
+
+            let mut response = match client.post(&self.rpc_url)
+                .json(&payload)
+                .timeout(std::time::Duration::from_secs(5))
+                .send()
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    warn!("⚠️ Failed to fetch bytecode for {:?}: {}", address, e);
+                    return None;
+                }
+            };
+
+            // Stream the body with a hard cap - never buffer an unbounded response
+            // (hex doubles the size, plus room for the JSON-RPC envelope)
+            let max_body = self.max_bytecode_size * 2 + 1024;
+            let mut body: Vec<u8> = Vec::new();
+            loop {
+                match response.chunk().await {
+                    Ok(Some(chunk)) => {
+                        if body.len() + chunk.len() > max_body {
+                            warn!(
+                                "⚠️ Bytecode for {:?} exceeds {} bytes - rejected",
+                                address, self.max_bytecode_size
+                            );
+                            return None;
                         }
+                        body.extend_from_slice(&chunk);
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        warn!("⚠️ Failed to read bytecode for {:?}: {}", address, e);
+                        return None;
                     }
                 }
-                None
-            }
-            Err(e) => {
-                warn!("⚠️ Failed to fetch bytecode for {:?}: {}", address, e);
-                None
             }
+
+            Some(body)
+        }
+        .await;
+        let json = body.and_then(|body| serde_json::from_slice::<serde_json::Value>(&body).ok());
+        UPSTREAM_LATENCY.record(
+            "eth_getCode",
+            started.elapsed(),
+            json.as_ref().is_some_and(|json| json.get("result").is_some()),
+        );
+
+        let result = json?.get("result").and_then(|r| r.as_str())?.to_string();
+        if result == "0x" || result.len() <= 2 {
+            return None;
+        }
+        let bytes = hex::decode(&result[2..]).ok()?;
+        if bytes.len() > self.max_bytecode_size {
+            warn!(
+                "⚠️ Bytecode for {:?} is {} bytes (cap {}) - rejected",
+                address, bytes.len(), self.max_bytecode_size
+            );
+            return None;
+        }
+        info!("📦 Fetched bytecode for {:?}: {} bytes", address, bytes.len());
+        Some(Bytes::from(bytes))
+    }
+
+    /// FetchBytecode stage: token / router / pair code over RPC
+    /// All None when the stage is disabled - the REVM run falls back to the built-in mocks
+    pub async fn fetch_code(&self, token: Address, pair: Option<Address>) -> FetchedCode {
+        if !self.pipeline.runs(Stage::FetchBytecode) {
+            return FetchedCode::default();
+        }
+        let pair_code = match pair {
+            Some(pair) => self.fetch_bytecode(pair).await,
+            None => None,
+        };
+        FetchedCode {
+            token: self.fetch_bytecode(token).await,
+            router: self.fetch_bytecode(self.primary_router()).await,
+            pair: pair_code,
+        }
+    }
+
+    /// ScanStatic stage (empty scan when disabled)
+    fn static_scan_stage(&self, bytecode: &Bytes) -> StaticScan {
+        if self.pipeline.runs(Stage::ScanStatic) {
+            scan_static(bytecode)
+        } else {
+            StaticScan::default()
         }
     }
 
     /// Detect honeypot with RPC bytecode fetching (async version)
     /// Uses eth_call to simulate swap on actual blockchain state
     /// Tries multiple DEX routers until one works
+    /// The result records the block number at analysis start (for staleness / disputes),
+    /// or the target block when analyzing historical state
     #[allow(dead_code)]
     pub async fn detect_async(
         &self,
         token: Address,
         test_amount_eth: U256,
+    ) -> Result<HoneypotResult> {
+        self.verify_chain_id().await?;
+        let block_number = match self.target_block {
+            Some(block) => Some(block),
+            None => self.fetch_block_number().await,
+        };
+        self.detect_on_block(token, test_amount_eth)
+            .await
+            .map(|result| result.at_block(block_number))
+    }
+
+    /// ERC20 / ERC777 / ERC1363 / rebasing via eth_call probes (works through proxies):
+    /// ERC777 granularity(), ERC-165 support for ERC1363 and rebasing-only getters.
+    /// Failed probes count as absent, so RPC trouble degrades to `Erc20`
+    pub async fn classify_token_standard(&self, token: Address) -> TokenStandard {
+        let (granularity, supports_erc1363, rebasing) = token_standard_probes();
+        let probe = |calldata: Vec<u8>| async move { self.eth_call_raw(token, Bytes::from(calldata)).await.ok() };
+
+        let (granularity, supports_erc1363, rebasing) = tokio::join!(
+            probe(granularity),
+            probe(supports_erc1363),
+            futures_util::future::join_all(rebasing.into_iter().map(probe)),
+        );
+        TokenStandard::classify(
+            first_word(granularity.as_deref()),
+            first_word(supports_erc1363.as_deref()) == Some(U256::from(1u64)),
+            rebasing.iter().any(|bytes| first_word(bytes.as_deref()).is_some()),
+        )
+    }
+
+    /// `classify_token_standard` against forked state (static calls, nothing committed)
+    fn classify_token_standard_forked<ExtDB>(&self, db: &mut CacheDB<ExtDB>, token: Address) -> TokenStandard
+    where
+        ExtDB: DatabaseRef,
+    {
+        let (granularity, supports_erc1363, rebasing) = token_standard_probes();
+        let mut probe = |calldata: Vec<u8>| self.static_call(db, Address::ZERO, token, calldata);
+
+        let granularity = first_word(probe(granularity).as_deref());
+        let supports_erc1363 = first_word(probe(supports_erc1363).as_deref()) == Some(U256::from(1u64));
+        let has_rebasing_getter = rebasing.into_iter().any(|calldata| probe(calldata).is_some());
+        TokenStandard::classify(granularity, supports_erc1363, has_rebasing_getter)
+    }
+
+    /// Current chain head via eth_blockNumber (None if RPC unavailable)
+    pub async fn fetch_block_number(&self) -> Option<u64> {
+        self.fetch_quantity("eth_blockNumber").await
+    }
+
+    /// Error if `rpc_url` serves another chain than `chain_id` (e.g. ETH_HTTP_URL on a testnet).
+    /// Checked once per URL; an unreachable endpoint is not an error here (quotes fail on their own)
+    pub async fn verify_chain_id(&self) -> Result<()> {
+        let cached = RPC_CHAIN_IDS.get(&self.rpc_url).map(|id| *id);
+        let reported = match cached {
+            Some(id) => id,
+            None => match self.fetch_chain_id().await {
+                Some(id) => {
+                    RPC_CHAIN_IDS.insert(self.rpc_url.clone(), id);
+                    id
+                }
+                None => {
+                    warn!("⚠️ eth_chainId unavailable on {} - chain id not verified", self.chain_name);
+                    return Ok(());
+                }
+            },
+        };
+        let endpoint = match crate::models::config::chain_env_prefix(self.chain_id) {
+            "" => "RPC URL".to_string(),
+            prefix => format!("{}_HTTP_URL", prefix),
+        };
+        check_chain_id(self.chain_id, reported, &endpoint)
+    }
+
+    /// Chain id served by `rpc_url` via eth_chainId (None if RPC unavailable)
+    async fn fetch_chain_id(&self) -> Option<u64> {
+        self.fetch_quantity("eth_chainId").await
+    }
+
+    /// Parameterless JSON-RPC `method` returning a hex quantity, timed into `UPSTREAM_LATENCY`
+    async fn fetch_quantity(&self, method: &str) -> Option<u64> {
+        let payload = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": [],
+            "id": 1
+        });
+
+        let started = Instant::now();
+        let json: Option<serde_json::Value> = async {
+            rpc_http_client(&self.rpc_url)
+                .post(&self.rpc_url)
+                .timeout(std::time::Duration::from_secs(5))
+                .json(&payload)
+                .send()
+                .await
+                .ok()?
+                .json()
+                .await
+                .ok()
+        }
+        .await;
+        let hex = json.as_ref().and_then(|json| json.get("result")?.as_str());
+        UPSTREAM_LATENCY.record(method, started.elapsed(), hex.is_some());
+
+        u64::from_str_radix(hex?.trim_start_matches("0x"), 16).ok()
+    }
+
+    /// Quote-based detection against the target block (latest by default)
+    async fn detect_on_block(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+    ) -> Result<HoneypotResult> {
+        // Fetch token bytecode for SELFDESTRUCT scan + heuristics
+        let (token_bytecode, standard) = if self.pipeline.runs(Stage::FetchBytecode) {
+            let (code, standard) = tokio::join!(self.fetch_bytecode(token), self.classify_token_standard(token));
+            self.report(Stage::FetchBytecode);
+            (code, Some(standard))
+        } else {
+            (None, None)
+        };
+
+        let result = self
+            .simulate_on_block(token, test_amount_eth, token_bytecode.as_ref(), true)
+            .await?;
+        let result = match standard {
+            Some(standard) => result.with_token_standard(standard),
+            None => result,
+        };
+        let result = if self.confirm_verdict && result.is_honeypot {
+            self.confirm_honeypot(token, test_amount_eth, token_bytecode.as_ref(), result)
+                .await
+        } else {
+            result
+        };
+        let result = self.run_heuristics(token, token_bytecode.as_ref(), result);
+        self.report(Stage::Score);
+        Ok(result)
+    }
+
+    /// Second buy → sell run for a honeypot verdict; a rerun that trades (or fails)
+    /// leaves the verdict unconfirmed
+    async fn confirm_honeypot(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+        token_bytecode: Option<&Bytes>,
+        first: HoneypotResult,
+    ) -> HoneypotResult {
+        info!("🔁 Honeypot verdict for {:?} - re-running the sell to confirm", token);
+        // Quiet rerun: its stages already went out with the first run
+        match self.simulate_on_block(token, test_amount_eth, token_bytecode, false).await {
+            Ok(rerun) if rerun.is_honeypot => first,
+            Ok(_) => {
+                warn!("⚠️ Honeypot verdict for {:?} not reproduced on the second run", token);
+                first.unconfirmed()
+            }
+            Err(e) => {
+                warn!("⚠️ Confirmation run for {:?} failed: {}", token, e);
+                first.unconfirmed()
+            }
+        }
+    }
+
+    /// Core buy/sell quote simulation on each DEX router
+    /// A token without a valid decimals() still runs the round trip (decimals only label
+    /// amounts), but its verdict is capped to Unverified
+    async fn simulate_on_block(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+        token_bytecode: Option<&Bytes>,
+        report_stages: bool,
+    ) -> Result<HoneypotResult> {
+        // Never guessed - garbage decimals would poison any human-readable amount
+        let token_decimals = match self.decimals_override {
+            Some(decimals) => Some(decimals),
+            None => self.fetch_token_info(token).await.decimals,
+        };
+        let result = self
+            .quote_round_trip(token, test_amount_eth, token_bytecode, token_decimals, report_stages)
+            .await?;
+        if token_decimals.is_some() {
+            return Ok(result);
+        }
+        warn!("⚠️ Token {:?} has no valid decimals() - returning UNVERIFIED", token);
+        Ok(result.with_unknown_decimals())
+    }
+
+    /// Quote buy → sell on each router until one has liquidity
+    /// (stages go to `with_progress` only when `report_stages`)
+    async fn quote_round_trip(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+        token_bytecode: Option<&Bytes>,
+        token_decimals: Option<u8>,
+        report_stages: bool,
     ) -> Result<HoneypotResult> {
         let start = Instant::now();
+        let report = |stage| {
+            if report_stages {
+                self.report(stage);
+            }
+        };
         let mut risk_factors: Vec<String> = Vec::new();
 
         info!("🔗 Simulating swap via RPC eth_call on {} ({} DEXes)...", 
               self.chain_name, self.routers.len());
 
-        // Fetch token bytecode for access control scan
-        let token_bytecode = self.fetch_bytecode(token).await;
-        
-        // Scan for access control functions
-        let access_control_penalty = if let Some(ref code) = token_bytecode {
-            self.scan_access_control_functions(code, &mut risk_factors)
-        } else {
-            0
-        };
+        // Scan for SELFDESTRUCT
+        let scan = token_bytecode
+            .map(|code| self.static_scan_stage(code))
+            .unwrap_or_default();
+        if token_bytecode.is_some() {
+            report(Stage::ScanStatic);
+        }
+        let access_control_penalty = scan.penalty;
+        risk_factors.extend(scan.risk_factors);
 
         // Try each DEX router until we find liquidity
         let mut last_error: Option<String> = None;
         let mut tried_dexes: Vec<String> = Vec::new();
+        let mut sell_failed_dex: Option<&DexInfo> = None;
 
         for dex in &self.routers {
             info!("🔄 Trying {} router...", dex.name);
@@ -336,57 +2033,110 @@ impl HoneypotDetector {
             
             match quote_result {
                 Ok(expected_tokens) if !expected_tokens.is_zero() => {
-                    info!("✅ Found liquidity on {}: {} tokens", dex.name, expected_tokens);
+                    match token_decimals {
+                        Some(decimals) => info!("✅ Found liquidity on {}: {:.4} tokens ({} decimals)",
+                                                dex.name, to_decimal_units(expected_tokens, decimals), decimals),
+                        None => info!("✅ Found liquidity on {}: {} raw token units (decimals unknown)",
+                                      dex.name, expected_tokens),
+                    }
+                    report(Stage::SimBuy);
 
                     // Try reverse quote (sell tokens back to native)
                     let sell_quote = self.get_amounts_out_reverse_with_router(expected_tokens, token, dex.address).await;
                     
                     match sell_quote {
                         Ok(native_back) => {
+                            report(Stage::SimSell);
                             let latency_ms = start.elapsed().as_millis() as u64;
                             
-                            // Calculate loss
-                            let test_f64: f64 = u128::try_from(test_amount_eth).unwrap_or(0) as f64;
-                            let back_f64: f64 = u128::try_from(native_back).unwrap_or(0) as f64;
-                            
-                            if test_f64 == 0.0 {
+                            if test_amount_eth.is_zero() {
                                 continue; // Try next DEX
                             }
 
-                            let total_loss = ((test_f64 - back_f64) / test_f64) * 100.0;
-                            let total_loss = total_loss.max(0.0);
+                            // Calculate loss in U256 (native in vs native out, both 18 decimals)
+                            let total_loss = loss_percent(test_amount_eth, native_back);
                             
-                            info!("💰 {} back from sell on {}: {} (loss: {:.2}%)", 
-                                  self.native_symbol, dex.name, native_back, total_loss);
+                            info!("💰 {} back from sell on {}: {:.6} (loss: {:.2}%)", 
+                                  self.native_symbol, dex.name, wei_to_eth(native_back), total_loss);
 
-                            // If loss > 90%, likely honeypot
-                            if total_loss > 90.0 {
+                            // Loss above the chain's cutoff = likely honeypot (same cutoff as the REVM path)
+                            if total_loss > self.loss_cutoffs.honeypot_loss_percent {
                                 risk_factors.push(format!("Extreme loss on {}: {:.2}%", dex.name, total_loss));
                                 return Ok(HoneypotResult::honeypot(
                                     format!("Extreme loss: {:.2}% on {} - likely honeypot", total_loss, dex.name),
                                     true, false, false,
                                     access_control_penalty, risk_factors, latency_ms,
-                                ));
+                                ).via_dex(dex));
                             }
 
+                            // Quotes only prove the pool math - also check a holder can approve the router
+                            let mut inconclusive_checks = 0u8;
+                            match self.simulate_approve_async(token, dex.address, expected_tokens).await {
+                                ApproveCheck::Blocked(reason) => {
+                                    risk_factors.push(format!("APPROVE REVERTED: {}", reason));
+                                    return Ok(HoneypotResult::honeypot(
+                                        format!("⛔ APPROVE BLOCKED: {} - holders can never sell, CONFIRMED HONEYPOT!", reason),
+                                        true, false, false,
+                                        access_control_penalty, risk_factors,
+                                        start.elapsed().as_millis() as u64,
+                                    ).via_dex(dex));
+                                }
+                                ApproveCheck::Unknown(e) => {
+                                    warn!("⚠️ Approve simulation inconclusive on {}: {}", dex.name, e);
+                                    inconclusive_checks += 1;
+                                }
+                                ApproveCheck::Allowed => {}
+                            }
+                            report(Stage::SimApprove);
+
+                            // Sell path aside, a spender must not move more than it was approved for
+                            let allowance_enforced = match self.simulate_transfer_from_async(token, dex.address).await {
+                                AllowanceCheck::Bypassed => {
+                                    risk_factors.push("ALLOWANCE BYPASS: transferFrom succeeds without approval".to_string());
+                                    false
+                                }
+                                AllowanceCheck::Unknown(e) => {
+                                    warn!("⚠️ Allowance check inconclusive on {}: {}", dex.name, e);
+                                    inconclusive_checks += 1;
+                                    true
+                                }
+                                AllowanceCheck::Enforced => true,
+                            };
+                            let access_control_penalty = if allowance_enforced {
+                                access_control_penalty
+                            } else {
+                                access_control_penalty.saturating_add(ALLOWANCE_BYPASS_PENALTY).min(100)
+                            };
+
                             // Success! Token is tradeable
                             let buy_tax = total_loss / 2.0;
                             let sell_tax = total_loss / 2.0;
 
                             risk_factors.push(format!("Tested on {}", dex.name));
 
-                            return Ok(HoneypotResult::safe(
+                            let mut result = HoneypotResult::safe(
                                 buy_tax,
                                 sell_tax,
                                 access_control_penalty,
                                 risk_factors,
                                 latency_ms,
-                            ));
+                            );
+                            result.allowance_enforced = allowance_enforced;
+                            result.sim_detail = Some(SimDetail::from_quotes(expected_tokens, native_back));
+                            let confidence = CONFIDENCE_VERIFIED
+                                .saturating_sub(inconclusive_checks * CONFIDENCE_INCONCLUSIVE_PENALTY);
+                            return Ok(result.with_confidence(confidence).via_dex(dex));
+                        }
+                        Err(e) if e.downcast_ref::<EmptyCallResult>().is_some() => {
+                            // Router returned bare 0x - clean revert, no usable reverse route
+                            info!("📭 No reverse liquidity on {} (empty eth_call result)", dex.name);
+                            last_error = Some(format!("No liquidity on {}", dex.name));
                         }
                         Err(e) => {
                             // Sell failed on this DEX - might be honeypot or just no reverse liquidity
                             warn!("⚠️ Sell quote failed on {}: {}", dex.name, e);
                             last_error = Some(format!("Sell failed on {}: {}", dex.name, e));
+                            sell_failed_dex = Some(dex);
                             // Continue to try next DEX
                         }
                     }
@@ -410,11 +2160,18 @@ impl HoneypotDetector {
         // Check if it's a sell failure (potential honeypot) or just no liquidity
         if let Some(ref err) = last_error {
             if err.contains("Sell failed") {
-                return Ok(HoneypotResult::honeypot(
+                // A failed eth_call quote is not an executed revert - weaker evidence
+                let result = HoneypotResult::honeypot(
                     format!("Cannot sell on any DEX (tried: {})", tried_dexes.join(", ")),
                     true, false, true,
                     access_control_penalty, risk_factors, latency_ms,
-                ));
+                )
+                .with_confidence(CONFIDENCE_QUOTE_FAILED)
+                .with_warning(WARNING_REVERSE_QUOTE_UNAVAILABLE);
+                return Ok(match sell_failed_dex {
+                    Some(dex) => result.via_dex(dex),
+                    None => result,
+                });
             }
         }
 
@@ -428,6 +2185,11 @@ impl HoneypotDetector {
             buy_tax_percent: 0.0,
             sell_tax_percent: 0.0,
             total_loss_percent: 0.0,
+            net_loss_percent: None,
+            gas_used: 0,
+            sell_buy_gas_ratio: None,
+            asymmetric_gas: false,
+            likely_honeypot: false,
             access_control_penalty,
             risk_factors: vec![
                 format!("No V2 pair found on: {}", tried_dexes.join(", ")),
@@ -435,6 +2197,31 @@ impl HoneypotDetector {
                 "Cannot verify buy/sell safety".to_string(),
             ],
             latency_ms,
+            allowance_enforced: true,
+            same_block_sell_blocked: false,
+            sell_lock_blocks: None,
+            sim_detail: None,
+            dynamic_blacklist_detected: false,
+            fee_class: None,
+            caller_type_gated: false,
+            transfer_to_pair_blocked: false,
+            permit_supported: false,
+            permit_mismatch: false,
+            gas_price_gated: false,
+            direct_holder_sell_blocked: false,
+            contract_recipient_blocked: false,
+            proceeds_received: true,
+            no_pair: true,
+            declared_tax: DeclaredTax::default(),
+            declared_vs_measured_tax_mismatch: false,
+            simulated_router: None,
+            simulated_dex: None,
+            block_number: None,
+            analyzed_at: unix_timestamp(),
+            confidence: 0,
+            warnings: Vec::new(),
+            token_standard: None,
+            revert: None,
         })
     }
 
@@ -450,26 +2237,144 @@ impl HoneypotDetector {
         self.eth_call(router, Bytes::from(calldata)).await
     }
 
-    /// Get expected native output for token input via specific router
-    #[allow(dead_code)]
-    async fn get_amounts_out_reverse_with_router(&self, amount_in: U256, token: Address, router: Address) -> Result<U256> {
-        let path = vec![token, self.weth];
-        let calldata = getAmountsOutCall {
-            amountIn: amount_in,
-            path,
+    /// Simulate `approve(router, amount)` from a fresh random holder on live state (eth_call)
+    /// Catches approve-blocking honeypots that pass quote-only checks
+    async fn simulate_approve_async(&self, token: Address, router: Address, amount: U256) -> ApproveCheck {
+        let calldata = approveCall {
+            spender: router,
+            amount,
         }.abi_encode();
+        let holder = self.sim_address("caller");
 
-        self.eth_call(router, Bytes::from(calldata)).await
+        match self.eth_call_with_retry(Some(holder), token, Bytes::from(calldata), 3).await {
+            Ok(result) => Self::classify_approve_result(&result),
+            Err(e) => match e.downcast_ref::<CallReverted>() {
+                Some(reverted) => ApproveCheck::Blocked(reverted.message.clone()),
+                None => ApproveCheck::Unknown(e.to_string()),
+            },
+        }
     }
 
-    /// Get expected output tokens for native input via Uniswap getAmountsOut (uses primary router)
-    #[allow(dead_code)]
-    async fn get_amounts_out(&self, amount_in: U256, token: Address) -> Result<U256> {
-        self.get_amounts_out_with_router(amount_in, token, self.primary_router()).await
+    /// Simulate `transferFrom(pair, spender, 1)` from a random spender the pair never approved
+    /// The V2 pair is a holder that is guaranteed to have a balance, so a revert here
+    /// can only come from the allowance check. Success = allowance is not enforced.
+    async fn simulate_transfer_from_async(&self, token: Address, router: Address) -> AllowanceCheck {
+        let pair = match self.get_pair_address(token, router).await {
+            Ok(pair) if pair != Address::ZERO => pair,
+            Ok(_) => return AllowanceCheck::Unknown("No V2 pair".to_string()),
+            Err(e) => return AllowanceCheck::Unknown(format!("Pair lookup failed: {}", e)),
+        };
+
+        let spender = self.sim_address("spender");
+        let calldata = transferFromCall {
+            from: pair,
+            to: spender,
+            amount: U256::from(1),
+        }.abi_encode();
+
+        match self.eth_call_with_retry(Some(spender), token, Bytes::from(calldata), 3).await {
+            Ok(result) => Self::classify_transfer_from_result(&result),
+            Err(e) if e.downcast_ref::<CallReverted>().is_some() => AllowanceCheck::Enforced,
+            Err(e) => AllowanceCheck::Unknown(e.to_string()),
+        }
     }
 
-    /// Get expected native output for token input (uses primary router)
-    #[allow(dead_code)]
+    /// transferFrom() return data: bool false = enforced; true or empty (non-standard) = moved
+    fn classify_transfer_from_result(result: &str) -> AllowanceCheck {
+        let bytes = match hex::decode(result.trim_start_matches("0x")) {
+            Ok(bytes) => bytes,
+            Err(e) => return AllowanceCheck::Unknown(format!("Failed to decode hex: {}", e)),
+        };
+
+        if bytes.len() >= 32 && U256::from_be_slice(&bytes[..32]).is_zero() {
+            AllowanceCheck::Enforced
+        } else {
+            AllowanceCheck::Bypassed
+        }
+    }
+
+    /// Token/WETH V2 pair straight from the chain: `factory().getPair(token, weth)` on each
+    /// known router, in priority order. For tokens DexScreener has not indexed yet
+    pub async fn discover_pair(&self, token: Address) -> Option<(DexInfo, Address)> {
+        for dex in &self.routers {
+            match self.get_pair_address(token, dex.address).await {
+                Ok(pair) if !pair.is_zero() => return Some((dex.clone(), pair)),
+                Ok(_) => {}
+                Err(e) => info!("📭 getPair via {} failed: {}", dex.name, e),
+            }
+        }
+        None
+    }
+
+    /// Resolve the token/WETH V2 pair via router.factory().getPair()
+    async fn get_pair_address(&self, token: Address, router: Address) -> Result<Address> {
+        let factory = self.eth_call_address(router, factoryCall {}.abi_encode()).await?;
+        let calldata = getPairCall {
+            tokenA: token,
+            tokenB: self.weth,
+        }.abi_encode();
+        self.eth_call_address(factory, calldata).await
+    }
+
+    /// Read a V2 pair's token0/token1
+    pub async fn fetch_pair_tokens(&self, pair: Address) -> Result<(Address, Address)> {
+        let token0 = self.eth_call_address(pair, token0Call {}.abi_encode()).await?;
+        let token1 = self.eth_call_address(pair, token1Call {}.abi_encode()).await?;
+        if token0 == Address::ZERO || token1 == Address::ZERO {
+            return Err(eyre!("{:?} is not a V2 pair", pair));
+        }
+        Ok((token0, token1))
+    }
+
+    /// eth_call returning a single ABI-encoded address
+    async fn eth_call_address(&self, to: Address, data: Vec<u8>) -> Result<Address> {
+        let result = self.eth_call_with_retry(None, to, Bytes::from(data), 3).await?;
+        let bytes = hex::decode(result.trim_start_matches("0x"))
+            .map_err(|e| eyre!("Failed to decode hex: {}", e))?;
+        if bytes.len() < 32 {
+            return Err(EmptyCallResult.into());
+        }
+        Ok(Address::from_slice(&bytes[12..32]))
+    }
+
+    /// approve() return data: empty (non-standard, e.g. USDT) = ok, bool false = blocked
+    fn classify_approve_result(result: &str) -> ApproveCheck {
+        let bytes = match hex::decode(result.trim_start_matches("0x")) {
+            Ok(bytes) => bytes,
+            Err(e) => return ApproveCheck::Unknown(format!("Failed to decode hex: {}", e)),
+        };
+
+        if bytes.is_empty() {
+            return ApproveCheck::Allowed;
+        }
+
+        if bytes.len() >= 32 && U256::from_be_slice(&bytes[..32]).is_zero() {
+            ApproveCheck::Blocked("approve returned false".to_string())
+        } else {
+            ApproveCheck::Allowed
+        }
+    }
+
+    /// Get expected native output for token input via specific router
+    #[allow(dead_code)]
+    async fn get_amounts_out_reverse_with_router(&self, amount_in: U256, token: Address, router: Address) -> Result<U256> {
+        let path = vec![token, self.weth];
+        let calldata = getAmountsOutCall {
+            amountIn: amount_in,
+            path,
+        }.abi_encode();
+
+        self.eth_call(router, Bytes::from(calldata)).await
+    }
+
+    /// Get expected output tokens for native input via Uniswap getAmountsOut (uses primary router)
+    #[allow(dead_code)]
+    async fn get_amounts_out(&self, amount_in: U256, token: Address) -> Result<U256> {
+        self.get_amounts_out_with_router(amount_in, token, self.primary_router()).await
+    }
+
+    /// Get expected native output for token input (uses primary router)
+    #[allow(dead_code)]
     async fn get_amounts_out_reverse(&self, amount_in: U256, token: Address) -> Result<U256> {
         self.get_amounts_out_reverse_with_router(amount_in, token, self.primary_router()).await
     }
@@ -478,13 +2383,22 @@ impl HoneypotDetector {
     /// CEO Directive: Retry logic with exponential backoff, User-Agent header
     #[allow(dead_code)]
     async fn eth_call(&self, to: Address, data: Bytes) -> Result<U256> {
-        let result = self.eth_call_with_retry(to, data, 3).await?;
-        
-        if result == "0x" || result.len() < 66 {
-            return Err(eyre!("Empty or invalid response"));
+        // JSON-RPC error objects are surfaced by eth_call_with_retry first
+        let result = self.eth_call_with_retry(None, to, data, 3).await?;
+        Self::parse_amounts_out(&result)
+    }
+
+    /// Parse getAmountsOut response - returns uint256[]
+    /// Bare "0x" (no error object) = clean revert, NOT a parse failure
+    fn parse_amounts_out(result: &str) -> Result<U256> {
+        if result == "0x" {
+            return Err(EmptyCallResult.into());
+        }
+
+        if result.len() < 66 {
+            return Err(eyre!("Invalid response: {}", result));
         }
 
-        // Parse getAmountsOut response - returns uint256[] 
         // Skip offset (32 bytes) + length (32 bytes), get last uint256
         let bytes = hex::decode(&result[2..])
             .map_err(|e| eyre!("Failed to decode hex: {}", e))?;
@@ -501,26 +2415,37 @@ impl HoneypotDetector {
     /// Execute eth_call with retry logic and exponential backoff
     /// CEO Directive: Max 3 retries, backoff 100ms -> 200ms -> 400ms
     #[allow(dead_code)]
-    async fn eth_call_with_retry(&self, to: Address, data: Bytes, max_retries: u32) -> Result<String> {
+    async fn eth_call_with_retry(
+        &self,
+        from: Option<Address>,
+        to: Address,
+        data: Bytes,
+        max_retries: u32,
+    ) -> Result<String> {
         use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
         
         let mut headers = HeaderMap::new();
         headers.insert(USER_AGENT, HeaderValue::from_static("RusterShield/1.0.0"));
         headers.insert("Content-Type", HeaderValue::from_static("application/json"));
         
-        let client = reqwest::Client::builder()
+        let client = rpc_client_builder(&self.rpc_url)
             .default_headers(headers)
             .timeout(std::time::Duration::from_secs(10))
             .build()
             .map_err(|e| eyre!("Failed to build client: {}", e))?;
 
+        let mut call = serde_json::json!({
+            "to": format!("{:?}", to),
+            "data": format!("0x{}", hex::encode(&data))
+        });
+        if let Some(from) = from {
+            call["from"] = serde_json::json!(format!("{:?}", from));
+        }
+
         let payload = serde_json::json!({
             "jsonrpc": "2.0",
             "method": "eth_call",
-            "params": [{
-                "to": format!("{:?}", to),
-                "data": format!("0x{}", hex::encode(&data))
-            }, "latest"],
+            "params": [call, self.block_tag()],
             "id": 1
         });
 
@@ -533,40 +2458,22 @@ impl HoneypotDetector {
                 tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
             }
 
-            match client.post(&self.rpc_url)
-                .json(&payload)
-                .send()
-                .await
-            {
-                Ok(response) => {
-                    // Check for rate limit
-                    if response.status() == 429 {
-                        warn!("⏳ Rate limited (429), backing off (attempt {}/{})", attempt + 1, max_retries);
-                        last_error = Some(eyre!("Rate limited (HTTP 429)"));
-                        continue;
-                    }
-
-                    match response.json::<serde_json::Value>().await {
-                        Ok(json) => {
-                            if let Some(error) = json.get("error") {
-                                last_error = Some(eyre!("RPC error: {}", error));
-                                continue;
-                            }
-
-                            if let Some(result) = json.get("result").and_then(|r| r.as_str()) {
-                                return Ok(result.to_string());
-                            }
-                            
-                            last_error = Some(eyre!("No result in response"));
-                        }
-                        Err(e) => {
-                            last_error = Some(eyre!("Failed to parse response: {}", e));
-                        }
-                    }
-                }
+            // Each attempt is timed into `UPSTREAM_LATENCY` like the RpcProvider calls
+            let started = Instant::now();
+            let outcome = self.eth_call_attempt(&client, &payload).await;
+            // A revert is still an answered call
+            let answered = match &outcome {
+                Ok(_) => true,
+                Err(e) => e.is::<CallReverted>(),
+            };
+            UPSTREAM_LATENCY.record("eth_call", started.elapsed(), answered);
+            match outcome {
+                Ok(result) => return Ok(result),
+                // Execution reverts are deterministic - don't retry
+                Err(e) if e.is::<CallReverted>() => return Err(e),
                 Err(e) => {
-                    warn!("⚠️ RPC request failed (attempt {}/{}): {}", attempt + 1, max_retries, e);
-                    last_error = Some(eyre!("RPC request failed: {}", e));
+                    warn!("⚠️ eth_call failed (attempt {}/{}): {}", attempt + 1, max_retries, e);
+                    last_error = Some(e);
                 }
             }
         }
@@ -574,14 +2481,52 @@ impl HoneypotDetector {
         Err(last_error.unwrap_or_else(|| eyre!("Unknown error after {} retries", max_retries)))
     }
 
+    /// One round trip of `eth_call_with_retry`: the hex result, or why this attempt failed
+    async fn eth_call_attempt(
+        &self,
+        client: &reqwest::Client,
+        payload: &serde_json::Value,
+    ) -> Result<String> {
+        let response = client
+            .post(&self.rpc_url)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| eyre!("RPC request failed: {}", e))?;
+        // Check for rate limit
+        if response.status() == 429 {
+            return Err(eyre!("Rate limited (HTTP 429)"));
+        }
+
+        let json = response
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| eyre!("Failed to parse response: {}", e))?;
+        if let Some(error) = json.get("error") {
+            if let Some(reverted) = CallReverted::from_rpc_error(error) {
+                return Err(reverted.into());
+            }
+            return Err(eyre!("RPC error: {}", error));
+        }
+
+        json.get("result")
+            .and_then(|r| r.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| eyre!("No result in response"))
+    }
+
     /// Execute eth_call on RPC (returns raw bytes)
     /// CEO Directive: Uses retry logic with User-Agent header
     #[allow(dead_code)]
     async fn eth_call_raw(&self, to: Address, data: Bytes) -> Result<Vec<u8>> {
-        let result = self.eth_call_with_retry(to, data, 3).await?;
+        let result = self.eth_call_with_retry(None, to, data, 3).await?;
+
+        if result == "0x" {
+            return Err(EmptyCallResult.into());
+        }
 
-        if result == "0x" || result.len() < 4 {
-            return Err(eyre!("Empty response"));
+        if result.len() < 4 {
+            return Err(eyre!("Invalid response: {}", result));
         }
 
         hex::decode(&result[2..])
@@ -612,39 +2557,63 @@ impl HoneypotDetector {
         // Fetch decimals
         let decimals_calldata = decimalsCall {}.abi_encode();
         if let Ok(bytes) = self.eth_call_raw(token, Bytes::from(decimals_calldata)).await {
-            if bytes.len() >= 32 {
-                // decimals is uint8, stored in last byte of 32-byte word
-                info.decimals = Some(bytes[31]);
-            }
+            info.decimals = Self::decode_decimals(&bytes);
         }
 
         info
     }
 
-    /// Decode ABI-encoded string from bytes
-    #[allow(dead_code)]
-    fn decode_string(bytes: &[u8]) -> Option<String> {
-        if bytes.len() < 64 {
-            // Try bytes32 format (some old tokens like MKR)
-            if bytes.len() >= 32 {
-                let end = bytes.iter().position(|&b| b == 0).unwrap_or(32);
-                return String::from_utf8(bytes[..end].to_vec()).ok();
-            }
+    /// Decode decimals() return data
+    /// None if empty/short, not a clean uint8, or above MAX_TOKEN_DECIMALS
+    fn decode_decimals(bytes: &[u8]) -> Option<u8> {
+        if bytes.len() < 32 {
             return None;
         }
+        let value = U256::from_be_slice(&bytes[..32]);
+        if value > U256::from(MAX_TOKEN_DECIMALS) {
+            return None;
+        }
+        Some(value.to::<u8>())
+    }
 
-        // Standard ABI string encoding:
-        // - First 32 bytes: offset to string data
-        // - Next 32 bytes: string length
-        // - Remaining: string data
-        let length = U256::from_be_slice(&bytes[32..64]);
-        let len = length.try_into().unwrap_or(0usize);
-        
-        if len == 0 || bytes.len() < 64 + len {
+    /// Decode a name()/symbol() result: ABI string or bytes32 (MKR-style)
+    ///
+    /// Tolerant of scam-token encodings: data is trimmed at the declared
+    /// length (clamped to what was returned), BOMs / control characters /
+    /// embedded nulls are stripped and invalid UTF-8 is decoded lossily.
+    /// Returns `UNREADABLE_STRING` when bytes came back but nothing printable is left.
+    fn decode_string(bytes: &[u8]) -> Option<String> {
+        let raw = if bytes.len() >= 64 && U256::from_be_slice(&bytes[..32]) == U256::from(32u64) {
+            // Standard ABI string encoding:
+            // - First 32 bytes: offset to string data
+            // - Next 32 bytes: string length
+            // - Remaining: string data (anything past the length is ignored)
+            let len: usize = U256::from_be_slice(&bytes[32..64]).try_into().unwrap_or(usize::MAX);
+            &bytes[64..64usize.saturating_add(len).min(bytes.len())]
+        } else if bytes.len() >= 32 {
+            // bytes32 format (some old tokens like MKR)
+            &bytes[..32]
+        } else {
             return None;
+        };
+
+        let text = Self::sanitize_string(&String::from_utf8_lossy(raw));
+        if !text.is_empty() {
+            Some(text)
+        } else if raw.iter().any(|&b| b != 0) {
+            Some(UNREADABLE_STRING.to_string())
+        } else {
+            None
         }
+    }
 
-        String::from_utf8(bytes[64..64 + len].to_vec()).ok()
+    /// Strip BOMs, control characters (incl. nulls) and surrounding whitespace
+    fn sanitize_string(text: &str) -> String {
+        text.chars()
+            .filter(|c| !c.is_control() && *c != '\u{feff}' && *c != char::REPLACEMENT_CHARACTER)
+            .collect::<String>()
+            .trim()
+            .to_string()
     }
 
     /// Detect if a token is a honeypot by simulating buy → sell cycle
@@ -671,104 +2640,346 @@ impl HoneypotDetector {
         pair_bytecode: Option<Bytes>,
         pair_address: Option<Address>,
     ) -> Result<HoneypotResult> {
-        let start = Instant::now();
-        let mut risk_factors: Vec<String> = Vec::new();
-
-        // Create fresh database for simulation
-        let mut db = CacheDB::new(EmptyDB::default());
+        let scanned_code = token_bytecode.clone().unwrap_or_else(|| self.mock_erc20_bytecode());
+        let (mock_router, mock_token) = (router_bytecode.is_none(), token_bytecode.is_none());
+        let mut result = self.detect_on_primary_router(
+            token,
+            test_amount_eth,
+            router_bytecode,
+            token_bytecode,
+            pair_bytecode,
+            pair_address,
+        )?;
+        if mock_router {
+            result = result.with_warning(WARNING_MOCK_ROUTER);
+        }
+        if mock_token {
+            result = result.with_warning(WARNING_MOCK_TOKEN);
+        }
+        let result = self.run_heuristics(token, Some(&scanned_code), result);
+        Ok(match self.routers.first() {
+            Some(dex) => result.via_dex(dex),
+            None => result,
+        })
+    }
 
-        // ============================================
-        // STEP 0: Generate RANDOM caller address
-        // This prevents honeypots from whitelisting deployer/known addresses
-        // ============================================
-        let test_account = Self::generate_random_address();
+    /// REVM buy → sell cycle against real chain state forked at `fork.block()`
+    /// The fork is only read through a private `CacheDB` layer, so one `RpcDb`
+    /// can back every token of a batch on the same chain
+    pub fn detect_forked(&self, fork: &RpcDb, token: Address, test_amount_eth: U256) -> Result<HoneypotResult> {
+        self.detect_forked_until(fork, token, test_amount_eth, None)
+    }
 
+    /// `detect_forked` that gives up at `deadline`: fork reads fail from then on, so the
+    /// simulation stops even when the caller has stopped waiting for it
+    pub fn detect_forked_until(
+        &self,
+        fork: &RpcDb,
+        token: Address,
+        test_amount_eth: U256,
+        deadline: Option<Instant>,
+    ) -> Result<HoneypotResult> {
+        let start = Instant::now();
+        let fork = fork.until(deadline);
+
+        let token_code = fork
+            .basic_ref(token)?
+            .and_then(|info| info.code)
+            .map(|code| code.original_bytes())
+            .filter(|code| !code.is_empty())
+            .ok_or_else(|| eyre!("No contract code at {:?} (block {})", token, fork.block()))?;
+        let scan = self.static_scan_stage(&token_code);
+
+        // Fresh random caller per token - writes stay in this token's CacheDB
+        let test_account = self.sim_address("caller");
+        let mut db = CacheDB::new(fork);
         db.insert_account_info(
             test_account,
             AccountInfo {
-                balance: U256::from(100_000_000_000_000_000_000u128), // 100 ETH
+                balance: self
+                    .test_account_balance
+                    .max(test_amount_eth.saturating_mul(U256::from(2u64))),
                 nonce: 0,
                 code_hash: KECCAK_EMPTY,
                 code: None,
             },
         );
 
-        // Setup router with bytecode (if provided, otherwise use minimal mock)
-        let router_code = router_bytecode.unwrap_or_else(|| self.mock_router_bytecode());
-        db.insert_account_info(
-            self.primary_router(),
-            AccountInfo {
-                balance: U256::ZERO,
-                nonce: 0,
-                code_hash: KECCAK_EMPTY,
-                code: Some(Bytecode::new_raw(router_code)),
-            },
-        );
+        let pair_address = self
+            .read_address(&mut db, self.primary_router(), factoryCall {}.abi_encode())
+            .and_then(|factory| {
+                let calldata = getPairCall { tokenA: token, tokenB: self.weth }.abi_encode();
+                self.read_address(&mut db, factory, calldata)
+            })
+            .filter(|pair| !pair.is_zero());
+        let standard = self.classify_token_standard_forked(&mut db, token);
+
+        let result = self.simulate_round_trip(
+            &mut db,
+            test_account,
+            token,
+            test_amount_eth,
+            scan.penalty,
+            scan.risk_factors,
+            pair_address,
+            None,
+            start,
+        )?;
+        let result = self
+            .run_heuristics(token, Some(&token_code), result.with_token_standard(standard))
+            .at_block(Some(fork.block()));
+        Ok(match self.routers.first() {
+            Some(dex) => result.via_dex(dex),
+            None => result,
+        })
+    }
 
-        // Setup WETH
-        db.insert_account_info(
-            self.weth,
-            AccountInfo {
-                balance: U256::from(1_000_000_000_000_000_000_000u128), // 1000 ETH liquidity
-                nonce: 0,
-                code_hash: KECCAK_EMPTY,
-                code: Some(Bytecode::new_raw(self.mock_weth_bytecode())),
-            },
-        );
+    /// REVM buy → sell cycle on caller-supplied state only: no RPC and no mocks, the
+    /// contracts and pair reserves all come from `input`. The verdict depends on the input
+    /// alone (set `with_caller_seed` for a fixed caller too) - for CI, regression corpora of
+    /// known honeypot bytecode and air-gapped analysis. The contract-caller rerun is skipped
+    pub fn detect_offline(&self, input: OfflineAnalysisInput) -> Result<HoneypotResult> {
+        let start = Instant::now();
+        let (mut db, test_account) = self.offline_simulation_db(&input)?;
+        let scan = self.static_scan_stage(&input.token_bytecode);
 
-        // Setup token with bytecode
-        let token_code = token_bytecode
-            .clone()
-            .unwrap_or_else(|| self.mock_erc20_bytecode());
+        let result = self.simulate_round_trip(
+            &mut db,
+            test_account,
+            input.token,
+            input.test_amount_eth,
+            scan.penalty,
+            scan.risk_factors,
+            Some(input.pair),
+            None,
+            start,
+        )?;
+        let result = self.run_heuristics(input.token, Some(&input.token_bytecode), result);
+        Ok(match self.routers.first() {
+            Some(dex) => result.via_dex(dex),
+            None => result,
+        })
+    }
+
+    /// Sell exactly `amount_tokens` (base units, see `from_decimal_units`) against
+    /// forked state, from a holder credited by a balanceOf storage write - no buy,
+    /// so token-denominated limits (max tx / max sell) can be probed exactly.
+    /// Err when the holder could not be credited (balanceOf slot not found)
+    pub fn probe_sell_forked(&self, fork: &RpcDb, token: Address, amount_tokens: U256) -> Result<SellProbe> {
+        let probe = SellProbe { amount_tokens, sellable: false, native_received: None, revert_reason: None };
+        match self.direct_holder_round_trip(&CacheDB::new(fork), token, amount_tokens) {
+            DirectHolderCheck::Sellable(native_received) => {
+                Ok(SellProbe { sellable: true, native_received: Some(native_received), ..probe })
+            }
+            DirectHolderCheck::SellBlocked(reason) => Ok(SellProbe { revert_reason: Some(reason), ..probe }),
+            DirectHolderCheck::Unknown(e) => Err(eyre!("Sell probe for {:?} inconclusive: {}", token, e)),
+        }
+    }
+
+    /// How far a holder can exit: sells 1% / 5% / 10% / 25% of a hypothetical holding
+    /// (`EXIT_HOLDING_SUPPLY_BPS` of total supply) through the primary router against forked
+    /// state, each from a freshly credited holder, so sell taxes and max-sell limits show up
+    /// in the curve. Fork reads fail from `deadline` on.
+    /// Err when the holder could not be credited (balanceOf slot not found)
+    pub fn estimate_exit_liquidity(
+        &self,
+        fork: &RpcDb,
+        token: Address,
+        pair: Address,
+        deadline: Option<Instant>,
+    ) -> Result<Vec<ExitPoint>> {
+        self.exit_curve(&CacheDB::new(fork.until(deadline)), token, pair)
+    }
+
+    /// `estimate_exit_liquidity` on any state; spot price from the pair's token / WETH balances
+    fn exit_curve<ExtDB>(&self, db: &CacheDB<ExtDB>, token: Address, pair: Address) -> Result<Vec<ExitPoint>>
+    where
+        ExtDB: DatabaseRef + Clone,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let mut reads = db.clone();
+        let reserve_token = self.read_token_balance(&mut reads, token, pair);
+        let reserve_quote = self.read_token_balance(&mut reads, self.weth, pair);
+        let (Some(reserve_token), Some(reserve_quote)) = (reserve_token, reserve_quote) else {
+            return Err(eyre!("Pair {:?} balances unreadable", pair));
+        };
+        let supply = self
+            .static_call(&mut reads, Address::ZERO, token, totalSupplyCall {}.abi_encode())
+            .ok_or_else(|| eyre!("totalSupply on {:?} failed", token))?;
+        let holding = U256::from_be_slice(&supply[..32]) * U256::from(EXIT_HOLDING_SUPPLY_BPS) / U256::from(10_000u64);
+
+        EXIT_CURVE_FRACTIONS_BPS
+            .iter()
+            .map(|&bps| {
+                let fraction = bps as f64 / 10_000.0;
+                let amount = holding * U256::from(bps) / U256::from(10_000u64);
+                match self.direct_holder_round_trip(db, token, amount) {
+                    DirectHolderCheck::Sellable(received) => {
+                        let impact_pct = exit_loss_percent(amount, received, reserve_token, reserve_quote)
+                            .ok_or_else(|| eyre!("Pair {:?} has no usable reserves for a {} bps exit", pair, bps))?;
+                        Ok(ExitPoint { fraction, impact_pct, revert_reason: None })
+                    }
+                    DirectHolderCheck::SellBlocked(reason) => {
+                        Ok(ExitPoint { fraction, impact_pct: 100.0, revert_reason: Some(reason) })
+                    }
+                    DirectHolderCheck::Unknown(e) => Err(eyre!("Exit sell for {:?} inconclusive: {}", token, e)),
+                }
+            })
+            .collect()
+    }
+
+    /// Credit a fresh wallet with `amount_tokens` (base units) by a balanceOf storage
+    /// write and `transfer` them to another fresh wallet against forked state. No router,
+    /// pair or liquidity is involved, so tokens without a pool still get a verdict:
+    /// "not listed yet" (transferable) vs "transfers are blocked".
+    /// Err when the sender could not be credited (balanceOf slot not found)
+    pub fn test_transferability(
+        &self,
+        fork: &RpcDb,
+        token: Address,
+        amount_tokens: U256,
+        deadline: Option<Instant>,
+    ) -> Result<TransferProbe> {
+        self.wallet_transfer(&CacheDB::new(fork.until(deadline)), token, amount_tokens)
+    }
+
+    /// `test_transferability` on any state, changes kept in a private copy
+    fn wallet_transfer<ExtDB>(&self, db: &CacheDB<ExtDB>, token: Address, amount: U256) -> Result<TransferProbe>
+    where
+        ExtDB: DatabaseRef + Clone,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let mut db = db.clone();
+        let sender = self.sim_address("transfer-sender");
+        let recipient = self.sim_address("transfer-recipient");
         db.insert_account_info(
-            token,
+            sender,
             AccountInfo {
-                balance: U256::ZERO,
+                balance: self.test_account_balance,
                 nonce: 0,
                 code_hash: KECCAK_EMPTY,
-                code: Some(Bytecode::new_raw(token_code.clone())),
+                code: None,
             },
         );
 
-        // Setup pair if provided
-        if let (Some(pair), Some(code)) = (pair_address, pair_bytecode) {
-            db.insert_account_info(
-                pair,
-                AccountInfo {
-                    balance: U256::ZERO,
-                    nonce: 0,
-                    code_hash: KECCAK_EMPTY,
-                    code: Some(Bytecode::new_raw(code)),
-                },
-            );
+        let slot = self
+            .find_balance_slot(&mut db, token, sender)
+            .ok_or_else(|| eyre!("Transfer probe for {:?} inconclusive: balanceOf storage slot not found", token))?;
+        db.insert_account_storage(token, slot, amount)
+            .map_err(|e| eyre!("Transfer probe for {:?} inconclusive: balance write failed: {:?}", token, e))?;
+
+        let before = self.read_token_balance(&mut db, token, recipient).unwrap_or_default();
+        let probe = TransferProbe { amount_tokens: amount, transferable: false, received_tokens: None, revert_reason: None };
+        let calldata = Bytes::from(transferCall { to: recipient, amount }.abi_encode());
+        match self.execute_tx(&mut db, sender, token, U256::ZERO, calldata, 0) {
+            // Tokens without a return value count as success; an explicit `false` does not
+            Ok(out) if out.len() >= 32 && U256::from_be_slice(&out[..32]).is_zero() => {
+                Ok(TransferProbe { revert_reason: Some("transfer returned false".to_string()), ..probe })
+            }
+            Ok(_) => {
+                let received = self
+                    .read_token_balance(&mut db, token, recipient)
+                    .map(|after| after.saturating_sub(before));
+                Ok(TransferProbe { transferable: true, received_tokens: received, ..probe })
+            }
+            Err(e) => Ok(TransferProbe { revert_reason: Some(e.to_string()), ..probe }),
         }
+    }
+
+    /// Local REVM buy → sell cycle through the primary router
+    fn detect_on_primary_router(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+        router_bytecode: Option<Bytes>,
+        token_bytecode: Option<Bytes>,
+        pair_bytecode: Option<Bytes>,
+        pair_address: Option<Address>,
+    ) -> Result<HoneypotResult> {
+        let start = Instant::now();
+
+        // Inputs for the contract-caller rerun (real token code only - the mocks don't discriminate)
+        let contract_run_inputs = (self.caller_type_check && token_bytecode.is_some())
+            .then(|| (router_bytecode.clone(), token_bytecode.clone(), pair_bytecode.clone()));
+
+        let (mut db, test_account, token_code) = self.build_simulation_db(
+            token,
+            test_amount_eth,
+            router_bytecode,
+            token_bytecode,
+            pair_bytecode,
+            pair_address,
+        );
 
         // ============================================
-        // STEP 1: Scan bytecode for Access Control functions
-        // Detect: setBots, blacklistAddress, addBot, isBot, etc.
+        // STEP 1: Scan bytecode for SELFDESTRUCT
+        // Access control (setBots, blacklistAddress, ...) runs as a heuristic afterwards
         // ============================================
-        let access_control_penalty =
-            self.scan_access_control_functions(&token_code, &mut risk_factors);
+        let scan = self.static_scan_stage(&token_code);
+
+        self.simulate_round_trip(
+            &mut db,
+            test_account,
+            token,
+            test_amount_eth,
+            scan.penalty,
+            scan.risk_factors,
+            pair_address,
+            contract_run_inputs,
+            start,
+        )
+    }
+
+    /// Buy → approve → sell → tax classification against prepared state
+    /// (synthesized `CacheDB<EmptyDB>` or a `CacheDB` over a forked `RpcDb`).
+    /// Runs the SimBuy / SimApprove / SimSell / ComputeTax stages of `self.pipeline`;
+    /// `caller_check` carries the bytecode for the contract-caller rerun
+    #[allow(clippy::too_many_arguments)]
+    fn simulate_round_trip<ExtDB>(
+        &self,
+        db: &mut CacheDB<ExtDB>,
+        test_account: Address,
+        token: Address,
+        test_amount_eth: U256,
+        access_control_penalty: u8,
+        mut risk_factors: Vec<String>,
+        pair_address: Option<Address>,
+        caller_check: Option<CallerRunInputs>,
+        start: Instant,
+    ) -> Result<HoneypotResult>
+    where
+        ExtDB: DatabaseRef + Clone,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        if !self.pipeline.simulates() {
+            return Ok(HoneypotResult::not_simulated(
+                access_control_penalty,
+                risk_factors,
+                start.elapsed().as_millis() as u64,
+            ));
+        }
 
         // ============================================
         // STEP 2: Simulate BUY (ETH → Token)
         // ============================================
-        let buy_result = self.simulate_buy(&mut db, test_account, token, test_amount_eth);
-
-        let (buy_success, tokens_received) = match buy_result {
-            Ok(tokens) => {
-                // If using mock bytecode, tokens will be minimal
-                // Use test_amount as proxy for tokens received
-                let effective_tokens = if tokens < U256::from(1000u64) {
-                    // Mock mode - assume we got tokens proportional to ETH input
-                    test_amount_eth
-                } else {
-                    tokens
-                };
-                (true, effective_tokens)
-            }
+        let buy = match self.sim_buy_stage(db, test_account, token, test_amount_eth) {
+            Ok(buy) => buy,
             Err(e) => {
-                return Ok(HoneypotResult::honeypot(
+                // A buy refused to the EOA: can a contract still trade?
+                let caller_type_gated = caller_check.is_some_and(|(router_code, token_code, pair_code)| {
+                    self.contract_caller_round_trip(
+                        token,
+                        test_amount_eth,
+                        router_code,
+                        token_code,
+                        pair_code,
+                        pair_address,
+                    )
+                });
+                if caller_type_gated {
+                    risk_factors.push("CALLER-TYPE GATED: buy fails for EOAs, round trip succeeds for contracts".to_string());
+                }
+                let mut result = HoneypotResult::honeypot(
                     format!("Buy failed: {}", e),
                     false,
                     false,
@@ -776,61 +2987,211 @@ impl HoneypotDetector {
                     access_control_penalty,
                     risk_factors,
                     start.elapsed().as_millis() as u64,
-                ));
+                );
+                result.caller_type_gated = caller_type_gated;
+                result.revert = e.downcast_ref::<RevertData>().cloned();
+                return Ok(result);
             }
         };
+        let tokens_received = buy.tokens_received;
 
-        // ============================================
-        // STEP 3: Simulate APPROVE (Token → Router)
-        // ============================================
-        let approve_result = self.simulate_approve(&mut db, test_account, token, tokens_received);
+        // Sells route tokens into the pair - a pair-only transfer block is a sell block
+        let transfer_to_pair_blocked = pair_address
+            .is_some_and(|pair| self.pair_transfer_blocked(db, test_account, token, pair, tokens_received));
+        if transfer_to_pair_blocked {
+            risk_factors.push("TRANSFER-TO-PAIR BLOCKED: transfer to the pair reverts, wallet transfers succeed".to_string());
+        }
 
-        if let Err(e) = approve_result {
-            return Ok(HoneypotResult::honeypot(
-                format!("Approve failed: {}", e),
-                true,
-                false,
-                false,
-                access_control_penalty,
-                risk_factors,
-                start.elapsed().as_millis() as u64,
-            ));
+        // Same sell approved by a signed permit, on a pre-approve copy of the state
+        let permit = self
+            .permit_check
+            .then(|| self.permit_round_trip(&mut db.clone(), test_account, token, tokens_received));
+        let permit_supported = matches!(permit, Some(PermitCheck::Sellable | PermitCheck::SellBlocked(_)));
+        match &permit {
+            Some(PermitCheck::Rejected(reason)) => {
+                risk_factors.push(format!("PERMIT REJECTED: EIP-2612 views present but permit() reverted ({})", reason));
+            }
+            Some(PermitCheck::Unknown(e)) => warn!("⚠️ Permit check inconclusive for {:?}: {}", token, e),
+            _ => {}
         }
 
+        // Holder who never bought (airdrop / transfer), on a copy of the post-buy state
+        let direct_holder_sell_blocked = match self
+            .direct_holder_check
+            .then(|| self.direct_holder_round_trip(db, token, tokens_received))
+        {
+            Some(DirectHolderCheck::SellBlocked(reason)) => {
+                risk_factors.push(format!(
+                    "DIRECT HOLDER BLOCKED: holder credited outside the router cannot sell ({})",
+                    reason
+                ));
+                true
+            }
+            Some(DirectHolderCheck::Unknown(e)) => {
+                warn!("⚠️ Direct holder check inconclusive for {:?}: {}", token, e);
+                false
+            }
+            _ => false,
+        };
+
         // ============================================
-        // STEP 4: Simulate SELL (Token → ETH)
-        // CRITICAL: If this REVERTS → HONEYPOT with risk_score = 100
+        // STEP 3: Simulate APPROVE (Token → Router)
         // ============================================
-        let sell_result =
-            self.simulate_sell_with_revert_detection(&mut db, test_account, token, tokens_received);
-
-        let (sell_success, sell_reverted, eth_received) = match sell_result {
-            Ok(SimSellResult::Success(eth)) => {
-                // If using mock bytecode, eth might be very small
-                // Use a reasonable estimate based on input
-                let effective_eth = if eth < U256::from(1000u64) {
-                    // Mock mode - assume ~95% return (5% total tax is reasonable)
-                    test_amount_eth * U256::from(95u64) / U256::from(100u64)
-                } else {
-                    eth
-                };
-                (true, false, effective_eth)
-            }
-            Ok(SimSellResult::Reverted(reason)) => {
-                // ⛔ SELL REVERTED = HONEYPOT! risk_score = 100
-                risk_factors.push(format!("SELL REVERTED: {}", reason));
-                return Ok(HoneypotResult::honeypot(
-                    format!("⛔ SELL REVERTED: {} - CONFIRMED HONEYPOT!", reason),
+        let approve_result = self.simulate_approve(db, test_account, token, tokens_received);
+
+        let approve_gas = match approve_result {
+            Ok(gas) => gas,
+            Err(e) => {
+                let permit_mismatch = permit == Some(PermitCheck::Sellable);
+                if permit_mismatch {
+                    risk_factors.push("APPROVAL PATH GATED: approve() reverts, permit() sells".to_string());
+                }
+                let mut result = HoneypotResult::honeypot(
+                    format!("Approve failed: {}", e),
                     true,
                     false,
-                    true, // sell_reverted = true
+                    false,
                     access_control_penalty,
                     risk_factors,
                     start.elapsed().as_millis() as u64,
-                ));
+                );
+                result.permit_supported = permit_supported;
+                result.permit_mismatch = permit_mismatch;
+                result.direct_holder_sell_blocked = direct_holder_sell_blocked;
+                result.revert = e.downcast_ref::<RevertData>().cloned();
+                return Ok(result);
             }
-            Err(e) => {
-                return Ok(HoneypotResult::honeypot(
+        };
+
+        // Same sell at a low and a high gas price, on copies of the pre-sell state
+        let gas_price_gated = match self
+            .gas_price_check
+            .then(|| self.gas_price_sells(db, test_account, token, tokens_received))
+            .flatten()
+        {
+            Some((low_ok, high_ok)) if low_ok != high_ok => {
+                risk_factors.push(format!(
+                    "GAS-PRICE GATED: sell {} at {} gwei, {} at {} gwei",
+                    if low_ok { "succeeds" } else { "reverts" },
+                    GAS_GATE_LOW_PRICE_WEI / 1_000_000_000,
+                    if high_ok { "succeeds" } else { "reverts" },
+                    GAS_GATE_HIGH_PRICE_WEI / 1_000_000_000,
+                ));
+                true
+            }
+            _ => false,
+        };
+
+        // Same transfer to an EOA and to a contract, on copies of the pre-sell state
+        let contract_recipient_blocked = self.contract_recipient_check
+            && self.contract_recipient_blocked(db, test_account, token, tokens_received);
+        if contract_recipient_blocked {
+            risk_factors.push(
+                "CONTRACT RECIPIENT BLOCKED: transfer to an EOA succeeds, transfer to a contract reverts".to_string(),
+            );
+        }
+
+        // ============================================
+        // STEP 4: Simulate SELL (Token → ETH)
+        // CRITICAL: If this REVERTS → HONEYPOT with risk_score = 100
+        // ============================================
+        let sell = self.sim_sell_stage(db, test_account, token, tokens_received, test_amount_eth);
+
+        // Anti-bot tokens refuse sells in the block of acquisition - not a honeypot
+        let same_block_sell_blocked = sell.same_block_revert.is_some();
+        let access_control_penalty = match (&sell.same_block_revert, sell.sell_lock_blocks) {
+            // Held longer than an anti-bot delay: a timed lock, sellable once it elapses
+            (Some(reason), Some(blocks)) if blocks > self.sell_delay_blocks => {
+                info!("⏳ Sell locked after buy ({}), succeeded {} blocks later", reason, blocks);
+                risk_factors.push(format!(
+                    "SELL LOCK: sells revert for {} blocks (~{} min) after buying ({})",
+                    blocks,
+                    blocks * SIM_BLOCK_TIME_SECS / 60,
+                    reason
+                ));
+                access_control_penalty.saturating_add(SELL_LOCK_PENALTY).min(100)
+            }
+            (Some(reason), blocks) => {
+                let blocks = blocks.unwrap_or(self.sell_delay_blocks);
+                info!("⏱️ Sell reverted in buy block ({}), succeeded {} blocks later", reason, blocks);
+                risk_factors.push(format!(
+                    "ANTI-BOT: sell blocked in buy block ({}), succeeds after {} blocks",
+                    reason, blocks
+                ));
+                access_control_penalty.saturating_add(SAME_BLOCK_SELL_PENALTY).min(100)
+            }
+            (None, _) => access_control_penalty,
+        };
+
+        // Same round trip from a contract account (buy-block sell): discrimination by caller type
+        let caller_type_gated = match caller_check {
+            Some((router_code, token_code, pair_code)) => {
+                let contract_ok = self.contract_caller_round_trip(
+                    token,
+                    test_amount_eth,
+                    router_code,
+                    token_code,
+                    pair_code,
+                    pair_address,
+                );
+                let gated = sell.differs_from_buy_block_sell(contract_ok);
+                if gated {
+                    risk_factors.push(format!(
+                        "CALLER-TYPE GATED: round trip {} for EOAs, {} for contracts",
+                        if contract_ok { "fails" } else { "succeeds" },
+                        if contract_ok { "succeeds" } else { "fails" },
+                    ));
+                }
+                gated
+            }
+            None => false,
+        };
+
+        // Sell approved via approve() vs via permit(): one mechanism blocked, the other not
+        let permit_factor = permit.as_ref().and_then(|permit| permit.mismatch_factor(&sell));
+        let permit_mismatch = permit_factor.is_some();
+        risk_factors.extend(permit_factor);
+        let access_control_penalty = if permit_mismatch {
+            access_control_penalty.saturating_add(PERMIT_MISMATCH_PENALTY).min(100)
+        } else {
+            access_control_penalty
+        };
+
+        let proceeds_received = sell.proceeds_received();
+        let sim_detail = SimDetail::from_outcomes(&buy, &sell);
+        let (eth_received, sell_mocked) = match sell.status {
+            SellStatus::Sold { native_received, mocked } => (native_received, mocked),
+            SellStatus::Reverted(reason) => {
+                // ⛔ SELL REVERTED = HONEYPOT! risk_score = 100
+                risk_factors.push(format!("SELL REVERTED: {}", reason));
+                if sell.dynamic_blacklist_detected {
+                    risk_factors.push(
+                        "⛔ DYNAMIC BLACKLIST: sell reverted right after reading a seller-keyed storage flag"
+                            .to_string(),
+                    );
+                }
+                let mut result = HoneypotResult::honeypot(
+                    format!("⛔ SELL REVERTED: {} - CONFIRMED HONEYPOT!", reason),
+                    true,
+                    false,
+                    true, // sell_reverted = true
+                    access_control_penalty,
+                    risk_factors,
+                    start.elapsed().as_millis() as u64,
+                );
+                result.dynamic_blacklist_detected = sell.dynamic_blacklist_detected;
+                result.caller_type_gated = caller_type_gated;
+                result.transfer_to_pair_blocked = transfer_to_pair_blocked;
+                result.permit_supported = permit_supported;
+                result.permit_mismatch = permit_mismatch;
+                result.gas_price_gated = gas_price_gated;
+                result.direct_holder_sell_blocked = direct_holder_sell_blocked;
+                result.contract_recipient_blocked = contract_recipient_blocked;
+                result.sim_detail = sim_detail;
+                return Ok(result);
+            }
+            SellStatus::Failed(e) => {
+                let mut result = HoneypotResult::honeypot(
                     format!("Sell failed: {} - HONEYPOT!", e),
                     true,
                     false,
@@ -838,62 +3199,512 @@ impl HoneypotDetector {
                     access_control_penalty,
                     risk_factors,
                     start.elapsed().as_millis() as u64,
-                ));
+                );
+                result.sim_detail = sim_detail;
+                return Ok(result);
             }
         };
 
+        // Router reported proceeds the seller's balance never saw: output sent elsewhere
+        if !proceeds_received {
+            risk_factors.push(format!(
+                "PROCEEDS DIVERTED: router reported {:.6} {}, seller received {:.6}",
+                wei_to_eth(eth_received),
+                self.native_symbol,
+                wei_to_eth(sell.balance_delta.unwrap_or_default()),
+            ));
+            let mut result = HoneypotResult::honeypot(
+                "⛔ SELL PROCEEDS DIVERTED: sell succeeded but the output never reached the seller - HONEYPOT!"
+                    .to_string(),
+                true,
+                false,
+                false,
+                access_control_penalty,
+                risk_factors,
+                start.elapsed().as_millis() as u64,
+            );
+            result.proceeds_received = false;
+            result.same_block_sell_blocked = same_block_sell_blocked;
+            result.caller_type_gated = caller_type_gated;
+            result.transfer_to_pair_blocked = transfer_to_pair_blocked;
+            result.permit_supported = permit_supported;
+            result.permit_mismatch = permit_mismatch;
+            result.gas_price_gated = gas_price_gated;
+            result.direct_holder_sell_blocked = direct_holder_sell_blocked;
+            result.contract_recipient_blocked = contract_recipient_blocked;
+            result.sim_detail = sim_detail;
+            return Ok(result);
+        }
+
         // ============================================
         // STEP 5: Calculate taxes
         // ============================================
         let latency_ms = start.elapsed().as_millis() as u64;
 
-        // Calculate loss percentage
-        // If we put in X ETH and got back Y ETH, loss = (X - Y) / X * 100
-        let test_amount_f64: f64 = u128::try_from(test_amount_eth).unwrap_or(0) as f64;
-        let received_f64: f64 = u128::try_from(eth_received).unwrap_or(0) as f64;
-
-        if test_amount_f64 == 0.0 {
-            return Ok(HoneypotResult::honeypot(
-                "Invalid test amount".to_string(),
-                buy_success,
-                sell_success,
-                sell_reverted,
+        let confidence = if buy.mocked || sell_mocked { CONFIDENCE_MOCKED } else { CONFIDENCE_VERIFIED };
+        let gas_used = buy.gas_used + approve_gas + sell.gas_used;
+        let gas_cost = U256::from(gas_used) * U256::from(SIM_GAS_PRICE_WEI);
+
+        let mut result = if !self.pipeline.runs(Stage::ComputeTax) {
+            // Sellable, loss not measured
+            let mut result = HoneypotResult::safe(0.0, 0.0, access_control_penalty, risk_factors, latency_ms);
+            result.reason = "Token passed buy/sell simulation (tax not computed)".to_string();
+            result
+        } else {
+            let Some(tax) =
+                compute_tax(test_amount_eth, eth_received, gas_cost, buy.buy_fee_percent, &self.loss_cutoffs)
+            else {
+                return Ok(HoneypotResult::honeypot(
+                    "Invalid test amount".to_string(),
+                    true,
+                    true,
+                    false,
+                    access_control_penalty,
+                    risk_factors,
+                    latency_ms,
+                ));
+            };
+
+            // Loss above the chain's cutoff = likely honeypot or extreme tax
+            if tax.exceeds_honeypot_cutoff {
+                risk_factors.push(format!("Extreme loss: {:.2}%", tax.total_loss_percent));
+                return Ok(HoneypotResult::honeypot(
+                    format!(
+                        "Extreme loss: {:.2}% - likely honeypot or high tax",
+                        tax.total_loss_percent
+                    ),
+                    true,
+                    true,
+                    false,
+                    access_control_penalty,
+                    risk_factors,
+                    latency_ms,
+                )
+                .with_confidence(confidence));
+            }
+
+            // Advertised tax vs measured, only when both sides were really measured
+            let declared_tax = if buy.buy_fee_percent.is_some() && confidence == CONFIDENCE_VERIFIED {
+                self.read_declared_tax(db, token)
+            } else {
+                DeclaredTax::default()
+            };
+            let tax_mismatch = declared_tax.understated(tax.buy_tax_percent, tax.sell_tax_percent);
+            if tax_mismatch {
+                let declared = |percent: Option<f64>| percent.map_or("-".to_string(), |p| format!("{:.2}%", p));
+                risk_factors.push(format!(
+                    "TAX UNDERSTATED: declared buy {} / sell {}, measured buy {:.2}% / sell {:.2}%",
+                    declared(declared_tax.buy_percent),
+                    declared(declared_tax.sell_percent),
+                    tax.buy_tax_percent,
+                    tax.sell_tax_percent
+                ));
+            }
+
+            let reason = match tax.fee_class {
+                Some(FeeClass::Reflection { fee_percent }) => {
+                    risk_factors.push(format!("REFLECTION: ~{:.2}% fee on every transfer", fee_percent));
+                    format!("💸 Reflection token: ~{:.2}% fee per transfer - safe but expensive", fee_percent)
+                }
+                Some(FeeClass::HighTax { fee_percent }) => {
+                    risk_factors.push(format!(
+                        "HIGH TAX: buy {:.2}% / sell {:.2}%",
+                        tax.buy_tax_percent, tax.sell_tax_percent
+                    ));
+                    format!("💸 High tax: up to {:.2}% per trade - sellable but expensive", fee_percent)
+                }
+                None => "Token passed buy/sell simulation".to_string(),
+            };
+
+            let mut result = HoneypotResult::safe(
+                tax.buy_tax_percent,
+                tax.sell_tax_percent,
                 access_control_penalty,
                 risk_factors,
                 latency_ms,
+            );
+            result.total_loss_percent = tax.total_loss_percent;
+            result.net_loss_percent = Some(tax.net_loss_percent);
+            result.reason = reason;
+            result.fee_class = tax.fee_class;
+            result.declared_tax = declared_tax;
+            result.declared_vs_measured_tax_mismatch = tax_mismatch;
+            result
+        };
+        result.same_block_sell_blocked = same_block_sell_blocked;
+        result.sell_lock_blocks = sell.sell_lock_blocks;
+        result.sim_detail = sim_detail;
+        result.caller_type_gated = caller_type_gated;
+        result.transfer_to_pair_blocked = transfer_to_pair_blocked;
+        result.permit_supported = permit_supported;
+        result.permit_mismatch = permit_mismatch;
+        result.gas_price_gated = gas_price_gated;
+        result.direct_holder_sell_blocked = direct_holder_sell_blocked;
+        result.contract_recipient_blocked = contract_recipient_blocked;
+        result.gas_used = gas_used;
+        result.sell_buy_gas_ratio = sell_buy_gas_ratio(buy.gas_used, sell.gas_used);
+        if let Some(ratio) = result.sell_buy_gas_ratio.filter(|r| *r >= ASYMMETRIC_GAS_RATIO) {
+            result.asymmetric_gas = true;
+            result.risk_factors.push(format!(
+                "ASYMMETRIC GAS: sell uses {:.1}x the buy's gas ({} vs {})",
+                ratio, sell.gas_used, buy.gas_used
             ));
         }
+        result.confidence = confidence;
+        if buy.mocked || sell_mocked {
+            result = result.with_warning(WARNING_MOCKED_AMOUNTS);
+        }
+        Ok(result)
+    }
 
-        let total_loss_percent = ((test_amount_f64 - received_f64) / test_amount_f64) * 100.0;
+    /// SimBuy stage: native → token through the primary router
+    /// Err carries the reason the buy failed
+    fn sim_buy_stage<ExtDB>(
+        &self,
+        db: &mut CacheDB<ExtDB>,
+        test_account: Address,
+        token: Address,
+        test_amount_eth: U256,
+    ) -> Result<BuyOutcome>
+    where
+        ExtDB: DatabaseRef,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let (tokens, gas_used) = self.simulate_buy(db, test_account, token, test_amount_eth)?;
+
+        // If using mock bytecode, tokens will be minimal
+        // Use test_amount as proxy for tokens received
+        if tokens < U256::from(1000u64) {
+            // Mock mode - assume we got tokens proportional to ETH input
+            return Ok(BuyOutcome {
+                tokens_received: test_amount_eth,
+                buy_fee_percent: None,
+                mocked: true,
+                gas_used,
+            });
+        }
 
-        // If loss > 50%, likely honeypot or extreme tax
-        if total_loss_percent > 50.0 {
-            risk_factors.push(format!("Extreme loss: {:.2}%", total_loss_percent));
-            return Ok(HoneypotResult::honeypot(
-                format!(
-                    "Extreme loss: {:.2}% - likely honeypot or high tax",
-                    total_loss_percent
-                ),
-                buy_success,
-                sell_success,
-                sell_reverted,
-                access_control_penalty,
-                risk_factors,
-                latency_ms,
-            ));
+        // Buy fee = pool output the router reported vs balance actually credited
+        Ok(match self.read_token_balance(db, token, test_account) {
+            // Fee-on-transfer: sell what is held, not what the pool sent
+            Some(held) if !held.is_zero() && held <= tokens => BuyOutcome {
+                tokens_received: held,
+                buy_fee_percent: Some(loss_percent(tokens, held)),
+                mocked: false,
+                gas_used,
+            },
+            _ => BuyOutcome {
+                tokens_received: tokens,
+                buy_fee_percent: None,
+                mocked: false,
+                gas_used,
+            },
+        })
+    }
+
+    /// SimSell stage: token → native in the buy block with the storage probe attached;
+    /// a same-block revert is retried `sell_delay_blocks` later (anti-bot delay), then
+    /// up to `sell_lock_max_blocks` later for the shortest lock-on-buy
+    fn sim_sell_stage<ExtDB>(
+        &self,
+        db: &mut CacheDB<ExtDB>,
+        test_account: Address,
+        token: Address,
+        tokens_received: U256,
+        test_amount_eth: U256,
+    ) -> SellOutcome
+    where
+        ExtDB: DatabaseRef + Clone,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        // Snapshot first: a same-block revert is retried a few blocks later
+        let pre_sell_db = (self.sell_delay_blocks > 0 || self.sell_lock_max_blocks > 0).then(|| db.clone());
+        // Proceeds are checked against the seller's balance, not the router's return value
+        let balance_before = Self::native_balance(db, test_account);
+        let (mut sell_result, dynamic_blacklist_detected, mut gas_used) =
+            match self.simulate_sell_probed(db, test_account, token, tokens_received) {
+                Ok((outcome, probe, gas)) => (Ok(outcome), probe.dynamic_blacklist_detected(), gas),
+                Err(e) => (Err(e), false, 0),
+            };
+        let mut balance_after = Self::native_balance(db, test_account);
+        let mut final_token_balance = self.read_token_balance(db, token, test_account);
+
+        let mut same_block_revert = None;
+        let mut sell_lock_blocks = None;
+        if let (Ok(SimSellResult::Reverted(reason)), Some(pre_sell_db)) = (&sell_result, pre_sell_db) {
+            // Each offset sells from its own copy of the pre-sell state
+            let delayed = search_sell_lock(self.sell_delay_blocks, self.sell_lock_max_blocks, |blocks| {
+                let mut delayed_db = pre_sell_db.clone();
+                match self.simulate_sell_with_revert_detection(
+                    &mut delayed_db,
+                    test_account,
+                    token,
+                    tokens_received,
+                    blocks,
+                ) {
+                    Ok((SimSellResult::Success(eth), delayed_gas)) => Some((delayed_db, eth, delayed_gas)),
+                    _ => None,
+                }
+            });
+            if let Some((blocks, (mut delayed_db, eth, delayed_gas))) = delayed {
+                same_block_revert = Some(reason.clone());
+                sell_lock_blocks = Some(blocks);
+                sell_result = Ok(SimSellResult::Success(eth));
+                gas_used = delayed_gas;
+                balance_after = Self::native_balance(&delayed_db, test_account);
+                final_token_balance = self.read_token_balance(&mut delayed_db, token, test_account);
+            }
+        }
+        let balance_delta = balance_before.zip(balance_after).map(|(before, after)| {
+            after
+                .saturating_add(U256::from(gas_used) * U256::from(SIM_GAS_PRICE_WEI))
+                .saturating_sub(before)
+        });
+
+        let status = match sell_result {
+            // If using mock bytecode, eth might be very small
+            // Mock mode - assume ~95% return (5% total tax is reasonable)
+            Ok(SimSellResult::Success(eth)) if eth < U256::from(1000u64) => SellStatus::Sold {
+                native_received: test_amount_eth * U256::from(95u64) / U256::from(100u64),
+                mocked: true,
+            },
+            Ok(SimSellResult::Success(eth)) => SellStatus::Sold { native_received: eth, mocked: false },
+            Ok(SimSellResult::Reverted(reason)) => SellStatus::Reverted(reason),
+            Err(e) => SellStatus::Failed(e.to_string()),
+        };
+
+        SellOutcome {
+            status,
+            dynamic_blacklist_detected,
+            same_block_revert,
+            sell_lock_blocks,
+            gas_used,
+            balance_delta,
+            final_token_balance,
+            final_native_balance: balance_after,
         }
+    }
 
-        // Estimate buy/sell tax (simplified - assumes equal split)
-        let buy_tax = total_loss_percent / 2.0;
-        let sell_tax = total_loss_percent / 2.0;
+    /// Native balance of `account` in the simulation state (zero for an untouched account)
+    fn native_balance<ExtDB>(db: &CacheDB<ExtDB>, account: Address) -> Option<U256>
+    where
+        ExtDB: DatabaseRef,
+    {
+        db.basic_ref(account).ok().map(|info| info.map_or(U256::ZERO, |info| info.balance))
+    }
 
-        Ok(HoneypotResult::safe(
-            buy_tax,
-            sell_tax,
-            access_control_penalty,
-            risk_factors,
-            latency_ms,
-        ))
+    /// Run Buy → Approve → Sell in REVM with the opcode trace inspector on the sell
+    /// Returns the compact trace (which opcode/call caused the revert, if any)
+    pub fn trace_sell(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+        router_bytecode: Option<Bytes>,
+        token_bytecode: Option<Bytes>,
+    ) -> Result<SellTrace> {
+        self.trace_sell_funded(token, test_amount_eth, router_bytecode, token_bytecode, None)
+    }
+
+    /// `trace_sell` with the mock WETH holding `weth_liquidity` (None = the default liquidity)
+    fn trace_sell_funded(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+        router_bytecode: Option<Bytes>,
+        token_bytecode: Option<Bytes>,
+        weth_liquidity: Option<U256>,
+    ) -> Result<SellTrace> {
+        let (mut db, test_account, _) = self.build_simulation_db(
+            token,
+            test_amount_eth,
+            router_bytecode,
+            token_bytecode,
+            None,
+            None,
+        );
+        if let (Some(liquidity), Some(weth)) = (weth_liquidity, db.accounts.get_mut(&self.weth)) {
+            weth.info.balance = liquidity;
+        }
+
+        let (tokens, _) = self
+            .simulate_buy(&mut db, test_account, token, test_amount_eth)
+            .map_err(|e| eyre!("Buy failed before sell trace: {}", e))?;
+        // Mock mode - use test amount as token proxy (same as detect)
+        let tokens = if tokens < U256::from(1000u64) { test_amount_eth } else { tokens };
+
+        self.simulate_approve(&mut db, test_account, token, tokens)
+            .map_err(|e| eyre!("Approve failed before sell trace: {}", e))?;
+
+        let (outcome, inspector) = self.simulate_sell_traced(&mut db, test_account, token, tokens, 0)?;
+        let trace = match outcome {
+            SimSellResult::Success(_) => inspector.into_trace(false, None),
+            SimSellResult::Reverted(reason) => inspector.into_trace(true, Some(reason)),
+        };
+
+        info!("🔬 Sell trace: {} steps, reverted: {}", trace.total_steps, trace.reverted);
+        Ok(trace)
+    }
+
+    /// Async wrapper for `trace_sell`: fetches real token + router bytecode first, and
+    /// funds the mock WETH with `pair`'s real WETH reserve when the pair is known
+    pub async fn trace_sell_async(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+        pair: Option<Address>,
+    ) -> Result<SellTrace> {
+        let code = self.fetch_code(token, None).await;
+        let weth_liquidity = match pair {
+            Some(pair) => self.forked_weth_liquidity(pair).await,
+            None => None,
+        };
+        self.trace_sell_funded(token, test_amount_eth, code.router, code.token, weth_liquidity)
+    }
+
+    /// Build an in-memory simulation DB (funded random caller, router, WETH, token, pair)
+    /// Returns the DB, the random caller, and the token bytecode used
+    fn build_simulation_db(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+        router_bytecode: Option<Bytes>,
+        token_bytecode: Option<Bytes>,
+        pair_bytecode: Option<Bytes>,
+        pair_address: Option<Address>,
+    ) -> (CacheDB<EmptyDB>, Address, Bytes) {
+        // Create fresh database for simulation
+        let mut db = CacheDB::new(EmptyDB::default());
+
+        // ============================================
+        // STEP 0: Generate RANDOM caller address
+        // This prevents honeypots from whitelisting deployer/known addresses
+        // (seeded via `with_caller_seed` for reproducible runs)
+        // ============================================
+        let test_account = self.sim_address("caller");
+
+        // Always fund enough for the trade itself plus gas headroom
+        let test_balance = self
+            .test_account_balance
+            .max(test_amount_eth.saturating_mul(U256::from(2u64)));
+        db.insert_account_info(
+            test_account,
+            AccountInfo {
+                balance: test_balance,
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+            },
+        );
+
+        // Setup router with bytecode (if provided, otherwise use minimal mock)
+        let router_code = router_bytecode.unwrap_or_else(|| self.mock_router_bytecode());
+        db.insert_account_info(
+            self.primary_router(),
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(router_code)),
+            },
+        );
+
+        // Setup WETH
+        db.insert_account_info(
+            self.weth,
+            AccountInfo {
+                balance: self.weth_liquidity,
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(self.mock_weth_bytecode())),
+            },
+        );
+
+        // Setup token with bytecode
+        let token_code = token_bytecode.unwrap_or_else(|| self.mock_erc20_bytecode());
+        db.insert_account_info(
+            token,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(token_code.clone())),
+            },
+        );
+
+        // Setup pair if provided
+        if let (Some(pair), Some(code)) = (pair_address, pair_bytecode) {
+            db.insert_account_info(
+                pair,
+                AccountInfo {
+                    balance: U256::ZERO,
+                    nonce: 0,
+                    code_hash: KECCAK_EMPTY,
+                    code: Some(Bytecode::new_raw(code)),
+                },
+            );
+        }
+
+        (db, test_account, token_code)
+    }
+
+    /// `build_simulation_db` for `detect_offline`: the supplied WETH replaces the mock and
+    /// the pair is seeded as a funded Uniswap V2 pair (tokens, reserves, lock, both balances).
+    /// Err when a reserve is zero / above uint112 or a balanceOf slot cannot be found
+    fn offline_simulation_db(&self, input: &OfflineAnalysisInput) -> Result<(CacheDB<EmptyDB>, Address)> {
+        let max_reserve = (U256::from(1u64) << 112) - U256::from(1u64);
+        for (name, reserve) in [("token", input.token_reserve), ("WETH", input.weth_reserve)] {
+            if reserve.is_zero() || reserve > max_reserve {
+                return Err(eyre!("Offline {} reserve {} outside 1..2^112", name, reserve));
+            }
+        }
+
+        let (mut db, test_account, _) = self.build_simulation_db(
+            input.token,
+            input.test_amount_eth,
+            Some(input.router_bytecode.clone()),
+            Some(input.token_bytecode.clone()),
+            Some(input.pair_bytecode.clone()),
+            Some(input.pair),
+        );
+        // WETH holds the native backing the pair's wrapped reserve
+        db.insert_account_info(
+            self.weth,
+            AccountInfo {
+                balance: input.weth_reserve,
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(input.weth_bytecode.clone())),
+            },
+        );
+
+        let (token0, token1, reserve0, reserve1) = if input.token < self.weth {
+            (input.token, self.weth, input.token_reserve, input.weth_reserve)
+        } else {
+            (self.weth, input.token, input.weth_reserve, input.token_reserve)
+        };
+        let word = |address: Address| U256::from_be_slice(address.as_slice());
+        let pair_slots = [
+            (V2_PAIR_TOKEN0_SLOT, word(token0)),
+            (V2_PAIR_TOKEN1_SLOT, word(token1)),
+            (V2_PAIR_RESERVES_SLOT, reserve0 | (reserve1 << 112)),
+            (V2_PAIR_UNLOCKED_SLOT, U256::from(1u64)),
+        ];
+        for (slot, value) in pair_slots {
+            db.insert_account_storage(input.pair, U256::from(slot), value)
+                .map_err(|e| eyre!("Offline pair state write failed: {:?}", e))?;
+        }
+
+        // The pair's balances must match its reserves, or the first swap skims the difference
+        let balances = [(input.token, "token", input.token_reserve), (self.weth, "WETH", input.weth_reserve)];
+        for (contract, name, reserve) in balances {
+            let slot = self
+                .find_balance_slot(&mut db, contract, input.pair)
+                .ok_or_else(|| eyre!("Offline {} balanceOf storage slot not found", name))?;
+            db.insert_account_storage(contract, slot, reserve)
+                .map_err(|e| eyre!("Offline {} balance write failed: {:?}", name, e))?;
+        }
+
+        Ok((db, test_account))
     }
 
     /// Generate a random Ethereum address for simulation
@@ -905,118 +3716,507 @@ impl HoneypotDetector {
         Address::from(FixedBytes::<20>::from(bytes))
     }
 
-    /// Scan bytecode for access control functions that could be used for blacklisting
-    /// Returns penalty score (0 or 50)
-    fn scan_access_control_functions(
-        &self,
-        bytecode: &Bytes,
-        risk_factors: &mut Vec<String>,
-    ) -> u8 {
-        let code_hex = hex::encode(bytecode.as_ref());
-        let mut penalty: u8 = 0;
-
-        // Function selectors for dangerous access control functions
-        // These are keccak256 hashes of function signatures (first 4 bytes)
-        let dangerous_selectors = [
-            // setBots(address[],bool) - common honeypot function
-            ("974d396d", "setBots"),
-            // setBot(address,bool)
-            ("3d18678e", "setBot"),
-            // blacklistAddress(address)
-            ("e4997dc5", "blacklistAddress"),
-            // addToBlacklist(address)
-            ("44337ea1", "addToBlacklist"),
-            // isBot(address)
-            ("b515566a", "isBot"),
-            // setBlacklist(address,bool)
-            ("0ecb93c0", "setBlacklist"),
-            // addBot(address)
-            ("09218e91", "addBot"),
-            // delBot(address)
-            ("363bf964", "delBot"),
-            // setTradingEnabled(bool) - can disable trading
-            ("8a8c523c", "setTradingEnabled"),
-            // enableTrading()
-            ("8da5cb5b", "enableTrading"),
-            // setMaxTxAmount - can limit sells
-            ("ec28438a", "setMaxTxAmount"),
-            // setMaxWalletSize - can limit holdings
-            ("f1d5f517", "setMaxWalletSize"),
-        ];
-
-        for (selector, name) in dangerous_selectors.iter() {
-            if code_hex.contains(selector) {
-                risk_factors.push(format!("⚠️ Access Control: {} detected", name));
-                penalty = 50; // +50 penalty for any access control function
-            }
+    /// Simulated account for `role` ("caller", "spender", ...): random by default,
+    /// keccak256(seed ‖ role) when `with_caller_seed` is set
+    fn sim_address(&self, role: &str) -> Address {
+        match self.caller_seed {
+            Some(seed) => Address::from_slice(&Self::seeded_word(seed, role)[12..]),
+            None => Self::generate_random_address(),
         }
+    }
 
-        // Also check for common blacklist storage patterns
-        // mapping(address => bool) bots/blacklist
-        if code_hex.contains("626f7473") || // "bots" in hex
-           code_hex.contains("626c61636b6c697374")
-        {
-            // "blacklist" in hex
-            if penalty == 0 {
-                risk_factors.push("⚠️ Blacklist storage pattern detected".to_string());
-                penalty = 50;
+    fn seeded_word(seed: u64, role: &str) -> B256 {
+        let mut preimage = seed.to_be_bytes().to_vec();
+        preimage.extend_from_slice(role.as_bytes());
+        keccak256(preimage)
+    }
+
+    /// Scan bytecode for the SELFDESTRUCT opcode (0xff)
+    /// Walks opcodes so 0xff bytes inside PUSH data are not counted, and
+    /// ignores the trailing Solidity CBOR metadata blob.
+    /// Returns SELFDESTRUCT_PENALTY if found.
+    pub(crate) fn scan_selfdestruct(bytecode: &Bytes, risk_factors: &mut Vec<String>) -> u8 {
+        let code = Self::strip_cbor_metadata(bytecode.as_ref());
+
+        let mut pc = 0;
+        while pc < code.len() {
+            let op = code[pc];
+            if op == 0xff {
+                risk_factors.push(
+                    "⛔ SELFDESTRUCT opcode present - contract can be destroyed, zeroing holders"
+                        .to_string(),
+                );
+                return SELFDESTRUCT_PENALTY;
             }
+            // PUSH1..PUSH32 carry 1..32 bytes of immediate data
+            if (0x60..=0x7f).contains(&op) {
+                pc += (op - 0x5f) as usize;
+            }
+            pc += 1;
         }
 
-        penalty
+        0
+    }
+
+    /// Strip Solidity's CBOR metadata (last 2 bytes = big-endian metadata length)
+    fn strip_cbor_metadata(code: &[u8]) -> &[u8] {
+        if code.len() < 2 {
+            return code;
+        }
+        let meta_len = u16::from_be_bytes([code[code.len() - 2], code[code.len() - 1]]) as usize;
+        let total = meta_len + 2;
+        // CBOR metadata is a map: 0xa1..0xa5 (1-5 entries)
+        if total < code.len() && (0xa1..=0xa5).contains(&code[code.len() - total]) {
+            &code[..code.len() - total]
+        } else {
+            code
+        }
     }
 
     /// Simulate buying tokens with ETH
-    fn simulate_buy(
+    fn simulate_buy<ExtDB>(
         &self,
-        db: &mut CacheDB<EmptyDB>,
+        db: &mut CacheDB<ExtDB>,
         from: Address,
         token: Address,
         amount_eth: U256,
-    ) -> Result<U256> {
-        let path = vec![self.weth, token];
-        let deadline = U256::from(u64::MAX);
-
-        let calldata = swapExactETHForTokensCall {
-            amountOutMin: U256::ZERO, // Accept any amount for testing
-            path,
-            to: from,
-            deadline,
-        }
-        .abi_encode();
+    ) -> Result<(U256, u64)>
+    where
+        ExtDB: DatabaseRef,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let calldata = self.buy_calldata(token, from);
 
-        let result =
-            self.execute_tx(db, from, self.primary_router(), amount_eth, Bytes::from(calldata), 0)?;
+        let (result, gas_used) =
+            self.execute_tx_metered(db, from, self.primary_router(), amount_eth, calldata, 0)?;
 
         // Parse return value (uint256[] amounts)
         // Last element is tokens received
         if result.len() >= 64 {
             // Skip array offset and length, get last uint256
             let tokens = U256::from_be_slice(&result[result.len() - 32..]);
-            Ok(tokens)
+            Ok((tokens, gas_used))
         } else {
             // Fallback: assume some tokens received
-            Ok(U256::from(1_000_000_000_000_000_000u128)) // 1 token
+            Ok((U256::from(1_000_000_000_000_000_000u128), gas_used)) // 1 token
+        }
+    }
+
+    /// REVM config: the chain's hardfork and chain id (txs carry `chain_id`, which must match)
+    fn cfg_env(&self) -> CfgEnvWithHandlerCfg {
+        let mut cfg = CfgEnv::default();
+        cfg.chain_id = self.chain_id;
+        CfgEnvWithHandlerCfg::new_with_spec_id(cfg, self.spec_id)
+    }
+
+    /// Read-only `balanceOf(holder)` against the simulation state (no commit, no nonce)
+    fn read_token_balance<ExtDB>(&self, db: &mut CacheDB<ExtDB>, token: Address, holder: Address) -> Option<U256>
+    where
+        ExtDB: DatabaseRef,
+    {
+        let calldata = balanceOfCall { account: holder }.abi_encode();
+        let bytes = self.static_call(db, holder, token, calldata)?;
+        Some(U256::from_be_slice(&bytes[..32]))
+    }
+
+    /// Read-only call returning an address word (`factory()`, `getPair(..)`)
+    fn read_address<ExtDB>(&self, db: &mut CacheDB<ExtDB>, to: Address, data: Vec<u8>) -> Option<Address>
+    where
+        ExtDB: DatabaseRef,
+    {
+        let bytes = self.static_call(db, Address::ZERO, to, data)?;
+        Some(Address::from_slice(&bytes[12..32]))
+    }
+
+    /// Execute without committing; Some(output) if it succeeded with at least one word
+    fn static_call<ExtDB>(&self, db: &mut CacheDB<ExtDB>, caller: Address, to: Address, data: Vec<u8>) -> Option<Bytes>
+    where
+        ExtDB: DatabaseRef,
+    {
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 100_000,
+            transact_to: TxKind::Call(to),
+            data: Bytes::from(data),
+            nonce: None,
+            chain_id: Some(self.chain_id),
+            ..Default::default()
+        };
+        let cfg = self.cfg_env();
+        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, self.sim_block_env(0), tx_env);
+
+        let mut evm = Evm::builder().with_db(db).with_env_with_handler_cfg(env).build();
+        match evm.transact().ok()?.result {
+            ExecutionResult::Success { output: Output::Call(bytes), .. } if bytes.len() >= 32 => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Taxes advertised by public getters (`buyTax()`, `_taxFee()`, ...), in percent
+    fn read_declared_tax<ExtDB>(&self, db: &mut CacheDB<ExtDB>, token: Address) -> DeclaredTax
+    where
+        ExtDB: DatabaseRef,
+    {
+        let mut read = |getters: &[&str]| {
+            getters.iter().find_map(|signature| {
+                let selector = keccak256(signature.as_bytes())[..4].to_vec();
+                let bytes = self.static_call(db, Address::ZERO, token, selector)?;
+                DeclaredTax::percent_from_raw(U256::from_be_slice(&bytes[..32]))
+            })
+        };
+        let flat = read(&FLAT_TAX_GETTERS);
+        DeclaredTax {
+            buy_percent: read(&BUY_TAX_GETTERS).or(flat),
+            sell_percent: read(&SELL_TAX_GETTERS).or(flat),
+        }
+    }
+
+    /// `transfer(pair)` fails while the same transfer to a fresh EOA succeeds
+    /// Both run on throwaway copies of the post-buy state
+    fn pair_transfer_blocked<ExtDB>(
+        &self,
+        db: &CacheDB<ExtDB>,
+        holder: Address,
+        token: Address,
+        pair: Address,
+        held: U256,
+    ) -> bool
+    where
+        ExtDB: DatabaseRef + Clone,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        // A slice of the balance stays under per-tx transfer limits
+        let amount = (held / U256::from(10u64)).max(U256::from(1u64));
+        let transfer_succeeds = |to: Address| self.transfer_succeeds(&mut db.clone(), holder, token, to, amount);
+        !transfer_succeeds(pair) && transfer_succeeds(self.sim_address("recipient"))
+    }
+
+    /// `transfer` to a fresh contract account fails while the same transfer to a fresh EOA
+    /// succeeds. Both run on throwaway copies of the post-buy state
+    fn contract_recipient_blocked<ExtDB>(&self, db: &CacheDB<ExtDB>, holder: Address, token: Address, held: U256) -> bool
+    where
+        ExtDB: DatabaseRef + Clone,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let amount = (held / U256::from(10u64)).max(U256::from(1u64));
+        let contract = self.sim_address("contract-recipient");
+        let mut with_contract = db.clone();
+        with_contract.insert_account_info(
+            contract,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 1,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(Bytes::from_static(&CONTRACT_RECIPIENT_RUNTIME))),
+            },
+        );
+        !self.transfer_succeeds(&mut with_contract, holder, token, contract, amount)
+            && self.transfer_succeeds(&mut db.clone(), holder, token, self.sim_address("recipient"), amount)
+    }
+
+    /// `transfer(to, amount)` from `holder` executed. Tokens without a return value count
+    /// as success; an explicit `false` does not
+    fn transfer_succeeds<ExtDB>(
+        &self,
+        db: &mut CacheDB<ExtDB>,
+        holder: Address,
+        token: Address,
+        to: Address,
+        amount: U256,
+    ) -> bool
+    where
+        ExtDB: DatabaseRef,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let calldata = Bytes::from(transferCall { to, amount }.abi_encode());
+        self.execute_tx(db, holder, token, U256::ZERO, calldata, 1)
+            .map(|out| out.len() < 32 || !U256::from_be_slice(&out[..32]).is_zero())
+            .unwrap_or(false)
+    }
+
+    /// Move `held` to a fresh keyed account, approve the router with a signed EIP-2612
+    /// permit (relayed by `holder`) and sell from the keyed account - no approve() call
+    fn permit_round_trip<ExtDB>(
+        &self,
+        db: &mut CacheDB<ExtDB>,
+        holder: Address,
+        token: Address,
+        held: U256,
+    ) -> PermitCheck
+    where
+        ExtDB: DatabaseRef,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let Some(domain) = self.static_call(db, holder, token, DOMAIN_SEPARATORCall {}.abi_encode()) else {
+            return PermitCheck::Unsupported;
+        };
+        let domain = B256::from_slice(&domain[..32]);
+
+        // Random key: the owner is as unknown to whitelists as the test account
+        let signer = match self.caller_seed {
+            Some(seed) => match PrivateKeySigner::from_bytes(&Self::seeded_word(seed, "permit-owner")) {
+                Ok(signer) => signer,
+                Err(e) => return PermitCheck::Unknown(format!("Seeded permit key: {}", e)),
+            },
+            None => PrivateKeySigner::random(),
+        };
+        let owner = signer.address();
+        db.insert_account_info(
+            owner,
+            AccountInfo {
+                balance: self.test_account_balance,
+                // sell_env sends at nonce 2; the owner sends nothing else
+                nonce: 2,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+            },
+        );
+
+        let calldata = Bytes::from(transferCall { to: owner, amount: held }.abi_encode());
+        if let Err(e) = self.execute_tx(db, holder, token, U256::ZERO, calldata, 1) {
+            return PermitCheck::Unknown(format!("transfer to permit owner failed: {}", e));
+        }
+        let amount = match self.read_token_balance(db, token, owner) {
+            Some(amount) if !amount.is_zero() => amount,
+            _ => return PermitCheck::Unknown("permit owner received no tokens".to_string()),
+        };
+
+        let Some(nonce) = self.static_call(db, owner, token, noncesCall { owner }.abi_encode()) else {
+            return PermitCheck::Unsupported;
+        };
+        let spender = self.primary_router();
+        let deadline = self.sim_block_env(0).timestamp + U256::from(3_600u64);
+        let permit = Permit {
+            owner,
+            spender,
+            value: amount,
+            nonce: U256::from_be_slice(&nonce[..32]),
+            deadline,
+        };
+
+        // EIP-712 digest: 0x1901 ++ domainSeparator ++ hashStruct(permit)
+        let mut preimage = Vec::with_capacity(66);
+        preimage.extend_from_slice(&[0x19, 0x01]);
+        preimage.extend_from_slice(domain.as_slice());
+        preimage.extend_from_slice(permit.eip712_hash_struct().as_slice());
+        let signature = match signer.sign_hash_sync(&keccak256(&preimage)) {
+            Ok(signature) => signature,
+            Err(e) => return PermitCheck::Unknown(format!("permit signing failed: {}", e)),
+        };
+
+        let calldata = permitCall {
+            owner,
+            spender,
+            value: amount,
+            deadline,
+            v: 27 + signature.v() as u8,
+            r: signature.r().into(),
+            s: signature.s().into(),
+        }
+        .abi_encode();
+        if let Err(e) = self.execute_tx(db, holder, token, U256::ZERO, Bytes::from(calldata), 2) {
+            return PermitCheck::Rejected(e.to_string());
+        }
+
+        match self.simulate_sell_with_revert_detection(db, owner, token, amount, 0) {
+            Ok((SimSellResult::Success(_), _)) => PermitCheck::Sellable,
+            Ok((SimSellResult::Reverted(reason), _)) => PermitCheck::SellBlocked(reason),
+            Err(e) => PermitCheck::Unknown(e.to_string()),
+        }
+    }
+
+    /// Credit a fresh account with `held` through the token's balanceOf storage slot
+    /// (no router buy, no transfer) and approve + sell from it, on a copy of `db`.
+    /// A buy-block revert gets the same delayed retry as the buyer's sell
+    fn direct_holder_round_trip<ExtDB>(&self, db: &CacheDB<ExtDB>, token: Address, held: U256) -> DirectHolderCheck
+    where
+        ExtDB: DatabaseRef + Clone,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let mut db = db.clone();
+        let holder = self.sim_address("direct-holder");
+        db.insert_account_info(
+            holder,
+            AccountInfo {
+                balance: self.test_account_balance,
+                // approve goes out at nonce 1, sell_env at nonce 2
+                nonce: 1,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+            },
+        );
+
+        let Some(slot) = self.find_balance_slot(&mut db, token, holder) else {
+            return DirectHolderCheck::Unknown("balanceOf storage slot not found".to_string());
+        };
+        if let Err(e) = db.insert_account_storage(token, slot, held) {
+            return DirectHolderCheck::Unknown(format!("balance write failed: {:?}", e));
+        }
+
+        if let Err(e) = self.simulate_approve(&mut db, holder, token, held) {
+            return DirectHolderCheck::SellBlocked(format!("approve: {}", e));
+        }
+        let pre_sell_db = (self.sell_delay_blocks > 0 || self.sell_lock_max_blocks > 0).then(|| db.clone());
+        match self.simulate_sell_with_revert_detection(&mut db, holder, token, held, 0) {
+            Ok((SimSellResult::Success(native_received), _)) => DirectHolderCheck::Sellable(native_received),
+            Ok((SimSellResult::Reverted(reason), _)) => {
+                let delayed = pre_sell_db.and_then(|pre_sell_db| {
+                    search_sell_lock(self.sell_delay_blocks, self.sell_lock_max_blocks, |blocks| {
+                        match self.simulate_sell_with_revert_detection(
+                            &mut pre_sell_db.clone(),
+                            holder,
+                            token,
+                            held,
+                            blocks,
+                        ) {
+                            Ok((SimSellResult::Success(native_received), _)) => Some(native_received),
+                            _ => None,
+                        }
+                    })
+                });
+                match delayed {
+                    Some((_, native_received)) => DirectHolderCheck::Sellable(native_received),
+                    None => DirectHolderCheck::SellBlocked(reason),
+                }
+            }
+            Err(e) => DirectHolderCheck::Unknown(e.to_string()),
+        }
+    }
+
+    /// Storage key of `holder`'s balance: mapping slot 0..MAX_BALANCE_SLOT_PROBE whose
+    /// write shows up in balanceOf, as keccak(holder ‖ slot) (Solidity) or keccak(slot ‖ holder) (Vyper).
+    /// Probes in place: every probed key is written back to its previous value
+    fn find_balance_slot<ExtDB>(&self, db: &mut CacheDB<ExtDB>, token: Address, holder: Address) -> Option<U256>
+    where
+        ExtDB: DatabaseRef + Clone,
+    {
+        let marker = U256::from(0x5eed_c0de_u64);
+        let holder_word = B256::left_padding_from(holder.as_slice());
+        (0..MAX_BALANCE_SLOT_PROBE).find_map(|index| {
+            let index_word = B256::from(U256::from(index));
+            [
+                keccak256([holder_word.as_slice(), index_word.as_slice()].concat()),
+                keccak256([index_word.as_slice(), holder_word.as_slice()].concat()),
+            ]
+            .into_iter()
+            .map(|key| U256::from_be_bytes(key.0))
+            .find(|&key| {
+                let Ok(previous) = db.storage_ref(token, key) else {
+                    return false;
+                };
+                if db.insert_account_storage(token, key, marker).is_err() {
+                    return false;
+                }
+                let found = self.read_token_balance(db, token, holder) == Some(marker);
+                let _ = db.insert_account_storage(token, key, previous);
+                found
+            })
+        })
+    }
+
+    /// Buy → approve → sell with a forwarder contract as holder and swap recipient
+    /// (tx.origin is an EOA, msg.sender / `to` have code). True if the round trip succeeds
+    fn contract_caller_round_trip(
+        &self,
+        token: Address,
+        test_amount_eth: U256,
+        router_bytecode: Option<Bytes>,
+        token_bytecode: Option<Bytes>,
+        pair_bytecode: Option<Bytes>,
+        pair_address: Option<Address>,
+    ) -> bool {
+        let (mut db, sender, _) = self.build_simulation_db(
+            token,
+            test_amount_eth,
+            router_bytecode,
+            token_bytecode,
+            pair_bytecode,
+            pair_address,
+        );
+        let forwarder = self.sim_address("forwarder");
+        db.insert_account_info(
+            forwarder,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 1,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(Bytes::from_static(&FORWARDER_RUNTIME))),
+            },
+        );
+        let via = |target: Address, payload: &[u8]| -> Bytes {
+            let mut data = target.to_vec();
+            data.extend_from_slice(payload);
+            Bytes::from(data)
+        };
+        let router = self.primary_router();
+
+        let buy = via(router, &self.buy_calldata(token, forwarder));
+        if let Err(e) = self.execute_tx(&mut db, sender, forwarder, test_amount_eth, buy, 0) {
+            info!("🤖 Contract-caller buy failed: {}", e);
+            return false;
+        }
+        let held = match self.read_token_balance(&mut db, token, forwarder) {
+            Some(held) if !held.is_zero() => held,
+            _ => return false,
+        };
+
+        let approve = via(token, &approveCall { spender: router, amount: held }.abi_encode());
+        if let Err(e) = self.execute_tx(&mut db, sender, forwarder, U256::ZERO, approve, 1) {
+            info!("🤖 Contract-caller approve failed: {}", e);
+            return false;
+        }
+
+        let sell = via(router, &self.sell_calldata(token, held, forwarder));
+        match self.execute_tx(&mut db, sender, forwarder, U256::ZERO, sell, 2) {
+            Ok(_) => true,
+            Err(e) => {
+                info!("🤖 Contract-caller sell failed: {}", e);
+                false
+            }
         }
     }
 
+    /// Native → token swap calldata (router flavour depends on the chain)
+    fn buy_calldata(&self, token: Address, to: Address) -> Bytes {
+        Self::test_swap(U256::ZERO, vec![self.weth, token]).encode_v2_buy(to, self.native_swap_abi())
+    }
+
+    /// Token → native swap calldata (router flavour depends on the chain)
+    fn sell_calldata(&self, token: Address, amount_tokens: U256, to: Address) -> Bytes {
+        Self::test_swap(amount_tokens, vec![token, self.weth]).encode_v2_sell(to, self.native_swap_abi())
+    }
+
+    /// Swap with no slippage bound and no deadline (accept any amount for testing)
+    fn test_swap(amount_in: U256, path: Vec<Address>) -> SwapParams {
+        SwapParams { amount_in, amount_out_min: U256::ZERO, path, deadline: U256::from(u64::MAX) }
+    }
+
+    /// Avalanche V2 routers expose `swapExactAVAXForTokens` instead of the ETH-named functions
+    fn native_swap_abi(&self) -> NativeSwapAbi {
+        NativeSwapAbi::for_chain(self.chain_id)
+    }
+
     /// Simulate approving router to spend tokens
-    fn simulate_approve(
+    fn simulate_approve<ExtDB>(
         &self,
-        db: &mut CacheDB<EmptyDB>,
+        db: &mut CacheDB<ExtDB>,
         from: Address,
         token: Address,
         amount: U256,
-    ) -> Result<()> {
+    ) -> Result<u64>
+    where
+        ExtDB: DatabaseRef,
+        ExtDB::Error: std::fmt::Debug,
+    {
         let calldata = approveCall {
             spender: self.primary_router(),
             amount,
         }
         .abi_encode();
 
-        self.execute_tx(db, from, token, U256::ZERO, Bytes::from(calldata), 1)?;
+        let (_, gas_used) = self.execute_tx_metered(db, from, token, U256::ZERO, Bytes::from(calldata), 1)?;
 
-        Ok(())
+        Ok(gas_used)
     }
 
     /// Simulate selling tokens for ETH
@@ -1028,20 +4228,10 @@ impl HoneypotDetector {
         token: Address,
         amount_tokens: U256,
     ) -> Result<U256> {
-        let path = vec![token, self.weth];
-        let deadline = U256::from(u64::MAX);
-
-        let calldata = swapExactTokensForETHCall {
-            amountIn: amount_tokens,
-            amountOutMin: U256::ZERO, // Accept any amount for testing
-            path,
-            to: from,
-            deadline,
-        }
-        .abi_encode();
+        let calldata = self.sell_calldata(token, amount_tokens, from);
 
         let result =
-            self.execute_tx(db, from, self.primary_router(), U256::ZERO, Bytes::from(calldata), 2)?;
+            self.execute_tx(db, from, self.primary_router(), U256::ZERO, calldata, 2)?;
 
         // Parse return value
         if result.len() >= 64 {
@@ -1054,136 +4244,249 @@ impl HoneypotDetector {
 
     /// Simulate selling tokens with explicit revert detection
     /// This is the CRITICAL function for honeypot detection
-    fn simulate_sell_with_revert_detection(
+    /// `blocks_ahead` > 0 executes the sell that many blocks after the buy
+    fn simulate_sell_with_revert_detection<ExtDB>(
+        &self,
+        db: &mut CacheDB<ExtDB>,
+        from: Address,
+        token: Address,
+        amount_tokens: U256,
+        blocks_ahead: u64,
+    ) -> Result<(SimSellResult, u64)>
+    where
+        ExtDB: DatabaseRef,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let env = self.sell_env(from, token, amount_tokens, blocks_ahead);
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_env_with_handler_cfg(env)
+            .build();
+
+        let result = evm
+            .transact_commit()
+            .map_err(|e| eyre!("EVM error: {:?}", e))?;
+        let gas_used = result.gas_used();
+
+        Ok((Self::sell_outcome(result), gas_used))
+    }
+
+    /// Same as `simulate_sell_with_revert_detection`, but with the opcode
+    /// trace inspector attached (expensive - only on explicit request)
+    fn simulate_sell_traced(
         &self,
         db: &mut CacheDB<EmptyDB>,
         from: Address,
         token: Address,
         amount_tokens: U256,
-    ) -> Result<SimSellResult> {
-        let path = vec![token, self.weth];
-        let deadline = U256::from(u64::MAX);
+        blocks_ahead: u64,
+    ) -> Result<(SimSellResult, SellTraceInspector)> {
+        let env = self.sell_env(from, token, amount_tokens, blocks_ahead);
 
-        let calldata = swapExactTokensForETHCall {
-            amountIn: amount_tokens,
-            amountOutMin: U256::ZERO,
-            path,
-            to: from,
-            deadline,
-        }
-        .abi_encode();
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(SellTraceInspector::new())
+            .with_env_with_handler_cfg(env)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm
+            .transact_commit()
+            .map_err(|e| eyre!("EVM error: {:?}", e))?;
+        let inspector = std::mem::take(&mut evm.context.external);
+
+        Ok((Self::sell_outcome(result), inspector))
+    }
+
+    /// Same-block sell with the storage probe attached (runtime blacklist detection)
+    fn simulate_sell_probed<ExtDB>(
+        &self,
+        db: &mut CacheDB<ExtDB>,
+        from: Address,
+        token: Address,
+        amount_tokens: U256,
+    ) -> Result<(SimSellResult, StorageProbeInspector, u64)>
+    where
+        ExtDB: DatabaseRef,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let env = self.sell_env(from, token, amount_tokens, 0);
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(StorageProbeInspector::new())
+            .with_env_with_handler_cfg(env)
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm
+            .transact_commit()
+            .map_err(|e| eyre!("EVM error: {:?}", e))?;
+        let probe = std::mem::take(&mut evm.context.external);
+        let gas_used = result.gas_used();
+
+        Ok((Self::sell_outcome(result), probe, gas_used))
+    }
+
+    /// Sell at GAS_GATE_LOW_PRICE_WEI and GAS_GATE_HIGH_PRICE_WEI, each on its own copy
+    /// of `db`. Some((low_ok, high_ok)) when both sells executed (revert or success)
+    fn gas_price_sells<ExtDB>(
+        &self,
+        db: &CacheDB<ExtDB>,
+        from: Address,
+        token: Address,
+        amount_tokens: U256,
+    ) -> Option<(bool, bool)>
+    where
+        ExtDB: DatabaseRef + Clone,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        let sells_at = |gas_price: u64| {
+            let env = self.sell_env_at(from, token, amount_tokens, 0, U256::from(gas_price));
+            let mut db = db.clone();
+            let mut evm = Evm::builder()
+                .with_db(&mut db)
+                .with_env_with_handler_cfg(env)
+                .build();
+            match evm.transact_commit() {
+                Ok(result) => Some(matches!(Self::sell_outcome(result), SimSellResult::Success(_))),
+                Err(e) => {
+                    warn!("⚠️ Sell at {} wei gas price failed to execute: {:?}", gas_price, e);
+                    None
+                }
+            }
+        };
+        Some((sells_at(GAS_GATE_LOW_PRICE_WEI)?, sells_at(GAS_GATE_HIGH_PRICE_WEI)?))
+    }
+
+    /// Build the EVM env for a Token → native sell via the primary router
+    fn sell_env(
+        &self,
+        from: Address,
+        token: Address,
+        amount_tokens: U256,
+        blocks_ahead: u64,
+    ) -> EnvWithHandlerCfg {
+        self.sell_env_at(from, token, amount_tokens, blocks_ahead, U256::from(SIM_GAS_PRICE_WEI))
+    }
+
+    /// Sell env at `gas_price`, with the block basefee pinned to it
+    fn sell_env_at(
+        &self,
+        from: Address,
+        token: Address,
+        amount_tokens: U256,
+        blocks_ahead: u64,
+        gas_price: U256,
+    ) -> EnvWithHandlerCfg {
+        let calldata = self.sell_calldata(token, amount_tokens, from);
 
         let tx_env = TxEnv {
             caller: from,
             gas_limit: 500_000,
-            gas_price: U256::from(20_000_000_000u64),
+            gas_price,
             transact_to: TxKind::Call(self.primary_router()),
             value: U256::ZERO,
-            data: Bytes::from(calldata),
+            data: calldata,
             nonce: Some(2),
             chain_id: Some(self.chain_id),
             ..Default::default()
         };
 
         let block_env = BlockEnv {
-            number: U256::from(19_000_000u64),
-            timestamp: U256::from(
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .map(|d| d.as_secs())
-                    .unwrap_or(0),
-            ),
-            gas_limit: U256::from(30_000_000u64),
-            basefee: U256::from(20_000_000_000u64),
-            ..Default::default()
+            basefee: gas_price,
+            ..self.sim_block_env(blocks_ahead)
         };
 
-        let cfg = CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::CANCUN);
-        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, tx_env);
-
-        let mut evm = Evm::builder()
-            .with_db(db)
-            .with_env_with_handler_cfg(env)
-            .build();
+        let cfg = self.cfg_env();
+        EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, tx_env)
+    }
 
-        let result = evm.transact_commit();
+    /// Simulation block, `blocks_ahead` blocks (and block times) past the base block
+    fn sim_block_env(&self, blocks_ahead: u64) -> BlockEnv {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        BlockEnv {
+            number: U256::from(self.target_block.unwrap_or(DEFAULT_SIM_BLOCK) + blocks_ahead),
+            timestamp: U256::from(now + blocks_ahead * SIM_BLOCK_TIME_SECS),
+            gas_limit: U256::from(30_000_000u64),
+            basefee: U256::from(SIM_GAS_PRICE_WEI),
+            ..Default::default()
+        }
+    }
 
+    /// Map a sell execution result to success / revert
+    fn sell_outcome(result: ExecutionResult) -> SimSellResult {
         match result {
-            Ok(ExecutionResult::Success { output, .. }) => match output {
+            ExecutionResult::Success { output, .. } => match output {
                 Output::Call(bytes) => {
                     if bytes.len() >= 64 {
                         let eth = U256::from_be_slice(&bytes[bytes.len() - 32..]);
-                        Ok(SimSellResult::Success(eth))
+                        SimSellResult::Success(eth)
                     } else {
-                        Ok(SimSellResult::Success(U256::ZERO))
+                        SimSellResult::Success(U256::ZERO)
                     }
                 }
-                Output::Create(_, _) => Ok(SimSellResult::Success(U256::ZERO)),
+                Output::Create(_, _) => SimSellResult::Success(U256::ZERO),
             },
-            Ok(ExecutionResult::Revert { output, .. }) => {
+            ExecutionResult::Revert { output, .. } => {
                 // ⛔ REVERT DETECTED - This is a HONEYPOT!
-                let reason = Self::decode_revert_reason(&output);
-                Ok(SimSellResult::Reverted(reason))
+                SimSellResult::Reverted(Self::decode_revert_reason(&output))
             }
-            Ok(ExecutionResult::Halt { reason, .. }) => {
-                Ok(SimSellResult::Reverted(format!("Halted: {:?}", reason)))
+            ExecutionResult::Halt { reason, .. } => {
+                SimSellResult::Reverted(format!("Halted: {:?}", reason))
             }
-            Err(e) => Err(eyre!("EVM error: {:?}", e)),
         }
     }
 
-    /// Decode revert reason from output bytes
+    /// Decode revert reason from output bytes (custom errors keep their selector)
     fn decode_revert_reason(output: &Bytes) -> String {
-        // Try to decode Error(string) selector: 0x08c379a0
-        if output.len() >= 68 && output[0..4] == [0x08, 0xc3, 0x79, 0xa0] {
-            // Skip selector (4) + offset (32) + length position
-            let len_start = 36;
-            if output.len() > len_start + 32 {
-                let len = U256::from_be_slice(&output[len_start..len_start + 32]);
-                let len_usize: usize = len.try_into().unwrap_or(0);
-                let str_start = len_start + 32;
-                if output.len() >= str_start + len_usize {
-                    if let Ok(s) =
-                        String::from_utf8(output[str_start..str_start + len_usize].to_vec())
-                    {
-                        return s;
-                    }
-                }
-            }
-        }
-
-        // Common revert reasons in honeypots
-        let hex_output = hex::encode(output.as_ref());
-        if hex_output.contains("626f74") {
-            // "bot"
-            return "Bot detected / Blacklisted".to_string();
-        }
-        if hex_output.contains("74726164696e67") {
-            // "trading"
-            return "Trading not enabled".to_string();
+        let revert = decode_revert(output);
+        match revert.selector {
+            Some(selector) if revert.custom_error => format!("{} [{}]", revert.message, selector),
+            _ => revert.message,
         }
-        if hex_output.contains("7472616e73666572") {
-            // "transfer"
-            return "Transfer blocked".to_string();
-        }
-
-        format!("Revert: 0x{}", hex::encode(&output[..output.len().min(64)]))
     }
 
     /// Execute a transaction in the EVM
-    fn execute_tx(
+    fn execute_tx<ExtDB>(
         &self,
-        db: &mut CacheDB<EmptyDB>,
+        db: &mut CacheDB<ExtDB>,
+        from: Address,
+        to: Address,
+        value: U256,
+        data: Bytes,
+        nonce: u64,
+    ) -> Result<Vec<u8>>
+    where
+        ExtDB: DatabaseRef,
+        ExtDB::Error: std::fmt::Debug,
+    {
+        self.execute_tx_metered(db, from, to, value, data, nonce).map(|(output, _)| output)
+    }
+
+    /// `execute_tx` that also returns the gas the transaction used
+    fn execute_tx_metered<ExtDB>(
+        &self,
+        db: &mut CacheDB<ExtDB>,
         from: Address,
         to: Address,
         value: U256,
         data: Bytes,
         nonce: u64,
-    ) -> Result<Vec<u8>> {
+    ) -> Result<(Vec<u8>, u64)>
+    where
+        ExtDB: DatabaseRef,
+        ExtDB::Error: std::fmt::Debug,
+    {
         let tx_env = TxEnv {
             caller: from,
             gas_limit: 500_000,
-            gas_price: U256::from(20_000_000_000u64),
+            gas_price: U256::from(SIM_GAS_PRICE_WEI),
             transact_to: TxKind::Call(to),
             value,
             data,
@@ -1192,20 +4495,9 @@ impl HoneypotDetector {
             ..Default::default()
         };
 
-        let block_env = BlockEnv {
-            number: U256::from(19_000_000u64),
-            timestamp: U256::from(
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .map(|d| d.as_secs())
-                    .unwrap_or(0),
-            ),
-            gas_limit: U256::from(30_000_000u64),
-            basefee: U256::from(20_000_000_000u64),
-            ..Default::default()
-        };
+        let block_env = self.sim_block_env(0);
 
-        let cfg = CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::CANCUN);
+        let cfg = self.cfg_env();
         let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, tx_env);
 
         let mut evm = Evm::builder()
@@ -1216,13 +4508,11 @@ impl HoneypotDetector {
         let result = evm.transact_commit();
 
         match result {
-            Ok(ExecutionResult::Success { output, .. }) => match output {
-                Output::Call(bytes) => Ok(bytes.to_vec()),
-                Output::Create(bytes, _) => Ok(bytes.to_vec()),
+            Ok(ExecutionResult::Success { output, gas_used, .. }) => match output {
+                Output::Call(bytes) => Ok((bytes.to_vec(), gas_used)),
+                Output::Create(bytes, _) => Ok((bytes.to_vec(), gas_used)),
             },
-            Ok(ExecutionResult::Revert { output, .. }) => {
-                Err(eyre!("Reverted: 0x{}", hex::encode(&output)))
-            }
+            Ok(ExecutionResult::Revert { output, .. }) => Err(decode_revert(&output).into()),
             Ok(ExecutionResult::Halt { reason, .. }) => Err(eyre!("Halted: {:?}", reason)),
             Err(e) => Err(eyre!("EVM error: {:?}", e)),
         }
@@ -1271,6 +4561,22 @@ impl HoneypotDetector {
 /// Quick honeypot check without full state
 /// Uses heuristics and minimal simulation
 #[allow(dead_code)]
+/// Pick the token to analyze from a pair: the side that is not a base token
+/// (wrapped native / stablecoin). Returns (analyzed, base); None when neither
+/// side is a base token. Two base tokens: token0 is analyzed.
+pub fn select_pair_target(
+    token0: Address,
+    token1: Address,
+    is_base: impl Fn(&Address) -> bool,
+) -> Option<(Address, Address)> {
+    match (is_base(&token0), is_base(&token1)) {
+        (false, true) => Some((token0, token1)),
+        (true, false) => Some((token1, token0)),
+        (true, true) => Some((token0, token1)),
+        (false, false) => None,
+    }
+}
+
 pub fn quick_honeypot_check(token: Address, test_eth: U256) -> HoneypotResult {
     let start = Instant::now();
     let detector = HoneypotDetector::mainnet();
@@ -1292,6 +4598,11 @@ pub fn quick_honeypot_check(token: Address, test_eth: U256) -> HoneypotResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::types::{
+        swapExactAVAXForTokensCall, swapExactETHForTokensCall, swapExactTokensForAVAXCall,
+        swapExactTokensForETHCall,
+    };
+    use crate::utils::constants::CHAIN_ID_AVALANCHE;
 
     #[test]
     fn test_honeypot_result_safe() {
@@ -1318,6 +4629,720 @@ mod tests {
         assert!(result.summary().contains("HONEYPOT"));
     }
 
+    #[test]
+    fn test_honeypot_result_too_illiquid() {
+        let result = HoneypotResult::too_illiquid(50.0, 1000.0, 5);
+        assert!(!result.is_honeypot);
+        assert!(!result.buy_success && !result.sell_success);
+        assert!(result.reason.contains("TOO ILLIQUID"));
+    }
+
+    #[test]
+    fn test_confidence_gate() {
+        let safe = HoneypotResult::safe(1.0, 1.0, 0, vec![], 10);
+        assert!(safe.is_verified(DEFAULT_MIN_CONFIDENCE));
+
+        // Reverse quote failure alone is not enough to call it a honeypot
+        let quote_failed = HoneypotResult::honeypot("Cannot sell".to_string(), true, false, true, 0, vec![], 10)
+            .with_confidence(CONFIDENCE_QUOTE_FAILED);
+        assert!(!quote_failed.is_verified(DEFAULT_MIN_CONFIDENCE));
+        assert!(quote_failed.is_verified(CONFIDENCE_QUOTE_FAILED));
+
+        assert!(!safe.clone().with_confidence(CONFIDENCE_MOCKED).is_verified(DEFAULT_MIN_CONFIDENCE));
+        assert!(!HoneypotResult::too_illiquid(50.0, 1000.0, 5).is_verified(DEFAULT_MIN_CONFIDENCE));
+        assert!(HoneypotResult::too_illiquid(50.0, 1000.0, 5).is_verified(0));
+    }
+
+    #[test]
+    fn test_default_funding_constants() {
+        let detector = HoneypotDetector::mainnet();
+        assert_eq!(detector.test_account_balance, U256::from(100_000_000_000_000_000_000u128));
+        assert_eq!(detector.weth_liquidity, U256::from(1_000_000_000_000_000_000_000u128));
+        let detector = detector.with_test_balance(U256::from(1u64));
+        assert_eq!(detector.test_account_balance, U256::from(1u64));
+    }
+
+    #[test]
+    fn test_selfdestruct_scan() {
+        let mut risk_factors = Vec::new();
+
+        // PUSH1 0x00, SELFDESTRUCT
+        let destructible = Bytes::from(vec![0x60, 0x00, 0xff]);
+        assert_eq!(
+            HoneypotDetector::scan_selfdestruct(&destructible, &mut risk_factors),
+            SELFDESTRUCT_PENALTY
+        );
+        assert!(risk_factors[0].contains("SELFDESTRUCT"));
+
+        // 0xff only inside PUSH2 data - not an opcode
+        let mut risk_factors = Vec::new();
+        let push_data = Bytes::from(vec![0x61, 0xff, 0xff, 0x00]);
+        assert_eq!(HoneypotDetector::scan_selfdestruct(&push_data, &mut risk_factors), 0);
+
+        // 0xff only inside trailing CBOR metadata - ignored
+        let with_metadata = Bytes::from(vec![0x00, 0xfe, 0xa1, 0xff, 0x00, 0x02]);
+        assert_eq!(HoneypotDetector::scan_selfdestruct(&with_metadata, &mut risk_factors), 0);
+        assert!(risk_factors.is_empty());
+    }
+
+    #[test]
+    fn test_trace_sell_mock() {
+        let detector = HoneypotDetector::mainnet();
+        let token = Address::repeat_byte(0x42);
+        let trace = detector
+            .trace_sell(token, U256::from(100_000_000_000_000_000u64), None, None)
+            .unwrap();
+
+        // Mock router always returns - sell succeeds, steps recorded
+        assert!(!trace.reverted);
+        assert!(trace.total_steps > 0);
+        assert!(trace.revert_site.is_none());
+        assert_eq!(trace.steps.last().unwrap().opcode, "RETURN");
+    }
+
+    #[test]
+    fn test_parse_amounts_out_classifies_empty() {
+        // Bare 0x = clean revert
+        let err = HoneypotDetector::parse_amounts_out("0x").unwrap_err();
+        assert!(err.downcast_ref::<EmptyCallResult>().is_some());
+
+        // Truncated data = generic failure, not a clean revert
+        let err = HoneypotDetector::parse_amounts_out("0x1234").unwrap_err();
+        assert!(err.downcast_ref::<EmptyCallResult>().is_none());
+
+        // uint256[2] = [1, 42]
+        let encoded = format!(
+            "0x{:064x}{:064x}{:064x}{:064x}",
+            0x20, 2, 1, 42
+        );
+        assert_eq!(HoneypotDetector::parse_amounts_out(&encoded).unwrap(), U256::from(42u64));
+    }
+
+    #[test]
+    fn test_approve_classification() {
+        let ok = format!("0x{:064x}", 1);
+        let denied = format!("0x{:064x}", 0);
+        assert!(matches!(HoneypotDetector::classify_approve_result(&ok), ApproveCheck::Allowed));
+        assert!(matches!(HoneypotDetector::classify_approve_result("0x"), ApproveCheck::Allowed));
+        assert!(matches!(HoneypotDetector::classify_approve_result(&denied), ApproveCheck::Blocked(_)));
+
+        let revert = serde_json::json!({"code": 3, "message": "execution reverted: blocked"});
+        assert!(CallReverted::from_rpc_error(&revert).is_some());
+        let limited = serde_json::json!({"code": -32005, "message": "limit exceeded"});
+        assert!(CallReverted::from_rpc_error(&limited).is_none());
+    }
+
+    #[test]
+    fn test_sim_block_env_advances() {
+        let detector = HoneypotDetector::mainnet().at_historical_block(100);
+        let same = detector.sim_block_env(0);
+        let later = detector.sim_block_env(DEFAULT_SELL_DELAY_BLOCKS);
+
+        assert_eq!(same.number, U256::from(100u64));
+        assert_eq!(later.number, U256::from(100 + DEFAULT_SELL_DELAY_BLOCKS));
+        assert!(later.timestamp >= same.timestamp + U256::from(DEFAULT_SELL_DELAY_BLOCKS * SIM_BLOCK_TIME_SECS));
+    }
+
+    #[test]
+    fn test_search_sell_lock() {
+        // Lock of 137 blocks: shortest working offset found, a handful of sells tried
+        let mut tried = Vec::new();
+        let found = search_sell_lock(DEFAULT_SELL_DELAY_BLOCKS, DEFAULT_SELL_LOCK_MAX_BLOCKS, |blocks| {
+            tried.push(blocks);
+            (blocks >= 137).then_some(blocks * 10)
+        });
+        assert_eq!(found, Some((137, 1370)));
+        assert_eq!(&tried[..2], &[DEFAULT_SELL_DELAY_BLOCKS, DEFAULT_SELL_LOCK_MAX_BLOCKS]);
+        assert!(tried.len() <= 16);
+
+        // Anti-bot delay of one block
+        assert_eq!(search_sell_lock(2, 7_200, |blocks| (blocks >= 1).then_some(())), Some((1, ())));
+        // Locked past the search window: a hard honeypot
+        assert_eq!(search_sell_lock(2, 7_200, |blocks| (blocks > 7_200).then_some(())), None);
+        // Search disabled: only the anti-bot retry
+        assert_eq!(search_sell_lock(2, 0, |blocks| (blocks >= 137).then_some(())), None);
+        assert_eq!(search_sell_lock(0, 0, |_| Some(())), None);
+    }
+
+    #[test]
+    fn test_forwarder_bubbles_result() {
+        let detector = HoneypotDetector::mainnet();
+        let mut db = CacheDB::new(EmptyDB::default());
+        let sender = Address::repeat_byte(0x01);
+        let forwarder = Address::repeat_byte(0x02);
+        let returns_42 = Address::repeat_byte(0x03);
+        let reverts = Address::repeat_byte(0x04);
+        let account = |balance: u64, code: &[u8]| AccountInfo {
+            balance: U256::from(balance) * U256::from(10u64).pow(U256::from(18u64)),
+            nonce: 0,
+            code_hash: KECCAK_EMPTY,
+            code: (!code.is_empty()).then(|| Bytecode::new_raw(Bytes::copy_from_slice(code))),
+        };
+        db.insert_account_info(sender, account(1, &[]));
+        db.insert_account_info(forwarder, account(0, &FORWARDER_RUNTIME));
+        // mstore(0, 42); return(0, 32)
+        db.insert_account_info(returns_42, account(0, &[0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]));
+        // revert(0, 0)
+        db.insert_account_info(reverts, account(0, &[0x60, 0x00, 0x60, 0x00, 0xfd]));
+
+        let out = detector
+            .execute_tx(&mut db, sender, forwarder, U256::ZERO, Bytes::from(returns_42.to_vec()), 0)
+            .unwrap();
+        assert_eq!(U256::from_be_slice(&out), U256::from(42u64));
+        assert!(detector
+            .execute_tx(&mut db, sender, forwarder, U256::ZERO, Bytes::from(reverts.to_vec()), 1)
+            .is_err());
+        // Plain ETH transfer (no target) is accepted
+        assert!(detector.execute_tx(&mut db, sender, forwarder, U256::from(1u64), Bytes::new(), 2).is_ok());
+    }
+
+    #[test]
+    fn test_decode_revert() {
+        let with_selector = |signature: &str, args: &[u8]| {
+            [&keccak256(signature.as_bytes())[..4], args].concat()
+        };
+
+        // require(false, "TRANSFER_FAILED")
+        let mut error_string = with_selector("Error(string)", &B256::from(U256::from(32u64)).0);
+        error_string.extend_from_slice(&B256::from(U256::from(15u64)).0);
+        error_string.extend_from_slice(&[b"TRANSFER_FAILED".as_slice(), &[0u8; 17][..]].concat());
+        let revert = decode_revert(&error_string);
+        assert_eq!(revert.selector.as_deref(), Some("0x08c379a0"));
+        assert_eq!(revert.message, "TRANSFER_FAILED");
+        assert!(!revert.custom_error);
+
+        let panic = decode_revert(&with_selector("Panic(uint256)", &B256::from(U256::from(0x11u64)).0));
+        assert_eq!(panic.message, "Panic 0x11: arithmetic overflow/underflow");
+
+        // Known custom error: named, selector kept
+        let trading = decode_revert(&with_selector("TradingNotOpen()", &[]));
+        assert_eq!(trading.selector.as_deref(), Some("0xe09f0331"));
+        assert_eq!(trading.message, "TradingNotOpen()");
+        assert!(trading.custom_error);
+        assert_eq!(trading.to_string(), "Reverted [0xe09f0331]: TradingNotOpen()");
+        let report: eyre::Report = trading.clone().into();
+        assert_eq!(report.downcast_ref::<RevertData>(), Some(&trading));
+
+        // Unknown custom error: selector and leading args only
+        let unknown = decode_revert(&[0xde, 0xad, 0xbe, 0xef, 0x01]);
+        assert_eq!(unknown.selector.as_deref(), Some("0xdeadbeef"));
+        assert_eq!(unknown.message, "custom error (0x01)");
+        assert_eq!(decode_revert(&[]).selector, None);
+    }
+
+    #[test]
+    fn test_sell_buy_gas_ratio() {
+        assert_eq!(sell_buy_gas_ratio(100_000, 120_000), Some(1.2));
+        assert!(sell_buy_gas_ratio(100_000, 500_000).unwrap() >= ASYMMETRIC_GAS_RATIO);
+        assert!(sell_buy_gas_ratio(100_000, 499_000).unwrap() < ASYMMETRIC_GAS_RATIO);
+        // A leg that never executed has no ratio
+        assert_eq!(sell_buy_gas_ratio(0, 120_000), None);
+        assert_eq!(sell_buy_gas_ratio(100_000, 0), None);
+    }
+
+    #[test]
+    fn test_v2_sell_impact() {
+        let reserve = U256::from(1_000_000u64) * U256::from(10u64).pow(U256::from(18u64));
+        // Dust: only the 0.3% LP fee
+        let dust = v2_sell_impact_percent(U256::from(10u64).pow(U256::from(15u64)), reserve, reserve).unwrap();
+        assert!((dust - 0.3).abs() < 0.01);
+        // 10% of the pool's token side: ~9.4% impact
+        let tenth = v2_sell_impact_percent(reserve / U256::from(10u64), reserve, reserve).unwrap();
+        assert!(tenth > 9.0 && tenth < 10.0);
+        // Larger sells always move the price further
+        let quarter = v2_sell_impact_percent(reserve / U256::from(4u64), reserve, reserve).unwrap();
+        assert!(quarter > tenth);
+        assert_eq!(v2_sell_impact_percent(U256::from(1u64), U256::ZERO, reserve), None);
+    }
+
+    #[test]
+    fn test_pair_transfer_blocked() {
+        let detector = HoneypotDetector::mainnet();
+        let mut db = CacheDB::new(EmptyDB::default());
+        let holder = Address::repeat_byte(0x01);
+        let token = Address::repeat_byte(0x02);
+        let pair = Address::repeat_byte(0x03);
+
+        // if calldataload(4) == pair { revert(0, 0) } mstore(0, 1); return(0, 32)
+        let mut code = vec![0x60, 0x04, 0x35, 0x73];
+        code.extend_from_slice(pair.as_slice());
+        code.extend_from_slice(&[
+            0x14, 0x60, 0x26, 0x57, 0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3, 0x5b, 0x60, 0x00,
+            0x80, 0xfd,
+        ]);
+        db.insert_account_info(
+            holder,
+            AccountInfo {
+                balance: U256::from(10u64).pow(U256::from(18u64)),
+                nonce: 1,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+            },
+        );
+        db.insert_account_info(
+            token,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(Bytes::from(code))),
+            },
+        );
+
+        let held = U256::from(1_000u64);
+        assert!(detector.pair_transfer_blocked(&db, holder, token, pair, held));
+        // Any other pair address transfers fine
+        assert!(!detector.pair_transfer_blocked(&db, holder, token, Address::repeat_byte(0x04), held));
+    }
+
+    #[test]
+    fn test_contract_recipient_blocked() {
+        let detector = HoneypotDetector::mainnet().with_caller_seed(7).with_contract_recipient_check(true);
+        assert!(detector.contract_recipient_check);
+        let mut db = CacheDB::new(EmptyDB::default());
+        let holder = Address::repeat_byte(0x01);
+        let gated = Address::repeat_byte(0x02);
+        let open = Address::repeat_byte(0x03);
+        db.insert_account_info(
+            holder,
+            AccountInfo {
+                balance: U256::from(10u64).pow(U256::from(18u64)),
+                nonce: 1,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+            },
+        );
+        let set_code = |db: &mut CacheDB<EmptyDB>, address: Address, code: Vec<u8>| {
+            db.insert_account_info(
+                address,
+                AccountInfo { balance: U256::ZERO, nonce: 0, code_hash: KECCAK_EMPTY, code: Some(Bytecode::new_raw(Bytes::from(code))) },
+            )
+        };
+        // if extcodesize(calldataload(4)) != 0 { revert(0, 0) } mstore(0, 1); return(0, 32)
+        set_code(
+            &mut db,
+            gated,
+            vec![
+                0x60, 0x04, 0x35, 0x3b, 0x60, 0x11, 0x57, 0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+                0x5b, 0x60, 0x00, 0x80, 0xfd,
+            ],
+        );
+        // mstore(0, 1); return(0, 32)
+        set_code(&mut db, open, vec![0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+
+        let held = U256::from(1_000u64);
+        assert!(detector.contract_recipient_blocked(&db, holder, gated, held));
+        assert!(!detector.contract_recipient_blocked(&db, holder, open, held));
+    }
+
+    #[test]
+    fn test_sim_detail_from_quotes() {
+        let detail = SimDetail::from_quotes(U256::from(1_000u64), U256::from(90u64));
+        assert_eq!(detail.tokens_bought, U256::from(1_000u64));
+        assert_eq!(detail.eth_returned, U256::from(90u64));
+        assert_eq!((detail.final_token_balance, detail.final_eth_balance), (None, None));
+    }
+
+    #[test]
+    fn test_permit_mismatch_factor() {
+        let sell = |status, same_block_revert: Option<&str>| SellOutcome {
+            status,
+            dynamic_blacklist_detected: false,
+            same_block_revert: same_block_revert.map(str::to_string),
+            sell_lock_blocks: same_block_revert.map(|_| 2),
+            gas_used: 0,
+            balance_delta: None,
+            final_token_balance: None,
+            final_native_balance: None,
+        };
+        let sold = || SellStatus::Sold { native_received: U256::from(1u64), mocked: false };
+        let in_buy_block = sell(sold(), None);
+        let delayed = sell(sold(), Some("too early"));
+        let reverted = sell(SellStatus::Reverted("blocked".to_string()), None);
+        let blocked = PermitCheck::SellBlocked("permit spender".to_string());
+
+        // Sellable: agrees with any approve() sell, mismatches only when approve() never sold
+        assert_eq!(PermitCheck::Sellable.mismatch_factor(&in_buy_block), None);
+        assert_eq!(PermitCheck::Sellable.mismatch_factor(&delayed), None);
+        assert_eq!(
+            PermitCheck::Sellable.mismatch_factor(&reverted).as_deref(),
+            Some("APPROVAL PATH GATED: sell after approve() reverts, permit() sells")
+        );
+
+        // SellBlocked: only contrasts with an approve() sell in the buy block
+        let factor = blocked.mismatch_factor(&in_buy_block).unwrap();
+        assert!(factor.contains("approve() sells") && factor.contains("permit spender"));
+        assert_eq!(blocked.mismatch_factor(&delayed), None); // anti-bot delay, not the approval
+        assert_eq!(blocked.mismatch_factor(&reverted), None);
+
+        // No verdict from the permit run
+        assert_eq!(PermitCheck::Unsupported.mismatch_factor(&reverted), None);
+        assert_eq!(PermitCheck::Rejected("x".to_string()).mismatch_factor(&in_buy_block), None);
+    }
+
+    #[test]
+    fn test_permit_round_trip_unsupported() {
+        let detector = HoneypotDetector::mainnet().with_permit_check(true);
+        let mut db = CacheDB::new(EmptyDB::default());
+        let holder = Address::repeat_byte(0x01);
+        let held = U256::from(1_000u64);
+
+        // No code at all: DOMAIN_SEPARATOR() returns nothing
+        let no_code = Address::repeat_byte(0x02);
+        assert_eq!(detector.permit_round_trip(&mut db, holder, no_code, held), PermitCheck::Unsupported);
+
+        // Plain ERC20 without EIP-2612: DOMAIN_SEPARATOR() reverts
+        let reverting = Address::repeat_byte(0x03);
+        db.insert_account_info(
+            reverting,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(Bytes::from(vec![0x60, 0x00, 0x80, 0xfd]))),
+            },
+        );
+        assert_eq!(detector.permit_round_trip(&mut db, holder, reverting, held), PermitCheck::Unsupported);
+    }
+
+    #[test]
+    fn test_find_balance_slot() {
+        let detector = HoneypotDetector::mainnet().with_direct_holder_check(true);
+        let mut db = CacheDB::new(EmptyDB::default());
+        let holder = Address::repeat_byte(0x01);
+
+        // balanceOf(a) = sload(keccak(a ‖ 3)), selector ignored
+        let token = Address::repeat_byte(0x04);
+        let balance_of = vec![
+            0x60, 0x04, 0x35, 0x60, 0x00, 0x52, // mstore(0, calldataload(4))
+            0x60, 0x03, 0x60, 0x20, 0x52, // mstore(32, 3)
+            0x60, 0x40, 0x60, 0x00, 0x20, 0x54, // sload(keccak256(0, 64))
+            0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3, // return word
+        ];
+        db.insert_account_info(
+            token,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(Bytes::from(balance_of))),
+            },
+        );
+        let expected = keccak256(
+            [B256::left_padding_from(holder.as_slice()).as_slice(), B256::from(U256::from(3u64)).as_slice()].concat(),
+        );
+        let slot = U256::from_be_bytes(expected.0);
+        assert_eq!(detector.find_balance_slot(&mut db, token, holder), Some(slot));
+        // Probing leaves the state as it found it
+        assert_eq!(db.storage_ref(token, slot).unwrap(), U256::ZERO);
+
+        // No balanceOf at all - no slot, no verdict
+        let no_code = Address::repeat_byte(0x05);
+        assert_eq!(detector.find_balance_slot(&mut db, no_code, holder), None);
+        assert!(matches!(
+            detector.direct_holder_round_trip(&db, no_code, U256::from(1_000u64)),
+            DirectHolderCheck::Unknown(_)
+        ));
+        assert!(detector.wallet_transfer(&db, no_code, U256::from(1_000u64)).is_err());
+
+        // The balanceOf-only token answers transfer(to, ..) with balanceOf(to) = 0,
+        // an explicit `false`: credited, but not transferable
+        let probe = detector.wallet_transfer(&db, token, U256::from(1_000u64)).unwrap();
+        assert!(!probe.transferable);
+        assert_eq!(probe.received_tokens, None);
+        assert_eq!(probe.revert_reason.as_deref(), Some("transfer returned false"));
+    }
+
+    #[test]
+    fn test_wallet_transfer_succeeds() {
+        let detector = HoneypotDetector::mainnet().with_caller_seed(5);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // Balances at keccak(a ‖ 3): transfer(to, amount) moves them from the caller and
+        // returns true, any other selector is balanceOf(a)
+        let token = Address::repeat_byte(0x06);
+        let code = vec![
+            0x60, 0x00, 0x35, 0x60, 0xe0, 0x1c, 0x63, 0xa9, 0x05, 0x9c, 0xbb, 0x14, 0x60, 0x28, 0x57, // selector == transfer
+            0x60, 0x04, 0x35, 0x60, 0x00, 0x52, 0x60, 0x03, 0x60, 0x20, 0x52, // balanceOf: mstore(0, a); mstore(32, 3)
+            0x60, 0x40, 0x60, 0x00, 0x20, 0x54, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3, // return sload(keccak)
+            0x5b, 0x33, 0x60, 0x00, 0x52, 0x60, 0x03, 0x60, 0x20, 0x52, // transfer: mstore(0, caller); mstore(32, 3)
+            0x60, 0x40, 0x60, 0x00, 0x20, 0x80, 0x54, 0x60, 0x24, 0x35, 0x90, 0x03, 0x90, 0x55, // sender -= amount
+            0x60, 0x04, 0x35, 0x60, 0x00, 0x52, // mstore(0, to)
+            0x60, 0x40, 0x60, 0x00, 0x20, 0x80, 0x54, 0x60, 0x24, 0x35, 0x01, 0x90, 0x55, // recipient += amount
+            0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3, // return true
+        ];
+        db.insert_account_info(
+            token,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(Bytes::from(code))),
+            },
+        );
+
+        let probe = detector.wallet_transfer(&db, token, U256::from(1_000u64)).unwrap();
+        assert!(probe.transferable);
+        assert_eq!(probe.amount_tokens, U256::from(1_000u64));
+        assert_eq!(probe.received_tokens, Some(U256::from(1_000u64)));
+        assert_eq!(probe.revert_reason, None);
+    }
+
+    #[test]
+    fn test_exit_curve_simulates_sells() {
+        let detector = HoneypotDetector::mainnet().with_caller_seed(3);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // balanceOf(a) = sload(keccak(a ‖ 3)), selector ignored - totalSupply() reads balanceOf(0)
+        let balance_of = Bytes::from(vec![
+            0x60, 0x04, 0x35, 0x60, 0x00, 0x52, 0x60, 0x03, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00,
+            0x20, 0x54, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+        ]);
+        let token = Address::repeat_byte(0x04);
+        let pair = Address::repeat_byte(0x05);
+        let set_code = |db: &mut CacheDB<EmptyDB>, address: Address, code: Bytes| {
+            db.insert_account_info(
+                address,
+                AccountInfo { balance: U256::ZERO, nonce: 0, code_hash: KECCAK_EMPTY, code: Some(Bytecode::new_raw(code)) },
+            );
+        };
+        let set_balance = |db: &mut CacheDB<EmptyDB>, contract: Address, holder: Address, amount: u128| {
+            let key = keccak256(
+                [B256::left_padding_from(holder.as_slice()).as_slice(), B256::from(U256::from(3u64)).as_slice()].concat(),
+            );
+            db.insert_account_storage(contract, U256::from_be_bytes(key.0), U256::from(amount)).unwrap();
+        };
+        set_code(&mut db, token, balance_of.clone());
+        set_code(&mut db, detector.weth(), balance_of);
+        // 1M supply: the 1% holding is 10k tokens; spot 0.001 WETH per token
+        set_balance(&mut db, token, Address::ZERO, 1_000_000 * 10u128.pow(18));
+        set_balance(&mut db, token, pair, 100_000 * 10u128.pow(18));
+        set_balance(&mut db, detector.weth(), pair, 100 * 10u128.pow(18));
+
+        // Router paying a flat 0.095 ETH per sell (returns [0x40, 0.095e18]): the 100-token exit
+        // (0.1 ETH spot) loses 5%, the bigger exits lose more
+        let flat_payout = Bytes::from(vec![
+            0x60, 0x40, 0x60, 0x00, 0x52, // mstore(0, 0x40)
+            0x67, 0x01, 0x51, 0x81, 0xff, 0x25, 0xa9, 0x80, 0x00, 0x60, 0x20, 0x52, // mstore(32, 0.095e18)
+            0x60, 0x40, 0x60, 0x00, 0xf3, // return 64 bytes
+        ]);
+        set_code(&mut db, detector.primary_router(), flat_payout);
+        let curve = detector.exit_curve(&db, token, pair).unwrap();
+        let fractions: Vec<f64> = curve.iter().map(|p| p.fraction).collect();
+        assert_eq!(fractions, vec![0.01, 0.05, 0.1, 0.25]);
+        let impacts: Vec<f64> = curve.iter().map(|p| p.impact_pct).collect();
+        assert_eq!(impacts, vec![5.0, 81.0, 90.5, 96.2]);
+        assert!(curve.iter().all(|p| p.revert_reason.is_none()));
+
+        // A router that reverts every sell: no exit at any size
+        set_code(&mut db, detector.primary_router(), Bytes::from(vec![0x60, 0x00, 0x80, 0xfd]));
+        let curve = detector.exit_curve(&db, token, pair).unwrap();
+        assert_eq!(curve.len(), EXIT_CURVE_FRACTIONS_BPS.len());
+        assert!(curve.iter().all(|p| p.impact_pct == 100.0 && p.revert_reason.is_some()));
+
+        // No balanceOf slot to credit the holder from - inconclusive, not a curve
+        let no_code = Address::repeat_byte(0x06);
+        assert!(detector.exit_curve(&db, no_code, pair).is_err());
+    }
+
+    #[test]
+    fn test_offline_state_seeds_v2_pair() {
+        let detector = HoneypotDetector::mainnet().with_caller_seed(7);
+        // balanceOf(a) = sload(keccak(a ‖ 3)), selector ignored
+        let balance_of = Bytes::from(vec![
+            0x60, 0x04, 0x35, 0x60, 0x00, 0x52, 0x60, 0x03, 0x60, 0x20, 0x52, 0x60, 0x40, 0x60, 0x00,
+            0x20, 0x54, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+        ]);
+        let token = Address::repeat_byte(0x01);
+        let input = OfflineAnalysisInput {
+            token,
+            token_bytecode: balance_of.clone(),
+            router_bytecode: detector.mock_router_bytecode(),
+            weth_bytecode: balance_of,
+            pair: Address::repeat_byte(0x02),
+            pair_bytecode: Bytes::from(vec![0x00]),
+            token_reserve: U256::from(5_000_000u64),
+            weth_reserve: U256::from(7_000u64),
+            test_amount_eth: U256::from(100u64),
+        };
+
+        let (mut db, caller) = detector.offline_simulation_db(&input).unwrap();
+        assert_eq!(caller, detector.sim_address("caller"));
+        // token (0x01..) sorts below WETH: reserve0 = token reserve
+        let slot = |index: u64| db.storage_ref(input.pair, U256::from(index)).unwrap();
+        assert_eq!(slot(V2_PAIR_TOKEN0_SLOT), U256::from_be_slice(token.as_slice()));
+        assert_eq!(slot(V2_PAIR_RESERVES_SLOT), U256::from(5_000_000u64) | (U256::from(7_000u64) << 112));
+        assert_eq!(slot(V2_PAIR_UNLOCKED_SLOT), U256::from(1u64));
+        assert_eq!(detector.read_token_balance(&mut db, token, input.pair), Some(input.token_reserve));
+        assert_eq!(detector.read_token_balance(&mut db, detector.weth(), input.pair), Some(input.weth_reserve));
+
+        // Missing state is an error, never a mock
+        let empty_pool = OfflineAnalysisInput { weth_reserve: U256::ZERO, ..input.clone() };
+        assert!(detector.detect_offline(empty_pool).is_err());
+        let no_balance_of = OfflineAnalysisInput { token_bytecode: detector.mock_erc20_bytecode(), ..input };
+        let err = detector.detect_offline(no_balance_of).unwrap_err().to_string();
+        assert!(err.contains("token balanceOf storage slot not found"), "{}", err);
+    }
+
+    #[test]
+    fn test_static_only_pipeline_is_unverified() {
+        let detector = HoneypotDetector::mainnet().with_pipeline(AnalysisPipeline::static_only());
+        let token = Address::repeat_byte(0x42);
+        let result = detector
+            .detect(token, U256::from(100_000_000_000_000_000u64), None, None, None, None)
+            .unwrap();
+        assert!(!result.is_honeypot);
+        assert!(!result.buy_success && !result.sell_success);
+        assert_eq!(result.confidence, 0);
+        assert!(!result.is_verified(DEFAULT_MIN_CONFIDENCE));
+    }
+
+    #[test]
+    fn test_mock_simulation_warnings() {
+        let token = Address::repeat_byte(0x42);
+        let result = HoneypotDetector::mainnet()
+            .detect(token, U256::from(100_000_000_000_000_000u64), None, None, None, None)
+            .unwrap();
+        assert!(result.warnings.iter().any(|w| w == WARNING_MOCK_ROUTER));
+        assert!(result.warnings.iter().any(|w| w == WARNING_MOCK_TOKEN));
+
+        let quote_failed = HoneypotResult::honeypot("Cannot sell".to_string(), true, false, true, 0, vec![], 10)
+            .with_warning(WARNING_REVERSE_QUOTE_UNAVAILABLE)
+            .with_warning(WARNING_REVERSE_QUOTE_UNAVAILABLE);
+        assert_eq!(quote_failed.warnings, vec![WARNING_REVERSE_QUOTE_UNAVAILABLE.to_string()]);
+        assert!(HoneypotResult::safe(1.0, 1.0, 0, vec![], 10).warnings.is_empty());
+    }
+
+    #[test]
+    fn test_avalanche_router_abi() {
+        let token = Address::repeat_byte(0x03);
+        let to = Address::repeat_byte(0x04);
+
+        let avax = HoneypotDetector::for_chain(CHAIN_ID_AVALANCHE).unwrap();
+        assert_eq!(avax.native_symbol(), "AVAX");
+        assert_eq!(avax.buy_calldata(token, to)[..4], swapExactAVAXForTokensCall::SELECTOR);
+        assert_eq!(avax.sell_calldata(token, U256::from(1u64), to)[..4], swapExactTokensForAVAXCall::SELECTOR);
+
+        let base = HoneypotDetector::for_chain(8453).unwrap();
+        assert_eq!(base.native_symbol(), "ETH");
+        assert_eq!(base.buy_calldata(token, to)[..4], swapExactETHForTokensCall::SELECTOR);
+        assert_eq!(base.sell_calldata(token, U256::from(1u64), to)[..4], swapExactTokensForETHCall::SELECTOR);
+    }
+
+    #[test]
+    fn test_select_pair_target() {
+        let weth = Address::repeat_byte(0x01);
+        let usdc = Address::repeat_byte(0x02);
+        let meme = Address::repeat_byte(0x03);
+        let is_base = |a: &Address| *a == weth || *a == usdc;
+
+        assert_eq!(select_pair_target(weth, meme, is_base), Some((meme, weth)));
+        assert_eq!(select_pair_target(meme, usdc, is_base), Some((meme, usdc)));
+        assert_eq!(select_pair_target(weth, usdc, is_base), Some((weth, usdc)));
+        assert_eq!(select_pair_target(meme, Address::repeat_byte(0x04), is_base), None);
+    }
+
+    #[test]
+    fn test_via_dex_records_router() {
+        let dex = DexInfo { name: "Uniswap V2".to_string(), address: Address::repeat_byte(0x11) };
+        let result = HoneypotResult::safe(1.0, 1.0, 0, vec![], 10);
+        assert!(result.simulated_router.is_none());
+
+        let result = result.via_dex(&dex);
+        assert_eq!(result.simulated_router, Some(Address::repeat_byte(0x11)));
+        assert_eq!(result.simulated_dex.as_deref(), Some("Uniswap V2"));
+    }
+
+    #[test]
+    fn test_block_tag() {
+        let detector = HoneypotDetector::mainnet();
+        assert_eq!(detector.block_tag(), "latest");
+
+        let historical = detector.at_historical_block(18_000_000);
+        assert_eq!(historical.block_tag(), "0x112a880");
+        assert_eq!(historical.target_block, Some(18_000_000));
+    }
+
+    #[test]
+    fn test_decode_decimals() {
+        let word = |v: u64| U256::from(v).to_be_bytes::<32>().to_vec();
+        assert_eq!(HoneypotDetector::decode_decimals(&word(18)), Some(18));
+        assert_eq!(HoneypotDetector::decode_decimals(&word(0)), Some(0));
+        assert_eq!(HoneypotDetector::decode_decimals(&word(36)), Some(36));
+        assert_eq!(HoneypotDetector::decode_decimals(&word(37)), None);
+        // Garbage in the high bytes must not be truncated to a "valid" uint8
+        assert_eq!(HoneypotDetector::decode_decimals(&word(256 + 18)), None);
+        assert_eq!(HoneypotDetector::decode_decimals(&[]), None);
+
+        // The round trip's evidence is kept, only the verdict is capped
+        let result = HoneypotResult::honeypot("sell reverted".to_string(), true, false, true, 0, vec![], 5)
+            .with_unknown_decimals();
+        assert!(result.is_honeypot && result.sell_reverted);
+        assert!(!result.is_verified(DEFAULT_MIN_CONFIDENCE));
+        assert_eq!(result.warnings, vec![WARNING_UNKNOWN_DECIMALS.to_string()]);
+    }
+
+    #[test]
+    fn test_decode_string_malformed() {
+        let abi = |data: &[u8], len: u64| {
+            let mut out = U256::from(32u64).to_be_bytes::<32>().to_vec();
+            out.extend_from_slice(&U256::from(len).to_be_bytes::<32>());
+            out.extend_from_slice(data);
+            out.resize(64 + data.len().div_ceil(32) * 32, 0);
+            out
+        };
+        let decode = HoneypotDetector::decode_string;
+
+        assert_eq!(decode(&abi(b"USDT", 4)), Some("USDT".to_string()));
+        // Trailing garbage after the declared length
+        assert_eq!(decode(&abi(b"PEPE\xff\xfe junk", 4)), Some("PEPE".to_string()));
+        // Declared length past the returned data is clamped
+        assert_eq!(decode(&abi(b"SCAM", 1000)), Some("SCAM".to_string()));
+        // BOM, embedded nulls and control characters
+        assert_eq!(decode(&abi("\u{feff}MO\0ON\n".as_bytes(), 10)), Some("MOON".to_string()));
+        // Invalid UTF-8 is decoded lossily
+        assert_eq!(decode(&abi(b"DO\xc3GE", 5)), Some("DOGE".to_string()));
+        // Nothing printable left
+        assert_eq!(decode(&abi(b"\x01\x02\xff", 3)), Some(UNREADABLE_STRING.to_string()));
+
+        let mut mkr = b"MKR".to_vec();
+        mkr.resize(32, 0);
+        assert_eq!(decode(&mkr), Some("MKR".to_string()));
+        assert_eq!(decode(&[0u8; 32]), None);
+        assert_eq!(decode(&[]), None);
+    }
+
+    #[test]
+    fn test_fee_classification() {
+        // 5% each way: reflection
+        assert_eq!(FeeClass::classify(5.0, 5.5), Some(FeeClass::Reflection { fee_percent: 5.25 }));
+        // One-sided 20% sell tax: high tax, not reflection
+        assert_eq!(FeeClass::classify(0.0, 20.0), Some(FeeClass::HighTax { fee_percent: 20.0 }));
+        // Symmetric but above the reflection bound
+        assert_eq!(FeeClass::classify(20.0, 20.0), Some(FeeClass::HighTax { fee_percent: 20.0 }));
+        // Small asymmetric tax and fee-free tokens carry no label
+        assert_eq!(FeeClass::classify(0.0, 4.0), None);
+        assert_eq!(FeeClass::classify(0.1, 0.2), None);
+        // BSC-style cutoff: a 15% one-sided meme tax is not labelled high tax
+        assert_eq!(FeeClass::classify(0.0, 15.0), Some(FeeClass::HighTax { fee_percent: 15.0 }));
+        assert_eq!(FeeClass::classify_with(0.0, 15.0, 20.0), None);
+
+        // 10% buy + 10% sell retains 81%
+        assert!((implied_sell_fee(19.0, 10.0) - 10.0).abs() < 1e-9);
+        assert_eq!(implied_sell_fee(5.0, 100.0), 0.0);
+    }
+
+    #[test]
+    fn test_transfer_from_classification() {
+        let moved = format!("0x{:064x}", 1);
+        let refused = format!("0x{:064x}", 0);
+        assert!(matches!(HoneypotDetector::classify_transfer_from_result(&moved), AllowanceCheck::Bypassed));
+        assert!(matches!(HoneypotDetector::classify_transfer_from_result("0x"), AllowanceCheck::Bypassed));
+        assert!(matches!(HoneypotDetector::classify_transfer_from_result(&refused), AllowanceCheck::Enforced));
+        assert!(matches!(HoneypotDetector::classify_transfer_from_result("0xzz"), AllowanceCheck::Unknown(_)));
+
+        let safe = HoneypotResult::safe(1.0, 1.0, 0, vec![], 10);
+        assert!(safe.allowance_enforced);
+    }
+
     #[test]
     fn test_detector_creation() {
         let detector = HoneypotDetector::mainnet();
@@ -1332,18 +5357,166 @@ mod tests {
         assert_ne!(addr1, addr2);
     }
 
+    #[test]
+    fn test_caller_seed() {
+        let seeded = HoneypotDetector::mainnet().with_caller_seed(7);
+        assert_eq!(seeded.sim_address("caller"), HoneypotDetector::mainnet().with_caller_seed(7).sim_address("caller"));
+        assert_ne!(seeded.sim_address("caller"), HoneypotDetector::mainnet().with_caller_seed(8).sim_address("caller"));
+        // Roles never collide with the caller
+        assert_ne!(seeded.sim_address("caller"), seeded.sim_address("spender"));
+        // Unseeded stays random
+        let random = HoneypotDetector::mainnet();
+        assert_ne!(random.sim_address("caller"), random.sim_address("caller"));
+    }
+
+    #[test]
+    fn test_capped_risk_factors() {
+        let mut result = HoneypotResult::safe(0.0, 0.0, 0, vec![], 10);
+        result.risk_factors = vec![
+            "Tested on Uniswap V2".to_string(),
+            "HIGH TAX: buy 12.00% / sell 15.00%".to_string(),
+            "⛔ SELFDESTRUCT opcode present - contract can be destroyed, zeroing holders".to_string(),
+            "decimals() missing or out of range".to_string(),
+        ];
+
+        let capped = result.capped_risk_factors(2);
+        assert_eq!(capped.len(), 3);
+        assert!(capped[0].starts_with('⛔'));
+        assert!(capped[1].starts_with("HIGH TAX"));
+        assert_eq!(capped[2], "and 2 more");
+
+        // Under the cap (or unlimited) nothing changes
+        assert_eq!(result.capped_risk_factors(4), result.risk_factors);
+        assert_eq!(result.capped_risk_factors(0), result.risk_factors);
+    }
+
+    #[test]
+    fn test_token_standard_classification() {
+        let one = Some(U256::from(1u64));
+        assert_eq!(TokenStandard::classify(None, false, false), TokenStandard::Erc20);
+        assert_eq!(TokenStandard::classify(one, false, false), TokenStandard::Erc777);
+        assert_eq!(TokenStandard::classify(None, true, false), TokenStandard::Erc1363);
+        assert_eq!(TokenStandard::classify(one, true, true), TokenStandard::Rebasing);
+        // granularity() must be a sane positive unit, not any 32-byte answer
+        assert_eq!(TokenStandard::classify(Some(U256::ZERO), false, false), TokenStandard::Erc20);
+        assert_eq!(TokenStandard::classify(Some(U256::MAX), false, false), TokenStandard::Erc20);
+
+        let (granularity, supports_erc1363, rebasing) = token_standard_probes();
+        assert_eq!(granularity, vec![0x55, 0x6f, 0x0d, 0xc7]);
+        assert_eq!(&supports_erc1363[..8], &[0x01, 0xff, 0xc9, 0xa7, 0xb0, 0x20, 0x2a, 0x11]);
+        assert_eq!(supports_erc1363.len(), 36);
+        assert_eq!(rebasing.len(), REBASING_GETTERS.len());
+        assert_eq!(rebasing[3].len(), 36); // sharesOf(address(0))
+
+        let rebasing = HoneypotResult::safe(1.0, 1.0, 0, vec![], 10).with_token_standard(TokenStandard::Rebasing);
+        assert_eq!(rebasing.token_standard, Some(TokenStandard::Rebasing));
+        assert_eq!(rebasing.warnings, vec![WARNING_REBASING_TOKEN.to_string()]);
+        let erc777 = HoneypotResult::safe(1.0, 1.0, 0, vec![], 10).with_token_standard(TokenStandard::Erc777);
+        assert!(erc777.risk_factors.iter().any(|f| f.starts_with("ERC777 HOOKS")));
+        assert!(erc777.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_unconfirmed_honeypot_is_unverified() {
+        let detector = HoneypotDetector::mainnet().with_verdict_confirmation(true);
+        assert!(detector.confirm_verdict);
+
+        let result = HoneypotResult::honeypot("Cannot sell".to_string(), true, false, true, 0, vec![], 10)
+            .with_confidence(CONFIDENCE_VERIFIED);
+        assert!(result.is_verified(DEFAULT_MIN_CONFIDENCE));
+
+        let unconfirmed = result.unconfirmed();
+        assert!(unconfirmed.is_honeypot);
+        assert_eq!(unconfirmed.confidence, CONFIDENCE_UNCONFIRMED);
+        assert!(!unconfirmed.is_verified(DEFAULT_MIN_CONFIDENCE));
+        assert_eq!(unconfirmed.warnings, vec![WARNING_VERDICT_UNCONFIRMED.to_string()]);
+        assert!(unconfirmed.risk_factors.iter().any(|f| f.starts_with("UNCONFIRMED")));
+    }
+
+    #[test]
+    fn test_gas_price_probe_env() {
+        let detector = HoneypotDetector::mainnet().with_gas_price_check(true);
+        assert!(detector.gas_price_check);
+
+        let (from, token) = (Address::repeat_byte(0x0a), Address::repeat_byte(0x0b));
+        let high = detector.sell_env_at(from, token, U256::from(1u64), 0, U256::from(GAS_GATE_HIGH_PRICE_WEI));
+        assert_eq!(high.tx.gas_price, U256::from(GAS_GATE_HIGH_PRICE_WEI));
+        assert_eq!(high.block.basefee, U256::from(GAS_GATE_HIGH_PRICE_WEI));
+
+        // Default sell keeps the regular simulation price
+        let default = detector.sell_env(from, token, U256::from(1u64), 0);
+        assert_eq!(default.tx.gas_price, U256::from(SIM_GAS_PRICE_WEI));
+        assert_eq!(default.block.basefee, U256::from(SIM_GAS_PRICE_WEI));
+    }
+
     #[test]
     fn test_access_control_scan() {
         let detector = HoneypotDetector::mainnet();
-        let mut risk_factors = Vec::new();
+        let result = HoneypotResult::safe(0.0, 0.0, 0, vec![], 10);
 
-        // Bytecode containing setBots selector (974d396d)
+        // Bytecode containing setBots selector (9c0db5f3)
         let malicious_bytecode =
-            Bytes::from(hex::decode("608060405234801561001057600080fd5b50974d396d").unwrap());
-        let penalty =
-            detector.scan_access_control_functions(&malicious_bytecode, &mut risk_factors);
+            Bytes::from(hex::decode("608060405234801561001057600080fd5b509c0db5f3").unwrap());
+        let result = detector.run_heuristics(Address::ZERO, Some(&malicious_bytecode), result);
+
+        // One selector: the per-selector share, not the full cap
+        assert_eq!(result.access_control_penalty, 20);
+        assert!(!result.risk_factors.is_empty());
+    }
+
+    #[test]
+    fn test_sim_detail_from_outcomes() {
+        let buy = BuyOutcome {
+            tokens_received: U256::from(1_000_000u64),
+            buy_fee_percent: None,
+            mocked: false,
+            gas_used: 120_000,
+        };
+        let sell = |status| SellOutcome {
+            status,
+            dynamic_blacklist_detected: false,
+            same_block_revert: None,
+            sell_lock_blocks: None,
+            gas_used: 90_000,
+            balance_delta: None,
+            final_token_balance: Some(U256::ZERO),
+            final_native_balance: Some(U256::from(5_000u64)),
+        };
+
+        let sold = sell(SellStatus::Sold { native_received: U256::from(4_000u64), mocked: false });
+        let detail = SimDetail::from_outcomes(&buy, &sold).unwrap();
+        assert_eq!(detail.tokens_bought, U256::from(1_000_000u64));
+        assert_eq!(detail.eth_returned, U256::from(4_000u64));
+        assert_eq!(detail.final_eth_balance, Some(U256::from(5_000u64)));
+
+        // A reverted sell returned nothing
+        let reverted = SimDetail::from_outcomes(&buy, &sell(SellStatus::Reverted("blocked".into()))).unwrap();
+        assert_eq!(reverted.eth_returned, U256::ZERO);
 
-        assert_eq!(penalty, 50);
-        assert!(!risk_factors.is_empty());
+        // Mocked amounts are not a raw outcome
+        let mocked = sell(SellStatus::Sold { native_received: U256::from(4_000u64), mocked: true });
+        assert!(SimDetail::from_outcomes(&buy, &mocked).is_none());
+        assert!(SimDetail::from_outcomes(&BuyOutcome { mocked: true, ..buy }, &sold).is_none());
+    }
+
+    #[test]
+    fn test_ownable_token_with_tax_is_not_escalated() {
+        let rule = CompositeRiskRule { min_dimensions: 2, ..CompositeRiskRule::default() };
+        let detector = HoneypotDetector::mainnet().with_composite_rule(rule);
+        // owner() + transferOwnership + renounceOwnership + transfer: a plain Ownable ERC20
+        let ownable = Bytes::from(hex::decode("60806040528da5cb5b00f2fde38b00715018a600a9059cbb").unwrap());
+
+        let result = HoneypotResult::safe(0.0, HIGH_TAX_MIN_PERCENT, 0, vec![], 10);
+        let result = detector.run_heuristics(Address::ZERO, Some(&ownable), result);
+        assert_eq!(result.access_control_penalty, 0);
+        assert!(!result.is_honeypot && !result.likely_honeypot);
+
+        // The same tax next to a real blacklist setter does escalate
+        let blacklist = Bytes::from(hex::decode("60806040528da5cb5b00153b0d1e").unwrap());
+        let result = HoneypotResult::safe(0.0, HIGH_TAX_MIN_PERCENT, 0, vec![], 10);
+        let result = detector.run_heuristics(Address::ZERO, Some(&blacklist), result);
+        assert!(result.likely_honeypot);
+        // A passing sell stays sellable: flagged, never reported as a honeypot
+        assert!(!result.is_honeypot);
     }
 }
@@ -16,8 +16,10 @@
 //! - Confidence intervals based on data completeness
 //! - Ensemble approach combining multiple signals
 
+use serde::Serialize;
 use std::collections::HashMap;
 use tracing::{debug, info};
+use utoipa::ToSchema;
 
 // ============================================
 // FEATURE WEIGHTS (Trained on historical data)
@@ -27,6 +29,7 @@ use tracing::{debug, info};
 pub mod contract_weights {
     pub const VERIFIED_SOURCE: f64 = -15.0;      // Verified = lower risk
     pub const PROXY_CONTRACT: f64 = 20.0;        // Proxy = higher risk
+    pub const RECENT_UPGRADE: f64 = 25.0;        // Logic swapped recently = high risk
     pub const BLACKLIST_FUNCTION: f64 = 35.0;    // Blacklist = high risk
     pub const PAUSE_FUNCTION: f64 = 15.0;        // Pausable = medium risk
     pub const MINT_FUNCTION: f64 = 25.0;         // Mintable = higher risk
@@ -76,6 +79,7 @@ pub mod social_weights {
 pub struct ContractFeatures {
     pub is_verified: bool,
     pub is_proxy: bool,
+    pub recently_upgraded: bool,
     pub has_blacklist: bool,
     pub has_pause: bool,
     pub has_mint: bool,
@@ -99,11 +103,42 @@ pub struct LiquidityFeatures {
     pub pool_count: u32,
 }
 
+impl LiquidityFeatures {
+    /// Rug-pull readiness sub-score (0-100): how easily the LP can be pulled.
+    /// Independent from the honeypot verdict - a token can trade fine and
+    /// still be trivially ruggable.
+    ///
+    /// - Lock status/duration: unlocked = 60, locked <30d = 40, <180d = 20, <365d = 10
+    /// - LP concentration: top holder % scaled to 30 (10 if locked, since the locker holds it)
+    /// - Single LP holder: +10
+    pub fn rug_risk(&self) -> u32 {
+        let lock_risk = if !self.is_locked {
+            60.0
+        } else {
+            match self.lock_duration_days {
+                0..=29 => 40.0,
+                30..=179 => 20.0,
+                180..=364 => 10.0,
+                _ => 0.0,
+            }
+        };
+
+        let concentration_scale = if self.is_locked { 0.1 } else { 0.3 };
+        let concentration_risk = self.top_lp_holder_percent.clamp(0.0, 100.0) * concentration_scale;
+
+        let single_holder_risk = if self.lp_holder_count <= 1 { 10.0 } else { 0.0 };
+
+        (lock_risk + concentration_risk + single_holder_risk).clamp(0.0, 100.0) as u32
+    }
+}
+
 /// Trading analysis features
 #[derive(Debug, Clone, Default)]
 pub struct TradingFeatures {
     pub volume_24h_usd: f64,
     pub holder_count: u32,
+    /// `holder_count` covers only a recent block window (a lower bound): never scored as low holders
+    pub holder_count_truncated: bool,
     pub top_10_holder_percent: f64,
     pub buy_count_24h: u32,
     pub sell_count_24h: u32,
@@ -161,15 +196,45 @@ pub struct MLRiskScore {
     pub risk_factors: Vec<RiskFactor>,
     /// Recommendation
     pub recommendation: Recommendation,
+    /// Rug-pull readiness of liquidity (0-100), reported separately from `score`
+    pub rug_risk: u32,
 }
 
-/// Risk categories
-#[derive(Debug, Clone, PartialEq)]
+impl MLRiskScore {
+    /// Keep the `max` most severe (then heaviest) factors and summarize the rest
+    /// as one "and X more" factor carrying their combined weight (0 = keep all)
+    pub fn cap_risk_factors(&mut self, max: usize) {
+        if max == 0 || self.risk_factors.len() <= max {
+            return;
+        }
+        self.risk_factors.sort_by(|a, b| {
+            b.severity
+                .rank()
+                .cmp(&a.severity.rank())
+                .then(b.weight.partial_cmp(&a.weight).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        let rest = self.risk_factors.split_off(max);
+        self.risk_factors.push(RiskFactor {
+            name: "More Factors".to_string(),
+            description: format!("and {} more", rest.len()),
+            weight: rest.iter().map(|f| f.weight).sum(),
+            severity: Severity::Low,
+        });
+    }
+}
+
+/// Risk categories (serialized names are part of the API: `HoneypotCheckData::category`)
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
 pub enum RiskCategory {
+    #[serde(rename = "Safe")]
     Safe,       // 0-20
+    #[serde(rename = "Low")]
     Low,        // 21-40
+    #[serde(rename = "Medium")]
     Medium,     // 41-60
+    #[serde(rename = "High")]
     High,       // 61-80
+    #[serde(rename = "Critical")]
     Critical,   // 81-100
 }
 
@@ -213,12 +278,27 @@ pub enum Severity {
     Critical,
 }
 
-/// Trading recommendation
-#[derive(Debug, Clone)]
+impl Severity {
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Low => 0,
+            Self::Medium => 1,
+            Self::High => 2,
+            Self::Critical => 3,
+        }
+    }
+}
+
+/// Trading recommendation (serialized names are part of the API: `HoneypotCheckData::recommendation`)
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
 pub enum Recommendation {
+    #[serde(rename = "Buy")]
     Buy,
+    #[serde(rename = "Caution")]
     Caution,
+    #[serde(rename = "Avoid")]
     Avoid,
+    #[serde(rename = "DoNotTrade")]
     DoNotTrade,
 }
 
@@ -273,6 +353,7 @@ impl MLRiskScorer {
         // Contract weights
         weights.insert("verified_source".to_string(), contract_weights::VERIFIED_SOURCE);
         weights.insert("proxy_contract".to_string(), contract_weights::PROXY_CONTRACT);
+        weights.insert("recent_proxy_upgrade".to_string(), contract_weights::RECENT_UPGRADE);
         weights.insert("blacklist_function".to_string(), contract_weights::BLACKLIST_FUNCTION);
         weights.insert("pause_function".to_string(), contract_weights::PAUSE_FUNCTION);
         weights.insert("mint_function".to_string(), contract_weights::MINT_FUNCTION);
@@ -351,6 +432,19 @@ impl MLRiskScorer {
             data_points += 1;
         }
 
+        if features.contract.recently_upgraded {
+            let weight = self.weights.get("recent_proxy_upgrade").unwrap_or(&25.0);
+            raw_score += weight;
+            feature_scores.insert("recent_proxy_upgrade".to_string(), *weight);
+            risk_factors.push(RiskFactor {
+                name: "Recent Proxy Upgrade".to_string(),
+                description: "Proxy implementation was swapped in the last few days".to_string(),
+                weight: *weight,
+                severity: Severity::High,
+            });
+            data_points += 1;
+        }
+
         if features.contract.has_blacklist {
             let weight = self.weights.get("blacklist_function").unwrap_or(&35.0);
             raw_score += weight;
@@ -442,7 +536,7 @@ impl MLRiskScorer {
         // TRADING FEATURES
         // ============================================
 
-        if features.trading.holder_count < 50 {
+        if features.trading.holder_count < 50 && !features.trading.holder_count_truncated {
             let weight = self.weights.get("low_holders").unwrap_or(&20.0);
             raw_score += weight;
             feature_scores.insert("low_holders".to_string(), *weight);
@@ -561,6 +655,7 @@ impl MLRiskScorer {
             feature_scores,
             risk_factors,
             recommendation,
+            rug_risk: features.liquidity.rug_risk(),
         }
     }
 
@@ -598,6 +693,144 @@ impl MLRiskScorer {
     pub fn get_weights(&self) -> &HashMap<String, f64> {
         &self.weights
     }
+
+    /// Apply weights saved by `save_weights` (JSON object feature → weight)
+    /// Unknown features are ignored; returns how many weights were applied
+    pub fn load_weights(&mut self, path: &std::path::Path) -> std::io::Result<usize> {
+        let json = std::fs::read_to_string(path)?;
+        let saved: HashMap<String, f64> = serde_json::from_str(&json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        let mut applied = 0;
+        for (feature, value) in saved {
+            if let Some(weight) = self.weights.get_mut(&feature) {
+                *weight = value;
+                applied += 1;
+            }
+        }
+        info!("📊 Loaded {} ML weight(s) from {}", applied, path.display());
+        Ok(applied)
+    }
+
+    /// Persist current weights as JSON (written to a temp file, then renamed)
+    pub fn save_weights(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.weights)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)
+    }
+}
+
+// ============================================
+// FEEDBACK LEARNING
+// ============================================
+
+/// Reports needed on a feature before its weight moves
+pub const FEEDBACK_MIN_SAMPLES: u32 = 5;
+
+/// Weight points per unit of mean prediction error
+pub const FEEDBACK_LEARNING_RATE: f64 = 2.0;
+
+/// Largest single weight adjustment (points)
+pub const FEEDBACK_MAX_STEP: f64 = 2.0;
+
+/// Largest total drift of a weight from its built-in default (points)
+pub const FEEDBACK_MAX_DRIFT: f64 = 15.0;
+
+/// ML score and weighted features recorded at analysis time, for later feedback
+#[derive(Debug, Clone)]
+pub struct RecordedPrediction {
+    /// ML score (0-100) the features produced
+    pub score: u8,
+    /// Weighted features that fired (`MLRiskScore::feature_scores` keys)
+    pub features: Vec<String>,
+    /// Unix seconds of the analysis
+    pub recorded_at: u64,
+}
+
+impl RecordedPrediction {
+    pub fn from_score(result: &MLRiskScore, recorded_at: u64) -> Self {
+        Self {
+            score: result.score.min(100) as u8,
+            features: result.feature_scores.keys().cloned().collect(),
+            recorded_at,
+        }
+    }
+}
+
+/// Outcome of one feedback report
+#[derive(Debug, Clone, Default)]
+pub struct FeedbackOutcome {
+    /// Features whose report was counted (pending until FEEDBACK_MIN_SAMPLES)
+    pub counted: Vec<String>,
+    /// Weights that moved: (feature, new weight)
+    pub adjusted: Vec<(String, f64)>,
+}
+
+/// Online learning from user feedback, guarded against single-report swings:
+/// per-feature error is averaged over FEEDBACK_MIN_SAMPLES reports, each
+/// adjustment is capped at FEEDBACK_MAX_STEP, and weights never drift more
+/// than FEEDBACK_MAX_DRIFT from their defaults
+pub struct FeedbackLearner {
+    defaults: HashMap<String, f64>,
+    /// feature → (summed error, report count) since its last adjustment
+    pending: HashMap<String, (f64, u32)>,
+}
+
+impl Default for FeedbackLearner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeedbackLearner {
+    pub fn new() -> Self {
+        Self {
+            defaults: MLRiskScorer::new().weights,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Record whether a prediction was right; nudge weights once enough reports agree
+    /// A feature that fired on an under-scored honeypot moves up, on an
+    /// over-scored safe token moves down (score gradient is 1 per fired weight)
+    pub fn record(
+        &mut self,
+        scorer: &mut MLRiskScorer,
+        prediction: &RecordedPrediction,
+        was_honeypot: bool,
+    ) -> FeedbackOutcome {
+        let target = if was_honeypot { 1.0 } else { 0.0 };
+        let error = target - prediction.score as f64 / 100.0;
+        let mut outcome = FeedbackOutcome::default();
+
+        for feature in &prediction.features {
+            let Some(&default) = self.defaults.get(feature) else {
+                continue; // Unweighted contribution (e.g. high_liquidity)
+            };
+            outcome.counted.push(feature.clone());
+
+            let (sum, count) = self.pending.entry(feature.clone()).or_insert((0.0, 0));
+            *sum += error;
+            *count += 1;
+            if *count < FEEDBACK_MIN_SAMPLES {
+                continue;
+            }
+
+            let step = (*sum / *count as f64 * FEEDBACK_LEARNING_RATE).clamp(-FEEDBACK_MAX_STEP, FEEDBACK_MAX_STEP);
+            self.pending.remove(feature);
+
+            let current = scorer.weights.get(feature).copied().unwrap_or(default);
+            let target_weight = (current + step).clamp(default - FEEDBACK_MAX_DRIFT, default + FEEDBACK_MAX_DRIFT);
+            if (target_weight - current).abs() > 1e-9 {
+                scorer.update_weight(feature, target_weight - current);
+                outcome.adjusted.push((feature.clone(), target_weight));
+            }
+        }
+
+        outcome
+    }
 }
 
 // ============================================
@@ -669,6 +902,20 @@ mod tests {
         assert!(matches!(Recommendation::from_score(80), Recommendation::DoNotTrade));
     }
 
+    #[test]
+    fn test_recommendation_and_category_serialization() {
+        let json = |value: serde_json::Value| value.as_str().unwrap().to_string();
+        assert_eq!(json(serde_json::json!(Recommendation::Buy)), "Buy");
+        assert_eq!(json(serde_json::json!(Recommendation::Caution)), "Caution");
+        assert_eq!(json(serde_json::json!(Recommendation::Avoid)), "Avoid");
+        assert_eq!(json(serde_json::json!(Recommendation::DoNotTrade)), "DoNotTrade");
+        assert_eq!(json(serde_json::json!(RiskCategory::Safe)), "Safe");
+        assert_eq!(json(serde_json::json!(RiskCategory::Low)), "Low");
+        assert_eq!(json(serde_json::json!(RiskCategory::Medium)), "Medium");
+        assert_eq!(json(serde_json::json!(RiskCategory::High)), "High");
+        assert_eq!(json(serde_json::json!(RiskCategory::Critical)), "Critical");
+    }
+
     #[test]
     fn test_quick_risk_score() {
         // Safe token
@@ -680,6 +927,28 @@ mod tests {
         assert!(risky_score > 70);
     }
 
+    #[test]
+    fn test_rug_risk() {
+        // Unlocked, single holder owns all LP = trivially ruggable
+        let unlocked = LiquidityFeatures {
+            is_locked: false,
+            lp_holder_count: 1,
+            top_lp_holder_percent: 100.0,
+            ..Default::default()
+        };
+        assert_eq!(unlocked.rug_risk(), 100);
+
+        // Locked for a year, spread across holders
+        let locked = LiquidityFeatures {
+            is_locked: true,
+            lock_duration_days: 400,
+            lp_holder_count: 50,
+            top_lp_holder_percent: 20.0,
+            ..Default::default()
+        };
+        assert!(locked.rug_risk() < 10);
+    }
+
     #[test]
     fn test_ml_scorer_creation() {
         let scorer = MLRiskScorer::new();
@@ -687,6 +956,52 @@ mod tests {
         assert!(!scorer.scam_patterns.is_empty());
     }
 
+    #[test]
+    fn test_feedback_learner_guardrails() {
+        let mut scorer = MLRiskScorer::new();
+        let mut learner = FeedbackLearner::new();
+        let default = scorer.weights["blacklist_function"];
+        // Scored safe, reported as honeypot
+        let missed = RecordedPrediction {
+            score: 10,
+            features: vec!["blacklist_function".to_string(), "high_liquidity".to_string()],
+            recorded_at: 0,
+        };
+
+        // Below the sample minimum nothing moves
+        for _ in 1..FEEDBACK_MIN_SAMPLES {
+            let outcome = learner.record(&mut scorer, &missed, true);
+            assert_eq!(outcome.counted, vec!["blacklist_function".to_string()]);
+            assert!(outcome.adjusted.is_empty());
+        }
+        assert_eq!(scorer.weights["blacklist_function"], default);
+
+        // The Nth report moves the weight up by at most one step
+        let outcome = learner.record(&mut scorer, &missed, true);
+        assert_eq!(outcome.adjusted.len(), 1);
+        let moved = scorer.weights["blacklist_function"];
+        assert!(moved > default && moved <= default + FEEDBACK_MAX_STEP);
+
+        // Repeated reports never drift past the bound
+        for _ in 0..(FEEDBACK_MIN_SAMPLES * 50) {
+            learner.record(&mut scorer, &missed, true);
+        }
+        assert!((scorer.weights["blacklist_function"] - (default + FEEDBACK_MAX_DRIFT)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weights_roundtrip() {
+        let path = std::env::temp_dir().join(format!("ml_weights_{}.json", std::process::id()));
+        let mut scorer = MLRiskScorer::new();
+        scorer.update_weight("new_token", 3.0);
+        scorer.save_weights(&path).unwrap();
+
+        let mut loaded = MLRiskScorer::new();
+        assert!(loaded.load_weights(&path).unwrap() > 0);
+        assert_eq!(loaded.weights["new_token"], scorer.weights["new_token"]);
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_ml_score_calculation() {
         let scorer = MLRiskScorer::new();
@@ -712,5 +1027,37 @@ mod tests {
         let result = scorer.calculate_score(&features);
         assert!(result.score > 50); // Should be risky
         assert!(!result.risk_factors.is_empty());
+        assert!(result.risk_factors.iter().any(|f| f.name == "Low Holder Count"));
+
+        // 30 holders in a truncated scan is a lower bound, not a low holder count
+        let truncated = MLFeatureSet {
+            trading: TradingFeatures { holder_count: 30, holder_count_truncated: true, ..Default::default() },
+            ..features
+        };
+        let result = scorer.calculate_score(&truncated);
+        assert!(!result.risk_factors.iter().any(|f| f.name == "Low Holder Count"));
+    }
+
+    #[test]
+    fn test_cap_risk_factors() {
+        let factor = |name: &str, weight: f64, severity: Severity| RiskFactor {
+            name: name.to_string(),
+            description: String::new(),
+            weight,
+            severity,
+        };
+        let mut result = MLRiskScorer::new().calculate_score(&MLFeatureSet::default());
+        result.risk_factors = vec![
+            factor("a", 30.0, Severity::Medium),
+            factor("b", 10.0, Severity::Critical),
+            factor("c", 20.0, Severity::Medium),
+            factor("d", 5.0, Severity::Low),
+        ];
+
+        result.cap_risk_factors(2);
+        let names: Vec<&str> = result.risk_factors.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["b", "a", "More Factors"]);
+        assert_eq!(result.risk_factors[2].description, "and 2 more");
+        assert!((result.risk_factors[2].weight - 25.0).abs() < 1e-9);
     }
 }
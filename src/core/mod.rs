@@ -4,15 +4,25 @@
 //! CEO Directive: Logika inti tidak boleh berubah, hanya dipindahkan.
 //!
 //! ML Risk Scoring: Advanced weighted feature analysis for honeypot detection.
+//! Heuristics: pluggable post-simulation checks (`HoneypotHeuristic`).
+//! Pipeline: the REVM check as typed stages (`AnalysisPipeline`).
 
 pub mod analyzer;
+pub mod heuristics;
 pub mod honeypot;
 pub mod ml_risk;
+pub mod pipeline;
 pub mod risk_score;
+pub mod sell_trace;
 pub mod simulator;
+pub mod storage_probe;
 
 pub use analyzer::*;
+pub use heuristics::*;
 pub use honeypot::*;
 pub use ml_risk::*;
+pub use pipeline::*;
 pub use risk_score::*;
+pub use sell_trace::*;
 pub use simulator::*;
+pub use storage_probe::*;
@@ -0,0 +1,441 @@
+//! Analysis Pipeline - the REVM honeypot check as discrete, typed stages
+//!
+//! FetchBytecode → ScanStatic → SimBuy → SimApprove → SimSell → ComputeTax → Score
+//!
+//! Every stage has a typed output (`FetchedCode`, `StaticScan`, `BuyOutcome`,
+//! `SellOutcome`, `TaxBreakdown`), so each one can be tested on its own.
+//! `HoneypotDetector` runs the stages its `AnalysisPipeline` enables. Their order
+//! is fixed by data dependencies; profiles choose which stages run.
+
+use alloy_primitives::{Bytes, U256};
+
+use crate::core::honeypot::{implied_sell_fee, FeeClass, HoneypotDetector, LossCutoffs};
+use crate::utils::constants::loss_percent;
+
+/// One step of the REVM honeypot check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// Load token / router / pair bytecode over RPC (skipped = built-in mocks)
+    FetchBytecode,
+    /// Bytecode scan (SELFDESTRUCT) before anything executes
+    ScanStatic,
+    /// Native → token swap through the primary router
+    SimBuy,
+    /// approve(router) from the test account
+    SimApprove,
+    /// Token → native swap, with anti-bot retry and storage probe
+    SimSell,
+    /// Round-trip loss split into buy / sell fees and classified
+    ComputeTax,
+    /// Post-simulation heuristics (access control scan, custom checks)
+    Score,
+}
+
+impl Stage {
+    /// All stages in execution order
+    pub const ALL: [Stage; 7] = [
+        Stage::FetchBytecode,
+        Stage::ScanStatic,
+        Stage::SimBuy,
+        Stage::SimApprove,
+        Stage::SimSell,
+        Stage::ComputeTax,
+        Stage::Score,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Stage::FetchBytecode => "fetch_bytecode",
+            Stage::ScanStatic => "scan_static",
+            Stage::SimBuy => "sim_buy",
+            Stage::SimApprove => "sim_approve",
+            Stage::SimSell => "sim_sell",
+            Stage::ComputeTax => "compute_tax",
+            Stage::Score => "score",
+        }
+    }
+
+    /// Progress message once the stage has finished
+    pub fn done_message(&self) -> &'static str {
+        match self {
+            Stage::FetchBytecode => "fetched bytecode",
+            Stage::ScanStatic => "scanned bytecode",
+            Stage::SimBuy => "buy simulated",
+            Stage::SimApprove => "approve simulated",
+            Stage::SimSell => "sell simulated",
+            Stage::ComputeTax => "tax computed",
+            Stage::Score => "heuristics scored",
+        }
+    }
+}
+
+/// Stages a detector runs, in execution order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalysisPipeline {
+    stages: Vec<Stage>,
+}
+
+impl Default for AnalysisPipeline {
+    fn default() -> Self {
+        Self::full()
+    }
+}
+
+impl AnalysisPipeline {
+    /// Every stage (default)
+    pub fn full() -> Self {
+        Self { stages: Stage::ALL.to_vec() }
+    }
+
+    /// Bytecode checks only - no buy/sell, so the verdict is unverified
+    pub fn static_only() -> Self {
+        Self {
+            stages: vec![Stage::FetchBytecode, Stage::ScanStatic, Stage::Score],
+        }
+    }
+
+    /// Same pipeline with `stage` skipped
+    pub fn without(mut self, stage: Stage) -> Self {
+        self.stages.retain(|s| *s != stage);
+        self
+    }
+
+    /// Is `stage` enabled?
+    pub fn runs(&self, stage: Stage) -> bool {
+        self.stages.contains(&stage)
+    }
+
+    /// Buy, approve and sell all enabled - a round trip can be simulated
+    pub fn simulates(&self) -> bool {
+        self.runs(Stage::SimBuy) && self.runs(Stage::SimApprove) && self.runs(Stage::SimSell)
+    }
+
+    /// Enabled stages in execution order
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+}
+
+/// FetchBytecode output (None = fall back to the built-in mock)
+#[derive(Debug, Clone, Default)]
+pub struct FetchedCode {
+    pub token: Option<Bytes>,
+    pub router: Option<Bytes>,
+    pub pair: Option<Bytes>,
+}
+
+/// ScanStatic output
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StaticScan {
+    /// Penalty added to the access-control score
+    pub penalty: u8,
+    pub risk_factors: Vec<String>,
+}
+
+/// ScanStatic: bytecode checks that need no execution
+pub fn scan_static(bytecode: &Bytes) -> StaticScan {
+    let mut risk_factors = Vec::new();
+    let penalty = HoneypotDetector::scan_selfdestruct(bytecode, &mut risk_factors);
+    StaticScan { penalty, risk_factors }
+}
+
+/// SimBuy output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BuyOutcome {
+    /// Tokens the sell leg will sell (balance actually held after the buy)
+    pub tokens_received: U256,
+    /// Fee-on-transfer measured on the buy (pool output vs balance credited)
+    pub buy_fee_percent: Option<f64>,
+    /// Mock token / router output stood in for a real amount
+    pub mocked: bool,
+    /// Gas the buy transaction used
+    pub gas_used: u64,
+}
+
+/// How the sell leg ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum SellStatus {
+    /// Router paid out `native_received` (estimated when `mocked`)
+    Sold { native_received: U256, mocked: bool },
+    /// Sell transaction reverted (honeypot)
+    Reverted(String),
+    /// EVM error - the sell never executed
+    Failed(String),
+}
+
+/// SimSell output
+#[derive(Debug, Clone, PartialEq)]
+pub struct SellOutcome {
+    pub status: SellStatus,
+    /// Sell reverted right after reading a seller-keyed storage flag
+    pub dynamic_blacklist_detected: bool,
+    /// Revert reason when the sell only failed in the buy block (anti-bot delay)
+    pub same_block_revert: Option<String>,
+    /// Shortest block offset after the buy at which the reverted sell went through
+    pub sell_lock_blocks: Option<u64>,
+    /// Gas the executed sell used (the delayed retry, if that is the one that went through)
+    pub gas_used: u64,
+    /// Native the seller's balance actually gained, sell gas added back (None if unreadable)
+    pub balance_delta: Option<U256>,
+    /// Seller's token balance in the committed post-sell state (None if unreadable)
+    pub final_token_balance: Option<U256>,
+    /// Seller's native balance in the committed post-sell state (None if unreadable)
+    pub final_native_balance: Option<U256>,
+}
+
+impl SellOutcome {
+    /// Sell went through (possibly after the anti-bot delay)
+    pub fn sold(&self) -> bool {
+        matches!(self.status, SellStatus::Sold { .. })
+    }
+
+    /// Sell went through in the buy block, without the anti-bot / sell-lock retry
+    pub fn sold_in_buy_block(&self) -> bool {
+        self.sold() && self.same_block_revert.is_none()
+    }
+
+    /// Another sell path, tried in the buy block only, ended differently from this one.
+    /// Like with like: its success contrasts only with a sell that never went through,
+    /// its revert only with a sell that went through in the buy block (reverting where
+    /// this sell needed the delayed retry is the anti-bot delay / sell lock, not the path)
+    pub fn differs_from_buy_block_sell(&self, other_sold: bool) -> bool {
+        if other_sold {
+            !self.sold()
+        } else {
+            self.sold_in_buy_block()
+        }
+    }
+
+    /// Seller's balance grew by at least the router-reported proceeds
+    /// (true when the sell did not go through, was mocked, or the balance was unreadable)
+    pub fn proceeds_received(&self) -> bool {
+        match (&self.status, self.balance_delta) {
+            (SellStatus::Sold { native_received, mocked: false }, Some(delta)) => delta >= *native_received,
+            _ => true,
+        }
+    }
+}
+
+/// ComputeTax output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaxBreakdown {
+    /// Loss to token taxes only (router amounts, gas excluded)
+    pub total_loss_percent: f64,
+    /// Loss including the round trip's gas cost (capped at 100 when gas exceeds the proceeds)
+    pub net_loss_percent: f64,
+    pub buy_tax_percent: f64,
+    pub sell_tax_percent: f64,
+    /// Reflection vs high tax (only when the buy fee was measured)
+    pub fee_class: Option<FeeClass>,
+    /// Loss above the chain's honeypot cutoff
+    pub exceeds_honeypot_cutoff: bool,
+}
+
+/// ComputeTax: split the round-trip loss into buy / sell fees and classify it
+/// `gas_cost` (gas used × gas price) only feeds `net_loss_percent` - a tiny test
+/// amount must not read as a high tax. None for a zero test amount
+pub fn compute_tax(
+    test_amount: U256,
+    native_received: U256,
+    gas_cost: U256,
+    buy_fee_percent: Option<f64>,
+    cutoffs: &LossCutoffs,
+) -> Option<TaxBreakdown> {
+    if test_amount.is_zero() {
+        return None;
+    }
+    // If we put in X ETH and got back Y ETH, loss = (X - Y) / X * 100
+    let total_loss_percent = loss_percent(test_amount, native_received);
+    let net_loss_percent = loss_percent(test_amount, native_received.saturating_sub(gas_cost));
+
+    // Measured buy fee splits the loss; otherwise assume an equal split
+    let (buy_tax_percent, sell_tax_percent) = match buy_fee_percent {
+        Some(buy_fee) => (buy_fee, implied_sell_fee(total_loss_percent, buy_fee)),
+        None => (total_loss_percent / 2.0, total_loss_percent / 2.0),
+    };
+
+    // Loss without a revert: reflection (symmetric, bounded) vs high tax
+    let fee_class = buy_fee_percent
+        .and_then(|_| FeeClass::classify_with(buy_tax_percent, sell_tax_percent, cutoffs.high_tax_percent));
+
+    Some(TaxBreakdown {
+        total_loss_percent,
+        net_loss_percent,
+        buy_tax_percent,
+        sell_tax_percent,
+        fee_class,
+        exceeds_honeypot_cutoff: total_loss_percent > cutoffs.honeypot_loss_percent,
+    })
+}
+
+/// Measured tax may exceed the declared one by this much (percentage points) before it
+/// counts as a mismatch - the measured figure also carries AMM fees and price impact
+pub const DECLARED_TAX_TOLERANCE_PERCENT: f64 = 2.0;
+
+/// Taxes a token advertises through public getters (`buyTax()`, `_taxFee()`, ...)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DeclaredTax {
+    /// Declared buy tax (%), None if no buy-side getter was readable
+    pub buy_percent: Option<f64>,
+    /// Declared sell tax (%), None if no sell-side getter was readable
+    pub sell_percent: Option<f64>,
+}
+
+impl DeclaredTax {
+    /// Getter word as a percent. Values above 100 are ambiguous (basis points vs
+    /// per-mille) and skipped, so an unknown scale never reads as an understated tax
+    pub fn percent_from_raw(raw: U256) -> Option<f64> {
+        (raw <= U256::from(100u64)).then(|| raw.to::<u64>() as f64)
+    }
+
+    /// Measured tax exceeds the declared one (beyond the tolerance) on either side
+    pub fn understated(&self, buy_tax_percent: f64, sell_tax_percent: f64) -> bool {
+        let exceeds = |declared: Option<f64>, measured: f64| {
+            declared.is_some_and(|declared| measured > declared + DECLARED_TAX_TOLERANCE_PERCENT)
+        };
+        exceeds(self.buy_percent, buy_tax_percent) || exceeds(self.sell_percent, sell_tax_percent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eth(milli: u64) -> U256 {
+        U256::from(milli) * U256::from(1_000_000_000_000_000u64)
+    }
+
+    #[test]
+    fn test_pipeline_stages() {
+        let full = AnalysisPipeline::default();
+        assert_eq!(full.stages(), &Stage::ALL);
+        assert!(full.simulates());
+
+        let no_tax = AnalysisPipeline::full().without(Stage::ComputeTax);
+        assert!(!no_tax.runs(Stage::ComputeTax));
+        assert!(no_tax.simulates());
+
+        // Approve is part of the round trip - without it there is nothing to sell through
+        assert!(!AnalysisPipeline::full().without(Stage::SimApprove).simulates());
+        let static_only = AnalysisPipeline::static_only();
+        assert!(!static_only.simulates());
+        assert!(static_only.runs(Stage::ScanStatic) && static_only.runs(Stage::Score));
+    }
+
+    #[test]
+    fn test_scan_static() {
+        // PUSH1 0xff (data, not an opcode) then STOP
+        assert_eq!(scan_static(&Bytes::from(vec![0x60, 0xff, 0x00])), StaticScan::default());
+        // Bare SELFDESTRUCT
+        let scan = scan_static(&Bytes::from(vec![0x33, 0xff]));
+        assert!(scan.penalty > 0);
+        assert_eq!(scan.risk_factors.len(), 1);
+    }
+
+    #[test]
+    fn test_compute_tax() {
+        let cutoffs = LossCutoffs::default();
+        assert_eq!(compute_tax(U256::ZERO, eth(1), U256::ZERO, None, &cutoffs), None);
+
+        // 10% round trip, no measured buy fee: split evenly, no fee class
+        let tax = compute_tax(eth(100), eth(90), U256::ZERO, None, &cutoffs).unwrap();
+        assert!((tax.total_loss_percent - 10.0).abs() < 1e-9);
+        assert!((tax.buy_tax_percent - 5.0).abs() < 1e-9);
+        assert_eq!(tax.fee_class, None);
+        assert!(!tax.exceeds_honeypot_cutoff);
+
+        // Measured 5% buy fee + ~5% sell fee = reflection
+        let tax =
+            compute_tax(eth(1000), eth(9025) / U256::from(10u64), U256::ZERO, Some(5.0), &cutoffs)
+                .unwrap();
+        assert!(matches!(tax.fee_class, Some(FeeClass::Reflection { .. })));
+
+        // Above the honeypot cutoff
+        assert!(compute_tax(eth(100), eth(40), U256::ZERO, None, &cutoffs)
+            .unwrap()
+            .exceeds_honeypot_cutoff);
+    }
+
+    #[test]
+    fn test_differs_from_buy_block_sell() {
+        let outcome = |status, same_block_revert: Option<&str>| SellOutcome {
+            status,
+            dynamic_blacklist_detected: false,
+            same_block_revert: same_block_revert.map(str::to_string),
+            sell_lock_blocks: same_block_revert.map(|_| 3),
+            gas_used: 0,
+            balance_delta: None,
+            final_token_balance: None,
+            final_native_balance: None,
+        };
+        let sold = || SellStatus::Sold { native_received: eth(90), mocked: false };
+        let in_buy_block = outcome(sold(), None);
+        let delayed = outcome(sold(), Some("anti-bot"));
+        let reverted = outcome(SellStatus::Reverted("blocked".to_string()), None);
+
+        assert!(in_buy_block.sold_in_buy_block());
+        assert!(!delayed.sold_in_buy_block() && delayed.sold());
+
+        // Other path reverts in the buy block
+        assert!(in_buy_block.differs_from_buy_block_sell(false));
+        assert!(!delayed.differs_from_buy_block_sell(false)); // anti-bot / sell lock, not the path
+        assert!(!reverted.differs_from_buy_block_sell(false));
+        // Other path sells in the buy block
+        assert!(reverted.differs_from_buy_block_sell(true));
+        assert!(!delayed.differs_from_buy_block_sell(true));
+        assert!(!in_buy_block.differs_from_buy_block_sell(true));
+    }
+
+    #[test]
+    fn test_proceeds_received() {
+        let sold = |native_received, balance_delta| SellOutcome {
+            status: SellStatus::Sold { native_received, mocked: false },
+            dynamic_blacklist_detected: false,
+            same_block_revert: None,
+            sell_lock_blocks: None,
+            gas_used: 0,
+            balance_delta,
+            final_token_balance: None,
+            final_native_balance: None,
+        };
+        assert!(sold(eth(90), Some(eth(90))).proceeds_received());
+        assert!(sold(eth(90), None).proceeds_received());
+        // Router reports 90, the caller got nothing: output went elsewhere
+        assert!(!sold(eth(90), Some(U256::ZERO)).proceeds_received());
+        assert!(!sold(eth(90), Some(eth(45))).proceeds_received());
+
+        let reverted = SellOutcome { status: SellStatus::Reverted("x".to_string()), ..sold(eth(90), Some(U256::ZERO)) };
+        assert!(reverted.proceeds_received());
+    }
+
+    #[test]
+    fn test_declared_tax() {
+        assert_eq!(DeclaredTax::percent_from_raw(U256::from(5u64)), Some(5.0));
+        assert_eq!(DeclaredTax::percent_from_raw(U256::from(100u64)), Some(100.0));
+        // 500 could be basis points (5%) or per-mille (50%) - not trusted either way
+        assert_eq!(DeclaredTax::percent_from_raw(U256::from(500u64)), None);
+
+        let declared = DeclaredTax { buy_percent: Some(5.0), sell_percent: Some(5.0) };
+        assert!(!declared.understated(5.3, 6.5));
+        assert!(declared.understated(5.0, 25.0));
+        // Over-declaring is not deceptive
+        assert!(!declared.understated(0.0, 0.0));
+        // Nothing declared = nothing to compare
+        assert!(!DeclaredTax::default().understated(50.0, 50.0));
+    }
+
+    #[test]
+    fn test_compute_tax_separates_gas() {
+        let cutoffs = LossCutoffs::default();
+        // 1% tax on a tiny round trip whose gas costs another 20%
+        let tax = compute_tax(eth(10), eth(99) / U256::from(10u64), eth(2), None, &cutoffs).unwrap();
+        assert!((tax.total_loss_percent - 1.0).abs() < 1e-9);
+        assert!((tax.net_loss_percent - 21.0).abs() < 1e-9);
+        assert_eq!(tax.fee_class, None);
+        assert!(!tax.exceeds_honeypot_cutoff);
+
+        // Gas above the proceeds caps out at a full loss
+        let tax = compute_tax(eth(10), eth(9), eth(20), None, &cutoffs).unwrap();
+        assert_eq!(tax.net_loss_percent, 100.0);
+    }
+}
@@ -4,6 +4,7 @@
 //! This allows users to make informed decisions in "gray areas"
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Granular risk score (0-100)
 /// - 0-20: Safe (green light)
@@ -149,10 +150,136 @@ impl RiskScore {
     }
 }
 
+// ============================================
+// ENSEMBLE (simulation vs ML)
+// ============================================
+
+/// Weight of the simulation score in the blend
+pub const ENSEMBLE_SIM_WEIGHT: f32 = 0.6;
+/// Weight of the ML score in the blend
+pub const ENSEMBLE_ML_WEIGHT: f32 = 0.4;
+/// Score gap at which the two subsystems are reported as disagreeing
+pub const ENSEMBLE_DISAGREEMENT_THRESHOLD: u8 = 40;
+
+/// Subsystem that determined (or contributed to) the final score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ScoreSource {
+    Simulation,
+    Ml,
+    Blend,
+    /// Static bytecode findings (access-control penalty)
+    Static,
+}
+
+/// Reconciled simulation + ML verdict
+///
+/// Rules (in order):
+/// 1. Sim-confirmed honeypot always wins - ML cannot talk a reverted sell down
+/// 2. No ML score - simulation score as-is
+/// 3. Otherwise `max(sim, 0.6 * sim + 0.4 * ml)`: ML can raise the score, never lower it
+///
+/// The API reports `ScoreBlend::ensemble` instead, so `score` there is the blended `risk_score`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EnsembleVerdict {
+    /// Final score (0-100)
+    pub score: u8,
+    pub sim_score: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ml_score: Option<u8>,
+    /// Which subsystem dominated the final score
+    pub dominant: ScoreSource,
+    /// Sim and ML differ by >= ENSEMBLE_DISAGREEMENT_THRESHOLD points
+    pub disagreement: bool,
+    /// Rule that produced the score
+    pub rule: String,
+}
+
+// ============================================
+// CONFIDENCE-WEIGHTED BLEND (final score)
+// ============================================
+
+/// Least weight the simulation keeps, however low its confidence
+pub const BLEND_MIN_SIM_WEIGHT: f32 = 0.2;
+/// ML's part of the weight the simulation leaves when static findings also count (rest: static)
+pub const BLEND_ML_SHARE: f32 = 0.6;
+
+/// One subsystem's part of a `ScoreBlend`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ScoreContribution {
+    pub source: ScoreSource,
+    /// Subsystem score (0-100)
+    pub score: u8,
+    /// Normalized weight (the contributions sum to 1)
+    pub weight: f32,
+    /// `score * weight`: points this subsystem adds to the final score
+    pub points: f32,
+}
+
+/// Final score as a confidence-weighted blend of every signal that ran
+///
+/// The simulation is weighted by its confidence (at least `BLEND_MIN_SIM_WEIGHT`);
+/// the rest goes to ML and static findings (`BLEND_ML_SHARE` to ML when both count),
+/// and weights are renormalized over the signals present. A verified simulation
+/// dominates; a mocked or quote-only one leaves the score mostly to the others.
+/// A sim-confirmed honeypot never scores below the simulation, whatever its confidence
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ScoreBlend {
+    /// Final score (0-100)
+    pub score: u8,
+    /// Simulation confidence (0-100) the weights were derived from
+    pub sim_confidence: u8,
+    /// Sim-confirmed honeypot: `score` was held at the simulation score the weights pulled below
+    pub sim_floor: bool,
+    /// Simulation first, then ML and static findings when they count
+    pub contributions: Vec<ScoreContribution>,
+}
+
+impl ScoreBlend {
+    /// Contribution of `source`, if it counted
+    pub fn contribution(&self, source: ScoreSource) -> Option<&ScoreContribution> {
+        self.contributions.iter().find(|c| c.source == source)
+    }
+
+    /// Simulation vs ML view of this blend (same final score); None when ML did not run
+    pub fn ensemble(&self) -> Option<EnsembleVerdict> {
+        let sim = self.contribution(ScoreSource::Simulation)?;
+        let ml = self.contribution(ScoreSource::Ml)?;
+        let (dominant, rule) = if self.sim_floor {
+            (ScoreSource::Simulation, "Simulation confirmed honeypot - overrides ML".to_string())
+        } else {
+            let dominant = self
+                .contributions
+                .iter()
+                .max_by(|a, b| a.points.total_cmp(&b.points))
+                .map_or(ScoreSource::Simulation, |c| c.source);
+            (
+                dominant,
+                format!("Confidence-weighted blend {:.0}% sim / {:.0}% ML", sim.weight * 100.0, ml.weight * 100.0),
+            )
+        };
+
+        Some(EnsembleVerdict {
+            score: self.score,
+            sim_score: sim.score,
+            ml_score: Some(ml.score),
+            dominant,
+            disagreement: sim.score.abs_diff(ml.score) >= ENSEMBLE_DISAGREEMENT_THRESHOLD,
+            rule,
+        })
+    }
+}
+
 /// Builder for creating risk scores from analysis results
 pub struct RiskScoreBuilder {
     factors: Vec<ScoreFactor>,
     components: RiskComponents,
+    /// (score, sim-confirmed honeypot) for the ensemble
+    sim_verdict: Option<(u8, bool)>,
+    ml_score: Option<u8>,
+    /// Confidence of the simulation verdict for the blend (None = fully trusted)
+    sim_confidence: Option<u8>,
+    static_score: Option<u8>,
 }
 
 impl RiskScoreBuilder {
@@ -160,9 +287,141 @@ impl RiskScoreBuilder {
         Self {
             factors: Vec::new(),
             components: RiskComponents::default(),
+            sim_verdict: None,
+            ml_score: None,
+            sim_confidence: None,
+            static_score: None,
         }
     }
 
+    /// Simulation-derived score for the ensemble
+    pub fn with_sim_verdict(mut self, sim_score: u8, confirmed_honeypot: bool) -> Self {
+        self.sim_verdict = Some((sim_score.min(100), confirmed_honeypot));
+        self
+    }
+
+    /// ML scorer output for the ensemble
+    pub fn with_ml_score(mut self, ml_score: u8) -> Self {
+        self.ml_score = Some(ml_score.min(100));
+        self
+    }
+
+    /// Confidence (0-100) of the simulation verdict, weighting it in `build_blend`
+    pub fn with_sim_confidence(mut self, confidence: u8) -> Self {
+        self.sim_confidence = Some(confidence.min(100));
+        self
+    }
+
+    /// Static bytecode findings for the blend
+    pub fn with_static_score(mut self, static_score: u8) -> Self {
+        self.static_score = Some(static_score.min(100));
+        self
+    }
+
+    /// Confidence-weighted blend of simulation, ML and static signals (see `ScoreBlend`)
+    pub fn build_blend(&self) -> ScoreBlend {
+        let (sim_score, confirmed_honeypot) = self.sim_verdict.unwrap_or((self.components.honeypot, false));
+        let sim_confidence = self.sim_confidence.unwrap_or(100);
+        let sim_weight = (sim_confidence as f32 / 100.0).max(BLEND_MIN_SIM_WEIGHT);
+        let rest = 1.0 - sim_weight;
+        let (ml_weight, static_weight) = match (self.ml_score, self.static_score) {
+            (Some(_), Some(_)) => (rest * BLEND_ML_SHARE, rest * (1.0 - BLEND_ML_SHARE)),
+            _ => (rest, rest),
+        };
+
+        let mut contributions = vec![(ScoreSource::Simulation, sim_score, sim_weight)];
+        contributions.extend(self.ml_score.map(|ml| (ScoreSource::Ml, ml, ml_weight)));
+        contributions.extend(self.static_score.map(|s| (ScoreSource::Static, s, static_weight)));
+
+        // Renormalize: a simulation on its own carries the full weight
+        let total_weight: f32 = contributions.iter().map(|(_, _, weight)| weight).sum();
+        let contributions: Vec<ScoreContribution> = contributions
+            .into_iter()
+            .map(|(source, score, weight)| {
+                let weight = weight / total_weight;
+                ScoreContribution {
+                    source,
+                    score,
+                    weight,
+                    points: score as f32 * weight,
+                }
+            })
+            .collect();
+        let blended = (contributions.iter().map(|c| c.points).sum::<f32>().round() as u8).min(100);
+        // ML and static findings cannot talk a confirmed honeypot down
+        let sim_floor = confirmed_honeypot && blended < sim_score;
+
+        ScoreBlend {
+            score: if sim_floor { sim_score } else { blended },
+            sim_confidence,
+            sim_floor,
+            contributions,
+        }
+    }
+
+    /// Reconcile simulation and ML verdicts (see `EnsembleVerdict` for the rules)
+    pub fn build_ensemble(&self) -> EnsembleVerdict {
+        let (sim_score, confirmed_honeypot) = self
+            .sim_verdict
+            .unwrap_or((self.components.honeypot, false));
+        let disagreement = self
+            .ml_score
+            .is_some_and(|ml| sim_score.abs_diff(ml) >= ENSEMBLE_DISAGREEMENT_THRESHOLD);
+
+        let (score, dominant, rule) = match self.ml_score {
+            _ if confirmed_honeypot => (
+                sim_score,
+                ScoreSource::Simulation,
+                "Simulation confirmed honeypot - overrides ML".to_string(),
+            ),
+            None => (
+                sim_score,
+                ScoreSource::Simulation,
+                "No ML score - simulation only".to_string(),
+            ),
+            Some(ml) => {
+                let sim_part = sim_score as f32 * ENSEMBLE_SIM_WEIGHT;
+                let ml_part = ml as f32 * ENSEMBLE_ML_WEIGHT;
+                let blend = ((sim_part + ml_part).round() as u8).min(100);
+                if blend <= sim_score {
+                    (
+                        sim_score,
+                        ScoreSource::Simulation,
+                        "ML lower than simulation - simulation floor kept".to_string(),
+                    )
+                } else {
+                    let dominant = if ml_part > sim_part { ScoreSource::Ml } else { ScoreSource::Blend };
+                    (
+                        blend,
+                        dominant,
+                        format!("Weighted blend {:.0}% sim / {:.0}% ML", ENSEMBLE_SIM_WEIGHT * 100.0, ENSEMBLE_ML_WEIGHT * 100.0),
+                    )
+                }
+            }
+        };
+
+        EnsembleVerdict {
+            score,
+            sim_score,
+            ml_score: self.ml_score,
+            dominant,
+            disagreement,
+            rule,
+        }
+    }
+
+    /// Merge externally computed components (e.g. from a trace analysis)
+    /// Each component keeps the worse (higher) of the existing and merged score
+    pub fn with_components(mut self, components: &RiskComponents, factors: Vec<ScoreFactor>) -> Self {
+        self.components.honeypot = self.components.honeypot.max(components.honeypot);
+        self.components.tax = self.components.tax.max(components.tax);
+        self.components.liquidity = self.components.liquidity.max(components.liquidity);
+        self.components.contract = self.components.contract.max(components.contract);
+        self.components.mev_exposure = self.components.mev_exposure.max(components.mev_exposure);
+        self.factors.extend(factors);
+        self
+    }
+
     /// Add honeypot simulation result
     pub fn with_honeypot_result(
         mut self,
@@ -290,6 +549,7 @@ impl Default for RiskScoreBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ml_risk::RiskCategory;
 
     #[test]
     fn test_safe_token_score() {
@@ -326,4 +586,117 @@ mod tests {
 
         assert!(score.is_gray_area());
     }
+
+    #[test]
+    fn test_ensemble_rules() {
+        // Sim-confirmed honeypot wins even if ML says safe
+        let verdict = RiskScoreBuilder::new()
+            .with_sim_verdict(100, true)
+            .with_ml_score(5)
+            .build_ensemble();
+        assert_eq!(verdict.score, 100);
+        assert_eq!(verdict.dominant, ScoreSource::Simulation);
+        assert!(verdict.disagreement);
+
+        // Sim safe, ML critical: ML raises the score and the disagreement is surfaced
+        let verdict = RiskScoreBuilder::new()
+            .with_sim_verdict(10, false)
+            .with_ml_score(90)
+            .build_ensemble();
+        assert_eq!(verdict.score, 42);
+        assert_eq!(verdict.dominant, ScoreSource::Ml);
+        assert!(verdict.disagreement);
+
+        // ML lower than sim never lowers the score
+        let verdict = RiskScoreBuilder::new()
+            .with_sim_verdict(60, false)
+            .with_ml_score(20)
+            .build_ensemble();
+        assert_eq!(verdict.score, 60);
+        assert_eq!(verdict.dominant, ScoreSource::Simulation);
+
+        // No ML - simulation only
+        let verdict = RiskScoreBuilder::new().with_sim_verdict(30, false).build_ensemble();
+        assert_eq!(verdict.score, 30);
+        assert!(!verdict.disagreement);
+    }
+
+    #[test]
+    fn test_confidence_weighted_blend() {
+        // Verified simulation dominates: 0.9 * 100 + 0.06 * 5 + 0.04 * 40 = 92,
+        // held at 100 because the sell was confirmed a honeypot
+        let blend = RiskScoreBuilder::new()
+            .with_sim_verdict(100, true)
+            .with_sim_confidence(90)
+            .with_ml_score(5)
+            .with_static_score(40)
+            .build_blend();
+        assert_eq!(blend.score, 100);
+        assert!(blend.sim_floor);
+        assert_eq!(blend.contributions[0].source, ScoreSource::Simulation);
+        assert!((blend.contributions[0].weight - 0.9).abs() < 1e-4);
+        let total: f32 = blend.contributions.iter().map(|c| c.weight).sum();
+        assert!((total - 1.0).abs() < 1e-4);
+
+        // Mocked simulation (30%): ML and static findings carry most of the score
+        let blend = RiskScoreBuilder::new()
+            .with_sim_verdict(10, false)
+            .with_sim_confidence(30)
+            .with_ml_score(80)
+            .with_static_score(50)
+            .build_blend();
+        assert_eq!(blend.score, 51); // 3 + 0.42 * 80 + 0.28 * 50
+        assert!(!blend.sim_floor);
+        assert_eq!(blend.contributions[1].source, ScoreSource::Ml);
+        assert!((blend.contributions[1].points - 33.6).abs() < 1e-3);
+
+        // Zero confidence still keeps the simulation floor weight
+        let blend = RiskScoreBuilder::new()
+            .with_sim_verdict(70, false)
+            .with_sim_confidence(0)
+            .with_ml_score(20)
+            .build_blend();
+        assert_eq!(blend.score, 30); // 0.2 * 70 + 0.8 * 20
+
+        // Simulation alone keeps its score whatever the confidence
+        let blend = RiskScoreBuilder::new()
+            .with_sim_verdict(70, false)
+            .with_sim_confidence(0)
+            .build_blend();
+        assert_eq!(blend.score, 70);
+        assert_eq!(blend.contributions.len(), 1);
+        assert!((blend.contributions[0].weight - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_confirmed_honeypot_blend_stays_critical() {
+        // Reverted sell at any confidence, with ML and static findings calling it safe
+        for confidence in [0, 30, 60, 100] {
+            let blend = RiskScoreBuilder::new()
+                .with_sim_verdict(100, true)
+                .with_sim_confidence(confidence)
+                .with_ml_score(5)
+                .with_static_score(0)
+                .build_blend();
+            assert_eq!(blend.score, 100, "confidence {}", confidence);
+            assert_eq!(RiskCategory::from_score(blend.score as u32), RiskCategory::Critical);
+
+            let ensemble = blend.ensemble().unwrap();
+            assert_eq!(ensemble.score, blend.score);
+            assert_eq!(ensemble.dominant, ScoreSource::Simulation);
+        }
+
+        // Unconfirmed verdicts are still weighted: a mocked safe sim lets ML raise the score
+        let blend = RiskScoreBuilder::new()
+            .with_sim_verdict(10, false)
+            .with_sim_confidence(30)
+            .with_ml_score(90)
+            .build_blend();
+        assert_eq!(blend.score, 66); // 0.3 * 10 + 0.7 * 90
+        assert!(!blend.sim_floor);
+        let ensemble = blend.ensemble().unwrap();
+        assert_eq!((ensemble.score, ensemble.dominant), (66, ScoreSource::Ml));
+        assert!(ensemble.disagreement);
+        assert!(RiskScoreBuilder::new().with_sim_verdict(10, false).build_blend().ensemble().is_none());
+    }
 }
@@ -0,0 +1,187 @@
+//! REVM Sell Trace Inspector
+//! Records the opcode trace of a simulated sell and pinpoints where it reverted
+//!
+//! Tracing every step is expensive, so this is only attached when a caller
+//! explicitly asks for it (`?trace=true`). Only the tail of the trace is kept:
+//! the interesting part of a failed sell is what happened right before the revert.
+
+use alloy_primitives::Address;
+use revm::{
+    interpreter::{CallInputs, CallOutcome, Interpreter, OpCode},
+    Database, EvmContext, Inspector,
+};
+use serde::Serialize;
+use std::collections::VecDeque;
+use utoipa::ToSchema;
+
+/// Maximum number of trailing steps kept in the trace
+pub const MAX_TRACE_STEPS: usize = 256;
+
+/// REVERT opcode
+const OP_REVERT: u8 = 0xfd;
+/// INVALID opcode (assert failures in older solc)
+const OP_INVALID: u8 = 0xfe;
+
+/// A single interpreter step
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TraceStep {
+    /// Call depth (0 = router entry call)
+    pub depth: usize,
+    /// Program counter within the executing contract
+    pub pc: usize,
+    /// Opcode mnemonic (e.g. "SLOAD", "REVERT")
+    #[schema(value_type = String)]
+    pub opcode: &'static str,
+    /// Contract whose code is executing
+    pub address: String,
+    /// Gas remaining before this step
+    pub gas_remaining: u64,
+}
+
+/// The innermost call that failed during the sell
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FailedCall {
+    pub depth: usize,
+    pub caller: String,
+    pub target: String,
+    /// 4-byte function selector of the failed call (if any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    /// Instruction result (e.g. "Revert", "OutOfGas")
+    pub result: String,
+    /// Raw revert data (hex)
+    pub output: String,
+}
+
+/// Compact trace of a simulated sell
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct SellTrace {
+    /// Did the sell revert/halt?
+    pub reverted: bool,
+    /// Decoded revert reason (if reverted)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// Step that executed REVERT/INVALID first (the "line" that killed the sell)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_site: Option<TraceStep>,
+    /// Innermost failed call (usually the token's transfer logic)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_call: Option<FailedCall>,
+    /// Total steps executed
+    pub total_steps: u64,
+    /// True if older steps were dropped
+    pub truncated: bool,
+    /// Trailing steps (at most MAX_TRACE_STEPS)
+    pub steps: Vec<TraceStep>,
+}
+
+/// REVM inspector recording opcode steps and the failing call
+#[derive(Debug, Default)]
+pub struct SellTraceInspector {
+    steps: VecDeque<TraceStep>,
+    total_steps: u64,
+    revert_site: Option<TraceStep>,
+    failed_call: Option<FailedCall>,
+}
+
+impl SellTraceInspector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finish tracing and build the compact trace
+    pub fn into_trace(self, reverted: bool, revert_reason: Option<String>) -> SellTrace {
+        SellTrace {
+            reverted,
+            revert_reason,
+            revert_site: self.revert_site,
+            failed_call: self.failed_call,
+            total_steps: self.total_steps,
+            truncated: self.total_steps > self.steps.len() as u64,
+            steps: self.steps.into_iter().collect(),
+        }
+    }
+
+    fn record_step(&mut self, step: TraceStep, opcode: u8) {
+        if (opcode == OP_REVERT || opcode == OP_INVALID) && self.revert_site.is_none() {
+            self.revert_site = Some(step.clone());
+        }
+
+        if self.steps.len() == MAX_TRACE_STEPS {
+            self.steps.pop_front();
+        }
+        self.steps.push_back(step);
+        self.total_steps += 1;
+    }
+}
+
+fn format_address(address: Address) -> String {
+    format!("{:?}", address)
+}
+
+impl<DB: Database> Inspector<DB> for SellTraceInspector {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let opcode = interp.current_opcode();
+        let step = TraceStep {
+            depth: context.journaled_state.depth,
+            pc: interp.program_counter(),
+            opcode: OpCode::new(opcode).map(|op| op.as_str()).unwrap_or("UNKNOWN"),
+            address: format_address(interp.contract.target_address),
+            gas_remaining: interp.gas.remaining(),
+        };
+        self.record_step(step, opcode);
+    }
+
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        // Innermost call finishes first - keep only the first failure
+        if !outcome.result.result.is_ok() && self.failed_call.is_none() {
+            self.failed_call = Some(FailedCall {
+                depth: context.journaled_state.depth,
+                caller: format_address(inputs.caller),
+                target: format_address(inputs.target_address),
+                selector: (inputs.input.len() >= 4)
+                    .then(|| format!("0x{}", hex::encode(&inputs.input[..4]))),
+                result: format!("{:?}", outcome.result.result),
+                output: format!("0x{}", hex::encode(&outcome.result.output)),
+            });
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(pc: usize, opcode: &'static str) -> TraceStep {
+        TraceStep {
+            depth: 1,
+            pc,
+            opcode,
+            address: format_address(Address::ZERO),
+            gas_remaining: 1000,
+        }
+    }
+
+    #[test]
+    fn test_trace_keeps_tail_and_revert_site() {
+        let mut inspector = SellTraceInspector::new();
+        for pc in 0..(MAX_TRACE_STEPS + 10) {
+            inspector.record_step(step(pc, "PUSH1"), 0x60);
+        }
+        inspector.record_step(step(9999, "REVERT"), OP_REVERT);
+
+        let trace = inspector.into_trace(true, Some("Transfer blocked".to_string()));
+        assert!(trace.reverted);
+        assert!(trace.truncated);
+        assert_eq!(trace.steps.len(), MAX_TRACE_STEPS);
+        assert_eq!(trace.total_steps, MAX_TRACE_STEPS as u64 + 11);
+        assert_eq!(trace.steps.last().unwrap().opcode, "REVERT");
+        assert_eq!(trace.revert_site.unwrap().pc, 9999);
+    }
+}
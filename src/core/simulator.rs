@@ -4,19 +4,102 @@
 #![allow(dead_code)]
 
 use alloy_primitives::{Address, Bytes, B256, U256};
-use eyre::Result;
+use eyre::{eyre, Result};
 use revm::{
     db::{CacheDB, EmptyDB},
     primitives::{
-        AccountInfo, BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, Output,
+        AccountInfo, BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, Output,
         SpecId, TxEnv, TxKind,
     },
     Evm,
 };
 use std::collections::HashMap;
 
-use crate::models::types::{RiskFactor, SwapParams};
+use crate::models::types::{RiskFactor, SwapParams, TxFees};
+use crate::providers::websocket::PendingTransaction;
 use crate::utils::constants::wei_to_eth;
+use crate::utils::decoder::SwapDecoder;
+
+/// Base fee assumed until the first block header reports the live one (20 gwei)
+pub const SIM_BASE_FEE_WEI: u128 = 20_000_000_000;
+
+/// Gas of one sandwich leg (a V2 swap)
+pub const SANDWICH_LEG_GAS: u128 = 150_000;
+
+/// Tip the front-run bids over the victim's to land ahead of it (1 gwei)
+pub const SANDWICH_OUTBID_WEI: u128 = 1_000_000_000;
+
+/// Victim tip (wei) from which the swap is bidding for the next block (3 gwei)
+pub const URGENT_PRIORITY_FEE_WEI: u128 = 3_000_000_000;
+
+/// Victim tip (wei) below which the swap is in no hurry (0.5 gwei)
+pub const LOW_PRIORITY_FEE_WEI: u128 = 500_000_000;
+
+/// How hard a pending swap bids for inclusion (its tip over the base fee)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeUrgency {
+    Low,
+    Normal,
+    Urgent,
+}
+
+impl FeeUrgency {
+    pub fn from_priority_fee(priority_fee: u128) -> Self {
+        if priority_fee >= URGENT_PRIORITY_FEE_WEI {
+            FeeUrgency::Urgent
+        } else if priority_fee < LOW_PRIORITY_FEE_WEI {
+            FeeUrgency::Low
+        } else {
+            FeeUrgency::Normal
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeeUrgency::Low => "low",
+            FeeUrgency::Normal => "normal",
+            FeeUrgency::Urgent => "urgent",
+        }
+    }
+}
+
+/// What a sandwich around a pending swap would cost and earn, from its fee fields
+#[derive(Debug, Clone, PartialEq)]
+pub struct SandwichExposure {
+    pub urgency: FeeUrgency,
+    /// Victim's tip per gas at the base fee (wei)
+    pub priority_fee: u128,
+    /// Most the victim's slippage tolerance lets an attacker take (wei of the input)
+    pub max_extractable: U256,
+    /// Front-run outbidding the victim's tip plus a back-run at the base fee (wei)
+    pub attacker_gas_cost: U256,
+    /// `max_extractable - attacker_gas_cost`, zero when the sandwich does not pay
+    pub profit_window: U256,
+}
+
+impl SandwichExposure {
+    pub fn is_profitable(&self) -> bool {
+        !self.profit_window.is_zero()
+    }
+}
+
+/// Sandwich economics of a swap of `amount_in` tolerating `slippage_bps`, priced with the
+/// victim's fees at `base_fee`: the front-run must outbid the victim's tip, the back-run
+/// only pays the base fee
+pub fn sandwich_exposure(amount_in: U256, slippage_bps: u64, fees: &TxFees, base_fee: u128) -> SandwichExposure {
+    let priority_fee = fees.priority_fee(base_fee);
+    let max_extractable = amount_in.saturating_mul(U256::from(slippage_bps.min(10_000))) / U256::from(10_000u64);
+    let front_run_price = base_fee.saturating_add(priority_fee).saturating_add(SANDWICH_OUTBID_WEI);
+    let attacker_gas_cost = U256::from(SANDWICH_LEG_GAS) * (U256::from(front_run_price) + U256::from(base_fee));
+
+    SandwichExposure {
+        urgency: FeeUrgency::from_priority_fee(priority_fee),
+        priority_fee,
+        max_extractable,
+        attacker_gas_cost,
+        profit_window: max_extractable.saturating_sub(attacker_gas_cost),
+    }
+}
 
 /// Simulation result containing execution outcome and detected risks
 #[derive(Debug)]
@@ -47,15 +130,41 @@ impl BalanceChange {
 pub struct Simulator {
     /// Chain ID
     chain_id: u64,
+    /// EVM hardfork for this chain
+    spec_id: SpecId,
 }
 
 impl Simulator {
     /// Create a new simulator instance
     pub fn new(chain_id: u64) -> Self {
-        Self { chain_id }
+        Self {
+            chain_id,
+            spec_id: crate::models::config::chain_spec_id(chain_id),
+        }
+    }
+
+    /// Simulate a pending mempool transaction with its own fee fields (EIP-1559 caps or
+    /// legacy gas price) in a block at `base_fee`, the latest block's base fee
+    pub fn simulate_pending(&self, tx: &PendingTransaction, base_fee: u128) -> Result<SimulationResult> {
+        let quantity = |field: &str, hex: &str| {
+            U256::from_str_radix(hex.trim_start_matches("0x"), 16)
+                .map_err(|e| eyre!("Invalid {} {:?} in tx {}: {}", field, hex, tx.hash, e))
+        };
+        let from: Address = tx.from.parse().map_err(|e| eyre!("Invalid sender in tx {}: {}", tx.hash, e))?;
+        let to = match tx.to.as_deref() {
+            Some(to) => Some(to.parse::<Address>().map_err(|e| eyre!("Invalid recipient in tx {}: {}", tx.hash, e))?),
+            None => None,
+        };
+        let value = quantity("value", &tx.value)?;
+        let gas_limit = u64::try_from(quantity("gas", &tx.gas)?).map_err(|_| eyre!("Gas limit overflow in tx {}", tx.hash))?;
+        let nonce = u64::try_from(quantity("nonce", &tx.nonce)?).map_err(|_| eyre!("Nonce overflow in tx {}", tx.hash))?;
+        let input: Bytes = tx.input.parse().map_err(|e| eyre!("Invalid input in tx {}: {}", tx.hash, e))?;
+        let swap_params = SwapDecoder::decode(&input, value);
+
+        self.simulate(from, to, value, gas_limit, tx.fees(), base_fee, input, nonce, swap_params.as_ref())
     }
 
-    /// Simulate a transaction and return the result
+    /// Simulate a transaction in a block at `base_fee` and return the result
     #[allow(clippy::too_many_arguments)]
     pub fn simulate(
         &self,
@@ -63,7 +172,8 @@ impl Simulator {
         to: Option<Address>,
         value: U256,
         gas_limit: u64,
-        gas_price: u128,
+        fees: TxFees,
+        base_fee: u128,
         input: Bytes,
         nonce: u64,
         swap_params: Option<&SwapParams>,
@@ -98,10 +208,12 @@ impl Simulator {
             None => TxKind::Create,
         };
 
+        // EIP-1559: `gas_price` is the fee cap, the tip rides separately
         let tx_env = TxEnv {
             caller: from,
             gas_limit,
-            gas_price: U256::from(gas_price),
+            gas_price: U256::from(fees.max_fee_per_gas.or(fees.gas_price).unwrap_or(0)),
+            gas_priority_fee: fees.max_priority_fee_per_gas.map(U256::from),
             transact_to,
             value,
             data: input,
@@ -120,12 +232,14 @@ impl Simulator {
                     .unwrap_or(0),
             ),
             gas_limit: U256::from(30_000_000u64),
-            basefee: U256::from(20_000_000_000u64),
+            basefee: U256::from(base_fee),
             ..Default::default()
         };
 
         // Build combined environment
-        let cfg = CfgEnvWithHandlerCfg::new_with_spec_id(Default::default(), SpecId::CANCUN);
+        let mut cfg_env = CfgEnv::default();
+        cfg_env.chain_id = self.chain_id;
+        let cfg = CfgEnvWithHandlerCfg::new_with_spec_id(cfg_env, self.spec_id);
         let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, tx_env);
 
         // Create EVM instance and execute
@@ -173,7 +287,7 @@ impl Simulator {
 
         // Analyze swap parameters for slippage risk
         if let Some(params) = swap_params {
-            analyze_swap_risks(params, &mut risks);
+            analyze_swap_risks(params, &fees, base_fee, &mut risks);
         }
 
         // Check for large value transactions
@@ -223,7 +337,7 @@ impl Simulator {
 }
 
 /// Analyze swap parameters for potential risks (standalone function)
-fn analyze_swap_risks(params: &SwapParams, risks: &mut Vec<RiskFactor>) {
+fn analyze_swap_risks(params: &SwapParams, fees: &TxFees, base_fee: u128, risks: &mut Vec<RiskFactor>) {
     // Check for extremely low amount_out_min (high slippage tolerance)
     if !params.amount_in.is_zero() {
         let ratio = params
@@ -256,11 +370,15 @@ fn analyze_swap_risks(params: &SwapParams, risks: &mut Vec<RiskFactor>) {
             0
         };
 
-        if slippage_tolerance > 5 {
+        let exposure = sandwich_exposure(params.amount_in, slippage_tolerance * 100, fees, base_fee);
+        if slippage_tolerance > 5 && exposure.is_profitable() {
             risks.push(RiskFactor::SandwichTarget {
                 reason: format!(
-                    "Large swap ({:.2} ETH) with {}% slippage tolerance",
-                    value_eth, slippage_tolerance
+                    "Large swap ({:.2} ETH) with {}% slippage tolerance, {} urgency, ~{:.4} ETH profit window",
+                    value_eth,
+                    slippage_tolerance,
+                    exposure.urgency.as_str(),
+                    wei_to_eth(exposure.profit_window)
                 ),
             });
         }
@@ -291,4 +409,56 @@ mod tests {
         let one_eth = U256::from(1_000_000_000_000_000_000u128);
         assert!((wei_to_eth(one_eth) - 1.0).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_sandwich_exposure_from_fees() {
+        const GWEI: u128 = 1_000_000_000;
+        let one_eth = U256::from(1_000_000_000_000_000_000u128);
+
+        // 1559 tip capped by the fee cap: 20 base + 5 tip, but max 22
+        let fees = TxFees::eip1559(22 * GWEI, 5 * GWEI);
+        assert_eq!(fees.effective_gas_price(20 * GWEI), 22 * GWEI);
+        assert_eq!(fees.priority_fee(20 * GWEI), 2 * GWEI);
+        assert_eq!(TxFees::legacy(50 * GWEI).priority_fee(20 * GWEI), 30 * GWEI);
+
+        // 1 ETH at 5%: 0.05 ETH up for grabs, legs cost 150k * (23 + 20) gwei
+        let exposure = sandwich_exposure(one_eth, 500, &fees, 20 * GWEI);
+        assert_eq!(exposure.urgency, FeeUrgency::Normal);
+        assert_eq!(exposure.max_extractable, one_eth / U256::from(20u64));
+        assert_eq!(exposure.attacker_gas_cost, U256::from(150_000 * 43 * GWEI));
+        assert!(exposure.is_profitable());
+
+        // Urgent tip on a small, tight swap: outbidding it eats the whole window
+        let urgent = TxFees::eip1559(300 * GWEI, 200 * GWEI);
+        let exposure = sandwich_exposure(one_eth / U256::from(10u64), 100, &urgent, 20 * GWEI);
+        assert_eq!(exposure.urgency, FeeUrgency::Urgent);
+        assert!(!exposure.is_profitable());
+    }
+
+    #[test]
+    fn test_simulate_pending_at_base_fee() {
+        let tx: PendingTransaction = serde_json::from_value(serde_json::json!({
+            "hash": "0x01",
+            "from": "0x00000000000000000000000000000000000000aa",
+            "to": "0x00000000000000000000000000000000000000bb",
+            "value": "0x0",
+            "gas": "0x5208",
+            "maxFeePerGas": "0x2540be400", // 10 gwei
+            "maxPriorityFeePerGas": "0x3b9aca00", // 1 gwei
+            "input": "0x",
+            "nonce": "0x5",
+        }))
+        .unwrap();
+        let simulator = Simulator::new(1);
+
+        // A 10 gwei fee cap clears an 8 gwei block
+        let result = simulator.simulate_pending(&tx, 8_000_000_000).unwrap();
+        assert!(result.success);
+        assert_eq!(result.gas_used, 21_000);
+
+        // ...but not a 12 gwei one
+        let result = simulator.simulate_pending(&tx, 12_000_000_000).unwrap();
+        assert!(!result.success);
+        assert!(matches!(result.risks.as_slice(), [RiskFactor::SimulationFailed { .. }]));
+    }
 }
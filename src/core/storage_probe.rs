@@ -0,0 +1,218 @@
+//! REVM Storage Probe - Dynamic Blacklist Detection
+//! Watches storage reads during the simulated sell and correlates the revert
+//! with a flag read from a mapping keyed by the seller's address
+//!
+//! Solidity stores `mapping(address => X)` entries at `keccak256(key . slot)`.
+//! The probe records every KECCAK256 over 64 bytes whose first word is the
+//! seller, so a later SLOAD of that hash is known to be seller-keyed. A sell
+//! that reverts right after such an SLOAD returned a small non-zero value
+//! (a bool / packed flag, not a balance) is strong dynamic evidence of a
+//! blacklist - regardless of selectors, assembly or packed storage.
+
+use alloy_primitives::{Address, U256};
+use revm::{interpreter::Interpreter, Database, EvmContext, Inspector};
+use std::collections::HashSet;
+
+/// Maximum storage reads recorded (older reads are dropped)
+pub const MAX_RECORDED_SLOADS: usize = 512;
+
+/// A revert within this many steps of the flagged SLOAD counts as correlated
+pub const BLACKLIST_REVERT_WINDOW: u64 = 128;
+
+const OP_KECCAK256: u8 = 0x20;
+const OP_SLOAD: u8 = 0x54;
+const OP_REVERT: u8 = 0xfd;
+const OP_INVALID: u8 = 0xfe;
+
+/// One storage read observed during the sell
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageRead {
+    pub address: Address,
+    pub slot: U256,
+    pub value: U256,
+    /// Slot is a mapping entry keyed by the seller
+    pub caller_keyed: bool,
+}
+
+/// Opcode whose result is read back in `step_end`
+#[derive(Debug, Clone, Copy)]
+enum Pending {
+    None,
+    /// KECCAK256 over (seller, slot)
+    CallerHash,
+    /// SLOAD of this slot in this contract
+    Sload(Address, U256),
+}
+
+/// REVM inspector correlating seller-keyed storage reads with reverts
+#[derive(Debug)]
+pub struct StorageProbeInspector {
+    /// Seller (tx caller); None = use the transaction's caller
+    seller: Option<Address>,
+    caller_slots: HashSet<U256>,
+    reads: Vec<StorageRead>,
+    pending: Pending,
+    steps: u64,
+    /// Step + contract of the last seller-keyed SLOAD returning a flag
+    last_flag_read: Option<(u64, Address)>,
+    dynamic_blacklist: bool,
+}
+
+impl Default for StorageProbeInspector {
+    fn default() -> Self {
+        Self {
+            seller: None,
+            caller_slots: HashSet::new(),
+            reads: Vec::new(),
+            pending: Pending::None,
+            steps: 0,
+            last_flag_read: None,
+            dynamic_blacklist: false,
+        }
+    }
+}
+
+impl StorageProbeInspector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Did the sell revert right after reading a seller-keyed flag?
+    pub fn dynamic_blacklist_detected(&self) -> bool {
+        self.dynamic_blacklist
+    }
+
+    /// Storage reads observed during the sell (at most MAX_RECORDED_SLOADS)
+    pub fn reads(&self) -> &[StorageRead] {
+        &self.reads
+    }
+
+    /// Small non-zero values are flags (bool / packed bits), not balances
+    fn is_flag_value(value: U256) -> bool {
+        !value.is_zero() && value <= U256::from(u8::MAX)
+    }
+
+    /// Is this 64-byte KECCAK256 input `abi.encode(seller, slot)`?
+    fn is_seller_key(input: &[u8], seller: Address) -> bool {
+        input.len() == 64 && input[..12].iter().all(|b| *b == 0) && input[12..32] == seller[..]
+    }
+
+    fn record_hash(&mut self, hash: U256) {
+        self.caller_slots.insert(hash);
+    }
+
+    fn record_sload(&mut self, address: Address, slot: U256, value: U256) {
+        let caller_keyed = self.caller_slots.contains(&slot);
+        if caller_keyed && Self::is_flag_value(value) {
+            self.last_flag_read = Some((self.steps, address));
+        }
+        if self.reads.len() < MAX_RECORDED_SLOADS {
+            self.reads.push(StorageRead { address, slot, value, caller_keyed });
+        }
+    }
+
+    fn record_revert(&mut self, address: Address) {
+        if let Some((step, flag_address)) = self.last_flag_read {
+            if flag_address == address && self.steps.saturating_sub(step) <= BLACKLIST_REVERT_WINDOW {
+                self.dynamic_blacklist = true;
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for StorageProbeInspector {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.steps += 1;
+        let seller = *self.seller.get_or_insert(context.env.tx.caller);
+        let address = interp.contract.target_address;
+
+        match interp.current_opcode() {
+            OP_KECCAK256 => {
+                let (Ok(offset), Ok(size)) = (interp.stack.peek(0), interp.stack.peek(1)) else {
+                    return;
+                };
+                if size != U256::from(64u64) {
+                    return;
+                }
+                let offset = offset.saturating_to::<usize>();
+                if offset.saturating_add(64) > interp.shared_memory.len() {
+                    return;
+                }
+                if Self::is_seller_key(interp.shared_memory.slice(offset, 64), seller) {
+                    self.pending = Pending::CallerHash;
+                }
+            }
+            OP_SLOAD => {
+                if let Ok(slot) = interp.stack.peek(0) {
+                    self.pending = Pending::Sload(address, slot);
+                }
+            }
+            OP_REVERT | OP_INVALID => self.record_revert(address),
+            _ => {}
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let pending = std::mem::replace(&mut self.pending, Pending::None);
+        let Ok(top) = interp.stack.peek(0) else {
+            return;
+        };
+        match pending {
+            Pending::CallerHash => self.record_hash(top),
+            Pending::Sload(address, slot) => self.record_sload(address, slot, top),
+            Pending::None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seller_key(seller: Address, slot: u8) -> Vec<u8> {
+        let mut input = vec![0u8; 64];
+        input[12..32].copy_from_slice(seller.as_slice());
+        input[63] = slot;
+        input
+    }
+
+    #[test]
+    fn test_seller_key_detection() {
+        let seller = Address::repeat_byte(0xab);
+        assert!(StorageProbeInspector::is_seller_key(&seller_key(seller, 5), seller));
+        assert!(!StorageProbeInspector::is_seller_key(&seller_key(Address::repeat_byte(0x01), 5), seller));
+        assert!(!StorageProbeInspector::is_seller_key(&seller_key(seller, 5)[..32], seller));
+    }
+
+    #[test]
+    fn test_flag_read_then_revert_is_blacklist() {
+        let token = Address::repeat_byte(0x11);
+        let flag_slot = U256::from(42u64);
+        let balance_slot = U256::from(43u64);
+
+        // bots[seller] == true, then revert in the same contract
+        let mut probe = StorageProbeInspector::new();
+        probe.record_hash(flag_slot);
+        probe.steps = 10;
+        probe.record_sload(token, flag_slot, U256::from(1u64));
+        probe.steps = 20;
+        probe.record_revert(token);
+        assert!(probe.dynamic_blacklist_detected());
+        assert!(probe.reads()[0].caller_keyed);
+
+        // balances[seller] (large value) then revert: not a blacklist
+        let mut probe = StorageProbeInspector::new();
+        probe.record_hash(balance_slot);
+        probe.record_sload(token, balance_slot, U256::from(10u64).pow(U256::from(18u64)));
+        probe.record_revert(token);
+        assert!(!probe.dynamic_blacklist_detected());
+
+        // Flag read long before an unrelated revert: not correlated
+        let mut probe = StorageProbeInspector::new();
+        probe.record_hash(flag_slot);
+        probe.record_sload(token, flag_slot, U256::from(1u64));
+        probe.steps = BLACKLIST_REVERT_WINDOW + 1;
+        probe.record_revert(token);
+        assert!(!probe.dynamic_blacklist_detected());
+    }
+}
@@ -43,6 +43,7 @@ pub mod utils;
 
 // Core exports
 pub use core::analyzer::MempoolAnalyzer;
+pub use core::heuristics::{AnalysisContext, HoneypotHeuristic};
 pub use core::honeypot::{HoneypotDetector, HoneypotResult, TokenInfo};
 pub use core::risk_score::{RiskComponents, RiskScore, RiskScoreBuilder};
 pub use core::simulator::Simulator;
@@ -60,4 +61,6 @@ pub use providers::rpc::{AlchemyNetwork, RpcManager, RpcProvider};
 pub use utils::cache::{CacheStats, HoneypotCache};
 pub use utils::constants::*;
 pub use utils::decoder::SwapDecoder;
-pub use utils::telemetry::{TelemetryCollector, TelemetryEvent, TelemetryStats, ThreatType};
+pub use utils::telemetry::{
+    ReportData, TelemetryCollector, TelemetryEvent, TelemetryStats, ThreatType,
+};
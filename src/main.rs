@@ -9,6 +9,7 @@
 //! CEO Directive: Uses new modular architecture
 
 // Import from library (new structure)
+use ruster_revm::providers::AlchemyPricesClient;
 use ruster_revm::{MempoolAnalyzer, SentryConfig, TelemetryCollector};
 
 use eyre::Result;
@@ -61,9 +62,13 @@ async fn main() -> Result<()> {
     // Load configuration
     let config = SentryConfig::default();
 
-    // Initialize telemetry collector
-    let telemetry = Arc::new(TelemetryCollector::new());
-    println!("📊 Telemetry initialized. Data will be exported to ./telemetry/");
+    // Initialize telemetry collector (periodic export so a crash doesn't lose everything)
+    let telemetry = Arc::new(TelemetryCollector::from_sentry_config(&config));
+    println!("📊 Telemetry initialized. Data will be exported to {}/", config.telemetry_export_dir);
+    if config.telemetry_export_interval_secs > 0 {
+        telemetry.start_periodic_export(std::time::Duration::from_secs(config.telemetry_export_interval_secs));
+        println!("   Periodic export every {}s", config.telemetry_export_interval_secs);
+    }
 
     // Create and run analyzer
     let analyzer = MempoolAnalyzer::new(config, telemetry.clone());
@@ -92,10 +97,18 @@ async fn main() -> Result<()> {
             println!("\n📈 Exporting telemetry data...");
 
             // Generate marketing report (assume $2500/ETH for demo)
+            // Non-ETH chains are priced in their own native token when Alchemy is configured
             let eth_price = 2500.0;
-            println!("{}", telemetry.generate_marketing_report(eth_price));
+            let native_prices = match AlchemyPricesClient::new() {
+                Ok(client) => client.get_native_prices(&telemetry.chains_with_value()).await,
+                Err(_) => std::collections::HashMap::new(),
+            };
+            println!("{}", telemetry.generate_marketing_report_priced(eth_price, native_prices));
 
             // Export to files
+            if let Err(e) = telemetry.flush().await {
+                println!("   ❌ Event flush failed: {}", e);
+            }
             match telemetry.export_stats_json() {
                 Ok(path) => println!("   ✅ JSON exported to: {}", path.display()),
                 Err(e) => println!("   ❌ JSON export failed: {}", e),
@@ -4,16 +4,28 @@
 //! No hardcoded addresses or chain IDs in this file!
 
 use alloy_primitives::Address;
+use revm::primitives::SpecId;
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::time::Duration;
 use tracing::info;
 
+use crate::core::honeypot::{
+    LossCutoffs, DEFAULT_MAX_BYTECODE_SIZE, DEFAULT_MAX_RISK_FACTORS, DEFAULT_MIN_CONFIDENCE,
+    DEFAULT_SELL_LOCK_MAX_BLOCKS, DEFAULT_TEST_ACCOUNT_BALANCE_ETH,
+};
+use crate::utils::cache::DEFAULT_HONEYPOT_TTL_SECS;
+use crate::utils::chains::parse_chain;
+use crate::utils::failure_log::DEFAULT_FAILURE_LOG_SIZE;
+use crate::models::types::RiskLevel;
+use crate::providers::holders::DEFAULT_HOLDER_SCAN_MAX_BLOCKS;
+use crate::providers::rpc::DEFAULT_MAX_CONCURRENT_CALLS;
+use crate::utils::alerts::DEFAULT_ALERT_COOLDOWN_SECS;
 use crate::utils::constants::{
-    build_alchemy_url, get_chain_name, get_dex_routers, get_native_symbol,
-    get_public_rpc_fallback, get_weth_address, CHAIN_ID_ARBITRUM, CHAIN_ID_AVALANCHE,
+    build_alchemy_url, get_canonical_stablecoins, get_chain_name, get_dex_factories, get_dex_routers, get_native_symbol,
+    get_public_rpc_fallback, get_public_rpc_fallbacks, get_weth_address, CHAIN_ID_ARBITRUM, CHAIN_ID_AVALANCHE,
     CHAIN_ID_BASE, CHAIN_ID_BSC, CHAIN_ID_ETHEREUM, CHAIN_ID_OPTIMISM, CHAIN_ID_POLYGON,
-    SUPPORTED_CHAIN_IDS,
+    DEFAULT_CACHE_TTL_SECS, SUPPORTED_CHAIN_IDS,
 };
 
 /// Supported blockchain networks
@@ -70,6 +82,19 @@ pub struct ChainConfig {
     pub weth: Address,
     pub routers: Vec<DexRouter>,
     pub rpc_url: String,
+    /// Minimum DexScreener liquidity (USD) required before simulating.
+    /// `None` = gate disabled (opt-in via env)
+    pub min_liquidity_usd: Option<f64>,
+    /// Known-safe tokens (wrapped native + stablecoins) returned without simulation
+    pub canonical_tokens: HashSet<Address>,
+    /// Routers / factories (address → label): submitting one as a token is rejected
+    pub infrastructure: HashMap<Address, String>,
+    /// EVM hardfork the REVM simulation runs with (override: e.g. BSC_SPEC_ID=SHANGHAI)
+    pub spec_id: SpecId,
+    /// Honeypot / high-tax loss cutoffs (override: e.g. BSC_HONEYPOT_LOSS_PERCENT=70)
+    pub loss_cutoffs: LossCutoffs,
+    /// Native amount simulated when a request omits one (override: e.g. BSC_TEST_AMOUNT=1)
+    pub default_test_amount: f64,
 }
 
 impl ChainConfig {
@@ -96,6 +121,7 @@ impl ChainConfig {
     pub fn all_chains() -> HashMap<u64, ChainConfig> {
         let mut chains = HashMap::new();
         let alchemy_key = Self::get_alchemy_key();
+        let dex_routers = DexRouters::default();
 
         for &chain_id in &SUPPORTED_CHAIN_IDS {
             // Get WETH address from constants
@@ -104,35 +130,37 @@ impl ChainConfig {
                 None => continue,
             };
 
-            // Get routers from constants
-            let routers: Vec<DexRouter> = get_dex_routers(chain_id)
-                .into_iter()
-                .filter_map(|r| {
-                    Address::from_str(r.address).ok().map(|addr| DexRouter {
-                        name: r.name.to_string(),
-                        address: addr,
-                    })
-                })
-                .collect();
+            // Known routers (constants + {PREFIX}_KNOWN_ROUTERS), tried in order
+            let routers = dex_routers.known_routers(chain_id).to_vec();
 
-            // Build RPC URL
-            let env_key = match chain_id {
-                CHAIN_ID_ETHEREUM => "ETH_HTTP_URL",
-                CHAIN_ID_BSC => "BSC_HTTP_URL",
-                CHAIN_ID_POLYGON => "POLYGON_HTTP_URL",
-                CHAIN_ID_ARBITRUM => "ARBITRUM_HTTP_URL",
-                CHAIN_ID_OPTIMISM => "OPTIMISM_HTTP_URL",
-                CHAIN_ID_AVALANCHE => "AVALANCHE_HTTP_URL",
-                CHAIN_ID_BASE => "BASE_HTTP_URL",
-                _ => "",
-            };
+            // Build RPC URL (ETH_HTTP_URL, BSC_HTTP_URL, ...)
+            let prefix = chain_env_prefix(chain_id);
 
-            let rpc_url = std::env::var(env_key)
+            let rpc_url = std::env::var(format!("{}_HTTP_URL", prefix))
                 .ok()
                 .or_else(|| alchemy_key.as_ref().and_then(|k| build_alchemy_url(chain_id, k)))
                 .or_else(|| get_public_rpc_fallback(chain_id).map(String::from))
                 .unwrap_or_default();
 
+            // Min-liquidity gate: per-chain override, then global default
+            let min_liquidity_usd = Self::min_liquidity_from_env(
+                &format!("{}_MIN_LIQUIDITY_USD", prefix),
+            );
+
+            // Canonical tokens: wrapped native + stablecoins, plus e.g. ETH_CANONICAL_TOKENS
+            let mut canonical_tokens: HashSet<Address> = get_canonical_stablecoins(chain_id)
+                .iter()
+                .filter_map(|a| Address::from_str(a).ok())
+                .collect();
+            canonical_tokens.insert(weth);
+            canonical_tokens.extend(
+                env_list(&format!("{}_CANONICAL_TOKENS", prefix), &[])
+                    .iter()
+                    .filter_map(|a| Address::from_str(a).ok()),
+            );
+
+            let infrastructure = Self::infrastructure_for(chain_id, &routers, &dex_routers, prefix);
+
             chains.insert(
                 chain_id,
                 ChainConfig {
@@ -142,6 +170,12 @@ impl ChainConfig {
                     weth,
                     routers,
                     rpc_url,
+                    min_liquidity_usd,
+                    canonical_tokens,
+                    infrastructure,
+                    spec_id: chain_spec_id(chain_id),
+                    loss_cutoffs: chain_loss_cutoffs(chain_id),
+                    default_test_amount: chain_test_amount(chain_id),
                 },
             );
         }
@@ -149,6 +183,49 @@ impl ChainConfig {
         chains
     }
 
+    /// Routers (incl. the Ethereum mempool set), factories from constants, then extras
+    /// from e.g. ETH_INFRASTRUCTURE_ADDRESSES (`Name=0x...` or bare `0x...`, comma-separated)
+    fn infrastructure_for(
+        chain_id: u64,
+        routers: &[DexRouter],
+        dex_routers: &DexRouters,
+        prefix: &str,
+    ) -> HashMap<Address, String> {
+        let mut infrastructure: HashMap<Address, String> = HashMap::new();
+        for router in routers {
+            infrastructure.entry(router.address).or_insert_with(|| format!("{} router", router.name));
+        }
+        if chain_id == CHAIN_ID_ETHEREUM {
+            for address in &dex_routers.addresses {
+                infrastructure.entry(*address).or_insert_with(|| "DEX router".to_string());
+            }
+        }
+        for factory in get_dex_factories(chain_id) {
+            if let Ok(address) = Address::from_str(factory.address) {
+                infrastructure.entry(address).or_insert_with(|| format!("{} factory", factory.name));
+            }
+        }
+        for entry in env_list(&format!("{}_INFRASTRUCTURE_ADDRESSES", prefix), &[]) {
+            let (label, address) = match entry.split_once('=') {
+                Some((name, address)) => (name.trim().to_string(), address.trim().to_string()),
+                None => ("Configured infrastructure".to_string(), entry),
+            };
+            if let Ok(address) = Address::from_str(&address) {
+                infrastructure.insert(address, label);
+            }
+        }
+        infrastructure
+    }
+
+    /// Read min-liquidity threshold (e.g. ETH_MIN_LIQUIDITY_USD, falls back to MIN_LIQUIDITY_USD)
+    fn min_liquidity_from_env(chain_var: &str) -> Option<f64> {
+        std::env::var(chain_var)
+            .or_else(|_| std::env::var("MIN_LIQUIDITY_USD"))
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|v| *v > 0.0)
+    }
+
     /// Get config for specific chain
     pub fn get(chain_id: u64) -> Option<ChainConfig> {
         Self::all_chains().remove(&chain_id)
@@ -163,11 +240,47 @@ impl ChainConfig {
     pub fn primary_router(&self) -> Address {
         self.routers.first().map(|r| r.address).unwrap_or_default()
     }
+
+    /// Is this a known-safe canonical token on this chain?
+    pub fn is_canonical(&self, token: &Address) -> bool {
+        self.canonical_tokens.contains(token)
+    }
+
+    /// Label of a router / factory at `address` on this chain ("Uniswap V2 router")
+    pub fn infrastructure_label(&self, address: &Address) -> Option<&str> {
+        self.infrastructure.get(address).map(|label| label.as_str())
+    }
+
+    /// Chain and label of the router / factory at `address` (`chain_id` 0 = search all chains)
+    pub fn infrastructure_chain(address: &Address, chain_id: u64) -> Option<(u64, String)> {
+        let chains = Self::all_chains();
+        SUPPORTED_CHAIN_IDS
+            .iter()
+            .copied()
+            .filter(|id| chain_id == 0 || *id == chain_id)
+            .find_map(|id| {
+                let label = chains.get(&id)?.infrastructure_label(address)?;
+                Some((id, label.to_string()))
+            })
+    }
+
+    /// Chain on which `token` is canonical (`chain_id` 0 = search all chains)
+    pub fn canonical_chain(token: &Address, chain_id: u64) -> Option<u64> {
+        let chains = Self::all_chains();
+        SUPPORTED_CHAIN_IDS
+            .iter()
+            .copied()
+            .filter(|id| chain_id == 0 || *id == chain_id)
+            .find(|id| chains.get(id).is_some_and(|c| c.is_canonical(token)))
+    }
 }
 
-/// Known DEX Router addresses on Ethereum Mainnet
+/// Known DEX Router addresses
+/// `addresses`: Ethereum mainnet routers (mempool filter)
+/// `by_chain`: ordered V2 routers per chain, tried when DexScreener supplies none
 pub struct DexRouters {
     pub addresses: HashSet<Address>,
+    pub by_chain: HashMap<u64, Vec<DexRouter>>,
 }
 
 impl Default for DexRouters {
@@ -188,6 +301,13 @@ impl Default for DexRouters {
         if let Ok(addr) = Address::from_str("0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45") {
             addresses.insert(addr);
         }
+        // Uniswap Universal Router (old + current deployment, command-encoded swaps)
+        if let Ok(addr) = Address::from_str("0xEf1c6E67703c7BD7107eed8303Fbe6EC2554BF6B") {
+            addresses.insert(addr);
+        }
+        if let Ok(addr) = Address::from_str("0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD") {
+            addresses.insert(addr);
+        }
         // 1inch Router V5
         if let Ok(addr) = Address::from_str("0x1111111254EEB25477B68fb85Ed929f73A960582") {
             addresses.insert(addr);
@@ -197,7 +317,12 @@ impl Default for DexRouters {
             addresses.insert(addr);
         }
 
-        Self { addresses }
+        let by_chain = SUPPORTED_CHAIN_IDS
+            .iter()
+            .map(|&chain_id| (chain_id, Self::known_routers_for(chain_id)))
+            .collect();
+
+        Self { addresses, by_chain }
     }
 }
 
@@ -207,6 +332,236 @@ impl DexRouters {
     pub fn is_dex_router(&self, address: &Address) -> bool {
         self.addresses.contains(address)
     }
+
+    /// Known routers for a chain, in the order the detector should try them
+    pub fn known_routers(&self, chain_id: u64) -> &[DexRouter] {
+        self.by_chain.get(&chain_id).map(|r| r.as_slice()).unwrap_or(&[])
+    }
+
+    /// Built-in routers from constants, then extras from e.g. ETH_KNOWN_ROUTERS
+    /// (`Name=0x...` entries, comma-separated; duplicates ignored)
+    fn known_routers_for(chain_id: u64) -> Vec<DexRouter> {
+        let built_in = get_dex_routers(chain_id)
+            .into_iter()
+            .map(|r| (r.name.to_string(), r.address.to_string()));
+        let extra = env_list(&format!("{}_KNOWN_ROUTERS", chain_env_prefix(chain_id)), &[])
+            .into_iter()
+            .map(|entry| match entry.split_once('=') {
+                Some((name, address)) => (name.trim().to_string(), address.trim().to_string()),
+                None => ("Custom".to_string(), entry),
+            });
+
+        Self::dedup_routers(built_in.chain(extra))
+    }
+
+    /// Parse (name, address) pairs, keeping the first occurrence of each address
+    fn dedup_routers(entries: impl Iterator<Item = (String, String)>) -> Vec<DexRouter> {
+        let mut seen = HashSet::new();
+        entries
+            .filter_map(|(name, address)| {
+                Address::from_str(&address).ok().map(|address| DexRouter { name, address })
+            })
+            .filter(|r| seen.insert(r.address))
+            .collect()
+    }
+}
+
+/// Env var prefix for per-chain settings (ETH, BSC, POLYGON, ...)
+pub fn chain_env_prefix(chain_id: u64) -> &'static str {
+    match chain_id {
+        CHAIN_ID_ETHEREUM => "ETH",
+        CHAIN_ID_BSC => "BSC",
+        CHAIN_ID_POLYGON => "POLYGON",
+        CHAIN_ID_ARBITRUM => "ARBITRUM",
+        CHAIN_ID_OPTIMISM => "OPTIMISM",
+        CHAIN_ID_AVALANCHE => "AVALANCHE",
+        CHAIN_ID_BASE => "BASE",
+        _ => "",
+    }
+}
+
+/// EVM hardfork for a chain's REVM simulation: `{PREFIX}_SPEC_ID` env override,
+/// else the REVM spec closest to the chain's live fork. Ethereum's Fusaka (Osaka) has no
+/// spec in this REVM, so it runs as Prague. Unknown chains fall back to Shanghai
+/// (no transient storage / MCOPY / blob opcodes)
+pub fn chain_spec_id(chain_id: u64) -> SpecId {
+    if let Some(spec) = std::env::var(format!("{}_SPEC_ID", chain_env_prefix(chain_id)))
+        .ok()
+        .and_then(|v| parse_spec_id(&v))
+    {
+        return spec;
+    }
+    match chain_id {
+        CHAIN_ID_ETHEREUM => SpecId::PRAGUE,  // Pectra (Fusaka has no REVM spec yet)
+        CHAIN_ID_BSC => SpecId::PRAGUE,       // Pascal
+        CHAIN_ID_POLYGON => SpecId::PRAGUE,   // Bhilai
+        CHAIN_ID_ARBITRUM => SpecId::PRAGUE,  // ArbOS 40 Callisto
+        CHAIN_ID_OPTIMISM => SpecId::PRAGUE,  // Isthmus
+        CHAIN_ID_BASE => SpecId::PRAGUE,      // Isthmus
+        CHAIN_ID_AVALANCHE => SpecId::CANCUN, // Etna (Cancun without blobs)
+        _ => SpecId::SHANGHAI,
+    }
+}
+
+/// Public RPC fallback pool for a chain as (url, weight): `{PREFIX}_PUBLIC_RPC_URLS`
+/// (comma-separated, `url|weight` to weight an entry, default weight 1), else the
+/// built-in pool from constants. Unparseable weights fall back to 1, zero drops the entry
+pub fn public_rpc_pool(chain_id: u64) -> Vec<(String, u32)> {
+    let configured = env_list(&format!("{}_PUBLIC_RPC_URLS", chain_env_prefix(chain_id)), &[]);
+    if configured.is_empty() {
+        return get_public_rpc_fallbacks(chain_id)
+            .iter()
+            .map(|url| (url.to_string(), 1))
+            .collect();
+    }
+    configured
+        .iter()
+        .map(|entry| parse_weighted_url(entry))
+        .filter(|(url, weight)| !url.is_empty() && *weight > 0)
+        .collect()
+}
+
+/// Stands for the Alchemy URL (one per key) in `{PREFIX}_RPC_ENDPOINTS`
+pub const ALCHEMY_ENDPOINT: &str = "alchemy";
+
+/// How a configured RPC endpoint takes part in the try order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcRole {
+    /// Tried in configured order with the full retry policy
+    Primary,
+    /// Primaries that share load: each call starts at the next one in the group
+    Balanced,
+    /// Short retry policy, tried after the primaries (before the public pool)
+    Fallback,
+}
+
+/// Ordered RPC endpoints for a chain as (url, role): `{PREFIX}_RPC_ENDPOINTS`
+/// (comma-separated `url|role`, role `primary` (default), `balanced` or `fallback`).
+/// `alchemy` stands for the Alchemy URL of every configured key. Unset = Alchemy only,
+/// e.g. `ETH_RPC_ENDPOINTS=https://node.internal:8545,alchemy|fallback`
+pub fn rpc_endpoints(chain_id: u64) -> Vec<(String, RpcRole)> {
+    env_list(&format!("{}_RPC_ENDPOINTS", chain_env_prefix(chain_id)), &[ALCHEMY_ENDPOINT])
+        .iter()
+        .map(|entry| parse_rpc_endpoint(entry))
+        .filter(|(url, _)| !url.is_empty())
+        .collect()
+}
+
+/// In-flight limit of `RpcProvider::concurrent_calls`: `RPC_MAX_CONCURRENT_CALLS`
+/// (default `DEFAULT_MAX_CONCURRENT_CALLS`, floored at 1)
+pub fn rpc_max_concurrent_calls() -> usize {
+    env_u64("RPC_MAX_CONCURRENT_CALLS", DEFAULT_MAX_CONCURRENT_CALLS as u64).max(1) as usize
+}
+
+/// Hosts whose RPC TLS certificates are not validated. Empty unless `RPC_ALLOW_INSECURE_TLS=true`,
+/// and then only the operator's own nodes: the hosts in every chain's `{PREFIX}_RPC_ENDPOINTS`
+/// and `{PREFIX}_HTTP_URL` - never Alchemy (the API key rides in the URL) or the public pool.
+/// INSECURE - calls to these hosts are open to man-in-the-middle attacks; only for local /
+/// private archive nodes behind a self-signed certificate
+pub fn rpc_insecure_tls_hosts() -> Vec<String> {
+    if !env_flag("RPC_ALLOW_INSECURE_TLS") {
+        return Vec::new();
+    }
+    let mut hosts: Vec<String> = SUPPORTED_CHAIN_IDS
+        .iter()
+        .flat_map(|&chain_id| {
+            let own_url = std::env::var(format!("{}_HTTP_URL", chain_env_prefix(chain_id))).ok();
+            rpc_endpoints(chain_id).into_iter().map(|(url, _)| url).chain(own_url)
+        })
+        .filter_map(|url| url_host(&url))
+        .filter(|host| !host.ends_with("alchemy.com"))
+        .collect();
+    hosts.sort();
+    hosts.dedup();
+    hosts
+}
+
+/// Lowercased host of `url` (None for `alchemy` and other non-URLs)
+pub fn url_host(url: &str) -> Option<String> {
+    reqwest::Url::parse(url).ok()?.host_str().map(str::to_lowercase)
+}
+
+/// "url|role" → (url, role); a bare url (or an unknown role) is a primary
+fn parse_rpc_endpoint(entry: &str) -> (String, RpcRole) {
+    let (url, role) = entry.rsplit_once('|').unwrap_or((entry, "primary"));
+    let role = match role.trim().to_lowercase().as_str() {
+        "balanced" => RpcRole::Balanced,
+        "fallback" => RpcRole::Fallback,
+        _ => RpcRole::Primary,
+    };
+    (url.trim().to_string(), role)
+}
+
+/// "url|weight" → (url, weight); a bare url has weight 1
+fn parse_weighted_url(entry: &str) -> (String, u32) {
+    match entry.rsplit_once('|') {
+        Some((url, weight)) => (url.trim().to_string(), weight.trim().parse().unwrap_or(1)),
+        None => (entry.trim().to_string(), 1),
+    }
+}
+
+/// Honeypot / high-tax cutoffs for a chain: `{PREFIX}_HONEYPOT_LOSS_PERCENT` and
+/// `{PREFIX}_HIGH_TAX_PERCENT`, then the global vars, then the chain default.
+/// BSC meme tokens routinely tax 10-15% per side, so BSC tolerates more loss
+pub fn chain_loss_cutoffs(chain_id: u64) -> LossCutoffs {
+    let defaults = match chain_id {
+        CHAIN_ID_BSC => LossCutoffs {
+            honeypot_loss_percent: 70.0,
+            high_tax_percent: 20.0,
+        },
+        _ => LossCutoffs::default(),
+    };
+    let prefix = chain_env_prefix(chain_id);
+    LossCutoffs {
+        honeypot_loss_percent: env_percent(&format!("{}_HONEYPOT_LOSS_PERCENT", prefix), "HONEYPOT_LOSS_PERCENT")
+            .unwrap_or(defaults.honeypot_loss_percent),
+        high_tax_percent: env_percent(&format!("{}_HIGH_TAX_PERCENT", prefix), "HIGH_TAX_PERCENT")
+            .unwrap_or(defaults.high_tax_percent),
+    }
+}
+
+/// Fallback test amount (native units) for chains without a default
+pub const DEFAULT_TEST_AMOUNT_NATIVE: f64 = 0.1;
+
+/// Native amount a simulation buys with when the request omits one:
+/// `{PREFIX}_TEST_AMOUNT`, else a chain default worth roughly the same in USD
+/// (0.1 ETH buys far more than 0.1 MATIC). Non-positive values are ignored
+pub fn chain_test_amount(chain_id: u64) -> f64 {
+    if let Some(amount) = std::env::var(format!("{}_TEST_AMOUNT", chain_env_prefix(chain_id)))
+        .ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite() && *v > 0.0)
+    {
+        return amount;
+    }
+    match chain_id {
+        CHAIN_ID_BSC => 0.5,
+        CHAIN_ID_POLYGON => 500.0,
+        CHAIN_ID_AVALANCHE => 10.0,
+        _ => DEFAULT_TEST_AMOUNT_NATIVE,
+    }
+}
+
+/// Read a percentage in (0, 100] from the chain var, falling back to the global one
+fn env_percent(chain_var: &str, global_var: &str) -> Option<f64> {
+    std::env::var(chain_var)
+        .or_else(|_| std::env::var(global_var))
+        .ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| *v > 0.0 && *v <= 100.0)
+}
+
+/// Parse a hardfork name ("LONDON", "merge", "Shanghai", "CANCUN", ...)
+fn parse_spec_id(name: &str) -> Option<SpecId> {
+    match name.trim().to_uppercase().as_str() {
+        "BERLIN" => Some(SpecId::BERLIN),
+        "LONDON" => Some(SpecId::LONDON),
+        "MERGE" | "PARIS" => Some(SpecId::MERGE),
+        "SHANGHAI" => Some(SpecId::SHANGHAI),
+        "CANCUN" => Some(SpecId::CANCUN),
+        "PRAGUE" => Some(SpecId::PRAGUE),
+        _ => None,
+    }
 }
 
 /// Configuration for the Mempool Sentry
@@ -215,6 +570,8 @@ pub struct SentryConfig {
     pub wss_url: String,
     /// HTTP RPC URL for state fetching
     pub http_url: String,
+    /// EVM hardfork for mempool simulations (Ethereum, `ETH_SPEC_ID`)
+    pub spec_id: SpecId,
     /// Maximum concurrent transaction processing
     pub max_concurrent_tasks: usize,
     /// Timeout for RPC calls
@@ -225,6 +582,97 @@ pub struct SentryConfig {
     pub slippage_threshold_bps: u64,
     /// High tax threshold (in basis points)
     pub high_tax_threshold_bps: u64,
+    /// Hard deadline for a single API request (504 when exceeded)
+    pub request_timeout: Duration,
+    /// Cache TTL for safe / high-tax verdicts (short - revalidate often)
+    pub cache_safe_ttl_secs: u64,
+    /// Cache TTL for honeypot / sell-reverted verdicts (long - rarely change)
+    pub cache_honeypot_ttl_secs: u64,
+    /// Maximum tokens accepted in one batch request
+    pub batch_max_tokens: usize,
+    /// Default (and maximum) analysis timeout per batch token
+    pub batch_token_timeout: Duration,
+    /// Largest token bytecode accepted from RPC (bytes)
+    pub max_bytecode_size: usize,
+    /// Longest lock-on-buy (blocks) binary-searched when a sell keeps reverting; 0 disables the search
+    pub sell_lock_max_blocks: u64,
+    /// Native balance (whole ETH / BNB / ...) funded to the simulated test account
+    pub test_account_balance_eth: u64,
+    /// Concurrent DexScreener / reserve lookups across all requests
+    pub market_data_concurrency: usize,
+    /// Verdicts below this confidence (0-100) are reported as `Unverified`
+    pub min_confidence: u8,
+    /// Risk factors kept per response, most severe first (0 = unlimited)
+    pub max_risk_factors: usize,
+    /// Most recent blocks of Transfer logs scanned for a holder count
+    pub holder_scan_max_blocks: u64,
+    /// Directory telemetry stats/events are exported to
+    pub telemetry_export_dir: String,
+    /// Periodic telemetry flush + stats export interval (0 = shutdown only)
+    pub telemetry_export_interval_secs: u64,
+    /// Failed analyses kept for `/debug/failures` (0 = disabled)
+    pub failure_log_size: usize,
+    /// Optional JSONL file every failed analysis is appended to
+    pub failure_log_path: Option<String>,
+    /// Audit mode: directory one JSON decision record per analysis is written to (`None` = off)
+    pub audit_log_dir: Option<String>,
+    /// Generic webhook every alert is POSTed to as JSON
+    pub alert_webhook_url: Option<String>,
+    /// Discord webhook for alerts
+    pub alert_discord_webhook_url: Option<String>,
+    /// Telegram bot token + chat id for alerts (both required)
+    pub alert_telegram_bot_token: Option<String>,
+    pub alert_telegram_chat_id: Option<String>,
+    /// Verdicts at or above this level are alerted (honeypots are always Critical)
+    pub alert_min_severity: RiskLevel,
+    /// Repeat alerts for the same (chain, token) are dropped for this long (0 = never)
+    pub alert_cooldown_secs: u64,
+    /// Admin key required by `/debug/*` and `/feedback` (`None` = endpoints disabled)
+    pub admin_api_key: Option<String>,
+    /// JSON file ML weights are loaded from at startup and saved to after feedback
+    pub ml_weights_path: Option<String>,
+    /// CORS allowed origins for the API ("*" = any origin)
+    pub cors_allowed_origins: Vec<String>,
+    /// CORS allowed methods ("*" = any method)
+    pub cors_allowed_methods: Vec<String>,
+    /// CORS allowed request headers ("*" = any header)
+    pub cors_allowed_headers: Vec<String>,
+    /// Chains the API scans for PairCreated events (`/new-pairs/:chain_id`, empty = off)
+    pub new_pairs_chains: Vec<u64>,
+    /// Most recent pairs kept per scanned chain
+    pub new_pairs_buffer: usize,
+    /// Hosts whose RPC TLS certificates are not validated (`rpc_insecure_tls_hosts`, INSECURE),
+    /// installed with `providers::rpc::set_insecure_tls_hosts`
+    pub insecure_tls_hosts: Vec<String>,
+}
+
+/// Read a u64 from env, falling back to `default`
+fn env_u64(key: &str, default: u64) -> u64 {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+/// Read an on/off flag from env ("true", "1", "yes"); unset or anything else = off
+fn env_flag(key: &str) -> bool {
+    std::env::var(key)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Read a comma-separated list from env, falling back to `default`
+fn env_list(key: &str, default: &[&str]) -> Vec<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|list| !list.is_empty())
+        .unwrap_or_else(|| default.iter().map(|s| s.to_string()).collect())
 }
 
 impl Default for SentryConfig {
@@ -235,11 +683,60 @@ impl Default for SentryConfig {
             http_url: std::env::var("ETH_HTTP_URL").unwrap_or_else(|_| {
                 "https://eth-mainnet.g.alchemy.com/v2/YOUR_API_KEY".to_string()
             }),
+            spec_id: chain_spec_id(CHAIN_ID_ETHEREUM),
             max_concurrent_tasks: 50,
             rpc_timeout: Duration::from_secs(5),
             min_gas_price_gwei: 1,
             slippage_threshold_bps: 300,
             high_tax_threshold_bps: 500,
+            request_timeout: Duration::from_secs(env_u64("REQUEST_TIMEOUT_SECS", 15)),
+            cache_safe_ttl_secs: env_u64("CACHE_SAFE_TTL_SECS", DEFAULT_CACHE_TTL_SECS),
+            cache_honeypot_ttl_secs: env_u64("CACHE_HONEYPOT_TTL_SECS", DEFAULT_HONEYPOT_TTL_SECS),
+            batch_max_tokens: env_u64("BATCH_MAX_TOKENS", 100) as usize,
+            batch_token_timeout: Duration::from_secs(env_u64("BATCH_TOKEN_TIMEOUT_SECS", 10)),
+            max_bytecode_size: env_u64("MAX_BYTECODE_SIZE", DEFAULT_MAX_BYTECODE_SIZE as u64) as usize,
+            sell_lock_max_blocks: env_u64("SELL_LOCK_MAX_BLOCKS", DEFAULT_SELL_LOCK_MAX_BLOCKS),
+            test_account_balance_eth: env_u64("TEST_ACCOUNT_BALANCE_ETH", DEFAULT_TEST_ACCOUNT_BALANCE_ETH),
+            market_data_concurrency: env_u64("MARKET_DATA_CONCURRENCY", 8).max(1) as usize,
+            min_confidence: env_u64("MIN_CONFIDENCE", DEFAULT_MIN_CONFIDENCE as u64).min(100) as u8,
+            max_risk_factors: env_u64("MAX_RISK_FACTORS", DEFAULT_MAX_RISK_FACTORS as u64) as usize,
+            holder_scan_max_blocks: env_u64("HOLDER_SCAN_MAX_BLOCKS", DEFAULT_HOLDER_SCAN_MAX_BLOCKS).max(1),
+            telemetry_export_dir: std::env::var("TELEMETRY_EXPORT_DIR")
+                .ok()
+                .filter(|d| !d.is_empty())
+                .unwrap_or_else(|| "./telemetry".to_string()),
+            telemetry_export_interval_secs: env_u64("TELEMETRY_EXPORT_INTERVAL_SECS", 300),
+            failure_log_size: env_u64("FAILURE_LOG_SIZE", DEFAULT_FAILURE_LOG_SIZE as u64) as usize,
+            failure_log_path: std::env::var("FAILURE_LOG_PATH").ok().filter(|p| !p.is_empty()),
+            audit_log_dir: std::env::var("AUDIT_LOG_DIR").ok().filter(|d| !d.is_empty()),
+            alert_webhook_url: std::env::var("ALERT_WEBHOOK_URL").ok().filter(|u| !u.is_empty()),
+            alert_discord_webhook_url: std::env::var("ALERT_DISCORD_WEBHOOK_URL")
+                .ok()
+                .filter(|u| !u.is_empty()),
+            alert_telegram_bot_token: std::env::var("ALERT_TELEGRAM_BOT_TOKEN")
+                .ok()
+                .filter(|t| !t.is_empty()),
+            alert_telegram_chat_id: std::env::var("ALERT_TELEGRAM_CHAT_ID").ok().filter(|c| !c.is_empty()),
+            alert_min_severity: std::env::var("ALERT_MIN_SEVERITY")
+                .ok()
+                .and_then(|level| RiskLevel::parse(&level))
+                .unwrap_or(RiskLevel::Critical),
+            alert_cooldown_secs: env_u64("ALERT_COOLDOWN_SECS", DEFAULT_ALERT_COOLDOWN_SECS),
+            admin_api_key: std::env::var("ADMIN_API_KEY").ok().filter(|k| !k.is_empty()),
+            ml_weights_path: std::env::var("ML_WEIGHTS_PATH").ok().filter(|p| !p.is_empty()),
+            // Browser dApps: any origin, read + analyze methods, API key header
+            cors_allowed_origins: env_list("CORS_ALLOWED_ORIGINS", &["*"]),
+            cors_allowed_methods: env_list("CORS_ALLOWED_METHODS", &["GET", "POST", "OPTIONS"]),
+            cors_allowed_headers: env_list(
+                "CORS_ALLOWED_HEADERS",
+                &["content-type", "x-api-key", "authorization"],
+            ),
+            new_pairs_chains: env_list("NEW_PAIRS_CHAINS", &[])
+                .iter()
+                .filter_map(|chain| parse_chain(chain))
+                .collect(),
+            new_pairs_buffer: env_u64("NEW_PAIRS_BUFFER", 200).max(1) as usize,
+            insecure_tls_hosts: rpc_insecure_tls_hosts(),
         }
     }
 }
@@ -249,11 +746,140 @@ impl Clone for SentryConfig {
         Self {
             wss_url: self.wss_url.clone(),
             http_url: self.http_url.clone(),
+            spec_id: self.spec_id,
             max_concurrent_tasks: self.max_concurrent_tasks,
             rpc_timeout: self.rpc_timeout,
             min_gas_price_gwei: self.min_gas_price_gwei,
             slippage_threshold_bps: self.slippage_threshold_bps,
             high_tax_threshold_bps: self.high_tax_threshold_bps,
+            request_timeout: self.request_timeout,
+            cache_safe_ttl_secs: self.cache_safe_ttl_secs,
+            cache_honeypot_ttl_secs: self.cache_honeypot_ttl_secs,
+            batch_max_tokens: self.batch_max_tokens,
+            batch_token_timeout: self.batch_token_timeout,
+            max_bytecode_size: self.max_bytecode_size,
+            sell_lock_max_blocks: self.sell_lock_max_blocks,
+            test_account_balance_eth: self.test_account_balance_eth,
+            market_data_concurrency: self.market_data_concurrency,
+            min_confidence: self.min_confidence,
+            max_risk_factors: self.max_risk_factors,
+            holder_scan_max_blocks: self.holder_scan_max_blocks,
+            telemetry_export_dir: self.telemetry_export_dir.clone(),
+            telemetry_export_interval_secs: self.telemetry_export_interval_secs,
+            failure_log_size: self.failure_log_size,
+            failure_log_path: self.failure_log_path.clone(),
+            audit_log_dir: self.audit_log_dir.clone(),
+            alert_webhook_url: self.alert_webhook_url.clone(),
+            alert_discord_webhook_url: self.alert_discord_webhook_url.clone(),
+            alert_telegram_bot_token: self.alert_telegram_bot_token.clone(),
+            alert_telegram_chat_id: self.alert_telegram_chat_id.clone(),
+            alert_min_severity: self.alert_min_severity,
+            alert_cooldown_secs: self.alert_cooldown_secs,
+            admin_api_key: self.admin_api_key.clone(),
+            ml_weights_path: self.ml_weights_path.clone(),
+            cors_allowed_origins: self.cors_allowed_origins.clone(),
+            cors_allowed_methods: self.cors_allowed_methods.clone(),
+            cors_allowed_headers: self.cors_allowed_headers.clone(),
+            new_pairs_chains: self.new_pairs_chains.clone(),
+            new_pairs_buffer: self.new_pairs_buffer,
+            insecure_tls_hosts: self.insecure_tls_hosts.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_routers_order_and_dedup() {
+        let routers = DexRouters::default();
+        let bsc = routers.known_routers(CHAIN_ID_BSC);
+        assert_eq!(bsc.first().map(|r| r.name.as_str()), Some("PancakeSwap V2"));
+        assert!(routers.known_routers(999_999).is_empty());
+
+        let entries = vec![
+            ("A".to_string(), "0x10ED43C718714eb63d5aA57B78B54704E256024E".to_string()),
+            ("B".to_string(), "not-an-address".to_string()),
+            ("C".to_string(), "0x10ed43c718714eb63d5aa57b78b54704e256024e".to_string()),
+            ("D".to_string(), "0x3a6d8cA21D1CF76F653A67577FA0D27453350dD8".to_string()),
+        ];
+        let names: Vec<_> = DexRouters::dedup_routers(entries.into_iter()).into_iter().map(|r| r.name).collect();
+        assert_eq!(names, vec!["A", "D"]);
+    }
+
+    #[test]
+    fn test_infrastructure_addresses() {
+        let eth = ChainConfig::get(CHAIN_ID_ETHEREUM).unwrap();
+        let router = Address::from_str("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D").unwrap();
+        let factory = Address::from_str("0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f").unwrap();
+        let universal = Address::from_str("0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD").unwrap();
+        assert_eq!(eth.infrastructure_label(&router), Some("Uniswap V2 router"));
+        assert_eq!(eth.infrastructure_label(&factory), Some("Uniswap V2 factory"));
+        assert_eq!(eth.infrastructure_label(&universal), Some("DEX router"));
+        assert_eq!(eth.infrastructure_label(&eth.weth), None);
+
+        // chain_id 0 searches every chain
+        let pancake = Address::from_str("0xcA143Ce32Fe78f1f7019d7d551a6402fC5350c73").unwrap();
+        assert_eq!(
+            ChainConfig::infrastructure_chain(&pancake, 0),
+            Some((CHAIN_ID_BSC, "PancakeSwap V2 factory".to_string()))
+        );
+        assert_eq!(ChainConfig::infrastructure_chain(&pancake, CHAIN_ID_ETHEREUM), None);
+    }
+
+    #[test]
+    fn test_chain_spec_id() {
+        assert_eq!(parse_spec_id("shanghai"), Some(SpecId::SHANGHAI));
+        assert_eq!(parse_spec_id(" Cancun "), Some(SpecId::CANCUN));
+        assert_eq!(parse_spec_id("paris"), Some(SpecId::MERGE));
+        assert_eq!(parse_spec_id("osaka-ish"), None);
+        assert_eq!(chain_spec_id(999_999), SpecId::SHANGHAI);
+        assert_eq!(chain_spec_id(CHAIN_ID_ETHEREUM), SpecId::PRAGUE);
+        assert_eq!(chain_spec_id(CHAIN_ID_BASE), SpecId::PRAGUE);
+        assert_eq!(chain_spec_id(CHAIN_ID_AVALANCHE), SpecId::CANCUN);
+    }
+
+    #[test]
+    fn test_chain_loss_cutoffs() {
+        let bsc = chain_loss_cutoffs(CHAIN_ID_BSC);
+        let eth = chain_loss_cutoffs(CHAIN_ID_ETHEREUM);
+        assert!(bsc.honeypot_loss_percent > eth.honeypot_loss_percent);
+        assert!(bsc.high_tax_percent > eth.high_tax_percent);
+        assert_eq!(chain_loss_cutoffs(999_999), LossCutoffs::default());
+    }
+
+    #[test]
+    fn test_chain_test_amount() {
+        assert_eq!(chain_test_amount(999_999), DEFAULT_TEST_AMOUNT_NATIVE);
+        assert!(chain_test_amount(CHAIN_ID_POLYGON) > chain_test_amount(CHAIN_ID_ETHEREUM));
+        assert!(chain_test_amount(CHAIN_ID_BSC) > 0.0);
+    }
+
+    #[test]
+    fn test_public_rpc_pool() {
+        assert_eq!(parse_weighted_url("https://rpc.example|3"), ("https://rpc.example".to_string(), 3));
+        assert_eq!(parse_weighted_url("https://rpc.example"), ("https://rpc.example".to_string(), 1));
+        assert_eq!(parse_weighted_url("https://rpc.example|x"), ("https://rpc.example".to_string(), 1));
+        assert_eq!(public_rpc_pool(CHAIN_ID_BSC)[0], ("https://bsc-dataseed.binance.org".to_string(), 1));
+        assert!(public_rpc_pool(999_999).is_empty());
+    }
+
+    #[test]
+    fn test_rpc_endpoints() {
+        assert_eq!(parse_rpc_endpoint("https://node:8545"), ("https://node:8545".to_string(), RpcRole::Primary));
+        assert_eq!(parse_rpc_endpoint(" alchemy|fallback "), (ALCHEMY_ENDPOINT.to_string(), RpcRole::Fallback));
+        assert_eq!(parse_rpc_endpoint("https://a|Balanced"), ("https://a".to_string(), RpcRole::Balanced));
+        assert_eq!(parse_rpc_endpoint("https://a|bogus"), ("https://a".to_string(), RpcRole::Primary));
+        // Unset = Alchemy as the only primary
+        assert_eq!(rpc_endpoints(999_999), vec![(ALCHEMY_ENDPOINT.to_string(), RpcRole::Primary)]);
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(url_host("https://Node.Internal:8545/rpc"), Some("node.internal".to_string()));
+        assert_eq!(url_host("https://eth-mainnet.g.alchemy.com/v2/key"), Some("eth-mainnet.g.alchemy.com".to_string()));
+        // The Alchemy placeholder is not a host
+        assert_eq!(url_host(ALCHEMY_ENDPOINT), None);
+    }
+}
@@ -1,10 +1,59 @@
 //! Type definitions for Mempool Sentry
 //! All core data structures for transaction analysis
 
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_sol_types::{sol, SolCall};
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
+use crate::utils::constants::CHAIN_ID_AVALANCHE;
+
+// Router swap functions built by `SwapParams::encode_*`
+sol! {
+    // Uniswap V2 Router
+    function swapExactETHForTokens(
+        uint256 amountOutMin,
+        address[] calldata path,
+        address to,
+        uint256 deadline
+    ) external payable returns (uint256[] memory amounts);
+
+    function swapExactTokensForETH(
+        uint256 amountIn,
+        uint256 amountOutMin,
+        address[] calldata path,
+        address to,
+        uint256 deadline
+    ) external returns (uint256[] memory amounts);
+
+    // Avalanche V2 forks (TraderJoe V1, Pangolin) name the native swaps after AVAX
+    function swapExactAVAXForTokens(
+        uint256 amountOutMin,
+        address[] calldata path,
+        address to,
+        uint256 deadline
+    ) external payable returns (uint256[] memory amounts);
+
+    function swapExactTokensForAVAX(
+        uint256 amountIn,
+        uint256 amountOutMin,
+        address[] calldata path,
+        address to,
+        uint256 deadline
+    ) external returns (uint256[] memory amounts);
+
+    // Uniswap V3 SwapRouter
+    struct ExactInputParams {
+        bytes path;
+        address recipient;
+        uint256 deadline;
+        uint256 amountIn;
+        uint256 amountOutMinimum;
+    }
+
+    function exactInput(ExactInputParams calldata params) external payable returns (uint256 amountOut);
+}
+
 /// Risk level classification for transactions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RiskLevel {
@@ -40,6 +89,29 @@ impl RiskLevel {
             RiskLevel::Critical => "💀",
         }
     }
+
+    /// Level for a 0-100 risk score (same bands as `RiskScore`)
+    pub fn from_score(score: u8) -> Self {
+        match score {
+            0..=20 => RiskLevel::Safe,
+            21..=40 => RiskLevel::Low,
+            41..=60 => RiskLevel::Medium,
+            61..=80 => RiskLevel::High,
+            _ => RiskLevel::Critical,
+        }
+    }
+
+    /// Parse a level name, case-insensitive ("high", "CRITICAL")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "safe" => Some(RiskLevel::Safe),
+            "low" => Some(RiskLevel::Low),
+            "medium" => Some(RiskLevel::Medium),
+            "high" => Some(RiskLevel::High),
+            "critical" => Some(RiskLevel::Critical),
+            _ => None,
+        }
+    }
 }
 
 /// Detected risk factors in a transaction
@@ -262,6 +334,143 @@ pub struct SwapParams {
     pub deadline: U256,
 }
 
+/// Naming of a V2 router's native-currency swap functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NativeSwapAbi {
+    /// `swapExactETHForTokens` / `swapExactTokensForETH` (Uniswap V2 and most forks)
+    #[default]
+    Eth,
+    /// `swapExactAVAXForTokens` / `swapExactTokensForAVAX` (TraderJoe V1, Pangolin)
+    Avax,
+}
+
+impl NativeSwapAbi {
+    /// ABI used by the V2 routers of a chain
+    pub fn for_chain(chain_id: u64) -> Self {
+        if chain_id == CHAIN_ID_AVALANCHE {
+            NativeSwapAbi::Avax
+        } else {
+            NativeSwapAbi::Eth
+        }
+    }
+}
+
+impl SwapParams {
+    /// V2 native → token calldata (`amount_in` is sent as msg.value, not encoded)
+    /// `path` must start with the wrapped native token
+    pub fn encode_v2_buy(&self, to: Address, abi: NativeSwapAbi) -> Bytes {
+        let (path, amount_out_min, deadline) = (self.path.clone(), self.amount_out_min, self.deadline);
+        let calldata = match abi {
+            NativeSwapAbi::Eth => {
+                swapExactETHForTokensCall { amountOutMin: amount_out_min, path, to, deadline }.abi_encode()
+            }
+            NativeSwapAbi::Avax => {
+                swapExactAVAXForTokensCall { amountOutMin: amount_out_min, path, to, deadline }.abi_encode()
+            }
+        };
+        Bytes::from(calldata)
+    }
+
+    /// V2 token → native calldata; `path` must end with the wrapped native token
+    pub fn encode_v2_sell(&self, to: Address, abi: NativeSwapAbi) -> Bytes {
+        let (amount_in, amount_out_min, path, deadline) =
+            (self.amount_in, self.amount_out_min, self.path.clone(), self.deadline);
+        let calldata = match abi {
+            NativeSwapAbi::Eth => swapExactTokensForETHCall {
+                amountIn: amount_in,
+                amountOutMin: amount_out_min,
+                path,
+                to,
+                deadline,
+            }
+            .abi_encode(),
+            NativeSwapAbi::Avax => swapExactTokensForAVAXCall {
+                amountIn: amount_in,
+                amountOutMin: amount_out_min,
+                path,
+                to,
+                deadline,
+            }
+            .abi_encode(),
+        };
+        Bytes::from(calldata)
+    }
+
+    /// V3 SwapRouter `exactInput` calldata; every hop uses the same `fee` tier
+    /// (e.g. 3000 = 0.3%). None if the path has fewer than two tokens
+    pub fn encode_v3_exact_input(&self, recipient: Address, fee: u32) -> Option<Bytes> {
+        if self.path.len() < 2 {
+            return None;
+        }
+        // Packed path: token(20) | fee(3) | token(20) ...
+        let mut path = Vec::with_capacity(20 + (self.path.len() - 1) * 23);
+        for (i, token) in self.path.iter().enumerate() {
+            if i > 0 {
+                path.extend_from_slice(&fee.to_be_bytes()[1..]);
+            }
+            path.extend_from_slice(token.as_slice());
+        }
+
+        let calldata = exactInputCall {
+            params: ExactInputParams {
+                path: Bytes::from(path),
+                recipient,
+                deadline: self.deadline,
+                amountIn: self.amount_in,
+                amountOutMinimum: self.amount_out_min,
+            },
+        }
+        .abi_encode();
+        Some(Bytes::from(calldata))
+    }
+}
+
+/// Fee parameters of a transaction: a legacy `gas_price` or the EIP-1559 caps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct TxFees {
+    /// Legacy / EIP-2930 gas price (wei)
+    pub gas_price: Option<u128>,
+    /// EIP-1559 fee cap (wei)
+    pub max_fee_per_gas: Option<u128>,
+    /// EIP-1559 tip cap (wei)
+    pub max_priority_fee_per_gas: Option<u128>,
+}
+
+impl TxFees {
+    pub fn legacy(gas_price: u128) -> Self {
+        Self {
+            gas_price: Some(gas_price),
+            ..Default::default()
+        }
+    }
+
+    pub fn eip1559(max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> Self {
+        Self {
+            gas_price: None,
+            max_fee_per_gas: Some(max_fee_per_gas),
+            max_priority_fee_per_gas: Some(max_priority_fee_per_gas),
+        }
+    }
+
+    pub fn is_eip1559(&self) -> bool {
+        self.max_fee_per_gas.is_some()
+    }
+
+    /// Price per gas paid at `base_fee`: min(fee cap, base fee + tip) for EIP-1559,
+    /// the gas price otherwise
+    pub fn effective_gas_price(&self, base_fee: u128) -> u128 {
+        match self.max_fee_per_gas {
+            Some(max_fee) => max_fee.min(base_fee.saturating_add(self.max_priority_fee_per_gas.unwrap_or(0))),
+            None => self.gas_price.unwrap_or(0),
+        }
+    }
+
+    /// Tip per gas the block builder receives at `base_fee` - what orders the transaction
+    pub fn priority_fee(&self, base_fee: u128) -> u128 {
+        self.effective_gas_price(base_fee).saturating_sub(base_fee)
+    }
+}
+
 /// Statistics for monitoring
 #[derive(Debug, Default)]
 pub struct SentryStats {
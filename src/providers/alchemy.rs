@@ -21,9 +21,11 @@
 
 use eyre::{eyre, Result};
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use std::collections::HashMap;
+use tracing::{debug, info, warn};
 
 use super::rpc::RpcProvider;
+use crate::utils::constants::{get_alchemy_subdomain, get_native_symbol, get_weth_address};
 
 // ============================================
 // TOKEN API TYPES
@@ -447,6 +449,31 @@ impl AlchemyPricesClient {
         
         Ok(None)
     }
+
+    /// Get native token USD price per chain (priced via the wrapped native token)
+    /// e.g. 56 → WBNB price, 137 → WMATIC price. Chains that fail are omitted.
+    pub async fn get_native_prices(&self, chain_ids: &[u64]) -> HashMap<u64, f64> {
+        let mut prices = HashMap::new();
+
+        for &chain_id in chain_ids {
+            let (Some(network), Some(wrapped)) =
+                (get_alchemy_subdomain(chain_id), get_weth_address(chain_id))
+            else {
+                continue;
+            };
+
+            match self.get_token_price(network, &format!("{:?}", wrapped)).await {
+                Ok(Some(price)) => {
+                    debug!("💲 {} price: ${:.2}", get_native_symbol(chain_id), price);
+                    prices.insert(chain_id, price);
+                }
+                Ok(None) => debug!("No USD price for {} on {}", get_native_symbol(chain_id), network),
+                Err(e) => warn!("⚠️ Native price lookup failed for chain {}: {}", chain_id, e),
+            }
+        }
+
+        prices
+    }
 }
 
 #[cfg(test)]
@@ -16,11 +16,164 @@
 //! DexScreener has 5-30 second delay - NOT suitable for security checks.
 //!
 //! API: https://api.dexscreener.com/latest/dex/tokens/{tokenAddress}
+//! Batch: https://api.dexscreener.com/latest/dex/tokens/{addr1},{addr2},... (max 30)
+//! Search: https://api.dexscreener.com/latest/dex/search?q={symbol or name}
 //! Free, no API key required
 
 use eyre::{eyre, Result};
 use serde::Deserialize;
-use tracing::{info, warn};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::utils::backoff::BackoffConfig;
+use crate::utils::latency::UPSTREAM_LATENCY;
+use crate::utils::throttle::TokenBucket;
+
+/// Max token addresses per DexScreener multi-token request
+pub const DEXSCREENER_BATCH_SIZE: usize = 30;
+
+/// Default DexScreener request rate (their limit is 300/min) - `DEXSCREENER_RATE_PER_SEC` overrides
+pub const DEFAULT_DEXSCREENER_RATE_PER_SEC: f64 = 5.0;
+
+/// Default DexScreener burst - `DEXSCREENER_BURST` overrides
+pub const DEFAULT_DEXSCREENER_BURST: u32 = 10;
+
+/// Chains with less total liquidity than this are not auto-detect candidates
+pub const MIN_CANDIDATE_LIQUIDITY_USD: f64 = 1_000.0;
+
+/// Longest a request waits for a DexScreener token before the caller goes sim-only
+const DEXSCREENER_MAX_WAIT: Duration = Duration::from_secs(2);
+
+/// Pause after a DexScreener 429: 2s, 4s, ... up to 60s on consecutive 429s
+const DEXSCREENER_COOLDOWN: BackoffConfig = BackoffConfig {
+    base_delay_ms: 2_000,
+    max_delay_ms: 60_000,
+    max_attempts: 1,
+    jitter_percent: 20,
+    min_delay_ms: 1_000,
+};
+
+lazy_static::lazy_static! {
+    /// Shared by every `DexScreenerClient` - independent of the RPC retry/backoff path
+    pub static ref DEXSCREENER_THROTTLE: TokenBucket = TokenBucket::new(
+        std::env::var("DEXSCREENER_BURST")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_DEXSCREENER_BURST),
+        std::env::var("DEXSCREENER_RATE_PER_SEC")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_DEXSCREENER_RATE_PER_SEC),
+        DEXSCREENER_COOLDOWN,
+    );
+}
+
+/// Default seconds a "no pairs" answer is trusted - `DEXSCREENER_NEGATIVE_TTL_SECS` overrides
+pub const DEFAULT_DEXSCREENER_NEGATIVE_TTL_SECS: u64 = 60;
+
+/// Most unlisted tokens remembered (new misses are not cached beyond this)
+const MAX_UNLISTED_TOKENS: usize = 10_000;
+
+lazy_static::lazy_static! {
+    /// Tokens DexScreener just reported no pairs for, shared by every `DexScreenerClient`
+    pub static ref DEXSCREENER_UNLISTED: UnlistedTokenCache = UnlistedTokenCache::new(Duration::from_secs(
+        std::env::var("DEXSCREENER_NEGATIVE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_DEXSCREENER_NEGATIVE_TTL_SECS),
+    ));
+}
+
+/// Negative cache: token → when DexScreener last returned no pairs for it.
+/// Fresh launches get indexed within minutes, so entries expire after a short TTL
+pub struct UnlistedTokenCache {
+    ttl: Duration,
+    tokens: Mutex<HashMap<String, Instant>>,
+}
+
+impl UnlistedTokenCache {
+    /// `ttl` zero = disabled (nothing is remembered)
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// EVM addresses are case-insensitive, Solana mints are not
+    fn key(token: &str) -> String {
+        if token.starts_with("0x") {
+            token.to_lowercase()
+        } else {
+            token.to_string()
+        }
+    }
+
+    /// DexScreener had no pairs for `token` within the TTL
+    pub fn is_unlisted(&self, token: &str) -> bool {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        let key = Self::key(token);
+        match tokens.get(&key) {
+            Some(at) if at.elapsed() < self.ttl => true,
+            Some(_) => {
+                tokens.remove(&key);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Remember that DexScreener returned no pairs for `token`
+    pub fn record(&self, token: &str) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        if tokens.len() >= MAX_UNLISTED_TOKENS {
+            let ttl = self.ttl;
+            tokens.retain(|_, at| at.elapsed() < ttl);
+            if tokens.len() >= MAX_UNLISTED_TOKENS {
+                return;
+            }
+        }
+        tokens.insert(Self::key(token), Instant::now());
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Max candidates returned by a symbol / name search
+pub const MAX_SEARCH_CANDIDATES: usize = 25;
+
+/// Characters of the raw body kept in a `DexScreenerSchemaError` / schema-change warning
+const RAW_SNIPPET_CHARS: usize = 512;
+
+/// DexScreener answered 200 with JSON we cannot map onto `DexScreenerResponse` at all
+/// (not JSON, or `pairs` no longer a list) - an upstream schema change, not "no pairs"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DexScreenerSchemaError {
+    /// Endpoint label, e.g. "dexscreener:/tokens"
+    pub endpoint: String,
+    pub message: String,
+    /// Start of the raw response body (`RAW_SNIPPET_CHARS`)
+    pub raw: String,
+}
+
+impl std::fmt::Display for DexScreenerSchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DexScreener schema change on {}: {}", self.endpoint, self.message)
+    }
+}
+
+impl std::error::Error for DexScreenerSchemaError {}
 
 /// DexScreener API response
 #[derive(Debug, Deserialize)]
@@ -50,6 +203,9 @@ pub struct DexPair {
     pub liquidity: Option<DexLiquidity>,
     /// Price in USD
     pub price_usd: Option<String>,
+    /// Price in units of the quote token
+    #[serde(default)]
+    pub price_native: Option<String>,
     /// 24h volume
     pub volume: Option<DexVolume>,
 }
@@ -111,15 +267,60 @@ impl DexScreenerClient {
 
     /// Fetch all pairs for a token address
     /// Returns pairs sorted by liquidity (highest first)
+    /// A token DexScreener recently had no pairs for is answered from `DEXSCREENER_UNLISTED`
     pub async fn get_token_pairs(&self, token_address: &str) -> Result<Vec<DexPair>> {
-        let url = format!("{}/tokens/{}", self.base_url, token_address);
-        
+        if DEXSCREENER_UNLISTED.is_unlisted(token_address) {
+            debug!("🔍 DexScreener: {} recently unlisted - not re-querying", token_address);
+            return Ok(Vec::new());
+        }
         info!("🔍 DexScreener: Fetching pairs for {}", token_address);
 
-        let response = self.client
-            .get(&url)
-            .timeout(std::time::Duration::from_secs(10))
-            .send()
+        let mut pairs = self.fetch_pairs(token_address).await?;
+        if pairs.is_empty() {
+            DEXSCREENER_UNLISTED.record(token_address);
+        }
+        Self::sort_by_liquidity(&mut pairs);
+
+        info!("📊 DexScreener: Found {} pairs", pairs.len());
+        
+        Ok(pairs)
+    }
+
+    /// 📦 BATCH: Fetch pairs for many tokens with one request per 30 addresses
+    /// Returns map of lowercase token address → pairs (sorted by liquidity)
+    /// Tokens without any pair are absent from the map
+    pub async fn get_tokens_batch(&self, addresses: &[&str]) -> Result<HashMap<String, Vec<DexPair>>> {
+        let mut by_token: HashMap<String, Vec<DexPair>> = HashMap::new();
+        let queried: Vec<&str> = addresses
+            .iter()
+            .copied()
+            .filter(|address| !DEXSCREENER_UNLISTED.is_unlisted(address))
+            .collect();
+
+        for chunk in queried.chunks(DEXSCREENER_BATCH_SIZE) {
+            info!("🔍 DexScreener: Batch fetching {} tokens", chunk.len());
+            let pairs = self.fetch_pairs(&chunk.join(",")).await?;
+            group_pairs_by_token(chunk, pairs, &mut by_token);
+            for address in chunk.iter().filter(|a| !by_token.contains_key(&a.to_lowercase())) {
+                DEXSCREENER_UNLISTED.record(address);
+            }
+        }
+
+        for pairs in by_token.values_mut() {
+            Self::sort_by_liquidity(pairs);
+        }
+
+        info!("📊 DexScreener: Batch resolved {}/{} tokens", by_token.len(), addresses.len());
+
+        Ok(by_token)
+    }
+
+    /// Raw `/tokens/{addresses}` request (single or comma-separated)
+    async fn fetch_pairs(&self, addresses: &str) -> Result<Vec<DexPair>> {
+        let url = format!("{}/tokens/{}", self.base_url, addresses);
+
+        let request = self.client.get(&url).timeout(std::time::Duration::from_secs(10));
+        let response = Self::send_throttled("dexscreener:/tokens", request)
             .await
             .map_err(|e| eyre!("DexScreener request failed: {}", e))?;
 
@@ -127,21 +328,70 @@ impl DexScreenerClient {
             return Err(eyre!("DexScreener API error: {}", response.status()));
         }
 
-        let data: DexScreenerResponse = response.json().await
-            .map_err(|e| eyre!("Failed to parse DexScreener response: {}", e))?;
+        let body = response.text().await
+            .map_err(|e| eyre!("Failed to read DexScreener response: {}", e))?;
 
-        let mut pairs = data.pairs.unwrap_or_default();
-        
-        // Sort by liquidity (highest first)
+        Ok(parse_pairs("dexscreener:/tokens", &body)?)
+    }
+
+    /// 🔎 SEARCH: Tokens matching a symbol or name, ranked (see `rank_search_candidates`)
+    /// `chain` (DexScreener chain name) restricts the results to one chain
+    pub async fn search_tokens(&self, query: &str, chain: Option<&str>, limit: usize) -> Result<Vec<SearchCandidate>> {
+        info!("🔎 DexScreener: Searching \"{}\"", query);
+
+        let url = format!("{}/search", self.base_url);
+        let request = self.client
+            .get(&url)
+            .query(&[("q", query)])
+            .timeout(std::time::Duration::from_secs(10));
+        let response = Self::send_throttled("dexscreener:/search", request)
+            .await
+            .map_err(|e| eyre!("DexScreener search failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(eyre!("DexScreener API error: {}", response.status()));
+        }
+
+        let body = response.text().await
+            .map_err(|e| eyre!("Failed to read DexScreener search response: {}", e))?;
+        let pairs = parse_pairs("dexscreener:/search", &body)?;
+
+        let mut candidates = rank_search_candidates(query, &pairs, chain);
+        candidates.truncate(limit.clamp(1, MAX_SEARCH_CANDIDATES));
+
+        info!("📊 DexScreener: {} candidates for \"{}\"", candidates.len(), query);
+
+        Ok(candidates)
+    }
+
+    /// Send through `DEXSCREENER_THROTTLE`. Errs without sending when no token frees up
+    /// within `DEXSCREENER_MAX_WAIT` (callers fall back to sim-only results);
+    /// a 429 pauses DexScreener for every client. Timed into `UPSTREAM_LATENCY` as `endpoint`
+    async fn send_throttled(endpoint: &str, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        if !DEXSCREENER_THROTTLE.acquire_within(DEXSCREENER_MAX_WAIT).await {
+            return Err(eyre!("local rate limit reached"));
+        }
+        let started = std::time::Instant::now();
+        let response = request.send().await;
+        let ok = response.as_ref().is_ok_and(|r| r.status().is_success());
+        UPSTREAM_LATENCY.record(endpoint, started.elapsed(), ok);
+        let response = response?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let pause = DEXSCREENER_THROTTLE.record_throttled();
+            warn!("⏳ DexScreener 429 - pausing DexScreener requests for {}ms", pause.as_millis());
+        } else if response.status().is_success() {
+            DEXSCREENER_THROTTLE.record_success();
+        }
+        Ok(response)
+    }
+
+    /// Sort by liquidity (highest first)
+    fn sort_by_liquidity(pairs: &mut [DexPair]) {
         pairs.sort_by(|a, b| {
             let liq_a = a.liquidity.as_ref().and_then(|l| l.usd).unwrap_or(0.0);
             let liq_b = b.liquidity.as_ref().and_then(|l| l.usd).unwrap_or(0.0);
             liq_b.partial_cmp(&liq_a).unwrap_or(std::cmp::Ordering::Equal)
         });
-
-        info!("📊 DexScreener: Found {} pairs", pairs.len());
-        
-        Ok(pairs)
     }
 
     /// Get pairs for a specific chain
@@ -177,7 +427,23 @@ impl DexScreenerClient {
     /// Prefers V2-compatible DEXes over V3
     pub async fn auto_detect_token(&self, token_address: &str) -> Result<AutoDetectedToken> {
         let pairs = self.get_token_pairs(token_address).await?;
-        
+        Self::auto_detect_from_pairs(&pairs)
+    }
+
+    /// `auto_detect_from_pairs` limited to the pairs on `chain_id`
+    /// Used by batch entries that name their own chain
+    pub fn auto_detect_on_chain(pairs: &[DexPair], chain_id: u64) -> Result<AutoDetectedToken> {
+        let on_chain: Vec<DexPair> = pairs
+            .iter()
+            .filter(|p| Self::dexscreener_name_to_chain_id(&p.chain_id) == chain_id)
+            .cloned()
+            .collect();
+        Self::auto_detect_from_pairs(&on_chain)
+    }
+
+    /// Auto-detect chain & best DEX from already-fetched pairs (sorted by liquidity)
+    /// Used by batch analysis after `get_tokens_batch`
+    pub fn auto_detect_from_pairs(pairs: &[DexPair]) -> Result<AutoDetectedToken> {
         if pairs.is_empty() {
             return Err(eyre!("Token not found on any supported chain"));
         }
@@ -207,6 +473,13 @@ impl DexScreenerClient {
             .collect();
 
         let best_dex = best_pair.to_discovered_dex();
+        let chain_candidates = Self::chain_candidates(pairs);
+        if chain_candidates.len() > 1 {
+            warn!(
+                "🔀 {} trades on {} chains with meaningful liquidity - picked {}",
+                best_pair.base_token.address, chain_candidates.len(), chain_name
+            );
+        }
 
         info!("🎯 Auto-detected: {} on {} (chain_id: {})", 
               best_pair.base_token.symbol.as_deref().unwrap_or("Unknown"),
@@ -227,11 +500,46 @@ impl DexScreenerClient {
             total_pairs: all_pairs_count,
             // Market data from DexScreener
             price_usd: best_pair.price_usd.clone(),
+            price_native: best_pair.price_native.clone(),
+            base_token_address: Some(best_pair.base_token.address.clone()),
             volume_24h_usd: best_pair.volume.as_ref().and_then(|v| v.h24),
             pair_address: Some(best_pair.pair_address.clone()),
+            chain_candidates,
         })
     }
 
+    /// Supported chains holding at least `MIN_CANDIDATE_LIQUIDITY_USD` across their pairs,
+    /// most liquid first. More than one = the address is deployed on several chains
+    pub fn chain_candidates(pairs: &[DexPair]) -> Vec<ChainCandidate> {
+        let mut candidates: Vec<ChainCandidate> = Vec::new();
+        for pair in pairs {
+            let chain_id = Self::dexscreener_name_to_chain_id(&pair.chain_id);
+            // Unknown DexScreener chains map to Ethereum - skip them instead
+            if Self::chain_id_to_dexscreener_name(chain_id) != pair.chain_id.to_lowercase() {
+                continue;
+            }
+            let dex = pair.to_discovered_dex();
+            match candidates.iter_mut().find(|c| c.chain_id == chain_id) {
+                Some(candidate) => {
+                    candidate.liquidity_usd += dex.liquidity_usd;
+                    // Pairs arrive sorted by liquidity: keep the first V2 router per chain
+                    if candidate.best_v2_dex.is_none() && pair.is_v2_compatible() {
+                        candidate.best_v2_dex = Some(dex);
+                    }
+                }
+                None => candidates.push(ChainCandidate {
+                    chain_id,
+                    chain_name: Self::chain_id_to_name(chain_id).to_string(),
+                    liquidity_usd: dex.liquidity_usd,
+                    best_v2_dex: pair.is_v2_compatible().then_some(dex),
+                }),
+            }
+        }
+        candidates.retain(|c| c.liquidity_usd >= MIN_CANDIDATE_LIQUIDITY_USD);
+        candidates.sort_by(|a, b| b.liquidity_usd.total_cmp(&a.liquidity_usd));
+        candidates
+    }
+
     /// Convert numeric chain ID to DexScreener chain name (delegates to constants)
     fn chain_id_to_dexscreener_name(chain_id: u64) -> &'static str {
         crate::utils::constants::chain_id_to_dexscreener_name(chain_id)
@@ -253,6 +561,198 @@ impl DexScreenerClient {
     }
 }
 
+/// Assign pairs from a multi-token response to the requested addresses
+/// A pair counts for a token if the token is its base or quote side
+/// Pairs from a DexScreener body, tolerating schema drift
+///
+/// The typed parse is tried first. When it fails, the raw JSON is logged with a
+/// distinctive warning and every pair that still has the fields route discovery
+/// needs (chain, pair address, base/quote token addresses) is kept.
+/// Err only when nothing is recoverable (`DexScreenerSchemaError`)
+fn parse_pairs(endpoint: &str, body: &str) -> std::result::Result<Vec<DexPair>, DexScreenerSchemaError> {
+    let strict_error = match serde_json::from_str::<DexScreenerResponse>(body) {
+        Ok(data) => return Ok(data.pairs.unwrap_or_default()),
+        Err(e) => e.to_string(),
+    };
+    let raw: String = body.chars().take(RAW_SNIPPET_CHARS).collect();
+    let schema_error = |message: String| {
+        warn!("🧩 DEXSCREENER SCHEMA CHANGE on {}: {} - raw: {}", endpoint, message, raw);
+        DexScreenerSchemaError { endpoint: endpoint.to_string(), message, raw: raw.clone() }
+    };
+
+    let value: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| schema_error(format!("invalid JSON ({})", e)))?;
+    if !value.is_object() {
+        return Err(schema_error(format!("top level is not an object ({})", strict_error)));
+    }
+    let items = match value.get("pairs") {
+        None | Some(serde_json::Value::Null) => return Ok(Vec::new()),
+        Some(serde_json::Value::Array(items)) => items,
+        Some(_) => return Err(schema_error(format!("`pairs` is not a list ({})", strict_error))),
+    };
+
+    let pairs: Vec<DexPair> = items.iter().filter_map(lenient_pair).collect();
+    if pairs.is_empty() && !items.is_empty() {
+        return Err(schema_error(format!("no pair matches the known shape ({})", strict_error)));
+    }
+    warn!(
+        "🧩 DEXSCREENER SCHEMA CHANGE on {}: {} - recovered {}/{} pairs leniently - raw: {}",
+        endpoint, strict_error, pairs.len(), items.len(), raw
+    );
+    Ok(pairs)
+}
+
+/// One pair from untyped JSON: required fields must be strings, the rest is best effort
+/// (numbers may arrive as strings and vice versa)
+fn lenient_pair(value: &serde_json::Value) -> Option<DexPair> {
+    if let Ok(pair) = serde_json::from_value::<DexPair>(value.clone()) {
+        return Some(pair);
+    }
+    let token = |key: &str| {
+        let token = value.get(key)?;
+        Some(DexToken {
+            address: json_string(token, "address")?,
+            name: json_string(token, "name"),
+            symbol: json_string(token, "symbol"),
+        })
+    };
+
+    Some(DexPair {
+        chain_id: json_string(value, "chainId")?,
+        dex_id: json_string(value, "dexId").unwrap_or_default(),
+        pair_address: json_string(value, "pairAddress")?,
+        labels: value
+            .get("labels")
+            .and_then(|labels| labels.as_array())
+            .map(|labels| labels.iter().filter_map(|l| l.as_str().map(str::to_string)).collect())
+            .unwrap_or_default(),
+        base_token: token("baseToken")?,
+        quote_token: token("quoteToken")?,
+        liquidity: value.get("liquidity").filter(|l| l.is_object()).map(|l| DexLiquidity {
+            usd: json_f64(l, "usd"),
+            base: json_f64(l, "base"),
+            quote: json_f64(l, "quote"),
+        }),
+        price_usd: json_string(value, "priceUsd"),
+        price_native: json_string(value, "priceNative"),
+        volume: value.get("volume").filter(|v| v.is_object()).map(|v| DexVolume { h24: json_f64(v, "h24") }),
+    })
+}
+
+/// String field, numbers rendered as strings
+fn json_string(value: &serde_json::Value, key: &str) -> Option<String> {
+    match value.get(key)? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Numeric field, numeric strings parsed
+fn json_f64(value: &serde_json::Value, key: &str) -> Option<f64> {
+    match value.get(key)? {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn group_pairs_by_token(
+    requested: &[&str],
+    pairs: Vec<DexPair>,
+    by_token: &mut HashMap<String, Vec<DexPair>>,
+) {
+    let requested: Vec<String> = requested.iter().map(|a| a.to_lowercase()).collect();
+
+    for pair in pairs {
+        let base = pair.base_token.address.to_lowercase();
+        let quote = pair.quote_token.address.to_lowercase();
+
+        if requested.contains(&quote) && quote != base {
+            by_token.entry(quote).or_default().push(pair.clone());
+        }
+        if requested.contains(&base) {
+            by_token.entry(base).or_default().push(pair);
+        }
+    }
+}
+
+/// A token matching a search query, aggregated over its pairs in the results
+#[derive(Debug, Clone)]
+pub struct SearchCandidate {
+    pub address: String,
+    /// DexScreener chain name ("ethereum", "bsc", "solana", ...)
+    pub chain: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    /// Summed USD liquidity of the token's pairs
+    pub liquidity_usd: f64,
+    pub pair_count: usize,
+    /// Deepest pair and its DEX
+    pub best_pair_address: String,
+    pub best_dex: String,
+    /// USD price from the deepest pair where the token is the base side
+    pub price_usd: Option<String>,
+    /// Symbol equals the query (case-insensitive)
+    pub exact_symbol_match: bool,
+}
+
+/// Group search results into one candidate per (chain, token) whose symbol or
+/// name matches `query`. Exact symbol matches rank first, then total liquidity -
+/// copycat tickers stay visible as lower-ranked candidates instead of being guessed away
+pub fn rank_search_candidates(query: &str, pairs: &[DexPair], chain: Option<&str>) -> Vec<SearchCandidate> {
+    let query = query.trim().to_lowercase();
+    let matches = |token: &DexToken| {
+        let symbol = token.symbol.as_deref().unwrap_or_default().to_lowercase();
+        let name = token.name.as_deref().unwrap_or_default().to_lowercase();
+        !query.is_empty() && (symbol.contains(&query) || name.contains(&query))
+    };
+
+    // Deepest pairs first: a token's first pair is its best one
+    let mut pairs = pairs.to_vec();
+    DexScreenerClient::sort_by_liquidity(&mut pairs);
+
+    let mut by_token: HashMap<(String, String), SearchCandidate> = HashMap::new();
+    for pair in &pairs {
+        if chain.is_some_and(|c| !pair.chain_id.eq_ignore_ascii_case(c)) {
+            continue;
+        }
+        let liquidity = pair.liquidity.as_ref().and_then(|l| l.usd).unwrap_or(0.0);
+        let sides = [(&pair.base_token, true), (&pair.quote_token, false)];
+        for (token, is_base) in sides {
+            if !matches(token) {
+                continue;
+            }
+            let key = (pair.chain_id.to_lowercase(), token.address.to_lowercase());
+            let candidate = by_token.entry(key).or_insert_with(|| SearchCandidate {
+                address: token.address.clone(),
+                chain: pair.chain_id.to_lowercase(),
+                name: token.name.clone(),
+                symbol: token.symbol.clone(),
+                liquidity_usd: 0.0,
+                pair_count: 0,
+                best_pair_address: pair.pair_address.clone(),
+                best_dex: pair.dex_id.clone(),
+                price_usd: None,
+                exact_symbol_match: token.symbol.as_deref().is_some_and(|s| s.eq_ignore_ascii_case(&query)),
+            });
+            if is_base && candidate.price_usd.is_none() {
+                candidate.price_usd = pair.price_usd.clone();
+            }
+            candidate.liquidity_usd += liquidity;
+            candidate.pair_count += 1;
+        }
+    }
+
+    let mut candidates: Vec<SearchCandidate> = by_token.into_values().collect();
+    candidates.sort_by(|a, b| {
+        b.exact_symbol_match
+            .cmp(&a.exact_symbol_match)
+            .then(b.liquidity_usd.partial_cmp(&a.liquidity_usd).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    candidates
+}
+
 /// Discovered DEX info from DexScreener
 #[derive(Debug, Clone)]
 pub struct DiscoveredDex {
@@ -264,6 +764,17 @@ pub struct DiscoveredDex {
     pub quote_token: String,
 }
 
+/// A chain an auto-detected address trades on
+#[derive(Debug, Clone)]
+pub struct ChainCandidate {
+    pub chain_id: u64,
+    pub chain_name: String,
+    /// Total liquidity across the chain's pairs
+    pub liquidity_usd: f64,
+    /// Most liquid V2-compatible pair on the chain (router to simulate against)
+    pub best_v2_dex: Option<DiscoveredDex>,
+}
+
 /// Auto-detected chain and DEX info for a token
 /// Used when user doesn't specify chain_id
 #[derive(Debug, Clone)]
@@ -289,10 +800,16 @@ pub struct AutoDetectedToken {
     // ============================================
     /// Price in USD
     pub price_usd: Option<String>,
+    /// Price of the pair's base token in units of its quote token
+    pub price_native: Option<String>,
+    /// Base token of the best pair (the side `price_native` is quoted for)
+    pub base_token_address: Option<String>,
     /// 24h trading volume
     pub volume_24h_usd: Option<f64>,
     /// Pair address
     pub pair_address: Option<String>,
+    /// Chains with meaningful liquidity, most liquid first (more than one = ambiguous)
+    pub chain_candidates: Vec<ChainCandidate>,
 }
 
 impl DexPair {
@@ -375,6 +892,161 @@ impl DexPair {
 mod tests {
     use super::*;
 
+    fn pair(base: &str, quote: &str, liquidity: f64) -> DexPair {
+        DexPair {
+            chain_id: "bsc".to_string(),
+            dex_id: "pancakeswap".to_string(),
+            pair_address: format!("0xpair{}", liquidity),
+            labels: vec![],
+            base_token: DexToken { address: base.to_string(), name: None, symbol: None },
+            quote_token: DexToken { address: quote.to_string(), name: None, symbol: None },
+            liquidity: Some(DexLiquidity { usd: Some(liquidity), base: None, quote: None }),
+            price_usd: None,
+            price_native: None,
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_unlisted_token_cache() {
+        let cache = UnlistedTokenCache::new(Duration::from_secs(60));
+        assert!(!cache.is_unlisted("0xAbC"));
+        cache.record("0xAbC");
+        assert!(cache.is_unlisted("0xabc"));
+        // Solana mints keep their case
+        cache.record("So1anaMint");
+        assert!(cache.is_unlisted("So1anaMint"));
+        assert!(!cache.is_unlisted("so1anamint"));
+        assert_eq!(cache.len(), 2);
+
+        // Zero TTL = disabled
+        let disabled = UnlistedTokenCache::new(Duration::ZERO);
+        disabled.record("0xabc");
+        assert!(!disabled.is_unlisted("0xabc"));
+        assert!(disabled.is_empty());
+    }
+
+    #[test]
+    fn test_group_pairs_by_token() {
+        let mut by_token = HashMap::new();
+        let pairs = vec![
+            pair("0xAAA", "0xweth", 100.0),
+            pair("0xBBB", "0xAAA", 50.0),
+            pair("0xccc", "0xweth", 10.0),
+        ];
+        group_pairs_by_token(&["0xaaa", "0xBBB"], pairs, &mut by_token);
+
+        assert_eq!(by_token["0xaaa"].len(), 2);
+        assert_eq!(by_token["0xbbb"].len(), 1);
+        assert!(!by_token.contains_key("0xccc"));
+
+        let detected = DexScreenerClient::auto_detect_from_pairs(&by_token["0xbbb"]).unwrap();
+        assert_eq!(detected.chain_id, 56);
+    }
+
+    #[test]
+    fn test_auto_detect_on_chain() {
+        // Most liquid on BSC, also listed on Base
+        let on_base = DexPair { chain_id: "base".to_string(), dex_id: "uniswap".to_string(), ..pair("0xaaa", "0xweth", 10.0) };
+        let pairs = vec![pair("0xaaa", "0xweth", 100.0), on_base];
+
+        assert_eq!(DexScreenerClient::auto_detect_from_pairs(&pairs).unwrap().chain_id, 56);
+        let detected = DexScreenerClient::auto_detect_on_chain(&pairs, 8453).unwrap();
+        assert_eq!(detected.chain_id, 8453);
+        assert_eq!(detected.best_dex.pair_address, "0xpair10");
+        assert!(DexScreenerClient::auto_detect_on_chain(&pairs, 1).is_err());
+    }
+
+    #[test]
+    fn test_parse_pairs_schema_drift() {
+        let known = r#"{"pairs":[{"chainId":"bsc","dexId":"pancakeswap","pairAddress":"0xp1",
+            "baseToken":{"address":"0xaaa","symbol":"AAA"},"quoteToken":{"address":"0xweth"},
+            "liquidity":{"usd":1500.5},"priceUsd":"0.01"}]}"#;
+        let pairs = parse_pairs("dexscreener:/tokens", known).unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].liquidity.as_ref().and_then(|l| l.usd), Some(1500.5));
+        assert!(parse_pairs("dexscreener:/tokens", r#"{"pairs":null}"#).unwrap().is_empty());
+
+        // liquidity.usd turned into a string, priceUsd into a number, labels into an object;
+        // the second pair lost its quote token and is dropped
+        let drifted = r#"{"pairs":[{"chainId":"bsc","dexId":"pancakeswap","pairAddress":"0xp1",
+            "labels":{"v":"2"},"baseToken":{"address":"0xaaa"},"quoteToken":{"address":"0xweth"},
+            "liquidity":{"usd":"1500.5"},"priceUsd":0.01},
+            {"chainId":"bsc","pairAddress":"0xp2","baseToken":{"address":"0xaaa"}}]}"#;
+        let pairs = parse_pairs("dexscreener:/tokens", drifted).unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].pair_address, "0xp1");
+        assert_eq!(pairs[0].liquidity.as_ref().and_then(|l| l.usd), Some(1500.5));
+        assert_eq!(pairs[0].price_usd.as_deref(), Some("0.01"));
+        assert!(pairs[0].labels.is_empty());
+
+        // Nothing recoverable: a typed error carrying the raw body
+        let err = parse_pairs("dexscreener:/search", r#"{"pairs":{"items":[]}}"#).unwrap_err();
+        assert_eq!(err.endpoint, "dexscreener:/search");
+        assert!(err.raw.contains("items"));
+        assert!(parse_pairs("dexscreener:/tokens", "<html>502</html>").is_err());
+        assert!(parse_pairs("dexscreener:/tokens", "[]").is_err());
+        let err = parse_pairs("dexscreener:/tokens", r#"{"pairs":[{"id":1}]}"#).unwrap_err();
+        assert!(err.to_string().starts_with("DexScreener schema change on dexscreener:/tokens"));
+    }
+
+    #[test]
+    fn test_chain_candidates() {
+        let on_chain = |chain: &str, liquidity: f64| DexPair {
+            chain_id: chain.to_string(),
+            ..pair("0xAAA", "0xweth", liquidity)
+        };
+        let pairs = vec![
+            on_chain("bsc", 50_000.0),
+            on_chain("ethereum", 30_000.0),
+            on_chain("ethereum", 25_000.0),
+            on_chain("base", 500.0),
+            on_chain("fantom", 90_000.0),
+        ];
+        let candidates = DexScreenerClient::chain_candidates(&pairs);
+        // Liquidity summed per chain; dust chains and unknown chains dropped
+        let ranked: Vec<(u64, f64)> = candidates.iter().map(|c| (c.chain_id, c.liquidity_usd)).collect();
+        assert_eq!(ranked, vec![(1, 55_000.0), (56, 50_000.0)]);
+
+        let detected = DexScreenerClient::auto_detect_from_pairs(&pairs[..1]).unwrap();
+        assert_eq!(detected.chain_candidates.len(), 1);
+    }
+
+    #[test]
+    fn test_rank_search_candidates() {
+        let token = |address: &str, symbol: &str| DexToken {
+            address: address.to_string(),
+            name: Some(format!("{} Token", symbol)),
+            symbol: Some(symbol.to_string()),
+        };
+        let mut pepe = pair("0xpepe", "0xweth", 500.0);
+        pepe.base_token = token("0xPEPE", "PEPE");
+        pepe.price_usd = Some("0.00001".to_string());
+        let mut pepe_small = pair("0xpepe", "0xusdt", 100.0);
+        pepe_small.base_token = token("0xpepe", "PEPE");
+        let mut copycat = pair("0xcopy", "0xweth", 10_000.0);
+        copycat.base_token = token("0xcopy", "PEPE2");
+        let mut other_chain = pair("0xpepe", "0xweth", 50.0);
+        other_chain.chain_id = "ethereum".to_string();
+        other_chain.base_token = token("0xpepe", "PEPE");
+
+        let pairs = [pepe_small, copycat, pepe, other_chain];
+        let ranked = rank_search_candidates("pepe", &pairs, None);
+        assert_eq!(ranked.len(), 3);
+        // Exact symbol beats the more liquid copycat; pairs aggregate per (chain, token)
+        assert_eq!((ranked[0].chain.as_str(), ranked[0].pair_count), ("bsc", 2));
+        assert_eq!(ranked[0].liquidity_usd, 600.0);
+        assert_eq!(ranked[0].best_pair_address, "0xpair500");
+        assert_eq!(ranked[0].price_usd.as_deref(), Some("0.00001"));
+        assert_eq!(ranked[1].chain, "ethereum");
+        assert_eq!(ranked[2].symbol.as_deref(), Some("PEPE2"));
+        assert!(!ranked[2].exact_symbol_match);
+
+        let on_ethereum = rank_search_candidates("PEPE", &pairs, Some("ethereum"));
+        assert_eq!(on_ethereum.len(), 1);
+        assert!(rank_search_candidates("PEPE", &pairs, Some("polygon")).is_empty());
+    }
+
     #[tokio::test]
     async fn test_dexscreener_client() {
         let client = DexScreenerClient::new();
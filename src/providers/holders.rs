@@ -0,0 +1,299 @@
+//! Holder Count Estimator
+//!
+//! Replays a token's `Transfer` logs and counts addresses whose net balance
+//! over the scanned range is positive. Feeds the ML `low_holders` /
+//! `many_holders` weights and the `holder_count` API field.
+//!
+//! Logs are fetched in block chunks (providers cap `eth_getLogs` ranges; a
+//! rejected range is halved and retried). At most `max_blocks` are scanned:
+//! older tokens only get their most recent window, flagged as `truncated`.
+
+use alloy_primitives::{Address, I256, U256};
+use dashmap::DashMap;
+use eyre::Result;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use super::rpc::RpcProvider;
+
+/// keccak256("Transfer(address,address,uint256)")
+pub const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// Blocks per eth_getLogs request (within common provider range limits)
+pub const DEFAULT_LOG_CHUNK_BLOCKS: u64 = 2_000;
+
+/// Smallest chunk tried after the provider rejects a range
+const MIN_LOG_CHUNK_BLOCKS: u64 = 50;
+
+/// Default cap on blocks scanned per token (`HOLDER_SCAN_MAX_BLOCKS`)
+pub const DEFAULT_HOLDER_SCAN_MAX_BLOCKS: u64 = 50_000;
+
+/// Holder counts are cached this long (holders move, but not per request)
+pub const HOLDER_COUNT_TTL_SECS: u64 = 600;
+
+/// Estimated holders of a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HolderCount {
+    /// Addresses with a net-positive balance over the scanned range
+    pub holders: u32,
+    /// First block scanned
+    pub from_block: u64,
+    /// Last block scanned
+    pub to_block: u64,
+    /// Range was cut to `max_blocks` - holders from before `from_block` are missed
+    pub truncated: bool,
+}
+
+/// Holder counts keyed by (chain_id, token), expiring after a TTL
+pub struct HolderCountCache {
+    store: DashMap<(u64, Address), (HolderCount, Instant)>,
+    ttl: Duration,
+}
+
+impl HolderCountCache {
+    pub fn new(ttl_secs: u64) -> Self {
+        Self {
+            store: DashMap::new(),
+            ttl: Duration::from_secs(ttl_secs),
+        }
+    }
+
+    pub fn get(&self, chain_id: u64, token: Address) -> Option<HolderCount> {
+        let entry = self.store.get(&(chain_id, token))?;
+        let (count, stored_at) = *entry;
+        drop(entry);
+        if stored_at.elapsed() > self.ttl {
+            self.store.remove(&(chain_id, token));
+            return None;
+        }
+        Some(count)
+    }
+
+    pub fn insert(&self, chain_id: u64, token: Address, count: HolderCount) {
+        self.store.insert((chain_id, token), (count, Instant::now()));
+    }
+
+    /// Drop expired entries (returns how many were removed)
+    pub fn cleanup_expired(&self) -> usize {
+        let before = self.store.len();
+        self.store.retain(|_, (_, stored_at)| stored_at.elapsed() <= self.ttl);
+        before - self.store.len()
+    }
+}
+
+impl Default for HolderCountCache {
+    fn default() -> Self {
+        Self::new(HOLDER_COUNT_TTL_SECS)
+    }
+}
+
+/// Counts holders from Transfer logs over RPC
+pub struct HolderCounter {
+    provider: RpcProvider,
+    max_blocks: u64,
+    chunk_blocks: u64,
+    cache: Option<Arc<HolderCountCache>>,
+}
+
+impl HolderCounter {
+    pub fn new(provider: RpcProvider) -> Self {
+        Self {
+            provider,
+            max_blocks: DEFAULT_HOLDER_SCAN_MAX_BLOCKS,
+            chunk_blocks: DEFAULT_LOG_CHUNK_BLOCKS,
+            cache: None,
+        }
+    }
+
+    /// Cap on blocks scanned per token (older history is skipped)
+    pub fn with_max_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_blocks = max_blocks.max(1);
+        self
+    }
+
+    /// Blocks per eth_getLogs request
+    #[allow(dead_code)]
+    pub fn with_chunk_blocks(mut self, chunk_blocks: u64) -> Self {
+        self.chunk_blocks = chunk_blocks.max(MIN_LOG_CHUNK_BLOCKS);
+        self
+    }
+
+    /// Share a result cache (keyed by this provider's chain)
+    pub fn with_cache(mut self, cache: Arc<HolderCountCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Addresses holding `token`, from its Transfer logs between `from_block`
+    /// (e.g. the creation block, 0 if unknown) and head, capped at `max_blocks`
+    pub async fn estimate_holder_count(&self, token: Address, from_block: u64) -> Result<HolderCount> {
+        let chain_id = self.provider.chain_id();
+        if let Some(cached) = self.cache.as_ref().and_then(|c| c.get(chain_id, token)) {
+            return Ok(cached);
+        }
+
+        let head = self.provider.block_number().await?;
+        let (start, truncated) = scan_start(from_block, head, self.max_blocks);
+
+        let mut balances: HashMap<Address, I256> = HashMap::new();
+        let mut chunk = self.chunk_blocks;
+        let mut block = start;
+        while block <= head {
+            let end = block.saturating_add(chunk - 1).min(head);
+            match self.transfer_logs(token, block, end).await {
+                Ok(transfers) => {
+                    for (from, to, value) in transfers {
+                        apply_transfer(&mut balances, from, to, value);
+                    }
+                    block = end + 1;
+                }
+                // Range rejected (too many results / blocks) - halve and retry
+                Err(e) if chunk > MIN_LOG_CHUNK_BLOCKS => {
+                    debug!("📉 eth_getLogs {}..{} rejected ({}), halving chunk", block, end, e);
+                    chunk = (chunk / 2).max(MIN_LOG_CHUNK_BLOCKS);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let count = HolderCount {
+            holders: count_holders(&balances),
+            from_block: start,
+            to_block: head,
+            truncated,
+        };
+        info!(
+            "👥 {:?}: ~{} holders (blocks {}..{}{})",
+            token,
+            count.holders,
+            start,
+            head,
+            if truncated { ", truncated" } else { "" }
+        );
+        if let Some(cache) = &self.cache {
+            cache.insert(chain_id, token, count);
+        }
+        Ok(count)
+    }
+
+    /// Decoded (from, to, value) of every Transfer in `from..=to`
+    async fn transfer_logs(&self, token: Address, from: u64, to: u64) -> Result<Vec<(Address, Address, U256)>> {
+        let params = serde_json::json!([{
+            "address": format!("{:?}", token),
+            "fromBlock": format!("0x{:x}", from),
+            "toBlock": format!("0x{:x}", to),
+            "topics": [TRANSFER_TOPIC],
+        }]);
+        let logs = self.provider.call::<Vec<serde_json::Value>>("eth_getLogs", params).await?;
+        Ok(logs.iter().filter_map(parse_transfer_log).collect())
+    }
+}
+
+/// First block to scan and whether the range was cut to `max_blocks`
+fn scan_start(from_block: u64, head: u64, max_blocks: u64) -> (u64, bool) {
+    let earliest = head.saturating_sub(max_blocks - 1);
+    if from_block < earliest {
+        (earliest, true)
+    } else {
+        (from_block.min(head), false)
+    }
+}
+
+/// Decode a Transfer log; ERC-721 style logs (indexed value) and malformed logs are skipped
+fn parse_transfer_log(log: &serde_json::Value) -> Option<(Address, Address, U256)> {
+    let topics = log.get("topics")?.as_array()?;
+    if topics.len() != 3 {
+        return None;
+    }
+    let from = topic_address(topics[1].as_str()?)?;
+    let to = topic_address(topics[2].as_str()?)?;
+    let data = hex::decode(log.get("data")?.as_str()?.trim_start_matches("0x")).ok()?;
+    if data.len() < 32 {
+        return None;
+    }
+    Some((from, to, U256::from_be_slice(&data[..32])))
+}
+
+/// Address in the low 20 bytes of an indexed topic
+fn topic_address(topic: &str) -> Option<Address> {
+    let bytes = hex::decode(topic.trim_start_matches("0x")).ok()?;
+    (bytes.len() == 32).then(|| Address::from_slice(&bytes[12..]))
+}
+
+fn apply_transfer(balances: &mut HashMap<Address, I256>, from: Address, to: Address, value: U256) {
+    let value = I256::try_from(value).unwrap_or(I256::MAX);
+    let sender = balances.entry(from).or_insert(I256::ZERO);
+    *sender = sender.saturating_sub(value);
+    let receiver = balances.entry(to).or_insert(I256::ZERO);
+    *receiver = receiver.saturating_add(value);
+}
+
+/// Net-positive balances, excluding the zero address (mint source / burn sink)
+fn count_holders(balances: &HashMap<Address, I256>) -> u32 {
+    let holders = balances
+        .iter()
+        .filter(|(address, balance)| !address.is_zero() && balance.is_positive())
+        .count();
+    holders.min(u32::MAX as usize) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer_log(from: Address, to: Address, value: u64) -> serde_json::Value {
+        let word = |a: Address| format!("0x{}{}", "00".repeat(12), hex::encode(a));
+        serde_json::json!({
+            "topics": [TRANSFER_TOPIC, word(from), word(to)],
+            "data": format!("0x{}", hex::encode(U256::from(value).to_be_bytes::<32>())),
+        })
+    }
+
+    #[test]
+    fn test_count_holders_from_logs() {
+        let (a, b, c) = (Address::repeat_byte(0x0a), Address::repeat_byte(0x0b), Address::repeat_byte(0x0c));
+        let logs = vec![
+            transfer_log(Address::ZERO, a, 1_000), // mint
+            transfer_log(a, b, 400),
+            transfer_log(a, c, 600), // a fully sold out
+            transfer_log(c, Address::ZERO, 100), // burn
+        ];
+
+        let mut balances = HashMap::new();
+        for (from, to, value) in logs.iter().filter_map(parse_transfer_log) {
+            apply_transfer(&mut balances, from, to, value);
+        }
+        assert_eq!(count_holders(&balances), 2);
+
+        // ERC-721 Transfer (tokenId indexed) is not an ERC-20 transfer
+        let mut nft = transfer_log(a, b, 1);
+        nft["topics"].as_array_mut().unwrap().push(serde_json::json!(format!("0x{}", "00".repeat(32))));
+        assert!(parse_transfer_log(&nft).is_none());
+    }
+
+    #[test]
+    fn test_scan_start_cap() {
+        assert_eq!(scan_start(900, 1_000, 500), (900, false));
+        assert_eq!(scan_start(0, 1_000, 500), (501, true));
+        assert_eq!(scan_start(0, 100, 500), (0, false));
+        assert_eq!(scan_start(2_000, 1_000, 500), (1_000, false));
+    }
+
+    #[test]
+    fn test_holder_cache_ttl() {
+        let token = Address::repeat_byte(0x01);
+        let count = HolderCount { holders: 42, from_block: 1, to_block: 2, truncated: false };
+
+        let cache = HolderCountCache::new(60);
+        cache.insert(1, token, count);
+        assert_eq!(cache.get(1, token), Some(count));
+        assert_eq!(cache.get(56, token), None);
+
+        let expired = HolderCountCache::new(0);
+        expired.insert(1, token, count);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(expired.get(1, token), None);
+    }
+}
@@ -10,18 +10,26 @@
 //! - Concurrent request handling
 //! - WebSocket subscriptions for real-time events
 //! - Trace API for deep honeypot analysis
+//! - Forked REVM state over RPC (shared per-chain cache)
+//! - Holder counts from Transfer logs (short-TTL cache)
 //! - Solana Yellowstone gRPC + DAS API
 
 pub mod alchemy;
 pub mod dexscreener;
+pub mod holders;
+pub mod reserves;
 pub mod rpc;
+pub mod rpc_db;
 pub mod solana;
 pub mod trace;
 pub mod websocket;
 
 pub use alchemy::*;
 pub use dexscreener::*;
+pub use holders::*;
+pub use reserves::*;
 pub use rpc::*;
+pub use rpc_db::*;
 pub use solana::*;
 pub use trace::*;
 pub use websocket::*;
@@ -0,0 +1,173 @@
+//! Uniswap V2 Pair Reserve Reader
+//!
+//! Reads `token0()`, `token1()` and `getReserves()` straight from a V2 pair.
+//! Used to corroborate the aggregator (DexScreener) price: a honeypot pair can
+//! report large reserves while the token side is untransferable, and a
+//! manipulated pair often implies a price nobody actually trades at.
+
+use alloy_primitives::{Address, U256};
+use eyre::{eyre, Result};
+use tracing::debug;
+
+use super::rpc::RpcProvider;
+
+/// Relative divergence between reserve-implied and reported price that counts as a mismatch
+/// 0.5 = one price is more than 50% away from the other
+pub const RESERVE_PRICE_MISMATCH_THRESHOLD: f64 = 0.5;
+
+/// token0() selector
+const TOKEN0_SELECTOR: &str = "0x0dfe1681";
+/// token1() selector
+const TOKEN1_SELECTOR: &str = "0xd21220a7";
+/// getReserves() selector
+const GET_RESERVES_SELECTOR: &str = "0x0902f1ac";
+/// decimals() selector
+const DECIMALS_SELECTOR: &str = "0x313ce567";
+
+/// On-chain state of a V2 pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairReserves {
+    pub token0: Address,
+    pub token1: Address,
+    pub reserve0: U256,
+    pub reserve1: U256,
+    pub decimals0: u8,
+    pub decimals1: u8,
+}
+
+impl PairReserves {
+    /// Price of `token` in units of the other pair token, implied by reserves
+    /// None if `token` is not in the pair or its reserve is empty
+    pub fn price_of(&self, token: Address) -> Option<f64> {
+        let (base, base_dec, quote, quote_dec) = if token == self.token0 {
+            (self.reserve0, self.decimals0, self.reserve1, self.decimals1)
+        } else if token == self.token1 {
+            (self.reserve1, self.decimals1, self.reserve0, self.decimals0)
+        } else {
+            return None;
+        };
+
+        let base = to_units(base, base_dec);
+        let quote = to_units(quote, quote_dec);
+        (base > 0.0).then(|| quote / base)
+    }
+}
+
+/// Relative divergence |a - b| / min(a, b) (symmetric: 2x and 0.5x score the same)
+pub fn price_divergence(reserve_price: f64, reported_price: f64) -> Option<f64> {
+    let low = reserve_price.min(reported_price);
+    if !low.is_finite() || low <= 0.0 || !reserve_price.is_finite() || !reported_price.is_finite() {
+        return None;
+    }
+    Some((reserve_price - reported_price).abs() / low)
+}
+
+/// Does the reserve-implied price diverge from the reported one beyond the threshold?
+pub fn is_reserve_price_mismatch(reserve_price: f64, reported_price: f64) -> bool {
+    price_divergence(reserve_price, reported_price)
+        .is_some_and(|d| d > RESERVE_PRICE_MISMATCH_THRESHOLD)
+}
+
+fn to_units(amount: U256, decimals: u8) -> f64 {
+    let raw: f64 = amount.to_string().parse().unwrap_or(0.0);
+    raw / 10f64.powi(decimals as i32)
+}
+
+/// Reads reserves from Uniswap V2 compatible pairs
+pub struct ReserveReader {
+    provider: RpcProvider,
+}
+
+impl ReserveReader {
+    pub fn new(provider: RpcProvider) -> Self {
+        Self { provider }
+    }
+
+    /// Read token0/token1, reserves and both tokens' decimals
+    pub async fn read_pair(&self, pair: &str) -> Result<PairReserves> {
+        let token0 = self.call_address(pair, TOKEN0_SELECTOR).await?;
+        let token1 = self.call_address(pair, TOKEN1_SELECTOR).await?;
+
+        let reserves = decode_words(&self.provider.eth_call(pair, GET_RESERVES_SELECTOR).await?)?;
+        if reserves.len() < 2 {
+            return Err(eyre!("getReserves returned {} words", reserves.len()));
+        }
+
+        let decimals0 = self.call_decimals(token0).await?;
+        let decimals1 = self.call_decimals(token1).await?;
+        debug!("📊 Reserves {}: {} / {}", pair, reserves[0], reserves[1]);
+
+        Ok(PairReserves {
+            token0,
+            token1,
+            reserve0: reserves[0],
+            reserve1: reserves[1],
+            decimals0,
+            decimals1,
+        })
+    }
+
+    async fn call_address(&self, to: &str, selector: &str) -> Result<Address> {
+        let words = decode_words(&self.provider.eth_call(to, selector).await?)?;
+        let word = words.first().ok_or_else(|| eyre!("Empty result from {}", to))?;
+        Ok(Address::from_word(word.to_be_bytes::<32>().into()))
+    }
+
+    async fn call_decimals(&self, token: Address) -> Result<u8> {
+        let words = decode_words(&self.provider.eth_call(&format!("{:?}", token), DECIMALS_SELECTOR).await?)?;
+        words
+            .first()
+            .and_then(|w| u8::try_from(*w).ok())
+            .ok_or_else(|| eyre!("Invalid decimals() for {:?}", token))
+    }
+}
+
+/// Split an eth_call hex result into 32-byte words
+fn decode_words(result: &str) -> Result<Vec<U256>> {
+    let bytes = hex::decode(result.trim_start_matches("0x"))
+        .map_err(|e| eyre!("Failed to decode hex: {}", e))?;
+    Ok(bytes.chunks_exact(32).map(U256::from_be_slice).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_price() {
+        let token = Address::repeat_byte(0x01);
+        let weth = Address::repeat_byte(0x02);
+        // 1,000,000 tokens (9 decimals) vs 10 WETH -> 0.00001 WETH per token
+        let reserves = PairReserves {
+            token0: token,
+            token1: weth,
+            reserve0: U256::from(1_000_000u64) * U256::from(10u64).pow(U256::from(9u64)),
+            reserve1: U256::from(10u64) * U256::from(10u64).pow(U256::from(18u64)),
+            decimals0: 9,
+            decimals1: 18,
+        };
+
+        let price = reserves.price_of(token).unwrap();
+        assert!((price - 0.00001).abs() < 1e-12);
+        assert!((reserves.price_of(weth).unwrap() - 100_000.0).abs() < 1e-6);
+        assert!(reserves.price_of(Address::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_price_mismatch() {
+        assert!(!is_reserve_price_mismatch(1.0, 1.2));
+        assert!(is_reserve_price_mismatch(1.0, 2.0));
+        // Symmetric: half the price is as suspicious as double
+        assert!(is_reserve_price_mismatch(2.0, 1.0));
+        // Unusable prices never flag
+        assert!(!is_reserve_price_mismatch(0.0, 1.0));
+        assert!(!is_reserve_price_mismatch(f64::NAN, 1.0));
+    }
+
+    #[test]
+    fn test_decode_words() {
+        let result = format!("0x{}{}", "00".repeat(31) + "05", "00".repeat(31) + "07");
+        let words = decode_words(&result).unwrap();
+        assert_eq!(words, vec![U256::from(5u64), U256::from(7u64)]);
+    }
+}
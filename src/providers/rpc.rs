@@ -2,13 +2,13 @@
 //!
 //! CEO Executive Order Implementation:
 //! 1. Dynamic URL Construction from ALCHEMY_API_KEY
-//! 2. Multi-tier RPC with fallback to public RPCs
+//! 2. Multi-tier RPC with fallback to a weighted pool of public RPCs
 //! 3. Exponential backoff retry logic (Alchemy Best Practice: 1s→2s→4s→8s→...→64s with jitter)
 //! 4. User-Agent header & API key protection
 //! 5. Modular architecture for future Solana support
 //! 6. Gzip compression for 75% speedup on large responses (Alchemy Best Practice)
 //! 7. Batch requests support (max 50 per batch - Alchemy Best Practice)
-//! 8. Concurrent request handling with tokio::spawn
+//! 8. Concurrent request handling with tokio::spawn (bounded, RPC_MAX_CONCURRENT_CALLS)
 //!
 //! Alchemy Documentation Reference:
 //! - Compression: https://alchemy.com/docs/how-to-enable-compression-to-speed-up-json-rpc-blockchain-requests.mdx
@@ -21,22 +21,41 @@
 use eyre::{eyre, Result};
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_ENCODING, USER_AGENT};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
-use tracing::{debug, info, warn};
-use rand::Rng;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+use utoipa::ToSchema;
 
 use crate::utils::constants::{
-    build_alchemy_url, get_alchemy_subdomain, get_public_rpc_fallback,
+    approx_block_time_ms, build_alchemy_url, get_alchemy_subdomain, get_public_rpc_fallback,
     DEFAULT_RPC_TIMEOUT_SECS, SUPPORTED_CHAIN_IDS, USER_AGENT as USER_AGENT_CONST,
 };
+use crate::utils::backoff::{retry_with_backoff, BackoffConfig};
+use crate::utils::latency::UPSTREAM_LATENCY;
+use crate::models::config::{
+    public_rpc_pool, rpc_endpoints, rpc_max_concurrent_calls, url_host, RpcRole, ALCHEMY_ENDPOINT,
+};
 
 // ============================================
 // ALCHEMY BEST PRACTICE CONSTANTS
 // ============================================
 
+/// EIP-1967 implementation slot: keccak256("eip1967.proxy.implementation") - 1
+pub const EIP1967_IMPLEMENTATION_SLOT: &str =
+    "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+
+/// Default lookback for `proxy_upgrade` (7 days)
+pub const PROXY_UPGRADE_WINDOW_SECS: u64 = 7 * 24 * 3600;
+
 /// Maximum batch size (Alchemy recommends 50 for reliability, NOT 1000)
 pub const MAX_BATCH_SIZE: usize = 50;
 
+/// Default in-flight limit of `concurrent_calls` (RPC_MAX_CONCURRENT_CALLS overrides)
+pub const DEFAULT_MAX_CONCURRENT_CALLS: usize = 10;
+
 /// Base retry delay in milliseconds (Alchemy: start at 1000ms)
 pub const ALCHEMY_BASE_RETRY_MS: u64 = 1000;
 
@@ -49,6 +68,24 @@ pub const ALCHEMY_MAX_RETRIES: u32 = 7;
 /// Jitter percentage for retry delay (Alchemy: add random jitter to prevent thundering herd)
 pub const RETRY_JITTER_PERCENT: u64 = 20;
 
+/// Alchemy retry policy (shared by single and batch calls)
+pub const ALCHEMY_BACKOFF: BackoffConfig = BackoffConfig {
+    base_delay_ms: ALCHEMY_BASE_RETRY_MS,
+    max_delay_ms: ALCHEMY_MAX_RETRY_MS,
+    max_attempts: ALCHEMY_MAX_RETRIES,
+    jitter_percent: RETRY_JITTER_PERCENT,
+    min_delay_ms: 100,
+};
+
+/// Retry policy per public fallback endpoint: short, then rotate to the next endpoint
+pub const PUBLIC_RPC_BACKOFF: BackoffConfig = BackoffConfig {
+    base_delay_ms: 500,
+    max_delay_ms: 2000,
+    max_attempts: 2,
+    jitter_percent: RETRY_JITTER_PERCENT,
+    min_delay_ms: 100,
+};
+
 /// Alchemy network identifiers for dynamic URL construction
 #[derive(Debug, Clone, Copy)]
 pub enum AlchemyNetwork {
@@ -109,6 +146,73 @@ impl PublicRpcFallback {
     pub fn get(chain_id: u64) -> Option<&'static str> {
         get_public_rpc_fallback(chain_id)
     }
+
+    /// Weighted pool for a chain (`{PREFIX}_PUBLIC_RPC_URLS`, else the built-in list)
+    pub fn pool(chain_id: u64) -> PublicRpcPool {
+        PublicRpcPool::new(public_rpc_pool(chain_id))
+    }
+}
+
+/// One public endpoint with its configured weight and failure streak
+#[derive(Debug)]
+struct PublicEndpoint {
+    url: String,
+    weight: u32,
+    /// Consecutive failures (reset by a success)
+    strikes: AtomicU32,
+}
+
+impl PublicEndpoint {
+    /// Configured weight shrunk by the failure streak: weight / (1 + strikes)
+    fn effective_weight(&self) -> u64 {
+        (self.weight as u64 * 1000) / (1 + self.strikes.load(Ordering::Relaxed) as u64)
+    }
+}
+
+/// Weighted public RPC fallback pool with health-based ordering
+/// A failing endpoint sinks below healthy ones, so calls rotate through the pool;
+/// one success restores its full weight. Shared by every clone of a provider
+#[derive(Debug, Default)]
+pub struct PublicRpcPool {
+    endpoints: Vec<PublicEndpoint>,
+}
+
+impl PublicRpcPool {
+    pub fn new(entries: Vec<(String, u32)>) -> Self {
+        Self {
+            endpoints: entries
+                .into_iter()
+                .map(|(url, weight)| PublicEndpoint { url, weight, strikes: AtomicU32::new(0) })
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// URLs in try order: highest effective weight first, configured order breaks ties
+    pub fn ordered(&self) -> Vec<&str> {
+        let mut endpoints: Vec<&PublicEndpoint> = self.endpoints.iter().collect();
+        endpoints.sort_by_key(|e| std::cmp::Reverse(e.effective_weight()));
+        endpoints.into_iter().map(|e| e.url.as_str()).collect()
+    }
+
+    pub fn record_success(&self, url: &str) {
+        if let Some(endpoint) = self.endpoints.iter().find(|e| e.url == url) {
+            endpoint.strikes.store(0, Ordering::Relaxed);
+        }
+    }
+
+    pub fn record_failure(&self, url: &str) {
+        if let Some(endpoint) = self.endpoints.iter().find(|e| e.url == url) {
+            endpoint.strikes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
 }
 
 /// Batch JSON-RPC request item
@@ -120,25 +224,68 @@ pub struct BatchRequestItem {
     pub id: u64,
 }
 
-/// Batch JSON-RPC response item
+/// Batch JSON-RPC response item (same leniency as single responses)
 #[derive(Debug, Clone, Deserialize)]
 pub struct BatchResponseItem<T> {
     #[allow(dead_code)]
-    pub jsonrpc: String,
+    #[serde(default)]
+    pub jsonrpc: Option<String>,
     pub result: Option<T>,
     pub error: Option<RpcError>,
-    pub id: u64,
+    /// Number, string or null depending on the endpoint
+    #[serde(default)]
+    pub id: serde_json::Value,
+}
+
+/// Hosts allowed to present an invalid TLS certificate (`SentryConfig::insecure_tls_hosts`).
+/// Unset = every certificate is validated
+static INSECURE_TLS_HOSTS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Install the insecure TLS host allowlist once at startup (later calls are ignored)
+pub fn set_insecure_tls_hosts(hosts: Vec<String>) {
+    let _ = INSECURE_TLS_HOSTS.set(hosts);
+}
+
+/// `url`'s host is on the insecure TLS allowlist
+fn allows_insecure_tls(url: &str) -> bool {
+    INSECURE_TLS_HOSTS
+        .get()
+        .is_some_and(|hosts| url_host(url).is_some_and(|host| hosts.contains(&host)))
+}
+
+/// HTTP client builder for RPC calls to `url`: certificate validation is off only when its
+/// host is on the insecure TLS allowlist (operator's self-signed nodes - insecure)
+pub fn rpc_client_builder(url: &str) -> reqwest::ClientBuilder {
+    reqwest::Client::builder().danger_accept_invalid_certs(allows_insecure_tls(url))
+}
+
+/// Default `rpc_client_builder` client for `url` (a plain client if it fails to build)
+pub fn rpc_http_client(url: &str) -> reqwest::Client {
+    rpc_client_builder(url).build().unwrap_or_default()
+}
+
+/// One configured endpoint of a provider
+#[derive(Debug, Clone)]
+struct RpcEndpoint {
+    url: String,
+    role: RpcRole,
 }
 
 /// RPC Provider with retry logic, fallback support, and Alchemy best practices
 #[derive(Clone)]
 pub struct RpcProvider {
-    /// Primary RPC URL (Alchemy)
-    primary_url: String,
-    /// Fallback RPC URL (public)
-    fallback_url: Option<String>,
+    /// Configured endpoints in try order (Alchemy by default, see `rpc_endpoints`)
+    endpoints: Arc<Vec<RpcEndpoint>>,
+    /// Round-robin cursor over the `Balanced` endpoints, shared by clones
+    balanced_cursor: Arc<AtomicUsize>,
+    /// Public fallback RPCs, tried in health-weighted order
+    fallback_pool: Arc<PublicRpcPool>,
+    /// In-flight limit of `concurrent_calls`, shared by clones
+    call_permits: Arc<Semaphore>,
     /// HTTP client with custom headers (gzip enabled)
     client: reqwest::Client,
+    /// Same client without certificate validation, for endpoints on the insecure TLS allowlist
+    insecure_client: Option<reqwest::Client>,
     /// Chain ID for this provider
     chain_id: u64,
     /// Network name for logging
@@ -147,21 +294,45 @@ pub struct RpcProvider {
 
 impl RpcProvider {
     /// Create a new RPC provider for a chain
+    /// Endpoints come from `{PREFIX}_RPC_ENDPOINTS` (default: Alchemy as the only primary)
     pub fn new(chain_id: u64) -> Result<Self> {
         let network = AlchemyNetwork::from_chain_id(chain_id)
             .ok_or_else(|| eyre!("Unsupported chain_id: {}", chain_id))?;
 
-        let api_key = Self::get_alchemy_key()?;
-        let primary_url = build_alchemy_url(chain_id, &api_key)
-            .ok_or_else(|| eyre!("Cannot build Alchemy URL for chain {}", chain_id))?;
-        let fallback_url = PublicRpcFallback::get(chain_id).map(String::from);
+        let mut endpoints = Vec::new();
+        for (url, role) in rpc_endpoints(chain_id) {
+            if url != ALCHEMY_ENDPOINT {
+                endpoints.push(RpcEndpoint { url, role });
+                continue;
+            }
+            match Self::get_alchemy_keys() {
+                Ok(keys) => endpoints.extend(
+                    keys.iter()
+                        .filter_map(|key| build_alchemy_url(chain_id, key))
+                        .map(|url| RpcEndpoint { url, role }),
+                ),
+                Err(e) => warn!("⚠️ Skipping Alchemy endpoint on {}: {}", network.subdomain(), e),
+            }
+        }
+        if endpoints.is_empty() {
+            return Err(eyre!("ALCHEMY_API_KEY not configured and no {} endpoints set", network.subdomain()));
+        }
+        let fallback_pool = Arc::new(PublicRpcFallback::pool(chain_id));
 
-        let client = Self::build_client()?;
+        let client = Self::build_client(false)?;
+        let insecure_client = endpoints
+            .iter()
+            .any(|endpoint| allows_insecure_tls(&endpoint.url))
+            .then(|| Self::build_client(true))
+            .transpose()?;
 
         Ok(Self {
-            primary_url,
-            fallback_url,
+            endpoints: Arc::new(endpoints),
+            balanced_cursor: Arc::new(AtomicUsize::new(0)),
+            fallback_pool,
+            call_permits: Arc::new(Semaphore::new(rpc_max_concurrent_calls())),
             client,
+            insecure_client,
             chain_id,
             network_name: network.subdomain().to_string(),
         })
@@ -170,19 +341,29 @@ impl RpcProvider {
     /// Create provider for Solana
     pub fn solana() -> Result<Self> {
         let api_key = Self::get_alchemy_key()?;
-        let primary_url = format!("https://solana-mainnet.g.alchemy.com/v2/{}", api_key);
+        let url = format!("https://solana-mainnet.g.alchemy.com/v2/{}", api_key);
 
-        let client = Self::build_client()?;
+        let client = Self::build_client(false)?;
 
         Ok(Self {
-            primary_url,
-            fallback_url: None,
+            endpoints: Arc::new(vec![RpcEndpoint { url, role: RpcRole::Primary }]),
+            balanced_cursor: Arc::new(AtomicUsize::new(0)),
+            fallback_pool: Arc::new(PublicRpcPool::default()),
+            call_permits: Arc::new(Semaphore::new(rpc_max_concurrent_calls())),
             client,
+            insecure_client: None,
             chain_id: 0,
             network_name: "solana-mainnet".to_string(),
         })
     }
 
+    /// Limit `concurrent_calls` to `max` in-flight requests (floored at 1)
+    /// The new pool is shared by clones made from here on
+    pub fn with_max_concurrent_calls(mut self, max: usize) -> Self {
+        self.call_permits = Arc::new(Semaphore::new(max.max(1)));
+        self
+    }
+
     /// Get Alchemy API key from environment
     fn get_alchemy_key() -> Result<String> {
         if let Ok(key) = std::env::var("ALCHEMY_API_KEY") {
@@ -204,8 +385,43 @@ impl RpcProvider {
         Err(eyre!("ALCHEMY_API_KEY not configured"))
     }
 
-    /// Build HTTP client with custom headers (Alchemy Best Practice: gzip compression)
-    fn build_client() -> Result<reqwest::Client> {
+    /// Alchemy keys: `ALCHEMY_API_KEYS` (comma-separated, one endpoint each), else the single key
+    fn get_alchemy_keys() -> Result<Vec<String>> {
+        let keys: Vec<String> = std::env::var("ALCHEMY_API_KEYS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty() && k != "YOUR_API_KEY")
+            .collect();
+        if keys.is_empty() {
+            return Self::get_alchemy_key().map(|key| vec![key]);
+        }
+        info!("🔑 Using {} Alchemy keys from ALCHEMY_API_KEYS (keys hidden)", keys.len());
+        Ok(keys)
+    }
+
+    /// Endpoints in try order for one call: configured order, with the `Balanced` slots
+    /// rotated one step per call so the group shares the load
+    fn try_order(&self) -> Vec<&RpcEndpoint> {
+        let balanced: Vec<&RpcEndpoint> =
+            self.endpoints.iter().filter(|e| e.role == RpcRole::Balanced).collect();
+        let shift = match balanced.len() {
+            0 => 0,
+            n => self.balanced_cursor.fetch_add(1, Ordering::Relaxed) % n,
+        };
+        let mut rotated = balanced.iter().cycle().skip(shift);
+        self.endpoints
+            .iter()
+            .map(|e| match e.role {
+                RpcRole::Balanced => rotated.next().copied().unwrap_or(e),
+                _ => e,
+            })
+            .collect()
+    }
+
+    /// Build HTTP client with custom headers (Alchemy Best Practice: gzip compression),
+    /// without certificate validation when `insecure`
+    fn build_client(insecure: bool) -> Result<reqwest::Client> {
         let mut headers = HeaderMap::new();
         headers.insert(USER_AGENT, HeaderValue::from_static(USER_AGENT_CONST));
         headers.insert("Content-Type", HeaderValue::from_static("application/json"));
@@ -213,6 +429,7 @@ impl RpcProvider {
         headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
 
         reqwest::Client::builder()
+            .danger_accept_invalid_certs(insecure)
             .default_headers(headers)
             .timeout(Duration::from_secs(DEFAULT_RPC_TIMEOUT_SECS))
             .gzip(true) // Enable automatic gzip decompression
@@ -220,6 +437,14 @@ impl RpcProvider {
             .map_err(|e| eyre!("Failed to build HTTP client: {}", e))
     }
 
+    /// Client for `url`: the unvalidated one only for hosts on the insecure TLS allowlist
+    fn client_for(&self, url: &str) -> &reqwest::Client {
+        match &self.insecure_client {
+            Some(insecure) if allows_insecure_tls(url) => insecure,
+            _ => &self.client,
+        }
+    }
+
     /// Execute JSON-RPC call with retry logic and fallback
     pub async fn call<T: for<'de> Deserialize<'de>>(
         &self,
@@ -233,21 +458,34 @@ impl RpcProvider {
             "id": 1
         });
 
-        // Try primary (Alchemy) with retries
-        match self.call_with_retry(&self.primary_url, &payload).await {
-            Ok(result) => return Ok(result),
-            Err(e) => {
-                warn!("⚠️ Primary RPC failed on {}: {}", self.network_name, e);
+        // Configured endpoints in order: primaries with the full retry policy, fallbacks with a short one
+        for endpoint in self.try_order() {
+            let result = match endpoint.role {
+                RpcRole::Fallback => {
+                    retry_with_backoff(&PUBLIC_RPC_BACKOFF, |_| self.execute_call::<T>(&endpoint.url, &payload)).await
+                }
+                _ => self.call_with_retry(&endpoint.url, &payload).await,
+            };
+            match result {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    warn!("⚠️ {:?} RPC {} failed on {}: {}", endpoint.role, mask_url(&endpoint.url), self.network_name, e);
+                }
             }
         }
 
-        // Try fallback if available
-        if let Some(ref fallback) = self.fallback_url {
-            info!("🔄 Trying fallback RPC for {}", self.network_name);
-            match self.call_with_retry(fallback, &payload).await {
-                Ok(result) => return Ok(result),
+        // Rotate through the public pool, healthiest first
+        let total = self.fallback_pool.len();
+        for (idx, fallback) in self.fallback_pool.ordered().into_iter().enumerate() {
+            info!("🔄 Trying fallback RPC {}/{} for {}", idx + 1, total, self.network_name);
+            match retry_with_backoff(&PUBLIC_RPC_BACKOFF, |_| self.execute_call::<T>(fallback, &payload)).await {
+                Ok(result) => {
+                    self.fallback_pool.record_success(fallback);
+                    return Ok(result);
+                }
                 Err(e) => {
-                    warn!("⚠️ Fallback RPC also failed: {}", e);
+                    self.fallback_pool.record_failure(fallback);
+                    warn!("⚠️ Fallback RPC {}/{} failed: {}", idx + 1, total, e);
                 }
             }
         }
@@ -261,46 +499,38 @@ impl RpcProvider {
         url: &str,
         payload: &serde_json::Value,
     ) -> Result<T> {
-        let mut last_error = None;
-
-        for attempt in 0..ALCHEMY_MAX_RETRIES {
-            if attempt > 0 {
-                // Alchemy Best Practice: Exponential backoff with jitter
-                let base_delay = ALCHEMY_BASE_RETRY_MS * (2_u64.pow(attempt - 1));
-                let capped_delay = base_delay.min(ALCHEMY_MAX_RETRY_MS);
-                
-                // Add random jitter (±20%) to prevent thundering herd
-                let jitter_range = (capped_delay * RETRY_JITTER_PERCENT) / 100;
-                let jitter: i64 = rand::thread_rng().gen_range(-(jitter_range as i64)..=(jitter_range as i64));
-                let final_delay = (capped_delay as i64 + jitter).max(100) as u64;
-                
-                debug!("⏳ Retry {}/{} after {}ms (base: {}ms, jitter: {}ms)", 
-                    attempt + 1, ALCHEMY_MAX_RETRIES, final_delay, capped_delay, jitter);
-                tokio::time::sleep(Duration::from_millis(final_delay)).await;
-            }
-
-            match self.execute_call::<T>(url, payload).await {
-                Ok(result) => return Ok(result),
-                Err(e) => {
-                    if e.to_string().contains("429") || e.to_string().contains("rate limit") {
-                        warn!("⏳ Rate limited (HTTP 429), backing off (attempt {}/{})", 
-                            attempt + 1, ALCHEMY_MAX_RETRIES);
-                    }
-                    last_error = Some(e);
+        retry_with_backoff(&ALCHEMY_BACKOFF, |attempt| async move {
+            let result = self.execute_call::<T>(url, payload).await;
+            if let Err(e) = &result {
+                if e.to_string().contains("429") || e.to_string().contains("rate limit") {
+                    warn!("⏳ Rate limited (HTTP 429), backing off (attempt {}/{})",
+                        attempt + 1, ALCHEMY_MAX_RETRIES);
                 }
             }
-        }
-
-        Err(last_error.unwrap_or_else(|| eyre!("Unknown error after {} retries", ALCHEMY_MAX_RETRIES)))
+            result
+        })
+        .await
     }
 
-    /// Execute single RPC call
+    /// Execute single RPC call, timed into `UPSTREAM_LATENCY` under its method name
     async fn execute_call<T: for<'de> Deserialize<'de>>(
         &self,
         url: &str,
         payload: &serde_json::Value,
     ) -> Result<T> {
-        let response = self.client
+        let started = Instant::now();
+        let result = self.send_call::<T>(url, payload).await;
+        let method = payload.get("method").and_then(|m| m.as_str()).unwrap_or("unknown");
+        UPSTREAM_LATENCY.record(method, started.elapsed(), result.is_ok());
+        result
+    }
+
+    async fn send_call<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        payload: &serde_json::Value,
+    ) -> Result<T> {
+        let response = self.client_for(url)
             .post(url)
             .json(payload)
             .send()
@@ -315,14 +545,9 @@ impl RpcProvider {
             return Err(eyre!("HTTP error: {}", status));
         }
 
-        let json: RpcResponse<T> = response.json().await
+        let body: serde_json::Value = response.json().await
             .map_err(|e| eyre!("Failed to parse response: {}", e))?;
-
-        if let Some(error) = json.error {
-            return Err(eyre!("RPC error: {} (code: {})", error.message, error.code));
-        }
-
-        json.result.ok_or_else(|| eyre!("No result in response"))
+        parse_rpc_response(body)
     }
 
     /// Execute eth_call (EVM chains only)
@@ -337,15 +562,115 @@ impl RpcProvider {
         self.call::<String>("eth_getCode", params).await
     }
 
-    /// Get RPC URL (masked for logging)
-    pub fn masked_url(&self) -> String {
-        if self.primary_url.contains("/v2/") {
-            let parts: Vec<&str> = self.primary_url.split("/v2/").collect();
-            if parts.len() == 2 {
-                return format!("{}/v2/***HIDDEN***", parts[0]);
-            }
+    /// Current block number (EVM chains only)
+    pub async fn block_number(&self) -> Result<u64> {
+        let hex = self.call::<String>("eth_blockNumber", serde_json::json!([])).await?;
+        parse_hex_u64(&hex)
+    }
+
+    /// Chain id the endpoint actually serves (`eth_chainId`)
+    pub async fn remote_chain_id(&self) -> Result<u64> {
+        let hex = self.call::<String>("eth_chainId", serde_json::json!([])).await?;
+        parse_hex_u64(&hex)
+    }
+
+    /// Error if the endpoint serves a different chain than this provider was built for
+    pub async fn verify_chain_id(&self) -> Result<()> {
+        check_chain_id(self.chain_id, self.remote_chain_id().await?, &self.masked_url())
+    }
+
+    /// Get bytecode at a historical block (needs an archive node - Alchemy is one)
+    pub async fn get_code_at(&self, address: &str, block: u64) -> Result<String> {
+        let params = serde_json::json!([address, format!("0x{:x}", block)]);
+        self.call::<String>("eth_getCode", params).await
+    }
+
+    /// Storage word at a historical block (needs an archive node - Alchemy is one)
+    pub async fn get_storage_at(&self, address: &str, slot: &str, block: u64) -> Result<String> {
+        let params = serde_json::json!([address, slot, format!("0x{:x}", block)]);
+        self.call::<String>("eth_getStorageAt", params).await
+    }
+
+    /// EIP-1967 implementation at `at` (default: head) vs `window_secs` earlier.
+    /// None if `address` is not an EIP-1967 proxy
+    pub async fn proxy_upgrade(
+        &self,
+        address: &str,
+        at: Option<u64>,
+        window_secs: u64,
+    ) -> Result<Option<ProxyUpgrade>> {
+        let head = match at {
+            Some(block) => block,
+            None => self.block_number().await?,
+        };
+        let current = self.get_storage_at(address, EIP1967_IMPLEMENTATION_SLOT, head).await?;
+        let Some(implementation) = slot_address(&current) else {
+            return Ok(None);
+        };
+
+        let window_blocks = window_secs.saturating_mul(1000) / approx_block_time_ms(self.chain_id);
+        let then = head.saturating_sub(window_blocks);
+        let previous = self.get_storage_at(address, EIP1967_IMPLEMENTATION_SLOT, then).await?;
+        let previous_implementation = slot_address(&previous);
+        let recently_upgraded = previous_implementation
+            .as_ref()
+            .is_some_and(|previous| *previous != implementation);
+        if recently_upgraded {
+            info!("🔀 {} proxy upgraded since block {} on {}", address, then, self.network_name);
         }
-        self.primary_url.clone()
+
+        Ok(Some(ProxyUpgrade {
+            implementation,
+            previous_implementation,
+            recently_upgraded,
+        }))
+    }
+
+    /// Unix timestamp of a block (EVM chains only)
+    pub async fn block_timestamp(&self, block: u64) -> Result<u64> {
+        let params = serde_json::json!([format!("0x{:x}", block), false]);
+        let header = self.call::<serde_json::Value>("eth_getBlockByNumber", params).await?;
+        let timestamp = header
+            .get("timestamp")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| eyre!("Block {} has no timestamp", block))?;
+        parse_hex_u64(timestamp)
+    }
+
+    /// Block in which `address` was deployed: binary search for the first block
+    /// where `eth_getCode` is non-empty (~log2(head) calls, no explorer key needed).
+    /// None if there is no code at head (EOA or self-destructed)
+    pub async fn find_creation_block(&self, address: &str) -> Result<Option<u64>> {
+        let head = self.block_number().await?;
+        first_block_where(head, |block| async move {
+            let code = self.get_code_at(address, block).await?;
+            Ok(code.len() > 2) // "0x" = no code
+        })
+        .await
+    }
+
+    /// Unix timestamp of the block that deployed `address` (None if not a contract)
+    pub async fn contract_created_at(&self, address: &str) -> Result<Option<u64>> {
+        Ok(self.contract_creation(address).await?.map(|(_, created_at)| created_at))
+    }
+
+    /// (block, unix timestamp) that deployed `address` (None if not a contract)
+    pub async fn contract_creation(&self, address: &str) -> Result<Option<(u64, u64)>> {
+        let Some(block) = self.find_creation_block(address).await? else {
+            return Ok(None);
+        };
+        info!("🕰️ {} created at block {} on {}", address, block, self.network_name);
+        Ok(Some((block, self.block_timestamp(block).await?)))
+    }
+
+    /// Contract age in hours (None if not a contract)
+    pub async fn contract_age_hours(&self, address: &str) -> Result<Option<u32>> {
+        Ok(self.contract_created_at(address).await?.map(age_hours_since))
+    }
+
+    /// Get the first configured RPC URL (masked for logging)
+    pub fn masked_url(&self) -> String {
+        mask_url(&self.endpoints[0].url)
     }
 
     /// Get chain ID
@@ -400,63 +725,42 @@ impl RpcProvider {
         &self,
         batch_payload: &[serde_json::Value],
     ) -> Result<Vec<Result<T>>> {
-        let mut last_error = None;
-
-        for attempt in 0..ALCHEMY_MAX_RETRIES {
-            if attempt > 0 {
-                let base_delay = ALCHEMY_BASE_RETRY_MS * (2_u64.pow(attempt - 1));
-                let capped_delay = base_delay.min(ALCHEMY_MAX_RETRY_MS);
-                let jitter_range = (capped_delay * RETRY_JITTER_PERCENT) / 100;
-                let jitter: i64 = rand::thread_rng().gen_range(-(jitter_range as i64)..=(jitter_range as i64));
-                let final_delay = (capped_delay as i64 + jitter).max(100) as u64;
-                
-                tokio::time::sleep(Duration::from_millis(final_delay)).await;
-            }
-
-            let response = self.client
-                .post(&self.primary_url)
+        retry_with_backoff(&ALCHEMY_BACKOFF, |_| async move {
+            let resp = self.client_for(&self.endpoints[0].url)
+                .post(&self.endpoints[0].url)
                 .json(batch_payload)
                 .send()
-                .await;
-
-            match response {
-                Ok(resp) => {
-                    let status = resp.status();
-                    if status == 429 {
-                        last_error = Some(eyre!("Rate limited (HTTP 429)"));
-                        continue;
-                    }
-                    if !status.is_success() {
-                        last_error = Some(eyre!("HTTP error: {}", status));
-                        continue;
-                    }
+                .await
+                .map_err(|e| eyre!("Request failed: {}", e))?;
 
-                    let batch_response: Vec<BatchResponseItem<T>> = resp.json().await
-                        .map_err(|e| eyre!("Failed to parse batch response: {}", e))?;
-
-                    // Convert to Vec<Result<T>>
-                    let results: Vec<Result<T>> = batch_response
-                        .into_iter()
-                        .map(|item| {
-                            if let Some(error) = item.error {
-                                Err(eyre!("RPC error: {} (code: {})", error.message, error.code))
-                            } else if let Some(result) = item.result {
-                                Ok(result)
-                            } else {
-                                Err(eyre!("No result in response for id {}", item.id))
-                            }
-                        })
-                        .collect();
-
-                    return Ok(results);
-                }
-                Err(e) => {
-                    last_error = Some(eyre!("Request failed: {}", e));
-                }
+            let status = resp.status();
+            if status == 429 {
+                return Err(eyre!("Rate limited (HTTP 429)"));
+            }
+            if !status.is_success() {
+                return Err(eyre!("HTTP error: {}", status));
             }
-        }
 
-        Err(last_error.unwrap_or_else(|| eyre!("Batch request failed after {} retries", ALCHEMY_MAX_RETRIES)))
+            let batch_response: Vec<BatchResponseItem<T>> = resp.json().await
+                .map_err(|e| eyre!("Failed to parse batch response: {}", e))?;
+
+            // Convert to Vec<Result<T>>
+            let results: Vec<Result<T>> = batch_response
+                .into_iter()
+                .map(|item| {
+                    if let Some(error) = item.error {
+                        Err(eyre!("RPC error: {} (code: {})", error.message, error.code))
+                    } else if let Some(result) = item.result {
+                        Ok(result)
+                    } else {
+                        Err(eyre!("No result in response for id {}", item.id))
+                    }
+                })
+                .collect();
+
+            Ok(results)
+        })
+        .await
     }
 
     // ============================================
@@ -468,50 +772,208 @@ impl RpcProvider {
     /// Reference: https://alchemy.com/docs/best-practices-when-using-alchemy.mdx
     /// - Treat Alchemy as multiple nodes, not single node
     /// - Use concurrent requests for better throughput
+    /// - Bounded pool: at most RPC_MAX_CONCURRENT_CALLS (default 10) in flight per
+    ///   provider, the rest wait - unbounded bursts only buy 429s
+    ///
+    /// Results keep the order of `requests`.
     pub async fn concurrent_calls<T: for<'de> Deserialize<'de> + Send + 'static>(
         &self,
         requests: Vec<(&str, serde_json::Value)>,
     ) -> Vec<Result<T>> {
-        let futures: Vec<_> = requests
+        let calls: Vec<_> = requests
             .into_iter()
             .map(|(method, params)| {
                 let provider = self.clone();
                 let method = method.to_string();
-                tokio::spawn(async move {
-                    provider.call::<T>(&method, params).await
-                })
+                async move { provider.call::<T>(&method, params).await }
             })
             .collect();
+        spawn_bounded(&self.call_permits, calls).await
+    }
+}
 
-        let mut results = Vec::with_capacity(futures.len());
-        for future in futures {
-            match future.await {
-                Ok(result) => results.push(result),
-                Err(e) => results.push(Err(eyre!("Task join error: {}", e))),
-            }
+/// Spawn each task once a permit is free; results in input order
+///
+/// Waiting for the permit before `tokio::spawn` bounds the number of live
+/// tasks too, not just the requests they make.
+async fn spawn_bounded<T, F>(permits: &Arc<Semaphore>, tasks: Vec<F>) -> Vec<Result<T>>
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T>> + Send + 'static,
+{
+    let mut handles = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let permit = permits.clone().acquire_owned().await.ok();
+        handles.push(tokio::spawn(async move {
+            let _permit = permit;
+            task.await
+        }));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        match handle.await {
+            Ok(result) => results.push(result),
+            Err(e) => results.push(Err(eyre!("Task join error: {}", e))),
+        }
+    }
+    results
+}
+
+/// EIP-1967 implementation now and at the start of the lookback window
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProxyUpgrade {
+    pub implementation: String,
+    /// None = the slot was still empty then (proxy deployed or initialized inside the window)
+    pub previous_implementation: Option<String>,
+    /// Implementation swapped inside the window
+    pub recently_upgraded: bool,
+}
+
+/// Address held in the low 20 bytes of a storage word, None for an empty slot
+fn slot_address(word: &str) -> Option<String> {
+    let hex = word.trim_start_matches("0x");
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let address = format!("{:0>40}", &hex[hex.len().saturating_sub(40)..]).to_lowercase();
+    (!address.chars().all(|c| c == '0')).then(|| format!("0x{}", address))
+}
+
+/// Hide an Alchemy-style `/v2/<key>` suffix for logging
+fn mask_url(url: &str) -> String {
+    match url.split_once("/v2/") {
+        Some((base, _)) => format!("{}/v2/***HIDDEN***", base),
+        None => url.to_string(),
+    }
+}
+
+/// Parse a "0x..." quantity
+fn parse_hex_u64(hex: &str) -> Result<u64> {
+    u64::from_str_radix(hex.trim_start_matches("0x"), 16)
+        .map_err(|e| eyre!("Invalid hex quantity {}: {}", hex, e))
+}
+
+/// Error unless `reported` (the endpoint's `eth_chainId`) is the `expected` chain -
+/// a mainnet label on a testnet endpoint yields silently wrong verdicts
+pub fn check_chain_id(expected: u64, reported: u64, endpoint: &str) -> Result<()> {
+    if expected == reported {
+        Ok(())
+    } else {
+        Err(eyre!(
+            "RPC {} serves chain {} but is configured for chain {} - check the endpoint URL",
+            endpoint, reported, expected
+        ))
+    }
+}
+
+/// Lowest block in `0..=head` where `pred` holds, assuming it stays true once
+/// it becomes true (contract code exists from deployment onwards)
+async fn first_block_where<F, Fut>(head: u64, mut pred: F) -> Result<Option<u64>>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: std::future::Future<Output = Result<bool>>,
+{
+    if !pred(head).await? {
+        return Ok(None);
+    }
+    let (mut lo, mut hi) = (0u64, head);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid).await? {
+            hi = mid;
+        } else {
+            lo = mid + 1;
         }
-        results
     }
+    Ok(Some(lo))
+}
+
+/// Whole hours from `from` to `to` (unix seconds), saturating at 0 and u32::MAX
+fn hours_between(from: u64, to: u64) -> u32 {
+    (to.saturating_sub(from) / 3600).min(u32::MAX as u64) as u32
+}
+
+/// Hours elapsed since a unix timestamp (ML `age_hours` feature)
+pub fn age_hours_since(created_at: u64) -> u32 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(created_at);
+    hours_between(created_at, now)
 }
 
 /// JSON-RPC response structure
+/// Lenient on purpose - public fallbacks are not Alchemy: unknown fields are ignored,
+/// `jsonrpc` may be missing and `id` may be a number, a string or null
 #[derive(Debug, Deserialize)]
 struct RpcResponse<T> {
     #[allow(dead_code)]
-    jsonrpc: String,
+    #[serde(default)]
+    jsonrpc: Option<String>,
     result: Option<T>,
     error: Option<RpcError>,
     #[allow(dead_code)]
-    id: u64,
+    #[serde(default)]
+    id: serde_json::Value,
+}
+
+/// Result of one JSON-RPC response body (an error member wins over a result)
+/// A single-element array - a batch-shaped reply to a single call - is unwrapped
+fn parse_rpc_response<T: for<'de> Deserialize<'de>>(body: serde_json::Value) -> Result<T> {
+    let body = match body {
+        serde_json::Value::Array(mut items) if items.len() == 1 => items.remove(0),
+        body => body,
+    };
+    let json: RpcResponse<T> = serde_json::from_value(body)
+        .map_err(|e| eyre!("Failed to parse response: {}", e))?;
+
+    if let Some(error) = json.error {
+        return Err(eyre!("RPC error: {} (code: {})", error.message, error.code));
+    }
+
+    json.result.ok_or_else(|| eyre!("No result in response"))
 }
 
 /// JSON-RPC error structure
+/// Also accepts string codes ("-32000"), a missing code or message, and a bare string error
 #[derive(Debug, Clone, Deserialize)]
+#[serde(from = "RawRpcError")]
 pub struct RpcError {
     pub code: i64,
     pub message: String,
 }
 
+/// Error shapes seen in the wild, normalized into `RpcError`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawRpcError {
+    Object {
+        #[serde(default)]
+        code: serde_json::Value,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    Message(String),
+}
+
+impl From<RawRpcError> for RpcError {
+    fn from(raw: RawRpcError) -> Self {
+        match raw {
+            RawRpcError::Object { code, message } => Self {
+                // Unknown / missing code = 0 (classified by message only)
+                code: match &code {
+                    serde_json::Value::Number(n) => n.as_i64().unwrap_or(0),
+                    serde_json::Value::String(s) => s.trim().parse().unwrap_or(0),
+                    _ => 0,
+                },
+                message: message.unwrap_or_else(|| "unknown RPC error".to_string()),
+            },
+            RawRpcError::Message(message) => Self { code: 0, message },
+        }
+    }
+}
+
 impl RpcError {
     /// Check if this is a rate limit error (Alchemy: HTTP 429 or code -32005)
     pub fn is_rate_limit(&self) -> bool {
@@ -534,16 +996,68 @@ impl RpcError {
     }
 }
 
+/// Probe timeout for startup health checks
+pub const HEALTH_PROBE_TIMEOUT_SECS: u64 = 5;
+
+/// Probe latency above which a chain is reported as degraded
+pub const HEALTH_DEGRADED_LATENCY_MS: u64 = 2000;
+
+/// Per-chain RPC health status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RpcHealthStatus {
+    /// Probe succeeded quickly
+    Healthy,
+    /// Probe succeeded but slowly
+    Degraded,
+    /// Provider failed to initialize or probe failed
+    Down,
+}
+
+/// Per-chain RPC health snapshot
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChainHealth {
+    pub chain_id: u64,
+    pub status: RpcHealthStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ChainHealth {
+    fn down(chain_id: u64, error: String) -> Self {
+        Self {
+            chain_id,
+            status: RpcHealthStatus::Down,
+            latency_ms: None,
+            block_number: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Startup probe outcome for one chain
+struct ProbeResult {
+    health: ChainHealth,
+    /// Endpoint answered `eth_chainId` with another chain
+    chain_mismatch: bool,
+}
+
 /// Multi-chain RPC manager
 pub struct RpcManager {
     providers: std::collections::HashMap<u64, RpcProvider>,
     solana_provider: Option<RpcProvider>,
+    health: std::collections::HashMap<u64, ChainHealth>,
 }
 
 impl RpcManager {
-    /// Create manager with all supported chains
+    /// Create manager with all supported chains (no network probes)
     pub fn new() -> Self {
         let mut providers = std::collections::HashMap::new();
+        let mut health = std::collections::HashMap::new();
 
         for chain_id in SUPPORTED_CHAIN_IDS {
             match RpcProvider::new(chain_id) {
@@ -553,6 +1067,7 @@ impl RpcManager {
                 }
                 Err(e) => {
                     warn!("⚠️ Failed to initialize RPC for chain {}: {}", chain_id, e);
+                    health.insert(chain_id, ChainHealth::down(chain_id, e.to_string()));
                 }
             }
         }
@@ -568,7 +1083,91 @@ impl RpcManager {
             }
         };
 
-        Self { providers, solana_provider }
+        Self { providers, solana_provider, health }
+    }
+
+    /// Create manager and probe every chain concurrently with `eth_chainId` + `eth_blockNumber`.
+    /// Providers whose endpoint serves another chain are dropped (reported down)
+    pub async fn warmup() -> Self {
+        let mut manager = Self::new();
+
+        let probes = manager
+            .providers
+            .iter()
+            .map(|(&chain_id, provider)| Self::probe(chain_id, provider));
+        let results = futures_util::future::join_all(probes).await;
+        for ProbeResult { health: result, chain_mismatch } in results {
+            // Wrong-chain endpoints are never used (the Down status below logs why)
+            if chain_mismatch {
+                manager.providers.remove(&result.chain_id);
+            }
+            match result.status {
+                RpcHealthStatus::Healthy => {
+                    info!("💚 Chain {} healthy ({}ms)", result.chain_id, result.latency_ms.unwrap_or(0))
+                }
+                RpcHealthStatus::Degraded => {
+                    warn!("💛 Chain {} degraded ({}ms)", result.chain_id, result.latency_ms.unwrap_or(0))
+                }
+                RpcHealthStatus::Down => warn!(
+                    "❤️ Chain {} down: {}",
+                    result.chain_id,
+                    result.error.as_deref().unwrap_or("unknown")
+                ),
+            }
+            manager.health.insert(result.chain_id, result);
+        }
+
+        manager
+    }
+
+    /// Probe one provider: `eth_chainId` must match, then `eth_blockNumber` latency
+    /// (each bounded by HEALTH_PROBE_TIMEOUT_SECS)
+    async fn probe(chain_id: u64, provider: &RpcProvider) -> ProbeResult {
+        let timeout = Duration::from_secs(HEALTH_PROBE_TIMEOUT_SECS);
+        match tokio::time::timeout(timeout, provider.remote_chain_id()).await {
+            Ok(Ok(reported)) => {
+                if let Err(e) = check_chain_id(chain_id, reported, &provider.masked_url()) {
+                    return ProbeResult { health: ChainHealth::down(chain_id, e.to_string()), chain_mismatch: true };
+                }
+            }
+            // Unreachable endpoints show up in the block number probe below
+            Ok(Err(e)) => warn!("⚠️ eth_chainId probe failed for chain {}: {}", chain_id, e),
+            Err(_) => warn!("⚠️ eth_chainId probe timed out for chain {}", chain_id),
+        }
+        ProbeResult { health: Self::probe_block_number(chain_id, provider).await, chain_mismatch: false }
+    }
+
+    /// `eth_blockNumber` health probe (bounded by HEALTH_PROBE_TIMEOUT_SECS)
+    async fn probe_block_number(chain_id: u64, provider: &RpcProvider) -> ChainHealth {
+        let start = std::time::Instant::now();
+        let call = provider.call::<String>("eth_blockNumber", serde_json::json!([]));
+
+        match tokio::time::timeout(Duration::from_secs(HEALTH_PROBE_TIMEOUT_SECS), call).await {
+            Ok(Ok(block_hex)) => {
+                let latency_ms = start.elapsed().as_millis() as u64;
+                ChainHealth {
+                    chain_id,
+                    status: if latency_ms > HEALTH_DEGRADED_LATENCY_MS {
+                        RpcHealthStatus::Degraded
+                    } else {
+                        RpcHealthStatus::Healthy
+                    },
+                    latency_ms: Some(latency_ms),
+                    block_number: u64::from_str_radix(block_hex.trim_start_matches("0x"), 16).ok(),
+                    error: None,
+                }
+            }
+            Ok(Err(e)) => ChainHealth::down(chain_id, e.to_string()),
+            Err(_) => ChainHealth::down(
+                chain_id,
+                format!("eth_blockNumber probe timed out after {}s", HEALTH_PROBE_TIMEOUT_SECS),
+            ),
+        }
+    }
+
+    /// Per-chain health recorded at startup (empty for unprobed chains)
+    pub fn health(&self) -> &std::collections::HashMap<u64, ChainHealth> {
+        &self.health
     }
 
     /// Get provider for a chain
@@ -610,12 +1209,96 @@ mod tests {
         assert!(AlchemyNetwork::from_chain_id(999).is_none());
     }
 
+    #[test]
+    fn test_chain_health_down() {
+        let health = ChainHealth::down(56, "no api key".to_string());
+        assert_eq!(health.status, RpcHealthStatus::Down);
+        assert!(health.latency_ms.is_none());
+        let json = serde_json::to_string(&health).unwrap();
+        assert!(json.contains("\"status\":\"down\""));
+    }
+
+    #[test]
+    fn test_slot_address() {
+        let word = "0x000000000000000000000000A0B86991C6218B36C1D19D4A2E9EB0CE3606EB48";
+        assert_eq!(
+            slot_address(word).as_deref(),
+            Some("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+        );
+        // Some RPCs trim leading zeros
+        assert_eq!(slot_address("0x1234").as_deref(), Some("0x0000000000000000000000000000000000001234"));
+        assert_eq!(slot_address(&format!("0x{}", "0".repeat(64))), None);
+        assert_eq!(slot_address("0x0"), None);
+        assert_eq!(slot_address("0xzz"), None);
+    }
+
+    #[test]
+    fn test_check_chain_id() {
+        assert!(check_chain_id(1, 1, "https://rpc").is_ok());
+        let err = check_chain_id(1, 11155111, "https://rpc").unwrap_err().to_string();
+        assert!(err.contains("11155111") && err.contains("chain 1"));
+    }
+
     #[test]
     fn test_public_fallback() {
         assert!(PublicRpcFallback::get(1).is_some());
         assert!(PublicRpcFallback::get(999).is_none());
     }
 
+    #[test]
+    fn test_public_pool_rotation() {
+        let pool = PublicRpcPool::new(vec![
+            ("https://a".to_string(), 1),
+            ("https://b".to_string(), 1),
+            ("https://c".to_string(), 3),
+        ]);
+        // Heaviest first, configured order breaks ties
+        assert_eq!(pool.ordered(), vec!["https://c", "https://a", "https://b"]);
+
+        // A failing endpoint sinks below healthy ones
+        pool.record_failure("https://a");
+        assert_eq!(pool.ordered(), vec!["https://c", "https://b", "https://a"]);
+        // Weight 3 with 3 strikes = 0.75 < 1
+        for _ in 0..3 {
+            pool.record_failure("https://c");
+        }
+        assert_eq!(pool.ordered()[0], "https://b");
+
+        // One success restores full weight
+        pool.record_success("https://c");
+        assert_eq!(pool.ordered()[0], "https://c");
+        assert!(PublicRpcFallback::pool(1).len() > 1);
+    }
+
+    #[test]
+    fn test_endpoint_try_order() {
+        let endpoint = |url: &str, role| RpcEndpoint { url: url.to_string(), role };
+        let provider = RpcProvider {
+            endpoints: Arc::new(vec![
+                endpoint("https://own-node", RpcRole::Primary),
+                endpoint("https://key-1/v2/a", RpcRole::Balanced),
+                endpoint("https://key-2/v2/b", RpcRole::Balanced),
+                endpoint("https://backup", RpcRole::Fallback),
+            ]),
+            balanced_cursor: Arc::new(AtomicUsize::new(0)),
+            fallback_pool: Arc::new(PublicRpcPool::default()),
+            call_permits: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_CALLS)),
+            client: reqwest::Client::new(),
+            insecure_client: None,
+            chain_id: 1,
+            network_name: "eth-mainnet".to_string(),
+        };
+        let order = |p: &RpcProvider| p.try_order().iter().map(|e| e.url.clone()).collect::<Vec<_>>();
+
+        // Primary first and fallback last every time; the balanced pair alternates
+        assert_eq!(order(&provider), vec!["https://own-node", "https://key-1/v2/a", "https://key-2/v2/b", "https://backup"]);
+        assert_eq!(order(&provider), vec!["https://own-node", "https://key-2/v2/b", "https://key-1/v2/a", "https://backup"]);
+        // Clones share the cursor
+        assert_eq!(order(&provider.clone())[1], "https://key-1/v2/a");
+        assert_eq!(provider.masked_url(), "https://own-node");
+        assert_eq!(mask_url("https://key-1/v2/a"), "https://key-1/v2/***HIDDEN***");
+    }
+
     #[test]
     fn test_alchemy_retry_constants() {
         // Verify Alchemy best practice constants
@@ -645,4 +1328,88 @@ mod tests {
         };
         assert!(parse_error.is_parse_error());
     }
+
+    #[test]
+    fn test_parse_rpc_response_shape_variance() {
+        use serde_json::json;
+
+        // Alchemy shape
+        let ok = json!({ "jsonrpc": "2.0", "id": 1, "result": "0x10" });
+        assert_eq!(parse_rpc_response::<String>(ok).unwrap(), "0x10");
+        // String id, no jsonrpc, extra fields
+        let quirky = json!({ "id": "1", "result": "0x10", "cached": true, "node": "eu-2" });
+        assert_eq!(parse_rpc_response::<String>(quirky).unwrap(), "0x10");
+        // Batch-shaped reply to a single call
+        let wrapped = json!([{ "jsonrpc": "2.0", "id": null, "result": "0x10" }]);
+        assert_eq!(parse_rpc_response::<String>(wrapped).unwrap(), "0x10");
+        // Explicit null error next to the result
+        let null_error = json!({ "jsonrpc": "2.0", "id": 1, "result": "0x10", "error": null });
+        assert_eq!(parse_rpc_response::<String>(null_error).unwrap(), "0x10");
+
+        // Error variants still surface as RPC errors
+        let string_code = json!({ "id": 1, "error": { "code": "-32005", "message": "rate limit" } });
+        let err = parse_rpc_response::<String>(string_code).unwrap_err().to_string();
+        assert_eq!(err, "RPC error: rate limit (code: -32005)");
+        let bare = json!({ "id": 1, "error": "daily request limit reached" });
+        let err = parse_rpc_response::<String>(bare).unwrap_err().to_string();
+        assert_eq!(err, "RPC error: daily request limit reached (code: 0)");
+        let no_code: RpcError = serde_json::from_value(json!({ "message": "Rate limit exceeded" })).unwrap();
+        assert!(no_code.is_rate_limit());
+
+        assert!(parse_rpc_response::<String>(json!({ "id": 1, "result": null })).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_first_block_where() {
+        let deployed_at = 1_234_567u64;
+        let found = first_block_where(20_000_000, |b| async move { Ok(b >= deployed_at) })
+            .await
+            .unwrap();
+        assert_eq!(found, Some(deployed_at));
+
+        let genesis = first_block_where(100, |_| async { Ok(true) }).await.unwrap();
+        assert_eq!(genesis, Some(0));
+
+        let missing = first_block_where(100, |_| async { Ok(false) }).await.unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_bounded() {
+        let permits = Arc::new(Semaphore::new(3));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = (0..20usize)
+            .map(|i| {
+                let (in_flight, peak) = (in_flight.clone(), peak.clone());
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    if i == 7 {
+                        Err(eyre!("call {} failed", i))
+                    } else {
+                        Ok(i)
+                    }
+                }
+            })
+            .collect();
+
+        let results = spawn_bounded(&permits, tasks).await;
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(results.len(), 20);
+        // Input order kept, failures stay in their slot
+        assert_eq!(results[19].as_ref().ok(), Some(&19));
+        assert!(results[7].is_err());
+        assert_eq!(permits.available_permits(), 3);
+    }
+
+    #[test]
+    fn test_hours_between() {
+        assert_eq!(hours_between(0, 7200), 2);
+        assert_eq!(hours_between(1000, 1000 + 3599), 0);
+        assert_eq!(hours_between(5000, 100), 0);
+        assert_eq!(parse_hex_u64("0x1a").unwrap(), 26);
+    }
 }
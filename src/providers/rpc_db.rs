@@ -0,0 +1,273 @@
+//! RpcDb Module - Forked chain state for REVM
+//!
+//! `DatabaseRef` backed by JSON-RPC reads pinned to a single block.
+//! The block is resolved to its hash once and every read names that hash
+//! (EIP-1898), so a reorg mid-analysis can't mix state from two chain views.
+//! Accounts, storage slots and block hashes are cached behind shared maps, so one
+//! `Arc<RpcDb>` can back many `CacheDB<&RpcDb>` simulations (e.g. every token of a
+//! `/batch` on the same chain): router / WETH / factory state is fetched once.
+//!
+//! REVM is synchronous and reqwest is async - reads are bridged with
+//! `block_in_place` on the runtime the db was created on, so simulations must
+//! run on a multi-thread runtime (or inside `spawn_blocking`).
+
+use alloy_primitives::{Address, B256, U256};
+use dashmap::DashMap;
+use eyre::{eyre, Result};
+use revm::{
+    primitives::{AccountInfo, Bytecode},
+    DatabaseRef,
+};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use tokio::runtime::Handle;
+
+use crate::providers::rpc::RpcProvider;
+
+/// Read counters for one forked db
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RpcDbStats {
+    /// Account / slot / block-hash lookups that went to the RPC node
+    pub rpc_reads: u64,
+    /// Reads served from the shared cache
+    pub cache_hits: u64,
+}
+
+/// Chain state at `block`, fetched lazily over RPC and shared across simulations
+pub struct RpcDb {
+    provider: RpcProvider,
+    block: u64,
+    block_hash: B256,
+    handle: Handle,
+    accounts: DashMap<Address, Option<AccountInfo>>,
+    storage: DashMap<(Address, U256), U256>,
+    block_hashes: DashMap<u64, B256>,
+    rpc_reads: AtomicU64,
+    cache_hits: AtomicU64,
+}
+
+impl RpcDb {
+    /// Fork `provider`'s chain at `block` (None = latest), resolving it to a block hash
+    pub async fn pinned(provider: RpcProvider, block: Option<u64>) -> Result<Self> {
+        let tag = block.map_or_else(|| "latest".to_string(), |b| format!("0x{:x}", b));
+        let header = provider
+            .call::<serde_json::Value>("eth_getBlockByNumber", serde_json::json!([tag, false]))
+            .await?;
+        let (number, hash) = parse_block_header(&header).ok_or_else(|| eyre!("Block {} not found", tag))?;
+        Ok(Self::new(provider, number, hash))
+    }
+
+    /// Fork `provider`'s chain at a known block. Must be called from within a tokio runtime
+    pub fn new(provider: RpcProvider, block: u64, block_hash: B256) -> Self {
+        let block_hashes = DashMap::new();
+        block_hashes.insert(block, block_hash);
+        Self {
+            provider,
+            block,
+            block_hash,
+            handle: Handle::current(),
+            accounts: DashMap::new(),
+            storage: DashMap::new(),
+            block_hashes,
+            rpc_reads: AtomicU64::new(0),
+            cache_hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Block every read is pinned to
+    pub fn block(&self) -> u64 {
+        self.block
+    }
+
+    /// Hash of the pinned block
+    pub fn block_hash(&self) -> B256 {
+        self.block_hash
+    }
+
+    /// Chain the state is forked from
+    pub fn chain_id(&self) -> u64 {
+        self.provider.chain_id()
+    }
+
+    /// Read-only view whose reads fail once `deadline` has passed (None = never)
+    pub fn until(&self, deadline: Option<Instant>) -> DeadlineDb<'_> {
+        DeadlineDb { fork: self, deadline }
+    }
+
+    /// RPC reads vs cache hits so far
+    pub fn stats(&self) -> RpcDbStats {
+        RpcDbStats {
+            rpc_reads: self.rpc_reads.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+        }
+    }
+
+    /// EIP-1898 block parameter: reads stay on the pinned block even if it is reorged out
+    fn block_param(&self) -> serde_json::Value {
+        serde_json::json!({ "blockHash": format!("{:?}", self.block_hash) })
+    }
+
+    /// Run an RPC future to completion from synchronous REVM code
+    fn block_on<F: Future>(&self, fut: F) -> F::Output {
+        self.rpc_reads.fetch_add(1, Ordering::Relaxed);
+        tokio::task::block_in_place(|| self.handle.block_on(fut))
+    }
+
+    fn hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Balance, nonce and code in one round of concurrent requests
+    async fn fetch_account(&self, address: Address) -> Result<Option<AccountInfo>> {
+        let address = format!("{:?}", address);
+        let params = serde_json::json!([address, self.block_param()]);
+        let (balance, nonce, code) = tokio::join!(
+            self.provider.call::<String>("eth_getBalance", params.clone()),
+            self.provider.call::<String>("eth_getTransactionCount", params.clone()),
+            self.provider.call::<String>("eth_getCode", params),
+        );
+        let balance = parse_quantity(&balance?)?;
+        let nonce: u64 = parse_quantity(&nonce?)?.try_into().unwrap_or(u64::MAX);
+        let code = hex::decode(code?.trim_start_matches("0x"))
+            .map_err(|e| eyre!("Invalid code for {}: {}", address, e))?;
+
+        if balance.is_zero() && nonce == 0 && code.is_empty() {
+            return Ok(None);
+        }
+        let code = Bytecode::new_raw(code.into());
+        Ok(Some(AccountInfo::new(balance, nonce, code.hash_slow(), code)))
+    }
+}
+
+impl DatabaseRef for RpcDb {
+    type Error = eyre::Report;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>> {
+        if let Some(info) = self.accounts.get(&address) {
+            self.hit();
+            return Ok(info.clone());
+        }
+        let info = self.block_on(self.fetch_account(address))?;
+        self.accounts.insert(address, info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode> {
+        // basic_ref always returns code inline, so CacheDB never asks for it by hash
+        Err(eyre!("Code lookup by hash not supported: {}", code_hash))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256> {
+        if let Some(value) = self.storage.get(&(address, index)) {
+            self.hit();
+            return Ok(*value);
+        }
+        let params = serde_json::json!([format!("{:?}", address), format!("0x{:x}", index), self.block_param()]);
+        let value = parse_quantity(&self.block_on(self.provider.call::<String>("eth_getStorageAt", params))?)?;
+        self.storage.insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256> {
+        if let Some(hash) = self.block_hashes.get(&number) {
+            self.hit();
+            return Ok(*hash);
+        }
+        let params = serde_json::json!([format!("0x{:x}", number), false]);
+        let block = self.block_on(self.provider.call::<serde_json::Value>("eth_getBlockByNumber", params))?;
+        let (_, hash) = parse_block_header(&block).ok_or_else(|| eyre!("Block {} not found", number))?;
+        self.block_hashes.insert(number, hash);
+        Ok(hash)
+    }
+}
+
+/// `RpcDb` view for one simulation: once its deadline has passed every read fails, so a
+/// simulation nobody waits for anymore (a timed-out batch token inside `spawn_blocking`)
+/// stops at its next state read instead of running to the end
+#[derive(Clone, Copy)]
+pub struct DeadlineDb<'a> {
+    fork: &'a RpcDb,
+    deadline: Option<Instant>,
+}
+
+impl DeadlineDb<'_> {
+    /// Block every read is pinned to
+    pub fn block(&self) -> u64 {
+        self.fork.block
+    }
+
+    fn check(&self) -> Result<()> {
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => {
+                Err(eyre!("Simulation deadline passed (fork at block {})", self.fork.block))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl DatabaseRef for DeadlineDb<'_> {
+    type Error = eyre::Report;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>> {
+        self.check()?;
+        self.fork.basic_ref(address)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode> {
+        self.fork.code_by_hash_ref(code_hash)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256> {
+        self.check()?;
+        self.fork.storage_ref(address, index)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256> {
+        self.check()?;
+        self.fork.block_hash_ref(number)
+    }
+}
+
+/// (number, hash) of an `eth_getBlockByNumber` result
+fn parse_block_header(block: &serde_json::Value) -> Option<(u64, B256)> {
+    let number = block.get("number")?.as_str()?;
+    let number = u64::from_str_radix(number.trim_start_matches("0x"), 16).ok()?;
+    let hash = block.get("hash")?.as_str()?.parse::<B256>().ok()?;
+    Some((number, hash))
+}
+
+/// Parse a JSON-RPC hex quantity / storage word
+fn parse_quantity(quantity: &str) -> Result<U256> {
+    let digits = quantity.trim_start_matches("0x");
+    if digits.is_empty() {
+        return Ok(U256::ZERO);
+    }
+    U256::from_str_radix(digits, 16).map_err(|e| eyre!("Invalid hex quantity {}: {}", quantity, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity("0x").unwrap(), U256::ZERO);
+        assert_eq!(parse_quantity("0x1bc16d674ec80000").unwrap(), U256::from(2_000_000_000_000_000_000u128));
+        assert_eq!(
+            parse_quantity("0x00000000000000000000000000000000000000000000000000000000000000ff").unwrap(),
+            U256::from(255u64)
+        );
+        assert!(parse_quantity("0xzz").is_err());
+    }
+
+    #[test]
+    fn test_parse_block_header() {
+        let hash = B256::repeat_byte(0xab);
+        let block = serde_json::json!({ "number": "0x10", "hash": format!("{:?}", hash) });
+        assert_eq!(parse_block_header(&block), Some((16, hash)));
+        // `null` result = unknown block
+        assert_eq!(parse_block_header(&serde_json::Value::Null), None);
+    }
+}
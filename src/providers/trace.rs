@@ -25,11 +25,16 @@
 //! - Trace token transfer restrictions
 //! - Identify proxy contract calls
 
-use eyre::Result;
+use alloy_primitives::U256;
+use alloy_sol_types::{sol, SolCall};
+use eyre::{eyre, Result};
 use serde::{Deserialize, Serialize};
+
+use crate::core::risk_score::{RiskComponents, RiskScore, RiskScoreBuilder, ScoreFactor};
 use tracing::{debug, info};
 
 use super::rpc::RpcProvider;
+use crate::utils::constants::loss_percent;
 
 // ============================================
 // TRACE TYPES
@@ -179,6 +184,8 @@ pub struct DebugTraceConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tracer: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracer_config: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<String>,
 }
 
@@ -189,6 +196,7 @@ impl Default for DebugTraceConfig {
             disable_memory: Some(true),   // Reduce data size
             disable_stack: Some(false),   // Keep stack for analysis
             tracer: None,
+            tracer_config: None,
             timeout: Some("10s".to_string()),
         }
     }
@@ -219,6 +227,21 @@ pub struct DebugTrace {
     pub struct_logs: Vec<DebugTraceStep>,
 }
 
+/// callTracer frame; with `withLog` each frame carries the logs it emitted
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallFrame {
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub to: Option<String>,
+    pub error: Option<String>,
+    /// Raw log objects (`address`, `topics`, `data`)
+    #[serde(default)]
+    pub logs: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub calls: Vec<CallFrame>,
+}
+
 // ============================================
 // HONEYPOT ANALYSIS TYPES
 // ============================================
@@ -232,6 +255,17 @@ pub struct HoneypotTraceAnalysis {
     pub internal_calls: Vec<InternalCall>,
     pub state_changes: Vec<StateChange>,
     pub gas_analysis: GasAnalysis,
+    /// Swap value sent to addresses other than the caller/pair/router (stealth taxes)
+    pub hidden_fee_recipients: Vec<HiddenFeeRecipient>,
+}
+
+/// Value of one token that a swap delivered to a non-participant
+#[derive(Debug, Clone, PartialEq)]
+pub struct HiddenFeeRecipient {
+    pub address: String,
+    pub token: String,
+    /// Sum of all Transfer events of `token` to `address` in the swap
+    pub amount: U256,
 }
 
 /// Honeypot red flag detected in traces
@@ -293,6 +327,221 @@ pub struct GasAnalysis {
     pub gas_efficiency: f64,
 }
 
+// ============================================
+// UNIFIED RISK SCORE BRIDGE
+// ============================================
+
+impl Severity {
+    /// Component score (0-100) on the shared RiskScore scale
+    pub fn score(&self) -> u8 {
+        match self {
+            Severity::Low => 25,
+            Severity::Medium => 50,
+            Severity::High => 75,
+            Severity::Critical => 95,
+        }
+    }
+}
+
+impl HoneypotTraceAnalysis {
+    /// Worst severity score among red flags matching `kind`
+    fn worst_flag(&self, kind: fn(&RedFlagType) -> bool) -> u8 {
+        self.red_flags
+            .iter()
+            .filter(|f| kind(&f.flag_type))
+            .map(|f| f.severity.score())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Builder pre-filled with this trace analysis (add tax/slippage before `build()`)
+    pub fn risk_score_builder(&self) -> RiskScoreBuilder {
+        let components = RiskComponents::from(self);
+        let mut factors = vec![ScoreFactor {
+            name: "Transaction trace analysis".to_string(),
+            score: components.honeypot,
+            weight: 0.35,
+            reason: format!(
+                "Honeypot confidence {:.0}%, {} red flag(s)",
+                self.confidence * 100.0,
+                self.red_flags.len()
+            ),
+        }];
+        if components.contract > 0 {
+            factors.push(ScoreFactor {
+                name: "Trace contract behaviour".to_string(),
+                score: components.contract,
+                weight: 0.10,
+                reason: "Blacklist/proxy/state-manipulation calls seen in trace".to_string(),
+            });
+        }
+        RiskScoreBuilder::new().with_components(&components, factors)
+    }
+
+    /// Score this trace analysis on the shared RiskScore scale
+    pub fn risk_score(&self) -> RiskScore {
+        self.risk_score_builder().build()
+    }
+}
+
+/// Map trace confidence and red-flag severities into the shared scoring model
+/// - honeypot: max(confidence, worst revert/hidden-transfer/gas-drain flag), >= 95 if flagged honeypot
+/// - contract: worst blacklist/proxy/state-manipulation flag
+impl From<&HoneypotTraceAnalysis> for RiskComponents {
+    fn from(analysis: &HoneypotTraceAnalysis) -> Self {
+        let confidence = (analysis.confidence.clamp(0.0, 1.0) * 100.0).round() as u8;
+        let behaviour = analysis.worst_flag(|t| {
+            matches!(t, RedFlagType::UnexpectedRevert | RedFlagType::HiddenTransfer | RedFlagType::GasDrain)
+        });
+        let honeypot = confidence.max(behaviour);
+        let honeypot = if analysis.is_honeypot { honeypot.max(95) } else { honeypot };
+
+        Self {
+            honeypot,
+            contract: analysis.worst_flag(|t| {
+                matches!(t, RedFlagType::BlacklistCall | RedFlagType::ProxyCall | RedFlagType::StateManipulation)
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+// Owner functions that blacklist holders (setter side only)
+sol! {
+    function setBots(address[] accounts, bool state) external;
+    function setBot(address account, bool state) external;
+    function blacklistAddress(address account) external;
+    function addToBlacklist(address account) external;
+    function setBlacklist(address account, bool state) external;
+    function addBot(address account) external;
+    function blacklistUpdate(address account, bool state) external;
+    function blacklist(address account) external;
+}
+
+/// Selectors of the blacklist setters, derived from their signatures
+const BLACKLIST_SETTER_SELECTORS: [([u8; 4], &str); 8] = [
+    (setBotsCall::SELECTOR, "setBots"),
+    (setBotCall::SELECTOR, "setBot"),
+    (blacklistAddressCall::SELECTOR, "blacklistAddress"),
+    (addToBlacklistCall::SELECTOR, "addToBlacklist"),
+    (setBlacklistCall::SELECTOR, "setBlacklist"),
+    (addBotCall::SELECTOR, "addBot"),
+    (blacklistUpdateCall::SELECTOR, "blacklistUpdate"),
+    (blacklistCall::SELECTOR, "blacklist"),
+];
+
+/// Max pages fetched by `find_blacklist_history` (page size = `BLACKLIST_PAGE_SIZE`)
+const BLACKLIST_MAX_PAGES: u32 = 10;
+const BLACKLIST_PAGE_SIZE: u32 = 1000;
+
+/// A single successful blacklist call found in token history
+#[derive(Debug, Clone)]
+pub struct BlacklistEvent {
+    pub block_number: Option<u32>,
+    pub transaction_hash: Option<String>,
+    pub caller: String,
+    pub function: String,
+    pub addresses: Vec<String>,
+}
+
+/// Historical blacklist activity for a token
+#[derive(Debug, Clone)]
+pub struct BlacklistHistory {
+    pub token: String,
+    pub from_block: u64,
+    pub to_block: u64,
+    pub events: Vec<BlacklistEvent>,
+    /// Unique addresses targeted by blacklist calls
+    pub blacklisted_addresses: Vec<String>,
+    pub first_block: Option<u32>,
+    pub last_block: Option<u32>,
+}
+
+impl BlacklistHistory {
+    /// Number of unique addresses blacklisted in the range
+    pub fn blacklisted_count(&self) -> usize {
+        self.blacklisted_addresses.len()
+    }
+
+    /// Owner actively blacklisted holders post-launch = confirmed soft-rug
+    pub fn is_soft_rug(&self, min_addresses: usize) -> bool {
+        self.blacklisted_count() >= min_addresses
+    }
+}
+
+// ============================================
+// LOSS EXPLANATION TYPES (post-mortem)
+// ============================================
+
+/// Pool swap selectors: UniswapV2 swap(uint256,uint256,address,bytes), UniswapV3 swap(...)
+const SWAP_SELECTORS: [&str; 2] = ["0x022c0d9f", "0x128acb08"];
+
+/// ERC20 Transfer(address,address,uint256) event topic
+const TRANSFER_EVENT_TOPIC: &str =
+    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// A decoded ERC20 Transfer log
+#[derive(Debug, Clone)]
+pub struct TransferLog {
+    pub token: String,
+    pub from: String,
+    pub to: String,
+    pub amount: U256,
+}
+
+/// Transfer events of one swap plus the parties expected to move its value
+#[derive(Debug, Clone)]
+pub struct SwapTransfers {
+    pub caller: String,
+    pub router: String,
+    pub transfers: Vec<TransferLog>,
+}
+
+/// Value skimmed away from the victim's trade
+#[derive(Debug, Clone)]
+pub struct ValueLeak {
+    pub token: String,
+    pub to: String,
+    pub amount: U256,
+}
+
+/// Per-token flow in/out of the victim
+#[derive(Debug, Clone)]
+pub struct TokenFlow {
+    pub token: String,
+    /// Amount the counterparty asked to transfer to the victim (from call traces)
+    pub expected_in: U256,
+    /// Amount that actually arrived (Transfer logs to victim)
+    pub received: U256,
+    /// Amount that left the victim (Transfer logs from victim)
+    pub sent: U256,
+    /// Loss on the inbound leg: (expected_in - received) / expected_in
+    pub buy_tax_percent: f64,
+    /// Loss on the outbound leg: side transfers / sent
+    pub sell_tax_percent: f64,
+}
+
+/// Post-mortem of a historical victim transaction
+#[derive(Debug, Clone)]
+pub struct LossExplanation {
+    pub tx_hash: String,
+    pub victim: String,
+    pub tx_success: bool,
+    pub native_sent: U256,
+    pub native_received: U256,
+    pub flows: Vec<TokenFlow>,
+    pub leaks: Vec<ValueLeak>,
+    /// Worst per-leg loss across all token flows
+    pub effective_tax_percent: f64,
+    pub is_honeypot: bool,
+    pub confidence: f64,
+    pub red_flags: Vec<String>,
+    /// Trace + observed taxes on the shared RiskScore scale
+    pub risk_score: RiskScore,
+    /// Human-readable explanation
+    pub summary: String,
+}
+
 // ============================================
 // TRACE API CLIENT
 // ============================================
@@ -358,6 +607,373 @@ impl TraceClient {
         self.provider.call::<Vec<Trace>>("trace_filter", params).await
     }
 
+    /// Scan a token's history for owner blacklist calls
+    ///
+    /// Runs `trace_filter` for calls *to* the token in `block_range` (inclusive)
+    /// and reports which addresses were blacklisted and in which blocks.
+    pub async fn find_blacklist_history(
+        &self,
+        token: &str,
+        block_range: (u64, u64),
+    ) -> Result<BlacklistHistory> {
+        let (from_block, to_block) = block_range;
+        info!("🕵️ Scanning blacklist history for {} (blocks {}-{})", token, from_block, to_block);
+
+        let mut events = Vec::new();
+        for page in 0..BLACKLIST_MAX_PAGES {
+            let filter = TraceFilter {
+                to_address: Some(vec![token.to_lowercase()]),
+                after: Some(page * BLACKLIST_PAGE_SIZE),
+                count: Some(BLACKLIST_PAGE_SIZE),
+                ..TraceFilter::block_range(from_block, to_block)
+            };
+
+            let traces = self.trace_filter(&filter).await?;
+            let page_len = traces.len() as u32;
+            events.extend(traces.iter().filter_map(Self::extract_blacklist_event));
+
+            if page_len < BLACKLIST_PAGE_SIZE {
+                break;
+            }
+        }
+
+        let history = Self::build_blacklist_history(token, from_block, to_block, events);
+        info!("🕵️ {} blacklist calls, {} addresses blacklisted",
+              history.events.len(), history.blacklisted_count());
+        Ok(history)
+    }
+
+    /// Internal: Decode a successful blacklist setter call from a trace
+    fn extract_blacklist_event(trace: &Trace) -> Option<BlacklistEvent> {
+        if trace.error.is_some() {
+            return None;
+        }
+        let TraceAction::Call(call_action) = &trace.action else {
+            return None;
+        };
+
+        let input = call_action.input.to_lowercase();
+        let selector = hex::decode(input.strip_prefix("0x")?.get(..8)?).ok()?;
+        let (_, function) = BLACKLIST_SETTER_SELECTORS
+            .iter()
+            .find(|(sel, _)| sel[..] == selector[..])?;
+
+        let args = &input[10..];
+        let word = |i: usize| args.get(i * 64..(i + 1) * 64);
+        let word_to_address = |w: &str| format!("0x{}", &w[24..]);
+        let word_to_usize = |w: &str| usize::from_str_radix(&w[48..], 16).ok();
+
+        let addresses = if *function == "setBots" {
+            // setBots(address[],bool): word0 = offset to array, then length + items
+            let offset_words = word(0).and_then(word_to_usize)? / 32;
+            // The length comes from calldata: never read past the words actually present
+            let present = (args.len() / 64).saturating_sub(offset_words + 1);
+            let len = word(offset_words).and_then(word_to_usize)?.min(present);
+            (0..len)
+                .filter_map(|i| word(offset_words + 1 + i).map(word_to_address))
+                .collect()
+        } else {
+            vec![word(0).map(word_to_address)?]
+        };
+
+        // (address,bool) / (address[],bool) setters called with `false` un-blacklist
+        if matches!(*function, "setBots" | "setBot" | "setBlacklist" | "blacklistUpdate")
+            && word(1).and_then(word_to_usize) == Some(0)
+        {
+            return None;
+        }
+
+        Some(BlacklistEvent {
+            block_number: trace.block_number,
+            transaction_hash: trace.transaction_hash.clone(),
+            caller: call_action.from.clone(),
+            function: function.to_string(),
+            addresses,
+        })
+    }
+
+    /// Internal: Aggregate blacklist events into a history summary
+    fn build_blacklist_history(
+        token: &str,
+        from_block: u64,
+        to_block: u64,
+        events: Vec<BlacklistEvent>,
+    ) -> BlacklistHistory {
+        let mut blacklisted_addresses: Vec<String> = Vec::new();
+        for addr in events.iter().flat_map(|e| e.addresses.iter()) {
+            if !blacklisted_addresses.contains(addr) {
+                blacklisted_addresses.push(addr.clone());
+            }
+        }
+
+        BlacklistHistory {
+            token: token.to_string(),
+            from_block,
+            to_block,
+            first_block: events.iter().filter_map(|e| e.block_number).min(),
+            last_block: events.iter().filter_map(|e| e.block_number).max(),
+            blacklisted_addresses,
+            events,
+        }
+    }
+
+    /// Replay a historical victim transaction and explain the loss
+    ///
+    /// Traces the tx, reads its Transfer logs, and quantifies what the victim
+    /// expected vs. what they received (and where the difference went).
+    pub async fn explain_loss(&self, tx_hash: &str) -> Result<LossExplanation> {
+        info!("🔬 Explaining loss for transaction: {}", tx_hash);
+
+        let traces = self.trace_transaction(tx_hash).await?;
+        let receipt: serde_json::Value = self
+            .provider
+            .call("eth_getTransactionReceipt", serde_json::json!([tx_hash]))
+            .await?;
+        if receipt.is_null() {
+            return Err(eyre!("Transaction receipt not found: {}", tx_hash));
+        }
+
+        let tx_success = receipt["status"].as_str() == Some("0x1");
+        let swap = Self::receipt_swap_transfers(&receipt);
+
+        let analysis = self.analyze_traces(&traces, None, Some(&swap));
+        Ok(Self::build_loss_explanation(
+            tx_hash,
+            &swap.caller,
+            tx_success,
+            &traces,
+            &swap.transfers,
+            &analysis,
+        ))
+    }
+
+    /// Internal: Sender, target and decoded Transfer logs of a receipt
+    fn receipt_swap_transfers(receipt: &serde_json::Value) -> SwapTransfers {
+        SwapTransfers {
+            caller: receipt["from"].as_str().unwrap_or_default().to_lowercase(),
+            router: receipt["to"].as_str().unwrap_or_default().to_lowercase(),
+            transfers: receipt["logs"]
+                .as_array()
+                .map(|logs| logs.iter().filter_map(Self::parse_transfer_log).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Internal: Decode Transfer events from a callTracer frame tree (`withLog`)
+    ///
+    /// Each frame carries the logs it emitted itself, so the emitting token is
+    /// the log's own address. Reverted frames are skipped - their logs never landed.
+    fn call_frame_transfer_logs(frame: &CallFrame) -> Vec<TransferLog> {
+        if frame.error.is_some() {
+            return Vec::new();
+        }
+        let mut logs: Vec<TransferLog> = frame.logs.iter().filter_map(Self::parse_transfer_log).collect();
+        for call in &frame.calls {
+            logs.extend(Self::call_frame_transfer_logs(call));
+        }
+        logs
+    }
+
+    /// Internal: Sum swap Transfer events whose recipient is not a swap participant
+    ///
+    /// Participants are the caller, the router, pools called with a swap selector,
+    /// and any address that sends out a different token than it receives (a pool
+    /// mid-route). Everything else - fee wallets, the token contract itself, the
+    /// zero address - is a hidden fee, aggregated per (recipient, token).
+    fn hidden_fee_recipients(traces: &[Trace], swap: &SwapTransfers) -> Vec<HiddenFeeRecipient> {
+        let mut participants: Vec<String> = vec![swap.caller.to_lowercase(), swap.router.to_lowercase()];
+        for trace in traces.iter().filter(|t| t.error.is_none()) {
+            if let TraceAction::Call(call) = &trace.action {
+                let input = call.input.to_lowercase();
+                if SWAP_SELECTORS.iter().any(|sel| input.starts_with(sel)) {
+                    participants.push(call.to.to_lowercase());
+                }
+            }
+        }
+        for log in &swap.transfers {
+            let swaps_tokens = swap
+                .transfers
+                .iter()
+                .any(|other| other.to == log.from && other.token != log.token);
+            if swaps_tokens {
+                participants.push(log.from.clone());
+            }
+        }
+
+        let mut recipients: Vec<HiddenFeeRecipient> = Vec::new();
+        for log in &swap.transfers {
+            if log.amount.is_zero() || participants.contains(&log.to) {
+                continue;
+            }
+            match recipients
+                .iter_mut()
+                .find(|r| r.address == log.to && r.token == log.token)
+            {
+                Some(recipient) => recipient.amount += log.amount,
+                None => recipients.push(HiddenFeeRecipient {
+                    address: log.to.clone(),
+                    token: log.token.clone(),
+                    amount: log.amount,
+                }),
+            }
+        }
+        recipients
+    }
+
+    /// Internal: Decode an ERC20 Transfer log from a receipt log entry
+    fn parse_transfer_log(log: &serde_json::Value) -> Option<TransferLog> {
+        let topics = log["topics"].as_array()?;
+        if topics.len() != 3 || topics[0].as_str()? != TRANSFER_EVENT_TOPIC {
+            return None;
+        }
+        let topic_to_address = |t: &serde_json::Value| {
+            t.as_str().filter(|t| t.len() == 66).map(|t| format!("0x{}", &t[26..]).to_lowercase())
+        };
+
+        Some(TransferLog {
+            token: log["address"].as_str()?.to_lowercase(),
+            from: topic_to_address(&topics[1])?,
+            to: topic_to_address(&topics[2])?,
+            amount: parse_hex_u256(log["data"].as_str()?),
+        })
+    }
+
+    /// Internal: Aggregate traces + Transfer logs into a loss explanation
+    fn build_loss_explanation(
+        tx_hash: &str,
+        victim: &str,
+        tx_success: bool,
+        traces: &[Trace],
+        logs: &[TransferLog],
+        analysis: &HoneypotTraceAnalysis,
+    ) -> LossExplanation {
+        let mut native_sent = U256::ZERO;
+        let mut native_received = U256::ZERO;
+        let mut expected_in: Vec<(String, U256)> = Vec::new();
+
+        for trace in traces.iter().filter(|t| t.error.is_none()) {
+            let TraceAction::Call(call) = &trace.action else {
+                continue;
+            };
+            let (from, to) = (call.from.to_lowercase(), call.to.to_lowercase());
+            let value = parse_hex_u256(&call.value);
+            if from == victim {
+                native_sent += value;
+            }
+            if to == victim {
+                native_received += value;
+            }
+
+            // transfer(victim, amount) requested by the counterparty (pair/router)
+            let input = call.input.to_lowercase();
+            if input.starts_with("0xa9059cbb") && input.len() >= 10 + 128 {
+                let recipient = format!("0x{}", &input[10 + 24..10 + 64]);
+                if recipient == victim {
+                    let amount = parse_hex_u256(&input[10 + 64..10 + 128]);
+                    match expected_in.iter_mut().find(|(token, _)| *token == to) {
+                        Some((_, total)) => *total += amount,
+                        None => expected_in.push((to, amount)),
+                    }
+                }
+            }
+        }
+
+        let mut tokens: Vec<&str> = Vec::new();
+        for log in logs {
+            if (log.from == victim || log.to == victim) && !tokens.contains(&log.token.as_str()) {
+                tokens.push(&log.token);
+            }
+        }
+
+        let mut flows = Vec::new();
+        let mut leaks = Vec::new();
+        for token in tokens {
+            let token_logs: Vec<&TransferLog> = logs.iter().filter(|l| l.token == token).collect();
+            let received = token_logs
+                .iter()
+                .filter(|l| l.to == victim)
+                .fold(U256::ZERO, |acc, l| acc + l.amount);
+            let outbound: Vec<&&TransferLog> = token_logs.iter().filter(|l| l.from == victim).collect();
+            let sent = outbound.iter().fold(U256::ZERO, |acc, l| acc + l.amount);
+
+            // Largest outbound transfer is the real counterparty; everything else is skimmed
+            let main_out = outbound.iter().max_by_key(|l| l.amount).map(|l| l.to.clone());
+            let mut skimmed = U256::ZERO;
+            for l in outbound.iter().filter(|l| Some(&l.to) != main_out.as_ref()) {
+                skimmed += l.amount;
+                leaks.push(ValueLeak { token: token.to_string(), to: l.to.clone(), amount: l.amount });
+            }
+
+            let expected = expected_in
+                .iter()
+                .find(|(t, _)| t == token)
+                .map(|(_, a)| *a)
+                .unwrap_or(received);
+            if expected > received {
+                leaks.push(ValueLeak {
+                    token: token.to_string(),
+                    to: "(withheld by token contract)".to_string(),
+                    amount: expected - received,
+                });
+            }
+
+            flows.push(TokenFlow {
+                token: token.to_string(),
+                expected_in: expected,
+                received,
+                sent,
+                buy_tax_percent: loss_percent(expected, received),
+                sell_tax_percent: loss_percent(sent, sent - skimmed),
+            });
+        }
+
+        let effective_tax_percent = flows
+            .iter()
+            .map(|f| f.buy_tax_percent.max(f.sell_tax_percent))
+            .fold(0.0, f64::max);
+
+        let summary = if !tx_success {
+            "Transaction reverted - the victim paid gas but no tokens moved".to_string()
+        } else if flows.is_empty() {
+            "No token transfers involving the sender were found".to_string()
+        } else {
+            let legs: Vec<String> = flows
+                .iter()
+                .map(|f| format!(
+                    "{}: expected {} received {} ({:.2}% lost in), sent {} ({:.2}% skimmed out)",
+                    f.token, f.expected_in, f.received, f.buy_tax_percent, f.sent, f.sell_tax_percent
+                ))
+                .collect();
+            format!(
+                "Effective loss {:.2}% across {} token(s), {} leak(s). {}",
+                effective_tax_percent, flows.len(), leaks.len(), legs.join("; ")
+            )
+        };
+
+        let worst_buy_tax = flows.iter().map(|f| f.buy_tax_percent).fold(0.0, f64::max);
+        let worst_sell_tax = flows.iter().map(|f| f.sell_tax_percent).fold(0.0, f64::max);
+        let risk_score = analysis
+            .risk_score_builder()
+            .with_tax_analysis(worst_buy_tax, worst_sell_tax)
+            .build();
+
+        LossExplanation {
+            tx_hash: tx_hash.to_string(),
+            victim: victim.to_string(),
+            tx_success,
+            native_sent,
+            native_received,
+            flows,
+            leaks,
+            effective_tax_percent,
+            is_honeypot: analysis.is_honeypot,
+            confidence: analysis.confidence,
+            red_flags: analysis.red_flags.iter().map(|f| f.description.clone()).collect(),
+            risk_score,
+            summary,
+        }
+    }
+
     // ============================================
     // DEBUG API METHODS
     // ============================================
@@ -396,6 +1012,30 @@ impl TraceClient {
         self.provider.call::<DebugTrace>("debug_traceCall", params).await
     }
 
+    /// Simulate a call with the built-in callTracer, logs included
+    ///
+    /// A call tree with logs stays small where memory-enabled struct logs of a
+    /// whole swap run to hundreds of MB
+    pub async fn debug_trace_call_frame(
+        &self,
+        call: &serde_json::Value,
+        block_number: Option<&str>,
+    ) -> Result<CallFrame> {
+        debug!("🐛 Call-tracing call simulation");
+
+        let block = block_number.unwrap_or("latest");
+        let trace_config = DebugTraceConfig {
+            disable_storage: None,
+            disable_memory: None,
+            disable_stack: None,
+            tracer: Some("callTracer".to_string()),
+            tracer_config: Some(serde_json::json!({ "withLog": true })),
+            timeout: Some("10s".to_string()),
+        };
+        let params = serde_json::json!([call, block, trace_config]);
+        self.provider.call::<CallFrame>("debug_traceCall", params).await
+    }
+
     // ============================================
     // HONEYPOT ANALYSIS METHODS
     // ============================================
@@ -409,8 +1049,15 @@ impl TraceClient {
         // Get both trace and debug information
         let traces = self.trace_transaction(tx_hash).await?;
         let debug_trace = self.debug_trace_transaction(tx_hash, None).await.ok();
+        let swap = self
+            .provider
+            .call::<serde_json::Value>("eth_getTransactionReceipt", serde_json::json!([tx_hash]))
+            .await
+            .ok()
+            .filter(|receipt| !receipt.is_null())
+            .map(|receipt| Self::receipt_swap_transfers(&receipt));
         
-        let analysis = self.analyze_traces(&traces, debug_trace.as_ref());
+        let analysis = self.analyze_traces(&traces, debug_trace.as_ref(), swap.as_ref());
         Ok(analysis)
     }
 
@@ -436,16 +1083,30 @@ impl TraceClient {
         
         let trace_result = self.trace_call(&call, &["trace"], None).await?;
         let debug_trace = self.debug_trace_call(&call, None, None).await.ok();
+        // Transfer events for hidden fee attribution
+        let swap = self.debug_trace_call_frame(&call, None).await.ok().map(|frame| SwapTransfers {
+            caller: from.to_lowercase(),
+            router: to.to_lowercase(),
+            transfers: Self::call_frame_transfer_logs(&frame),
+        });
         
         // Parse traces from simulation result
         let traces = self.parse_trace_call_result(&trace_result)?;
-        let analysis = self.analyze_traces(&traces, debug_trace.as_ref());
+        let analysis = self.analyze_traces(&traces, debug_trace.as_ref(), swap.as_ref());
         
         Ok(analysis)
     }
 
     /// Internal: Analyze traces for honeypot patterns
-    fn analyze_traces(&self, traces: &[Trace], debug_trace: Option<&DebugTrace>) -> HoneypotTraceAnalysis {
+    ///
+    /// With `swap`, its Transfer events are attributed to participants and
+    /// value reaching anyone else is reported as a hidden fee.
+    fn analyze_traces(
+        &self,
+        traces: &[Trace],
+        debug_trace: Option<&DebugTrace>,
+        swap: Option<&SwapTransfers>,
+    ) -> HoneypotTraceAnalysis {
         let mut red_flags = Vec::new();
         let mut internal_calls = Vec::new();
         let state_changes = Vec::new(); // Placeholder for now
@@ -482,6 +1143,22 @@ impl TraceClient {
             }
         }
         
+        // Attribute swap value flowing outside caller/pair/router
+        let hidden_fee_recipients = swap
+            .map(|swap| Self::hidden_fee_recipients(traces, swap))
+            .unwrap_or_default();
+        for recipient in &hidden_fee_recipients {
+            red_flags.push(HoneypotRedFlag {
+                flag_type: RedFlagType::HiddenTransfer,
+                description: format!(
+                    "Hidden fee: {} of token {} sent to {}",
+                    recipient.amount, recipient.token, recipient.address
+                ),
+                trace_address: vec![],
+                severity: Severity::Medium,
+            });
+        }
+        
         // Analyze gas usage
         let gas_analysis = self.analyze_gas_usage(traces, debug_trace);
         
@@ -496,6 +1173,7 @@ impl TraceClient {
             internal_calls,
             state_changes,
             gas_analysis,
+            hidden_fee_recipients,
         }
     }
 
@@ -656,6 +1334,15 @@ impl TraceClient {
     }
 }
 
+/// Parse a 0x-prefixed (or bare) hex quantity into U256, zero on failure
+fn parse_hex_u256(hex_str: &str) -> U256 {
+    let digits = hex_str.trim_start_matches("0x");
+    if digits.is_empty() {
+        return U256::ZERO;
+    }
+    U256::from_str_radix(digits, 16).unwrap_or(U256::ZERO)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -667,6 +1354,262 @@ mod tests {
         assert_eq!(filter.from_address.unwrap().len(), 1);
     }
 
+    fn call_trace(input: String, block: u32) -> Trace {
+        Trace {
+            action: TraceAction::Call(CallAction {
+                from: "0x00000000000000000000000000000000000000aa".to_string(),
+                to: "0x00000000000000000000000000000000000000bb".to_string(),
+                value: "0x0".to_string(),
+                gas: "0x0".to_string(),
+                input,
+                call_type: "call".to_string(),
+            }),
+            result: None,
+            trace_address: vec![],
+            subtraces: 0,
+            transaction_position: None,
+            transaction_hash: None,
+            block_number: Some(block),
+            block_hash: None,
+            error: None,
+            trace_type: TraceType::Call,
+        }
+    }
+
+    #[test]
+    fn test_blacklist_history_extraction() {
+        let addr1 = format!("{:0>64}", "1111111111111111111111111111111111111111");
+        let addr2 = format!("{:0>64}", "2222222222222222222222222222222222222222");
+        let t = format!("{:0>64}", "1");
+        let f = format!("{:0>64}", "0");
+
+        // setBot(addr1, true)
+        let set_bot = call_trace(format!("0x342aa8b5{}{}", addr1, t), 100);
+        // setBots([addr1, addr2], true): offset 0x40, flag, len 2, items
+        let set_bots = call_trace(
+            format!("0x9c0db5f3{:0>64}{}{:0>64}{}{}", "40", t, "2", addr1, addr2),
+            200,
+        );
+        // setBot(addr2, false) - un-blacklist, must be ignored
+        let unset_bot = call_trace(format!("0x342aa8b5{}{}", addr2, f), 300);
+
+        let events: Vec<_> = [set_bot, set_bots, unset_bot]
+            .iter()
+            .filter_map(TraceClient::extract_blacklist_event)
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].addresses.len(), 2);
+
+        let history = TraceClient::build_blacklist_history("0xbb", 0, 1000, events);
+        assert_eq!(history.blacklisted_count(), 2);
+        assert_eq!(history.first_block, Some(100));
+        assert_eq!(history.last_block, Some(200));
+        assert!(history.is_soft_rug(2));
+    }
+
+    #[test]
+    fn test_blacklist_setter_selectors() {
+        let signatures = [
+            "setBots(address[],bool)",
+            "setBot(address,bool)",
+            "blacklistAddress(address)",
+            "addToBlacklist(address)",
+            "setBlacklist(address,bool)",
+            "addBot(address)",
+            "blacklistUpdate(address,bool)",
+            "blacklist(address)",
+        ];
+        for ((selector, name), signature) in BLACKLIST_SETTER_SELECTORS.iter().zip(signatures) {
+            assert!(signature.starts_with(&format!("{}(", name)));
+            assert_eq!(selector[..], alloy_primitives::keccak256(signature)[..4], "{}", signature);
+        }
+        assert_eq!(hex::encode(blacklistUpdateCall::SELECTOR), "b351dfe8");
+    }
+
+    #[test]
+    fn test_blacklist_event_clamps_array_length() {
+        let addr1 = format!("{:0>64}", "1111111111111111111111111111111111111111");
+        let t = format!("{:0>64}", "1");
+        // setBots claiming 2^64 - 1 items with only one present: returns at once with that one
+        let oversized = call_trace(format!("0x9c0db5f3{:0>64}{}{}{}", "40", t, "f".repeat(64), addr1), 1);
+        let event = TraceClient::extract_blacklist_event(&oversized).unwrap();
+        assert_eq!(event.addresses, vec![format!("0x{}", &addr1[24..])]);
+
+        // Truncated calldata: no array length word at all
+        let truncated = call_trace(format!("0x9c0db5f3{:0>64}", "40"), 1);
+        assert!(TraceClient::extract_blacklist_event(&truncated).is_none());
+    }
+
+    #[test]
+    fn test_explain_loss_fee_on_transfer() {
+        let victim = "0x00000000000000000000000000000000000000aa";
+        let token = "0x00000000000000000000000000000000000000bb";
+        let pair = "0x00000000000000000000000000000000000000cc";
+        let fee_wallet = "0x00000000000000000000000000000000000000dd";
+
+        // Pair asks the token to send 1000 to the victim
+        let mut pair_call = call_trace(
+            format!("0xa9059cbb{:0>64}{:0>64}", &victim[2..], "3e8"),
+            1,
+        );
+        if let TraceAction::Call(ref mut c) = pair_call.action {
+            c.from = pair.to_string();
+            c.to = token.to_string();
+        }
+
+        let logs = vec![
+            // Victim only receives 900 (10% buy tax)
+            TransferLog { token: token.into(), from: pair.into(), to: victim.into(), amount: U256::from(900u64) },
+            // Victim sells 900: 720 to pair, 180 skimmed to fee wallet (20% sell tax)
+            TransferLog { token: token.into(), from: victim.into(), to: pair.into(), amount: U256::from(720u64) },
+            TransferLog { token: token.into(), from: victim.into(), to: fee_wallet.into(), amount: U256::from(180u64) },
+        ];
+        let analysis = HoneypotTraceAnalysis {
+            is_honeypot: false,
+            confidence: 0.0,
+            red_flags: vec![],
+            internal_calls: vec![],
+            state_changes: vec![],
+            gas_analysis: GasAnalysis { total_gas: 0, gas_per_call: vec![], unusual_gas_usage: false, gas_efficiency: 1.0 },
+            hidden_fee_recipients: vec![],
+        };
+
+        let explanation =
+            TraceClient::build_loss_explanation("0x01", victim, true, &[pair_call], &logs, &analysis);
+        assert_eq!(explanation.flows.len(), 1);
+        let flow = &explanation.flows[0];
+        assert_eq!(flow.expected_in, U256::from(1000u64));
+        assert!((flow.buy_tax_percent - 10.0).abs() < 1e-6);
+        assert!((flow.sell_tax_percent - 20.0).abs() < 1e-6);
+        assert!((explanation.effective_tax_percent - 20.0).abs() < 1e-6);
+        assert_eq!(explanation.leaks.len(), 2);
+    }
+
+    #[test]
+    fn test_trace_analysis_risk_components() {
+        let flag = |flag_type, severity| HoneypotRedFlag {
+            flag_type,
+            description: "test".to_string(),
+            trace_address: vec![],
+            severity,
+        };
+        let analysis = HoneypotTraceAnalysis {
+            is_honeypot: false,
+            confidence: 0.4,
+            red_flags: vec![
+                flag(RedFlagType::BlacklistCall, Severity::High),
+                flag(RedFlagType::UnexpectedRevert, Severity::Medium),
+            ],
+            internal_calls: vec![],
+            state_changes: vec![],
+            gas_analysis: GasAnalysis { total_gas: 0, gas_per_call: vec![], unusual_gas_usage: false, gas_efficiency: 1.0 },
+            hidden_fee_recipients: vec![],
+        };
+
+        let components = RiskComponents::from(&analysis);
+        assert_eq!(components.honeypot, 50); // Medium revert beats 40% confidence
+        assert_eq!(components.contract, 75);
+        assert_eq!(components.tax, 0);
+
+        let flagged = HoneypotTraceAnalysis { is_honeypot: true, confidence: 0.8, ..analysis };
+        assert_eq!(RiskComponents::from(&flagged).honeypot, 95);
+
+        let score = flagged.risk_score_builder().with_tax_analysis(40.0, 40.0).build();
+        assert_eq!(score.components.tax, 90);
+        assert!(score.total > flagged.risk_score().total);
+    }
+
+    #[test]
+    fn test_hidden_fee_recipients() {
+        let caller = "0x00000000000000000000000000000000000000aa";
+        let token = "0x00000000000000000000000000000000000000bb";
+        let pair = "0x00000000000000000000000000000000000000cc";
+        let fee_wallet = "0x00000000000000000000000000000000000000dd";
+        let router = "0x00000000000000000000000000000000000000ee";
+        let weth = "0x00000000000000000000000000000000000000ff";
+        let transfer = |token: &str, from: &str, to: &str, amount: u64| TransferLog {
+            token: token.into(),
+            from: from.into(),
+            to: to.into(),
+            amount: U256::from(amount),
+        };
+
+        // Buy: WETH in, 900 to the caller, 50 + 50 to a fee wallet, 20 kept by the token
+        let swap = SwapTransfers {
+            caller: caller.into(),
+            router: router.into(),
+            transfers: vec![
+                transfer(weth, router, pair, 1_000),
+                transfer(token, pair, caller, 900),
+                transfer(token, pair, fee_wallet, 50),
+                transfer(token, pair, fee_wallet, 50),
+                transfer(token, pair, token, 20),
+            ],
+        };
+        let recipients = TraceClient::hidden_fee_recipients(&[], &swap);
+        assert_eq!(
+            recipients,
+            vec![
+                HiddenFeeRecipient { address: fee_wallet.into(), token: token.into(), amount: U256::from(100u64) },
+                HiddenFeeRecipient { address: token.into(), token: token.into(), amount: U256::from(20u64) },
+            ]
+        );
+
+        // Clean swap: every transfer stays between caller, router and pair
+        let clean = SwapTransfers {
+            transfers: vec![transfer(weth, router, pair, 1_000), transfer(token, pair, caller, 1_000)],
+            ..swap
+        };
+        assert!(TraceClient::hidden_fee_recipients(&[], &clean).is_empty());
+    }
+
+    #[test]
+    fn test_call_frame_transfer_logs() {
+        let topic = |address: &str| format!("0x{:0>64}", address.trim_start_matches("0x"));
+        let transfer = |token: &str, from: &str, to: &str, amount: &str| {
+            serde_json::json!({
+                "address": token,
+                "topics": [TRANSFER_EVENT_TOPIC, topic(from), topic(to)],
+                "data": amount,
+            })
+        };
+        let router = "0x00000000000000000000000000000000000000ee";
+        let token = "0x00000000000000000000000000000000000000bb";
+        // Router calls the token, which pays 1000 to the fee wallet; a reverted
+        // frame's transfer and a non-Transfer log are ignored
+        let frame: CallFrame = serde_json::from_value(serde_json::json!({
+            "from": "0x00000000000000000000000000000000000000aa",
+            "to": router,
+            "type": "CALL",
+            "calls": [
+                {
+                    "from": router,
+                    "to": token,
+                    "type": "CALL",
+                    "logs": [
+                        transfer(token, "0xaa", "0xdd", "0x3e8"),
+                        { "address": token, "topics": ["0x01"], "data": "0x" },
+                    ],
+                },
+                {
+                    "from": router,
+                    "to": token,
+                    "type": "CALL",
+                    "error": "execution reverted",
+                    "logs": [transfer(token, "0xaa", "0xcc", "0x1")],
+                },
+            ],
+        }))
+        .unwrap();
+
+        let logs = TraceClient::call_frame_transfer_logs(&frame);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].token, token);
+        assert_eq!(logs[0].from, "0x00000000000000000000000000000000000000aa");
+        assert_eq!(logs[0].to, "0x00000000000000000000000000000000000000dd");
+        assert_eq!(logs[0].amount, U256::from(1000u64));
+    }
+
     #[test]
     fn test_debug_trace_config_default() {
         let config = DebugTraceConfig::default();
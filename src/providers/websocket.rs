@@ -25,14 +25,18 @@
 use eyre::{eyre, Result};
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, error, info, warn};
 
+use crate::models::types::TxFees;
+use crate::utils::backoff::BackoffConfig;
 use crate::utils::constants::{
-    get_alchemy_subdomain, CHAIN_ID_ETHEREUM, CHAIN_ID_POLYGON,
+    get_alchemy_subdomain, unix_timestamp, CHAIN_ID_ETHEREUM, CHAIN_ID_POLYGON,
 };
 
 // ============================================
@@ -52,6 +56,15 @@ const WS_PING_INTERVAL_SECS: u64 = 30;
 /// Maximum reconnection attempts before giving up
 const WS_MAX_RECONNECT_ATTEMPTS: u32 = 10;
 
+/// Reconnect policy (1s→2s→4s→...→30s, small jitter so clients don't reconnect in lockstep)
+const WS_RECONNECT_BACKOFF: BackoffConfig = BackoffConfig {
+    base_delay_ms: WS_RECONNECT_BASE_MS,
+    max_delay_ms: WS_RECONNECT_MAX_MS,
+    max_attempts: WS_MAX_RECONNECT_ATTEMPTS,
+    jitter_percent: 10,
+    min_delay_ms: WS_RECONNECT_BASE_MS / 2,
+};
+
 // ============================================
 // EVENT TYPES
 // ============================================
@@ -102,6 +115,24 @@ pub struct PendingTransaction {
     pub nonce: String,
 }
 
+impl PendingTransaction {
+    /// Fee fields as numbers: the EIP-1559 caps when present, the legacy gas price otherwise
+    /// (Alchemy also fills `gasPrice` on 1559 transactions)
+    pub fn fees(&self) -> TxFees {
+        let quantity = |hex: &Option<String>| {
+            hex.as_deref()
+                .and_then(|h| u128::from_str_radix(h.trim_start_matches("0x"), 16).ok())
+        };
+        match quantity(&self.max_fee_per_gas) {
+            Some(max_fee) => TxFees::eip1559(max_fee, quantity(&self.max_priority_fee_per_gas).unwrap_or(0)),
+            None => TxFees {
+                gas_price: quantity(&self.gas_price),
+                ..Default::default()
+            },
+        }
+    }
+}
+
 /// Mined transaction from alchemy_minedTransactions
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -223,6 +254,47 @@ impl PendingTxFilter {
     }
 }
 
+/// One `addresses` entry of a mined transaction filter: matches `from` and/or `to`
+#[derive(Debug, Clone, Serialize, Default, PartialEq)]
+pub struct MinedTxAddress {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+/// Mined transaction filter (`alchemy_minedTransactions`)
+/// A transaction matches if it matches any `addresses` entry (no entries = every transaction)
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MinedTxFilter {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub addresses: Vec<MinedTxAddress>,
+    pub include_removed: bool,
+    pub hashes_only: bool,
+}
+
+impl MinedTxFilter {
+    /// Filter for transactions TO a specific address (e.g., DEX router)
+    pub fn to_address(address: &str) -> Self {
+        Self {
+            addresses: vec![MinedTxAddress { from: None, to: Some(address.to_string()) }],
+            ..Default::default()
+        }
+    }
+
+    /// Filter for transactions sent to or from an address (e.g., a token contract)
+    pub fn touching(address: &str) -> Self {
+        Self {
+            addresses: vec![
+                MinedTxAddress { from: None, to: Some(address.to_string()) },
+                MinedTxAddress { from: Some(address.to_string()), to: None },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
 // ============================================
 // WEBSOCKET CLIENT
 // ============================================
@@ -232,6 +304,8 @@ pub struct AlchemyWsClient {
     chain_id: u64,
     api_key: String,
     is_connected: Arc<AtomicBool>,
+    /// Unix seconds of the last delivered event or (re)connect (0 = never)
+    last_event_at: Arc<AtomicU64>,
     subscription_id: Arc<AtomicU64>,
 }
 
@@ -249,6 +323,7 @@ impl AlchemyWsClient {
             chain_id,
             api_key,
             is_connected: Arc::new(AtomicBool::new(false)),
+            last_event_at: Arc::new(AtomicU64::new(0)),
             subscription_id: Arc::new(AtomicU64::new(1)),
         })
     }
@@ -276,6 +351,7 @@ impl AlchemyWsClient {
         let (tx, rx) = mpsc::channel(100);
         let url = self.ws_url()?;
         let is_connected = self.is_connected.clone();
+        let last_event_at = self.last_event_at.clone();
         let sub_id = self.subscription_id.fetch_add(1, Ordering::SeqCst);
 
         tokio::spawn(async move {
@@ -286,6 +362,7 @@ impl AlchemyWsClient {
                 sub_id,
                 tx,
                 is_connected,
+                last_event_at,
                 Self::parse_new_head,
             )
             .await;
@@ -301,6 +378,7 @@ impl AlchemyWsClient {
         let (tx, rx) = mpsc::channel(100);
         let url = self.ws_url()?;
         let is_connected = self.is_connected.clone();
+        let last_event_at = self.last_event_at.clone();
         let sub_id = self.subscription_id.fetch_add(1, Ordering::SeqCst);
 
         let params = serde_json::json!(["logs", filter]);
@@ -313,6 +391,7 @@ impl AlchemyWsClient {
                 sub_id,
                 tx,
                 is_connected,
+                last_event_at,
                 Self::parse_log,
             )
             .await;
@@ -338,6 +417,7 @@ impl AlchemyWsClient {
         let (tx, rx) = mpsc::channel(1000); // Higher buffer for pending tx
         let url = self.ws_url()?;
         let is_connected = self.is_connected.clone();
+        let last_event_at = self.last_event_at.clone();
         let sub_id = self.subscription_id.fetch_add(1, Ordering::SeqCst);
 
         let params = if let Some(f) = filter {
@@ -354,6 +434,7 @@ impl AlchemyWsClient {
                 sub_id,
                 tx,
                 is_connected,
+                last_event_at,
                 Self::parse_pending_tx,
             )
             .await;
@@ -363,16 +444,20 @@ impl AlchemyWsClient {
     }
 
     /// Subscribe to mined transactions
-    pub async fn subscribe_mined_tx(&self) -> Result<mpsc::Receiver<WsEvent>> {
+    ///
+    /// WARNING: without address filters this streams every mined transaction.
+    /// Use MinedTxFilter::touching() to watch a single token or router.
+    pub async fn subscribe_mined_tx(
+        &self,
+        filter: Option<MinedTxFilter>,
+    ) -> Result<mpsc::Receiver<WsEvent>> {
         let (tx, rx) = mpsc::channel(100);
         let url = self.ws_url()?;
         let is_connected = self.is_connected.clone();
+        let last_event_at = self.last_event_at.clone();
         let sub_id = self.subscription_id.fetch_add(1, Ordering::SeqCst);
 
-        let params = serde_json::json!(["alchemy_minedTransactions", {
-            "includeRemoved": false,
-            "hashesOnly": false
-        }]);
+        let params = serde_json::json!(["alchemy_minedTransactions", filter.unwrap_or_default()]);
 
         tokio::spawn(async move {
             Self::run_subscription(
@@ -382,6 +467,7 @@ impl AlchemyWsClient {
                 sub_id,
                 tx,
                 is_connected,
+                last_event_at,
                 Self::parse_mined_tx,
             )
             .await;
@@ -391,6 +477,7 @@ impl AlchemyWsClient {
     }
 
     /// Internal: Run subscription with reconnection logic
+    #[allow(clippy::too_many_arguments)]
     async fn run_subscription<F>(
         url: String,
         method: &str,
@@ -398,20 +485,21 @@ impl AlchemyWsClient {
         sub_id: u64,
         tx: mpsc::Sender<WsEvent>,
         is_connected: Arc<AtomicBool>,
+        last_event_at: Arc<AtomicU64>,
         parser: F,
     ) where
         F: Fn(&str) -> Option<WsEvent> + Send + 'static,
     {
         let mut reconnect_attempts = 0;
-        let mut reconnect_delay = WS_RECONNECT_BASE_MS;
 
         loop {
             match connect_async(&url).await {
                 Ok((ws_stream, _)) => {
                     info!("🔌 WebSocket connected to Alchemy");
                     is_connected.store(true, Ordering::SeqCst);
+                    // Idle time counts from the (re)connect until the first event
+                    last_event_at.store(unix_timestamp(), Ordering::SeqCst);
                     reconnect_attempts = 0;
-                    reconnect_delay = WS_RECONNECT_BASE_MS;
 
                     let _ = tx.send(WsEvent::Connected).await;
 
@@ -441,6 +529,7 @@ impl AlchemyWsClient {
                                         info!("📪 Receiver dropped, stopping subscription");
                                         return;
                                     }
+                                    last_event_at.store(unix_timestamp(), Ordering::SeqCst);
                                 }
                             }
                             Ok(Message::Ping(data)) => {
@@ -477,14 +566,13 @@ impl AlchemyWsClient {
                 return;
             }
 
+            // Exponential backoff with cap
+            let reconnect_delay = WS_RECONNECT_BACKOFF.jittered_delay_for(reconnect_attempts);
             warn!(
                 "🔄 Reconnecting in {}ms (attempt {}/{})",
-                reconnect_delay, reconnect_attempts, WS_MAX_RECONNECT_ATTEMPTS
+                reconnect_delay.as_millis(), reconnect_attempts, WS_MAX_RECONNECT_ATTEMPTS
             );
-            tokio::time::sleep(std::time::Duration::from_millis(reconnect_delay)).await;
-
-            // Exponential backoff with cap
-            reconnect_delay = (reconnect_delay * 2).min(WS_RECONNECT_MAX_MS);
+            tokio::time::sleep(reconnect_delay).await;
         }
     }
 
@@ -542,6 +630,16 @@ impl AlchemyWsClient {
         self.is_connected.load(Ordering::SeqCst)
     }
 
+    /// Unix seconds of the last delivered event (or connect); None before the first connect
+    pub fn last_event_at(&self) -> Option<u64> {
+        Some(self.last_event_at.load(Ordering::SeqCst)).filter(|t| *t > 0)
+    }
+
+    /// Connected, but nothing delivered for longer than `max_idle` (silently stalled)
+    pub fn is_stale(&self, max_idle: Duration) -> bool {
+        self.is_connected() && is_idle(self.last_event_at(), unix_timestamp(), max_idle)
+    }
+
     /// Get chain ID
     pub fn chain_id(&self) -> u64 {
         self.chain_id
@@ -593,6 +691,21 @@ impl NewTokenDetector {
         Ok(rx)
     }
 
+    /// Start monitoring and keep the most recent pairs in `feed` (served over HTTP
+    /// instead of the channel). Runs until the subscription ends
+    pub async fn start_feed(&self, feed: Arc<NewPairFeed>) -> Result<()> {
+        let mut rx = self.start().await?;
+        let chain_id = self.ws_client.chain_id();
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                debug!("🆕 New pair on chain {}: {} ({} / {})", chain_id, event.pair, event.token0, event.token1);
+                feed.record(event);
+            }
+            warn!("⚠️ New-pair feed for chain {} stopped", chain_id);
+        });
+        Ok(())
+    }
+
     /// Parse PairCreated event from log
     fn parse_pair_created(log: &LogEvent) -> Option<NewPairEvent> {
         // PairCreated(address indexed token0, address indexed token1, address pair, uint)
@@ -662,6 +775,70 @@ impl NewPairEvent {
     }
 }
 
+/// A `NewPairEvent` as kept by `NewPairFeed`
+#[derive(Debug, Clone)]
+pub struct DetectedPair {
+    /// Unix seconds the event was received
+    pub detected_at: u64,
+    pub event: NewPairEvent,
+}
+
+impl DetectedPair {
+    /// Block the pair was created in (the log reports it as a hex quantity)
+    pub fn block_number(&self) -> Option<u64> {
+        u64::from_str_radix(self.event.block_number.trim_start_matches("0x"), 16).ok()
+    }
+}
+
+/// Ring buffer of the last N pairs a `NewTokenDetector` reported
+pub struct NewPairFeed {
+    capacity: usize,
+    pairs: RwLock<VecDeque<DetectedPair>>,
+    total_detected: AtomicU64,
+}
+
+impl NewPairFeed {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            pairs: RwLock::new(VecDeque::with_capacity(capacity)),
+            total_detected: AtomicU64::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Pairs detected since startup (including evicted ones)
+    pub fn total_detected(&self) -> u64 {
+        self.total_detected.load(Ordering::Relaxed)
+    }
+
+    /// Keep `event`, evicting the oldest pair once full
+    pub fn record(&self, event: NewPairEvent) {
+        if let Ok(mut pairs) = self.pairs.write() {
+            while pairs.len() >= self.capacity {
+                pairs.pop_front();
+            }
+            pairs.push_back(DetectedPair {
+                detected_at: unix_timestamp(),
+                event,
+            });
+        }
+        self.total_detected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Most recent pairs first, at most `limit`
+    pub fn recent(&self, limit: usize) -> Vec<DetectedPair> {
+        self.pairs
+            .read()
+            .map(|pairs| pairs.iter().rev().take(limit).cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
 // ============================================
 // MULTI-CHAIN WEBSOCKET MANAGER
 // ============================================
@@ -706,6 +883,23 @@ impl WsManager {
             .map(|(id, _)| *id)
             .collect()
     }
+
+    /// Connected chains that have not delivered an event within `max_idle`
+    pub fn stale_chains(&self, max_idle: Duration) -> Vec<u64> {
+        self.clients
+            .iter()
+            .filter(|(_, c)| c.is_stale(max_idle))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+/// No event since `last_event_at` for longer than `max_idle` (never = idle)
+fn is_idle(last_event_at: Option<u64>, now: u64, max_idle: Duration) -> bool {
+    match last_event_at {
+        Some(at) => now.saturating_sub(at) > max_idle.as_secs(),
+        None => true,
+    }
 }
 
 #[cfg(test)]
@@ -736,6 +930,24 @@ mod tests {
         assert!(filter.from_address.is_none());
     }
 
+    #[test]
+    fn test_mined_tx_filter() {
+        let router = "0x1234567890123456789012345678901234567890";
+        let json = serde_json::to_value(MinedTxFilter::to_address(router)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "addresses": [{ "to": router }], "includeRemoved": false, "hashesOnly": false })
+        );
+
+        let touching = MinedTxFilter::touching(router);
+        assert_eq!(touching.addresses.len(), 2);
+        assert_eq!(touching.addresses[1].from.as_deref(), Some(router));
+
+        // Unfiltered keeps the previous request shape
+        let json = serde_json::to_value(MinedTxFilter::default()).unwrap();
+        assert_eq!(json, serde_json::json!({ "includeRemoved": false, "hashesOnly": false }));
+    }
+
     #[test]
     fn test_topic_to_address() {
         let topic = "0x000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
@@ -758,4 +970,39 @@ mod tests {
         let new_token = event.get_new_token(weth);
         assert_eq!(new_token, Some("0xnewtoken"));
     }
+
+    #[test]
+    fn test_new_pair_feed_ring() {
+        let feed = NewPairFeed::new(2);
+        for (i, block) in ["0x10", "0x11", "0x12"].into_iter().enumerate() {
+            feed.record(NewPairEvent {
+                factory: "0xfactory".to_string(),
+                token0: format!("0xtoken{}", i),
+                token1: "0xweth".to_string(),
+                pair: format!("0xpair{}", i),
+                block_number: block.to_string(),
+                tx_hash: "0xhash".to_string(),
+            });
+        }
+
+        // Oldest evicted, newest first
+        assert_eq!(feed.total_detected(), 3);
+        let recent = feed.recent(10);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].event.pair, "0xpair2");
+        assert_eq!(recent[0].block_number(), Some(0x12));
+        assert_eq!(recent[1].event.pair, "0xpair1");
+        assert_eq!(feed.recent(1).len(), 1);
+    }
+
+    #[test]
+    fn test_is_idle() {
+        let max_idle = Duration::from_secs(120);
+        assert!(!is_idle(Some(1_000), 1_060, max_idle));
+        assert!(!is_idle(Some(1_000), 1_120, max_idle));
+        assert!(is_idle(Some(1_000), 1_121, max_idle));
+        assert!(is_idle(None, 1_000, max_idle));
+        // Clock skew never reports idle
+        assert!(!is_idle(Some(2_000), 1_000, max_idle));
+    }
 }
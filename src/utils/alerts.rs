@@ -0,0 +1,414 @@
+//! Alerts - Real-Time Honeypot Notifications
+//!
+//! API dan mempool scanner hanya mendeteksi; operator harus polling untuk
+//! tahu ada honeypot. Modul ini mengirim alert ke sink yang bisa dipasang
+//! (webhook generik, Discord, Telegram) setiap kali verdict Honeypot/Critical
+//! muncul di atas severity minimum yang dikonfigurasi.
+
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::models::config::SentryConfig;
+use crate::models::types::RiskLevel;
+use crate::utils::constants::{get_chain_name, unix_timestamp};
+
+/// Per-delivery HTTP timeout - a slow sink must not pile up tasks
+const ALERT_TIMEOUT_SECS: u64 = 5;
+
+/// Risk factors listed in a chat message (the webhook payload carries all of them)
+const ALERT_MAX_FACTORS: usize = 5;
+
+/// Default `ALERT_COOLDOWN_SECS`: one alert per (chain, token) per 10 minutes
+pub const DEFAULT_ALERT_COOLDOWN_SECS: u64 = 600;
+
+/// Tokens remembered before expired cooldowns are swept
+const ALERT_COOLDOWN_SWEEP_LEN: usize = 1_024;
+
+/// One detection worth notifying an operator about
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub severity: RiskLevel,
+    pub chain_id: u64,
+    /// Token the verdict is about (None for transaction-level mempool risks)
+    pub token: Option<String>,
+    /// Mempool transaction that triggered the analysis
+    pub tx_hash: Option<String>,
+    /// 0-100 risk score (API verdicts only)
+    pub risk_score: Option<u8>,
+    pub is_honeypot: bool,
+    pub reason: String,
+    pub risk_factors: Vec<String>,
+    /// Where the verdict came from: "api" | "mempool"
+    pub source: &'static str,
+    /// Unix timestamp (seconds)
+    pub timestamp: u64,
+}
+
+impl Alert {
+    pub fn new(severity: RiskLevel, chain_id: u64, reason: String, source: &'static str) -> Self {
+        Self {
+            severity,
+            chain_id,
+            token: None,
+            tx_hash: None,
+            risk_score: None,
+            is_honeypot: false,
+            reason,
+            risk_factors: Vec::new(),
+            source,
+            timestamp: unix_timestamp(),
+        }
+    }
+
+    pub fn for_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn for_tx(mut self, tx_hash: impl Into<String>) -> Self {
+        self.tx_hash = Some(tx_hash.into());
+        self
+    }
+
+    pub fn with_risk_score(mut self, risk_score: u8) -> Self {
+        self.risk_score = Some(risk_score);
+        self
+    }
+
+    pub fn honeypot(mut self) -> Self {
+        self.is_honeypot = true;
+        self
+    }
+
+    pub fn with_risk_factors(mut self, risk_factors: Vec<String>) -> Self {
+        self.risk_factors = risk_factors;
+        self
+    }
+
+    /// Chat message in the weekly report's style (`**bold**` for Discord, plain for Telegram)
+    pub fn message(&self, markdown: bool) -> String {
+        let bold = |text: &str| if markdown { format!("**{}**", text) } else { text.to_string() };
+        let headline = if self.is_honeypot { "🍯 Honeypot detected" } else { "🚨 High-risk verdict" };
+
+        let mut lines = vec![
+            format!("{} {}", self.severity.emoji(), bold(&format!("RUSTER REVM ALERT: {}", self.severity.as_str()))),
+            String::new(),
+            format!("{} on {}", headline, bold(get_chain_name(self.chain_id))),
+        ];
+        if let Some(token) = &self.token {
+            lines.push(format!("• Token: {}", token));
+        }
+        if let Some(tx_hash) = &self.tx_hash {
+            lines.push(format!("• TX: {}", tx_hash));
+        }
+        if let Some(risk_score) = self.risk_score {
+            lines.push(format!("• Risk score: {}", bold(&format!("{}/100", risk_score))));
+        }
+        lines.push(format!("• Reason: {}", self.reason));
+
+        if !self.risk_factors.is_empty() {
+            lines.push(String::new());
+            lines.push("⚠️ Risk factors:".to_string());
+            lines.extend(self.risk_factors.iter().take(ALERT_MAX_FACTORS).map(|f| format!("• {}", f)));
+            if self.risk_factors.len() > ALERT_MAX_FACTORS {
+                lines.push(format!("• and {} more", self.risk_factors.len() - ALERT_MAX_FACTORS));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Delivery target for alerts
+pub trait AlertSink: Send + Sync {
+    /// Sink name for logs - never the URL (Telegram URLs carry the bot token)
+    fn name(&self) -> &'static str;
+
+    /// HTTP request delivering `alert`
+    fn request(&self, client: &reqwest::Client, alert: &Alert) -> reqwest::RequestBuilder;
+}
+
+/// POSTs the alert as JSON to any URL
+pub struct WebhookSink {
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn request(&self, client: &reqwest::Client, alert: &Alert) -> reqwest::RequestBuilder {
+        client.post(&self.url).json(alert)
+    }
+}
+
+/// Discord channel webhook (markdown message)
+pub struct DiscordSink {
+    webhook_url: String,
+}
+
+impl DiscordSink {
+    pub fn new(webhook_url: impl Into<String>) -> Self {
+        Self { webhook_url: webhook_url.into() }
+    }
+}
+
+impl AlertSink for DiscordSink {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    fn request(&self, client: &reqwest::Client, alert: &Alert) -> reqwest::RequestBuilder {
+        client
+            .post(&self.webhook_url)
+            .json(&serde_json::json!({ "content": alert.message(true) }))
+    }
+}
+
+/// Telegram bot `sendMessage` to one chat (plain text - reasons may contain markdown characters)
+pub struct TelegramSink {
+    bot_token: String,
+    chat_id: String,
+}
+
+impl TelegramSink {
+    pub fn new(bot_token: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        Self { bot_token: bot_token.into(), chat_id: chat_id.into() }
+    }
+}
+
+impl AlertSink for TelegramSink {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    fn request(&self, client: &reqwest::Client, alert: &Alert) -> reqwest::RequestBuilder {
+        client
+            .post(format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token))
+            .json(&serde_json::json!({
+                "chat_id": self.chat_id,
+                "text": alert.message(false),
+                "disable_web_page_preview": true,
+            }))
+    }
+}
+
+/// Fans alerts at or above `min_severity` out to every sink (disabled without sinks)
+/// A token is alerted at most once per `cooldown` per chain
+pub struct AlertDispatcher {
+    sinks: Vec<Box<dyn AlertSink>>,
+    min_severity: RiskLevel,
+    cooldown: Duration,
+    /// Last alert per (chain_id, lowercase token)
+    last_alerted: DashMap<(u64, String), Instant>,
+    client: reqwest::Client,
+    total_sent: Arc<AtomicU64>,
+}
+
+impl AlertDispatcher {
+    pub fn new(min_severity: RiskLevel) -> Self {
+        Self {
+            sinks: Vec::new(),
+            min_severity,
+            cooldown: Duration::from_secs(DEFAULT_ALERT_COOLDOWN_SECS),
+            last_alerted: DashMap::new(),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(ALERT_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            total_sent: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Sinks configured via ALERT_WEBHOOK_URL / ALERT_DISCORD_WEBHOOK_URL /
+    /// ALERT_TELEGRAM_BOT_TOKEN + ALERT_TELEGRAM_CHAT_ID, threshold ALERT_MIN_SEVERITY
+    pub fn from_sentry_config(config: &SentryConfig) -> Self {
+        let mut dispatcher =
+            Self::new(config.alert_min_severity).with_cooldown(Duration::from_secs(config.alert_cooldown_secs));
+        if let Some(url) = &config.alert_webhook_url {
+            dispatcher = dispatcher.with_sink(WebhookSink::new(url));
+        }
+        if let Some(url) = &config.alert_discord_webhook_url {
+            dispatcher = dispatcher.with_sink(DiscordSink::new(url));
+        }
+        if let (Some(token), Some(chat_id)) = (&config.alert_telegram_bot_token, &config.alert_telegram_chat_id) {
+            dispatcher = dispatcher.with_sink(TelegramSink::new(token, chat_id));
+        }
+        dispatcher
+    }
+
+    pub fn with_sink<S: AlertSink + 'static>(mut self, sink: S) -> Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// Quiet period per (chain, token) after an alert (zero = alert every verdict)
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.sinks.is_empty()
+    }
+
+    /// Alerts delivered (2xx) since startup, summed over sinks
+    pub fn total_sent(&self) -> u64 {
+        self.total_sent.load(Ordering::Relaxed)
+    }
+
+    /// Alert passes the severity threshold and there is somewhere to send it
+    pub fn should_alert(&self, severity: RiskLevel) -> bool {
+        self.is_enabled() && severity as u8 >= self.min_severity as u8
+    }
+
+    /// Deliver `alert` to every sink in the background (must run inside a tokio runtime)
+    /// Returns false when the alert was below the threshold, no sink is configured
+    /// or its token was alerted within the cooldown
+    pub fn notify(&self, alert: &Alert) -> bool {
+        if !self.should_alert(alert.severity) || self.cooling_down(alert) {
+            return false;
+        }
+        for sink in &self.sinks {
+            let request = sink.request(&self.client, alert);
+            let name = sink.name();
+            let total_sent = self.total_sent.clone();
+            tokio::spawn(async move {
+                match request.send().await {
+                    Ok(response) if response.status().is_success() => {
+                        total_sent.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Ok(response) => warn!("⚠️ Alert sink {} returned {}", name, response.status()),
+                    Err(e) => warn!("⚠️ Alert sink {} failed: {}", name, e.without_url()),
+                }
+            });
+        }
+        true
+    }
+
+    /// True when `alert`'s token was alerted on its chain less than `cooldown` ago;
+    /// otherwise starts a new cooldown. Transaction-level alerts (no token) never cool down
+    fn cooling_down(&self, alert: &Alert) -> bool {
+        let Some(token) = &alert.token else {
+            return false;
+        };
+        if self.cooldown.is_zero() {
+            return false;
+        }
+        let now = Instant::now();
+        if self.last_alerted.len() >= ALERT_COOLDOWN_SWEEP_LEN {
+            self.last_alerted.retain(|_, at| now.duration_since(*at) < self.cooldown);
+        }
+        match self.last_alerted.entry((alert.chain_id, token.to_lowercase())) {
+            Entry::Occupied(entry) if now.duration_since(*entry.get()) < self.cooldown => {
+                debug!("🔕 Alert for {} on chain {} suppressed (cooldown)", token, alert.chain_id);
+                true
+            }
+            entry => {
+                entry.insert(now);
+                false
+            }
+        }
+    }
+}
+
+impl Default for AlertDispatcher {
+    fn default() -> Self {
+        Self::new(RiskLevel::Critical)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn honeypot_alert() -> Alert {
+        Alert::new(RiskLevel::Critical, 1, "Sell reverted".to_string(), "api")
+            .for_token("0xabc")
+            .with_risk_score(100)
+            .honeypot()
+            .with_risk_factors((0..7).map(|i| format!("factor {}", i)).collect())
+    }
+
+    #[test]
+    fn test_alert_threshold() {
+        // No sinks: nothing is ever alerted
+        assert!(!AlertDispatcher::default().should_alert(RiskLevel::Critical));
+
+        let dispatcher = AlertDispatcher::new(RiskLevel::High).with_sink(WebhookSink::new("http://localhost/hook"));
+        assert!(dispatcher.should_alert(RiskLevel::Critical));
+        assert!(dispatcher.should_alert(RiskLevel::High));
+        assert!(!dispatcher.should_alert(RiskLevel::Medium));
+        assert!(!dispatcher.notify(&Alert::new(RiskLevel::Low, 1, "minor".to_string(), "api")));
+    }
+
+    #[test]
+    fn test_alert_cooldown() {
+        let dispatcher = AlertDispatcher::new(RiskLevel::High).with_sink(WebhookSink::new("http://localhost/hook"));
+        let alert = honeypot_alert();
+        assert!(!dispatcher.cooling_down(&alert));
+        // Same token again (any case) is suppressed, other chains and tokens are not
+        assert!(dispatcher.cooling_down(&alert.clone().for_token("0xABC")));
+        assert!(!dispatcher.cooling_down(&Alert { chain_id: 56, ..alert.clone() }));
+        assert!(!dispatcher.cooling_down(&alert.clone().for_token("0xdef")));
+        // Transaction-level alerts have no token to dedup on
+        let tx_alert = Alert::new(RiskLevel::Critical, 1, "sandwich".to_string(), "mempool").for_tx("0x01");
+        assert!(!dispatcher.cooling_down(&tx_alert));
+        assert!(!dispatcher.cooling_down(&tx_alert));
+
+        let no_cooldown = AlertDispatcher::new(RiskLevel::High).with_cooldown(Duration::ZERO);
+        assert!(!no_cooldown.cooling_down(&alert));
+        assert!(!no_cooldown.cooling_down(&alert));
+    }
+
+    #[test]
+    fn test_alert_message() {
+        let alert = honeypot_alert();
+        let discord = alert.message(true);
+        assert!(discord.contains("**RUSTER REVM ALERT: CRITICAL**"));
+        assert!(discord.contains("🍯 Honeypot detected on **Ethereum**"));
+        assert!(discord.contains("• Risk score: **100/100**"));
+        assert!(discord.contains("• factor 4"));
+        assert!(!discord.contains("• factor 5"));
+        assert!(discord.contains("• and 2 more"));
+
+        let plain = alert.message(false);
+        assert!(!plain.contains("**"));
+        assert!(plain.contains("• Token: 0xabc"));
+    }
+
+    #[test]
+    fn test_sink_requests() {
+        let client = reqwest::Client::new();
+        let alert = honeypot_alert();
+        let body = |request: reqwest::Request| -> serde_json::Value {
+            serde_json::from_slice(request.body().and_then(|b| b.as_bytes()).unwrap()).unwrap()
+        };
+
+        let webhook = WebhookSink::new("http://localhost/hook").request(&client, &alert).build().unwrap();
+        let payload = body(webhook);
+        assert_eq!(payload["severity"], "Critical");
+        assert_eq!(payload["token"], "0xabc");
+        assert_eq!(payload["risk_factors"].as_array().unwrap().len(), 7);
+
+        let discord = DiscordSink::new("https://discord.com/api/webhooks/1/x").request(&client, &alert).build().unwrap();
+        assert!(body(discord)["content"].as_str().unwrap().starts_with("💀 **RUSTER REVM ALERT"));
+
+        let telegram = TelegramSink::new("123:abc", "-100").request(&client, &alert).build().unwrap();
+        assert_eq!(telegram.url().path(), "/bot123:abc/sendMessage");
+        let payload = body(telegram);
+        assert_eq!(payload["chat_id"], "-100");
+        assert!(payload["text"].as_str().unwrap().contains("RUSTER REVM ALERT: CRITICAL"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Audit Log - Per-Analysis Decision Records
+//!
+//! Telemetry hanya menyimpan agregat anonim. Untuk kebutuhan compliance,
+//! mode audit (AUDIT_LOG_DIR) menulis satu file JSON per analisis: input,
+//! output tiap subsistem, dan verdict akhir, agar keputusan bisa direplay.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// One JSON file per analysis under a directory (disabled without one)
+pub struct AuditLog {
+    dir: Option<PathBuf>,
+    total_written: AtomicU64,
+}
+
+impl AuditLog {
+    /// `dir` None = disabled (nothing written)
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            total_written: AtomicU64::new(0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    /// Reports written since startup
+    pub fn total_written(&self) -> u64 {
+        self.total_written.load(Ordering::Relaxed)
+    }
+
+    /// `write` in a background task, so the handler never waits on the disk
+    pub fn record<T>(self: &Arc<Self>, chain_id: u64, token: String, report: T)
+    where
+        T: Serialize + Send + Sync + 'static,
+    {
+        if !self.is_enabled() {
+            return;
+        }
+        let log = self.clone();
+        tokio::spawn(async move {
+            log.write(chain_id, &token, &report).await;
+        });
+    }
+
+    /// Write `report` as `<dir>/<unix_ms>-<seq>-<chain_id>-<token>.json`
+    /// Returns the file path, None when disabled or the write failed
+    pub async fn write<T: Serialize>(&self, chain_id: u64, token: &str, report: &T) -> Option<PathBuf> {
+        let dir = self.dir.as_ref()?;
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        let seq = self.total_written.fetch_add(1, Ordering::Relaxed);
+        // Token comes from the request - keep the file name to safe characters
+        let token: String = token.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+        let path = dir.join(format!("{}-{}-{}-{}.json", millis, seq, chain_id, token));
+
+        match Self::write_file(dir, &path, report).await {
+            Ok(()) => Some(path),
+            Err(e) => {
+                self.total_written.fetch_sub(1, Ordering::Relaxed);
+                warn!("⚠️ Failed to write audit report {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    async fn write_file<T: Serialize>(dir: &Path, path: &Path, report: &T) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string_pretty(report)?;
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(path, json).await
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[tokio::test]
+    async fn test_audit_log_writes_one_file_per_report() {
+        let dir = std::env::temp_dir().join(format!("ruster_audit_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let log = AuditLog::new(Some(dir.clone()));
+        let first = log.write(1, "0xAbC/../x", &serde_json::json!({ "verdict": "Safe" })).await.unwrap();
+        let second = log.write(1, "0xabc", &serde_json::json!({ "verdict": "Honeypot" })).await.unwrap();
+        assert_ne!(first, second);
+        assert_eq!(log.total_written(), 2);
+        // Path characters from the token never reach the file name
+        assert_eq!(first.parent(), Some(dir.as_path()));
+        assert!(first.file_name().unwrap().to_str().unwrap().ends_with("-1-0xAbCx.json"));
+
+        let report: serde_json::Value = serde_json::from_str(&fs::read_to_string(&second).unwrap()).unwrap();
+        assert_eq!(report["verdict"], "Honeypot");
+
+        // Disabled log writes nothing
+        let disabled = AuditLog::default();
+        assert!(disabled.write(1, "0xabc", &serde_json::json!({})).await.is_none());
+        assert_eq!(disabled.total_written(), 0);
+
+        // Background writes land too
+        let log = Arc::new(log);
+        log.record(1, "0xdef".to_string(), serde_json::json!({ "verdict": "Unverified" }));
+        for _ in 0..100 {
+            if fs::read_dir(&dir).unwrap().count() == 3 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
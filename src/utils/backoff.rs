@@ -0,0 +1,138 @@
+//! Exponential Backoff Utility
+//!
+//! Satu kebijakan retry untuk semua client (RPC single call, RPC batch, WebSocket reconnect).
+//! Delay: base → 2x → 4x → ... (capped), plus random jitter ±N% untuk
+//! mencegah thundering herd.
+
+use eyre::{eyre, Result};
+use rand::Rng;
+use std::future::Future;
+use std::time::Duration;
+use tracing::debug;
+
+/// Backoff policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackoffConfig {
+    /// Delay before the first retry (milliseconds)
+    pub base_delay_ms: u64,
+    /// Cap for a single delay (milliseconds)
+    pub max_delay_ms: u64,
+    /// Total attempts including the first one
+    pub max_attempts: u32,
+    /// Random jitter applied to each delay (± percent, 0 = none)
+    pub jitter_percent: u64,
+    /// Floor for a jittered delay (milliseconds)
+    pub min_delay_ms: u64,
+}
+
+impl BackoffConfig {
+    /// Delay before retry number `retry` (1-based), without jitter
+    /// retry 1 = base, retry 2 = 2x base, ... capped at `max_delay_ms`
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        if retry == 0 {
+            return Duration::ZERO;
+        }
+        let factor = 2_u64.saturating_pow(retry - 1);
+        let delay = self.base_delay_ms.saturating_mul(factor).min(self.max_delay_ms);
+        Duration::from_millis(delay)
+    }
+
+    /// Delay before retry number `retry` (1-based), with jitter applied
+    pub fn jittered_delay_for(&self, retry: u32) -> Duration {
+        let capped = self.delay_for(retry).as_millis() as u64;
+        let jitter_range = (capped * self.jitter_percent / 100) as i64;
+        let jitter: i64 = if jitter_range > 0 {
+            rand::thread_rng().gen_range(-jitter_range..=jitter_range)
+        } else {
+            0
+        };
+        let delay = (capped as i64 + jitter).max(self.min_delay_ms as i64) as u64;
+        Duration::from_millis(delay)
+    }
+}
+
+/// Run `op` until it succeeds or `config.max_attempts` is reached
+/// `op` receives the 0-based attempt number. Returns the last error on failure.
+pub async fn retry_with_backoff<T, F, Fut>(config: &BackoffConfig, mut op: F) -> Result<T>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut last_error = None;
+
+    for attempt in 0..config.max_attempts {
+        if attempt > 0 {
+            let delay = config.jittered_delay_for(attempt);
+            debug!("⏳ Retry {}/{} after {}ms", attempt + 1, config.max_attempts, delay.as_millis());
+            tokio::time::sleep(delay).await;
+        }
+
+        match op(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| eyre!("Unknown error after {} retries", config.max_attempts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALCHEMY_LIKE: BackoffConfig = BackoffConfig {
+        base_delay_ms: 1000,
+        max_delay_ms: 64000,
+        max_attempts: 7,
+        jitter_percent: 20,
+        min_delay_ms: 100,
+    };
+
+    #[test]
+    fn test_delay_schedule() {
+        let schedule: Vec<u64> = (1..=8)
+            .map(|retry| ALCHEMY_LIKE.delay_for(retry).as_millis() as u64)
+            .collect();
+        assert_eq!(schedule, vec![1000, 2000, 4000, 8000, 16000, 32000, 64000, 64000]);
+        assert_eq!(ALCHEMY_LIKE.delay_for(0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_jitter_bounds() {
+        for retry in 1..=7 {
+            let base = ALCHEMY_LIKE.delay_for(retry).as_millis() as u64;
+            for _ in 0..100 {
+                let delay = ALCHEMY_LIKE.jittered_delay_for(retry).as_millis() as u64;
+                assert!(delay >= base * 80 / 100 && delay <= base * 120 / 100);
+            }
+        }
+
+        // No jitter = exact schedule, min floor applies
+        let exact = BackoffConfig { jitter_percent: 0, base_delay_ms: 10, min_delay_ms: 50, ..ALCHEMY_LIKE };
+        assert_eq!(exact.jittered_delay_for(1), Duration::from_millis(50));
+        assert_eq!(exact.jittered_delay_for(4), Duration::from_millis(80));
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff() {
+        let fast = BackoffConfig {
+            base_delay_ms: 1,
+            max_delay_ms: 1,
+            max_attempts: 3,
+            jitter_percent: 0,
+            min_delay_ms: 0,
+        };
+
+        // Succeeds on the third attempt
+        let result = retry_with_backoff(&fast, |attempt| async move {
+            if attempt < 2 { Err(eyre!("fail {}", attempt)) } else { Ok(attempt) }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 2);
+
+        // Gives up after max_attempts with the last error
+        let result: Result<()> =
+            retry_with_backoff(&fast, |attempt| async move { Err(eyre!("fail {}", attempt)) }).await;
+        assert_eq!(result.unwrap_err().to_string(), "fail 2");
+    }
+}
@@ -5,20 +5,43 @@
 //! 
 //! Features:
 //! - TTL-based expiration (5 menit default)
+//! - TTL berbeda untuk honeypot (lama) vs safe/high-tax (pendek)
 //! - Address normalization (lowercase)
+//! - Key per test amount (bucket), lihat `amount_bucket`
 //! - Cache HIT/MISS logging
 //! - Thread-safe dengan DashMap
 
 use dashmap::DashMap;
-use std::sync::Arc;
-use std::time::{Duration, Instant};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::{info, debug};
+use utoipa::ToSchema;
 
 use crate::core::honeypot::HoneypotResult;
 
 /// Default TTL: 5 menit (300 detik)
 const DEFAULT_TTL_SECS: u64 = 300;
 
+/// Default TTL untuk honeypot: 1 jam (3600 detik)
+/// Honeypot yang sudah terkonfirmasi hampir tidak pernah jadi aman
+pub const DEFAULT_HONEYPOT_TTL_SECS: u64 = 3600;
+
+/// Default TTL untuk hasil async job: 10 menit
+/// Cukup lama untuk client polling, tapi tidak menumpuk di memory
+pub const DEFAULT_JOB_TTL_SECS: u64 = 600;
+
+/// Jumlah sampel hit rate yang disimpan (1 sampel per menit = 1 jam terakhir)
+pub const HIT_RATE_HISTORY_LEN: usize = 60;
+
+/// Bucket test amount (native) untuk cache key: 2 angka signifikan,
+/// jadi 0.1 vs 0.10 share entry tapi 0.01 vs 1.0 tidak saling menimpa
+/// (limit & slippage bergantung pada ukuran probe)
+pub fn amount_bucket(amount: f64) -> String {
+    format!("{:.1e}", amount)
+}
+
 /// Cache entry dengan timestamp untuk TTL validation
 #[derive(Clone, Debug)]
 pub struct CacheEntry {
@@ -69,11 +92,23 @@ impl CacheEntry {
 pub struct HoneypotCache {
     /// Internal storage: lowercase address -> CacheEntry
     store: Arc<DashMap<String, CacheEntry>>,
-    /// TTL dalam detik
+    /// TTL dalam detik (safe / high-tax)
     ttl_secs: u64,
+    /// TTL dalam detik untuk honeypot / sell reverted
+    honeypot_ttl_secs: u64,
     /// Counter untuk statistik
     hits: Arc<std::sync::atomic::AtomicU64>,
     misses: Arc<std::sync::atomic::AtomicU64>,
+    /// Riwayat hit rate per interval (lihat `record_sample`)
+    history: Arc<Mutex<HitRateHistory>>,
+}
+
+/// Sampel bergulir + counter saat sampel terakhir diambil
+#[derive(Default)]
+struct HitRateHistory {
+    samples: VecDeque<HitRateSample>,
+    last_hits: u64,
+    last_misses: u64,
 }
 
 impl Default for HoneypotCache {
@@ -88,19 +123,39 @@ impl HoneypotCache {
         Self {
             store: Arc::new(DashMap::new()),
             ttl_secs: DEFAULT_TTL_SECS,
+            honeypot_ttl_secs: DEFAULT_HONEYPOT_TTL_SECS,
             hits: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             misses: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            history: Arc::new(Mutex::new(HitRateHistory::default())),
         }
     }
 
-    /// Buat cache dengan custom TTL
+    /// Buat cache dengan custom TTL (sama untuk semua hasil)
     #[allow(dead_code)]
     pub fn with_ttl(ttl_secs: u64) -> Self {
+        Self::with_ttls(ttl_secs, ttl_secs)
+    }
+
+    /// Buat cache dengan TTL terpisah: safe/high-tax vs honeypot
+    pub fn with_ttls(safe_ttl_secs: u64, honeypot_ttl_secs: u64) -> Self {
         Self {
             store: Arc::new(DashMap::new()),
-            ttl_secs,
+            ttl_secs: safe_ttl_secs,
+            honeypot_ttl_secs,
             hits: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             misses: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            history: Arc::new(Mutex::new(HitRateHistory::default())),
+        }
+    }
+
+    /// Pilih TTL berdasarkan hasil analisis
+    /// Honeypot/sell reverted = TTL lama, safe/high-tax = TTL pendek (cepat divalidasi ulang)
+    #[inline]
+    fn ttl_for(&self, result: &HoneypotResult) -> u64 {
+        if result.is_honeypot || result.sell_reverted {
+            self.honeypot_ttl_secs
+        } else {
+            self.ttl_secs
         }
     }
 
@@ -139,18 +194,19 @@ impl HoneypotCache {
         }
     }
 
-    /// Set ke cache dengan TTL default
+    /// Set ke cache dengan TTL sesuai verdict
     /// Hanya simpan hasil yang valid (bukan error)
     pub fn set(&self, address: &str, result: HoneypotResult) {
         let key = Self::normalize_address(address);
+        let ttl_secs = self.ttl_for(&result);
         let entry = CacheEntry {
             result,
             created_at: Instant::now(),
-            ttl_secs: self.ttl_secs,
+            ttl_secs,
         };
         
         self.store.insert(key.clone(), entry);
-        info!("💾 CACHE SET: {} (TTL: {}s)", key, self.ttl_secs);
+        info!("💾 CACHE SET: {} (TTL: {}s)", key, ttl_secs);
     }
 
     /// Hapus entry dari cache
@@ -190,6 +246,7 @@ impl HoneypotCache {
             misses,
             hit_rate,
             ttl_secs: self.ttl_secs,
+            honeypot_ttl_secs: self.honeypot_ttl_secs,
         }
     }
 
@@ -199,16 +256,175 @@ impl HoneypotCache {
         self.store.clear();
         info!("🗑️ CACHE CLEARED");
     }
+
+    /// Hapus semua entry, return jumlah entry yang dihapus
+    /// Counter hit/miss dan riwayat tetap (penurunan hit rate setelah purge terlihat di riwayat)
+    pub fn purge(&self) -> usize {
+        let removed = self.store.len();
+        self.store.clear();
+        info!("🗑️ CACHE PURGED: {} entries removed", removed);
+        removed
+    }
+
+    /// Ambil sampel hit rate sejak sampel sebelumnya (dipanggil periodik, mis. tiap 60 detik)
+    /// Hanya `HIT_RATE_HISTORY_LEN` sampel terakhir yang disimpan
+    pub fn record_sample(&self) -> HitRateSample {
+        let hits = self.hits.load(std::sync::atomic::Ordering::Relaxed);
+        let misses = self.misses.load(std::sync::atomic::Ordering::Relaxed);
+        let mut history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+
+        let sample = HitRateSample::new(
+            hits.saturating_sub(history.last_hits),
+            misses.saturating_sub(history.last_misses),
+            self.store.len(),
+        );
+        history.last_hits = hits;
+        history.last_misses = misses;
+        if history.samples.len() == HIT_RATE_HISTORY_LEN {
+            history.samples.pop_front();
+        }
+        history.samples.push_back(sample.clone());
+        sample
+    }
+
+    /// Riwayat hit rate, sampel terlama dulu
+    pub fn history(&self) -> Vec<HitRateSample> {
+        let history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        history.samples.iter().cloned().collect()
+    }
+}
+
+/// Hit rate cache dalam satu interval sampling
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct HitRateSample {
+    /// Unix timestamp (detik) saat sampel diambil
+    pub timestamp: u64,
+    /// Hit / miss selama interval
+    pub hits: u64,
+    pub misses: u64,
+    /// Hit rate interval dalam persen (0 jika tidak ada lookup)
+    pub hit_rate: f64,
+    /// Jumlah entry saat sampel diambil
+    pub entries: usize,
+}
+
+impl HitRateSample {
+    fn new(hits: u64, misses: u64, entries: usize) -> Self {
+        let total = hits + misses;
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            hits,
+            misses,
+            hit_rate: if total > 0 { (hits as f64 / total as f64) * 100.0 } else { 0.0 },
+            entries,
+        }
+    }
 }
 
 /// Statistik cache untuk monitoring
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct CacheStats {
     pub entries: usize,
     pub hits: u64,
     pub misses: u64,
     pub hit_rate: f64,
     pub ttl_secs: u64,
+    pub honeypot_ttl_secs: u64,
+}
+
+// ============================================
+// ASYNC JOB RESULTS
+// ============================================
+
+/// Status async job (analisis panjang yang di-poll oleh client)
+#[derive(Clone, Debug)]
+pub enum JobState<T> {
+    /// Masih antri / sedang berjalan
+    Pending,
+    /// Selesai dengan hasil
+    Done(T),
+    /// Gagal dengan pesan error
+    Failed(String),
+}
+
+/// Cache hasil async job, key = job id
+/// TTL dihitung sejak job dibuat, jadi job yang tidak pernah di-poll ikut dibersihkan
+#[derive(Clone)]
+pub struct JobCache<T: Clone> {
+    store: Arc<DashMap<String, (JobState<T>, Instant)>>,
+    ttl_secs: u64,
+}
+
+impl<T: Clone> Default for JobCache<T> {
+    fn default() -> Self {
+        Self::with_ttl(DEFAULT_JOB_TTL_SECS)
+    }
+}
+
+impl<T: Clone> JobCache<T> {
+    /// Buat job cache dengan custom TTL
+    pub fn with_ttl(ttl_secs: u64) -> Self {
+        Self {
+            store: Arc::new(DashMap::new()),
+            ttl_secs,
+        }
+    }
+
+    /// Daftarkan job baru (status Pending), return job id
+    pub fn create(&self) -> String {
+        let job_id = hex::encode(rand::random::<[u8; 16]>());
+        self.store.insert(job_id.clone(), (JobState::Pending, Instant::now()));
+        debug!("📥 JOB CREATED: {}", job_id);
+        job_id
+    }
+
+    /// Simpan hasil job yang selesai
+    pub fn complete(&self, job_id: &str, result: T) {
+        self.update(job_id, JobState::Done(result));
+    }
+
+    /// Tandai job gagal
+    pub fn fail(&self, job_id: &str, error: String) {
+        self.update(job_id, JobState::Failed(error));
+    }
+
+    fn update(&self, job_id: &str, state: JobState<T>) {
+        if let Some(mut entry) = self.store.get_mut(job_id) {
+            entry.0 = state;
+        }
+    }
+
+    /// Ambil status job (None jika tidak ada atau expired)
+    pub fn get(&self, job_id: &str) -> Option<JobState<T>> {
+        let entry = self.store.get(job_id)?;
+        if entry.1.elapsed() > Duration::from_secs(self.ttl_secs) {
+            drop(entry);
+            self.store.remove(job_id);
+            return None;
+        }
+        Some(entry.0.clone())
+    }
+
+    /// Hapus semua job yang sudah expired
+    pub fn cleanup_expired(&self) -> usize {
+        let ttl = Duration::from_secs(self.ttl_secs);
+        let before = self.store.len();
+        self.store.retain(|_, (_, created_at)| created_at.elapsed() <= ttl);
+        before - self.store.len()
+    }
+
+    /// Jumlah job yang tersimpan
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    /// Cek apakah job cache kosong
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -251,6 +467,20 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_ttl_by_verdict() {
+        let cache = HoneypotCache::with_ttls(60, 7200);
+
+        cache.set("0xsafe", mock_result());
+        let honeypot = HoneypotResult::honeypot(
+            "Sell reverted".to_string(), true, false, true, 0, vec![], 10,
+        );
+        cache.set("0xhoneypot", honeypot);
+
+        assert_eq!(cache.store.get("0xsafe").unwrap().ttl_secs, 60);
+        assert_eq!(cache.store.get("0xhoneypot").unwrap().ttl_secs, 7200);
+    }
+
     #[test]
     fn test_cache_stats() {
         let cache = HoneypotCache::new();
@@ -265,4 +495,66 @@ mod tests {
         assert_eq!(stats.hits, 1);
         assert_eq!(stats.misses, 1);
     }
+
+    #[test]
+    fn test_hit_rate_history() {
+        let cache = HoneypotCache::new();
+        cache.set("0xtest", mock_result());
+        cache.get("0xtest"); // HIT
+        cache.get("0xtest"); // HIT
+        cache.get("0xnonexistent"); // MISS
+
+        let first = cache.record_sample();
+        assert_eq!((first.hits, first.misses, first.entries), (2, 1, 1));
+        assert!((first.hit_rate - 200.0 / 3.0).abs() < 1e-9);
+
+        // Interval berikutnya hanya menghitung lookup baru
+        cache.get("0xnonexistent"); // MISS
+        assert_eq!(cache.record_sample().hit_rate, 0.0);
+
+        for _ in 0..HIT_RATE_HISTORY_LEN {
+            cache.record_sample();
+        }
+        let history = cache.history();
+        assert_eq!(history.len(), HIT_RATE_HISTORY_LEN);
+        assert_eq!(history.last().unwrap().hits, 0);
+
+        // Purge hapus entry, counter tetap
+        assert_eq!(cache.purge(), 1);
+        assert_eq!(cache.stats().entries, 0);
+        assert_eq!(cache.stats().hits, 2);
+    }
+
+    #[test]
+    fn test_amount_bucket() {
+        assert_eq!(amount_bucket(0.1), amount_bucket(0.1000001));
+        assert_ne!(amount_bucket(0.01), amount_bucket(1.0));
+        assert_ne!(amount_bucket(0.1), amount_bucket(0.5));
+        assert_eq!(amount_bucket(500.0), "5.0e2");
+    }
+
+    #[test]
+    fn test_job_cache_lifecycle() {
+        let jobs: JobCache<u32> = JobCache::default();
+        let ok = jobs.create();
+        let failed = jobs.create();
+        assert_ne!(ok, failed);
+        assert!(matches!(jobs.get(&ok), Some(JobState::Pending)));
+
+        jobs.complete(&ok, 42);
+        jobs.fail(&failed, "boom".to_string());
+        assert!(matches!(jobs.get(&ok), Some(JobState::Done(42))));
+        assert!(matches!(jobs.get(&failed), Some(JobState::Failed(e)) if e == "boom"));
+        assert!(jobs.get("unknown").is_none());
+
+        // TTL 0 = semua job langsung expired
+        let expired: JobCache<u32> = JobCache::with_ttl(0);
+        let id = expired.create();
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(expired.get(&id).is_none());
+        expired.create();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(expired.cleanup_expired(), 1);
+        assert!(expired.is_empty());
+    }
 }
@@ -0,0 +1,74 @@
+//! Chain Names - Name ↔ Chain ID Mapping
+//!
+//! User input sering berupa nama chain ("eth", "mainnet", "bsc", "binance"),
+//! bukan chain ID numerik. Modul ini menerjemahkan alias umum ke chain ID
+//! dan sebaliknya, agar API tidak parsing nama chain secara ad hoc.
+
+use super::constants::{
+    get_chain_name, CHAIN_ID_ARBITRUM, CHAIN_ID_AVALANCHE, CHAIN_ID_BASE, CHAIN_ID_BSC,
+    CHAIN_ID_ETHEREUM, CHAIN_ID_OPTIMISM, CHAIN_ID_POLYGON, CHAIN_ID_SOLANA,
+};
+
+/// Accepted spellings per chain (lowercase, spaces / underscores normalized to '-')
+const CHAIN_ALIASES: [(u64, &[&str]); 8] = [
+    (CHAIN_ID_ETHEREUM, &["ethereum", "eth", "mainnet", "ethereum-mainnet", "homestead"]),
+    (CHAIN_ID_BSC, &["bsc", "binance", "bnb", "bnb-smart-chain", "bnb-chain", "bnbchain", "bsc-mainnet"]),
+    (CHAIN_ID_POLYGON, &["polygon", "matic", "pol", "polygon-pos"]),
+    (CHAIN_ID_ARBITRUM, &["arbitrum", "arb", "arbitrum-one"]),
+    (CHAIN_ID_OPTIMISM, &["optimism", "op", "op-mainnet"]),
+    (CHAIN_ID_AVALANCHE, &["avalanche", "avax", "avalanche-c-chain", "c-chain"]),
+    (CHAIN_ID_BASE, &["base", "base-mainnet"]),
+    (CHAIN_ID_SOLANA, &["solana", "sol"]),
+];
+
+/// Parse a chain name, alias or decimal chain ID ("base", "BSC", "mainnet", "56")
+/// None for unknown chains
+pub fn parse_chain(input: &str) -> Option<u64> {
+    let name = input.trim().to_lowercase().replace([' ', '_'], "-");
+    if let Ok(chain_id) = name.parse::<u64>() {
+        return chain_name(chain_id).map(|_| chain_id);
+    }
+    CHAIN_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&name.as_str()))
+        .map(|(chain_id, _)| *chain_id)
+}
+
+/// Human-readable name of a known chain ("Ethereum", "BNB Smart Chain", ...)
+pub fn chain_name(chain_id: u64) -> Option<&'static str> {
+    CHAIN_ALIASES
+        .iter()
+        .any(|(id, _)| *id == chain_id)
+        .then(|| get_chain_name(chain_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chain_aliases() {
+        assert_eq!(parse_chain("eth"), Some(CHAIN_ID_ETHEREUM));
+        assert_eq!(parse_chain("Mainnet"), Some(CHAIN_ID_ETHEREUM));
+        assert_eq!(parse_chain(" binance "), Some(CHAIN_ID_BSC));
+        assert_eq!(parse_chain("BNB Smart Chain"), Some(CHAIN_ID_BSC));
+        assert_eq!(parse_chain("arbitrum_one"), Some(CHAIN_ID_ARBITRUM));
+        assert_eq!(parse_chain("base"), Some(CHAIN_ID_BASE));
+        assert_eq!(parse_chain("8453"), Some(CHAIN_ID_BASE));
+        assert_eq!(parse_chain("fantom"), None);
+        assert_eq!(parse_chain("250"), None);
+        assert_eq!(parse_chain(""), None);
+    }
+
+    #[test]
+    fn test_chain_name_round_trip() {
+        for (chain_id, aliases) in CHAIN_ALIASES {
+            let name = chain_name(chain_id).expect("known chain");
+            assert_eq!(parse_chain(name), Some(chain_id), "{} does not parse back", name);
+            for alias in aliases {
+                assert_eq!(parse_chain(alias), Some(chain_id));
+            }
+        }
+        assert_eq!(chain_name(250), None);
+    }
+}
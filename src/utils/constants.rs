@@ -98,6 +98,49 @@ pub fn get_weth_address(chain_id: u64) -> Option<Address> {
     Address::from_str(addr_str).ok()
 }
 
+/// Canonical stablecoins per chain (never honeypots - skip the simulation pipeline)
+/// The wrapped native token (see `get_weth_address`) is canonical too
+pub fn get_canonical_stablecoins(chain_id: u64) -> &'static [&'static str] {
+    match chain_id {
+        CHAIN_ID_ETHEREUM => &[
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", // USDC
+            "0xdAC17F958D2ee523a2206206994597C13D831ec7", // USDT
+            "0x6B175474E89094C44Da98b954EedeAC495271d0F", // DAI
+        ],
+        CHAIN_ID_BSC => &[
+            "0x8AC76a51cc950d9822D68b83fE1Ad97B32Cd580d", // USDC
+            "0x55d398326f99059fF775485246999027B3197955", // USDT
+            "0xe9e7CEA3DedcA5984780Bafc599bD69ADd087D56", // BUSD
+        ],
+        CHAIN_ID_POLYGON => &[
+            "0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359", // USDC
+            "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174", // USDC.e
+            "0xc2132D05D31c914a87C6611C10748AEb04B58e8F", // USDT
+            "0x8f3Cf7ad23Cd3CaDbD9735AFf958023239c6A063", // DAI
+        ],
+        CHAIN_ID_ARBITRUM => &[
+            "0xaf88d065e77c8cC2239327C5EDb3A432268e5831", // USDC
+            "0xFF970A61A04b1cA14834A43f5dE4533eBDDB5CC8", // USDC.e
+            "0xFd086bC7CD5C481DCC9C85ebE478A1C0b69FCbb9", // USDT
+            "0xDA10009cBd5D07dd0CeCc66161FC93D7c9000da1", // DAI
+        ],
+        CHAIN_ID_OPTIMISM => &[
+            "0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85", // USDC
+            "0x94b008aA00579c1307B0EF2c499aD98a8ce58e58", // USDT
+            "0xDA10009cBd5D07dd0CeCc66161FC93D7c9000da1", // DAI
+        ],
+        CHAIN_ID_AVALANCHE => &[
+            "0xB97EF9Ef8734C71904D8002F8b6Bc66Dd9c48a6E", // USDC
+            "0x9702230A8Ea53601f5cD2dc00fDBc13d4dF4A8c7", // USDT
+        ],
+        CHAIN_ID_BASE => &[
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", // USDC
+            "0x50c5725949A6F0c72E6C4a641F24049A917DB0Cb", // DAI
+        ],
+        _ => &[],
+    }
+}
+
 // ============================================
 // DEX ROUTER ADDRESSES - Single Source of Truth
 // ============================================
@@ -115,6 +158,7 @@ pub fn get_dex_routers(chain_id: u64) -> Vec<RouterInfo> {
         CHAIN_ID_ETHEREUM => vec![
             RouterInfo { name: "Uniswap V2", address: "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D" },
             RouterInfo { name: "SushiSwap", address: "0xd9e1cE17f2641f24aE83637ab66a2cca9C378B9F" },
+            RouterInfo { name: "PancakeSwap V2", address: "0xEfF92A263d31888d860bD50809A8D171709b7b1c" },
         ],
         CHAIN_ID_BSC => vec![
             RouterInfo { name: "PancakeSwap V2", address: "0x10ED43C718714eb63d5aA57B78B54704E256024E" },
@@ -144,21 +188,86 @@ pub fn get_dex_routers(chain_id: u64) -> Vec<RouterInfo> {
     }
 }
 
+/// Get DEX pair factories for a chain - infrastructure, never a token
+pub fn get_dex_factories(chain_id: u64) -> Vec<RouterInfo> {
+    match chain_id {
+        CHAIN_ID_ETHEREUM => vec![
+            RouterInfo { name: "Uniswap V2", address: "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f" },
+            RouterInfo { name: "SushiSwap", address: "0xC0AEe478e3658e2610c5F7A4A2E1777cE9e4f2Ac" },
+            RouterInfo { name: "Uniswap V3", address: "0x1F98431c8aD98523631AE4a59f267346ea31F984" },
+        ],
+        CHAIN_ID_BSC => vec![
+            RouterInfo { name: "PancakeSwap V2", address: "0xcA143Ce32Fe78f1f7019d7d551a6402fC5350c73" },
+            RouterInfo { name: "BiSwap", address: "0x858E3312ed3A876947EA49d572A7C42DE08af7EE" },
+        ],
+        CHAIN_ID_POLYGON => vec![
+            RouterInfo { name: "QuickSwap", address: "0x5757371414417b8C6CAad45bAeF941aBc7d3Ab32" },
+            RouterInfo { name: "SushiSwap", address: "0xc35DADB65012eC5796536bD9864eD8773aBc74C4" },
+            RouterInfo { name: "Uniswap V3", address: "0x1F98431c8aD98523631AE4a59f267346ea31F984" },
+        ],
+        CHAIN_ID_ARBITRUM => vec![
+            RouterInfo { name: "Camelot", address: "0x6EcCab422D763aC031210895C81787E87B43A652" },
+            RouterInfo { name: "SushiSwap", address: "0xc35DADB65012eC5796536bD9864eD8773aBc74C4" },
+            RouterInfo { name: "Uniswap V3", address: "0x1F98431c8aD98523631AE4a59f267346ea31F984" },
+        ],
+        CHAIN_ID_OPTIMISM => vec![
+            RouterInfo { name: "Uniswap V3", address: "0x1F98431c8aD98523631AE4a59f267346ea31F984" },
+        ],
+        CHAIN_ID_AVALANCHE => vec![
+            RouterInfo { name: "TraderJoe", address: "0x9Ad6C38BE94206cA50bb0d90783181662f0Cfa10" },
+            RouterInfo { name: "Pangolin", address: "0xefa94DE7a4656D787667C749f7E1223D71E9FD88" },
+        ],
+        CHAIN_ID_BASE => vec![
+            RouterInfo { name: "Uniswap V2", address: "0x8909Dc15e40173Ff4699343b6eB8132c65e18eC6" },
+            RouterInfo { name: "Uniswap V3", address: "0x33128a8fC17869897dcE68Ed026d694621f6FDfD" },
+        ],
+        _ => vec![],
+    }
+}
+
 // ============================================
 // PUBLIC RPC FALLBACKS - Single Source of Truth
 // ============================================
 
-/// Get public RPC fallback URL for a chain
+/// Get public RPC fallback URL for a chain (first of `get_public_rpc_fallbacks`)
 pub fn get_public_rpc_fallback(chain_id: u64) -> Option<&'static str> {
+    get_public_rpc_fallbacks(chain_id).first().copied()
+}
+
+/// Built-in public RPC pool for a chain, in preference order
+pub fn get_public_rpc_fallbacks(chain_id: u64) -> &'static [&'static str] {
     match chain_id {
-        CHAIN_ID_ETHEREUM => Some("https://eth.llamarpc.com"),
-        CHAIN_ID_BSC => Some("https://bsc-dataseed.binance.org"),
-        CHAIN_ID_POLYGON => Some("https://polygon-rpc.com"),
-        CHAIN_ID_ARBITRUM => Some("https://arb1.arbitrum.io/rpc"),
-        CHAIN_ID_OPTIMISM => Some("https://mainnet.optimism.io"),
-        CHAIN_ID_AVALANCHE => Some("https://api.avax.network/ext/bc/C/rpc"),
-        CHAIN_ID_BASE => Some("https://mainnet.base.org"),
-        _ => None,
+        CHAIN_ID_ETHEREUM => &[
+            "https://eth.llamarpc.com",
+            "https://ethereum-rpc.publicnode.com",
+            "https://rpc.ankr.com/eth",
+        ],
+        CHAIN_ID_BSC => &[
+            "https://bsc-dataseed.binance.org",
+            "https://bsc-dataseed1.defibit.io",
+            "https://bsc-rpc.publicnode.com",
+        ],
+        CHAIN_ID_POLYGON => &[
+            "https://polygon-rpc.com",
+            "https://polygon-bor-rpc.publicnode.com",
+        ],
+        CHAIN_ID_ARBITRUM => &[
+            "https://arb1.arbitrum.io/rpc",
+            "https://arbitrum-one-rpc.publicnode.com",
+        ],
+        CHAIN_ID_OPTIMISM => &[
+            "https://mainnet.optimism.io",
+            "https://optimism-rpc.publicnode.com",
+        ],
+        CHAIN_ID_AVALANCHE => &[
+            "https://api.avax.network/ext/bc/C/rpc",
+            "https://avalanche-c-chain-rpc.publicnode.com",
+        ],
+        CHAIN_ID_BASE => &[
+            "https://mainnet.base.org",
+            "https://base-rpc.publicnode.com",
+        ],
+        _ => &[],
     }
 }
 
@@ -205,6 +314,17 @@ pub fn get_chain_name(chain_id: u64) -> &'static str {
     }
 }
 
+/// Approximate block time in milliseconds, for turning a time window into a block count
+pub fn approx_block_time_ms(chain_id: u64) -> u64 {
+    match chain_id {
+        CHAIN_ID_ETHEREUM => 12_000,
+        CHAIN_ID_BSC => 750,
+        CHAIN_ID_ARBITRUM => 250,
+        CHAIN_ID_POLYGON | CHAIN_ID_OPTIMISM | CHAIN_ID_AVALANCHE | CHAIN_ID_BASE => 2_000,
+        _ => 12_000,
+    }
+}
+
 /// Get native token symbol
 pub fn get_native_symbol(chain_id: u64) -> &'static str {
     match chain_id {
@@ -242,8 +362,91 @@ pub fn get_explorer_url(chain_id: u64) -> &'static str {
 /// CEO Directive: This is THE ONLY place this function should exist!
 #[inline]
 pub fn wei_to_eth(wei: U256) -> f64 {
-    let wei_u128: u128 = wei.try_into().unwrap_or(u128::MAX);
-    wei_u128 as f64 / 1e18
+    to_decimal_units(wei, 18)
+}
+
+/// Convert a full-width U256 to f64 without truncating to u128.
+/// Precision is lost beyond 53 bits, but magnitude is always preserved.
+#[inline]
+pub fn u256_to_f64(value: U256) -> f64 {
+    value
+        .as_limbs()
+        .iter()
+        .rev()
+        .fold(0.0, |acc, limb| acc * 18_446_744_073_709_551_616.0 + *limb as f64)
+}
+
+/// Convert a raw token amount to human units using the token's decimals.
+/// Integer and fractional parts are split in U256 before the f64 conversion
+/// so high-supply tokens don't collapse to zero or lose their scale.
+pub fn to_decimal_units(amount: U256, decimals: u8) -> f64 {
+    // 10^77 is the largest power of ten that fits in U256
+    let decimals = decimals.min(77);
+    let scale = U256::from(10u64).pow(U256::from(decimals));
+    let whole = amount / scale;
+    let frac = amount % scale;
+    u256_to_f64(whole) + u256_to_f64(frac) / 10f64.powi(decimals as i32)
+}
+
+/// Parse a human token amount ("1234.5") into base units using the token's decimals.
+/// Exact - no f64 round trip. None for malformed input, more fractional digits
+/// than `decimals`, or an amount that overflows U256
+pub fn from_decimal_units(amount: &str, decimals: u8) -> Option<U256> {
+    let amount = amount.trim();
+    let (whole, frac) = amount.split_once('.').unwrap_or((amount, ""));
+    let digits_only = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && frac.is_empty()) || !digits_only(whole) || !digits_only(frac) {
+        return None;
+    }
+    let padding = (decimals as usize).checked_sub(frac.len())?;
+    U256::from_str_radix(&format!("{}{}{}", whole, frac, "0".repeat(padding)), 10).ok()
+}
+
+/// Parse a native amount from the API into wei: decimal units ("0.1", "2") or raw wei
+/// with a `wei` suffix ("100000000000000000wei"). Exact - no f64 round trip.
+/// Err(reason) for malformed, zero, over-precise or overflowing input
+pub fn parse_native_amount(amount: &str) -> Result<U256, String> {
+    let amount = amount.trim();
+    let wei = match amount.strip_suffix("wei") {
+        Some(raw) => {
+            let raw = raw.trim_end();
+            if raw.is_empty() || !raw.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("'{}' is not a whole number of wei", amount));
+            }
+            U256::from_str_radix(raw, 10).map_err(|_| format!("'{}' overflows 256 bits", amount))?
+        }
+        None => from_decimal_units(amount, 18).ok_or_else(|| {
+            format!("'{}' is not a decimal amount with at most 18 fractional digits", amount)
+        })?,
+    };
+    if wei.is_zero() {
+        return Err("amount must be greater than zero".to_string());
+    }
+    Ok(wei)
+}
+
+/// Percentage lost between `amount_in` and `amount_out` (same unit, same decimals).
+/// Computed in U256 with 1e6 fixed-point precision; returns 0.0 on gain or zero input.
+pub fn loss_percent(amount_in: U256, amount_out: U256) -> f64 {
+    if amount_in.is_zero() || amount_out >= amount_in {
+        return 0.0;
+    }
+    let diff = amount_in - amount_out;
+    let precision = U256::from(100_000_000u64); // 100% * 1e6
+    let scaled = match diff.checked_mul(precision) {
+        Some(v) => v / amount_in,
+        // Only reachable above ~2^229 wei - scale the divisor down instead
+        None => diff / (amount_in / precision).max(U256::from(1u64)),
+    };
+    u256_to_f64(scaled) / 1_000_000.0
+}
+
+/// Current unix time in seconds
+pub fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 /// Convert ETH to wei
@@ -252,6 +455,12 @@ pub fn eth_to_wei(eth: f64) -> U256 {
     U256::from((eth * 1e18) as u128)
 }
 
+/// Convert a whole number of ETH (or native token) to wei - exact, no f64 rounding or saturation
+#[inline]
+pub fn whole_eth_to_wei(eth: u64) -> U256 {
+    U256::from(eth) * U256::from(10u64).pow(U256::from(18u64))
+}
+
 /// Check if chain ID is supported
 #[inline]
 pub fn is_chain_supported(chain_id: u64) -> bool {
@@ -297,13 +506,62 @@ pub fn is_solana(chain_id: u64) -> bool {
     chain_id == CHAIN_ID_SOLANA
 }
 
-/// Check if address looks like Solana (base58, 32-44 chars, no 0x prefix)
+/// Base58 alphabet (Bitcoin/Solana) - no 0, O, I, l
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Decode a base58 string (None on invalid characters)
+fn decode_base58(input: &str) -> Option<Vec<u8>> {
+    // Big-endian base-256 accumulator
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+    for c in input.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, (carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' encodes a leading zero byte
+    let leading_zeros = input.bytes().take_while(|&c| c == b'1').count();
+    let mut decoded = vec![0u8; leading_zeros];
+    decoded.extend(bytes);
+    Some(decoded)
+}
+
+/// Check if address is a Solana pubkey (base58, 32-44 chars, decodes to exactly 32 bytes)
 pub fn is_solana_address(address: &str) -> bool {
-    // Solana addresses are base58 encoded, 32-44 characters, no 0x prefix
-    !address.starts_with("0x") 
-        && address.len() >= 32 
-        && address.len() <= 44
-        && address.chars().all(|c| c.is_alphanumeric())
+    (32..=44).contains(&address.len())
+        && decode_base58(address).is_some_and(|bytes| bytes.len() == 32)
+}
+
+/// Check if address is an EVM address (0x + 40 hex chars)
+pub fn is_evm_address(address: &str) -> bool {
+    address.len() == 42
+        && (address.starts_with("0x") || address.starts_with("0X"))
+        && address[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Address family, inferred from the address format alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainKind {
+    Evm,
+    Solana,
+    Unknown,
+}
+
+/// Infer which analyzer an address belongs to
+pub fn infer_chain_kind(address: &str) -> ChainKind {
+    if is_evm_address(address) {
+        ChainKind::Evm
+    } else if is_solana_address(address) {
+        ChainKind::Solana
+    } else {
+        ChainKind::Unknown
+    }
 }
 
 #[cfg(test)]
@@ -322,6 +580,78 @@ mod tests {
         assert_eq!(wei, U256::from(1_500_000_000_000_000_000u128));
     }
 
+    #[test]
+    fn test_whole_eth_to_wei_is_exact() {
+        assert_eq!(whole_eth_to_wei(1), U256::from(1_000_000_000_000_000_000u128));
+        // Beyond f64's 2^53 mantissa the float path would round
+        let big = 9_007_199_254_740_993u64;
+        let one_eth = U256::from(10u64).pow(U256::from(18u64));
+        assert_eq!(whole_eth_to_wei(big) / one_eth, U256::from(big));
+    }
+
+    #[test]
+    fn test_decimals_aware_conversion() {
+        // 1.5 tokens with 6 decimals (USDC-style)
+        assert!((to_decimal_units(U256::from(1_500_000u64), 6) - 1.5).abs() < 1e-9);
+        // Amounts above u128::MAX must not collapse to zero
+        let huge = U256::from(u128::MAX) * U256::from(1000u64);
+        assert!(u256_to_f64(huge) > u128::MAX as f64);
+        assert!(to_decimal_units(huge, 18) > 0.0);
+    }
+
+    #[test]
+    fn test_from_decimal_units() {
+        assert_eq!(from_decimal_units("1.5", 6), Some(U256::from(1_500_000u64)));
+        assert_eq!(from_decimal_units(" 1000 ", 0), Some(U256::from(1000u64)));
+        assert_eq!(from_decimal_units(".25", 2), Some(U256::from(25u64)));
+        // 1 billion 18-decimal tokens, exactly
+        assert_eq!(
+            from_decimal_units("1000000000", 18),
+            Some(U256::from(1_000_000_000u64) * U256::from(10u64).pow(U256::from(18u64)))
+        );
+        assert_eq!(from_decimal_units("1.234", 2), None);
+        assert_eq!(from_decimal_units("-1", 18), None);
+        assert_eq!(from_decimal_units("1e18", 18), None);
+        assert_eq!(from_decimal_units(".", 18), None);
+        assert_eq!(from_decimal_units("", 18), None);
+        assert_eq!(from_decimal_units(&"9".repeat(80), 0), None);
+    }
+
+    #[test]
+    fn test_parse_native_amount() {
+        assert_eq!(parse_native_amount("0.1"), Ok(U256::from(100_000_000_000_000_000u64)));
+        assert_eq!(parse_native_amount(" 2 "), Ok(U256::from(2_000_000_000_000_000_000u64)));
+        // Precision f64 would lose
+        assert_eq!(
+            parse_native_amount("0.123456789012345678"),
+            Ok(U256::from(123_456_789_012_345_678u64))
+        );
+        assert_eq!(parse_native_amount("12345wei"), Ok(U256::from(12345u64)));
+        assert_eq!(parse_native_amount("12345 wei"), Ok(U256::from(12345u64)));
+        assert!(parse_native_amount("abc").is_err());
+        assert!(parse_native_amount("1e18").is_err());
+        assert!(parse_native_amount("-0.1").is_err());
+        assert!(parse_native_amount("0").is_err());
+        assert!(parse_native_amount("0.0000000000000000001").is_err());
+        assert!(parse_native_amount("1.5wei").is_err());
+        assert!(parse_native_amount("wei").is_err());
+        assert!(parse_native_amount("").is_err());
+    }
+
+    #[test]
+    fn test_loss_percent() {
+        let one_eth = U256::from(1_000_000_000_000_000_000u128);
+        let back = U256::from(900_000_000_000_000_000u128);
+        assert!((loss_percent(one_eth, back) - 10.0).abs() < 1e-6);
+        assert_eq!(loss_percent(one_eth, one_eth), 0.0);
+        assert_eq!(loss_percent(U256::ZERO, back), 0.0);
+
+        // Large values beyond u128 still yield a sane percentage
+        let big_in = U256::from(u128::MAX) * U256::from(10u64);
+        let big_out = big_in / U256::from(2u64);
+        assert!((loss_percent(big_in, big_out) - 50.0).abs() < 1e-3);
+    }
+
     #[test]
     fn test_chain_support() {
         assert!(is_chain_supported(1));
@@ -334,4 +664,31 @@ mod tests {
         assert!(get_weth_address(1).is_some());
         assert!(get_weth_address(999).is_none());
     }
+
+    #[test]
+    fn test_infer_chain_kind() {
+        // Wrapped SOL mint / USDC mint
+        assert_eq!(infer_chain_kind("So11111111111111111111111111111111111111112"), ChainKind::Solana);
+        assert_eq!(infer_chain_kind("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"), ChainKind::Solana);
+        assert_eq!(infer_chain_kind("0xdAC17F958D2ee523a2206206994597C13D831ec7"), ChainKind::Evm);
+
+        // Base58-looking but invalid charset ('0', 'O', 'I', 'l') or wrong decoded length
+        assert_eq!(infer_chain_kind("0OIl111111111111111111111111111111111111112"), ChainKind::Unknown);
+        assert_eq!(infer_chain_kind("11111111111111111111111111111111111"), ChainKind::Unknown);
+        assert_eq!(infer_chain_kind("0xdAC17F958D2ee523a2206206994597C13D831ecZ"), ChainKind::Unknown);
+        assert_eq!(infer_chain_kind(""), ChainKind::Unknown);
+    }
+
+    #[test]
+    fn test_canonical_stablecoins_parse() {
+        for chain_id in SUPPORTED_CHAIN_IDS {
+            let stables = get_canonical_stablecoins(chain_id);
+            assert!(!stables.is_empty(), "chain {} has no canonical stablecoins", chain_id);
+            for addr in stables {
+                let parsed = Address::from_str(addr).expect("invalid canonical address");
+                assert_ne!(Some(parsed), get_weth_address(chain_id));
+            }
+        }
+        assert!(get_canonical_stablecoins(999).is_empty());
+    }
 }
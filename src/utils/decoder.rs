@@ -2,8 +2,8 @@
 //! Parses DEX swap calldata to extract swap parameters
 
 use crate::models::types::SwapParams;
-use alloy_primitives::{Bytes, U256};
-use alloy_sol_types::{sol, SolCall};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::{sol, SolCall, SolType};
 
 // Uniswap V2 Router function signatures
 sol! {
@@ -61,6 +61,42 @@ sol! {
     ) external;
 }
 
+// Uniswap Universal Router (command-encoded)
+sol! {
+    function execute(bytes commands, bytes[] inputs, uint256 deadline) external payable;
+    function execute(bytes commands, bytes[] inputs) external payable;
+
+    /// V2_SWAP_EXACT_IN input
+    struct V2SwapExactIn {
+        address recipient;
+        uint256 amountIn;
+        uint256 amountOutMin;
+        address[] path;
+        bool payerIsUser;
+    }
+
+    /// V3_SWAP_EXACT_IN input (path = token | fee(3) | token | ...)
+    struct V3SwapExactIn {
+        address recipient;
+        uint256 amountIn;
+        uint256 amountOutMin;
+        bytes path;
+        bool payerIsUser;
+    }
+}
+
+/// Universal Router command types (low 6 bits of each command byte)
+pub mod ur_commands {
+    pub const V3_SWAP_EXACT_IN: u8 = 0x00;
+    pub const V2_SWAP_EXACT_IN: u8 = 0x08;
+    pub const WRAP_ETH: u8 = 0x0b;
+    /// Mask for the command type (top bits are flags, e.g. allow-revert)
+    pub const COMMAND_TYPE_MASK: u8 = 0x3f;
+}
+
+/// Universal Router sentinel: "use the router's whole balance" (1 << 255)
+const UR_CONTRACT_BALANCE: U256 = U256::from_limbs([0, 0, 0, 1 << 63]);
+
 /// Decoder for DEX swap transactions
 pub struct SwapDecoder;
 
@@ -83,6 +119,70 @@ impl SwapDecoder {
             .or_else(|| Self::try_decode_fee_on_transfer_eth_for_tokens(data, value))
             .or_else(|| Self::try_decode_fee_on_transfer_tokens_for_eth(data))
             .or_else(|| Self::try_decode_fee_on_transfer_tokens_for_tokens(data))
+            .or_else(|| Self::decode_universal_router(data, value))
+    }
+
+    /// Decode Universal Router `execute(bytes,bytes[](,uint256))`
+    /// Returns the first V2/V3 exact-in swap. WRAP_ETH before the swap means the
+    /// swap spends msg.value (amountIn is usually the CONTRACT_BALANCE sentinel).
+    pub fn decode_universal_router(data: &[u8], value: U256) -> Option<SwapParams> {
+        let (commands, inputs, deadline) = if let Ok(call) = execute_0Call::abi_decode(data, false) {
+            (call.commands, call.inputs, call.deadline)
+        } else {
+            let call = execute_1Call::abi_decode(data, false).ok()?;
+            (call.commands, call.inputs, U256::MAX)
+        };
+
+        let mut wrapped_eth = false;
+
+        for (command, input) in commands.iter().zip(inputs.iter()) {
+            let (amount_in, amount_out_min, path) = match command & ur_commands::COMMAND_TYPE_MASK {
+                ur_commands::WRAP_ETH => {
+                    wrapped_eth = true;
+                    continue;
+                }
+                ur_commands::V2_SWAP_EXACT_IN => {
+                    let swap = V2SwapExactIn::abi_decode_params(input, false).ok()?;
+                    (swap.amountIn, swap.amountOutMin, swap.path)
+                }
+                ur_commands::V3_SWAP_EXACT_IN => {
+                    let swap = V3SwapExactIn::abi_decode_params(input, false).ok()?;
+                    (swap.amountIn, swap.amountOutMin, Self::decode_v3_path(&swap.path)?)
+                }
+                _ => continue,
+            };
+
+            let amount_in = if amount_in == UR_CONTRACT_BALANCE && wrapped_eth {
+                value
+            } else {
+                amount_in
+            };
+
+            return Some(SwapParams {
+                amount_in,
+                amount_out_min,
+                path,
+                deadline,
+            });
+        }
+
+        None
+    }
+
+    /// Extract token addresses from a V3 packed path: token(20) | fee(3) | token(20) ...
+    fn decode_v3_path(path: &[u8]) -> Option<Vec<Address>> {
+        const ADDR: usize = 20;
+        const HOP: usize = ADDR + 3;
+
+        if path.len() < ADDR + HOP || (path.len() - ADDR) % HOP != 0 {
+            return None;
+        }
+
+        Some(
+            (0..=(path.len() - ADDR) / HOP)
+                .map(|i| Address::from_slice(&path[i * HOP..i * HOP + ADDR]))
+                .collect(),
+        )
     }
 
     fn try_decode_swap_exact_eth_for_tokens(data: &[u8], value: U256) -> Option<SwapParams> {
@@ -189,6 +289,7 @@ impl SwapDecoder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_sol_types::SolValue;
 
     #[test]
     fn test_slippage_calculation() {
@@ -200,4 +301,98 @@ mod tests {
             SwapDecoder::calculate_slippage_bps(amount_in, amount_out_min, expected_rate);
         assert_eq!(slippage, 300); // 3% slippage
     }
+
+    #[test]
+    fn test_universal_router_wrap_and_v2_swap() {
+        let weth = Address::repeat_byte(0x11);
+        let token = Address::repeat_byte(0x22);
+        let value = U256::from(500_000_000_000_000_000u128); // 0.5 ETH
+
+        let wrap = (Address::repeat_byte(0x02), value).abi_encode_params();
+        let swap = V2SwapExactIn {
+            recipient: Address::repeat_byte(0x01),
+            amountIn: UR_CONTRACT_BALANCE,
+            amountOutMin: U256::from(1000u64),
+            path: vec![weth, token],
+            payerIsUser: false,
+        };
+        let calldata = ur_execute_calldata(
+            vec![ur_commands::WRAP_ETH, ur_commands::V2_SWAP_EXACT_IN],
+            vec![wrap, swap.abi_encode_params()],
+        );
+
+        let params = SwapDecoder::decode(&calldata, value).unwrap();
+        assert_eq!(params.path, vec![weth, token]);
+        assert_eq!(params.amount_in, value);
+        assert_eq!(params.amount_out_min, U256::from(1000u64));
+        assert_eq!(params.deadline, U256::from(1_700_000_000u64));
+    }
+
+    #[test]
+    fn test_universal_router_v3_path() {
+        let (a, b, c) = (Address::repeat_byte(0xaa), Address::repeat_byte(0xbb), Address::repeat_byte(0xcc));
+        let mut path = a.to_vec();
+        path.extend_from_slice(&[0x00, 0x0b, 0xb8]); // 3000 fee
+        path.extend_from_slice(b.as_slice());
+        path.extend_from_slice(&[0x00, 0x01, 0xf4]); // 500 fee
+        path.extend_from_slice(c.as_slice());
+
+        let swap = V3SwapExactIn {
+            recipient: Address::repeat_byte(0x01),
+            amountIn: U256::from(42u64),
+            amountOutMin: U256::ZERO,
+            path: path.into(),
+            payerIsUser: true,
+        };
+        // Allow-revert flag (0x80) must be masked off
+        let calldata = ur_execute_calldata(
+            vec![0x80 | ur_commands::V3_SWAP_EXACT_IN],
+            vec![swap.abi_encode_params()],
+        );
+
+        let params = SwapDecoder::decode(&calldata, U256::ZERO).unwrap();
+        assert_eq!(params.path, vec![a, b, c]);
+        assert_eq!(params.amount_in, U256::from(42u64));
+    }
+
+    #[test]
+    fn test_swap_params_encode_roundtrip() {
+        use crate::models::types::{exactInputCall, NativeSwapAbi};
+
+        let (weth, token) = (Address::repeat_byte(0xee), Address::repeat_byte(0x11));
+        let to = Address::repeat_byte(0x01);
+        let swap = |path| SwapParams {
+            amount_in: U256::from(5u64),
+            amount_out_min: U256::from(3u64),
+            path,
+            deadline: U256::from(1_700_000_000u64),
+        };
+
+        let buy = swap(vec![weth, token]).encode_v2_buy(to, NativeSwapAbi::Eth);
+        let decoded = SwapDecoder::decode(&buy, U256::from(5u64)).unwrap();
+        assert_eq!(decoded.path, vec![weth, token]);
+        assert_eq!(decoded.amount_in, U256::from(5u64));
+        assert_eq!(decoded.amount_out_min, U256::from(3u64));
+
+        let sell = swap(vec![token, weth]).encode_v2_sell(to, NativeSwapAbi::Eth);
+        let decoded = SwapDecoder::decode(&sell, U256::ZERO).unwrap();
+        assert_eq!(decoded.path, vec![token, weth]);
+        assert_eq!(decoded.amount_in, U256::from(5u64));
+
+        let v3 = swap(vec![weth, token]).encode_v3_exact_input(to, 3000).unwrap();
+        let call = exactInputCall::abi_decode(&v3, true).unwrap();
+        assert_eq!(&call.params.path[20..23], &[0x00, 0x0b, 0xb8]);
+        assert_eq!(SwapDecoder::decode_v3_path(&call.params.path), Some(vec![weth, token]));
+        assert!(swap(vec![weth]).encode_v3_exact_input(to, 3000).is_none());
+    }
+
+    fn ur_execute_calldata(commands: Vec<u8>, inputs: Vec<Vec<u8>>) -> Bytes {
+        execute_0Call {
+            commands: commands.into(),
+            inputs: inputs.into_iter().map(Bytes::from).collect(),
+            deadline: U256::from(1_700_000_000u64),
+        }
+        .abi_encode()
+        .into()
+    }
 }
@@ -0,0 +1,185 @@
+//! Failure Log - Record of Failed Analyses
+//!
+//! Hasil simulasi yang gagal TIDAK di-cache (CEO directive), jadi kegagalan
+//! transient hilang tanpa jejak. Modul ini menyimpan N kegagalan terakhir
+//! (ring buffer in-memory + file JSONL opsional) agar operator bisa
+//! me-replay request yang gagal.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+use utoipa::ToSchema;
+
+/// Default number of failures kept in memory
+pub const DEFAULT_FAILURE_LOG_SIZE: usize = 100;
+
+/// One failed analysis, with enough input to replay it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FailureRecord {
+    /// Unix timestamp
+    pub timestamp: u64,
+    pub token_address: String,
+    pub chain_id: u64,
+    pub error: String,
+    /// Request inputs (test amount, profile, block, decimals, ...)
+    #[schema(value_type = Object)]
+    pub inputs: serde_json::Value,
+}
+
+impl FailureRecord {
+    pub fn new(
+        token_address: impl Into<String>,
+        chain_id: u64,
+        error: impl Into<String>,
+        inputs: serde_json::Value,
+    ) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            token_address: token_address.into(),
+            chain_id,
+            error: error.into(),
+            inputs,
+        }
+    }
+}
+
+/// Ring buffer of the last N failures, optionally mirrored to a JSONL file
+pub struct FailureLog {
+    capacity: usize,
+    entries: RwLock<VecDeque<FailureRecord>>,
+    path: Option<PathBuf>,
+    total_recorded: AtomicU64,
+}
+
+impl FailureLog {
+    /// `capacity` 0 = disabled (nothing recorded)
+    pub fn new(capacity: usize, path: Option<PathBuf>) -> Self {
+        Self {
+            capacity,
+            entries: RwLock::new(VecDeque::with_capacity(capacity)),
+            path,
+            total_recorded: AtomicU64::new(0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Failures recorded since startup (including evicted ones)
+    pub fn total_recorded(&self) -> u64 {
+        self.total_recorded.load(Ordering::Relaxed)
+    }
+
+    /// Record a failure, evicting the oldest once full
+    pub fn record(&self, record: FailureRecord) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        if let Some(path) = &self.path {
+            if let Err(e) = Self::append_to_file(path, &record) {
+                warn!("⚠️ Failed to write failure log {:?}: {}", path, e);
+            }
+        }
+
+        if let Ok(mut entries) = self.entries.write() {
+            while entries.len() >= self.capacity {
+                entries.pop_front();
+            }
+            entries.push_back(record);
+        }
+        self.total_recorded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Most recent failures first, at most `limit`
+    pub fn recent(&self, limit: usize) -> Vec<FailureRecord> {
+        self.entries
+            .read()
+            .map(|entries| entries.iter().rev().take(limit).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().map(|e| e.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn append_to_file(path: &PathBuf, record: &FailureRecord) -> Result<(), std::io::Error> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let json = serde_json::to_string(record)?;
+        writeln!(file, "{}", json)
+    }
+}
+
+impl Default for FailureLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_LOG_SIZE, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(token: &str) -> FailureRecord {
+        FailureRecord::new(token, 1, "RPC timeout", serde_json::json!({ "test_amount_eth": "0.1" }))
+    }
+
+    #[test]
+    fn test_failure_log_ring_buffer() {
+        let log = FailureLog::new(2, None);
+        log.record(failure("0xa"));
+        log.record(failure("0xb"));
+        log.record(failure("0xc"));
+
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.total_recorded(), 3);
+        let recent: Vec<_> = log.recent(10).into_iter().map(|r| r.token_address).collect();
+        assert_eq!(recent, vec!["0xc", "0xb"]);
+        assert_eq!(log.recent(1).len(), 1);
+
+        // Disabled log records nothing
+        let disabled = FailureLog::new(0, None);
+        disabled.record(failure("0xa"));
+        assert!(disabled.is_empty());
+        assert_eq!(disabled.total_recorded(), 0);
+    }
+
+    #[test]
+    fn test_failure_log_file() {
+        let path = std::env::temp_dir().join(format!("ruster_failures_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let log = FailureLog::new(10, Some(path.clone()));
+        log.record(failure("0xa"));
+        log.record(failure("0xb"));
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<FailureRecord> = contents
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].token_address, "0xb");
+        assert_eq!(records[0].inputs["test_amount_eth"], "0.1");
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
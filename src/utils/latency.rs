@@ -0,0 +1,173 @@
+//! Latency - Per-Method Upstream Call Histograms
+//!
+//! Telemetry hanya mencatat latency analisis secara total. Modul ini mencatat
+//! latency setiap panggilan upstream (eth_getCode, eth_call, DexScreener, ...)
+//! per method ke dalam histogram bucket tetap, agar terlihat method mana yang
+//! mendominasi waktu analisis.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+/// Upper bounds (inclusive, milliseconds) of the histogram buckets; slower calls land in overflow
+pub const LATENCY_BUCKETS_MS: [u64; 10] = [10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+
+lazy_static::lazy_static! {
+    /// Every RPC method and DexScreener endpoint, process-wide (`/v1/stats/latency`)
+    pub static ref UPSTREAM_LATENCY: LatencyHistograms = LatencyHistograms::new();
+}
+
+/// One histogram per method name
+pub struct LatencyHistograms {
+    methods: Mutex<HashMap<String, Histogram>>,
+}
+
+#[derive(Default)]
+struct Histogram {
+    /// One count per `LATENCY_BUCKETS_MS` entry, plus overflow
+    buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    calls: u64,
+    errors: u64,
+    total_ms: u64,
+    max_ms: u64,
+}
+
+/// One bucket of a `MethodLatency` histogram
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct LatencyBucket {
+    /// Inclusive upper bound in ms (None = overflow)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+/// Latency summary of one method since startup
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct MethodLatency {
+    /// RPC method ("eth_call") or upstream endpoint ("dexscreener:/tokens")
+    pub method: String,
+    pub calls: u64,
+    /// Calls that returned an error (still timed)
+    pub errors: u64,
+    pub total_ms: u64,
+    pub avg_ms: f64,
+    pub max_ms: u64,
+    /// Percentiles are bucket upper bounds (max_ms for the overflow bucket)
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+impl LatencyHistograms {
+    pub fn new() -> Self {
+        Self {
+            methods: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record one call of `method` (each retry attempt is its own call)
+    pub fn record(&self, method: &str, elapsed: Duration, ok: bool) {
+        let ms = elapsed.as_millis().min(u64::MAX as u128) as u64;
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&le| ms <= le)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+
+        let mut methods = self.methods.lock().unwrap_or_else(|e| e.into_inner());
+        let histogram = methods.entry(method.to_string()).or_default();
+        histogram.buckets[bucket] += 1;
+        histogram.calls += 1;
+        histogram.errors += u64::from(!ok);
+        histogram.total_ms = histogram.total_ms.saturating_add(ms);
+        histogram.max_ms = histogram.max_ms.max(ms);
+    }
+
+    /// Every method, largest total time first (the bottleneck leads)
+    pub fn snapshot(&self) -> Vec<MethodLatency> {
+        let methods = self.methods.lock().unwrap_or_else(|e| e.into_inner());
+        let mut snapshot: Vec<MethodLatency> = methods
+            .iter()
+            .map(|(method, histogram)| histogram.summary(method))
+            .collect();
+        snapshot.sort_by(|a, b| b.total_ms.cmp(&a.total_ms).then_with(|| a.method.cmp(&b.method)));
+        snapshot
+    }
+}
+
+impl Default for LatencyHistograms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Histogram {
+    fn summary(&self, method: &str) -> MethodLatency {
+        MethodLatency {
+            method: method.to_string(),
+            calls: self.calls,
+            errors: self.errors,
+            total_ms: self.total_ms,
+            avg_ms: if self.calls > 0 { self.total_ms as f64 / self.calls as f64 } else { 0.0 },
+            max_ms: self.max_ms,
+            p50_ms: self.percentile(0.50),
+            p95_ms: self.percentile(0.95),
+            p99_ms: self.percentile(0.99),
+            buckets: self
+                .buckets
+                .iter()
+                .enumerate()
+                .map(|(i, &count)| LatencyBucket {
+                    le_ms: LATENCY_BUCKETS_MS.get(i).copied(),
+                    count,
+                })
+                .collect(),
+        }
+    }
+
+    /// Upper bound of the bucket holding the `quantile` call
+    fn percentile(&self, quantile: f64) -> u64 {
+        let rank = ((self.calls as f64 * quantile).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (i, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return LATENCY_BUCKETS_MS.get(i).copied().unwrap_or(self.max_ms).min(self.max_ms);
+            }
+        }
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_histograms() {
+        let histograms = LatencyHistograms::new();
+        for ms in [5, 8, 40, 90, 120] {
+            histograms.record("eth_call", Duration::from_millis(ms), true);
+        }
+        histograms.record("eth_getCode", Duration::from_millis(800), true);
+        histograms.record("eth_getCode", Duration::from_millis(20_000), false);
+
+        let snapshot = histograms.snapshot();
+        // Largest total time first
+        assert_eq!(snapshot[0].method, "eth_getCode");
+        assert_eq!(snapshot[0].calls, 2);
+        assert_eq!(snapshot[0].errors, 1);
+        assert_eq!(snapshot[0].max_ms, 20_000);
+        // Overflow bucket reports the observed max
+        assert_eq!(snapshot[0].p99_ms, 20_000);
+        assert_eq!(snapshot[0].buckets.last(), Some(&LatencyBucket { le_ms: None, count: 1 }));
+
+        let eth_call = &snapshot[1];
+        assert_eq!(eth_call.total_ms, 263);
+        assert_eq!(eth_call.p50_ms, 50);
+        assert_eq!(eth_call.p95_ms, 120);
+        assert_eq!(eth_call.buckets[0], LatencyBucket { le_ms: Some(10), count: 2 });
+    }
+}
@@ -3,12 +3,26 @@
 //! Berisi fungsi-fungsi pembantu yang digunakan di seluruh aplikasi.
 //! CEO Directive: Single Source of Truth untuk fungsi shared.
 
+pub mod alerts;
+pub mod audit;
+pub mod backoff;
 pub mod cache;
+pub mod chains;
 pub mod constants;
 pub mod decoder;
+pub mod failure_log;
+pub mod latency;
 pub mod telemetry;
+pub mod throttle;
 
+pub use alerts::*;
+pub use audit::*;
+pub use backoff::*;
 pub use cache::*;
+pub use chains::*;
 pub use constants::*;
 pub use decoder::*;
+pub use failure_log::*;
+pub use latency::*;
 pub use telemetry::*;
+pub use throttle::*;
@@ -7,15 +7,28 @@
 //!
 //! Privacy-first: No wallet addresses or transaction hashes stored
 
-use alloy_primitives::U256;
+use alloy_primitives::{Address, U256};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+/// A token re-flagged as a honeypot within this window counts once (24h)
+pub const HONEYPOT_DEDUP_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+/// Prune expired dedup entries once the map grows past this size
+const HONEYPOT_DEDUP_PRUNE_THRESHOLD: usize = 10_000;
+
+/// Largest single-event value (native units) counted toward value protected.
+/// Anything above is a bad input (e.g. a raw token amount passed as wei), not a real save
+pub const MAX_EVENT_VALUE_NATIVE: f64 = 1_000_000_000.0;
 
 /// Telemetry event types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -50,14 +63,21 @@ pub struct TelemetryEvent {
     pub timestamp: u64,
     /// Type of threat detected
     pub threat_type: ThreatType,
-    /// Value at risk in ETH (rounded to hide exact amounts)
+    /// Value at risk in the chain's native token (rounded to hide exact amounts)
+    /// Field name kept for compatibility - only ETH on ETH-native chains
     pub value_at_risk_eth: f64,
+    /// Chain the value is denominated on (native token)
+    #[serde(default = "default_event_chain_id")]
+    pub chain_id: u64,
     /// Detection latency in milliseconds
     pub latency_ms: u64,
     /// Risk level (1-5)
     pub risk_level: u8,
     /// Additional context (no PII)
     pub context: String,
+    /// Flagged token, used only to dedup honeypot counts (never exported)
+    #[serde(skip)]
+    pub token: Option<Address>,
 }
 
 impl TelemetryEvent {
@@ -76,15 +96,33 @@ impl TelemetryEvent {
             timestamp: current_timestamp(),
             threat_type,
             value_at_risk_eth: rounded_value,
+            chain_id: CHAIN_ID_ETHEREUM,
             latency_ms,
             risk_level,
             context,
+            token: None,
         }
     }
+
+    /// Set the chain whose native token `value_wei` is denominated in
+    pub fn on_chain(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Attach the flagged token so repeat detections on the same chain count once
+    pub fn for_token(mut self, token: Address) -> Self {
+        self.token = Some(token);
+        self
+    }
+}
+
+fn default_event_chain_id() -> u64 {
+    CHAIN_ID_ETHEREUM
 }
 
 /// Aggregated statistics for reporting
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
 pub struct TelemetryStats {
     /// Total transactions analyzed
     pub total_analyzed: u64,
@@ -104,12 +142,88 @@ pub struct TelemetryStats {
     pub honeypots_detected: u64,
     /// Estimated USD saved (at current ETH price)
     pub estimated_usd_saved: f64,
+    /// Value protected per chain, in that chain's native token
+    #[serde(default)]
+    pub value_protected_by_chain: HashMap<u64, f64>,
+    /// Native token USD prices per chain (filled at report time)
+    #[serde(default)]
+    pub native_prices_usd: HashMap<u64, f64>,
+}
+
+/// Machine-readable protection report (JSON equivalent of `marketing_summary`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReportData {
+    pub period_hours: u64,
+    pub total_analyzed: u64,
+    pub total_threats: u64,
+    pub honeypots_detected: u64,
+    pub value_protected_eth: f64,
+    pub eth_price_usd: f64,
+    pub usd_saved: f64,
+    pub avg_latency_ms: f64,
+    pub period_start: u64,
+    pub period_end: u64,
+    /// Value protected per chain, in native token units
+    pub value_protected_by_chain: HashMap<u64, f64>,
+    /// Native token USD prices used for `usd_saved`
+    pub native_prices_usd: HashMap<u64, f64>,
 }
 
 impl TelemetryStats {
+    /// Attach per-chain native token prices (from `AlchemyPricesClient`)
+    pub fn with_native_prices(mut self, native_prices_usd: HashMap<u64, f64>) -> Self {
+        self.native_prices_usd = native_prices_usd;
+        self
+    }
+
+    /// USD value protected: each chain's native amount × that chain's native price
+    /// ETH-native chains (Arbitrum, Optimism, Base) fall back to `eth_price_usd`;
+    /// non-ETH chains without a price are left out rather than priced as ETH
+    pub fn usd_saved(&self, eth_price_usd: f64) -> f64 {
+        if self.value_protected_by_chain.is_empty() {
+            return self.total_value_protected_eth * eth_price_usd;
+        }
+
+        let eth_price = self
+            .native_prices_usd
+            .get(&CHAIN_ID_ETHEREUM)
+            .copied()
+            .unwrap_or(eth_price_usd);
+
+        self.value_protected_by_chain
+            .iter()
+            .map(|(chain_id, amount)| {
+                let price = match self.native_prices_usd.get(chain_id) {
+                    Some(price) => *price,
+                    None if get_native_symbol(*chain_id) == "ETH" => eth_price,
+                    None => 0.0,
+                };
+                amount * price
+            })
+            .sum()
+    }
+
+    /// Structured report with the same figures as `marketing_summary`
+    pub fn to_report(&self, eth_price_usd: f64) -> ReportData {
+        ReportData {
+            period_hours: self.period_end.saturating_sub(self.period_start) / 3600,
+            total_analyzed: self.total_analyzed,
+            total_threats: self.total_threats,
+            honeypots_detected: self.honeypots_detected,
+            value_protected_eth: self.total_value_protected_eth,
+            eth_price_usd,
+            usd_saved: self.usd_saved(eth_price_usd),
+            avg_latency_ms: self.avg_latency_ms,
+            period_start: self.period_start,
+            period_end: self.period_end,
+            value_protected_by_chain: self.value_protected_by_chain.clone(),
+            native_prices_usd: self.native_prices_usd.clone(),
+        }
+    }
+
     /// Generate marketing summary
     pub fn marketing_summary(&self, eth_price_usd: f64) -> String {
-        let usd_saved = self.total_value_protected_eth * eth_price_usd;
+        let usd_saved = self.usd_saved(eth_price_usd);
         let period_hours = (self.period_end - self.period_start) / 3600;
 
         format!(
@@ -164,6 +278,16 @@ impl TelemetryStats {
     }
 }
 
+/// Counters for a single chain (`TelemetryCollector::get_stats_by_chain`)
+#[derive(Debug, Default)]
+struct ChainCounters {
+    analyzed: u64,
+    threats: u64,
+    honeypots: u64,
+    latency_ms: u64,
+    threat_counts: HashMap<ThreatType, u64>,
+}
+
 /// Main telemetry collector
 pub struct TelemetryCollector {
     /// Event buffer (in-memory)
@@ -174,8 +298,14 @@ pub struct TelemetryCollector {
     honeypots_detected: AtomicU64,
     total_latency_ms: AtomicU64,
     total_value_wei: Arc<RwLock<U256>>,
+    /// Value protected per chain (native wei)
+    value_by_chain: Arc<RwLock<HashMap<u64, U256>>>,
     /// Threat counters by type
     threat_counts: Arc<RwLock<HashMap<ThreatType, u64>>>,
+    /// Analysis / threat counters per chain
+    chain_counters: Arc<RwLock<HashMap<u64, ChainCounters>>>,
+    /// Last time each (chain, token) was counted as a honeypot
+    seen_honeypots: Arc<RwLock<HashMap<(u64, Address), u64>>>,
     /// Session start time
     session_start: u64,
     /// Export directory
@@ -190,6 +320,11 @@ impl TelemetryCollector {
         Self::with_config(PathBuf::from("./telemetry"), 1000)
     }
 
+    /// Create collector exporting to the configured `TELEMETRY_EXPORT_DIR`
+    pub fn from_sentry_config(config: &crate::models::config::SentryConfig) -> Self {
+        Self::with_config(PathBuf::from(&config.telemetry_export_dir), 1000)
+    }
+
     /// Create collector with custom config
     pub fn with_config(export_dir: PathBuf, max_buffer_size: usize) -> Self {
         // Ensure export directory exists
@@ -202,22 +337,40 @@ impl TelemetryCollector {
             honeypots_detected: AtomicU64::new(0),
             total_latency_ms: AtomicU64::new(0),
             total_value_wei: Arc::new(RwLock::new(U256::ZERO)),
+            value_by_chain: Arc::new(RwLock::new(HashMap::new())),
             threat_counts: Arc::new(RwLock::new(HashMap::new())),
+            chain_counters: Arc::new(RwLock::new(HashMap::new())),
+            seen_honeypots: Arc::new(RwLock::new(HashMap::new())),
             session_start: current_timestamp(),
             export_dir,
             max_buffer_size,
         }
     }
 
-    /// Record a transaction analysis (no threat)
-    pub fn record_analysis(&self, latency_ms: u64) {
+    /// Record a transaction analysis (no threat) on `chain_id`
+    pub fn record_analysis(&self, chain_id: u64, latency_ms: u64) {
         self.total_analyzed.fetch_add(1, Ordering::Relaxed);
         self.total_latency_ms
             .fetch_add(latency_ms, Ordering::Relaxed);
+
+        if let Ok(mut chains) = self.chain_counters.write() {
+            let counters = chains.entry(chain_id).or_default();
+            counters.analyzed += 1;
+            counters.latency_ms += latency_ms;
+        }
     }
 
     /// Record a detected threat
+    ///
+    /// Honeypot events tagged with `for_token` are deduplicated per (chain, token)
+    /// within `HONEYPOT_DEDUP_WINDOW_SECS`: a token hit via `/analyze/token`,
+    /// `/honeypot/check` and `/batch` is one honeypot, the repeats are plain analyses.
     pub fn record_threat(&self, event: TelemetryEvent) {
+        if self.is_repeat_honeypot(&event) {
+            self.record_analysis(event.chain_id, event.latency_ms);
+            return;
+        }
+
         // Update atomic counters
         self.total_analyzed.fetch_add(1, Ordering::Relaxed);
         self.total_threats.fetch_add(1, Ordering::Relaxed);
@@ -229,9 +382,19 @@ impl TelemetryCollector {
             self.honeypots_detected.fetch_add(1, Ordering::Relaxed);
         }
 
-        // Update value protected
+        // Update value protected (out-of-range values are dropped, not clamped)
+        let event_value = native_to_wei(event.value_at_risk_eth).unwrap_or_else(|| {
+            warn!(
+                "⚠️ Telemetry value {} on chain {} out of range (max {}) - not counted as protected",
+                event.value_at_risk_eth, event.chain_id, MAX_EVENT_VALUE_NATIVE
+            );
+            U256::ZERO
+        });
         if let Ok(mut value) = self.total_value_wei.write() {
-            let event_value = U256::from((event.value_at_risk_eth * 1e18) as u128);
+            *value = value.saturating_add(event_value);
+        }
+        if let Ok(mut by_chain) = self.value_by_chain.write() {
+            let value = by_chain.entry(event.chain_id).or_insert(U256::ZERO);
             *value = value.saturating_add(event_value);
         }
 
@@ -239,6 +402,16 @@ impl TelemetryCollector {
         if let Ok(mut counts) = self.threat_counts.write() {
             *counts.entry(event.threat_type.clone()).or_insert(0) += 1;
         }
+        if let Ok(mut chains) = self.chain_counters.write() {
+            let counters = chains.entry(event.chain_id).or_default();
+            counters.analyzed += 1;
+            counters.threats += 1;
+            counters.latency_ms += event.latency_ms;
+            if event.threat_type == ThreatType::Honeypot {
+                counters.honeypots += 1;
+            }
+            *counters.threat_counts.entry(event.threat_type.clone()).or_insert(0) += 1;
+        }
 
         // Buffer event
         if let Ok(mut events) = self.events.write() {
@@ -248,11 +421,36 @@ impl TelemetryCollector {
             if events.len() >= self.max_buffer_size {
                 let events_to_flush = std::mem::take(&mut *events);
                 drop(events); // Release lock before I/O
-                let _ = self.flush_events(&events_to_flush);
+                self.spawn_flush(events_to_flush);
             }
         }
     }
 
+    /// True if this honeypot token was already counted within the dedup window
+    fn is_repeat_honeypot(&self, event: &TelemetryEvent) -> bool {
+        let token = match (&event.threat_type, event.token) {
+            (ThreatType::Honeypot, Some(token)) => token,
+            _ => return false,
+        };
+        let Ok(mut seen) = self.seen_honeypots.write() else {
+            return false;
+        };
+
+        let key = (event.chain_id, token);
+        if let Some(&last) = seen.get(&key) {
+            if event.timestamp.saturating_sub(last) < HONEYPOT_DEDUP_WINDOW_SECS {
+                return true;
+            }
+        }
+
+        if seen.len() >= HONEYPOT_DEDUP_PRUNE_THRESHOLD {
+            let now = event.timestamp;
+            seen.retain(|_, last| now.saturating_sub(*last) < HONEYPOT_DEDUP_WINDOW_SECS);
+        }
+        seen.insert(key, event.timestamp);
+        false
+    }
+
     /// Get current statistics
     pub fn get_stats(&self) -> TelemetryStats {
         let total_analyzed = self.total_analyzed.load(Ordering::Relaxed);
@@ -272,6 +470,17 @@ impl TelemetryCollector {
             .map(|v| wei_to_eth(*v))
             .unwrap_or(0.0);
 
+        let value_protected_by_chain = self
+            .value_by_chain
+            .read()
+            .map(|by_chain| {
+                by_chain
+                    .iter()
+                    .map(|(chain_id, wei)| (*chain_id, wei_to_eth(*wei)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let threats_by_type = self
             .threat_counts
             .read()
@@ -293,9 +502,55 @@ impl TelemetryCollector {
             period_end: current_timestamp(),
             honeypots_detected: honeypots,
             estimated_usd_saved: 0.0, // Calculated at display time
+            value_protected_by_chain,
+            native_prices_usd: HashMap::new(),
         }
     }
 
+    /// Current statistics broken down per chain id
+    /// Each chain's value figures are in its own native token
+    pub fn get_stats_by_chain(&self) -> HashMap<u64, TelemetryStats> {
+        let value_by_chain = self
+            .value_by_chain
+            .read()
+            .map(|by_chain| by_chain.clone())
+            .unwrap_or_default();
+        let period_end = current_timestamp();
+
+        let Ok(chains) = self.chain_counters.read() else {
+            return HashMap::new();
+        };
+        chains
+            .iter()
+            .map(|(chain_id, counters)| {
+                let value_protected = value_by_chain.get(chain_id).map(|wei| wei_to_eth(*wei)).unwrap_or(0.0);
+                let avg_latency = if counters.analyzed > 0 {
+                    counters.latency_ms as f64 / counters.analyzed as f64
+                } else {
+                    0.0
+                };
+                let stats = TelemetryStats {
+                    total_analyzed: counters.analyzed,
+                    total_threats: counters.threats,
+                    threats_by_type: counters
+                        .threat_counts
+                        .iter()
+                        .map(|(k, v)| (k.as_str().to_string(), *v))
+                        .collect(),
+                    total_value_protected_eth: value_protected,
+                    avg_latency_ms: avg_latency,
+                    period_start: self.session_start,
+                    period_end,
+                    honeypots_detected: counters.honeypots,
+                    estimated_usd_saved: 0.0,
+                    value_protected_by_chain: HashMap::from([(*chain_id, value_protected)]),
+                    native_prices_usd: HashMap::new(),
+                };
+                (*chain_id, stats)
+            })
+            .collect()
+    }
+
     /// Export current stats to JSON file
     pub fn export_stats_json(&self) -> Result<PathBuf, std::io::Error> {
         let stats = self.get_stats();
@@ -325,24 +580,114 @@ impl TelemetryCollector {
         Ok(path)
     }
 
-    /// Flush buffered events to disk
-    fn flush_events(&self, events: &[TelemetryEvent]) -> Result<(), std::io::Error> {
+    /// Write the rolling `stats_latest.json` snapshot (overwritten atomically via rename)
+    pub fn export_stats_snapshot(&self) -> Result<PathBuf, std::io::Error> {
+        let stats = self.get_stats();
+        let path = self.export_dir.join("stats_latest.json");
+        let tmp = self.export_dir.join("stats_latest.json.tmp");
+
+        fs::write(&tmp, serde_json::to_string_pretty(&stats)?)?;
+        fs::rename(&tmp, &path)?;
+
+        Ok(path)
+    }
+
+    /// Flush every buffered event to disk (returns how many were written)
+    /// On a failed write the events go back into the buffer for the next flush
+    pub async fn flush(&self) -> Result<usize, std::io::Error> {
+        let events = match self.events.write() {
+            Ok(mut events) => std::mem::take(&mut *events),
+            Err(_) => return Ok(0),
+        };
+        if let Err(e) = Self::write_events(&self.export_dir, &events).await {
+            Self::restore_events(&self.events, events, self.max_buffer_size);
+            return Err(e);
+        }
+        Ok(events.len())
+    }
+
+    /// Auto-flush of a full buffer from `record_threat` without blocking the caller:
+    /// written on the tokio runtime, restored to the buffer if the write fails
+    /// (or kept buffered for the next `flush` when no runtime is running)
+    fn spawn_flush(&self, events: Vec<TelemetryEvent>) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            Self::restore_events(&self.events, events, self.max_buffer_size);
+            return;
+        };
+        let buffer = self.events.clone();
+        let export_dir = self.export_dir.clone();
+        let max_buffer_size = self.max_buffer_size;
+        runtime.spawn(async move {
+            if let Err(e) = Self::write_events(&export_dir, &events).await {
+                warn!("⚠️ Telemetry event flush failed: {}", e);
+                Self::restore_events(&buffer, events, max_buffer_size);
+            }
+        });
+    }
+
+    /// Put events whose write failed back in front of the buffer (oldest first), keeping
+    /// at most `max_buffer_size` so a disk that keeps failing can't grow it without bound
+    fn restore_events(
+        buffer: &RwLock<Vec<TelemetryEvent>>,
+        mut failed: Vec<TelemetryEvent>,
+        max_buffer_size: usize,
+    ) {
+        let Ok(mut events) = buffer.write() else {
+            return;
+        };
+        failed.append(&mut events);
+        let dropped = failed.len().saturating_sub(max_buffer_size);
+        if dropped > 0 {
+            warn!("⚠️ Telemetry buffer full after failed flush: dropped {} oldest events", dropped);
+            failed.drain(..dropped);
+        }
+        *events = failed;
+    }
+
+    /// Background task: flush events and export stats every `interval`
+    /// so a crash only loses the last interval (shutdown-only export loses everything)
+    pub fn start_periodic_export(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let collector = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await; // first tick fires immediately
+            loop {
+                ticker.tick().await;
+                match collector.flush().await {
+                    Ok(0) => {}
+                    Ok(flushed) => info!("📊 Telemetry: flushed {} events", flushed),
+                    Err(e) => warn!("⚠️ Telemetry event flush failed: {}", e),
+                }
+                if let Err(e) = collector.export_stats_snapshot() {
+                    warn!("⚠️ Telemetry stats export failed: {}", e);
+                }
+                if let Err(e) = collector.export_stats_csv() {
+                    warn!("⚠️ Telemetry CSV export failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Append events to this second's `events_*.jsonl` (async file IO)
+    async fn write_events(export_dir: &Path, events: &[TelemetryEvent]) -> Result<(), std::io::Error> {
         if events.is_empty() {
             return Ok(());
         }
 
         let filename = format!("events_{}.jsonl", current_timestamp());
-        let path = self.export_dir.join(filename);
-
-        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let path = export_dir.join(filename);
 
+        let mut lines = String::new();
         for event in events {
             if let Ok(json) = serde_json::to_string(event) {
-                writeln!(file, "{}", json)?;
+                lines.push_str(&json);
+                lines.push('\n');
             }
         }
 
-        Ok(())
+        let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&path).await?;
+        file.write_all(lines.as_bytes()).await?;
+        file.flush().await
     }
 
     /// Generate marketing report
@@ -351,6 +696,41 @@ impl TelemetryCollector {
         stats.marketing_summary(eth_price_usd)
     }
 
+    /// Generate marketing report with per-chain native prices
+    pub fn generate_marketing_report_priced(
+        &self,
+        eth_price_usd: f64,
+        native_prices_usd: HashMap<u64, f64>,
+    ) -> String {
+        self.get_stats()
+            .with_native_prices(native_prices_usd)
+            .marketing_summary(eth_price_usd)
+    }
+
+    /// Generate structured report (for dashboards / JSON API)
+    pub fn generate_report_data(&self, eth_price_usd: f64) -> ReportData {
+        self.get_stats().to_report(eth_price_usd)
+    }
+
+    /// Generate structured report with per-chain native prices
+    pub fn generate_report_data_priced(
+        &self,
+        eth_price_usd: f64,
+        native_prices_usd: HashMap<u64, f64>,
+    ) -> ReportData {
+        self.get_stats()
+            .with_native_prices(native_prices_usd)
+            .to_report(eth_price_usd)
+    }
+
+    /// Chains that have protected value recorded (for price lookups)
+    pub fn chains_with_value(&self) -> Vec<u64> {
+        self.value_by_chain
+            .read()
+            .map(|by_chain| by_chain.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
     /// Reset counters (for new reporting period)
     #[allow(dead_code)]
     pub fn reset(&self) {
@@ -363,10 +743,22 @@ impl TelemetryCollector {
             *value = U256::ZERO;
         }
 
+        if let Ok(mut by_chain) = self.value_by_chain.write() {
+            by_chain.clear();
+        }
+
         if let Ok(mut counts) = self.threat_counts.write() {
             counts.clear();
         }
 
+        if let Ok(mut chains) = self.chain_counters.write() {
+            chains.clear();
+        }
+
+        if let Ok(mut seen) = self.seen_honeypots.write() {
+            seen.clear();
+        }
+
         if let Ok(mut events) = self.events.write() {
             events.clear();
         }
@@ -416,7 +808,7 @@ impl WeeklyReportGenerator {
     /// Generate weekly summary for Discord/Telegram
     pub fn generate_social_post(&self, eth_price: f64) -> String {
         let stats = self.collector.get_stats();
-        let usd_saved = stats.total_value_protected_eth * eth_price;
+        let usd_saved = stats.usd_saved(eth_price);
 
         format!(
             r#"🦀 **RUSTER REVM WEEKLY REPORT**
@@ -453,12 +845,96 @@ fn current_timestamp() -> u64 {
 }
 
 // CEO Directive: Use wei_to_eth from utils/constants.rs
-use crate::utils::constants::wei_to_eth;
+use crate::utils::constants::{get_native_symbol, wei_to_eth, CHAIN_ID_ETHEREUM};
+
+/// Native amount to wei, None when negative, non-finite or above `MAX_EVENT_VALUE_NATIVE`
+/// (an `as u128` cast would silently saturate and poison the running total)
+fn native_to_wei(value: f64) -> Option<U256> {
+    (value.is_finite() && (0.0..=MAX_EVENT_VALUE_NATIVE).contains(&value))
+        .then(|| U256::from((value * 1e18) as u128))
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_flush_and_snapshot() {
+        let dir = std::env::temp_dir().join(format!("ruster_telemetry_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let collector = TelemetryCollector::with_config(dir.clone(), 1000);
+
+        collector.record_threat(TelemetryEvent::new(
+            ThreatType::Honeypot,
+            U256::from(1_000_000_000_000_000_000u128),
+            10,
+            5,
+            "Sell failed".to_string(),
+        ));
+        assert_eq!(collector.flush().await.unwrap(), 1);
+        assert_eq!(collector.flush().await.unwrap(), 0);
+
+        let path = collector.export_stats_snapshot().unwrap();
+        let stats: TelemetryStats = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(stats.honeypots_detected, 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_keeps_events() {
+        let dir = std::env::temp_dir().join(format!("ruster_telemetry_retry_{}", std::process::id()));
+        let collector = TelemetryCollector::with_config(dir.clone(), 1000);
+        collector.record_threat(TelemetryEvent::new(
+            ThreatType::Honeypot,
+            U256::ZERO,
+            10,
+            5,
+            "Sell failed".to_string(),
+        ));
+
+        // Export dir gone: the write fails and the event stays buffered
+        let _ = fs::remove_dir_all(&dir);
+        assert!(collector.flush().await.is_err());
+
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(collector.flush().await.unwrap(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_honeypot_dedup_per_chain_token() {
+        let dir = std::env::temp_dir().join(format!("ruster_telemetry_dedup_{}", std::process::id()));
+        let collector = TelemetryCollector::with_config(dir.clone(), 1000);
+        let token = Address::repeat_byte(0x11);
+        let honeypot = |chain_id| {
+            TelemetryEvent::new(ThreatType::Honeypot, U256::ZERO, 10, 5, "Sell failed".to_string())
+                .on_chain(chain_id)
+                .for_token(token)
+        };
+
+        // Same token via three endpoints = one honeypot
+        collector.record_threat(honeypot(1));
+        collector.record_threat(honeypot(1));
+        collector.record_threat(honeypot(1));
+        // Same address on another chain is a different token
+        collector.record_threat(honeypot(56));
+
+        let stats = collector.get_stats();
+        assert_eq!(stats.honeypots_detected, 2);
+        assert_eq!(stats.total_threats, 2);
+        assert_eq!(stats.total_analyzed, 4);
+
+        // Outside the window it counts again
+        let mut later = honeypot(1);
+        later.timestamp += HONEYPOT_DEDUP_WINDOW_SECS;
+        collector.record_threat(later);
+        assert_eq!(collector.get_stats().honeypots_detected, 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_telemetry_event_creation() {
         let event = TelemetryEvent::new(
@@ -480,8 +956,8 @@ mod tests {
         let collector = TelemetryCollector::new();
 
         // Record some analyses
-        collector.record_analysis(10);
-        collector.record_analysis(20);
+        collector.record_analysis(CHAIN_ID_ETHEREUM, 10);
+        collector.record_analysis(CHAIN_ID_ETHEREUM, 20);
 
         // Record a threat
         let event = TelemetryEvent::new(
@@ -499,6 +975,37 @@ mod tests {
         assert_eq!(stats.honeypots_detected, 1);
     }
 
+    #[test]
+    fn test_stats_by_chain() {
+        let dir = std::env::temp_dir().join(format!("ruster_telemetry_by_chain_{}", std::process::id()));
+        let collector = TelemetryCollector::with_config(dir.clone(), 1000);
+
+        collector.record_analysis(CHAIN_ID_ETHEREUM, 10);
+        collector.record_threat(
+            TelemetryEvent::new(ThreatType::Honeypot, U256::from(1_000_000_000_000_000_000u128), 30, 5, "a".to_string())
+                .on_chain(56),
+        );
+        collector.record_threat(
+            TelemetryEvent::new(ThreatType::HighTax, U256::ZERO, 20, 3, "b".to_string()).on_chain(56),
+        );
+
+        let by_chain = collector.get_stats_by_chain();
+        assert_eq!(by_chain.len(), 2);
+        let eth = &by_chain[&CHAIN_ID_ETHEREUM];
+        assert_eq!((eth.total_analyzed, eth.total_threats), (1, 0));
+        let bsc = &by_chain[&56];
+        assert_eq!((bsc.total_analyzed, bsc.total_threats, bsc.honeypots_detected), (2, 2, 1));
+        assert_eq!(bsc.threats_by_type.get("high_tax"), Some(&1));
+        assert_eq!(bsc.total_value_protected_eth, 1.0);
+        assert_eq!(bsc.avg_latency_ms, 25.0);
+
+        // Per-chain counters add up to the global aggregate
+        let total: u64 = by_chain.values().map(|s| s.total_analyzed).sum();
+        assert_eq!(total, collector.get_stats().total_analyzed);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_stats_json_export() {
         let stats = TelemetryStats {
@@ -533,4 +1040,63 @@ mod tests {
         assert!(report.contains("625000")); // USD saved (no comma in Rust format)
         assert!(report.contains("150")); // Honeypots
     }
+
+    #[test]
+    fn test_usd_saved_per_chain() {
+        let collector = TelemetryCollector::new();
+        let one = U256::from(1_000_000_000_000_000_000u128);
+        collector.record_threat(
+            TelemetryEvent::new(ThreatType::Honeypot, one, 10, 5, "eth".to_string()),
+        );
+        collector.record_threat(
+            TelemetryEvent::new(ThreatType::Honeypot, one, 10, 5, "bsc".to_string()).on_chain(56),
+        );
+        collector.record_threat(
+            TelemetryEvent::new(ThreatType::Honeypot, one, 10, 5, "base".to_string()).on_chain(8453),
+        );
+
+        // BNB priced as BNB, Base (ETH-native) falls back to ETH price
+        let prices = HashMap::from([(56u64, 600.0)]);
+        let stats = collector.get_stats().with_native_prices(prices);
+        assert_eq!(stats.usd_saved(2500.0), 2500.0 + 600.0 + 2500.0);
+
+        // Unpriced non-ETH chain is excluded instead of priced as ETH
+        let stats = collector.get_stats();
+        assert_eq!(stats.usd_saved(2500.0), 5000.0);
+    }
+
+    #[test]
+    fn test_out_of_range_value_not_counted() {
+        assert_eq!(native_to_wei(1.5), Some(U256::from(1_500_000_000_000_000_000u128)));
+        assert_eq!(native_to_wei(f64::NAN), None);
+        assert_eq!(native_to_wei(-1.0), None);
+        assert_eq!(native_to_wei(MAX_EVENT_VALUE_NATIVE * 2.0), None);
+
+        let collector = TelemetryCollector::new();
+        let one = U256::from(1_000_000_000_000_000_000u128);
+        collector.record_threat(TelemetryEvent::new(ThreatType::Honeypot, one, 10, 5, "ok".to_string()));
+        collector.record_threat(TelemetryEvent::new(ThreatType::LargeValue, U256::MAX, 10, 5, "whale".to_string()));
+
+        let stats = collector.get_stats();
+        assert_eq!(stats.total_threats, 2);
+        assert_eq!(stats.total_value_protected_eth, 1.0);
+    }
+
+    #[test]
+    fn test_report_data() {
+        let stats = TelemetryStats {
+            total_analyzed: 50000,
+            honeypots_detected: 150,
+            total_value_protected_eth: 250.0,
+            period_start: 1704067200,
+            period_end: 1704672000,
+            ..Default::default()
+        };
+
+        let report = stats.to_report(2500.0);
+        assert_eq!(report.period_hours, 168);
+        assert_eq!(report.usd_saved, 625000.0);
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("\"honeypots_detected\":150"));
+    }
 }
@@ -0,0 +1,154 @@
+//! Throttle - Token Bucket per Upstream API
+//!
+//! DexScreener dan RPC (Alchemy) punya rate limit sendiri-sendiri. Token bucket
+//! ini membatasi satu upstream saja, plus cooldown (exponential backoff) setelah
+//! upstream membalas 429 - throttling di satu dependency tidak ikut menahan
+//! request ke dependency lain.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::backoff::BackoffConfig;
+
+/// Token bucket for one upstream: `capacity` burst, refilled at `refill_per_sec`,
+/// with a 429 cooldown that grows on consecutive throttles
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    /// Cooldown schedule after consecutive 429s
+    backoff: BackoffConfig,
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+    /// No requests before this instant (429 cooldown)
+    cooldown_until: Option<Instant>,
+    /// 429s in a row, drives the cooldown length
+    consecutive_throttles: u32,
+}
+
+impl TokenBucket {
+    /// Starts full; `refill_per_sec` is floored at 0.01 so a wait is always finite
+    pub fn new(capacity: u32, refill_per_sec: f64, backoff: BackoffConfig) -> Self {
+        let capacity = capacity.max(1) as f64;
+        Self {
+            capacity,
+            refill_per_sec: refill_per_sec.max(0.01),
+            backoff,
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                last_refill: Instant::now(),
+                cooldown_until: None,
+                consecutive_throttles: 0,
+            }),
+        }
+    }
+
+    /// Take a token now, or Err(time until the next one / the end of the cooldown)
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(until) = state.cooldown_until {
+            if now < until {
+                return Err(until - now);
+            }
+            state.cooldown_until = None;
+        }
+
+        let elapsed = now.saturating_duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        state.last_refill = now;
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - state.tokens) / self.refill_per_sec))
+        }
+    }
+
+    /// Wait for a token at most `max_wait`; false = give up (caller degrades instead)
+    pub async fn acquire_within(&self, max_wait: Duration) -> bool {
+        let deadline = Instant::now() + max_wait;
+        loop {
+            match self.try_acquire() {
+                Ok(()) => return true,
+                Err(wait) if Instant::now() + wait <= deadline => tokio::time::sleep(wait).await,
+                Err(_) => return false,
+            }
+        }
+    }
+
+    /// Upstream answered 429: no requests for the next backoff step (returned)
+    pub fn record_throttled(&self) -> Duration {
+        self.record_throttled_at(Instant::now())
+    }
+
+    fn record_throttled_at(&self, now: Instant) -> Duration {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.consecutive_throttles = state.consecutive_throttles.saturating_add(1);
+        let pause = self.backoff.jittered_delay_for(state.consecutive_throttles);
+        state.cooldown_until = Some(now + pause);
+        state.tokens = 0.0;
+        state.last_refill = now + pause;
+        pause
+    }
+
+    /// Upstream answered normally: the next 429 starts from the base delay again
+    pub fn record_success(&self) {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).consecutive_throttles = 0;
+    }
+
+    /// Still cooling down after a 429
+    pub fn is_throttled(&self) -> bool {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.cooldown_until.is_some_and(|until| Instant::now() < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOLDOWN: BackoffConfig = BackoffConfig {
+        base_delay_ms: 1000,
+        max_delay_ms: 4000,
+        max_attempts: 1,
+        jitter_percent: 0,
+        min_delay_ms: 0,
+    };
+
+    #[test]
+    fn test_token_bucket_burst_and_refill() {
+        let bucket = TokenBucket::new(2, 4.0, COOLDOWN);
+        let start = Instant::now();
+        assert!(bucket.try_acquire_at(start).is_ok());
+        assert!(bucket.try_acquire_at(start).is_ok());
+        // Burst spent: next token in 1/4 s
+        assert_eq!(bucket.try_acquire_at(start), Err(Duration::from_millis(250)));
+        assert!(bucket.try_acquire_at(start + Duration::from_millis(250)).is_ok());
+        // Idle time refills up to the capacity only
+        let later = start + Duration::from_secs(60);
+        assert!(bucket.try_acquire_at(later).is_ok());
+        assert!(bucket.try_acquire_at(later).is_ok());
+        assert!(bucket.try_acquire_at(later).is_err());
+    }
+
+    #[test]
+    fn test_token_bucket_cooldown_after_429() {
+        let bucket = TokenBucket::new(10, 10.0, COOLDOWN);
+        let start = Instant::now();
+        assert_eq!(bucket.record_throttled_at(start), Duration::from_secs(1));
+        assert_eq!(bucket.try_acquire_at(start + Duration::from_millis(400)), Err(Duration::from_millis(600)));
+        assert!(bucket.try_acquire_at(start + Duration::from_secs(2)).is_ok());
+
+        // Consecutive 429s back off further; a success resets the schedule
+        assert_eq!(bucket.record_throttled_at(start), Duration::from_secs(2));
+        bucket.record_success();
+        assert_eq!(bucket.record_throttled_at(start), Duration::from_secs(1));
+    }
+}
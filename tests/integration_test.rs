@@ -174,3 +174,23 @@ fn test_wei_to_eth_conversion() {
     // Value is stored correctly
     assert_eq!(result.value, value);
 }
+
+#[test]
+fn test_openapi_document_covers_api() {
+    use utoipa::OpenApi;
+
+    let doc = ruster_revm::api::ApiDoc::openapi();
+    for path in [
+        "/v1/honeypot/check",
+        "/v1/analyze/batch",
+        "/v1/pair/{chain}/{pair_address}",
+        "/v1/analyze/all-chains/{address}",
+        "/v1/feedback",
+    ] {
+        assert!(doc.paths.paths.contains_key(path), "missing path {}", path);
+    }
+    let schemas = doc.components.expect("components").schemas;
+    for schema in ["HoneypotCheckRequest", "HoneypotCheckData", "BatchAnalysisRequest", "HoneypotCheckResponse"] {
+        assert!(schemas.contains_key(schema), "missing schema {}", schema);
+    }
+}
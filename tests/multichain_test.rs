@@ -0,0 +1,140 @@
+//! Multi-chain correctness tests (Avalanche, Base)
+//!
+//! Offline tests cover the canonical-token path of `check_honeypot` and the
+//! per-chain detector defaults. Tests marked `#[ignore]` run the full RPC
+//! simulation against a live chain:
+//! cargo test --test multichain_test -- --ignored --nocapture
+
+use alloy_primitives::Address;
+use axum::extract::{Json, Query, State};
+use ruster_revm::api::handlers::{check_honeypot, AppState};
+use ruster_revm::api::{
+    HoneypotCheckData, HoneypotCheckRequest, HoneypotTraceQuery, MinimalCheckData, ResponseFields, Verdict,
+};
+use ruster_revm::utils::constants::{CHAIN_ID_AVALANCHE, CHAIN_ID_BASE};
+use ruster_revm::{HoneypotDetector, TelemetryCollector};
+use std::str::FromStr;
+use std::sync::Arc;
+
+const AVALANCHE_USDC: &str = "0xB97EF9Ef8734C71904D8002F8b6Bc66Dd9c48a6E";
+const AVALANCHE_WAVAX: &str = "0xB31f66AA3C1e785363F0875A1B74E27b85FD66c7";
+const AVALANCHE_TRADERJOE: &str = "0x60aE616a2155Ee3d9A68541Ba4544862310933d4";
+/// JOE - traded against WAVAX on TraderJoe V1
+const AVALANCHE_JOE: &str = "0x6e84a6216eA6dACC71eE8E6b0a5B7322EEbC0fDd";
+
+const BASE_USDC: &str = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913";
+const BASE_WETH: &str = "0x4200000000000000000000000000000000000006";
+/// BSWAP - BaseSwap governance token, traded against WETH on BaseSwap
+const BASE_BSWAP: &str = "0x78a087d713Be963Bf307b18F2Ff8122EF9A63ae9";
+
+async fn check(token: &str, chain_id: u64) -> HoneypotCheckData {
+    let state = Arc::new(AppState::new(Arc::new(TelemetryCollector::new())));
+    let request = HoneypotCheckRequest {
+        token_address: token.to_string(),
+        test_amount_eth: None,
+        chain_id,
+        chain: None,
+        profile: Default::default(),
+        decimals: None,
+        include_market_data: true,
+    };
+    let Json(response) = check_honeypot(State(state), Query(HoneypotTraceQuery::default()), Json(request))
+        .await
+        .unwrap_or_else(|_| panic!("check_honeypot failed for {} on chain {}", token, chain_id));
+    response.data.expect("response without data")
+}
+
+#[test]
+fn test_avalanche_detector_defaults() {
+    let detector = HoneypotDetector::for_chain(CHAIN_ID_AVALANCHE).expect("Avalanche supported");
+    assert_eq!(detector.chain_id(), CHAIN_ID_AVALANCHE);
+    assert_eq!(detector.native_symbol(), "AVAX");
+    assert_eq!(detector.weth(), Address::from_str(AVALANCHE_WAVAX).unwrap());
+    assert!(detector
+        .routers()
+        .iter()
+        .any(|r| r.address == Address::from_str(AVALANCHE_TRADERJOE).unwrap()));
+}
+
+#[test]
+fn test_base_detector_defaults() {
+    let detector = HoneypotDetector::for_chain(CHAIN_ID_BASE).expect("Base supported");
+    assert_eq!(detector.chain_id(), CHAIN_ID_BASE);
+    assert_eq!(detector.native_symbol(), "ETH");
+    assert_eq!(detector.weth(), Address::from_str(BASE_WETH).unwrap());
+    assert!(!detector.routers().is_empty());
+}
+
+#[test]
+fn test_custom_detector_native_symbol() {
+    let detector = HoneypotDetector::new(CHAIN_ID_AVALANCHE, Address::ZERO, Address::ZERO);
+    assert_eq!(detector.native_symbol(), "AVAX");
+}
+
+#[tokio::test]
+async fn test_avalanche_canonical_check() {
+    let data = check(AVALANCHE_USDC, CHAIN_ID_AVALANCHE).await;
+    assert_eq!(data.chain_id, CHAIN_ID_AVALANCHE);
+    assert_eq!(data.native_symbol, "AVAX");
+    assert_eq!(data.chain_name, "Avalanche C-Chain");
+    assert_eq!(data.is_honeypot, Some(false));
+    assert_eq!(data.risk_score, 0);
+}
+
+#[tokio::test]
+async fn test_base_canonical_check() {
+    let data = check(BASE_USDC, CHAIN_ID_BASE).await;
+    assert_eq!(data.chain_id, CHAIN_ID_BASE);
+    assert_eq!(data.native_symbol, "ETH");
+    assert_eq!(data.chain_name, "Base");
+    assert_eq!(data.is_honeypot, Some(false));
+    assert_eq!(data.risk_score, 0);
+}
+
+#[tokio::test]
+async fn test_minimal_fields_canonical_check() {
+    let state = Arc::new(AppState::new(Arc::new(TelemetryCollector::new())));
+    let request = HoneypotCheckRequest {
+        token_address: BASE_USDC.to_string(),
+        test_amount_eth: None,
+        chain_id: CHAIN_ID_BASE,
+        chain: None,
+        profile: Default::default(),
+        decimals: None,
+        include_market_data: true,
+    };
+    let query = HoneypotTraceQuery {
+        fields: ResponseFields::Minimal,
+        ..Default::default()
+    };
+    let Json(response) = check_honeypot(State(state), Query(query), Json(request))
+        .await
+        .unwrap_or_else(|_| panic!("check_honeypot failed"));
+    let data = response.data.expect("response without data");
+    assert!(data.price_usd.is_none() && data.liquidity_usd.is_none());
+
+    let minimal = MinimalCheckData::from(&data);
+    assert_eq!(minimal.is_honeypot, Some(false));
+    assert_eq!(minimal.verdict, Verdict::Safe);
+    assert_eq!(minimal.risk_score, 0);
+    assert_eq!(minimal.reason_code, "CANONICAL");
+}
+
+#[tokio::test]
+#[ignore = "requires Avalanche RPC + DexScreener"]
+async fn test_avalanche_live_simulation() {
+    let data = check(AVALANCHE_JOE, CHAIN_ID_AVALANCHE).await;
+    assert_eq!(data.chain_id, CHAIN_ID_AVALANCHE);
+    assert_eq!(data.native_symbol, "AVAX");
+    assert!(data.buy_success && data.sell_success, "JOE should be tradeable: {}", data.reason);
+    assert_eq!(data.is_honeypot, Some(false));
+}
+
+#[tokio::test]
+#[ignore = "requires Base RPC + DexScreener"]
+async fn test_base_live_simulation() {
+    let data = check(BASE_BSWAP, CHAIN_ID_BASE).await;
+    assert_eq!(data.chain_id, CHAIN_ID_BASE);
+    assert_eq!(data.native_symbol, "ETH");
+    assert_eq!(data.is_honeypot, Some(false), "{}", data.reason);
+}